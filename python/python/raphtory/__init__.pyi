@@ -147,7 +147,11 @@ class ConstProperties:
 class DiskGraphStorage:
     def __init__(self):
         """Initialize self.  See help(type(self)) for accurate signature."""
+    def compact(self, graph_dir):
+        """Merge this graph with all the updates staged for it into a new graph in `graph_dir`"""
     def graph_dir(self): ...
+    def has_staged_appends(self):
+        """Whether updates were staged for this graph since it was last compacted"""
     @staticmethod
     def load_from_dir(graph_dir): ...
     @staticmethod
@@ -169,6 +173,11 @@ class DiskGraphStorage:
         Merge this graph with another `DiskGraph`. Note that both graphs should have nodes that are
         sorted by their global ids or the resulting graph will be nonsense!
         """
+    def stage_graph(self, batch):
+        """
+        Stage the updates of `batch` for this graph. The updates are only included in the graph
+        once it is compacted.
+        """
     def to_events(self): ...
     def to_persistent(self): ...
 
@@ -242,6 +251,15 @@ class Edge:
     def dst(self):
         """Returns the destination node of the edge."""
     @property
+    def duration(self):
+        """
+        Gets the total time the edge is alive in the current view, following the semantics of
+        persistent graphs (from an addition until the next deletion).
+
+        Returns:
+            (int) The duration of the edge or None if it stays alive without bound
+        """
+    @property
     def earliest_date_time(self):
         """
         Gets of earliest datetime of an edge.
@@ -313,7 +331,7 @@ class Edge:
         Returns:
              Edge: The layered view
         """
-    def expanding(self, step):
+    def expanding(self, step, timezone=None, alignment=None):
         """
         Creates a `WindowSet` with the given `step` size using an expanding window.
 
@@ -321,6 +339,8 @@ class Edge:
 
         Arguments:
             step (int | str): The step size of the window.
+            timezone (str | None): Step in the wall clock time of this time zone (e.g., "Europe/London") instead of UTC.
+            alignment (str | int | DateTime | None): "calendar" to align windows to the calendar (e.g., days to midnight and weeks to Mondays), or a time to anchor the windows to. Defaults to starting at the start of the timeline.
 
         Returns:
             A `WindowSet` object.
@@ -417,7 +437,7 @@ class Edge:
         Returns:
           Properties on the Edge.
         """
-    def rolling(self, window, step=None):
+    def rolling(self, window, step=None, timezone=None, alignment=None):
         """
         Creates a `WindowSet` with the given `window` size and optional `step` using a rolling window.
 
@@ -426,6 +446,8 @@ class Edge:
         Arguments:
             window (int | str): The size of the window.
             step (int | str | None): The step size of the window. Defaults to `window`.
+            timezone (str | None): Step and size the windows in the wall clock time of this time zone (e.g., "Europe/London") instead of UTC.
+            alignment (str | int | DateTime | None): "calendar" to align windows to the calendar (e.g., days to midnight and weeks to Mondays), or a time to anchor the windows to. Defaults to starting at the start of the timeline.
 
         Returns:
             A `WindowSet` object.
@@ -656,7 +678,7 @@ class Edges:
         Returns:
              Edges: The layered view
         """
-    def expanding(self, step):
+    def expanding(self, step, timezone=None, alignment=None):
         """
         Creates a `WindowSet` with the given `step` size using an expanding window.
 
@@ -664,6 +686,8 @@ class Edges:
 
         Arguments:
             step (int | str): The step size of the window.
+            timezone (str | None): Step in the wall clock time of this time zone (e.g., "Europe/London") instead of UTC.
+            alignment (str | int | DateTime | None): "calendar" to align windows to the calendar (e.g., days to midnight and weeks to Mondays), or a time to anchor the windows to. Defaults to starting at the start of the timeline.
 
         Returns:
             A `WindowSet` object.
@@ -755,7 +779,7 @@ class Edges:
     @property
     def properties(self):
         """Returns all properties of the edges"""
-    def rolling(self, window, step=None):
+    def rolling(self, window, step=None, timezone=None, alignment=None):
         """
         Creates a `WindowSet` with the given `window` size and optional `step` using a rolling window.
 
@@ -764,6 +788,8 @@ class Edges:
         Arguments:
             window (int | str): The size of the window.
             step (int | str | None): The step size of the window. Defaults to `window`.
+            timezone (str | None): Step and size the windows in the wall clock time of this time zone (e.g., "Europe/London") instead of UTC.
+            alignment (str | int | DateTime | None): "calendar" to align windows to the calendar (e.g., days to midnight and weeks to Mondays), or a time to anchor the windows to. Defaults to starting at the start of the timeline.
 
         Returns:
             A `WindowSet` object.
@@ -897,6 +923,19 @@ class Graph:
         Returns:
           None
         """
+    def add_hyperedge(self, timestamp, id, participants, properties=None):
+        """
+        Adds a new hyperedge connecting an arbitrary number of nodes to the graph.
+
+        Arguments:
+           timestamp (int, str, or datetime(utc)): The timestamp of the hyperedge.
+           id (str or int): The unique id of the hyperedge.
+           participants (list): The participating nodes, either as node ids or as (node id, role) tuples.
+           properties (dict): The properties of the hyperedge, as a dict of string and properties (optional).
+
+        Returns:
+          Hyperedge - The added hyperedge
+        """
     def add_node(self, timestamp, id, properties=None, node_type=None):
         """
         Adds a new node with the given id and properties to the graph.
@@ -950,6 +989,16 @@ class Graph:
         Returns:
              A GraphView object.
         """
+    def bipartite_projection(self):
+        """
+        Project the hyperedges of the graph to a new bipartite graph
+
+        Each hyperedge becomes a node of type "hyperedge" with the properties of the hyperedge,
+        its participants are connected to it by an edge in the layer of their role.
+
+        Returns:
+           Graph - the bipartite projection
+        """
     def cache(self, path):
         """
          Write Graph to cache file and initialise the cache.
@@ -960,6 +1009,26 @@ class Graph:
         Arguments:
             path (str): The path to the cache file
         """
+    def canonical_id(self, id):
+        """
+        Resolve `id` to the id of the node it refers to, following merges
+
+        Arguments:
+            id (str or int): The node id or alias
+
+        Returns:
+            Optional[str | int]: The id of the node, or None if the node does not exist
+        """
+    def checkpoint(self):
+        """ Rewrite the cache file with the current state of the Graph and clear its write-ahead log."""
+    def clique_projection(self):
+        """
+        Project the hyperedges of the graph to a new graph where the participants of each
+        hyperedge are connected by a clique of edges with the properties of the hyperedge
+
+        Returns:
+           Graph - the clique projection
+        """
     def count_edges(self):
         """
         Number of edges in the graph
@@ -967,6 +1036,13 @@ class Graph:
         Returns:
            the number of edges in the graph
         """
+    def count_hyperedges(self):
+        """
+        Number of hyperedges in the graph
+
+        Returns:
+          the number of hyperedges in the graph
+        """
     def count_nodes(self):
         """
         Number of nodes in the graph
@@ -998,6 +1074,20 @@ class Graph:
         Returns:
            Graph
         """
+    def diff(self, other):
+        """
+        Returns the changes that turn this view into `other`
+
+        Nodes are matched by id and edges by source, destination and layer. Temporal properties
+        are compared by their latest value, such that `g.at(t1).diff(g.at(t2))` returns the
+        changes between `t1` and `t2`.
+
+        Arguments:
+          other (GraphView): The graph view to compare with
+
+        Returns:
+           GraphDiff - The added, removed and changed nodes, edges, layers and properties
+        """
     @property
     def earliest_date_time(self):
         """
@@ -1099,7 +1189,7 @@ class Graph:
         Returns:
              GraphView: The layered view
         """
-    def expanding(self, step):
+    def expanding(self, step, timezone=None, alignment=None):
         """
         Creates a `WindowSet` with the given `step` size using an expanding window.
 
@@ -1107,6 +1197,8 @@ class Graph:
 
         Arguments:
             step (int | str): The step size of the window.
+            timezone (str | None): Step in the wall clock time of this time zone (e.g., "Europe/London") instead of UTC.
+            alignment (str | int | DateTime | None): "calendar" to align windows to the calendar (e.g., days to midnight and weeks to Mondays), or a time to anchor the windows to. Defaults to starting at the start of the timeline.
 
         Returns:
             A `WindowSet` object.
@@ -1157,6 +1249,23 @@ class Graph:
         Returns:
           true if the graph contains the specified node, false otherwise
         """
+    def hyperedge(self, id):
+        """
+        Gets the hyperedge with the specified id
+
+        Arguments:
+          id (str or int): the hyperedge id
+
+        Returns:
+          the hyperedge with the specified id, or None if the hyperedge is not part of the graph
+        """
+    def hyperedges(self):
+        """
+        Return the hyperedges in the window of the graph that have at least one participant in the graph
+
+        Returns:
+           list[Hyperedge] - the hyperedges of the graph
+        """
     def import_edge(self, edge, force=False):
         """
         Import a single edge into the graph.
@@ -1221,6 +1330,20 @@ class Graph:
         Returns:
            GraphIndex - Returns a GraphIndex
         """
+    def intersection(self, other):
+        """
+        Returns a view of the nodes and edges that are in both this view and `other`
+
+        Both views need to be views of the same graph with the same time semantics and time
+        bounds, window the result instead of the views to restrict it in time. Properties are
+        taken from this view.
+
+        Arguments:
+          other (GraphView): The graph view to combine with
+
+        Returns:
+           GraphView - Returns the intersection of both views
+        """
     def largest_connected_component(self):
         """
         Gives the large connected component of a graph.
@@ -1527,6 +1650,33 @@ class Graph:
         Returns:
            GraphView - Returns a graph clone
         """
+    def merge(self, other, policy=None):
+        """
+        Merge this graph with `other` into a new graph
+
+        Nodes are matched by id and edges by the ids of their endpoints, combining the histories
+        and layers of both graphs. Conflicting node types and constant property values are
+        resolved using `policy`, where this graph is the left and `other` the right side.
+
+        Arguments:
+            other (GraphView): The graph to merge with
+            policy (str | Callable): "keep_left", "keep_right", "error" (the default) or a function
+                `f(key, left, right)` returning the merged value, where `key` is None for node types
+
+        Returns:
+           Graph - The merged graph
+        """
+    def merge_nodes(self, keep, merge):
+        """
+        Merge node `merge` into node `keep`
+
+        The history, properties and edges of `merge` are added to `keep` and the id of `merge`
+        resolves to `keep` afterwards. Constant properties that `keep` already has take precedence.
+
+        Arguments:
+            keep (str or int): The id of the node to keep
+            merge (str or int): The id of the node to merge into `keep`
+        """
     def node(self, id):
         """
         Gets the node with the specified id
@@ -1537,6 +1687,16 @@ class Graph:
         Returns:
           the node with the specified id, or None if the node does not exist
         """
+    def node_aliases(self, node):
+        """
+        The ids of the nodes that were merged into `node`
+
+        Arguments:
+            node (str or int): The id of the node
+
+        Returns:
+            list[str | int]: The aliases of the node
+        """
     @property
     def nodes(self):
         """
@@ -1558,7 +1718,54 @@ class Graph:
         Returns:
            HashMap<String, Prop> - Properties paired with their names
         """
-    def rolling(self, window, step=None):
+    def remove_edge(self, src, dst, layer=None):
+        """
+        Remove an edge from `layer`, or from all its layers if `layer` is not set
+
+        Arguments:
+            src (str or int): The id of the source node
+            dst (str or int): The id of the destination node
+            layer (str): The layer to remove the edge from (optional)
+        """
+    def remove_edge_property_updates(self, src, dst, start, end, name=None, layer=None):
+        """
+        Remove the updates of a temporal edge property in the interval `[start, end)`
+
+        Arguments:
+            src (str or int): The id of the source node
+            dst (str or int): The id of the destination node
+            start (TimeInput): The start of the interval
+            end (TimeInput): The end of the interval
+            name (str): The name of the property, all temporal properties are affected if not set
+            layer (str): Only remove updates in this layer (optional)
+        """
+    def remove_node(self, node):
+        """
+        Remove a node together with all its edges
+
+        Arguments:
+            node (str or int): The id of the node
+        """
+    def remove_node_property_updates(self, node, start, end, name=None):
+        """
+        Remove the updates of a temporal node property in the interval `[start, end)`
+
+        Arguments:
+            node (str or int): The id of the node
+            start (TimeInput): The start of the interval
+            end (TimeInput): The end of the interval
+            name (str): The name of the property, all temporal properties are affected if not set
+        """
+    def remove_property_updates(self, start, end, name=None):
+        """
+        Remove the updates of a temporal graph property in the interval `[start, end)`
+
+        Arguments:
+            start (TimeInput): The start of the interval
+            end (TimeInput): The end of the interval
+            name (str): The name of the property, all temporal properties are affected if not set
+        """
+    def rolling(self, window, step=None, timezone=None, alignment=None):
         """
         Creates a `WindowSet` with the given `window` size and optional `step` using a rolling window.
 
@@ -1567,6 +1774,8 @@ class Graph:
         Arguments:
             window (int | str): The size of the window.
             step (int | str | None): The step size of the window. Defaults to `window`.
+            timezone (str | None): Step and size the windows in the wall clock time of this time zone (e.g., "Europe/London") instead of UTC.
+            alignment (str | int | DateTime | None): "calendar" to align windows to the calendar (e.g., days to midnight and weeks to Mondays), or a time to anchor the windows to. Defaults to starting at the start of the timeline.
 
         Returns:
             A `WindowSet` object.
@@ -1708,9 +1917,39 @@ class Graph:
             Returns:
                 A pyvis network
         """
+    def truncate_before(self, time):
+        """
+        Drop all history before `time`
+
+        Nodes and edges without updates at or after `time` are removed.
+
+        Arguments:
+            time (TimeInput): The new start of the graph
+        """
+    def union(self, other):
+        """
+        Returns a view of the nodes and edges that are in this view or in `other`
+
+        Both views need to be views of the same graph with the same time semantics and time
+        bounds, window the result instead of the views to restrict it in time. Properties are
+        taken from this view.
+
+        Arguments:
+          other (GraphView): The graph view to combine with
+
+        Returns:
+           GraphView - Returns the union of both views
+        """
     @property
     def unique_layers(self):
         """Return all the layer ids in the graph"""
+    def unmerge_node(self, node):
+        """
+        Undo the merge of a node, restoring it with its edges and its id
+
+        Arguments:
+            node (str or int): The id of the merged node
+        """
     def update_constant_properties(self, properties):
         """
         Updates static properties to the graph.
@@ -1770,8 +2009,91 @@ class Graph:
     @property
     def window_size(self):
         """Get the window size (difference between start and end) for this GraphView"""
+    def with_edge_property(self, name, prop, cached=False):
+        """
+        Returns a view with an additional computed edge property
+
+        The property is evaluated lazily for the current window of the view whenever it is
+        accessed. Expressions that refer to temporal properties are temporal themselves, all
+        other computed properties are constant.
+
+        Arguments:
+          name (str): The name of the property, it hides any stored edge property with the same name
+          prop (str | Callable[[Edge], Any]): An arithmetic expression over the properties of the
+              edge like `"amount * fx_rate"` or a function that computes the value for an edge
+          cached (bool): Cache the values of the function for each edge and window (defaults to False)
+
+        Returns:
+           GraphView - Returns the view with the computed property
+        """
+    def with_node_property(self, name, prop, cached=False):
+        """
+        Returns a view with an additional computed node property
+
+        The property is evaluated lazily for the current window of the view whenever it is
+        accessed. Expressions that refer to temporal properties are temporal themselves, all
+        other computed properties are constant.
+
+        Arguments:
+          name (str): The name of the property, it hides any stored node property with the same name
+          prop (str | Callable[[Node], Any]): An arithmetic expression over the properties of the
+              node like `"amount * fx_rate"` or a function that computes the value for a node
+          cached (bool): Cache the values of the function for each node and window (defaults to False)
+
+        Returns:
+           GraphView - Returns the view with the computed property
+        """
     def write_updates(self):
-        """Persist the new updates by appending them to the cache file."""
+        """Persist the new updates by appending them to the write-ahead log of the cache file."""
+
+class GraphDiff:
+    """The changes between two graph views, as returned by `GraphView.diff`"""
+
+    def __init__(self):
+        """Initialize self.  See help(type(self)) for accurate signature."""
+    @property
+    def added_edges(self):
+        """The (src, dst, layer) of the edges that were added"""
+    @property
+    def added_layers(self):
+        """The layers that have edges after but not before the change"""
+    @property
+    def added_nodes(self):
+        """The ids of the nodes that were added"""
+    def apply(self, graph, time):
+        """
+        Apply the changes to `graph` at time `time`
+
+        Removed nodes and edges are removed from `graph`. Nothing is changed if the diff does not
+        apply to `graph`, i.e., if a property value has a different type than in `graph`, a node
+        would change its type or a removed node or edge does not exist.
+
+        Arguments:
+            graph (Graph | PersistentGraph): The graph to update
+            time (int | str | datetime): The time of the updates
+        """
+    @property
+    def changed_edges(self):
+        """The (src, dst, layer) of the edges with changed property values"""
+    @property
+    def changed_nodes(self):
+        """The ids of the nodes with a changed node type or changed property values"""
+    @staticmethod
+    def from_json(json):
+        """Load a diff from a JSON string created with `to_json`"""
+    def is_empty(self):
+        """Check if there are no changes"""
+    @property
+    def removed_edges(self):
+        """The (src, dst, layer) of the edges that were removed"""
+    @property
+    def removed_layers(self):
+        """The layers that have edges before but not after the change"""
+    @property
+    def removed_nodes(self):
+        """The ids of the nodes that were removed"""
+    def to_json(self):
+        """Export all changes including the property values as a JSON string"""
 
 class GraphIndex:
     """
@@ -1840,6 +2162,33 @@ class GraphIndex:
            A list of nodes which match the query. The list will be empty if no nodes match.
         """
 
+class Hyperedge:
+    """
+    A hyperedge connects an arbitrary number of nodes at a single point in time.
+    Each participant can optionally take part in the hyperedge with a named role.
+    """
+
+    def __init__(self):
+        """Initialize self.  See help(type(self)) for accurate signature."""
+    @property
+    def date_time(self):
+        """The time of the hyperedge as a datetime"""
+    @property
+    def id(self):
+        """The id of the hyperedge"""
+    @property
+    def nodes(self):
+        """The distinct participating nodes"""
+    @property
+    def participants(self):
+        """The participating nodes together with their role (or None if they do not have a role)"""
+    @property
+    def properties(self):
+        """The properties of the hyperedge"""
+    @property
+    def time(self):
+        """The time of the hyperedge"""
+
 class MutableEdge:
     def __init__(self):
         """Initialize self.  See help(type(self)) for accurate signature."""
@@ -2005,7 +2354,7 @@ class MutableEdge:
         Returns:
              Edge: The layered view
         """
-    def expanding(self, step):
+    def expanding(self, step, timezone=None, alignment=None):
         """
         Creates a `WindowSet` with the given `step` size using an expanding window.
 
@@ -2013,6 +2362,8 @@ class MutableEdge:
 
         Arguments:
             step (int | str): The step size of the window.
+            timezone (str | None): Step in the wall clock time of this time zone (e.g., "Europe/London") instead of UTC.
+            alignment (str | int | DateTime | None): "calendar" to align windows to the calendar (e.g., days to midnight and weeks to Mondays), or a time to anchor the windows to. Defaults to starting at the start of the timeline.
 
         Returns:
             A `WindowSet` object.
@@ -2109,7 +2460,7 @@ class MutableEdge:
         Returns:
           Properties on the Edge.
         """
-    def rolling(self, window, step=None):
+    def rolling(self, window, step=None, timezone=None, alignment=None):
         """
         Creates a `WindowSet` with the given `window` size and optional `step` using a rolling window.
 
@@ -2118,6 +2469,8 @@ class MutableEdge:
         Arguments:
             window (int | str): The size of the window.
             step (int | str | None): The step size of the window. Defaults to `window`.
+            timezone (str | None): Step and size the windows in the wall clock time of this time zone (e.g., "Europe/London") instead of UTC.
+            alignment (str | int | DateTime | None): "calendar" to align windows to the calendar (e.g., days to midnight and weeks to Mondays), or a time to anchor the windows to. Defaults to starting at the start of the timeline.
 
         Returns:
             A `WindowSet` object.
@@ -2371,7 +2724,7 @@ class MutableNode:
         Returns:
              Node: The layered view
         """
-    def expanding(self, step):
+    def expanding(self, step, timezone=None, alignment=None):
         """
         Creates a `WindowSet` with the given `step` size using an expanding window.
 
@@ -2379,6 +2732,8 @@ class MutableNode:
 
         Arguments:
             step (int | str): The step size of the window.
+            timezone (str | None): Step in the wall clock time of this time zone (e.g., "Europe/London") instead of UTC.
+            alignment (str | int | DateTime | None): "calendar" to align windows to the calendar (e.g., days to midnight and weeks to Mondays), or a time to anchor the windows to. Defaults to starting at the start of the timeline.
 
         Returns:
             A `WindowSet` object.
@@ -2400,6 +2755,29 @@ class MutableNode:
             A list of timestamps of the event history of node.
 
         """
+    def hyperedge_degree(self):
+        """
+        Get the number of hyperedges this node takes part in.
+
+        Returns:
+            The hyperedge degree of this node.
+        """
+    @property
+    def hyperedge_neighbours(self):
+        """
+        Get the nodes that share at least one hyperedge with this node.
+
+        Returns:
+
+        An iterator over the distinct co-participants of this node.
+        """
+    def hyperedges(self):
+        """
+        Get the hyperedges this node takes part in.
+
+        Returns:
+            A list of the hyperedges in the window of the graph that include this node.
+        """
     @property
     def id(self):
         """
@@ -2525,7 +2903,7 @@ class MutableNode:
         Returns:
             A list of properties.
         """
-    def rolling(self, window, step=None):
+    def rolling(self, window, step=None, timezone=None, alignment=None):
         """
         Creates a `WindowSet` with the given `window` size and optional `step` using a rolling window.
 
@@ -2534,6 +2912,8 @@ class MutableNode:
         Arguments:
             window (int | str): The size of the window.
             step (int | str | None): The step size of the window. Defaults to `window`.
+            timezone (str | None): Step and size the windows in the wall clock time of this time zone (e.g., "Europe/London") instead of UTC.
+            alignment (str | int | DateTime | None): "calendar" to align windows to the calendar (e.g., days to midnight and weeks to Mondays), or a time to anchor the windows to. Defaults to starting at the start of the timeline.
 
         Returns:
             A `WindowSet` object.
@@ -2760,7 +3140,7 @@ class Node:
         Returns:
              Node: The layered view
         """
-    def expanding(self, step):
+    def expanding(self, step, timezone=None, alignment=None):
         """
         Creates a `WindowSet` with the given `step` size using an expanding window.
 
@@ -2768,6 +3148,8 @@ class Node:
 
         Arguments:
             step (int | str): The step size of the window.
+            timezone (str | None): Step in the wall clock time of this time zone (e.g., "Europe/London") instead of UTC.
+            alignment (str | int | DateTime | None): "calendar" to align windows to the calendar (e.g., days to midnight and weeks to Mondays), or a time to anchor the windows to. Defaults to starting at the start of the timeline.
 
         Returns:
             A `WindowSet` object.
@@ -2789,6 +3171,29 @@ class Node:
             A list of timestamps of the event history of node.
 
         """
+    def hyperedge_degree(self):
+        """
+        Get the number of hyperedges this node takes part in.
+
+        Returns:
+            The hyperedge degree of this node.
+        """
+    @property
+    def hyperedge_neighbours(self):
+        """
+        Get the nodes that share at least one hyperedge with this node.
+
+        Returns:
+
+        An iterator over the distinct co-participants of this node.
+        """
+    def hyperedges(self):
+        """
+        Get the hyperedges this node takes part in.
+
+        Returns:
+            A list of the hyperedges in the window of the graph that include this node.
+        """
     @property
     def id(self):
         """
@@ -2914,7 +3319,7 @@ class Node:
         Returns:
             A list of properties.
         """
-    def rolling(self, window, step=None):
+    def rolling(self, window, step=None, timezone=None, alignment=None):
         """
         Creates a `WindowSet` with the given `window` size and optional `step` using a rolling window.
 
@@ -2923,6 +3328,8 @@ class Node:
         Arguments:
             window (int | str): The size of the window.
             step (int | str | None): The step size of the window. Defaults to `window`.
+            timezone (str | None): Step and size the windows in the wall clock time of this time zone (e.g., "Europe/London") instead of UTC.
+            alignment (str | int | DateTime | None): "calendar" to align windows to the calendar (e.g., days to midnight and weeks to Mondays), or a time to anchor the windows to. Defaults to starting at the start of the timeline.
 
         Returns:
             A `WindowSet` object.
@@ -3126,7 +3533,7 @@ class Nodes:
         Returns:
              Nodes: The layered view
         """
-    def expanding(self, step):
+    def expanding(self, step, timezone=None, alignment=None):
         """
         Creates a `WindowSet` with the given `step` size using an expanding window.
 
@@ -3134,6 +3541,8 @@ class Nodes:
 
         Arguments:
             step (int | str): The step size of the window.
+            timezone (str | None): Step in the wall clock time of this time zone (e.g., "Europe/London") instead of UTC.
+            alignment (str | int | DateTime | None): "calendar" to align windows to the calendar (e.g., days to midnight and weeks to Mondays), or a time to anchor the windows to. Defaults to starting at the start of the timeline.
 
         Returns:
             A `WindowSet` object.
@@ -3156,6 +3565,22 @@ class Nodes:
            An  list of timestamps.
 
         """
+    def hyperedge_degree(self):
+        """
+        Returns the number of hyperedges the nodes take part in
+
+        Returns:
+            An iterator of the hyperedge degree of the nodes
+        """
+    @property
+    def hyperedge_neighbours(self):
+        """
+        Get the nodes that share at least one hyperedge with this node.
+
+        Returns:
+
+        An iterator over the distinct co-participants of this node.
+        """
     @property
     def id(self):
         """Returns an iterator over the nodes ids"""
@@ -3262,7 +3687,7 @@ class Nodes:
         Returns:
             A List of properties
         """
-    def rolling(self, window, step=None):
+    def rolling(self, window, step=None, timezone=None, alignment=None):
         """
         Creates a `WindowSet` with the given `window` size and optional `step` using a rolling window.
 
@@ -3271,6 +3696,8 @@ class Nodes:
         Arguments:
             window (int | str): The size of the window.
             step (int | str | None): The step size of the window. Defaults to `window`.
+            timezone (str | None): Step and size the windows in the wall clock time of this time zone (e.g., "Europe/London") instead of UTC.
+            alignment (str | int | DateTime | None): "calendar" to align windows to the calendar (e.g., days to midnight and weeks to Mondays), or a time to anchor the windows to. Defaults to starting at the start of the timeline.
 
         Returns:
             A `WindowSet` object.
@@ -3365,6 +3792,19 @@ class PersistentGraph:
 
     def __init__(self):
         """Initialize self.  See help(type(self)) for accurate signature."""
+    def active_during(self, start, end, semantics="overlap"):
+        """
+        Returns a view of the graph that only includes the edges that are active during `[start, end)`
+
+        Arguments:
+          start (int): The start of the interval.
+          end (int): The end of the interval (exclusive).
+          semantics (str): "overlap" to include edges that are alive at any point of the interval,
+                           "containment" to only include edges that are alive for all of it. Defaults to "overlap".
+
+        Returns:
+          GraphView: The filtered view
+        """
     def add_constant_properties(self, properties):
         """
         Adds static properties to the graph.
@@ -3389,6 +3829,19 @@ class PersistentGraph:
         Returns:
           None
         """
+    def add_hyperedge(self, timestamp, id, participants, properties=None):
+        """
+        Adds a new hyperedge connecting an arbitrary number of nodes to the graph.
+
+        Arguments:
+           timestamp (int, str, or datetime(utc)): The timestamp of the hyperedge.
+           id (str or int): The unique id of the hyperedge.
+           participants (list): The participating nodes, either as node ids or as (node id, role) tuples.
+           properties (dict): The properties of the hyperedge, as a dict of string and properties (optional).
+
+        Returns:
+          Hyperedge - The added hyperedge
+        """
     def add_node(self, timestamp, id, properties=None, node_type=None):
         """
         Adds a new node with the given id and properties to the graph.
@@ -3402,6 +3855,22 @@ class PersistentGraph:
         Returns:
           None
         """
+    def add_interval_edge(self, start, end, src, dst, properties=None, layer=None):
+        """
+        Adds an edge that is alive for the interval `[start, end)`, i.e., the edge is added at
+        `start` and deleted again at `end`.
+
+        Arguments:
+           start (int): The time the edge is added.
+           end (int): The time the edge is deleted, needs to be at least `start`.
+           src (str or int): The id of the source node.
+           dst (str or int): The id of the destination node.
+           properties (dict): The properties of the edge, as a dict of string and properties
+           layer (str): The layer of the edge.
+
+        Returns:
+          The added edge
+        """
     def add_property(self, timestamp, properties):
         """
         Adds properties to the graph.
@@ -3443,6 +3912,16 @@ class PersistentGraph:
         Returns:
              A GraphView object.
         """
+    def bipartite_projection(self):
+        """
+        Project the hyperedges of the graph to a new bipartite graph
+
+        Each hyperedge becomes a node of type "hyperedge" with the properties of the hyperedge,
+        its participants are connected to it by an edge in the layer of their role.
+
+        Returns:
+           Graph - the bipartite projection
+        """
     def cache(self, path):
         """
          Write PersistentGraph to cache file and initialise the cache.
@@ -3453,6 +3932,26 @@ class PersistentGraph:
         Arguments:
             path (str): The path to the cache file
         """
+    def canonical_id(self, id):
+        """
+        Resolve `id` to the id of the node it refers to, following merges
+
+        Arguments:
+            id (str or int): The node id or alias
+
+        Returns:
+            Optional[str | int]: The id of the node, or None if the node does not exist
+        """
+    def checkpoint(self):
+        """ Rewrite the cache file with the current state of the PersistentGraph and clear its write-ahead log."""
+    def clique_projection(self):
+        """
+        Project the hyperedges of the graph to a new graph where the participants of each
+        hyperedge are connected by a clique of edges with the properties of the hyperedge
+
+        Returns:
+           Graph - the clique projection
+        """
     def count_edges(self):
         """
         Number of edges in the graph
@@ -3460,6 +3959,13 @@ class PersistentGraph:
         Returns:
            the number of edges in the graph
         """
+    def count_hyperedges(self):
+        """
+        Number of hyperedges in the graph
+
+        Returns:
+          the number of hyperedges in the graph
+        """
     def count_nodes(self):
         """
         Number of nodes in the graph
@@ -3504,6 +4010,20 @@ class PersistentGraph:
         Returns:
            PersistentGraph
         """
+    def diff(self, other):
+        """
+        Returns the changes that turn this view into `other`
+
+        Nodes are matched by id and edges by source, destination and layer. Temporal properties
+        are compared by their latest value, such that `g.at(t1).diff(g.at(t2))` returns the
+        changes between `t1` and `t2`.
+
+        Arguments:
+          other (GraphView): The graph view to compare with
+
+        Returns:
+           GraphDiff - The added, removed and changed nodes, edges, layers and properties
+        """
     @property
     def earliest_date_time(self):
         """
@@ -3607,7 +4127,7 @@ class PersistentGraph:
         Returns:
              GraphView: The layered view
         """
-    def expanding(self, step):
+    def expanding(self, step, timezone=None, alignment=None):
         """
         Creates a `WindowSet` with the given `step` size using an expanding window.
 
@@ -3615,6 +4135,8 @@ class PersistentGraph:
 
         Arguments:
             step (int | str): The step size of the window.
+            timezone (str | None): Step in the wall clock time of this time zone (e.g., "Europe/London") instead of UTC.
+            alignment (str | int | DateTime | None): "calendar" to align windows to the calendar (e.g., days to midnight and weeks to Mondays), or a time to anchor the windows to. Defaults to starting at the start of the timeline.
 
         Returns:
             A `WindowSet` object.
@@ -3665,6 +4187,23 @@ class PersistentGraph:
         Returns:
           true if the graph contains the specified node, false otherwise
         """
+    def hyperedge(self, id):
+        """
+        Gets the hyperedge with the specified id
+
+        Arguments:
+          id (str or int): the hyperedge id
+
+        Returns:
+          the hyperedge with the specified id, or None if the hyperedge is not part of the graph
+        """
+    def hyperedges(self):
+        """
+        Return the hyperedges in the window of the graph that have at least one participant in the graph
+
+        Returns:
+           list[Hyperedge] - the hyperedges of the graph
+        """
     def import_edge(self, edge, force=False):
         """
         Import a single edge into the graph.
@@ -3729,6 +4268,20 @@ class PersistentGraph:
         Returns:
            GraphIndex - Returns a GraphIndex
         """
+    def intersection(self, other):
+        """
+        Returns a view of the nodes and edges that are in both this view and `other`
+
+        Both views need to be views of the same graph with the same time semantics and time
+        bounds, window the result instead of the views to restrict it in time. Properties are
+        taken from this view.
+
+        Arguments:
+          other (GraphView): The graph view to combine with
+
+        Returns:
+           GraphView - Returns the intersection of both views
+        """
     @property
     def latest_date_time(self):
         """
@@ -3813,6 +4366,78 @@ class PersistentGraph:
         Returns:
             None: If the operation is successful.
 
+        Raises:
+            GraphError: If the operation fails.
+        """
+    def load_edge_intervals_from_pandas(
+        self,
+        df,
+        start_col,
+        end_col,
+        src,
+        dst,
+        properties=None,
+        constant_properties=None,
+        shared_constant_properties=None,
+        layer=None,
+        layer_col=None,
+    ):
+        """
+        Load edges that are alive for the intervals `[start, end)` from a Pandas DataFrame into the graph.
+
+        Every row adds the edge at its start time and deletes it again at its end time.
+        Rows without an end time only add the edge.
+
+        Arguments:
+            df (Dataframe): The Pandas DataFrame containing the edges.
+            start_col (str): The column name for the times the edges are added.
+            end_col (str): The column name for the times the edges are deleted.
+            src (str): The column name for the source node ids.
+            dst (str): The column name for the destination node ids.
+            properties (List[str]): List of edge property column names. Defaults to None. (optional)
+            constant_properties (List[str]): List of constant edge property column names. Defaults to None. (optional)
+            shared_constant_properties (dict): A dictionary of constant properties that will be added to every edge. Defaults to None. (optional)
+            layer (str): A constant value to use as the layer for all edges (optional) Defaults to None. (cannot be used in combination with layer_col)
+            layer_col (str): The edge layer col name in dataframe (optional) Defaults to None. (cannot be used in combination with layer)
+        Returns:
+            None: If the operation is successful.
+
+        Raises:
+            GraphError: If the operation fails.
+        """
+    def load_edge_intervals_from_parquet(
+        self,
+        parquet_path,
+        start_col,
+        end_col,
+        src,
+        dst,
+        properties=None,
+        constant_properties=None,
+        shared_constant_properties=None,
+        layer=None,
+        layer_col=None,
+    ):
+        """
+        Load edges that are alive for the intervals `[start, end)` from a Parquet file into the graph.
+
+        Every row adds the edge at its start time and deletes it again at its end time.
+        Rows without an end time only add the edge.
+
+        Arguments:
+            parquet_path (str): Parquet file or directory of Parquet files path containing edges
+            start_col (str): The column name for the times the edges are added.
+            end_col (str): The column name for the times the edges are deleted.
+            src (str): The column name for the source node ids.
+            dst (str): The column name for the destination node ids.
+            properties (List[str]): List of edge property column names. Defaults to None. (optional)
+            constant_properties (List[str]): List of constant edge property column names. Defaults to None. (optional)
+            shared_constant_properties (dict): A dictionary of constant properties that will be added to every edge. Defaults to None. (optional)
+            layer (str): A constant value to use as the layer for all edges (optional) Defaults to None. (cannot be used in combination with layer_col)
+            layer_col (str): The edge layer col name in dataframe (optional) Defaults to None. (cannot be used in combination with layer)
+        Returns:
+            None: If the operation is successful.
+
         Raises:
             GraphError: If the operation fails.
         """
@@ -4062,6 +4687,33 @@ class PersistentGraph:
         Returns:
            GraphView - Returns a graph clone
         """
+    def merge(self, other, policy=None):
+        """
+        Merge this graph with `other` into a new graph
+
+        Nodes are matched by id and edges by the ids of their endpoints, combining the histories
+        and layers of both graphs. Edge deletions of both graphs are preserved. Conflicting node types and constant property values are
+        resolved using `policy`, where this graph is the left and `other` the right side.
+
+        Arguments:
+            other (GraphView): The graph to merge with
+            policy (str | Callable): "keep_left", "keep_right", "error" (the default) or a function
+                `f(key, left, right)` returning the merged value, where `key` is None for node types
+
+        Returns:
+           PersistentGraph - The merged graph
+        """
+    def merge_nodes(self, keep, merge):
+        """
+        Merge node `merge` into node `keep`
+
+        The history, properties and edges of `merge` are added to `keep` and the id of `merge`
+        resolves to `keep` afterwards. Constant properties that `keep` already has take precedence.
+
+        Arguments:
+            keep (str or int): The id of the node to keep
+            merge (str or int): The id of the node to merge into `keep`
+        """
     def node(self, id):
         """
         Gets the node with the specified id
@@ -4072,6 +4724,16 @@ class PersistentGraph:
         Returns:
           the node with the specified id, or None if the node does not exist
         """
+    def node_aliases(self, node):
+        """
+        The ids of the nodes that were merged into `node`
+
+        Arguments:
+            node (str or int): The id of the node
+
+        Returns:
+            list[str | int]: The aliases of the node
+        """
     @property
     def nodes(self):
         """
@@ -4089,7 +4751,54 @@ class PersistentGraph:
         Returns:
            HashMap<String, Prop> - Properties paired with their names
         """
-    def rolling(self, window, step=None):
+    def remove_edge(self, src, dst, layer=None):
+        """
+        Remove an edge from `layer`, or from all its layers if `layer` is not set
+
+        Arguments:
+            src (str or int): The id of the source node
+            dst (str or int): The id of the destination node
+            layer (str): The layer to remove the edge from (optional)
+        """
+    def remove_edge_property_updates(self, src, dst, start, end, name=None, layer=None):
+        """
+        Remove the updates of a temporal edge property in the interval `[start, end)`
+
+        Arguments:
+            src (str or int): The id of the source node
+            dst (str or int): The id of the destination node
+            start (TimeInput): The start of the interval
+            end (TimeInput): The end of the interval
+            name (str): The name of the property, all temporal properties are affected if not set
+            layer (str): Only remove updates in this layer (optional)
+        """
+    def remove_node(self, node):
+        """
+        Remove a node together with all its edges
+
+        Arguments:
+            node (str or int): The id of the node
+        """
+    def remove_node_property_updates(self, node, start, end, name=None):
+        """
+        Remove the updates of a temporal node property in the interval `[start, end)`
+
+        Arguments:
+            node (str or int): The id of the node
+            start (TimeInput): The start of the interval
+            end (TimeInput): The end of the interval
+            name (str): The name of the property, all temporal properties are affected if not set
+        """
+    def remove_property_updates(self, start, end, name=None):
+        """
+        Remove the updates of a temporal graph property in the interval `[start, end)`
+
+        Arguments:
+            start (TimeInput): The start of the interval
+            end (TimeInput): The end of the interval
+            name (str): The name of the property, all temporal properties are affected if not set
+        """
+    def rolling(self, window, step=None, timezone=None, alignment=None):
         """
         Creates a `WindowSet` with the given `window` size and optional `step` using a rolling window.
 
@@ -4098,6 +4807,8 @@ class PersistentGraph:
         Arguments:
             window (int | str): The size of the window.
             step (int | str | None): The step size of the window. Defaults to `window`.
+            timezone (str | None): Step and size the windows in the wall clock time of this time zone (e.g., "Europe/London") instead of UTC.
+            alignment (str | int | DateTime | None): "calendar" to align windows to the calendar (e.g., days to midnight and weeks to Mondays), or a time to anchor the windows to. Defaults to starting at the start of the timeline.
 
         Returns:
             A `WindowSet` object.
@@ -4238,9 +4949,39 @@ class PersistentGraph:
             Returns:
                 A pyvis network
         """
+    def truncate_before(self, time):
+        """
+        Drop all history before `time`
+
+        Nodes and edges without updates at or after `time` are removed.
+
+        Arguments:
+            time (TimeInput): The new start of the graph
+        """
+    def union(self, other):
+        """
+        Returns a view of the nodes and edges that are in this view or in `other`
+
+        Both views need to be views of the same graph with the same time semantics and time
+        bounds, window the result instead of the views to restrict it in time. Properties are
+        taken from this view.
+
+        Arguments:
+          other (GraphView): The graph view to combine with
+
+        Returns:
+           GraphView - Returns the union of both views
+        """
     @property
     def unique_layers(self):
         """Return all the layer ids in the graph"""
+    def unmerge_node(self, node):
+        """
+        Undo the merge of a node, restoring it with its edges and its id
+
+        Arguments:
+            node (str or int): The id of the merged node
+        """
     def update_constant_properties(self, properties):
         """
         Updates static properties to the graph.
@@ -4300,8 +5041,42 @@ class PersistentGraph:
     @property
     def window_size(self):
         """Get the window size (difference between start and end) for this GraphView"""
+    def with_edge_property(self, name, prop, cached=False):
+        """
+        Returns a view with an additional computed edge property
+
+        The property is evaluated lazily for the current window of the view whenever it is
+        accessed. Expressions that refer to temporal properties are temporal themselves, all
+        other computed properties are constant.
+
+        Arguments:
+          name (str): The name of the property, it hides any stored edge property with the same name
+          prop (str | Callable[[Edge], Any]): An arithmetic expression over the properties of the
+              edge like `"amount * fx_rate"` or a function that computes the value for an edge
+          cached (bool): Cache the values of the function for each edge and window (defaults to False)
+
+        Returns:
+           GraphView - Returns the view with the computed property
+        """
+    def with_node_property(self, name, prop, cached=False):
+        """
+        Returns a view with an additional computed node property
+
+        The property is evaluated lazily for the current window of the view whenever it is
+        accessed. Expressions that refer to temporal properties are temporal themselves, all
+        other computed properties are constant.
+
+        Arguments:
+          name (str): The name of the property, it hides any stored node property with the same name
+          prop (str | Callable[[Node], Any]): An arithmetic expression over the properties of the
+              node like `"amount * fx_rate"` or a function that computes the value for a node
+          cached (bool): Cache the values of the function for each node and window (defaults to False)
+
+        Returns:
+           GraphView - Returns the view with the computed property
+        """
     def write_updates(self):
-        """Persist the new updates by appending them to the cache file."""
+        """Persist the new updates by appending them to the write-ahead log of the cache file."""
 
 class Properties:
     """A view of the properties of an entity"""
@@ -17,6 +17,9 @@ class GraphServer:
         cache_tti_seconds=None,
         log_level=None,
         config_path=None,
+        max_query_depth=None,
+        max_query_complexity=None,
+        query_timeout_seconds=None,
     ):
         """Initialize self.  See help(type(self)) for accurate signature."""
     def run(self, port=1736, timeout_ms=...):
@@ -7,7 +7,7 @@ import re
 import pandas as pd
 import pandas.core.frame
 import pytest
-from raphtory import Graph, PersistentGraph, PyDirection
+from raphtory import Graph, GraphDiff, PersistentGraph, PyDirection
 from raphtory import algorithms
 from raphtory import graph_loader
 import tempfile
@@ -1600,6 +1600,28 @@ def test_rolling_as_iterable():
     assert time_index == [1, 2, 3, 4]
 
 
+def test_rolling_in_timezone_and_aligned():
+    g = Graph()
+
+    g.add_node("2024-03-30T12:00:00+00:00", 1)
+    g.add_node("2024-04-01T12:00:00+01:00", 2)
+
+    # the day the clocks go forward in London only has 23 hours
+    windows = g.rolling("1 day", timezone="Europe/London", alignment="calendar")
+    assert [w.end - w.start for w in windows] == [
+        24 * 3600 * 1000,
+        23 * 3600 * 1000,
+        24 * 3600 * 1000,
+    ]
+    assert [w.count_nodes() for w in windows] == [1, 0, 1]
+
+    windows = g.rolling("1 day", alignment="2024-03-30 06:00:00")
+    assert [w.start_date_time.hour for w in windows] == [6, 6, 6]
+
+    with pytest.raises(Exception):
+        g.rolling("1 day", timezone="Mars/Olympus")
+
+
 def test_layer_name():
     g = Graph()
 
@@ -2389,6 +2411,190 @@ def test_persistent_event_graphs():
     pg.delete_edge(4, 1, 3)
 
 
+def test_graph_diff():
+    g = PersistentGraph()
+    g.add_edge(1, 1, 2, {"weight": 1})
+    g.add_edge(1, 2, 3, layer="friends")
+    g.add_edge(5, 1, 2, {"weight": 2})
+    g.delete_edge(6, 2, 3, layer="friends")
+    g.add_edge(7, 3, 4)
+
+    diff = g.at(2).diff(g.at(8))
+    assert diff.added_nodes == [4]
+    assert diff.added_edges == [(3, 4, "_default")]
+    assert diff.removed_edges == [(2, 3, "friends")]
+    assert diff.changed_edges == [(1, 2, "_default")]
+    assert diff.removed_layers == ["friends"]
+    assert not diff.is_empty()
+    assert GraphDiff.from_json(diff.to_json()).changed_edges == diff.changed_edges
+
+    patched = PersistentGraph()
+    patched.add_edge(1, 1, 2, {"weight": 1})
+    patched.add_edge(1, 2, 3, layer="friends")
+    diff.apply(patched, 3)
+    assert patched.at(4).diff(g.at(8)).is_empty()
+
+
+def test_merge_graphs():
+    left = Graph()
+    left.add_edge(1, "a", "b", {"weight": 1}, layer="x")
+    left.add_node(0, "a", node_type="person")
+    left.node("a").add_constant_properties({"name": "left"})
+    right = Graph()
+    right.add_edge(2, "a", "b", {"weight": 2}, layer="y")
+    right.add_node(0, "a", node_type="company")
+    right.node("a").add_constant_properties({"name": "right"})
+
+    with pytest.raises(Exception):
+        left.merge(right)
+
+    merged = left.merge(right, "keep_right")
+    assert merged.edge("a", "b").history() == [1, 2]
+    assert sorted(merged.unique_layers) == ["x", "y"]
+    assert merged.node("a").node_type == "company"
+    assert merged.node("a").properties["name"] == "right"
+
+    merged = left.merge(right, lambda key, l, r: l + "+" + r)
+    assert merged.node("a").node_type == "person+company"
+    assert merged.node("a").properties["name"] == "left+right"
+
+
+def test_union_intersection():
+    g = Graph()
+    g.add_edge(1, 1, 2)
+    g.add_edge(5, 2, 3)
+    g.add_edge(9, 3, 4)
+
+    union = g.subgraph([1, 2]).union(g.subgraph([3, 4]))
+    assert union.count_edges() == 2
+    assert union.has_edge(3, 4)
+    assert not union.has_edge(2, 3)
+    assert union.window(0, 3).count_edges() == 1
+
+    intersection = g.subgraph([1, 2, 3]).intersection(g.subgraph([2, 3, 4]))
+    assert sorted(intersection.nodes.id) == [2, 3]
+    assert intersection.count_edges() == 1
+
+    with pytest.raises(Exception, match="different time bounds"):
+        g.window(0, 3).union(g.window(8, 10))
+    with pytest.raises(Exception, match="not based on the same graph"):
+        g.intersection(Graph())
+
+
+def test_removals():
+    g = Graph()
+    g.add_edge(1, "a", "b", {"weight": 1})
+    g.add_edge(2, "b", "c", {"weight": 2}, layer="x")
+    g.add_edge(3, "b", "c", {"weight": 3}, layer="y")
+    g.add_edge(6, "c", "d", {"weight": 6})
+
+    g.remove_edge("b", "c", layer="x")
+    assert g.edge("b", "c").history() == [3]
+    g.remove_edge_property_updates("b", "c", 0, 10, "weight")
+    assert g.edge("b", "c").properties.get("weight") is None
+
+    g.remove_node("a")
+    assert not g.has_node("a")
+    assert g.count_edges() == 2
+    with pytest.raises(Exception):
+        g.remove_node("a")
+
+    g.truncate_before(5)
+    assert sorted(g.nodes.id) == ["c", "d"]
+    assert g.earliest_time == 6
+
+
+def test_truncate_persistent_graph():
+    g = PersistentGraph()
+    g.add_edge(1, 1, 2, {"weight": 1})
+    g.add_edge(2, 2, 3)
+    g.delete_edge(3, 2, 3)
+
+    g.truncate_before(4)
+    assert g.edge(1, 2).history() == [4]
+    assert g.edge(1, 2).properties["weight"] == 1
+    assert not g.has_edge(2, 3)
+
+
+def test_merge_nodes():
+    g = Graph()
+    g.add_node(1, "acct_1", {"balance": 10})
+    g.add_edge(2, "A-0001", "b", {"amount": 5})
+    g.merge_nodes("acct_1", "A-0001")
+
+    assert g.count_nodes() == 2
+    assert g.node("A-0001").name == "acct_1"
+    assert g.edge("acct_1", "b").history() == [2]
+    assert g.node_aliases("acct_1") == ["A-0001"]
+    assert g.canonical_id("A-0001") == "acct_1"
+
+    g.unmerge_node("A-0001")
+    assert g.count_nodes() == 3
+    assert g.canonical_id("A-0001") == "A-0001"
+    assert not g.has_edge("acct_1", "b")
+    with pytest.raises(Exception):
+        g.unmerge_node("A-0001")
+
+
+def test_hyperedges():
+    g = Graph()
+    g.add_hyperedge(1, "m1", [("alice", "host"), "bob", "charlie"], {"duration": 30})
+    h = g.add_hyperedge(5, "m2", [("bob", "host"), "dave"])
+    assert h.id == "m2"
+    assert g.count_hyperedges() == 2
+    assert g.window(0, 3).count_hyperedges() == 1
+
+    m1 = g.hyperedge("m1")
+    assert m1.time == 1
+    assert m1.properties["duration"] == 30
+    assert [(n.name, role) for n, role in m1.participants] == [
+        ("alice", "host"),
+        ("bob", None),
+        ("charlie", None),
+    ]
+
+    bob = g.node("bob")
+    assert bob.hyperedge_degree() == 2
+    assert [h.id for h in bob.hyperedges()] == ["m1", "m2"]
+    assert sorted(bob.hyperedge_neighbours.name) == ["alice", "charlie", "dave"]
+
+    bipartite = g.bipartite_projection()
+    assert bipartite.node("m1").node_type == "hyperedge"
+    assert bipartite.layer("host").has_edge("alice", "m1")
+
+    clique = g.clique_projection()
+    assert clique.count_edges() == 4
+    assert clique.edge("alice", "bob").properties["duration"] == 30
+
+    with pytest.raises(Exception):
+        g.add_hyperedge(7, "m1", ["eve"])
+
+
+def test_computed_properties():
+    g = Graph()
+    g.add_node(1, "a", {"amount": 10})
+    g.add_node(3, "a", {"amount": 20})
+    g.node("a").add_constant_properties({"fx_rate": 1.5})
+    g.add_edge(1, "a", "b", {"weight": 2})
+    g.add_edge(5, "a", "c", {"weight": 4})
+
+    view = g.with_node_property("amount_usd", "amount * fx_rate")
+    view = view.with_node_property("degree", lambda node: node.degree(), cached=True)
+    view = view.with_edge_property("double_weight", "weight * 2")
+
+    a = view.node("a")
+    assert a.properties.temporal.get("amount_usd").items() == [(1, 15.0), (3, 30.0)]
+    assert a.properties["degree"] == 2
+    assert view.window(0, 3).node("a").properties["degree"] == 1
+    assert view.edge("a", "c").properties["double_weight"] == 8
+    assert "amount_usd" not in g.node("a").properties
+
+    with pytest.raises(Exception):
+        g.with_node_property("x", "x + 1")
+    with pytest.raises(Exception):
+        g.with_node_property("x", "amount *")
+
+
 def test_is_self_loop():
     g = Graph()
     g.add_node(0, "A", None, None)
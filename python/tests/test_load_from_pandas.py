@@ -1063,6 +1063,40 @@ def test_load_edge_deletions_from_pandas():
     assert g.window(10, 12).edges.src.id.collect() == [1, 2, 5]
 
 
+def test_load_edge_intervals_from_pandas():
+    edges_df = pd.DataFrame(
+        {
+            "src": [1, 2, 3],
+            "dst": [2, 3, 4],
+            "start": [1, 3, 8],
+            "end": [5, 10, 12],
+            "weight": [1.0, 2.0, 3.0],
+        }
+    )
+
+    g = PersistentGraph()
+    g.load_edge_intervals_from_pandas(
+        edges_df, "start", "end", "src", "dst", properties=["weight"]
+    )
+    assert g.edge(1, 2).deletions() == [5]
+    assert g.edge(2, 3).duration == 7
+    assert g.at(4).edges.src.id.collect() == [1, 2]
+    assert g.active_during(4, 6).edges.src.id.collect() == [1, 2]
+    assert g.active_during(4, 6, "containment").edges.src.id.collect() == [2]
+
+
+def test_load_adjacent_edge_intervals_from_pandas():
+    edges_df = pd.DataFrame(
+        {"src": [1, 1], "dst": [2, 2], "start": [1, 5], "end": [5, 10]}
+    )
+
+    g = PersistentGraph()
+    g.load_edge_intervals_from_pandas(edges_df, "start", "end", "src", "dst")
+    assert g.edge(1, 2).deletions() == [5, 10]
+    # the first interval ends before the second one starts
+    assert g.at(5).edge(1, 2).is_valid()
+
+
 def test_edge_both_option_failures_pandas():
     edges_df = pd.DataFrame(
         {
@@ -184,6 +184,7 @@ mod cypher {
                             src_col: &src_col,
                             dst_col: &dst_col,
                             time_col: &time_col,
+                            deletions_dir: None,
                         }
                     })
                     .collect();
@@ -345,6 +345,7 @@ mod cypher {
                         src_col: "source",
                         dst_col: "destination",
                         time_col: "time",
+                        deletions_dir: None,
                     },
                     ParquetLayerCols {
                         parquet_dir: v1_layer_path.to_str().unwrap(),
@@ -352,6 +353,7 @@ mod cypher {
                         src_col: "src",
                         dst_col: "dst",
                         time_col: "epoch_time",
+                        deletions_dir: None,
                     },
                 ];
 
@@ -1,5 +1,13 @@
+#[cfg(feature = "storage")]
+use crate::model::graph::cypher::ConvertedGraphs;
 use crate::{
-    model::{algorithms::global_plugins::GlobalPlugins, create_dirs_if_not_present, GqlGraphType},
+    model::{
+        algorithms::global_plugins::GlobalPlugins,
+        create_dirs_if_not_present,
+        graph::{bulk_load::LoadProgressMap, versions::VersionLocks},
+        GqlGraphType,
+    },
+    observability::metrics::Metrics,
     server_config::AppConfig,
 };
 use moka::sync::Cache;
@@ -17,6 +25,7 @@ use raphtory::{
     db::{api::view::MaterializedGraph, graph::views::deletion_graph::PersistentGraph},
     prelude::*,
     search::IndexedGraph,
+    serialise::wal_path,
 };
 use std::{
     collections::HashMap,
@@ -28,32 +37,60 @@ use std::{
 };
 use walkdir::WalkDir;
 
+#[derive(Clone)]
 pub struct Data {
     pub(crate) work_dir: PathBuf,
     pub(crate) graphs: Cache<PathBuf, IndexedGraph<MaterializedGraph>>,
+    /// Read-only snapshots of the graphs, keyed by graph path and version name
+    pub(crate) snapshots: Cache<(PathBuf, String), IndexedGraph<MaterializedGraph>>,
     pub(crate) global_plugins: GlobalPlugins,
+    pub(crate) load_progress: LoadProgressMap,
+    pub(crate) version_locks: VersionLocks,
+    pub(crate) metrics: Arc<Metrics>,
+    #[cfg(feature = "storage")]
+    pub(crate) cypher_graphs: ConvertedGraphs,
 }
 
 impl Data {
     pub fn new(work_dir: &Path, configs: &AppConfig) -> Self {
         let cache_configs = &configs.cache;
 
+        let metrics = Arc::new(Metrics::new());
+        let eviction_metrics = metrics.clone();
+
         let graphs_cache_builder = Cache::<_, IndexedGraph<MaterializedGraph>>::builder()
             .max_capacity(cache_configs.capacity)
             .time_to_idle(std::time::Duration::from_secs(cache_configs.tti_seconds))
-            .eviction_listener(|_, value, _| {
-                value
-                    .write_updates()
-                    .unwrap_or_else(|err| println!("Write on eviction failed: {err:?}"))
+            .eviction_listener(move |path, value, cause| {
+                eviction_metrics.record_eviction(cause);
+                eviction_metrics.forget_graph(&path, &value);
+                value.write_updates().unwrap_or_else(|err| {
+                    eviction_metrics.write_back_failures.inc();
+                    tracing::error!("Write on eviction failed: {err:?}")
+                })
             })
             .build();
 
         let graphs_cache: Cache<PathBuf, IndexedGraph<MaterializedGraph>> = graphs_cache_builder;
 
+        let snapshots_cache = Cache::builder()
+            .max_capacity(cache_configs.capacity)
+            .time_to_idle(std::time::Duration::from_secs(cache_configs.tti_seconds))
+            .build();
+
         Self {
             work_dir: work_dir.to_path_buf(),
             graphs: graphs_cache,
+            snapshots: snapshots_cache,
             global_plugins: GlobalPlugins::default(),
+            load_progress: Default::default(),
+            version_locks: Default::default(),
+            metrics,
+            #[cfg(feature = "storage")]
+            cypher_graphs: Cache::builder()
+                .max_capacity(cache_configs.capacity)
+                .time_to_idle(std::time::Duration::from_secs(cache_configs.tti_seconds))
+                .build(),
         }
     }
 
@@ -67,8 +104,18 @@ impl Data {
         if !full_path.exists() {
             return Err(GraphError::GraphNotFound(path.to_path_buf()).into());
         } else {
-            self.graphs
-                .try_get_with(path.to_path_buf(), || get_graph_from_path(&full_path))
+            let mut loaded = false;
+            let graph = self.graphs.try_get_with(path.to_path_buf(), || {
+                loaded = true;
+                get_graph_from_path(&full_path)
+            })?;
+            if loaded {
+                self.metrics.cache_misses.inc();
+                self.metrics.record_graph(path, &graph);
+            } else {
+                self.metrics.cache_hits.inc();
+            }
+            Ok(graph)
         }
     }
 
@@ -78,6 +125,11 @@ impl Data {
             return Err(GraphError::GraphNameAlreadyExists(path.to_path_buf()).into());
         }
         create_dirs_if_not_present(&full_path)?;
+        // a log left behind by a deleted graph would match the empty snapshot and be replayed
+        let wal = wal_path(&full_path);
+        if wal.exists() {
+            fs::remove_file(wal)?;
+        }
         let mut cache = File::create_new(full_path)?;
         match graph_type {
             GqlGraphType::Persistent => {
@@ -125,6 +177,10 @@ impl Data {
             if let Ok(entries) = fs::read_dir(dir) {
                 for entry in entries {
                     if let Ok(entry) = entry {
+                        // hidden entries hold server metadata such as the version history
+                        if entry.file_name().to_string_lossy().starts_with('.') {
+                            continue;
+                        }
                         let path = entry.path();
                         if path.is_dir() {
                             if is_disk_graph_dir(&path) {
@@ -550,6 +606,54 @@ pub(crate) mod data_tests {
         assert!(!data.graphs.contains_key(&PathBuf::from("test_g")));
     }
 
+    #[test]
+    fn test_cache_metrics() {
+        let tmp_work_dir = tempfile::tempdir().unwrap();
+        let graph = Graph::new();
+        graph.add_edge(0, 1, 2, NO_PROPS, None).unwrap();
+        graph.encode(&tmp_work_dir.path().join("test_g")).unwrap();
+
+        let data = Data::new(tmp_work_dir.path(), &AppConfig::default());
+        data.get_graph(Path::new("test_g")).unwrap();
+        data.get_graph(Path::new("test_g")).unwrap();
+        assert_eq!(data.metrics.cache_misses.get(), 1);
+        assert_eq!(data.metrics.cache_hits.get(), 1);
+
+        data.graphs.invalidate(&PathBuf::from("test_g"));
+        data.graphs.run_pending_tasks();
+        assert_eq!(
+            data.metrics
+                .cache_evictions
+                .with_label_values(&["explicit"])
+                .get(),
+            1
+        );
+
+        assert!(!data
+            .metrics
+            .encode()
+            .unwrap()
+            .contains("raphtory_graph_nodes{"));
+
+        let graph = data.get_graph(Path::new("test_g")).unwrap();
+        let metrics = data.metrics.encode().unwrap();
+        assert!(metrics.contains("raphtory_graph_nodes{graph=\"test_g\"} 2"));
+        assert!(metrics.contains("raphtory_graph_cache_misses_total 2"));
+
+        // gauges are only updated for graphs that changed
+        graph.add_edge(1, 2, 3, NO_PROPS, None).unwrap();
+        assert!(data
+            .metrics
+            .encode()
+            .unwrap()
+            .contains("raphtory_graph_nodes{graph=\"test_g\"} 2"));
+        data.metrics.record_mutations(&data.graphs);
+        let metrics = data.metrics.encode().unwrap();
+        assert!(metrics.contains("raphtory_graph_nodes{graph=\"test_g\"} 3"));
+        assert!(metrics.contains("raphtory_graph_edges{graph=\"test_g\"} 2"));
+        assert!(metrics.contains("raphtory_graph_cache_entries 1"));
+    }
+
     #[cfg(any(target_os = "macos", target_os = "linux"))]
     #[test]
     fn test_invalid_utf8_failure() {
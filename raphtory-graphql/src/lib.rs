@@ -4,6 +4,7 @@ pub mod azure_auth;
 mod data;
 pub mod model;
 mod observability;
+mod query_limits;
 mod routes;
 pub mod server;
 pub mod server_config;
@@ -17,7 +18,8 @@ mod graphql_test {
     use crate::{
         data::{data_tests::save_graphs_to_work_dir, Data},
         model::App,
-        server_config::AppConfig,
+        query_limits::with_query_limits,
+        server_config::{AppConfig, AppConfigBuilder},
         url_encode::{url_decode_graph, url_encode_graph},
     };
     use async_graphql::UploadValue;
@@ -195,6 +197,55 @@ mod graphql_test {
         );
     }
 
+    #[tokio::test]
+    async fn test_rolling_windows_in_timezone() {
+        let graph = Graph::new();
+        // 2024-03-30T12:00:00Z and 2024-04-01T12:00:00+01:00, the clocks go forward in between
+        graph.add_node(1711800000000, 1, NO_PROPS, None).unwrap();
+        graph.add_node(1711969200000, 2, NO_PROPS, None).unwrap();
+        let graphs = HashMap::from([("graph".to_string(), MaterializedGraph::from(graph))]);
+        let tmp_dir = tempdir().unwrap();
+        save_graphs_to_work_dir(tmp_dir.path(), &graphs).unwrap();
+
+        let data = Data::new(tmp_dir.path(), &AppConfig::default());
+        let schema = App::create_schema().data(data).finish().unwrap();
+        let query = r#"
+        {
+          graph(path: "graph") {
+            rolling(window: "1 day", timezone: "Europe/London", alignment: CALENDAR) {
+              count
+              list {
+                start
+                end
+                countNodes
+              }
+              page(limit: 2, offset: 1) {
+                start
+              }
+            }
+          }
+        }
+        "#;
+
+        let res = schema.execute(Request::new(query)).await;
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            json!({
+                "graph": {
+                    "rolling": {
+                        "count": 3,
+                        "list": [
+                            { "start": 1711756800000i64, "end": 1711843200000i64, "countNodes": 1 },
+                            { "start": 1711843200000i64, "end": 1711926000000i64, "countNodes": 0 },
+                            { "start": 1711926000000i64, "end": 1712012400000i64, "countNodes": 1 },
+                        ],
+                        "page": [{ "start": 1711926000000i64 }],
+                    }
+                }
+            }),
+        );
+    }
+
     #[tokio::test]
     async fn test_unique_temporal_properties() {
         let g = Graph::new();
@@ -714,6 +765,171 @@ mod graphql_test {
         );
     }
 
+    #[tokio::test]
+    async fn test_load_edges_from_parquet_upload() {
+        let graphs = HashMap::from([("test".to_string(), Graph::new().into())]);
+        let tmp_dir = tempdir().unwrap();
+        save_graphs_to_work_dir(tmp_dir.path(), &graphs).unwrap();
+        let data = Data::new(tmp_dir.path(), &AppConfig::default());
+        let schema = App::create_schema().data(data).finish().unwrap();
+
+        let parquet_file = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("../raphtory/resources/test/test_data.parquet");
+        let upload_val = UploadValue {
+            filename: "test_data.parquet".into(),
+            content_type: Some("application/octet-stream".into()),
+            content: std::fs::File::open(parquet_file).unwrap(),
+        };
+
+        let query = r#"
+        query($file: Upload!) {
+            updateGraph(path: "test") {
+                loadEdgesFromFile(
+                    file: $file,
+                    columns: { time: "time", src: "src", dst: "dst", properties: ["weight"] }
+                ) {
+                    numRows
+                    numLoaded
+                    numFailed
+                }
+            }
+        }
+        "#;
+        let variables = json!({ "file": null });
+        let mut req = Request::new(query).variables(Variables::from_json(variables));
+        req.set_upload("variables.file", upload_val);
+        let res = schema.execute(req).await;
+        assert_eq!(res.errors.len(), 0);
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            json!({"updateGraph": {"loadEdgesFromFile": {"numRows": 5, "numLoaded": 5, "numFailed": 0}}})
+        );
+
+        let req = Request::new(r#"query { graph(path: "test") { countEdges } }"#);
+        let res = schema.execute(req).await;
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            json!({"graph": {"countEdges": 5}})
+        );
+    }
+
+    #[tokio::test]
+    async fn test_query_limits() {
+        let graph = Graph::new();
+        graph.add_edge(0, 1, 2, NO_PROPS, None).unwrap();
+        graph.add_edge(0, 2, 3, NO_PROPS, None).unwrap();
+        let graphs = HashMap::from([("test".to_string(), graph.into())]);
+        let tmp_dir = tempdir().unwrap();
+        save_graphs_to_work_dir(tmp_dir.path(), &graphs).unwrap();
+
+        let config = AppConfigBuilder::new()
+            .with_query_max_depth(5)
+            .with_query_timeout_seconds(10)
+            .build();
+        let data = Data::new(tmp_dir.path(), &config);
+        let schema = with_query_limits(App::create_schema().data(data), &config.query)
+            .finish()
+            .unwrap();
+
+        let shallow = r#"
+        {
+            graph(path: "test") {
+                nodes { list { name } }
+            }
+        }
+        "#;
+        let res = schema.execute(Request::new(shallow)).await;
+        assert_eq!(res.errors.len(), 0);
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            json!({"graph": {"nodes": {"list": [{"name": "1"}, {"name": "2"}, {"name": "3"}]}}})
+        );
+
+        let deep = r#"
+        {
+            graph(path: "test") {
+                nodes { list { neighbours { list { neighbours { list { name } } } } } }
+            }
+        }
+        "#;
+        let res = schema.execute(Request::new(deep)).await;
+        assert_eq!(res.errors.len(), 1);
+        assert!(res.errors[0].message.contains("nested too deep"));
+    }
+
+    #[tokio::test]
+    async fn test_graph_snapshots() {
+        let graph = Graph::new();
+        graph.add_edge(0, 1, 2, NO_PROPS, None).unwrap();
+        let graphs = HashMap::from([("test".to_string(), graph.into())]);
+        let tmp_dir = tempdir().unwrap();
+        save_graphs_to_work_dir(tmp_dir.path(), &graphs).unwrap();
+        let data = Data::new(tmp_dir.path(), &AppConfig::default());
+        let schema = App::create_schema().data(data).finish().unwrap();
+
+        let snapshot = r#"
+        mutation {
+            createSnapshot(path: "test", name: "v1", description: "first") {
+                name
+                numEdges
+            }
+        }
+        "#;
+        let res = schema.execute(Request::new(snapshot)).await;
+        assert_eq!(res.errors.len(), 0);
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            json!({"createSnapshot": {"name": "v1", "numEdges": 1}})
+        );
+
+        let add_edge = r#"
+        {
+            updateGraph(path: "test") {
+                addEdge(time: 1, src: "2", dst: "3") { success }
+            }
+        }
+        "#;
+        let res = schema.execute(Request::new(add_edge)).await;
+        assert_eq!(res.errors.len(), 0);
+
+        let counts = r#"
+        {
+            graph(path: "test") { countEdges }
+            graphVersion(path: "test", name: "v1") { countEdges }
+            graphVersions(path: "test") { name description }
+        }
+        "#;
+        let res = schema.execute(Request::new(counts)).await;
+        assert_eq!(res.errors.len(), 0);
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            json!({
+                "graph": {"countEdges": 2},
+                "graphVersion": {"countEdges": 1},
+                "graphVersions": [{"name": "v1", "description": "first"}],
+            })
+        );
+
+        let rollback = r#"mutation { rollbackGraph(path: "test", name: "v1") }"#;
+        let res = schema.execute(Request::new(rollback)).await;
+        assert_eq!(res.errors.len(), 0);
+
+        let res = schema
+            .execute(Request::new(r#"{ graph(path: "test") { countEdges } }"#))
+            .await;
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            json!({"graph": {"countEdges": 1}})
+        );
+
+        // the version history must not show up as a graph
+        let res = schema.execute(Request::new(r#"{ graphs { name } }"#)).await;
+        assert_eq!(
+            res.data.into_json().unwrap(),
+            json!({"graphs": {"name": ["test"]}})
+        );
+    }
+
     #[tokio::test]
     async fn test_graph_send_receive_base64() {
         let g = PersistentGraph::new();
@@ -980,4 +1196,178 @@ mod graphql_test {
         let expected_error_message = "Disk Graph is immutable";
         assert_eq!(error_message, expected_error_message);
     }
+
+    #[cfg(feature = "storage")]
+    #[tokio::test]
+    async fn test_cypher_on_in_memory_graph() {
+        let graph = Graph::new();
+        graph.add_edge(1, 1, 2, [("weight", 3.0)], None).unwrap();
+        graph.add_edge(2, 1, 3, [("weight", 4.0)], None).unwrap();
+        graph.add_edge(3, 2, 3, [("weight", 5.0)], None).unwrap();
+
+        let graph: MaterializedGraph = graph.into();
+        let graphs = HashMap::from([("graph".to_string(), graph)]);
+        let tmp_dir = tempdir().unwrap();
+        save_graphs_to_work_dir(tmp_dir.path(), &graphs).unwrap();
+
+        let data = Data::new(tmp_dir.path(), &AppConfig::default());
+        let schema = App::create_schema().data(data).finish().unwrap();
+
+        let req = r#"
+        {
+          graph(path: "graph") {
+            cypher(query: "match ()-[e]->() RETURN e.weight ORDER BY e.weight", limit: 2) {
+              columns {
+                name
+              }
+              rows {
+                values
+              }
+              truncated
+            }
+          }
+        }
+        "#;
+
+        let req = Request::new(req);
+        let res = schema.execute(req).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let data = res.data.into_json().unwrap();
+        assert_eq!(
+            data["graph"]["cypher"]["rows"],
+            json!([{ "values": [3.0] }, { "values": [4.0] }]),
+        );
+        assert_eq!(data["graph"]["cypher"]["truncated"], json!(true));
+    }
+
+    #[cfg(feature = "storage")]
+    const CYPHER_WEIGHTS: &str = r#"
+    {
+      graph(path: "graph") {
+        cypher(query: "match ()-[e]->() RETURN e.weight ORDER BY e.weight", limit: 1) {
+          rows {
+            values
+          }
+        }
+      }
+    }
+    "#;
+
+    #[cfg(feature = "storage")]
+    #[tokio::test]
+    async fn test_cypher_on_disk_graph() {
+        let graph = Graph::new();
+        graph.add_edge(1, 1, 2, [("weight", 3.0)], None).unwrap();
+        graph.add_edge(2, 1, 3, [("weight", 4.0)], None).unwrap();
+
+        let tmp_work_dir = tempdir().unwrap();
+        let _ = DiskGraphStorage::from_graph(&graph, &tmp_work_dir.path().join("graph")).unwrap();
+        let data = Data::new(tmp_work_dir.path(), &AppConfig::default());
+        let schema = App::create_schema().data(data.clone()).finish().unwrap();
+
+        let res = schema.execute(Request::new(CYPHER_WEIGHTS)).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let result = res.data.into_json().unwrap();
+        assert_eq!(
+            result["graph"]["cypher"]["rows"],
+            json!([{ "values": [3.0] }])
+        );
+        // disk graphs are queried directly
+        assert_eq!(data.cypher_graphs.entry_count(), 0);
+    }
+
+    #[cfg(feature = "storage")]
+    #[tokio::test]
+    async fn test_cypher_conversion_is_cached_until_mutation() {
+        let graph = Graph::new();
+        graph.add_edge(1, 1, 2, [("weight", 3.0)], None).unwrap();
+        let graph: MaterializedGraph = graph.into();
+        let graphs = HashMap::from([("graph".to_string(), graph)]);
+        let tmp_dir = tempdir().unwrap();
+        save_graphs_to_work_dir(tmp_dir.path(), &graphs).unwrap();
+
+        let data = Data::new(tmp_dir.path(), &AppConfig::default());
+        let schema = App::create_schema().data(data.clone()).finish().unwrap();
+        let path = std::path::Path::new("graph");
+
+        let res = schema.execute(Request::new(CYPHER_WEIGHTS)).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        let converted = data.cypher_graphs.get(path).unwrap();
+        let res = schema.execute(Request::new(CYPHER_WEIGHTS)).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert!(std::sync::Arc::ptr_eq(
+            &converted,
+            &data.cypher_graphs.get(path).unwrap()
+        ));
+
+        data.get_graph(path)
+            .unwrap()
+            .add_edge(2, 1, 3, [("weight", 1.0)], None)
+            .unwrap();
+        let res = schema.execute(Request::new(CYPHER_WEIGHTS)).await;
+        assert!(res.errors.is_empty(), "{:?}", res.errors);
+        assert_eq!(
+            res.data.into_json().unwrap()["graph"]["cypher"]["rows"],
+            json!([{ "values": [1.0] }])
+        );
+        assert!(!std::sync::Arc::ptr_eq(
+            &converted,
+            &data.cypher_graphs.get(path).unwrap()
+        ));
+    }
+
+    #[cfg(feature = "storage")]
+    #[tokio::test]
+    async fn test_cypher_timeout_discards_conversion() {
+        let graph = Graph::new();
+        graph.add_edge(1, 1, 2, [("weight", 3.0)], None).unwrap();
+        let graph: MaterializedGraph = graph.into();
+        let graphs = HashMap::from([("graph".to_string(), graph)]);
+        let tmp_dir = tempdir().unwrap();
+        save_graphs_to_work_dir(tmp_dir.path(), &graphs).unwrap();
+
+        let data = Data::new(tmp_dir.path(), &AppConfig::default());
+        let schema = App::create_schema().data(data.clone()).finish().unwrap();
+
+        let req = r#"
+        {
+          graph(path: "graph") {
+            cypher(query: "match ()-[e]->() RETURN e.weight", timeoutMs: 0) {
+              count
+            }
+          }
+        }
+        "#;
+        let res = schema.execute(Request::new(req)).await;
+        assert_eq!(res.errors[0].message, "Cypher query timed out after 0ms");
+
+        // give the conversion time to reach its next check point
+        tokio::time::sleep(std::time::Duration::from_millis(500)).await;
+        data.cypher_graphs.run_pending_tasks();
+        assert!(data
+            .cypher_graphs
+            .get(std::path::Path::new("graph"))
+            .is_none());
+    }
+
+    #[cfg(feature = "storage")]
+    #[tokio::test]
+    async fn test_cypher_on_persistent_graph_fails() {
+        let graph = PersistentGraph::new();
+        graph.add_edge(1, 1, 2, [("weight", 3.0)], None).unwrap();
+        graph.delete_edge(2, 1, 2, None).unwrap();
+        let graph: MaterializedGraph = graph.into();
+        let graphs = HashMap::from([("graph".to_string(), graph)]);
+        let tmp_dir = tempdir().unwrap();
+        save_graphs_to_work_dir(tmp_dir.path(), &graphs).unwrap();
+
+        let data = Data::new(tmp_dir.path(), &AppConfig::default());
+        let schema = App::create_schema().data(data).finish().unwrap();
+
+        let res = schema.execute(Request::new(CYPHER_WEIGHTS)).await;
+        assert_eq!(
+            res.errors[0].message,
+            "Cypher queries are not supported on persistent graphs"
+        );
+    }
 }
@@ -1,5 +1,8 @@
-use crate::model::algorithms::{
-    algorithm_entry_point::AlgorithmEntryPoint, graph_algorithms::GraphAlgorithms,
+use crate::{
+    model::algorithms::{
+        algorithm_entry_point::AlgorithmEntryPoint, graph_algorithms::GraphAlgorithms,
+    },
+    query_limits::{cancellation_token, run_cancellable, QueryLimitError},
 };
 use async_graphql::{
     dynamic::{Field, FieldFuture, FieldValue, InputValue, Object, ResolverContext, TypeRef},
@@ -33,6 +36,11 @@ pub trait Algorithm<'a, A: AlgorithmEntryPoint<'a> + 'static> {
         let registry = registry.register::<Self::OutputType>();
         let mut field = Field::new(name, Self::output_type(), |ctx| {
             FieldFuture::new(async move {
+                // the future of an algorithm is dropped if its request is cancelled, algorithms
+                // doing blocking work should additionally run it with `run_cancellable`
+                if cancellation_token(&ctx).is_cancelled() {
+                    return Err(QueryLimitError::Cancelled.into());
+                }
                 let algos: &A = ctx.parent_value.downcast_ref().unwrap();
                 Self::apply_algo(&algos, ctx).await
             })
@@ -102,15 +110,29 @@ impl<'a> Algorithm<'a, GraphAlgorithms> for Pagerank {
         entry_point: &GraphAlgorithms,
         ctx: ResolverContext,
     ) -> BoxFuture<'b, FieldResult<Option<FieldValue<'b>>>> {
-        let result = apply_pagerank(entry_point, ctx);
-        Box::pin(async move { result })
+        let graph = entry_point.graph.clone();
+        let token = cancellation_token(&ctx);
+        let args = pagerank_args(&ctx);
+        Box::pin(async move {
+            let (iter_count, threads, tol, damping_factor) = args?;
+            let result = run_cancellable(token, move || {
+                unweighted_page_rank(&graph, Some(iter_count), threads, tol, true, damping_factor)
+                    .get_all_with_names()
+                    .into_iter()
+                    .map(PagerankOutput::from)
+                    .collect_vec()
+            })
+            .await?;
+            Ok(Some(FieldValue::list(
+                result.into_iter().map(FieldValue::owned_any),
+            )))
+        })
     }
 }
 
-fn apply_pagerank<'b>(
-    entry_point: &GraphAlgorithms,
-    ctx: ResolverContext,
-) -> FieldResult<Option<FieldValue<'b>>> {
+fn pagerank_args(
+    ctx: &ResolverContext,
+) -> FieldResult<(usize, Option<usize>, Option<f64>, Option<f64>)> {
     let iter_count = ctx.args.try_get("iterCount")?.u64()? as usize;
     let threads = ctx.args.get("threads").map(|v| v.u64()).transpose()?;
     let threads = threads.map(|v| v as usize);
@@ -120,20 +142,7 @@ fn apply_pagerank<'b>(
         .get("damping_factor")
         .map(|v| v.f64())
         .transpose()?;
-    let binding = unweighted_page_rank(
-        &entry_point.graph,
-        Some(iter_count),
-        threads,
-        tol,
-        true,
-        damping_factor,
-    );
-    let result = binding
-        .get_all_with_names()
-        .into_iter()
-        .map(|pair| FieldValue::owned_any(PagerankOutput::from(pair)))
-        .collect_vec();
-    Ok(Some(FieldValue::list(result)))
+    Ok((iter_count, threads, tol, damping_factor))
 }
 
 pub(crate) struct ShortestPath;
@@ -166,15 +175,30 @@ impl<'a> Algorithm<'a, GraphAlgorithms> for ShortestPath {
         entry_point: &GraphAlgorithms,
         ctx: ResolverContext,
     ) -> BoxFuture<'b, FieldResult<Option<FieldValue<'b>>>> {
-        let result = apply_shortest_path(entry_point, ctx);
-        Box::pin(async move { result })
+        let graph = entry_point.graph.clone();
+        let token = cancellation_token(&ctx);
+        let args = shortest_path_args(&ctx);
+        Box::pin(async move {
+            let (source, targets, direction) = args?;
+            let result = run_cancellable(token, move || {
+                dijkstra_single_source_shortest_paths(&graph, source, targets, None, direction)
+                    .into_iter()
+                    .flat_map(|pair| {
+                        pair.into_iter().map(|(key, value)| {
+                            ShortestPathOutput::from((key.to_string(), value.1))
+                        })
+                    })
+                    .collect_vec()
+            })
+            .await?;
+            Ok(Some(FieldValue::list(
+                result.into_iter().map(FieldValue::owned_any),
+            )))
+        })
     }
 }
 
-fn apply_shortest_path<'b>(
-    entry_point: &GraphAlgorithms,
-    ctx: ResolverContext,
-) -> FieldResult<Option<FieldValue<'b>>> {
+fn shortest_path_args(ctx: &ResolverContext) -> FieldResult<(String, Vec<String>, Direction)> {
     let source = ctx.args.try_get("source")?.string()?;
     let targets = ctx.args.try_get("targets")?.list()?;
     let direction = match ctx.args.try_get("direction")?.string()? {
@@ -185,18 +209,7 @@ fn apply_shortest_path<'b>(
     };
     let targets = targets
         .iter()
-        .map(|v| v.string())
-        .collect::<Result<Vec<&str>, _>>()?;
-    let binding =
-        dijkstra_single_source_shortest_paths(&entry_point.graph, source, targets, None, direction);
-    let result: Vec<FieldValue> = binding
-        .into_iter()
-        .flat_map(|pair| {
-            pair.into_iter()
-                .map(|(key, value)| ShortestPathOutput::from((key.to_string(), value.1)))
-        })
-        .map(FieldValue::owned_any)
-        .collect();
-
-    Ok(Some(FieldValue::list(result)))
+        .map(|v| v.string().map(|target| target.to_owned()))
+        .collect::<Result<Vec<String>, _>>()?;
+    Ok((source.to_owned(), targets, direction))
 }
@@ -1,5 +1,8 @@
-use crate::model::algorithms::{
-    algorithm::Algorithm, document::GqlDocument, global_plugins::GlobalPlugins,
+use crate::{
+    model::algorithms::{
+        algorithm::Algorithm, document::GqlDocument, global_plugins::GlobalPlugins,
+    },
+    query_limits::{cancellation_token, run_cancellable},
 };
 use async_graphql::{
     dynamic::{FieldValue, ResolverContext, TypeRef},
@@ -36,15 +39,18 @@ impl<'a> Algorithm<'a, GlobalPlugins> for GlobalSearch {
             .to_owned();
         let limit = ctx.args.try_get("limit").unwrap().u64().unwrap() as usize;
         let vectorised_graphs = entry_point.vectorised_graphs.clone();
+        let token = cancellation_token(&ctx);
 
         Box::pin(async move {
             let embedding = openai_embedding(vec![query.clone()]).await.remove(0);
             println!("running global search for {query}");
 
-            let graphs = vectorised_graphs.read();
-
-            let cluster = VectorisedCluster::new(graphs.deref());
-            let documents = cluster.search_graph_documents(&embedding, limit, None); // TODO: add window
+            let documents = run_cancellable(token, move || {
+                let graphs = vectorised_graphs.read();
+                let cluster = VectorisedCluster::new(graphs.deref());
+                cluster.search_graph_documents(&embedding, limit, None) // TODO: add window
+            })
+            .await?;
 
             let gql_documents = documents
                 .into_iter()
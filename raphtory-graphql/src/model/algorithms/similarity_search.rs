@@ -1,5 +1,8 @@
-use crate::model::algorithms::{
-    algorithm::Algorithm, document::GqlDocument, vector_algorithms::VectorAlgorithms,
+use crate::{
+    model::algorithms::{
+        algorithm::Algorithm, document::GqlDocument, vector_algorithms::VectorAlgorithms,
+    },
+    query_limits::{cancellation_token, run_cancellable},
 };
 use async_graphql::{
     dynamic::{FieldValue, ResolverContext, TypeRef},
@@ -35,14 +38,18 @@ impl<'a> Algorithm<'a, VectorAlgorithms> for SimilaritySearch {
             .to_owned();
         let limit = ctx.args.try_get("limit").unwrap().u64().unwrap() as usize;
         let graph = entry_point.graph.clone();
+        let token = cancellation_token(&ctx);
 
         Box::pin(async move {
             let embedding = openai_embedding(vec![query.clone()]).await.remove(0);
             println!("running similarity search for {query}");
 
-            let documents = graph
-                .append_by_similarity(&embedding, limit, None)
-                .get_documents();
+            let documents = run_cancellable(token, move || {
+                graph
+                    .append_by_similarity(&embedding, limit, None)
+                    .get_documents()
+            })
+            .await?;
 
             let gql_documents = documents
                 .into_iter()
@@ -0,0 +1,267 @@
+use crate::model::graph::mutable_graph::GqlPropInput;
+use async_graphql::UploadValue;
+use dynamic_graphql::{Enum, InputObject, ResolvedObject, ResolvedObjectFields, SimpleObject};
+use parking_lot::RwLock;
+use raphtory::{
+    core::utils::errors::GraphError,
+    db::api::view::MaterializedGraph,
+    io::bulk_loaders::{load_edges_from_file, load_nodes_from_file, DataFileFormat, LoadReport},
+    prelude::*,
+    search::IndexedGraph,
+};
+use std::{
+    collections::HashMap,
+    io::copy,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+use tempfile::NamedTempFile;
+
+/// Maximum number of row errors returned by a bulk load if no limit is specified
+const DEFAULT_MAX_ERRORS: usize = 100;
+
+/// Progress of the bulk loads currently running on the server, keyed by graph path
+pub(crate) type LoadProgressMap = Arc<RwLock<HashMap<PathBuf, LoadProgress>>>;
+
+#[derive(Enum, Clone, Copy)]
+pub enum GqlDataFileFormat {
+    Parquet,
+    ArrowIpc,
+}
+
+impl From<GqlDataFileFormat> for DataFileFormat {
+    fn from(value: GqlDataFileFormat) -> Self {
+        match value {
+            GqlDataFileFormat::Parquet => DataFileFormat::Parquet,
+            GqlDataFileFormat::ArrowIpc => DataFileFormat::ArrowIpc,
+        }
+    }
+}
+
+/// Column mapping for loading edges, mirrors the arguments of `load_edges_from_parquet`
+#[derive(InputObject)]
+pub struct EdgeFileColumns {
+    pub(crate) time: String,
+    pub(crate) src: String,
+    pub(crate) dst: String,
+    pub(crate) properties: Option<Vec<String>>,
+    pub(crate) constant_properties: Option<Vec<String>>,
+    pub(crate) shared_constant_properties: Option<Vec<GqlPropInput>>,
+    pub(crate) layer: Option<String>,
+    pub(crate) layer_col: Option<String>,
+}
+
+/// Column mapping for loading nodes, mirrors the arguments of `load_nodes_from_parquet`
+#[derive(InputObject)]
+pub struct NodeFileColumns {
+    pub(crate) time: String,
+    pub(crate) id: String,
+    pub(crate) node_type: Option<String>,
+    pub(crate) node_type_col: Option<String>,
+    pub(crate) properties: Option<Vec<String>>,
+    pub(crate) constant_properties: Option<Vec<String>>,
+    pub(crate) shared_constant_properties: Option<Vec<GqlPropInput>>,
+}
+
+#[derive(Clone, Copy, Default, SimpleObject)]
+pub struct LoadProgress {
+    rows_read: usize,
+    num_rows: usize,
+}
+
+#[derive(SimpleObject)]
+pub struct GqlRowError {
+    row: usize,
+    message: String,
+}
+
+#[derive(ResolvedObject)]
+pub struct GqlLoadReport {
+    report: LoadReport,
+}
+
+#[ResolvedObjectFields]
+impl GqlLoadReport {
+    /// Total number of rows in the uploaded file
+    async fn num_rows(&self) -> usize {
+        self.report.num_rows
+    }
+
+    /// Number of rows that were added to the graph completely, rows with missing values are skipped
+    async fn num_loaded(&self) -> usize {
+        self.report.num_loaded
+    }
+
+    /// Number of rows that could not be added to the graph, these may have been added partially
+    async fn num_failed(&self) -> usize {
+        self.report.num_failed
+    }
+
+    /// The first `maxErrors` rows that could not be added to the graph
+    async fn errors(&self) -> Vec<GqlRowError> {
+        self.report
+            .errors
+            .iter()
+            .map(|e| GqlRowError {
+                row: e.row,
+                message: e.error.to_string(),
+            })
+            .collect()
+    }
+}
+
+pub(crate) enum BulkLoad {
+    Edges(EdgeFileColumns),
+    Nodes(NodeFileColumns),
+}
+
+/// Load an uploaded file into `graph`, tracking progress in `progress` under `path` and flushing
+/// the updates to the graph's cache once done.
+pub(crate) async fn run_bulk_load(
+    path: &Path,
+    graph: &IndexedGraph<MaterializedGraph>,
+    upload: UploadValue,
+    format: Option<GqlDataFileFormat>,
+    load: BulkLoad,
+    max_errors: Option<usize>,
+    progress: &LoadProgressMap,
+) -> Result<GqlLoadReport, GraphError> {
+    let format = match format {
+        Some(format) => format.into(),
+        None => DataFileFormat::from_path(Path::new(&upload.filename)).ok_or_else(|| {
+            GraphError::LoadFailure(format!(
+                "Unable to infer the format of '{}', please specify it explicitly",
+                upload.filename
+            ))
+        })?,
+    };
+    let max_errors = max_errors.unwrap_or(DEFAULT_MAX_ERRORS);
+
+    // the guard is moved into the load itself as dropping the request does not stop the load
+    let guard = ProgressGuard::start(progress, path)?;
+    let graph = graph.clone();
+    let mut content = upload.content;
+    let result = tokio::task::spawn_blocking(move || {
+        // the loaders read from a path so we need to persist the upload first, the file is
+        // removed when dropped
+        let mut tmp_file = NamedTempFile::new()?;
+        copy(&mut content, tmp_file.as_file_mut())?;
+        let tmp_path = tmp_file.path();
+        let on_progress = |rows_read, num_rows| guard.update(rows_read, num_rows);
+        let report = match load {
+            BulkLoad::Edges(columns) => {
+                let properties = as_str_vec(&columns.properties);
+                let constant_properties = as_str_vec(&columns.constant_properties);
+                let shared = as_prop_map(columns.shared_constant_properties);
+                load_edges_from_file(
+                    &graph,
+                    tmp_path,
+                    format,
+                    &columns.time,
+                    &columns.src,
+                    &columns.dst,
+                    properties.as_deref(),
+                    constant_properties.as_deref(),
+                    shared.as_ref(),
+                    columns.layer.as_deref(),
+                    columns.layer_col.as_deref(),
+                    max_errors,
+                    on_progress,
+                )
+            }
+            BulkLoad::Nodes(columns) => {
+                let properties = as_str_vec(&columns.properties);
+                let constant_properties = as_str_vec(&columns.constant_properties);
+                let shared = as_prop_map(columns.shared_constant_properties);
+                load_nodes_from_file(
+                    &graph,
+                    tmp_path,
+                    format,
+                    &columns.time,
+                    &columns.id,
+                    columns.node_type.as_deref(),
+                    columns.node_type_col.as_deref(),
+                    properties.as_deref(),
+                    constant_properties.as_deref(),
+                    shared.as_ref(),
+                    max_errors,
+                    on_progress,
+                )
+            }
+        }?;
+        graph.write_updates()?;
+        Ok::<_, GraphError>(report)
+    })
+    .await;
+
+    let report = result.map_err(|err| GraphError::LoadFailure(err.to_string()))??;
+    Ok(GqlLoadReport { report })
+}
+
+/// Tracks the progress of a bulk load and removes it from the progress map when dropped, also if
+/// the load fails or panics
+struct ProgressGuard {
+    progress: LoadProgressMap,
+    path: PathBuf,
+}
+
+impl ProgressGuard {
+    fn start(progress: &LoadProgressMap, path: &Path) -> Result<Self, GraphError> {
+        let mut entries = progress.write();
+        if entries.contains_key(path) {
+            return Err(GraphError::LoadAlreadyRunning(path.to_path_buf()));
+        }
+        entries.insert(path.to_path_buf(), LoadProgress::default());
+        Ok(Self {
+            progress: progress.clone(),
+            path: path.to_path_buf(),
+        })
+    }
+
+    fn update(&self, rows_read: usize, num_rows: usize) {
+        if let Some(entry) = self.progress.write().get_mut(&self.path) {
+            *entry = LoadProgress {
+                rows_read,
+                num_rows,
+            };
+        }
+    }
+}
+
+impl Drop for ProgressGuard {
+    fn drop(&mut self) {
+        self.progress.write().remove(&self.path);
+    }
+}
+
+fn as_str_vec(columns: &Option<Vec<String>>) -> Option<Vec<&str>> {
+    columns
+        .as_ref()
+        .map(|cols| cols.iter().map(|c| c.as_str()).collect())
+}
+
+fn as_prop_map(properties: Option<Vec<GqlPropInput>>) -> Option<HashMap<String, Prop>> {
+    properties.map(|props| props.into_iter().map(|p| (p.key, p.value.0)).collect())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn progress_is_removed_when_the_guard_is_dropped() {
+        let progress = LoadProgressMap::default();
+        let path = Path::new("graph");
+        let guard = ProgressGuard::start(&progress, path).unwrap();
+        assert!(matches!(
+            ProgressGuard::start(&progress, path),
+            Err(GraphError::LoadAlreadyRunning(_))
+        ));
+        guard.update(1, 2);
+        assert_eq!(progress.read()[path].rows_read, 1);
+
+        drop(guard);
+        assert!(progress.read().is_empty());
+        assert!(ProgressGuard::start(&progress, path).is_ok());
+    }
+}
@@ -0,0 +1,397 @@
+#[cfg(feature = "storage")]
+use crate::query_limits::CancelOnDrop;
+use crate::{data::Data, model::graph::property::GqlPropValue};
+use dynamic_graphql::{ResolvedObject, ResolvedObjectFields};
+#[cfg(feature = "storage")]
+use moka::sync::Cache;
+use raphtory::{core::Prop, db::api::view::StaticGraphViewOps};
+#[cfg(feature = "storage")]
+use raphtory::{
+    core::{entities::LayerIds, utils::errors::GraphError},
+    db::{
+        api::{
+            storage::graph::storage_ops::GraphStorage,
+            view::internal::{
+                CoreGraphOps, EdgeFilterOps, GraphType, InternalLayerOps, InternalMaterialize,
+                NodeFilterOps, TimeSemantics,
+            },
+        },
+        task::cancellation::CancellationToken,
+    },
+    disk_graph::DiskGraphStorage,
+};
+use std::path::Path;
+#[cfg(feature = "storage")]
+use std::{path::PathBuf, sync::Arc, time::Duration};
+#[cfg(feature = "storage")]
+use tempfile::TempDir;
+
+/// Maximum number of rows returned by a cypher query if no limit is specified
+pub const DEFAULT_CYPHER_LIMIT: usize = 1000;
+/// Wall-clock timeout for a cypher query if none is specified
+pub const DEFAULT_CYPHER_TIMEOUT_MS: u64 = 30_000;
+
+#[derive(thiserror::Error, Debug)]
+pub enum CypherError {
+    #[error("Cypher queries require the server to be built with the 'storage' feature")]
+    StorageNotEnabled,
+    #[error("Cypher query timed out after {0}ms")]
+    Timeout(u64),
+    #[error("Cypher queries are not supported on persistent graphs")]
+    PersistentGraphNotSupported,
+    #[error("Cypher query was cancelled")]
+    Cancelled,
+    #[cfg(feature = "storage")]
+    #[error("Failed to execute cypher query: {0}")]
+    ExecError(#[from] raphtory_cypher::executor::ExecError),
+    #[cfg(feature = "storage")]
+    #[error(transparent)]
+    GraphError(#[from] GraphError),
+    #[cfg(feature = "storage")]
+    #[error("Failed to convert the graph for cypher: {0}")]
+    ConversionFailed(String),
+}
+
+#[derive(ResolvedObject)]
+pub(crate) struct GqlCypherColumn {
+    name: String,
+    dtype: String,
+}
+
+#[ResolvedObjectFields]
+impl GqlCypherColumn {
+    async fn name(&self) -> String {
+        self.name.clone()
+    }
+
+    /// The arrow data type of the column
+    async fn dtype(&self) -> String {
+        self.dtype.clone()
+    }
+}
+
+#[derive(ResolvedObject)]
+pub(crate) struct GqlCypherRow {
+    values: Vec<Option<Prop>>,
+}
+
+#[ResolvedObjectFields]
+impl GqlCypherRow {
+    /// Values of the row in the same order as the columns of the table (null values are returned as null)
+    async fn values(&self) -> Vec<Option<GqlPropValue>> {
+        self.values
+            .iter()
+            .map(|v| v.clone().map(GqlPropValue))
+            .collect()
+    }
+}
+
+#[derive(ResolvedObject)]
+pub(crate) struct GqlCypherTable {
+    columns: Vec<(String, String)>,
+    rows: Vec<Vec<Option<Prop>>>,
+    truncated: bool,
+}
+
+#[ResolvedObjectFields]
+impl GqlCypherTable {
+    async fn columns(&self) -> Vec<GqlCypherColumn> {
+        self.columns
+            .iter()
+            .map(|(name, dtype)| GqlCypherColumn {
+                name: name.clone(),
+                dtype: dtype.clone(),
+            })
+            .collect()
+    }
+
+    async fn rows(&self) -> Vec<GqlCypherRow> {
+        self.rows
+            .iter()
+            .map(|values| GqlCypherRow {
+                values: values.clone(),
+            })
+            .collect()
+    }
+
+    async fn count(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// True if the query produced more rows than the requested limit
+    async fn truncated(&self) -> bool {
+        self.truncated
+    }
+}
+
+#[cfg(not(feature = "storage"))]
+pub(crate) async fn run_cypher_query<G: StaticGraphViewOps>(
+    _graph: &G,
+    _path: &Path,
+    _data: &Data,
+    _query: &str,
+    _limit: usize,
+    _timeout_ms: u64,
+) -> Result<GqlCypherTable, CypherError> {
+    Err(CypherError::StorageNotEnabled)
+}
+
+/// A temporary disk graph converted from an in-memory graph to run cypher queries against
+#[cfg(feature = "storage")]
+pub(crate) struct ConvertedGraph {
+    source: GraphStorage,
+    version: usize,
+    graph: DiskGraphStorage,
+    _tmp_dir: TempDir,
+}
+
+/// Converted graphs keyed by graph path, reused as long as the graph does not change
+#[cfg(feature = "storage")]
+pub(crate) type ConvertedGraphs = Cache<PathBuf, Arc<ConvertedGraph>>;
+
+/// Run `query` against `graph` and collect at most `limit` rows.
+///
+/// Disk graphs are queried directly. In-memory graphs (and views on them) are materialised and
+/// converted to a temporary disk graph first, as the cypher engine only runs on disk storage. The
+/// conversion of an unfiltered graph is cached until the graph is mutated. The timeout includes
+/// the conversion, which is stopped at its next check point once the query times out or is dropped.
+#[cfg(feature = "storage")]
+pub(crate) async fn run_cypher_query<G: StaticGraphViewOps>(
+    graph: &G,
+    path: &Path,
+    data: &Data,
+    query: &str,
+    limit: usize,
+    timeout_ms: u64,
+) -> Result<GqlCypherTable, CypherError> {
+    if matches!(graph.graph_type(), GraphType::PersistentGraph) {
+        return Err(CypherError::PersistentGraphNotSupported);
+    }
+    let token = CancellationToken::new();
+    // the conversion runs on a blocking thread which keeps going after this future is dropped
+    let _cancel = CancelOnDrop::new(token.clone());
+    let run = async {
+        match graph.core_graph() {
+            GraphStorage::Disk(dg) => storage::collect_rows(query, dg, limit).await,
+            _ => {
+                let converted =
+                    converted_graph(graph, path, &data.cypher_graphs, token.clone()).await?;
+                storage::collect_rows(query, &converted.graph, limit).await
+            }
+        }
+    };
+    tokio::time::timeout(Duration::from_millis(timeout_ms), run)
+        .await
+        .unwrap_or(Err(CypherError::Timeout(timeout_ms)))
+}
+
+#[cfg(feature = "storage")]
+async fn converted_graph<G: StaticGraphViewOps>(
+    graph: &G,
+    path: &Path,
+    cache: &ConvertedGraphs,
+    token: CancellationToken,
+) -> Result<Arc<ConvertedGraph>, CypherError> {
+    let source = graph.core_graph().clone();
+    let version = source.version();
+    // only the full graph is cached, views would need to be part of the key
+    let cacheable = !graph.nodes_filtered()
+        && !graph.edges_filtered()
+        && graph.view_start().is_none()
+        && graph.view_end().is_none()
+        && matches!(graph.layer_ids(), LayerIds::All);
+    if cacheable {
+        if let Some(converted) = cache.get(path) {
+            if converted.source.ptr_eq(&source) && converted.version == version {
+                return Ok(converted);
+            }
+        }
+    }
+    let view = graph.clone();
+    let task_token = token.clone();
+    let (disk_graph, tmp_dir) =
+        tokio::task::spawn_blocking(move || storage::to_temp_disk_graph(&view, &task_token))
+            .await
+            .map_err(|err| CypherError::ConversionFailed(err.to_string()))??;
+    if token.is_cancelled() {
+        return Err(CypherError::Cancelled);
+    }
+    let converted = Arc::new(ConvertedGraph {
+        source,
+        version,
+        graph: disk_graph,
+        _tmp_dir: tmp_dir,
+    });
+    if cacheable {
+        cache.insert(path.to_path_buf(), converted.clone());
+    }
+    Ok(converted)
+}
+
+#[cfg(feature = "storage")]
+mod storage {
+    use super::{CancellationToken, CypherError, GqlCypherTable, GraphError};
+    use arrow_array::{
+        cast::AsArray,
+        types::{
+            Float32Type, Float64Type, Int16Type, Int32Type, Int64Type, Int8Type,
+            TimestampMicrosecondType, TimestampMillisecondType, TimestampNanosecondType,
+            TimestampSecondType, UInt16Type, UInt32Type, UInt64Type, UInt8Type,
+        },
+        Array, RecordBatch,
+    };
+    use arrow_schema::{DataType, TimeUnit};
+    use chrono::DateTime;
+    use futures_util::StreamExt;
+    use raphtory::{
+        core::{IntoPropMap, Prop},
+        db::api::view::{MaterializedGraph, StaticGraphViewOps},
+        disk_graph::DiskGraphStorage,
+        prelude::*,
+    };
+    use raphtory_cypher::{executor::ExecError, run_cypher};
+    use std::collections::HashMap;
+    use tempfile::TempDir;
+
+    /// Materialise `graph` and write it to a temporary disk graph, stopping between the steps
+    /// once `token` is cancelled (the temporary graph is removed again in that case)
+    pub(super) fn to_temp_disk_graph<G: StaticGraphViewOps>(
+        graph: &G,
+        token: &CancellationToken,
+    ) -> Result<(DiskGraphStorage, TempDir), CypherError> {
+        let check_cancelled = || {
+            if token.is_cancelled() {
+                Err(CypherError::Cancelled)
+            } else {
+                Ok(())
+            }
+        };
+        check_cancelled()?;
+        let graph = match graph.materialize()? {
+            MaterializedGraph::EventGraph(g) => g,
+            // converting to an event graph would drop the deletions
+            MaterializedGraph::PersistentGraph(_) => {
+                return Err(CypherError::PersistentGraphNotSupported)
+            }
+        };
+        check_cancelled()?;
+        let tmp_dir = tempfile::tempdir().map_err(GraphError::from)?;
+        let disk_graph = DiskGraphStorage::from_graph(&graph, tmp_dir.path())?;
+        check_cancelled()?;
+        Ok((disk_graph, tmp_dir))
+    }
+
+    pub(super) async fn collect_rows(
+        query: &str,
+        graph: &DiskGraphStorage,
+        limit: usize,
+    ) -> Result<GqlCypherTable, CypherError> {
+        let df = run_cypher(query, graph, true).await?;
+        let columns = df
+            .schema()
+            .fields()
+            .iter()
+            .map(|field| (field.name().to_string(), field.data_type().to_string()))
+            .collect();
+
+        // the engine stops after one row more than requested, which only tells us that the
+        // result is truncated
+        let df = df
+            .limit(0, Some(limit.saturating_add(1)))
+            .map_err(ExecError::from)?;
+        let mut stream = df.execute_stream().await.map_err(ExecError::from)?;
+        let mut rows = Vec::new();
+        let mut truncated = false;
+        while let Some(batch) = stream.next().await {
+            let batch = batch.map_err(ExecError::from)?;
+            let remaining = limit - rows.len();
+            if batch.num_rows() > remaining {
+                rows.extend(batch_rows(&batch, remaining));
+                truncated = true;
+                break;
+            }
+            rows.extend(batch_rows(&batch, batch.num_rows()));
+        }
+
+        Ok(GqlCypherTable {
+            columns,
+            rows,
+            truncated,
+        })
+    }
+
+    fn batch_rows(batch: &RecordBatch, num_rows: usize) -> Vec<Vec<Option<Prop>>> {
+        (0..num_rows)
+            .map(|row| {
+                batch
+                    .columns()
+                    .iter()
+                    .map(|col| arrow_value_to_prop(col.as_ref(), row))
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn arrow_value_to_prop(array: &dyn Array, idx: usize) -> Option<Prop> {
+        if array.is_null(idx) {
+            return None;
+        }
+        let prop = match array.data_type() {
+            DataType::Boolean => Prop::Bool(array.as_boolean().value(idx)),
+            DataType::Int8 => Prop::I32(array.as_primitive::<Int8Type>().value(idx) as i32),
+            DataType::Int16 => Prop::I32(array.as_primitive::<Int16Type>().value(idx) as i32),
+            DataType::Int32 => Prop::I32(array.as_primitive::<Int32Type>().value(idx)),
+            DataType::Int64 => Prop::I64(array.as_primitive::<Int64Type>().value(idx)),
+            DataType::UInt8 => Prop::U8(array.as_primitive::<UInt8Type>().value(idx)),
+            DataType::UInt16 => Prop::U16(array.as_primitive::<UInt16Type>().value(idx)),
+            DataType::UInt32 => Prop::U32(array.as_primitive::<UInt32Type>().value(idx)),
+            DataType::UInt64 => Prop::U64(array.as_primitive::<UInt64Type>().value(idx)),
+            DataType::Float32 => Prop::F32(array.as_primitive::<Float32Type>().value(idx)),
+            DataType::Float64 => Prop::F64(array.as_primitive::<Float64Type>().value(idx)),
+            DataType::Utf8 => Prop::str(array.as_string::<i32>().value(idx)),
+            DataType::LargeUtf8 => Prop::str(array.as_string::<i64>().value(idx)),
+            DataType::Timestamp(unit, _) => {
+                let millis = match unit {
+                    TimeUnit::Second => {
+                        array.as_primitive::<TimestampSecondType>().value(idx) * 1000
+                    }
+                    TimeUnit::Millisecond => {
+                        array.as_primitive::<TimestampMillisecondType>().value(idx)
+                    }
+                    TimeUnit::Microsecond => {
+                        array.as_primitive::<TimestampMicrosecondType>().value(idx) / 1000
+                    }
+                    TimeUnit::Nanosecond => {
+                        array.as_primitive::<TimestampNanosecondType>().value(idx) / 1_000_000
+                    }
+                };
+                DateTime::from_timestamp_millis(millis).map(Prop::DTime)?
+            }
+            DataType::List(_) => list_to_prop(array.as_list::<i32>().value(idx).as_ref()),
+            DataType::LargeList(_) => list_to_prop(array.as_list::<i64>().value(idx).as_ref()),
+            DataType::Struct(fields) => {
+                let array = array.as_struct();
+                let values: HashMap<String, Prop> = fields
+                    .iter()
+                    .zip(array.columns())
+                    .filter_map(|(field, col)| {
+                        arrow_value_to_prop(col.as_ref(), idx)
+                            .map(|value| (field.name().to_string(), value))
+                    })
+                    .collect();
+                values.into_prop_map()
+            }
+            _ => Prop::str(format!("{:?}", array.slice(idx, 1))),
+        };
+        Some(prop)
+    }
+
+    fn list_to_prop(values: &dyn Array) -> Prop {
+        Prop::List(
+            (0..values.len())
+                .filter_map(|i| arrow_value_to_prop(values, i))
+                .collect::<Vec<_>>()
+                .into(),
+        )
+    }
+}
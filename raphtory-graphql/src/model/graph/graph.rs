@@ -3,23 +3,35 @@ use crate::{
     model::{
         algorithms::graph_algorithms::GraphAlgorithms,
         graph::{
-            edge::Edge, edges::GqlEdges, node::Node, nodes::GqlNodes, property::GqlProperties,
+            cypher::{
+                run_cypher_query, CypherError, GqlCypherTable, DEFAULT_CYPHER_LIMIT,
+                DEFAULT_CYPHER_TIMEOUT_MS,
+            },
+            edge::Edge,
+            edges::GqlEdges,
+            node::Node,
+            nodes::GqlNodes,
+            property::GqlProperties,
+            windowset::GqlWindowSet,
         },
         schema::graph_schema::GraphSchema,
     },
 };
 use async_graphql::{Context, DataContext};
-use dynamic_graphql::{ResolvedObject, ResolvedObjectFields};
+use dynamic_graphql::{Enum, ResolvedObject, ResolvedObjectFields};
 use itertools::Itertools;
 use raphtory::{
     core::{
         entities::nodes::node_ref::{AsNodeRef, NodeRef},
-        utils::errors::{GraphError, InvalidPathReason::PathNotParsable},
+        utils::{
+            errors::{GraphError, InvalidPathReason::PathNotParsable},
+            time::{error::ParseTimeError, Interval},
+        },
     },
     db::{
         api::{
-            properties::dyn_props::DynProperties,
-            view::{DynamicGraph, NodeViewOps, TimeOps},
+            properties::{computed::PropExpr, dyn_props::DynProperties},
+            view::{Alignment, ComputedPropertiesOps, DynamicGraph, NodeViewOps, TimeOps},
         },
         graph::node::NodeView,
     },
@@ -28,6 +40,37 @@ use raphtory::{
 };
 use std::{collections::HashSet, convert::Into, path::PathBuf, sync::Arc};
 
+#[derive(Enum, Clone, Copy)]
+pub enum WindowAlignment {
+    /// The first window starts at the start of the graph
+    Unaligned,
+    /// Windows are aligned to the calendar, e.g., days to midnight and weeks to Mondays
+    Calendar,
+}
+
+/// An interval given as a number of milliseconds or a string like "1 day"
+struct GqlInterval<'a>(&'a str);
+
+impl TryFrom<GqlInterval<'_>> for Interval {
+    type Error = ParseTimeError;
+
+    fn try_from(value: GqlInterval<'_>) -> Result<Self, Self::Error> {
+        match value.0.parse::<u64>() {
+            Ok(millis) => millis.try_into(),
+            Err(_) => value.0.try_into(),
+        }
+    }
+}
+
+fn window_alignment(alignment: Option<WindowAlignment>, anchor: Option<i64>) -> Option<Alignment> {
+    match (anchor, alignment) {
+        (Some(anchor), _) => Some(Alignment::Anchor(anchor)),
+        (None, Some(WindowAlignment::Calendar)) => Some(Alignment::Calendar),
+        (None, Some(WindowAlignment::Unaligned)) => Some(Alignment::Unaligned),
+        (None, None) => None,
+    }
+}
+
 #[derive(ResolvedObject)]
 pub(crate) struct GqlGraph {
     path: PathBuf,
@@ -116,6 +159,40 @@ impl GqlGraph {
         GqlGraph::new(self.path.clone(), self.graph.after(time))
     }
 
+    /// Split the graph into windows of size `window` that move forward by `step` (defaults to
+    /// `window`). Intervals are numbers of milliseconds or strings like "1 day", and are measured
+    /// in the wall clock time of `timezone` (e.g., "Europe/London") if set. Windows start at the
+    /// start of the graph unless aligned to the calendar or to the time `anchor`.
+    async fn rolling(
+        &self,
+        window: String,
+        step: Option<String>,
+        timezone: Option<String>,
+        alignment: Option<WindowAlignment>,
+        anchor: Option<i64>,
+    ) -> Result<GqlWindowSet, GraphError> {
+        let step = step.as_deref().map(GqlInterval);
+        let windows = self.graph.rolling(GqlInterval(&window), step)?;
+        let windows =
+            windows.with_options(timezone.as_deref(), window_alignment(alignment, anchor))?;
+        Ok(GqlWindowSet::new(self.path.clone(), windows))
+    }
+
+    /// Split the graph into windows that all start at the start of the graph and end `step`
+    /// further each time, see `rolling` for the other arguments
+    async fn expanding(
+        &self,
+        step: String,
+        timezone: Option<String>,
+        alignment: Option<WindowAlignment>,
+        anchor: Option<i64>,
+    ) -> Result<GqlWindowSet, GraphError> {
+        let windows = self.graph.expanding(GqlInterval(&step))?;
+        let windows =
+            windows.with_options(timezone.as_deref(), window_alignment(alignment, anchor))?;
+        Ok(GqlWindowSet::new(self.path.clone(), windows))
+    }
+
     async fn shrink_window(&self, start: i64, end: i64) -> Self {
         GqlGraph::new(self.path.clone(), self.graph.shrink_window(start, end))
     }
@@ -128,6 +205,26 @@ impl GqlGraph {
         GqlGraph::new(self.path.clone(), self.graph.shrink_end(end))
     }
 
+    /// Return a graph with an additional computed node property `name` that evaluates the
+    /// arithmetic expression `expr` over the properties of each node, e.g., "amount * fx_rate"
+    async fn with_node_property(&self, name: String, expr: String) -> Result<GqlGraph, GraphError> {
+        let expr: PropExpr = expr.parse()?;
+        Ok(GqlGraph::new(
+            self.path.clone(),
+            self.graph.with_node_property(&name, expr)?,
+        ))
+    }
+
+    /// Return a graph with an additional computed edge property `name` that evaluates the
+    /// arithmetic expression `expr` over the properties of each edge, e.g., "amount * fx_rate"
+    async fn with_edge_property(&self, name: String, expr: String) -> Result<GqlGraph, GraphError> {
+        let expr: PropExpr = expr.parse()?;
+        Ok(GqlGraph::new(
+            self.path.clone(),
+            self.graph.with_edge_property(&name, expr)?,
+        ))
+    }
+
     ////////////////////////
     //// TIME QUERIES //////
     ////////////////////////
@@ -305,6 +402,29 @@ impl GqlGraph {
             .collect()
     }
 
+    /// Run a cypher query against this graph and return the result as a table
+    ///
+    /// At most `limit` rows are returned (defaults to 1000) and the query is aborted if it takes
+    /// longer than `timeout_ms` milliseconds (defaults to 30 seconds).
+    async fn cypher(
+        &self,
+        ctx: &Context<'_>,
+        query: String,
+        limit: Option<usize>,
+        timeout_ms: Option<u64>,
+    ) -> Result<GqlCypherTable, CypherError> {
+        let data = ctx.data_unchecked::<Data>();
+        run_cypher_query(
+            &self.graph,
+            &self.path,
+            data,
+            &query,
+            limit.unwrap_or(DEFAULT_CYPHER_LIMIT),
+            timeout_ms.unwrap_or(DEFAULT_CYPHER_TIMEOUT_MS),
+        )
+        .await
+    }
+
     ////////////////////////
     /////// PROPERTIES /////
     ////////////////////////
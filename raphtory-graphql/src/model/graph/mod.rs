@@ -1,3 +1,5 @@
+pub(crate) mod bulk_load;
+pub(crate) mod cypher;
 pub(crate) mod edge;
 mod edges;
 pub(crate) mod graph;
@@ -8,3 +10,5 @@ mod nodes;
 mod path_from_node;
 pub(crate) mod property;
 pub(crate) mod vectorised_graph;
+pub(crate) mod versions;
+mod windowset;
@@ -1,5 +1,18 @@
-use crate::model::graph::{edge::Edge, graph::GqlGraph, node::Node, property::GqlPropValue};
-use dynamic_graphql::{InputObject, ResolvedObject, ResolvedObjectFields};
+use crate::{
+    data::Data,
+    model::graph::{
+        bulk_load::{
+            run_bulk_load, BulkLoad, EdgeFileColumns, GqlDataFileFormat, GqlLoadReport,
+            NodeFileColumns,
+        },
+        edge::Edge,
+        graph::GqlGraph,
+        node::Node,
+        property::GqlPropValue,
+    },
+};
+use async_graphql::Context;
+use dynamic_graphql::{InputObject, ResolvedObject, ResolvedObjectFields, Result, Upload};
 use raphtory::{
     core::utils::errors::GraphError,
     db::{
@@ -14,8 +27,8 @@ use std::path::PathBuf;
 
 #[derive(InputObject)]
 pub struct GqlPropInput {
-    key: String,
-    value: GqlPropValue,
+    pub(crate) key: String,
+    pub(crate) value: GqlPropValue,
 }
 
 #[derive(InputObject)]
@@ -56,6 +69,33 @@ impl GqlMutableGraph {
     }
 }
 
+impl GqlMutableGraph {
+    async fn bulk_load(
+        &self,
+        ctx: &Context<'_>,
+        file: Upload,
+        format: Option<GqlDataFileFormat>,
+        load: BulkLoad,
+        max_errors: Option<usize>,
+    ) -> Result<GqlLoadReport> {
+        if self.graph.graph.storage().is_immutable() {
+            return Err(GraphError::ImmutableDiskGraph.into());
+        }
+        let data = ctx.data_unchecked::<Data>();
+        let upload = file.value(ctx)?;
+        Ok(run_bulk_load(
+            &self.path,
+            &self.graph,
+            upload,
+            format,
+            load,
+            max_errors,
+            &data.load_progress,
+        )
+        .await?)
+    }
+}
+
 fn as_properties(properties: Vec<GqlPropInput>) -> impl Iterator<Item = (String, Prop)> {
     properties.into_iter().map(|p| (p.key, p.value.0))
 }
@@ -169,6 +209,38 @@ impl GqlMutableGraph {
         Ok(true)
     }
 
+    /// Bulk load edges from an uploaded Parquet or Arrow IPC file
+    ///
+    /// The format is inferred from the file extension if not given. Rows that fail to load are
+    /// skipped and reported (up to `maxErrors` of them).
+    async fn load_edges_from_file<'a>(
+        &self,
+        ctx: &Context<'a>,
+        file: Upload,
+        columns: EdgeFileColumns,
+        format: Option<GqlDataFileFormat>,
+        max_errors: Option<usize>,
+    ) -> Result<GqlLoadReport> {
+        self.bulk_load(ctx, file, format, BulkLoad::Edges(columns), max_errors)
+            .await
+    }
+
+    /// Bulk load nodes from an uploaded Parquet or Arrow IPC file
+    ///
+    /// The format is inferred from the file extension if not given. Rows that fail to load are
+    /// skipped and reported (up to `maxErrors` of them).
+    async fn load_nodes_from_file<'a>(
+        &self,
+        ctx: &Context<'a>,
+        file: Upload,
+        columns: NodeFileColumns,
+        format: Option<GqlDataFileFormat>,
+        max_errors: Option<usize>,
+    ) -> Result<GqlLoadReport> {
+        self.bulk_load(ctx, file, format, BulkLoad::Nodes(columns), max_errors)
+            .await
+    }
+
     /// Mark an edge as deleted (creates the edge if it did not exist)
     async fn delete_edge(
         &self,
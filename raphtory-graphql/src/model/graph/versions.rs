@@ -0,0 +1,313 @@
+//! Named snapshots of the graphs served from the work dir.
+//!
+//! Graph files are snapshots that are only ever replaced as a whole plus a write-ahead log that is
+//! only appended to (see `CacheOps`), so a snapshot hard links both into
+//! `<work_dir>/.versions/<graph path>.snapshots/` and records the length of the log at the time it
+//! was taken. Replaying that prefix of the linked log on top of the linked snapshot reproduces the
+//! graph as it was, even after later checkpoints replaced the files of the graph. The snapshots of
+//! a graph are stored as JSON in `<work_dir>/.versions/<graph path>.json`.
+
+use crate::{data::Data, model::create_dirs_if_not_present};
+use chrono::Utc;
+use dynamic_graphql::SimpleObject;
+use parking_lot::{lock_api::ArcMutexGuard, Mutex, RawMutex};
+use raphtory::{
+    core::utils::errors::GraphError,
+    db::api::view::MaterializedGraph,
+    prelude::*,
+    search::IndexedGraph,
+    serialise::{restore_cache, wal_path, CacheOps},
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fs::{self, File},
+    io,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+/// Name of the directory (inside the work dir) holding the version history of the graphs
+pub(crate) const VERSIONS_DIR: &str = ".versions";
+
+/// Locks serialising the changes to the version history of each graph, keyed by graph path
+pub(crate) type VersionLocks = Arc<Mutex<HashMap<PathBuf, Arc<Mutex<()>>>>>;
+
+#[derive(thiserror::Error, Debug)]
+pub enum VersionError {
+    #[error("Version '{0}' does not exist")]
+    VersionNotFound(String),
+    #[error("Version '{0}' already exists")]
+    VersionAlreadyExists(String),
+    #[error("Disk graphs are immutable and cannot be versioned")]
+    ImmutableDiskGraph,
+    #[error("Failed to read the version history: {0}")]
+    InvalidHistory(#[from] serde_json::Error),
+    #[error(transparent)]
+    GraphError(#[from] GraphError),
+    #[error(transparent)]
+    CachedGraphError(#[from] Arc<GraphError>),
+}
+
+impl From<io::Error> for VersionError {
+    fn from(error: io::Error) -> Self {
+        VersionError::GraphError(error.into())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize, SimpleObject)]
+pub struct GraphVersion {
+    name: String,
+    description: Option<String>,
+    /// Creation time of the snapshot in milliseconds since the epoch
+    created: i64,
+    /// Name of the links to the graph files in the snapshot dir
+    #[graphql(skip)]
+    file: String,
+    /// Length of the write-ahead log of the graph when the snapshot was taken
+    log_offset: u64,
+    num_nodes: usize,
+    num_edges: usize,
+}
+
+fn versions_path(data: &Data, path: &Path, extension: &str) -> Result<PathBuf, VersionError> {
+    // validate the path the same way as for the graph itself
+    data.construct_graph_full_path(path)
+        .map_err(GraphError::from)?;
+    let mut versions_path = data.work_dir.join(VERSIONS_DIR).join(path).into_os_string();
+    versions_path.push(extension);
+    Ok(versions_path.into())
+}
+
+fn history_path(data: &Data, path: &Path) -> Result<PathBuf, VersionError> {
+    versions_path(data, path, ".json")
+}
+
+fn snapshot_dir(data: &Data, path: &Path) -> Result<PathBuf, VersionError> {
+    versions_path(data, path, ".snapshots")
+}
+
+/// Lock the version history of the graph at `path` until the guard is dropped, every read-modify-write
+/// of the history needs to hold it
+fn lock_history(data: &Data, path: &Path) -> ArcMutexGuard<RawMutex, ()> {
+    let lock = data
+        .version_locks
+        .lock()
+        .entry(path.to_path_buf())
+        .or_default()
+        .clone();
+    lock.lock_arc()
+}
+
+fn remove_if_exists(path: &Path) -> Result<(), VersionError> {
+    let result = if path.is_dir() {
+        fs::remove_dir_all(path)
+    } else {
+        fs::remove_file(path)
+    };
+    match result {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err.into()),
+        _ => Ok(()),
+    }
+}
+
+fn remove_snapshot_files(snapshot_dir: &Path, version: &GraphVersion) -> Result<(), VersionError> {
+    let snapshot_path = snapshot_dir.join(&version.file);
+    remove_if_exists(&wal_path(&snapshot_path))?;
+    remove_if_exists(&snapshot_path)
+}
+
+fn read_history(history_path: &Path) -> Result<Vec<GraphVersion>, VersionError> {
+    if !history_path.exists() {
+        return Ok(vec![]);
+    }
+    Ok(serde_json::from_reader(File::open(history_path)?)?)
+}
+
+fn write_history(history_path: &Path, versions: &[GraphVersion]) -> Result<(), VersionError> {
+    create_dirs_if_not_present(history_path)?;
+    // write to a temporary file first so a crash never leaves a partial history behind
+    let mut tmp_path = history_path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    serde_json::to_writer(File::create(&tmp_path)?, versions)?;
+    fs::rename(&tmp_path, history_path)?;
+    Ok(())
+}
+
+fn find_version(versions: &[GraphVersion], name: &str) -> Result<GraphVersion, VersionError> {
+    versions
+        .iter()
+        .find(|v| v.name == name)
+        .cloned()
+        .ok_or_else(|| VersionError::VersionNotFound(name.to_owned()))
+}
+
+fn graph_file(data: &Data, path: &Path) -> Result<PathBuf, VersionError> {
+    let full_path = data
+        .construct_graph_full_path(path)
+        .map_err(GraphError::from)?;
+    if !full_path.exists() {
+        return Err(GraphError::GraphNotFound(path.to_path_buf()).into());
+    }
+    if full_path.is_dir() {
+        return Err(VersionError::ImmutableDiskGraph);
+    }
+    Ok(full_path)
+}
+
+/// Flush the pending updates of the graph at `path` and record the current state as `name`
+pub(crate) fn create_snapshot(
+    data: &Data,
+    path: &Path,
+    name: String,
+    description: Option<String>,
+) -> Result<GraphVersion, VersionError> {
+    graph_file(data, path)?;
+    let history_path = history_path(data, path)?;
+    let _lock = lock_history(data, path);
+    let mut versions = read_history(&history_path)?;
+    if versions.iter().any(|v| v.name == name) {
+        return Err(VersionError::VersionAlreadyExists(name));
+    }
+
+    let graph = data.get_graph(path)?;
+    let created = Utc::now().timestamp_millis();
+    let file = format!("{created}-{}", versions.len());
+    let snapshot_dir = snapshot_dir(data, path)?;
+    fs::create_dir_all(&snapshot_dir)?;
+    let version = GraphVersion {
+        name,
+        description,
+        created,
+        log_offset: graph.link_cache(snapshot_dir.join(&file))?,
+        file,
+        num_nodes: graph.count_nodes(),
+        num_edges: graph.count_edges(),
+    };
+    versions.push(version.clone());
+    write_history(&history_path, &versions)?;
+    Ok(version)
+}
+
+/// The snapshots of the graph at `path`, oldest first
+pub(crate) fn list_versions(data: &Data, path: &Path) -> Result<Vec<GraphVersion>, VersionError> {
+    read_history(&history_path(data, path)?)
+}
+
+/// Load the snapshot `name` of the graph at `path`
+pub(crate) fn get_version(
+    data: &Data,
+    path: &Path,
+    name: &str,
+) -> Result<IndexedGraph<MaterializedGraph>, VersionError> {
+    graph_file(data, path)?;
+    let version = find_version(&list_versions(data, path)?, name)?;
+    let snapshot_path = snapshot_dir(data, path)?.join(version.file);
+    let graph = data
+        .snapshots
+        .try_get_with((path.to_path_buf(), name.to_owned()), || {
+            let graph = MaterializedGraph::load_linked_cache(&snapshot_path, version.log_offset)?;
+            Ok::<_, GraphError>(IndexedGraph::from_graph(&graph)?)
+        })?;
+    Ok(graph)
+}
+
+/// Restore the graph at `path` to the snapshot `name`.
+///
+/// All updates made after the snapshot are discarded, as are the snapshots taken after it.
+pub(crate) fn rollback(data: &Data, path: &Path, name: &str) -> Result<(), VersionError> {
+    let full_path = graph_file(data, path)?;
+    let history_path = history_path(data, path)?;
+    let _lock = lock_history(data, path);
+    let mut versions = read_history(&history_path)?;
+    let position = versions
+        .iter()
+        .position(|v| v.name == name)
+        .ok_or_else(|| VersionError::VersionNotFound(name.to_owned()))?;
+    let snapshot_dir = snapshot_dir(data, path)?;
+
+    // evicting the graph flushes its pending updates, they are discarded with the rest of the log
+    data.graphs.remove(&path.to_path_buf());
+    let version = &versions[position];
+    restore_cache(
+        snapshot_dir.join(&version.file),
+        version.log_offset,
+        &full_path,
+    )?;
+
+    // restoring truncates the linked log, the later snapshots may share it and are invalid now
+    for discarded in versions.split_off(position + 1) {
+        data.snapshots.remove(&(path.to_path_buf(), discarded.name));
+        remove_snapshot_files(&snapshot_dir, &discarded)?;
+    }
+    write_history(&history_path, &versions)
+}
+
+/// Delete the snapshot `name` of the graph at `path`, the graph itself is not modified
+pub(crate) fn delete_version(data: &Data, path: &Path, name: &str) -> Result<(), VersionError> {
+    let history_path = history_path(data, path)?;
+    let _lock = lock_history(data, path);
+    let mut versions = read_history(&history_path)?;
+    let version = find_version(&versions, name)?;
+    versions.retain(|v| v.name != name);
+    data.snapshots
+        .remove(&(path.to_path_buf(), name.to_owned()));
+    write_history(&history_path, &versions)?;
+    remove_snapshot_files(&snapshot_dir(data, path)?, &version)
+}
+
+/// Drop the version history of the graph at `path`.
+///
+/// This needs to be called whenever the graph at `path` is replaced by a different graph, as the
+/// snapshots no longer belong to it.
+pub(crate) fn clear_versions(data: &Data, path: &Path) -> Result<(), VersionError> {
+    let history_path = history_path(data, path)?;
+    let _lock = lock_history(data, path);
+    for version in read_history(&history_path)? {
+        data.snapshots.remove(&(path.to_path_buf(), version.name));
+    }
+    remove_if_exists(&snapshot_dir(data, path)?)?;
+    remove_if_exists(&history_path)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{data::data_tests::save_graphs_to_work_dir, server_config::AppConfig};
+    use std::collections::HashSet;
+    use tempfile::tempdir;
+
+    #[test]
+    fn concurrent_snapshots_are_all_recorded() {
+        let graph = Graph::new();
+        graph.add_edge(0, 1, 2, NO_PROPS, None).unwrap();
+        let graphs = HashMap::from([("test".to_string(), graph.into())]);
+        let tmp_dir = tempdir().unwrap();
+        save_graphs_to_work_dir(tmp_dir.path(), &graphs).unwrap();
+        let data = Data::new(tmp_dir.path(), &AppConfig::default());
+        let path = Path::new("test");
+
+        std::thread::scope(|s| {
+            for i in 0..8 {
+                let data = &data;
+                s.spawn(move || create_snapshot(data, path, format!("v{i}"), None).unwrap());
+            }
+        });
+
+        let versions = list_versions(&data, path).unwrap();
+        let names: HashSet<_> = versions.iter().map(|v| v.name.clone()).collect();
+        assert_eq!(names, (0..8).map(|i| format!("v{i}")).collect());
+
+        // every linked file belongs to a recorded snapshot
+        let files: HashSet<_> = versions.iter().map(|v| v.file.clone()).collect();
+        for entry in fs::read_dir(snapshot_dir(&data, path).unwrap()).unwrap() {
+            let name = entry.unwrap().file_name().into_string().unwrap();
+            // the log of a snapshot is linked as `.<file>.wal`
+            let file = name
+                .strip_prefix('.')
+                .and_then(|name| name.strip_suffix(".wal"))
+                .unwrap_or(&name);
+            assert!(files.contains(file), "orphaned snapshot file {name}");
+        }
+    }
+}
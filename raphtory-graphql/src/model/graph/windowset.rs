@@ -0,0 +1,44 @@
+use crate::model::graph::graph::GqlGraph;
+use dynamic_graphql::{ResolvedObject, ResolvedObjectFields};
+use raphtory::{
+    db::api::view::{DynamicGraph, WindowSet},
+    search::IndexedGraph,
+};
+use std::path::PathBuf;
+
+#[derive(ResolvedObject)]
+pub(crate) struct GqlWindowSet {
+    path: PathBuf,
+    windows: WindowSet<'static, IndexedGraph<DynamicGraph>>,
+}
+
+impl GqlWindowSet {
+    pub(crate) fn new(
+        path: PathBuf,
+        windows: WindowSet<'static, IndexedGraph<DynamicGraph>>,
+    ) -> Self {
+        Self { path, windows }
+    }
+
+    fn iter(&self) -> impl Iterator<Item = GqlGraph> + '_ {
+        self.windows
+            .clone()
+            .map(|g| GqlGraph::new(self.path.clone(), g))
+    }
+}
+
+#[ResolvedObjectFields]
+impl GqlWindowSet {
+    async fn count(&self) -> usize {
+        self.windows.clone().count()
+    }
+
+    async fn page(&self, limit: usize, offset: usize) -> Vec<GqlGraph> {
+        let start = offset * limit;
+        self.iter().skip(start).take(limit).collect()
+    }
+
+    async fn list(&self) -> Vec<GqlGraph> {
+        self.iter().collect()
+    }
+}
@@ -3,8 +3,12 @@ use crate::{
     model::{
         algorithms::global_plugins::GlobalPlugins,
         graph::{
-            graph::GqlGraph, graphs::GqlGraphs, mutable_graph::GqlMutableGraph,
+            bulk_load::LoadProgress,
+            graph::GqlGraph,
+            graphs::GqlGraphs,
+            mutable_graph::GqlMutableGraph,
             vectorised_graph::GqlVectorisedGraph,
+            versions::{self, GraphVersion, VersionError},
         },
     },
     url_encode::{url_decode_graph, url_encode_graph},
@@ -22,6 +26,7 @@ use raphtory::{
     core::{utils::errors::GraphError, Prop},
     db::api::view::MaterializedGraph,
     prelude::*,
+    serialise::wal_path,
 };
 use raphtory_api::core::storage::arc_str::ArcStr;
 use std::{
@@ -88,6 +93,27 @@ impl QueryRoot {
             .map(|g| GqlGraph::new(path.to_path_buf(), g))?)
     }
 
+    /// Snapshots of the graph at `path`, oldest first
+    async fn graph_versions<'a>(
+        ctx: &Context<'a>,
+        path: String,
+    ) -> Result<Vec<GraphVersion>, VersionError> {
+        let data = ctx.data_unchecked::<Data>();
+        versions::list_versions(data, Path::new(&path))
+    }
+
+    /// Returns the snapshot `name` of a graph (read-only)
+    async fn graph_version<'a>(
+        ctx: &Context<'a>,
+        path: String,
+        name: String,
+    ) -> Result<GqlGraph, VersionError> {
+        let path = Path::new(&path);
+        let data = ctx.data_unchecked::<Data>();
+        let graph = versions::get_version(data, path, &name)?;
+        Ok(GqlGraph::new(path.to_path_buf(), graph))
+    }
+
     async fn update_graph<'a>(ctx: &Context<'a>, path: String) -> Result<GqlMutableGraph> {
         let data = ctx.data_unchecked::<Data>();
         let graph = data
@@ -96,6 +122,12 @@ impl QueryRoot {
         Ok(graph)
     }
 
+    /// Progress of the bulk load currently running into the graph at `path` (if any)
+    async fn load_progress<'a>(ctx: &Context<'a>, path: String) -> Option<LoadProgress> {
+        let data = ctx.data_unchecked::<Data>();
+        data.load_progress.read().get(Path::new(&path)).copied()
+    }
+
     async fn vectorised_graph<'a>(ctx: &Context<'a>, path: String) -> Option<GqlVectorisedGraph> {
         let data = ctx.data_unchecked::<Data>();
         let g = data
@@ -145,8 +177,10 @@ impl Mut {
             return Err(GraphError::GraphNotFound(path.to_path_buf()).into());
         }
 
-        delete_graph(&full_path)?;
+        // evict first, flushing the pending updates must not recreate the deleted files
         data.graphs.remove(&path.to_path_buf());
+        delete_graph(&full_path)?;
+        versions::clear_versions(data, path)?;
         Ok(true)
     }
 
@@ -191,8 +225,9 @@ impl Mut {
             create_dirs_if_not_present(&new_full_path)?;
             graph.graph.encode(&new_full_path)?;
 
-            delete_graph(&full_path)?;
             data.graphs.remove(&path.to_path_buf());
+            delete_graph(&full_path)?;
+            versions::clear_versions(data, path)?;
         }
 
         Ok(true)
@@ -382,6 +417,7 @@ impl Mut {
         new_subgraph.update_constant_properties([("isArchive", Prop::U8(is_archive))])?;
 
         new_subgraph.cache(new_graph_full_path)?;
+        versions::clear_versions(data, new_graph_path)?;
 
         data.graphs.remove(&graph_path.to_path_buf());
         data.graphs
@@ -430,6 +466,7 @@ impl Mut {
         let mut out_file = File::create(&full_path)?;
         copy(&mut in_file, &mut out_file)?;
         let g = MaterializedGraph::load_cached(&full_path)?;
+        versions::clear_versions(data, path)?;
         data.graphs.insert(path.to_path_buf(), g.into());
         Ok(path.display().to_string())
     }
@@ -453,10 +490,44 @@ impl Mut {
         let g: MaterializedGraph = url_decode_graph(graph)?;
         create_dirs_if_not_present(&full_path)?;
         g.cache(&full_path)?;
+        versions::clear_versions(data, path)?;
         data.graphs.insert(path.to_path_buf(), g.into());
         Ok(path.display().to_string())
     }
 
+    /// Record the current state of the graph at `path` as the snapshot `name`
+    async fn create_snapshot<'a>(
+        ctx: &Context<'a>,
+        path: String,
+        name: String,
+        description: Option<String>,
+    ) -> Result<GraphVersion, VersionError> {
+        let data = ctx.data_unchecked::<Data>();
+        versions::create_snapshot(data, Path::new(&path), name, description)
+    }
+
+    /// Restore the graph at `path` to the snapshot `name`, discarding all later updates and snapshots
+    async fn rollback_graph<'a>(
+        ctx: &Context<'a>,
+        path: String,
+        name: String,
+    ) -> Result<bool, VersionError> {
+        let data = ctx.data_unchecked::<Data>();
+        versions::rollback(data, Path::new(&path), &name)?;
+        Ok(true)
+    }
+
+    /// Delete the snapshot `name` of the graph at `path` (the graph itself is unchanged)
+    async fn delete_snapshot<'a>(
+        ctx: &Context<'a>,
+        path: String,
+        name: String,
+    ) -> Result<bool, VersionError> {
+        let data = ctx.data_unchecked::<Data>();
+        versions::delete_version(data, Path::new(&path), &name)?;
+        Ok(true)
+    }
+
     async fn archive_graph<'a>(ctx: &Context<'a>, path: String, is_archive: u8) -> Result<bool> {
         let path = Path::new(&path);
         let data = ctx.data_unchecked::<Data>();
@@ -487,6 +558,10 @@ pub struct App(QueryRoot, MutRoot, Mut);
 fn delete_graph(path: &Path) -> Result<()> {
     if path.is_file() {
         fs::remove_file(path)?;
+        let wal = wal_path(path);
+        if wal.exists() {
+            fs::remove_file(wal)?;
+        }
     } else if path.is_dir() {
         fs::remove_dir_all(path)?;
     } else {
@@ -0,0 +1,318 @@
+use async_graphql::{
+    extensions::{Extension, ExtensionContext, ExtensionFactory, NextExecute, NextParseQuery},
+    parser::types::{ExecutableDocument, OperationType},
+    Response, ServerResult, Variables,
+};
+use moka::{notification::RemovalCause, sync::Cache};
+use parking_lot::Mutex;
+use poem::{handler, http::StatusCode, web::Data, IntoResponse};
+use prometheus::{
+    Encoder, HistogramOpts, HistogramVec, IntCounter, IntCounterVec, IntGauge, IntGaugeVec, Opts,
+    Registry, TextEncoder,
+};
+use raphtory::{
+    db::api::{
+        storage::graph::storage_ops::GraphStorage,
+        view::{internal::CoreGraphOps, MaterializedGraph},
+    },
+    prelude::*,
+    search::IndexedGraph,
+};
+use std::{
+    collections::HashMap,
+    path::{Path, PathBuf},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
+
+/// Operation label used for requests without an operation name
+pub(crate) const ANONYMOUS_OPERATION: &str = "anonymous";
+
+/// Prometheus metrics of the server, exposed on `/metrics`
+pub(crate) struct Metrics {
+    registry: Registry,
+    pub(crate) requests: IntCounterVec,
+    pub(crate) request_duration: HistogramVec,
+    pub(crate) resolver_errors: IntCounterVec,
+    pub(crate) cache_hits: IntCounter,
+    pub(crate) cache_misses: IntCounter,
+    pub(crate) cache_evictions: IntCounterVec,
+    pub(crate) cache_entries: IntGauge,
+    pub(crate) graph_nodes: IntGaugeVec,
+    pub(crate) graph_edges: IntGaugeVec,
+    pub(crate) write_back_failures: IntCounter,
+    /// Each cached graph together with its version when its gauges were last updated
+    recorded_graphs: Mutex<HashMap<PathBuf, (GraphStorage, usize)>>,
+}
+
+impl Default for Metrics {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Metrics {
+    pub(crate) fn new() -> Self {
+        let registry = Registry::new();
+        let requests = IntCounterVec::new(
+            Opts::new(
+                "raphtory_graphql_requests_total",
+                "Number of GraphQL requests by operation",
+            ),
+            &["operation"],
+        )
+        .unwrap();
+        let request_duration = HistogramVec::new(
+            HistogramOpts::new(
+                "raphtory_graphql_request_duration_seconds",
+                "Latency of GraphQL requests by operation",
+            ),
+            &["operation"],
+        )
+        .unwrap();
+        let resolver_errors = IntCounterVec::new(
+            Opts::new(
+                "raphtory_graphql_resolver_errors_total",
+                "Number of errors returned by GraphQL requests by operation",
+            ),
+            &["operation"],
+        )
+        .unwrap();
+        let cache_hits = IntCounter::new(
+            "raphtory_graph_cache_hits_total",
+            "Number of graph lookups served from the cache",
+        )
+        .unwrap();
+        let cache_misses = IntCounter::new(
+            "raphtory_graph_cache_misses_total",
+            "Number of graph lookups that had to load the graph from disk",
+        )
+        .unwrap();
+        let cache_evictions = IntCounterVec::new(
+            Opts::new(
+                "raphtory_graph_cache_evictions_total",
+                "Number of graphs removed from the cache by cause",
+            ),
+            &["cause"],
+        )
+        .unwrap();
+        let cache_entries = IntGauge::new(
+            "raphtory_graph_cache_entries",
+            "Number of graphs currently in the cache",
+        )
+        .unwrap();
+        let graph_nodes = IntGaugeVec::new(
+            Opts::new(
+                "raphtory_graph_nodes",
+                "Number of nodes of the cached graphs",
+            ),
+            &["graph"],
+        )
+        .unwrap();
+        let graph_edges = IntGaugeVec::new(
+            Opts::new(
+                "raphtory_graph_edges",
+                "Number of edges of the cached graphs",
+            ),
+            &["graph"],
+        )
+        .unwrap();
+        let write_back_failures = IntCounter::new(
+            "raphtory_graph_write_back_failures_total",
+            "Number of cached graphs that failed to write their updates to disk on eviction",
+        )
+        .unwrap();
+
+        registry.register(Box::new(requests.clone())).unwrap();
+        registry
+            .register(Box::new(request_duration.clone()))
+            .unwrap();
+        registry
+            .register(Box::new(resolver_errors.clone()))
+            .unwrap();
+        registry.register(Box::new(cache_hits.clone())).unwrap();
+        registry.register(Box::new(cache_misses.clone())).unwrap();
+        registry
+            .register(Box::new(cache_evictions.clone()))
+            .unwrap();
+        registry.register(Box::new(cache_entries.clone())).unwrap();
+        registry.register(Box::new(graph_nodes.clone())).unwrap();
+        registry.register(Box::new(graph_edges.clone())).unwrap();
+        registry
+            .register(Box::new(write_back_failures.clone()))
+            .unwrap();
+
+        Self {
+            registry,
+            requests,
+            request_duration,
+            resolver_errors,
+            cache_hits,
+            cache_misses,
+            cache_evictions,
+            cache_entries,
+            graph_nodes,
+            graph_edges,
+            write_back_failures,
+            recorded_graphs: Default::default(),
+        }
+    }
+
+    pub(crate) fn record_eviction(&self, cause: RemovalCause) {
+        let cause = match cause {
+            RemovalCause::Expired => "expired",
+            RemovalCause::Explicit => "explicit",
+            RemovalCause::Replaced => "replaced",
+            RemovalCause::Size => "size",
+        };
+        self.cache_evictions.with_label_values(&[cause]).inc();
+    }
+
+    /// Update the gauges of the graph at `path`, called when the graph is loaded into the cache
+    pub(crate) fn record_graph(&self, path: &Path, graph: &IndexedGraph<MaterializedGraph>) {
+        let name = path.display().to_string();
+        let storage = graph.core_graph();
+        let mut recorded = self.recorded_graphs.lock();
+        recorded.insert(path.to_path_buf(), (storage.clone(), storage.version()));
+        self.graph_nodes
+            .with_label_values(&[&name])
+            .set(graph.count_nodes() as i64);
+        self.graph_edges
+            .with_label_values(&[&name])
+            .set(graph.count_edges() as i64);
+        self.cache_entries.set(recorded.len() as i64);
+    }
+
+    /// Stop reporting the graph at `path`, called when `graph` is removed from the cache. Does
+    /// nothing if the graph was already replaced by another one.
+    pub(crate) fn forget_graph(&self, path: &Path, graph: &IndexedGraph<MaterializedGraph>) {
+        let mut recorded = self.recorded_graphs.lock();
+        if recorded
+            .get(path)
+            .is_some_and(|(storage, _)| storage.ptr_eq(graph.core_graph()))
+        {
+            recorded.remove(path);
+            let name = path.display().to_string();
+            let _ = self.graph_nodes.remove_label_values(&[&name]);
+            let _ = self.graph_edges.remove_label_values(&[&name]);
+        }
+        self.cache_entries.set(recorded.len() as i64);
+    }
+
+    /// Update the gauges of the cached graphs that were modified since they were last recorded
+    pub(crate) fn record_mutations(
+        &self,
+        graphs: &Cache<PathBuf, IndexedGraph<MaterializedGraph>>,
+    ) {
+        for (path, graph) in graphs.iter() {
+            let storage = graph.core_graph();
+            let up_to_date = self.recorded_graphs.lock().get(path.as_path()).is_some_and(
+                |(recorded, version)| recorded.ptr_eq(storage) && *version == storage.version(),
+            );
+            if !up_to_date {
+                self.record_graph(&path, &graph);
+            }
+        }
+    }
+
+    /// Encode all metrics in the Prometheus text format
+    pub(crate) fn encode(&self) -> Result<String, prometheus::Error> {
+        let mut buffer = vec![];
+        TextEncoder::new().encode(&self.registry.gather(), &mut buffer)?;
+        Ok(String::from_utf8_lossy(&buffer).into_owned())
+    }
+}
+
+#[handler]
+pub(crate) async fn metrics(Data(metrics): Data<&Arc<Metrics>>) -> impl IntoResponse {
+    match metrics.encode() {
+        Ok(body) => body
+            .with_content_type(TextEncoder::new().format_type())
+            .with_status(StatusCode::OK)
+            .into_response(),
+        Err(err) => err
+            .to_string()
+            .with_status(StatusCode::INTERNAL_SERVER_ERROR)
+            .into_response(),
+    }
+}
+
+/// Schema extension recording the count, latency and errors of every request and updating the
+/// graph gauges after mutations
+pub(crate) struct RequestMetrics {
+    metrics: Arc<Metrics>,
+    graphs: Cache<PathBuf, IndexedGraph<MaterializedGraph>>,
+}
+
+impl RequestMetrics {
+    pub(crate) fn new(
+        metrics: Arc<Metrics>,
+        graphs: Cache<PathBuf, IndexedGraph<MaterializedGraph>>,
+    ) -> Self {
+        Self { metrics, graphs }
+    }
+}
+
+impl ExtensionFactory for RequestMetrics {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(RequestMetricsExtension {
+            metrics: self.metrics.clone(),
+            graphs: self.graphs.clone(),
+            is_mutation: AtomicBool::new(false),
+        })
+    }
+}
+
+struct RequestMetricsExtension {
+    metrics: Arc<Metrics>,
+    graphs: Cache<PathBuf, IndexedGraph<MaterializedGraph>>,
+    is_mutation: AtomicBool,
+}
+
+#[async_trait::async_trait]
+impl Extension for RequestMetricsExtension {
+    async fn parse_query(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        query: &str,
+        variables: &Variables,
+        next: NextParseQuery<'_>,
+    ) -> ServerResult<ExecutableDocument> {
+        let document = next.run(ctx, query, variables).await?;
+        let is_mutation = document
+            .operations
+            .iter()
+            .any(|(_, operation)| operation.node.ty == OperationType::Mutation);
+        self.is_mutation.store(is_mutation, Ordering::Relaxed);
+        Ok(document)
+    }
+
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> Response {
+        let start = Instant::now();
+        let response = next.run(ctx, operation_name).await;
+        let operation = [operation_name.unwrap_or(ANONYMOUS_OPERATION)];
+        self.metrics.requests.with_label_values(&operation).inc();
+        self.metrics
+            .request_duration
+            .with_label_values(&operation)
+            .observe(start.elapsed().as_secs_f64());
+        if !response.errors.is_empty() {
+            self.metrics
+                .resolver_errors
+                .with_label_values(&operation)
+                .inc_by(response.errors.len() as u64);
+        }
+        if self.is_mutation.load(Ordering::Relaxed) {
+            self.metrics.record_mutations(&self.graphs);
+        }
+        response
+    }
+}
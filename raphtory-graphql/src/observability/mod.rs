@@ -1 +1,3 @@
+pub(crate) mod metrics;
+pub(crate) mod request_log;
 pub(crate) mod tracing;
@@ -0,0 +1,56 @@
+use crate::observability::metrics::ANONYMOUS_OPERATION;
+use async_graphql::{
+    extensions::{Extension, ExtensionContext, ExtensionFactory, NextExecute},
+    Response,
+};
+use itertools::Itertools;
+use std::{sync::Arc, time::Instant};
+
+/// Schema extension emitting one structured log event per request.
+///
+/// The events have the fields `operation`, `duration_ms`, `num_errors` and `errors` so that they
+/// can be filtered and aggregated when the logs are formatted as JSON.
+pub(crate) struct RequestLogging;
+
+impl ExtensionFactory for RequestLogging {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(RequestLoggingExtension)
+    }
+}
+
+struct RequestLoggingExtension;
+
+#[async_trait::async_trait]
+impl Extension for RequestLoggingExtension {
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> Response {
+        let start = Instant::now();
+        let response = next.run(ctx, operation_name).await;
+        let duration_ms = start.elapsed().as_millis() as u64;
+        let operation = operation_name.unwrap_or(ANONYMOUS_OPERATION);
+        if response.errors.is_empty() {
+            tracing::info!(
+                target: "raphtory_graphql::request",
+                operation,
+                duration_ms,
+                num_errors = 0,
+                "request completed"
+            );
+        } else {
+            let errors = response.errors.iter().map(|err| &err.message).join("; ");
+            tracing::warn!(
+                target: "raphtory_graphql::request",
+                operation,
+                duration_ms,
+                num_errors = response.errors.len(),
+                errors,
+                "request failed"
+            );
+        }
+        response
+    }
+}
@@ -225,7 +225,7 @@ impl PyGraphServer {
 impl PyGraphServer {
     #[new]
     #[pyo3(
-        signature = (work_dir, cache_capacity = None, cache_tti_seconds = None, log_level = None, config_path = None)
+        signature = (work_dir, cache_capacity = None, cache_tti_seconds = None, log_level = None, config_path = None, max_query_depth = None, max_query_complexity = None, query_timeout_seconds = None)
     )]
     fn py_new(
         work_dir: PathBuf,
@@ -233,6 +233,9 @@ impl PyGraphServer {
         cache_tti_seconds: Option<u64>,
         log_level: Option<String>,
         config_path: Option<PathBuf>,
+        max_query_depth: Option<usize>,
+        max_query_complexity: Option<usize>,
+        query_timeout_seconds: Option<u64>,
     ) -> PyResult<Self> {
         let mut app_config_builder = AppConfigBuilder::new();
         if let Some(log_level) = log_level {
@@ -244,6 +247,16 @@ impl PyGraphServer {
         if let Some(cache_tti_seconds) = cache_tti_seconds {
             app_config_builder = app_config_builder.with_cache_tti_seconds(cache_tti_seconds);
         }
+        if let Some(max_query_depth) = max_query_depth {
+            app_config_builder = app_config_builder.with_query_max_depth(max_query_depth);
+        }
+        if let Some(max_query_complexity) = max_query_complexity {
+            app_config_builder = app_config_builder.with_query_max_complexity(max_query_complexity);
+        }
+        if let Some(query_timeout_seconds) = query_timeout_seconds {
+            app_config_builder =
+                app_config_builder.with_query_timeout_seconds(query_timeout_seconds);
+        }
         let app_config = Some(app_config_builder.build());
 
         let server = GraphServer::new(work_dir, app_config, config_path)?;
@@ -0,0 +1,310 @@
+use crate::server_config::QueryConfig;
+use async_graphql::{
+    dynamic::{ResolverContext, SchemaBuilder},
+    extensions::{
+        Extension, ExtensionContext, ExtensionFactory, NextExecute, NextPrepareRequest,
+        NextResolve, ResolveInfo,
+    },
+    Request, Response, ServerError, ServerResult, Value,
+};
+use raphtory::db::task::cancellation::{with_cancellation, CancellationToken};
+use std::{
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
+
+#[derive(thiserror::Error, Debug)]
+pub enum QueryLimitError {
+    #[error("Query exceeded the timeout of {0} seconds")]
+    Timeout(u64),
+    #[error("Query was cancelled")]
+    Cancelled,
+}
+
+/// Apply the depth, complexity and timeout limits of `config` to the schema
+pub(crate) fn with_query_limits(
+    schema_builder: SchemaBuilder,
+    config: &QueryConfig,
+) -> SchemaBuilder {
+    let schema_builder = schema_builder.extension(QueryTimeout::new(config.timeout_seconds));
+    let schema_builder = match config.max_depth {
+        Some(max_depth) => schema_builder.limit_depth(max_depth),
+        None => schema_builder,
+    };
+    match config.max_complexity {
+        Some(max_complexity) => schema_builder.limit_complexity(max_complexity),
+        None => schema_builder,
+    }
+}
+
+/// Schema extension enforcing the per-request timeout and stopping the work of a request once it
+/// times out or its client disconnects.
+///
+/// Every request gets its own [CancellationToken] which is made available to resolvers as request
+/// data. The token is cancelled when the timeout expires or when the request future is dropped
+/// before completion. Remaining fields are then not resolved anymore and algorithms started with
+/// [run_cancellable] stop at their next check point.
+pub(crate) struct QueryTimeout {
+    timeout_seconds: Option<u64>,
+}
+
+impl QueryTimeout {
+    pub(crate) fn new(timeout_seconds: Option<u64>) -> Self {
+        Self { timeout_seconds }
+    }
+}
+
+impl ExtensionFactory for QueryTimeout {
+    fn create(&self) -> Arc<dyn Extension> {
+        Arc::new(QueryTimeoutExtension {
+            timeout_seconds: self.timeout_seconds,
+            token: CancellationToken::new(),
+            timed_out: Arc::new(AtomicBool::new(false)),
+        })
+    }
+}
+
+struct QueryTimeoutExtension {
+    timeout_seconds: Option<u64>,
+    token: CancellationToken,
+    timed_out: Arc<AtomicBool>,
+}
+
+/// Cancels the token if dropped before being disarmed, i.e., if the request future is dropped
+pub(crate) struct CancelOnDrop(Option<CancellationToken>);
+
+impl CancelOnDrop {
+    pub(crate) fn new(token: CancellationToken) -> Self {
+        Self(Some(token))
+    }
+
+    fn disarm(mut self) {
+        self.0 = None;
+    }
+}
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        if let Some(token) = self.0.take() {
+            token.cancel();
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl Extension for QueryTimeoutExtension {
+    async fn prepare_request(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        request: Request,
+        next: NextPrepareRequest<'_>,
+    ) -> ServerResult<Request> {
+        next.run(ctx, request.data(self.token.clone())).await
+    }
+
+    async fn execute(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        operation_name: Option<&str>,
+        next: NextExecute<'_>,
+    ) -> Response {
+        let guard = CancelOnDrop::new(self.token.clone());
+        // resolvers doing blocking work do not yield, so the timeout is enforced from another task
+        let watchdog = self.timeout_seconds.map(|timeout_seconds| {
+            let token = self.token.clone();
+            let timed_out = self.timed_out.clone();
+            tokio::spawn(async move {
+                tokio::time::sleep(Duration::from_secs(timeout_seconds)).await;
+                timed_out.store(true, Ordering::Relaxed);
+                token.cancel();
+            })
+        });
+        let response = match self.timeout_seconds {
+            Some(timeout_seconds) => tokio::time::timeout(
+                Duration::from_secs(timeout_seconds),
+                next.run(ctx, operation_name),
+            )
+            .await
+            .ok(),
+            None => Some(next.run(ctx, operation_name).await),
+        };
+        if let Some(watchdog) = watchdog {
+            watchdog.abort();
+        }
+
+        match response {
+            Some(response) if !self.timed_out.load(Ordering::Relaxed) => {
+                guard.disarm();
+                response
+            }
+            // dropping the armed guard stops blocking work that may still be running, even if the
+            // timeout elapsed before the watchdog got to cancel the token
+            _ => Response::from_errors(vec![ServerError::new(
+                QueryLimitError::Timeout(self.timeout_seconds.unwrap_or_default()).to_string(),
+                None,
+            )]),
+        }
+    }
+
+    async fn resolve(
+        &self,
+        ctx: &ExtensionContext<'_>,
+        info: ResolveInfo<'_>,
+        next: NextResolve<'_>,
+    ) -> ServerResult<Option<Value>> {
+        if self.token.is_cancelled() {
+            return Err(ServerError::new(
+                QueryLimitError::Cancelled.to_string(),
+                None,
+            ));
+        }
+        next.run(ctx, info).await
+    }
+}
+
+/// The cancellation token of the current request (a fresh one if the schema has no [QueryTimeout])
+pub(crate) fn cancellation_token(ctx: &ResolverContext) -> CancellationToken {
+    ctx.data_opt::<CancellationToken>()
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Run the blocking computation `f` on the blocking thread pool with `token` installed so that
+/// algorithms stop early once the request is cancelled. The token is moved into the blocking task
+/// rather than installed on the thread polling the request, which tokio may change at every await. Returns an error instead of the
+/// (incomplete) result if the token was cancelled.
+pub(crate) async fn run_cancellable<R: Send + 'static>(
+    token: CancellationToken,
+    f: impl FnOnce() -> R + Send + 'static,
+) -> Result<R, QueryLimitError> {
+    let task_token = token.clone();
+    let result = tokio::task::spawn_blocking(move || with_cancellation(&task_token, f))
+        .await
+        .unwrap_or_else(|err| std::panic::resume_unwind(err.into_panic()));
+    if token.is_cancelled() {
+        Err(QueryLimitError::Cancelled)
+    } else {
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+mod query_limits_tests {
+    use super::*;
+    use async_graphql::dynamic::{Field, FieldFuture, FieldValue, Object, Schema, TypeRef};
+    use raphtory::db::task::cancellation;
+
+    /// Schema with a cheap field, a nested field and a field that keeps a blocking thread busy
+    /// until its request is cancelled and then sets `stopped`
+    fn schema(config: &QueryConfig, stopped: Arc<AtomicBool>) -> Schema {
+        let query = Object::new("Query")
+            .field(Field::new("fast", TypeRef::named_nn(TypeRef::INT), |_| {
+                FieldFuture::new(async { Ok(Some(Value::from(1))) })
+            }))
+            .field(Field::new("nested", TypeRef::named_nn("Query"), |_| {
+                FieldFuture::new(async { Ok(Some(FieldValue::owned_any(()))) })
+            }))
+            .field(Field::new(
+                "busy",
+                TypeRef::named_nn(TypeRef::INT),
+                move |ctx| {
+                    let token = cancellation_token(&ctx);
+                    let stopped = stopped.clone();
+                    FieldFuture::new(async move {
+                        run_cancellable(token, move || {
+                            while !cancellation::is_cancelled() {
+                                std::thread::sleep(Duration::from_millis(10));
+                            }
+                            stopped.store(true, Ordering::Relaxed);
+                        })
+                        .await?;
+                        Ok(Some(Value::from(1)))
+                    })
+                },
+            ));
+        with_query_limits(Schema::build("Query", None, None).register(query), config)
+            .finish()
+            .unwrap()
+    }
+
+    async fn wait_until_stopped(stopped: &AtomicBool) {
+        for _ in 0..500 {
+            if stopped.load(Ordering::Relaxed) {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        panic!("blocking work was not cancelled");
+    }
+
+    #[tokio::test]
+    async fn test_timeout_cancels_blocking_work() {
+        let stopped = Arc::new(AtomicBool::new(false));
+        let config = QueryConfig {
+            timeout_seconds: Some(1),
+            ..Default::default()
+        };
+        let schema = schema(&config, stopped.clone());
+
+        let res = schema.execute("{ fast }").await;
+        assert!(res.errors.is_empty());
+
+        let res = schema.execute("{ busy }").await;
+        assert_eq!(res.errors.len(), 1);
+        assert_eq!(
+            res.errors[0].message,
+            "Query exceeded the timeout of 1 seconds"
+        );
+        wait_until_stopped(&stopped).await;
+    }
+
+    #[tokio::test]
+    async fn test_dropped_request_cancels_blocking_work() {
+        let stopped = Arc::new(AtomicBool::new(false));
+        let schema = schema(&QueryConfig::default(), stopped.clone());
+
+        // dropping the request future, e.g. because the client disconnected, cancels the request
+        let res =
+            tokio::time::timeout(Duration::from_millis(200), schema.execute("{ busy }")).await;
+        assert!(res.is_err());
+        wait_until_stopped(&stopped).await;
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_work_returns_an_error() {
+        let token = CancellationToken::new();
+        token.cancel();
+        let res = run_cancellable(token, cancellation::is_cancelled).await;
+        assert!(matches!(res, Err(QueryLimitError::Cancelled)));
+
+        let res = run_cancellable(CancellationToken::new(), cancellation::is_cancelled).await;
+        assert!(matches!(res, Ok(false)));
+    }
+
+    #[tokio::test]
+    async fn test_depth_and_complexity_limits() {
+        let stopped = Arc::new(AtomicBool::new(false));
+        let config = QueryConfig {
+            max_depth: Some(2),
+            max_complexity: Some(3),
+            ..Default::default()
+        };
+        let schema = schema(&config, stopped);
+
+        let res = schema.execute("{ fast nested { fast } }").await;
+        assert!(res.errors.is_empty());
+
+        let res = schema.execute("{ nested { nested { fast } } }").await;
+        assert_eq!(res.errors.len(), 1);
+        assert!(res.errors[0].message.contains("nested too deep"));
+
+        let res = schema
+            .execute("{ fast a: fast nested { fast b: fast } }")
+            .await;
+        assert_eq!(res.errors.len(), 1);
+        assert!(res.errors[0].message.contains("too complex"));
+    }
+}
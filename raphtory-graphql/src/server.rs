@@ -6,7 +6,12 @@ use crate::{
         algorithms::{algorithm::Algorithm, algorithm_entry_point::AlgorithmEntryPoint},
         App,
     },
-    observability::tracing::create_tracer_from_env,
+    observability::{
+        metrics::{metrics as metrics_endpoint, RequestMetrics},
+        request_log::RequestLogging,
+        tracing::create_tracer_from_env,
+    },
+    query_limits::with_query_limits,
     routes::{graphql_playground, health},
 };
 use async_graphql_poem::GraphQL;
@@ -18,7 +23,10 @@ use poem::{
     EndpointExt, Route, Server,
 };
 use raphtory::{
-    db::api::view::{DynamicGraph, IntoDynamic},
+    core::utils::errors::GraphError,
+    db::api::view::{DynamicGraph, IntoDynamic, MaterializedGraph},
+    io::stream_loader::StreamLoader,
+    search::IndexedGraph,
     vectors::{
         document_template::{DefaultTemplate, DocumentTemplate},
         vectorisable::Vectorisable,
@@ -26,12 +34,18 @@ use raphtory::{
     },
 };
 
-use crate::server_config::{load_config, AppConfig, LoggingConfig};
+use crate::server_config::{load_config, AppConfig, LogFormat, LoggingConfig};
 use config::ConfigError;
+use serde::de::DeserializeOwned;
 use std::{
     fs,
     path::{Path, PathBuf},
-    sync::Arc,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread,
+    time::Duration,
 };
 use thiserror::Error;
 use tokio::{
@@ -73,10 +87,35 @@ impl From<ServerError> for io::Error {
     }
 }
 
+type Ingester = Box<dyn FnOnce(Data, Arc<AtomicBool>) -> thread::JoinHandle<()> + Send>;
+
+/// Upper bound of the wait before a failed ingestion is retried
+const MAX_INGESTION_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Sleep for `duration` or until `stop` is set
+fn sleep_unless_stopped(duration: Duration, stop: &AtomicBool) {
+    let step = Duration::from_millis(100);
+    let mut remaining = duration;
+    while !remaining.is_zero() && !stop.load(Ordering::Relaxed) {
+        let sleep = remaining.min(step);
+        thread::sleep(sleep);
+        remaining -= sleep;
+    }
+}
+
+fn join_ingestion_threads(threads: Vec<thread::JoinHandle<()>>) {
+    for thread in threads {
+        if thread.join().is_err() {
+            tracing::error!("Ingestion thread panicked");
+        }
+    }
+}
+
 /// A struct for defining and running a Raphtory GraphQL server
 pub struct GraphServer {
     data: Data,
     configs: AppConfig,
+    ingesters: Vec<Ingester>,
 }
 
 impl GraphServer {
@@ -92,7 +131,11 @@ impl GraphServer {
             load_config(app_config, config_path).map_err(|err| ServerError::ConfigError(err))?;
         let data = Data::new(work_dir.as_path(), &configs);
 
-        Ok(Self { data, configs })
+        Ok(Self {
+            data,
+            configs,
+            ingesters: vec![],
+        })
     }
 
     /// Vectorise a subset of the graphs of the server.
@@ -161,6 +204,62 @@ impl GraphServer {
         Ok(self)
     }
 
+    /// Continuously ingest the records of `loader` into the graph at `path` while the server runs.
+    ///
+    /// The graph is looked up on every poll so the records keep reaching the graph that is served
+    /// even if it is evicted from the cache and loaded again. Failures (e.g., of the mapping) are
+    /// logged and retried with an exponential backoff, starting from the poll interval of the
+    /// loader. Ingestion stops when the server is stopped, which waits for the current poll.
+    ///
+    /// Arguments:
+    ///   * `path` - the path of the graph in the work dir.
+    ///   * `loader` - the loader following the source of the records.
+    ///   * `mapping` - the function applying a record to the graph.
+    pub fn with_stream_ingestion<REC, F>(
+        mut self,
+        path: &str,
+        mut loader: StreamLoader<REC>,
+        mapping: F,
+    ) -> Self
+    where
+        REC: DeserializeOwned + Send + 'static,
+        F: Fn(REC, &IndexedGraph<MaterializedGraph>) -> Result<(), GraphError> + Send + 'static,
+    {
+        let path = PathBuf::from(path);
+        self.ingesters.push(Box::new(move |data, stop| {
+            thread::spawn(move || {
+                let mut backoff = loader.poll_interval();
+                while !stop.load(Ordering::Relaxed) && !loader.is_finished() {
+                    let polled = data
+                        .get_graph(&path)
+                        .map_err(|err| err.to_string())
+                        .and_then(|graph| {
+                            loader
+                                .poll_once(&graph, &mapping)
+                                .map_err(|err| err.to_string())
+                        });
+                    match polled {
+                        Ok(stats) => {
+                            backoff = loader.poll_interval();
+                            if stats.is_empty() {
+                                sleep_unless_stopped(loader.poll_interval(), &stop);
+                            }
+                        }
+                        Err(err) => {
+                            tracing::error!(
+                                "Ingestion into {} failed, retrying in {backoff:?}: {err}",
+                                path.display()
+                            );
+                            sleep_unless_stopped(backoff, &stop);
+                            backoff = (backoff * 2).min(MAX_INGESTION_BACKOFF);
+                        }
+                    }
+                }
+            })
+        }));
+        self
+    }
+
     pub fn register_algorithm<
         'a,
         E: AlgorithmEntryPoint<'a> + 'static,
@@ -179,12 +278,22 @@ impl GraphServer {
     }
 
     /// Start the server on the port `port` and return a handle to it.
-    pub async fn start_with_port(self, port: u16) -> IoResult<RunningGraphServer> {
+    pub async fn start_with_port(mut self, port: u16) -> IoResult<RunningGraphServer> {
         fn configure_logger(configs: &LoggingConfig) {
             let log_level = &configs.log_level;
             let filter = EnvFilter::new(log_level);
-            let subscriber = FmtSubscriber::builder().with_env_filter(filter).finish();
-            if let Err(err) = tracing::subscriber::set_global_default(subscriber) {
+            let result = match configs.log_format {
+                LogFormat::Pretty => tracing::subscriber::set_global_default(
+                    FmtSubscriber::builder().with_env_filter(filter).finish(),
+                ),
+                LogFormat::Json => tracing::subscriber::set_global_default(
+                    FmtSubscriber::builder()
+                        .with_env_filter(filter)
+                        .json()
+                        .finish(),
+                ),
+            };
+            if let Err(err) = result {
                 eprintln!(
                     "Log level cannot be updated within the same runtime environment: {}",
                     err
@@ -206,6 +315,13 @@ impl GraphServer {
         }
         .unwrap_or(());
 
+        let stop_ingestion = Arc::new(AtomicBool::new(false));
+        let ingestion_threads = self
+            .ingesters
+            .drain(..)
+            .map(|ingester| ingester(self.data.clone(), stop_ingestion.clone()))
+            .collect();
+
         // it is important that this runs after algorithms have been pushed to PLUGIN_ALGOS static variable
 
         let app: CorsEndpoint<CookieJarManagerEndpoint<Route>> = self.generate_endpoint().await?;
@@ -220,17 +336,27 @@ impl GraphServer {
         Ok(RunningGraphServer {
             signal_sender,
             server_result,
+            stop_ingestion,
+            ingestion_threads: Mutex::new(ingestion_threads),
         })
     }
 
     async fn generate_endpoint(self) -> IoResult<CorsEndpoint<CookieJarManagerEndpoint<Route>>> {
+        let metrics = self.data.metrics.clone();
+        let graphs = self.data.graphs.clone();
+
         let schema_builder = App::create_schema();
-        let schema_builder = schema_builder.data(self.data);
+        let schema_builder = schema_builder
+            .data(self.data)
+            .extension(RequestMetrics::new(metrics.clone(), graphs))
+            .extension(RequestLogging);
+        let schema_builder = with_query_limits(schema_builder, &self.configs.query);
         let schema = schema_builder.finish().unwrap();
 
         let app = Route::new()
             .at("/", get(graphql_playground).post(GraphQL::new(schema)))
             .at("/health", get(health))
+            .at("/metrics", get(metrics_endpoint).data(metrics))
             .with(CookieJarManager::new())
             .with(Cors::new());
         Ok(app)
@@ -349,19 +475,35 @@ impl GraphServer {
 pub struct RunningGraphServer {
     signal_sender: Sender<()>,
     server_result: JoinHandle<IoResult<()>>,
+    stop_ingestion: Arc<AtomicBool>,
+    ingestion_threads: Mutex<Vec<thread::JoinHandle<()>>>,
 }
 
 impl RunningGraphServer {
-    /// Stop the server.
+    /// Stop the server and wait for the ingestion into its graphs to finish.
     pub async fn stop(&self) {
+        self.stop_ingestion().await;
         let _ignored = self.signal_sender.send(()).await;
     }
 
     /// Wait until server completion.
     pub async fn wait(self) -> IoResult<()> {
-        self.server_result
+        let result = self
+            .server_result
             .await
-            .expect("Coudln't join tokio task for the server")
+            .expect("Coudln't join tokio task for the server");
+        self.stop_ingestion().await;
+        result
+    }
+
+    async fn stop_ingestion(&self) {
+        self.stop_ingestion.store(true, Ordering::Relaxed);
+        let threads = std::mem::take(&mut *self.ingestion_threads.lock().unwrap());
+        if !threads.is_empty() {
+            // the current polls write the pending updates to the graphs before the threads exit
+            let _ignored =
+                tokio::task::spawn_blocking(move || join_ingestion_threads(threads)).await;
+        }
     }
 
     // TODO: make this optional with some python feature flag
@@ -2,15 +2,26 @@ use config::{Config, ConfigError, File};
 use serde::Deserialize;
 use std::path::PathBuf;
 
+#[derive(Debug, Deserialize, PartialEq, Clone, Copy)]
+#[serde(rename_all = "lowercase")]
+pub enum LogFormat {
+    /// Human readable logs
+    Pretty,
+    /// One JSON object per log event
+    Json,
+}
+
 #[derive(Debug, Deserialize, PartialEq, Clone)]
 pub struct LoggingConfig {
     pub log_level: String,
+    pub log_format: LogFormat,
 }
 
 impl Default for LoggingConfig {
     fn default() -> Self {
         Self {
             log_level: "INFO".to_string(),
+            log_format: LogFormat::Pretty,
         }
     }
 }
@@ -30,6 +41,16 @@ impl Default for CacheConfig {
     }
 }
 
+#[derive(Debug, Deserialize, PartialEq, Clone, Default)]
+pub struct QueryConfig {
+    /// Maximum nesting depth of a query, unlimited if None
+    pub max_depth: Option<usize>,
+    /// Maximum complexity (number of fields, weighted by list nesting) of a query, unlimited if None
+    pub max_complexity: Option<usize>,
+    /// Wall-clock timeout of a single request, unlimited if None
+    pub timeout_seconds: Option<u64>,
+}
+
 #[derive(Debug, Deserialize, PartialEq, Clone)]
 pub struct AuthConfig {
     pub client_id: Option<String>,
@@ -51,6 +72,7 @@ impl Default for AuthConfig {
 pub struct AppConfig {
     pub logging: LoggingConfig,
     pub cache: CacheConfig,
+    pub query: QueryConfig,
     // pub auth: AuthConfig,
 }
 
@@ -59,6 +81,7 @@ impl Default for AppConfig {
         Self {
             logging: LoggingConfig::default(),
             cache: CacheConfig::default(),
+            query: QueryConfig::default(),
             // auth: AuthConfig::default(),
         }
     }
@@ -67,6 +90,7 @@ impl Default for AppConfig {
 pub struct AppConfigBuilder {
     logging: LoggingConfig,
     cache: CacheConfig,
+    query: QueryConfig,
     // auth: AuthConfig,
 }
 
@@ -75,6 +99,7 @@ impl AppConfigBuilder {
         Self {
             logging: LoggingConfig::default(),
             cache: CacheConfig::default(),
+            query: QueryConfig::default(),
             // auth: AuthConfig::default(),
         }
     }
@@ -83,6 +108,7 @@ impl AppConfigBuilder {
         Self {
             logging: config.logging,
             cache: config.cache,
+            query: config.query,
             // auth: config.auth,
         }
     }
@@ -92,6 +118,11 @@ impl AppConfigBuilder {
         self
     }
 
+    pub fn with_log_format(mut self, log_format: LogFormat) -> Self {
+        self.logging.log_format = log_format;
+        self
+    }
+
     pub fn with_cache_capacity(mut self, cache_capacity: u64) -> Self {
         self.cache.capacity = cache_capacity;
         self
@@ -102,6 +133,21 @@ impl AppConfigBuilder {
         self
     }
 
+    pub fn with_query_max_depth(mut self, max_depth: usize) -> Self {
+        self.query.max_depth = Some(max_depth);
+        self
+    }
+
+    pub fn with_query_max_complexity(mut self, max_complexity: usize) -> Self {
+        self.query.max_complexity = Some(max_complexity);
+        self
+    }
+
+    pub fn with_query_timeout_seconds(mut self, timeout_seconds: u64) -> Self {
+        self.query.timeout_seconds = Some(timeout_seconds);
+        self
+    }
+
     // pub fn with_auth_client_id(mut self, client_id: String) -> Self {
     //     self.auth.client_id = Some(client_id);
     //     self
@@ -121,6 +167,7 @@ impl AppConfigBuilder {
         AppConfig {
             logging: self.logging,
             cache: self.cache,
+            query: self.query,
             // auth: self.auth,
         }
     }
@@ -150,12 +197,24 @@ pub fn load_config(
     if let Some(log_level) = settings.get::<String>("logging.log_level").ok() {
         app_config_builder = app_config_builder.with_log_level(log_level);
     }
+    if let Some(log_format) = settings.get::<LogFormat>("logging.log_format").ok() {
+        app_config_builder = app_config_builder.with_log_format(log_format);
+    }
     if let Some(cache_capacity) = settings.get::<u64>("cache.capacity").ok() {
         app_config_builder = app_config_builder.with_cache_capacity(cache_capacity);
     }
     if let Some(cache_tti_seconds) = settings.get::<u64>("cache.tti_seconds").ok() {
         app_config_builder = app_config_builder.with_cache_tti_seconds(cache_tti_seconds);
     }
+    if let Some(max_depth) = settings.get::<usize>("query.max_depth").ok() {
+        app_config_builder = app_config_builder.with_query_max_depth(max_depth);
+    }
+    if let Some(max_complexity) = settings.get::<usize>("query.max_complexity").ok() {
+        app_config_builder = app_config_builder.with_query_max_complexity(max_complexity);
+    }
+    if let Some(timeout_seconds) = settings.get::<u64>("query.timeout_seconds").ok() {
+        app_config_builder = app_config_builder.with_query_timeout_seconds(timeout_seconds);
+    }
     // if let Some(client_id) = settings.get::<String>("auth.client_id").ok() {
     //     app_config_builder = app_config_builder.with_auth_client_id(client_id);
     // }
@@ -211,6 +270,46 @@ mod tests {
         assert_eq!(result.unwrap(), app_config);
     }
 
+    #[test]
+    fn test_load_query_limits_from_toml() {
+        let config_toml = r#"
+            [query]
+            max_depth = 10
+            timeout_seconds = 60
+        "#;
+        let config_path = PathBuf::from("test_query_config.toml");
+        fs::write(&config_path, config_toml).unwrap();
+
+        let result = load_config(None, Some(config_path.clone()));
+        let expected_config = AppConfigBuilder::new()
+            .with_query_max_depth(10)
+            .with_query_timeout_seconds(60)
+            .build();
+
+        assert_eq!(result.unwrap(), expected_config);
+
+        fs::remove_file(config_path).unwrap();
+    }
+
+    #[test]
+    fn test_load_log_format_from_toml() {
+        let config_toml = r#"
+            [logging]
+            log_format = "json"
+        "#;
+        let config_path = PathBuf::from("test_log_format_config.toml");
+        fs::write(&config_path, config_toml).unwrap();
+
+        let result = load_config(None, Some(config_path.clone()));
+        let expected_config = AppConfigBuilder::new()
+            .with_log_format(LogFormat::Json)
+            .build();
+
+        assert_eq!(result.unwrap(), expected_config);
+
+        fs::remove_file(config_path).unwrap();
+    }
+
     #[test]
     fn test_load_config_with_custom_auth() {
         let app_config = AppConfigBuilder::new()
@@ -4,7 +4,10 @@ use std::{
 };
 
 /// Dijkstra's algorithm
-use crate::{core::entities::nodes::node_ref::AsNodeRef, db::api::view::StaticGraphViewOps};
+use crate::{
+    core::entities::nodes::node_ref::AsNodeRef,
+    db::{api::view::StaticGraphViewOps, task::cancellation},
+};
 use crate::{
     core::{Direction, PropType},
     prelude::{EdgeViewOps, NodeViewOps, Prop},
@@ -132,6 +135,7 @@ pub fn dijkstra_single_source_shortest_paths<G: StaticGraphViewOps, T: AsNodeRef
         PropType::PersistentGraph => return Err("Weight type: Persistent Graph, not supported"),
         PropType::Document => return Err("Weight type: Document, not supported"),
     };
+    let token = cancellation::current();
     let mut heap = BinaryHeap::new();
     heap.push(State {
         cost: cost_val.clone(),
@@ -150,6 +154,9 @@ pub fn dijkstra_single_source_shortest_paths<G: StaticGraphViewOps, T: AsNodeRef
         node: node_name,
     }) = heap.pop()
     {
+        if token.is_cancelled() {
+            break;
+        }
         if target_nodes.contains(&node_name) && !paths.contains_key(&node_name) {
             let mut path = vec![node_name.clone()];
             let mut current_node_name = node_name.clone();
@@ -28,6 +28,10 @@ pub struct EdgeStore {
     pub(crate) eid: EID,
     pub(crate) src: VID,
     pub(crate) dst: VID,
+    // set if the edge was removed from all its layers or is hidden as its node was merged into
+    // another node
+    #[serde(default)]
+    pub(crate) removed: bool,
 }
 
 pub trait EdgeDataLike<'a> {
@@ -69,6 +73,10 @@ impl EdgeLayer {
         self.props.as_ref()
     }
 
+    pub(crate) fn props_mut(&mut self) -> Option<&mut Props> {
+        self.props.as_mut()
+    }
+
     pub fn into_props(self) -> Option<Props> {
         self.props
     }
@@ -112,12 +120,17 @@ impl EdgeStore {
             eid: 0.into(),
             src,
             dst,
+            removed: false,
         }
     }
 
     pub fn as_edge_ref(&self) -> EdgeRef {
         EdgeRef::new_outgoing(self.eid, self.src, self.dst)
     }
+
+    pub fn is_removed(&self) -> bool {
+        self.removed
+    }
 }
 
 impl EdgeStorageIntoOps for EdgeArcGuard {
@@ -75,6 +75,61 @@ impl Mapping {
         let map = self.map.get()?;
         map.as_u64().and_then(|m| m.get(&gid).map(|id| *id))
     }
+
+    /// Point `gid`, which resolves to `from`, to `to`.
+    ///
+    /// This is used to resolve the ids of merged nodes to the node they were merged into. Returns
+    /// an error if `gid` is already mapped to a node other than `from`.
+    pub fn alias(&self, gid: GidRef, from: VID, to: VID) -> Result<(), GraphError> {
+        let map = self.map.get_or_init(|| match &gid {
+            GidRef::U64(_) => Map::U64(FxDashMap::default()),
+            GidRef::Str(_) => Map::Str(FxDashMap::default()),
+        });
+        let updated = match gid {
+            GidRef::U64(id) => map.as_u64().map(|m| replace(m, id, from, to)),
+            GidRef::Str(id) => map.as_str().map(|m| replace(m, id.to_owned(), from, to)),
+        };
+        let source = match updated {
+            Some(true) => return Ok(()),
+            Some(false) => MutateGraphError::NodeIdAlreadyMapped(gid.into()),
+            None => MutateGraphError::InvalidNodeId(gid.into()),
+        };
+        Err(GraphError::FailedToMutateGraph { source })
+    }
+
+    /// Point `gid` to `vid` unless it already points to a node that was created later.
+    ///
+    /// Used when decoding a graph where a removed node was added again with the same id.
+    pub fn insert_latest(&self, gid: GidRef, vid: VID) -> Result<(), GraphError> {
+        let map = self.map.get_or_init(|| match &gid {
+            GidRef::U64(_) => Map::U64(FxDashMap::default()),
+            GidRef::Str(_) => Map::Str(FxDashMap::default()),
+        });
+        let updated = match gid {
+            GidRef::U64(id) => map.as_u64().map(|m| insert_max(m, id, vid)),
+            GidRef::Str(id) => map.as_str().map(|m| insert_max(m, id.to_owned(), vid)),
+        };
+        updated.ok_or(GraphError::FailedToMutateGraph {
+            source: MutateGraphError::InvalidNodeId(gid.into()),
+        })
+    }
+
+    /// Remove the mapping for `gid` if it points to `vid`
+    pub fn remove(&self, gid: GidRef, vid: VID) -> bool {
+        let Some(map) = self.map.get() else {
+            return false;
+        };
+        match gid {
+            GidRef::U64(id) => map
+                .as_u64()
+                .and_then(|m| m.remove_if(&id, |_, v| *v == vid))
+                .is_some(),
+            GidRef::Str(id) => map
+                .as_str()
+                .and_then(|m| m.remove_if(id, |_, v| *v == vid))
+                .is_some(),
+        }
+    }
 }
 
 #[inline]
@@ -88,6 +143,37 @@ fn optim_get_or_insert(
         .unwrap_or_else(|| get_or_new(m, id.to_owned(), f_init))
 }
 
+/// Point `id` to `to` unless it is mapped to a node other than `from`
+#[inline]
+fn replace<K: Eq + Hash>(m: &FxDashMap<K, VID>, id: K, from: VID, to: VID) -> bool {
+    match m.entry(id) {
+        Entry::Occupied(mut entry) => {
+            if *entry.get() != from {
+                return false;
+            }
+            entry.insert(to);
+        }
+        Entry::Vacant(entry) => {
+            entry.insert(to);
+        }
+    }
+    true
+}
+
+#[inline]
+fn insert_max<K: Eq + Hash>(m: &FxDashMap<K, VID>, id: K, vid: VID) {
+    match m.entry(id) {
+        Entry::Occupied(mut entry) => {
+            if *entry.get() < vid {
+                entry.insert(vid);
+            }
+        }
+        Entry::Vacant(entry) => {
+            entry.insert(vid);
+        }
+    }
+}
+
 #[inline]
 fn get_or_new<K: Eq + Hash>(
     m: &FxDashMap<K, VID>,
@@ -132,3 +218,22 @@ impl Serialize for Mapping {
         }
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_alias_of_mapped_id() {
+        let mapping = Mapping::new();
+        mapping.get_or_init(GidRef::Str("a"), || VID(0)).unwrap();
+        mapping.get_or_init(GidRef::Str("b"), || VID(1)).unwrap();
+        assert!(mapping.alias(GidRef::Str("a"), VID(1), VID(2)).is_err());
+        assert_eq!(mapping.get_str("a"), Some(VID(0)));
+
+        mapping.alias(GidRef::Str("a"), VID(0), VID(1)).unwrap();
+        assert_eq!(mapping.get_str("a"), Some(VID(1)));
+        mapping.alias(GidRef::Str("a"), VID(1), VID(0)).unwrap();
+        assert_eq!(mapping.get_str("a"), Some(VID(0)));
+    }
+}
@@ -1,4 +1,5 @@
 mod logical_to_physical;
+pub mod node_merge;
 pub mod tgraph;
 pub mod tgraph_storage;
 pub(crate) mod timer;
@@ -0,0 +1,576 @@
+//! Merging of nodes that turn out to be the same entity, e.g., after entity resolution.
+//!
+//! Merging a node into another node copies its history, properties and edges and hides the
+//! merged node together with its original edges, their data is kept. The ids of the merged node
+//! resolve to the node it was merged into. Every merge keeps a record of the updates it added
+//! such that it can be undone.
+
+use crate::core::{
+    entities::{edges::edge_store::EdgeStore, graph::tgraph::TemporalGraph, LayerIds, EID, VID},
+    storage::timeindex::{TimeIndex, TimeIndexEntry},
+    utils::errors::GraphError,
+    Direction, Prop,
+};
+use itertools::Itertools;
+use parking_lot::{MappedRwLockReadGuard, RwLock, RwLockReadGuard};
+use raphtory_api::core::entities::{GidRef, GID};
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeSet, iter, sync::atomic::Ordering};
+
+/// The updates that were added to a layer of an edge by merging another edge into it
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct LayerMerge {
+    pub layer: usize,
+    pub additions: Vec<TimeIndexEntry>,
+    pub deletions: Vec<TimeIndexEntry>,
+    /// temporal property updates as `(time, prop_id)`
+    pub tprops: Vec<(TimeIndexEntry, usize)>,
+    pub cprops: Vec<usize>,
+}
+
+/// An edge of a merged node and the edge it was merged into
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct EdgeMerge {
+    pub source: EID,
+    pub target: EID,
+    /// set if `target` was created by the merge
+    pub created: bool,
+    pub layers: Vec<LayerMerge>,
+}
+
+/// The record of merging node `merged` into node `keep`
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq, Default)]
+pub struct NodeMerge {
+    pub keep: VID,
+    pub merged: VID,
+    /// timestamps added to `keep`
+    pub times: Vec<i64>,
+    /// temporal property updates added to `keep` as `(time, prop_id)`
+    pub tprops: Vec<(TimeIndexEntry, usize)>,
+    /// constant properties added to `keep`
+    pub cprops: Vec<usize>,
+    pub edges: Vec<EdgeMerge>,
+}
+
+/// All node merges of a graph in the order they were applied
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct NodeMerges {
+    entries: RwLock<MergeEntries>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Default)]
+struct MergeEntries {
+    merges: Vec<NodeMerge>,
+    /// the node each merged node was merged into
+    merged_into: FxHashMap<VID, VID>,
+    /// the nodes that were merged directly into a node in the order they were merged
+    merged_nodes: FxHashMap<VID, Vec<VID>>,
+    /// the merged node for the id it had before it was merged
+    merged_ids: FxHashMap<GID, VID>,
+}
+
+impl NodeMerges {
+    pub fn read(&self) -> MappedRwLockReadGuard<Vec<NodeMerge>> {
+        RwLockReadGuard::map(self.entries.read(), |entries| &entries.merges)
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.entries.read().merges.is_empty()
+    }
+
+    /// The node `v` was merged into, `None` if `v` is not merged
+    pub fn merged_into(&self, v: VID) -> Option<VID> {
+        self.entries.read().merged_into.get(&v).copied()
+    }
+
+    /// The node that `v` resolves to, i.e., `v` itself if it is not merged or the node that is
+    /// kept at the end of the chain of merges
+    pub fn resolve(&self, v: VID) -> VID {
+        let entries = self.entries.read();
+        let mut v = v;
+        while let Some(keep) = entries.merged_into.get(&v) {
+            v = *keep;
+        }
+        v
+    }
+
+    /// The merged node with id `gid`, `None` if there is no merged node with this id
+    pub fn merged_node(&self, gid: GidRef) -> Option<VID> {
+        self.entries.read().merged_ids.get(&gid.to_owned()).copied()
+    }
+
+    /// The node that was merged into `v` last, only this merge can be undone
+    pub fn last_merged_into(&self, v: VID) -> Option<VID> {
+        self.entries
+            .read()
+            .merged_nodes
+            .get(&v)
+            .and_then(|nodes| nodes.last())
+            .copied()
+    }
+
+    /// The nodes that were merged into `v`, directly or by merging a node they were merged into
+    pub fn merged_nodes_of(&self, v: VID) -> Vec<VID> {
+        let entries = self.entries.read();
+        let mut nodes = vec![];
+        let mut stack = vec![v];
+        while let Some(keep) = stack.pop() {
+            for merged in entries.merged_nodes.get(&keep).into_iter().flatten() {
+                nodes.push(*merged);
+                stack.push(*merged);
+            }
+        }
+        nodes
+    }
+
+    fn push(&self, merge: NodeMerge, gid: GID) {
+        let mut entries = self.entries.write();
+        entries.merged_into.insert(merge.merged, merge.keep);
+        entries
+            .merged_nodes
+            .entry(merge.keep)
+            .or_default()
+            .push(merge.merged);
+        entries.merged_ids.insert(gid, merge.merged);
+        entries.merges.push(merge)
+    }
+
+    /// Remove the merge of node `merged` with id `gid`
+    pub(crate) fn remove(&self, merged: VID, gid: &GID) -> Option<NodeMerge> {
+        let mut entries = self.entries.write();
+        let keep = entries.merged_into.remove(&merged)?;
+        if let Some(nodes) = entries.merged_nodes.get_mut(&keep) {
+            nodes.retain(|v| *v != merged);
+            if nodes.is_empty() {
+                entries.merged_nodes.remove(&keep);
+            }
+        }
+        entries.merged_ids.remove(gid);
+        let index = entries
+            .merges
+            .iter()
+            .rposition(|merge| merge.merged == merged)?;
+        Some(entries.merges.remove(index))
+    }
+}
+
+/// The updates of an edge in a single layer
+#[derive(Default)]
+struct LayerUpdates {
+    additions: BTreeSet<TimeIndexEntry>,
+    deletions: BTreeSet<TimeIndexEntry>,
+    tprops: FxHashMap<usize, Vec<(TimeIndexEntry, Prop)>>,
+    cprops: FxHashMap<usize, Prop>,
+}
+
+impl LayerUpdates {
+    fn tprop_times(&self, prop_id: usize) -> BTreeSet<TimeIndexEntry> {
+        self.tprops
+            .get(&prop_id)
+            .into_iter()
+            .flatten()
+            .map(|(t, _)| *t)
+            .collect()
+    }
+}
+
+impl TemporalGraph {
+    fn node_gid(&self, v: VID) -> GID {
+        self.storage.get_node(v).global_id.clone()
+    }
+
+    /// Copy the updates of edge `eid` for all its layers with additions or deletions
+    fn edge_layer_updates(&self, eid: EID) -> FxHashMap<usize, LayerUpdates> {
+        let mut edge = self.storage.get_edge_mut(eid);
+        let mut layers = FxHashMap::default();
+        for layer in 0..edge.internal_num_layers() {
+            let mut updates = LayerUpdates::default();
+            if let Some(additions) = edge.get_additions_mut(layer) {
+                updates.additions.extend(additions.iter());
+            }
+            if let Some(deletions) = edge.get_deletions_mut(layer) {
+                updates.deletions.extend(deletions.iter());
+            }
+            if updates.additions.is_empty() && updates.deletions.is_empty() {
+                continue;
+            }
+            if let Some(props) = edge.get_layer_mut(layer).and_then(|layer| layer.props()) {
+                for prop_id in props.temporal_prop_ids() {
+                    if let Some(t_prop) = props.temporal_prop(prop_id) {
+                        updates
+                            .tprops
+                            .insert(prop_id, t_prop.iter_inner().collect());
+                    }
+                }
+                for prop_id in props.const_prop_ids() {
+                    if let Some(prop) = props.const_prop(prop_id) {
+                        updates.cprops.insert(prop_id, prop.clone());
+                    }
+                }
+            }
+            layers.insert(layer, updates);
+        }
+        layers
+    }
+
+    /// Merge node `merged` into node `keep` and return the record of the merge.
+    ///
+    /// The updates and properties of `merged` that `keep` does not have already are added to
+    /// `keep`, existing constant properties of `keep` take precedence. The edges of `merged` are
+    /// merged into the corresponding edges of `keep` (which are created if necessary), edges
+    /// between the two nodes become self-loops. The merged node and its edges are hidden and the
+    /// ids that resolved to the merged node resolve to `keep` afterwards.
+    pub(crate) fn merge_nodes(&self, keep: VID, merged: VID) -> Result<NodeMerge, GraphError> {
+        let error = |reason: &str| GraphError::InvalidNodeMerge {
+            keep: self.node_gid(keep),
+            merge: self.node_gid(merged),
+            reason: reason.to_owned(),
+        };
+        if keep == merged {
+            return Err(error("a node cannot be merged into itself"));
+        }
+        for v in [keep, merged] {
+            if self.storage.get_node(v).removed {
+                return Err(error("the node was removed or merged"));
+            }
+        }
+        let mut record = NodeMerge {
+            keep,
+            merged,
+            ..Default::default()
+        };
+        let source_edges = {
+            let nodes = self.storage.pair_node_mut(keep, merged);
+            let keep_node = nodes.get_i();
+            let merged_node = nodes.get_j();
+            record.times = merged_node
+                .timestamps()
+                .iter()
+                .filter(|t| !keep_node.timestamps().contains(*t..t.saturating_add(1)))
+                .collect();
+            if let Some(props) = merged_node.props.as_ref() {
+                let keep_props = keep_node.props.as_ref();
+                for prop_id in props.temporal_prop_ids() {
+                    let existing: BTreeSet<_> = keep_props
+                        .and_then(|props| props.temporal_prop(prop_id))
+                        .into_iter()
+                        .flat_map(|t_prop| t_prop.iter_inner().map(|(t, _)| t))
+                        .collect();
+                    record.tprops.extend(
+                        props
+                            .temporal_prop(prop_id)
+                            .into_iter()
+                            .flat_map(|t_prop| t_prop.iter_inner())
+                            .filter(|(t, _)| !existing.contains(t))
+                            .map(|(t, _)| (t, prop_id)),
+                    );
+                }
+                record.cprops = props
+                    .const_prop_ids()
+                    .filter(|prop_id| {
+                        keep_props
+                            .and_then(|props| props.const_prop(*prop_id))
+                            .is_none()
+                    })
+                    .collect();
+            }
+            merged_node
+                .edge_tuples(&LayerIds::All, Direction::BOTH)
+                .map(|e| e.pid())
+                .unique()
+                .collect_vec()
+        };
+
+        let mut targets: FxHashMap<(VID, VID), (EID, bool)> = FxHashMap::default();
+        for source in source_edges {
+            let (src, dst) = {
+                let edge = self.storage.edge_entry(source);
+                let edge = edge.as_mem_edge();
+                if edge.edge_store().removed {
+                    continue;
+                }
+                let map = |v: VID| if v == merged { keep } else { v };
+                (map(edge.edge_store().src), map(edge.edge_store().dst))
+            };
+            let (target, created) = *targets.entry((src, dst)).or_insert_with(|| {
+                let existing = self
+                    .storage
+                    .get_node(src)
+                    .find_edge_eid(dst, &LayerIds::All);
+                match existing {
+                    Some(eid) => (eid, false),
+                    None => (
+                        self.storage
+                            .push_edge(EdgeStore::new(src, dst))
+                            .edge_store()
+                            .eid,
+                        true,
+                    ),
+                }
+            });
+            let target_layers = self.edge_layer_updates(target);
+            let mut layers = vec![];
+            for (layer, updates) in self
+                .edge_layer_updates(source)
+                .into_iter()
+                .sorted_by_key(|(layer, _)| *layer)
+            {
+                let existing = target_layers.get(&layer);
+                let is_new = |t: &TimeIndexEntry, existing: Option<&BTreeSet<TimeIndexEntry>>| {
+                    !existing.is_some_and(|existing| existing.contains(t))
+                };
+                let mut layer_merge = LayerMerge {
+                    layer,
+                    additions: updates
+                        .additions
+                        .iter()
+                        .filter(|t| is_new(*t, existing.map(|e| &e.additions)))
+                        .copied()
+                        .collect(),
+                    deletions: updates
+                        .deletions
+                        .iter()
+                        .filter(|t| is_new(*t, existing.map(|e| &e.deletions)))
+                        .copied()
+                        .collect(),
+                    ..Default::default()
+                };
+                for (prop_id, values) in updates.tprops.iter().sorted_by_key(|(id, _)| **id) {
+                    let existing_times = existing.map(|e| e.tprop_times(*prop_id));
+                    layer_merge.tprops.extend(
+                        values
+                            .iter()
+                            .filter(|(t, _)| is_new(t, existing_times.as_ref()))
+                            .map(|(t, _)| (*t, *prop_id)),
+                    );
+                }
+                layer_merge.cprops = updates
+                    .cprops
+                    .keys()
+                    .filter(|prop_id| !existing.is_some_and(|e| e.cprops.contains_key(prop_id)))
+                    .copied()
+                    .sorted()
+                    .collect();
+                layers.push(layer_merge);
+            }
+            record.edges.push(EdgeMerge {
+                source,
+                target,
+                created,
+                layers,
+            });
+        }
+        self.apply_node_merge(record.clone())?;
+        Ok(record)
+    }
+
+    /// Apply the merge described by `record`, copying the recorded updates from the merged node
+    /// and its edges.
+    ///
+    /// Decoding a graph replays the merges of its snapshot before the updates of the nodes. In
+    /// that case nothing is copied as the snapshot already contains the copied updates and the
+    /// merge only hides the merged node and updates the id mapping.
+    pub(crate) fn apply_node_merge(&self, record: NodeMerge) -> Result<(), GraphError> {
+        {
+            let mut nodes = self.storage.pair_node_mut(record.keep, record.merged);
+            let merged_node = nodes.get_j();
+            let tprops: BTreeSet<_> = record.tprops.iter().copied().collect();
+            let mut values = vec![];
+            let mut cprops = vec![];
+            if let Some(props) = merged_node.props.as_ref() {
+                for prop_id in record.tprops.iter().map(|(_, id)| *id).unique() {
+                    values.extend(
+                        props
+                            .temporal_prop(prop_id)
+                            .into_iter()
+                            .flat_map(|t_prop| t_prop.iter_inner())
+                            .filter(|(t, _)| tprops.contains(&(*t, prop_id)))
+                            .map(|(t, prop)| (t, prop_id, prop)),
+                    );
+                }
+                cprops.extend(record.cprops.iter().filter_map(|prop_id| {
+                    props
+                        .const_prop(*prop_id)
+                        .map(|prop| (*prop_id, prop.clone()))
+                }));
+            }
+            let keep_node = nodes.get_mut_i();
+            for t in record.times.iter() {
+                keep_node.timestamps_mut().insert(*t);
+            }
+            for (t, prop_id, prop) in values {
+                keep_node.add_prop(t, prop_id, prop)?;
+            }
+            for (prop_id, prop) in cprops {
+                keep_node.update_constant_prop(prop_id, prop)?;
+            }
+            let merged_node = nodes.get_mut_j();
+            if !merged_node.removed {
+                merged_node.removed = true;
+                self.num_removed_nodes.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        for edge in record.edges.iter() {
+            let source_layers = self.edge_layer_updates(edge.source);
+            for layer in edge.layers.iter() {
+                let Some(updates) = source_layers.get(&layer.layer) else {
+                    continue;
+                };
+                let Some(t) = updates
+                    .additions
+                    .first()
+                    .or(updates.deletions.first())
+                    .copied()
+                else {
+                    continue;
+                };
+                let tprops: BTreeSet<_> = layer.tprops.iter().copied().collect();
+                self.link_edge(edge.target, t, layer.layer, |target| {
+                    for t in layer.additions.iter() {
+                        target.additions_mut(layer.layer).insert(*t);
+                    }
+                    for t in layer.deletions.iter() {
+                        target.deletions_mut(layer.layer).insert(*t);
+                    }
+                    let target_layer = target.layer_mut(layer.layer);
+                    for (prop_id, values) in updates.tprops.iter() {
+                        for (t, prop) in values {
+                            if tprops.contains(&(*t, *prop_id)) {
+                                target_layer.add_prop(*t, *prop_id, prop.clone())?;
+                            }
+                        }
+                    }
+                    for prop_id in layer.cprops.iter() {
+                        if let Some(prop) = updates.cprops.get(prop_id) {
+                            target_layer.update_constant_prop(*prop_id, prop.clone())?;
+                        }
+                    }
+                    Ok(())
+                })?;
+            }
+            let mut source = self.storage.get_edge_mut(edge.source);
+            let source = source.edge_store_mut();
+            if !source.removed {
+                source.removed = true;
+                self.num_removed_edges.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+
+        // the ids of the merged node and of the nodes that were merged into it now resolve to
+        // the node that is kept
+        for v in iter::once(record.merged).chain(self.node_merges.merged_nodes_of(record.merged)) {
+            let gid = self.node_gid(v);
+            self.logical_to_physical
+                .alias((&gid).into(), record.merged, record.keep)?;
+        }
+        let gid = self.node_gid(record.merged);
+        self.node_merges.push(record, gid);
+        Ok(())
+    }
+
+    /// Undo the merge of node `merged`.
+    ///
+    /// The updates that were added by the merge are removed from the node it was merged into and
+    /// from its edges, and the merged node and its edges are restored. Merges into the same node
+    /// need to be undone in reverse order.
+    pub(crate) fn unmerge_node(&self, merged: VID) -> Result<(), GraphError> {
+        let error = |reason: &str| GraphError::InvalidNodeUnmerge {
+            node: self.node_gid(merged),
+            reason: reason.to_owned(),
+        };
+        let keep = self
+            .node_merges
+            .merged_into(merged)
+            .ok_or_else(|| error("the node was not merged"))?;
+        if self.storage.get_node(keep).removed {
+            return Err(error(
+                "the node it was merged into was removed or merged into another node",
+            ));
+        }
+        if self.node_merges.last_merged_into(keep) != Some(merged) {
+            return Err(error(
+                "later merges into the same node need to be undone first",
+            ));
+        }
+        let record = self
+            .node_merges
+            .remove(merged, &self.node_gid(merged))
+            .ok_or_else(|| error("the node was not merged"))?;
+
+        {
+            let mut nodes = self.storage.pair_node_mut(record.keep, record.merged);
+            let keep_node = nodes.get_mut_i();
+            let times: BTreeSet<_> = record.times.iter().copied().collect();
+            keep_node.timestamps_mut().retain(|t| !times.contains(t));
+            if let Some(props) = keep_node.props.as_mut() {
+                let tprops: BTreeSet<_> = record.tprops.iter().copied().collect();
+                for prop_id in record.tprops.iter().map(|(_, id)| *id).unique() {
+                    props.retain_temporal(Some(prop_id), &|t| !tprops.contains(&(t, prop_id)))?;
+                }
+                for prop_id in record.cprops.iter() {
+                    props.remove_constant_prop(*prop_id);
+                }
+            }
+            nodes.get_mut_j().removed = false;
+            self.num_removed_nodes.fetch_sub(1, Ordering::Relaxed);
+        }
+
+        for edge in record.edges.iter() {
+            let mut empty_layers = vec![];
+            {
+                let mut target = self.storage.get_edge_mut(edge.target);
+                for layer in edge.layers.iter() {
+                    let additions: BTreeSet<_> = layer.additions.iter().copied().collect();
+                    let deletions: BTreeSet<_> = layer.deletions.iter().copied().collect();
+                    let tprops: BTreeSet<_> = layer.tprops.iter().copied().collect();
+                    if let Some(t_index) = target.get_additions_mut(layer.layer) {
+                        t_index.retain(|t| !additions.contains(t));
+                    }
+                    if let Some(t_index) = target.get_deletions_mut(layer.layer) {
+                        t_index.retain(|t| !deletions.contains(t));
+                    }
+                    if let Some(props) = target
+                        .get_layer_mut(layer.layer)
+                        .and_then(|layer| layer.props_mut())
+                    {
+                        for prop_id in layer.tprops.iter().map(|(_, id)| *id).unique() {
+                            props.retain_temporal(Some(prop_id), &|t| {
+                                !tprops.contains(&(t, prop_id))
+                            })?;
+                        }
+                        for prop_id in layer.cprops.iter() {
+                            props.remove_constant_prop(*prop_id);
+                        }
+                    }
+                    let is_empty = |t_index: Option<&mut TimeIndex<TimeIndexEntry>>| {
+                        t_index.map_or(true, |t_index| t_index.is_empty())
+                    };
+                    if is_empty(target.get_additions_mut(layer.layer))
+                        && is_empty(target.get_deletions_mut(layer.layer))
+                    {
+                        empty_layers.push(layer.layer);
+                    }
+                }
+            }
+            // layers without updates are removed from the adjacency lists, the edge is removed
+            // once it has no layers left
+            for layer in empty_layers {
+                self.remove_edge(edge.target, &LayerIds::One(layer));
+            }
+            let mut source = self.storage.get_edge_mut(edge.source);
+            source.edge_store_mut().removed = false;
+            self.num_removed_edges.fetch_sub(1, Ordering::Relaxed);
+        }
+
+        for v in iter::once(merged).chain(self.node_merges.merged_nodes_of(merged)) {
+            let gid = self.node_gid(v);
+            self.logical_to_physical
+                .alias((&gid).into(), record.keep, merged)?;
+        }
+        self.recompute_time_bounds();
+        Ok(())
+    }
+}
@@ -1,4 +1,4 @@
-use super::logical_to_physical::Mapping;
+use super::{logical_to_physical::Mapping, node_merge::NodeMerges};
 use crate::{
     core::{
         entities::{
@@ -7,35 +7,40 @@ use crate::{
                 tgraph_storage::GraphStorage,
                 timer::{MaxCounter, MinCounter, TimeCounterTrait},
             },
+            hyperedges::{HyperedgeStore, Hyperedges},
             nodes::{node_ref::NodeRef, node_store::NodeStore},
             properties::{graph_meta::GraphMeta, props::Meta},
             LayerIds, EID, VID,
         },
         storage::{
             raw_edges::EdgeWGuard,
-            timeindex::{AsTime, TimeIndexEntry},
+            timeindex::{AsTime, TimeIndex, TimeIndexEntry, TimeIndexOps},
             PairEntryMut,
         },
         utils::errors::GraphError,
-        Direction, Prop,
+        Direction, Prop, PropType,
     },
     db::api::{storage::graph::edges::edge_storage_ops::EdgeStorageOps, view::Layer},
 };
 use dashmap::DashSet;
 use itertools::Itertools;
 use raphtory_api::core::{
-    entities::{edges::edge_ref::EdgeRef, GidRef},
+    entities::{edges::edge_ref::EdgeRef, GidRef, GID},
     input::input_node::InputNode,
     storage::{arc_str::ArcStr, dict_mapper::MaybeNew},
 };
 use rustc_hash::FxHasher;
 use serde::{Deserialize, Serialize};
 use std::{
-    collections::HashMap,
+    collections::{BTreeSet, HashMap},
     fmt::Debug,
     hash::BuildHasherDefault,
     iter,
-    sync::{atomic::AtomicUsize, Arc},
+    ops::Range,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
 };
 
 pub(crate) type FxDashSet<K> = DashSet<K, BuildHasherDefault<FxHasher>>;
@@ -64,6 +69,24 @@ pub struct TemporalGraph {
 
     // graph properties
     pub(crate) graph_meta: GraphMeta,
+
+    // number of nodes and edges that were removed and are kept as empty tombstones in storage
+    #[serde(default)]
+    pub(crate) num_removed_nodes: AtomicUsize,
+    #[serde(default)]
+    pub(crate) num_removed_edges: AtomicUsize,
+
+    // multi-party events that connect an arbitrary number of nodes
+    #[serde(default)]
+    pub(crate) hyperedges: Hyperedges,
+
+    // records of merged nodes, used to resolve their ids and to undo merges
+    #[serde(default)]
+    pub(crate) node_merges: NodeMerges,
+
+    // incremented on every mutation, used to detect whether the graph changed
+    #[serde(skip)]
+    pub(crate) version: AtomicUsize,
 }
 
 impl std::fmt::Display for TemporalGraph {
@@ -95,6 +118,11 @@ impl TemporalGraph {
             node_meta: Arc::new(Meta::new()),
             edge_meta: Arc::new(Meta::new()),
             graph_meta: GraphMeta::new(),
+            num_removed_nodes: AtomicUsize::new(0),
+            num_removed_edges: AtomicUsize::new(0),
+            hyperedges: Hyperedges::default(),
+            node_merges: NodeMerges::default(),
+            version: AtomicUsize::new(0),
         }
     }
 
@@ -312,6 +340,27 @@ impl TemporalGraph {
     }
 
     #[inline]
+    pub(crate) fn add_hyperedge(
+        &self,
+        t: TimeIndexEntry,
+        id: GID,
+        participants: &[(VID, Option<usize>)],
+        props: &[(usize, Prop)],
+    ) -> Result<MaybeNew<usize>, GraphError> {
+        let hyperedge = HyperedgeStore {
+            id,
+            time: t,
+            participants: participants.to_vec(),
+            props: props
+                .iter()
+                .map(|(id, prop)| (*id, self.process_prop_value(prop)))
+                .collect(),
+        };
+        let hid = self.hyperedges.insert(hyperedge)?;
+        self.update_time(t);
+        Ok(hid)
+    }
+
     pub(crate) fn update_time(&self, time: TimeIndexEntry) {
         let t = time.t();
         self.earliest_time.update(t);
@@ -387,6 +436,335 @@ impl TemporalGraph {
         }
     }
 
+    /// Set the type of node property `prop_id` to `dtype` and replace its values with the result
+    /// of `map`
+    pub(crate) fn migrate_node_property(
+        &self,
+        prop_id: usize,
+        is_static: bool,
+        dtype: PropType,
+        map: impl Fn(Prop) -> Result<Prop, GraphError>,
+    ) -> Result<(), GraphError> {
+        let meta = if is_static {
+            self.node_meta.const_prop_meta()
+        } else {
+            self.node_meta.temporal_prop_meta()
+        };
+        meta.set_dtype(prop_id, dtype);
+        let map = |prop| Ok(self.process_prop_value(&map(prop)?));
+        for vid in 0..self.storage.nodes_len() {
+            let mut node = self.storage.get_node_mut(VID(vid));
+            if let Some(props) = node.props.as_mut() {
+                props.migrate_prop(prop_id, is_static, &map)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Set the type of edge property `prop_id` to `dtype` and replace its values in all layers
+    /// with the result of `map`
+    pub(crate) fn migrate_edge_property(
+        &self,
+        prop_id: usize,
+        is_static: bool,
+        dtype: PropType,
+        map: impl Fn(Prop) -> Result<Prop, GraphError>,
+    ) -> Result<(), GraphError> {
+        let meta = if is_static {
+            self.edge_meta.const_prop_meta()
+        } else {
+            self.edge_meta.temporal_prop_meta()
+        };
+        meta.set_dtype(prop_id, dtype);
+        let map = |prop| Ok(self.process_prop_value(&map(prop)?));
+        for eid in 0..self.storage.edges_len() {
+            let mut edge = self.storage.get_edge_mut(EID(eid));
+            for layer in edge.layers_mut() {
+                if let Some(props) = layer.props_mut() {
+                    props.migrate_prop(prop_id, is_static, &map)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    #[inline]
+    pub(crate) fn has_removed_nodes(&self) -> bool {
+        self.num_removed_nodes.load(Ordering::Relaxed) > 0
+    }
+
+    #[inline]
+    pub(crate) fn has_removed_edges(&self) -> bool {
+        self.num_removed_edges.load(Ordering::Relaxed) > 0
+    }
+
+    /// Remove the node `vid` together with all its edges.
+    ///
+    /// The data of the node is dropped and the node is kept as an empty tombstone such that the
+    /// ids of the other nodes do not change. Adding an update for the same global id later creates
+    /// a new node.
+    pub(crate) fn remove_node(&self, vid: VID) {
+        let edges = {
+            let node = self.storage.get_node(vid);
+            if node.removed {
+                return;
+            }
+            node.edge_tuples(&LayerIds::All, Direction::BOTH)
+                .map(|e| e.pid())
+                .collect_vec()
+        };
+        for eid in edges {
+            self.remove_edge(eid, &LayerIds::All);
+        }
+        // the ids of nodes that were merged into this node no longer resolve and the merges can
+        // no longer be undone
+        for merged in self.node_merges.merged_nodes_of(vid) {
+            let gid = self.storage.get_node(merged).global_id.clone();
+            self.logical_to_physical.remove((&gid).into(), vid);
+            self.node_merges.remove(merged, &gid);
+            self.hyperedges.remove_node(merged);
+        }
+        self.hyperedges.remove_node(vid);
+        let mut node = self.storage.get_node_mut(vid);
+        self.logical_to_physical
+            .remove((&node.global_id).into(), vid);
+        node.clear();
+        self.num_removed_nodes.fetch_add(1, Ordering::Relaxed);
+    }
+
+    /// Remove the updates and properties of edge `eid` in `layer_ids`.
+    ///
+    /// The edge is marked as removed once it has no updates left in any layer. The times of the
+    /// removed updates are dropped from the history of the endpoints unless the endpoints have
+    /// other edges or property updates at the same time.
+    pub(crate) fn remove_edge(&self, eid: EID, layer_ids: &LayerIds) {
+        let (src, dst) = {
+            let edge = self.storage.edges.get_edge(eid);
+            let edge = edge.as_mem_edge();
+            (edge.src(), edge.dst())
+        };
+        let times = {
+            // need to get the node pair first to avoid deadlocks with link_nodes
+            let mut node_pair = self.storage.pair_node_mut(src, dst);
+            let mut edge = self.storage.get_edge_mut(eid);
+            if edge.edge_store().removed {
+                return;
+            }
+            let layers = match layer_ids {
+                LayerIds::None => vec![],
+                LayerIds::All => (0..self.num_layers().max(edge.internal_num_layers())).collect(),
+                LayerIds::One(id) => vec![*id],
+                LayerIds::Multiple(ids) => ids.iter().copied().collect(),
+            };
+            let mut times = BTreeSet::new();
+            for layer in layers {
+                if let Some(additions) = edge.get_additions_mut(layer) {
+                    times.extend(additions.iter().map(|t| t.t()));
+                }
+                if let Some(deletions) = edge.get_deletions_mut(layer) {
+                    times.extend(deletions.iter().map(|t| t.t()));
+                }
+                edge.clear_layer(layer);
+                node_pair
+                    .get_mut_i()
+                    .remove_edge(dst, Direction::OUT, layer);
+                node_pair.get_mut_j().remove_edge(src, Direction::IN, layer);
+            }
+            if !edge.has_updates() {
+                edge.edge_store_mut().removed = true;
+                self.num_removed_edges.fetch_add(1, Ordering::Relaxed);
+            }
+            times
+        };
+        self.remove_node_times(src, &times);
+        if dst != src {
+            self.remove_node_times(dst, &times);
+        }
+    }
+
+    /// Remove `times` from the history of node `vid` unless the node still has edge or property
+    /// updates at those times
+    fn remove_node_times(&self, vid: VID, times: &BTreeSet<i64>) {
+        let (Some(&start), Some(&last)) = (times.first(), times.last()) else {
+            return;
+        };
+        let w = start..last.saturating_add(1);
+        let mut node = self.storage.get_node_mut(vid);
+        if node.removed {
+            return;
+        }
+        let mut in_use = BTreeSet::new();
+        for e in node.edge_tuples(&LayerIds::All, Direction::BOTH) {
+            let edge = self.storage.edges.get_edge(e.pid());
+            let edge = edge.as_mem_edge();
+            for (_, additions, deletions) in edge.updates_iter(&LayerIds::All) {
+                in_use.extend(additions.range_t(w.clone()).iter_t());
+                in_use.extend(deletions.range_t(w.clone()).iter_t());
+            }
+        }
+        if let Some(props) = node.props.as_ref() {
+            for prop_id in props.temporal_prop_ids() {
+                in_use.extend(
+                    props
+                        .temporal_props_window(prop_id, w.start, w.end)
+                        .map(|(t, _)| t),
+                );
+            }
+        }
+        node.timestamps_mut()
+            .retain(|t| !times.contains(t) || in_use.contains(t));
+    }
+
+    /// Remove the updates of temporal node property `prop_id` (or all temporal properties if
+    /// `None`) in the window `w`
+    pub(crate) fn remove_node_property_updates(
+        &self,
+        vid: VID,
+        w: Range<i64>,
+        prop_id: Option<usize>,
+    ) -> Result<(), GraphError> {
+        let mut node = self.storage.get_node_mut(vid);
+        if let Some(props) = node.props.as_mut() {
+            props.retain_temporal(prop_id, &|t| !w.contains(&t.t()))?;
+        }
+        Ok(())
+    }
+
+    /// Remove the updates of temporal edge property `prop_id` (or all temporal properties if
+    /// `None`) in the window `w` and the layers `layer_ids`
+    pub(crate) fn remove_edge_property_updates(
+        &self,
+        eid: EID,
+        layer_ids: &LayerIds,
+        w: Range<i64>,
+        prop_id: Option<usize>,
+    ) -> Result<(), GraphError> {
+        let mut edge = self.storage.get_edge_mut(eid);
+        let num_layers = edge.internal_num_layers();
+        for layer in (0..num_layers).filter(|layer| layer_ids.contains(layer)) {
+            if let Some(props) = edge
+                .get_layer_mut(layer)
+                .and_then(|layer| layer.props_mut())
+            {
+                props.retain_temporal(prop_id, &|t| !w.contains(&t.t()))?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Remove the updates of temporal graph property `prop_id` (or all temporal properties if
+    /// `None`) in the window `w`
+    pub(crate) fn remove_graph_property_updates(
+        &self,
+        w: Range<i64>,
+        prop_id: Option<usize>,
+    ) -> Result<(), GraphError> {
+        self.graph_meta
+            .retain_temporal(prop_id, &|t| !w.contains(&t.t()))
+    }
+
+    /// Drop all history before `t` and remove the nodes and edges that have no updates left.
+    /// Entities that had no updates to begin with are kept, they are only created ahead of their
+    /// updates when replaying a log.
+    ///
+    /// If `keep_alive` is set, edges that are alive at `t` (i.e., their last update before `t` is
+    /// an addition) get an addition at `t` and the last property values before `t` are moved to
+    /// `t`, such that the state of the graph at `t` is preserved for persistent semantics.
+    pub(crate) fn truncate_before(&self, t: i64, keep_alive: bool) -> Result<(), GraphError> {
+        let cutoff = TimeIndexEntry::start(t);
+        for eid in 0..self.storage.edges_len() {
+            let eid = EID(eid);
+            let is_empty = {
+                let mut edge = self.storage.get_edge_mut(eid);
+                if edge.edge_store().removed || !edge.has_updates() {
+                    continue;
+                }
+                for layer in 0..edge.internal_num_layers() {
+                    let alive = keep_alive && {
+                        let last_before = |t_index: Option<&mut TimeIndex<TimeIndexEntry>>| {
+                            t_index.and_then(|t_index| {
+                                t_index.iter().take_while(|ti| *ti < cutoff).last()
+                            })
+                        };
+                        let last_addition = last_before(edge.get_additions_mut(layer));
+                        let last_deletion = last_before(edge.get_deletions_mut(layer));
+                        last_addition.is_some() && last_addition > last_deletion
+                    };
+                    if let Some(additions) = edge.get_additions_mut(layer) {
+                        additions.retain(|ti| *ti >= cutoff);
+                        if alive {
+                            additions.insert(cutoff);
+                        }
+                    }
+                    if let Some(deletions) = edge.get_deletions_mut(layer) {
+                        deletions.retain(|ti| *ti >= cutoff);
+                    }
+                    if let Some(props) = edge
+                        .get_layer_mut(layer)
+                        .and_then(|layer| layer.props_mut())
+                    {
+                        props.truncate_before(cutoff, alive)?;
+                    }
+                }
+                !edge.has_updates()
+            };
+            if is_empty {
+                self.remove_edge(eid, &LayerIds::All);
+            }
+        }
+        for vid in 0..self.storage.nodes_len() {
+            let vid = VID(vid);
+            let is_empty = {
+                let mut node = self.storage.get_node_mut(vid);
+                if node.removed {
+                    continue;
+                }
+                let timestamps = node.timestamps_mut();
+                if timestamps.is_empty() {
+                    continue;
+                }
+                let had_history = timestamps.first_t().is_some_and(|first| first < t);
+                timestamps.retain(|ti| *ti >= t);
+                if keep_alive && had_history {
+                    timestamps.insert(t);
+                }
+                if let Some(props) = node.props.as_mut() {
+                    props.truncate_before(cutoff, keep_alive)?;
+                }
+                node.timestamps().is_empty()
+            };
+            if is_empty {
+                self.remove_node(vid);
+            }
+        }
+        self.graph_meta.truncate_before(cutoff, keep_alive)?;
+        self.hyperedges.retain(|hyperedge| hyperedge.time >= cutoff);
+        self.recompute_time_bounds();
+        Ok(())
+    }
+
+    /// Recompute the earliest and latest time of the graph after history was removed
+    pub(crate) fn recompute_time_bounds(&self) {
+        let mut earliest = i64::MAX;
+        let mut latest = i64::MIN;
+        for vid in 0..self.storage.nodes_len() {
+            let node = self.storage.get_node(VID(vid));
+            if let (Some(first), Some(last)) = (node.timestamps().first(), node.timestamps().last())
+            {
+                earliest = earliest.min(first);
+                latest = latest.max(last);
+            }
+        }
+        for (_, t_prop) in self.graph_meta.temporal_props() {
+            for (t, _) in t_prop.iter_t() {
+                earliest = earliest.min(t);
+                latest = latest.max(t);
+            }
+        }
+        self.earliest_time.set(earliest);
+        self.latest_time.set(latest);
+    }
+
     /// Checks if the same string value already exists and returns a pointer to the same existing value if it exists,
     /// otherwise adds the string to the pool.
     fn resolve_str(&self, value: &ArcStr) -> ArcStr {
@@ -20,6 +20,11 @@ pub(crate) trait TimeCounterTrait {
         }
     }
     fn get(&self) -> i64;
+
+    /// Overwrite the value of the counter, e.g., when history was removed
+    fn set(&self, value: i64) {
+        self.counter().store(value, Ordering::Relaxed)
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug)]
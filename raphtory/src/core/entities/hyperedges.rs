@@ -0,0 +1,306 @@
+//! Storage for temporal hyperedges, i.e., events at a single point in time that connect an
+//! arbitrary set of nodes.
+
+use crate::core::{
+    entities::{properties::props::PropMapper, VID},
+    storage::timeindex::TimeIndexEntry,
+    utils::errors::GraphError,
+    Prop, PropType,
+};
+use parking_lot::{Mutex, RwLock, RwLockReadGuard};
+use raphtory_api::core::{
+    entities::GID,
+    storage::dict_mapper::{DictMapper, MaybeNew},
+};
+use rustc_hash::{FxHashMap, FxHashSet};
+use serde::{Deserialize, Serialize};
+
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct HyperedgeStore {
+    pub id: GID,
+    pub time: TimeIndexEntry,
+    /// the participating nodes with their optional role id, a node can take part more than once
+    /// with different roles
+    pub participants: Vec<(VID, Option<usize>)>,
+    pub props: Vec<(usize, Prop)>,
+}
+
+impl HyperedgeStore {
+    /// The roles of node `v` in this hyperedge, `None` if `v` participates without a role
+    pub fn roles(&self, v: VID) -> impl Iterator<Item = Option<usize>> + '_ {
+        self.participants
+            .iter()
+            .filter(move |(node, _)| *node == v)
+            .map(|(_, role)| *role)
+    }
+
+    pub fn prop(&self, prop_id: usize) -> Option<&Prop> {
+        self.props
+            .iter()
+            .find(|(id, _)| *id == prop_id)
+            .map(|(_, prop)| prop)
+    }
+}
+
+/// The hyperedges of a graph indexed by their internal id.
+///
+/// Internal ids are stable, removed hyperedges leave an empty slot behind.
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct HyperedgeEntries {
+    entries: Vec<Option<HyperedgeStore>>,
+    ids: FxHashMap<GID, usize>,
+    by_node: FxHashMap<VID, Vec<usize>>,
+    num_removed: usize,
+}
+
+impl HyperedgeEntries {
+    fn insert(&mut self, hyperedge: HyperedgeStore) -> usize {
+        let hid = self.entries.len();
+        self.ids.insert(hyperedge.id.clone(), hid);
+        for (v, _) in hyperedge.participants.iter() {
+            let hids = self.by_node.entry(*v).or_default();
+            if hids.last() != Some(&hid) {
+                hids.push(hid);
+            }
+        }
+        self.entries.push(Some(hyperedge));
+        hid
+    }
+
+    fn remove(&mut self, hid: usize) {
+        if let Some(hyperedge) = self.entries[hid].take() {
+            self.ids.remove(&hyperedge.id);
+            for (v, _) in hyperedge.participants.iter() {
+                if let Some(hids) = self.by_node.get_mut(v) {
+                    hids.retain(|other| *other != hid);
+                }
+            }
+            self.num_removed += 1;
+        }
+    }
+
+    fn remove_node(&mut self, v: VID) {
+        for hid in self.by_node.remove(&v).unwrap_or_default() {
+            if let Some(hyperedge) = self.entries[hid].as_mut() {
+                hyperedge.participants.retain(|(node, _)| *node != v);
+            }
+        }
+    }
+
+    /// The hyperedge with internal id `hid`, `None` if it was removed
+    #[inline]
+    pub fn get(&self, hid: usize) -> Option<&HyperedgeStore> {
+        self.entries.get(hid)?.as_ref()
+    }
+
+    #[inline]
+    pub fn find(&self, id: &GID) -> Option<usize> {
+        self.ids.get(id).copied()
+    }
+
+    /// The hyperedges node `v` participates in, in insertion order
+    #[inline]
+    pub fn of_node(&self, v: VID) -> &[usize] {
+        self.by_node
+            .get(&v)
+            .map(|hids| hids.as_slice())
+            .unwrap_or(&[])
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (usize, &HyperedgeStore)> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter_map(|(hid, hyperedge)| Some((hid, hyperedge.as_ref()?)))
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.entries.len() - self.num_removed
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+/// All hyperedges of a graph together with the mappings for their role and property names
+#[derive(Serialize, Deserialize, Debug, Default)]
+pub struct Hyperedges {
+    role_meta: DictMapper,
+    prop_meta: PropMapper,
+    entries: RwLock<HyperedgeEntries>,
+    /// ids of the hyperedges that are currently being added
+    #[serde(skip)]
+    reserved: Mutex<FxHashSet<GID>>,
+}
+
+/// Exclusive claim on the id of a hyperedge that is being added, released when dropped
+pub(crate) struct HyperedgeReservation<'a> {
+    hyperedges: &'a Hyperedges,
+    id: GID,
+}
+
+impl Drop for HyperedgeReservation<'_> {
+    fn drop(&mut self) {
+        self.hyperedges.reserved.lock().remove(&self.id);
+    }
+}
+
+impl Hyperedges {
+    #[inline]
+    pub fn role_meta(&self) -> &DictMapper {
+        &self.role_meta
+    }
+
+    #[inline]
+    pub fn prop_meta(&self) -> &PropMapper {
+        &self.prop_meta
+    }
+
+    pub(crate) fn resolve_role(&self, role: &str) -> MaybeNew<usize> {
+        self.role_meta.get_or_create_id(role)
+    }
+
+    pub(crate) fn resolve_property(
+        &self,
+        prop: &str,
+        dtype: PropType,
+    ) -> Result<MaybeNew<usize>, GraphError> {
+        self.prop_meta.get_or_create_and_validate(prop, dtype)
+    }
+
+    /// Claim `id` for a new hyperedge until the reservation is dropped.
+    ///
+    /// Fails if a hyperedge with the same id exists or is being added concurrently.
+    pub(crate) fn reserve(&self, id: &GID) -> Result<HyperedgeReservation<'_>, GraphError> {
+        // hold the read lock such that the id cannot be inserted in between the checks
+        let entries = self.entries.read();
+        let mut reserved = self.reserved.lock();
+        if entries.find(id).is_some() || !reserved.insert(id.clone()) {
+            return Err(GraphError::HyperedgeExistsError(id.clone()));
+        }
+        Ok(HyperedgeReservation {
+            hyperedges: self,
+            id: id.clone(),
+        })
+    }
+
+    /// Add a new hyperedge.
+    ///
+    /// Inserting an exact copy of an existing hyperedge is a no-op such that replaying updates
+    /// is harmless, any other hyperedge with an existing id is an error.
+    pub(crate) fn insert(&self, hyperedge: HyperedgeStore) -> Result<MaybeNew<usize>, GraphError> {
+        let mut entries = self.entries.write();
+        match entries.find(&hyperedge.id) {
+            Some(hid) if entries.get(hid) == Some(&hyperedge) => Ok(MaybeNew::Existing(hid)),
+            Some(_) => Err(GraphError::HyperedgeExistsError(hyperedge.id)),
+            None => Ok(MaybeNew::New(entries.insert(hyperedge))),
+        }
+    }
+
+    /// Remove the hyperedges for which `keep` returns `false`, the internal ids of the remaining
+    /// hyperedges do not change.
+    pub(crate) fn retain(&self, keep: impl Fn(&HyperedgeStore) -> bool) {
+        let mut entries = self.entries.write();
+        let removed = entries
+            .iter()
+            .filter(|(_, hyperedge)| !keep(hyperedge))
+            .map(|(hid, _)| hid)
+            .collect::<Vec<_>>();
+        for hid in removed {
+            entries.remove(hid);
+        }
+    }
+
+    /// Remove node `v` from the participants of all hyperedges, the hyperedges themselves are kept
+    pub(crate) fn remove_node(&self, v: VID) {
+        self.entries.write().remove_node(v);
+    }
+
+    #[inline]
+    pub fn read(&self) -> RwLockReadGuard<HyperedgeEntries> {
+        self.entries.read_recursive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::core::storage::timeindex::AsTime;
+
+    fn hyperedge(id: u64, t: i64, nodes: &[usize]) -> HyperedgeStore {
+        HyperedgeStore {
+            id: GID::U64(id),
+            time: TimeIndexEntry::start(t),
+            participants: nodes.iter().map(|v| (VID(*v), None)).collect(),
+            props: vec![],
+        }
+    }
+
+    #[test]
+    fn test_insert_is_idempotent() {
+        let hyperedges = Hyperedges::default();
+        assert!(matches!(
+            hyperedges.insert(hyperedge(1, 0, &[0, 1, 2])),
+            Ok(MaybeNew::New(0))
+        ));
+        assert!(matches!(
+            hyperedges.insert(hyperedge(1, 0, &[0, 1, 2])),
+            Ok(MaybeNew::Existing(0))
+        ));
+        assert!(hyperedges.insert(hyperedge(1, 1, &[0, 1])).is_err());
+        assert_eq!(hyperedges.read().len(), 1);
+    }
+
+    #[test]
+    fn test_node_index() {
+        let hyperedges = Hyperedges::default();
+        hyperedges.insert(hyperedge(1, 0, &[0, 1, 1])).unwrap();
+        hyperedges.insert(hyperedge(2, 1, &[1, 2])).unwrap();
+        assert_eq!(hyperedges.read().of_node(VID(1)), [0, 1]);
+        assert_eq!(hyperedges.read().of_node(VID(3)), [] as [usize; 0]);
+
+        hyperedges.retain(|h| h.time.t() > 0);
+        let entries = hyperedges.read();
+        assert_eq!(entries.of_node(VID(0)), [] as [usize; 0]);
+        assert_eq!(entries.of_node(VID(1)), [1]);
+        assert_eq!(entries.of_node(VID(2)), [1]);
+        assert_eq!(entries.find(&GID::U64(1)), None);
+    }
+
+    #[test]
+    fn test_ids_are_stable() {
+        let hyperedges = Hyperedges::default();
+        hyperedges.insert(hyperedge(1, 0, &[0, 1])).unwrap();
+        hyperedges.insert(hyperedge(2, 1, &[1, 2])).unwrap();
+        hyperedges.retain(|h| h.time.t() > 0);
+        assert!(matches!(
+            hyperedges.insert(hyperedge(3, 2, &[2])),
+            Ok(MaybeNew::New(2))
+        ));
+
+        let entries = hyperedges.read();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries.get(0), None);
+        assert_eq!(entries.find(&GID::U64(2)), Some(1));
+        assert_eq!(entries.get(1).map(|h| h.id.clone()), Some(GID::U64(2)));
+        assert_eq!(
+            entries.iter().map(|(hid, _)| hid).collect::<Vec<_>>(),
+            [1, 2]
+        );
+    }
+
+    #[test]
+    fn test_reserve_id() {
+        let hyperedges = Hyperedges::default();
+        hyperedges.insert(hyperedge(1, 0, &[0])).unwrap();
+        assert!(hyperedges.reserve(&GID::U64(1)).is_err());
+
+        let reservation = hyperedges.reserve(&GID::U64(2)).unwrap();
+        assert!(hyperedges.reserve(&GID::U64(2)).is_err());
+        drop(reservation);
+        assert!(hyperedges.reserve(&GID::U64(2)).is_ok());
+    }
+}
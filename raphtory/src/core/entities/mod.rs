@@ -4,6 +4,7 @@ use raphtory_api::core::entities::edges::edge_ref::EdgeRef;
 
 pub mod edges;
 pub mod graph;
+pub mod hyperedges;
 pub mod nodes;
 pub mod properties;
 
@@ -61,6 +62,34 @@ impl LayerIds {
         }
     }
 
+    pub fn union(&self, other: &LayerIds) -> LayerIds {
+        match (self, other) {
+            (LayerIds::All, _) | (_, LayerIds::All) => LayerIds::All,
+            (LayerIds::None, other) => other.clone(),
+            (this, LayerIds::None) => this.clone(),
+            (this, other) => {
+                // `Multiple` needs to be sorted for `find`
+                let mut ids: Vec<usize> = this.iter_ids().chain(other.iter_ids()).collect();
+                ids.sort_unstable();
+                ids.dedup();
+                match ids.len() {
+                    1 => LayerIds::One(ids[0]),
+                    _ => LayerIds::Multiple(ids.into()),
+                }
+            }
+        }
+    }
+
+    /// The ids of `One` and `Multiple`, nothing for `All` and `None`
+    fn iter_ids(&self) -> impl Iterator<Item = usize> + '_ {
+        let ids: &[usize] = match self {
+            LayerIds::One(id) => std::slice::from_ref(id),
+            LayerIds::Multiple(ids) => ids,
+            LayerIds::All | LayerIds::None => &[],
+        };
+        ids.iter().copied()
+    }
+
     pub fn diff<'a>(
         &self,
         graph: impl crate::prelude::GraphViewOps<'a>,
@@ -160,3 +189,21 @@ impl From<Arc<[usize]>> for LayerIds {
         LayerIds::Multiple(id)
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::LayerIds;
+
+    #[test]
+    fn test_layer_ids_union_is_sorted_and_deduplicated() {
+        let union = LayerIds::Multiple([3, 1].into()).union(&LayerIds::Multiple([1, 2].into()));
+        assert!(matches!(union, LayerIds::Multiple(ids) if *ids == [1, 2, 3]));
+        let union = LayerIds::One(1).union(&LayerIds::Multiple([2, 0].into()));
+        assert!(matches!(union, LayerIds::Multiple(ids) if *ids == [0, 1, 2]));
+        assert!(matches!(
+            LayerIds::One(1).union(&LayerIds::One(1)),
+            LayerIds::One(1)
+        ));
+        assert!(union.find(1).is_some());
+    }
+}
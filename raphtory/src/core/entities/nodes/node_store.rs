@@ -28,6 +28,10 @@ pub struct NodeStore {
     // props for node
     pub(crate) props: Option<Props>,
     pub(crate) node_type: usize,
+    // set if the node was removed from the graph or merged into another node, the data of
+    // removed nodes is cleared while merged nodes keep their data such that the merge can be undone
+    #[serde(default)]
+    pub(crate) removed: bool,
 }
 
 impl NodeStore {
@@ -41,6 +45,7 @@ impl NodeStore {
             layers,
             props: None,
             node_type: 0,
+            removed: false,
         }
     }
 
@@ -54,6 +59,7 @@ impl NodeStore {
             layers,
             props: None,
             node_type: 0,
+            removed: false,
         }
     }
 
@@ -65,6 +71,23 @@ impl NodeStore {
         &self.timestamps
     }
 
+    pub(crate) fn timestamps_mut(&mut self) -> &mut TimeIndex<i64> {
+        &mut self.timestamps
+    }
+
+    pub fn is_removed(&self) -> bool {
+        self.removed
+    }
+
+    /// Drop all the data of the node and mark it as removed
+    pub(crate) fn clear(&mut self) {
+        self.timestamps = TimeIndex::Empty;
+        self.layers = Vec::new();
+        self.props = None;
+        self.node_type = 0;
+        self.removed = true;
+    }
+
     pub fn update_time(&mut self, t: TimeIndexEntry) {
         self.timestamps.insert(t.t());
     }
@@ -134,6 +157,10 @@ impl NodeStore {
         }
     }
 
+    pub(crate) fn remove_edge(&mut self, v_id: VID, dir: Direction, layer: usize) -> Option<EID> {
+        self.layers.get_mut(layer)?.remove_edge(v_id, dir)
+    }
+
     #[inline]
     pub(crate) fn edge_tuples<'a>(
         &'a self,
@@ -58,6 +58,27 @@ impl Adj {
         }
     }
 
+    /// Remove the edge to or from `v` in direction `dir` and return its id
+    pub(crate) fn remove_edge(&mut self, v: VID, dir: Direction) -> Option<EID> {
+        match self {
+            Adj::Solo => None,
+            Adj::List { out, into } => {
+                let eid = match dir {
+                    Direction::OUT => out.remove(v),
+                    Direction::IN => into.remove(v),
+                    Direction::BOTH => {
+                        let out_eid = out.remove(v);
+                        into.remove(v).or(out_eid)
+                    }
+                };
+                if out.is_empty() && into.is_empty() {
+                    *self = Adj::Solo;
+                }
+                eid
+            }
+        }
+    }
+
     pub(crate) fn iter(&self, dir: Direction) -> Box<dyn Iterator<Item = (VID, EID)> + Send + '_> {
         match self {
             Adj::Solo => Box::new(std::iter::empty()),
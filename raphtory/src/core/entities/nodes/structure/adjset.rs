@@ -85,6 +85,32 @@ impl<K: Ord + Copy + Hash + Send + Sync, V: Into<usize> + Copy + Send + Sync> Ad
         }
     }
 
+    /// Remove the neighbour `v` and return the metadata of its edge
+    pub fn remove(&mut self, v: K) -> Option<V> {
+        match self {
+            AdjSet::Empty => None,
+            AdjSet::One(vv, e) => {
+                if *vv == v {
+                    let e = *e;
+                    *self = AdjSet::Empty;
+                    Some(e)
+                } else {
+                    None
+                }
+            }
+            AdjSet::Small { vs, edges } => {
+                let i = vs.binary_search(&v).ok()?;
+                vs.remove(i);
+                let e = edges.remove(i);
+                if vs.is_empty() {
+                    *self = AdjSet::Empty;
+                }
+                Some(e)
+            }
+            AdjSet::Large { vs } => vs.remove(&v),
+        }
+    }
+
     pub fn iter(&self) -> Box<dyn Iterator<Item = (K, V)> + Send + '_> {
         match self {
             AdjSet::Empty => Box::new(std::iter::empty()),
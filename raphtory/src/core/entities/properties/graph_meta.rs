@@ -98,6 +98,33 @@ impl GraphMeta {
         (*prop_entry).set(t, prop)
     }
 
+    /// Only keep the updates of temporal property `prop_id` (or of all temporal properties if
+    /// `prop_id` is `None`) for which `keep` returns `true`
+    pub(crate) fn retain_temporal(
+        &self,
+        prop_id: Option<usize>,
+        keep: &impl Fn(TimeIndexEntry) -> bool,
+    ) -> Result<(), GraphError> {
+        for mut entry in self.temporal.iter_mut() {
+            if prop_id.map_or(true, |id| id == *entry.key()) {
+                entry.value_mut().retain(keep)?;
+            }
+        }
+        self.temporal
+            .retain(|_, t_prop| !matches!(t_prop, TProp::Empty));
+        Ok(())
+    }
+
+    /// Drop the temporal property updates before `t`, see [TProp::truncate_before]
+    pub(crate) fn truncate_before(&self, t: TimeIndexEntry, carry: bool) -> Result<(), GraphError> {
+        for mut entry in self.temporal.iter_mut() {
+            entry.value_mut().truncate_before(t, carry)?;
+        }
+        self.temporal
+            .retain(|_, t_prop| !matches!(t_prop, TProp::Empty));
+        Ok(())
+    }
+
     pub(crate) fn get_constant(&self, id: usize) -> Option<Prop> {
         let entry = self.constant.get(&id)?;
         entry.as_ref().cloned()
@@ -64,6 +64,64 @@ impl Props {
         })
     }
 
+    /// Remove the value of constant property `prop_id`
+    pub(crate) fn remove_constant_prop(&mut self, prop_id: usize) {
+        if let Some(prop) = self.constant_props.get_mut(prop_id) {
+            *prop = None;
+        }
+    }
+
+    /// Replace the values of property `prop_id` with the result of `map`
+    pub(crate) fn migrate_prop(
+        &mut self,
+        prop_id: usize,
+        is_static: bool,
+        map: &impl Fn(Prop) -> Result<Prop, GraphError>,
+    ) -> Result<(), GraphError> {
+        if is_static {
+            if let Some(Some(prop)) = self.constant_props.get_mut(prop_id) {
+                *prop = map(prop.clone())?;
+            }
+        } else if let Some(t_prop) = self.temporal_props.get_mut(prop_id) {
+            t_prop.map_values(map)?;
+        }
+        Ok(())
+    }
+
+    /// Only keep the updates of temporal property `prop_id` (or of all temporal properties if
+    /// `prop_id` is `None`) for which `keep` returns `true`
+    pub(crate) fn retain_temporal(
+        &mut self,
+        prop_id: Option<usize>,
+        keep: &impl Fn(TimeIndexEntry) -> bool,
+    ) -> Result<(), GraphError> {
+        let prop_ids = match prop_id {
+            Some(prop_id) => vec![prop_id],
+            None => self.temporal_props.filled_ids().collect(),
+        };
+        for prop_id in prop_ids {
+            if let Some(t_prop) = self.temporal_props.get_mut(prop_id) {
+                t_prop.retain(keep)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Drop the temporal property updates before `t`, see [TProp::truncate_before]
+    pub(crate) fn truncate_before(
+        &mut self,
+        t: TimeIndexEntry,
+        carry: bool,
+    ) -> Result<(), GraphError> {
+        let prop_ids: Vec<_> = self.temporal_props.filled_ids().collect();
+        for prop_id in prop_ids {
+            if let Some(t_prop) = self.temporal_props.get_mut(prop_id) {
+                t_prop.truncate_before(t, carry)?;
+            }
+        }
+        Ok(())
+    }
+
     pub fn temporal_props(&self, prop_id: usize) -> Box<dyn Iterator<Item = (i64, Prop)> + '_> {
         let o = self.temporal_props.get(prop_id);
         if let Some(t_prop) = o {
@@ -322,6 +380,15 @@ impl PropMapper {
         dtypes[id] = dtype;
     }
 
+    /// Change the type of an existing property, the caller is responsible for migrating its values
+    pub(crate) fn set_dtype(&self, prop_id: usize, dtype: PropType) {
+        let mut dtypes = self.dtypes.write();
+        if dtypes.len() <= prop_id {
+            dtypes.resize(prop_id + 1, PropType::Empty);
+        }
+        dtypes[prop_id] = dtype;
+    }
+
     pub fn get_dtype(&self, prop_id: usize) -> Option<PropType> {
         self.dtypes.read_recursive().get(prop_id).copied()
     }
@@ -147,6 +147,61 @@ impl TProp {
         Ok(())
     }
 
+    /// Replace every value of the property with the result of `map`
+    pub(crate) fn map_values(
+        &mut self,
+        map: impl Fn(Prop) -> Result<Prop, GraphError>,
+    ) -> Result<(), GraphError> {
+        let old = std::mem::take(self);
+        for (t, value) in old.iter_inner() {
+            self.set(t, map(value)?)?;
+        }
+        Ok(())
+    }
+
+    /// Only keep the updates for which `keep` returns `true`
+    pub(crate) fn retain(
+        &mut self,
+        keep: impl Fn(TimeIndexEntry) -> bool,
+    ) -> Result<(), GraphError> {
+        let old = std::mem::take(self);
+        for (t, value) in old.iter_inner() {
+            if keep(t) {
+                self.set(t, value)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Drop the updates before `t`. If `carry` is set, the last value before `t` is moved to `t`
+    /// such that the value of the property at `t` is preserved.
+    pub(crate) fn truncate_before(
+        &mut self,
+        t: TimeIndexEntry,
+        carry: bool,
+    ) -> Result<(), GraphError> {
+        let old = std::mem::take(self);
+        let mut last = None;
+        for (ti, value) in old.iter_inner() {
+            if ti < t {
+                last = Some(value);
+            } else {
+                if let Some(last) = last.take() {
+                    if carry && ti != t {
+                        self.set(t, last)?;
+                    }
+                }
+                self.set(ti, value)?;
+            }
+        }
+        if let Some(last) = last {
+            if carry {
+                self.set(t, last)?;
+            }
+        }
+        Ok(())
+    }
+
     pub(crate) fn iter_inner(
         &self,
     ) -> Box<dyn Iterator<Item = (TimeIndexEntry, Prop)> + Send + '_> {
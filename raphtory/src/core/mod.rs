@@ -41,9 +41,6 @@ use std::{
     sync::Arc,
 };
 
-#[cfg(feature = "storage")]
-use crate::arrow2::datatypes::ArrowDataType as DataType;
-
 #[cfg(test)]
 extern crate core;
 
@@ -138,27 +135,6 @@ impl PropType {
     }
 }
 
-#[cfg(feature = "storage")]
-impl From<&DataType> for PropType {
-    fn from(value: &DataType) -> Self {
-        match value {
-            DataType::Utf8 => PropType::Str,
-            DataType::LargeUtf8 => PropType::Str,
-            DataType::UInt8 => PropType::U8,
-            DataType::UInt16 => PropType::U16,
-            DataType::Int32 => PropType::I32,
-            DataType::Int64 => PropType::I64,
-            DataType::UInt32 => PropType::U32,
-            DataType::UInt64 => PropType::U64,
-            DataType::Float32 => PropType::F32,
-            DataType::Float64 => PropType::F64,
-            DataType::Boolean => PropType::Bool,
-
-            _ => PropType::Empty,
-        }
-    }
-}
-
 /// Denotes the types of properties allowed to be stored in the graph.
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub enum Prop {
@@ -361,6 +337,19 @@ impl Prop {
         }
     }
 
+    /// The value of an integer property
+    pub fn as_i128(&self) -> Option<i128> {
+        match self {
+            Prop::U8(v) => Some(*v as i128),
+            Prop::U16(v) => Some(*v as i128),
+            Prop::U32(v) => Some(*v as i128),
+            Prop::U64(v) => Some(*v as i128),
+            Prop::I32(v) => Some(*v as i128),
+            Prop::I64(v) => Some(*v as i128),
+            _ => None,
+        }
+    }
+
     pub fn as_f64(&self) -> Option<f64> {
         match self {
             Prop::U8(v) => Some(*v as f64),
@@ -110,7 +110,7 @@ where
         }
     }
 
-    fn get_mut(&mut self, id: usize) -> Option<&mut A> {
+    pub(crate) fn get_mut(&mut self, id: usize) -> Option<&mut A> {
         match self {
             LazyVec::LazyVec1(only_id, value) if *only_id == id => Some(value),
             LazyVec::LazyVecN(vec) => vec.get_mut(id),
@@ -238,6 +238,61 @@ impl<'a> EdgeWGuard<'a> {
 
         &mut self.guard.props[layer_id][self.i]
     }
+
+    pub(crate) fn get_additions_mut(
+        &mut self,
+        layer_id: usize,
+    ) -> Option<&mut TimeIndex<TimeIndexEntry>> {
+        self.guard.additions.get_mut(layer_id)?.get_mut(self.i)
+    }
+
+    pub(crate) fn get_deletions_mut(
+        &mut self,
+        layer_id: usize,
+    ) -> Option<&mut TimeIndex<TimeIndexEntry>> {
+        self.guard.deletions.get_mut(layer_id)?.get_mut(self.i)
+    }
+
+    pub(crate) fn get_layer_mut(&mut self, layer_id: usize) -> Option<&mut EdgeLayer> {
+        self.guard.props.get_mut(layer_id)?.get_mut(self.i)
+    }
+
+    /// Drop the updates and properties of the edge in layer `layer_id`
+    pub(crate) fn clear_layer(&mut self, layer_id: usize) {
+        if let Some(additions) = self.get_additions_mut(layer_id) {
+            *additions = TimeIndex::Empty;
+        }
+        if let Some(deletions) = self.get_deletions_mut(layer_id) {
+            *deletions = TimeIndex::Empty;
+        }
+        if let Some(layer) = self.get_layer_mut(layer_id) {
+            *layer = EdgeLayer::default();
+        }
+    }
+
+    /// Check if the edge has additions or deletions in any layer
+    pub(crate) fn has_updates(&self) -> bool {
+        let i = self.i;
+        self.guard
+            .additions
+            .iter()
+            .chain(self.guard.deletions.iter())
+            .any(|layer| layer.get(i).is_some_and(|t_index| !t_index.is_empty()))
+    }
+
+    /// The number of layers allocated in the shard of the edge
+    pub(crate) fn internal_num_layers(&self) -> usize {
+        self.guard.internal_num_layers().max(self.guard.props.len())
+    }
+
+    /// The layers of the edge that have been allocated
+    pub(crate) fn layers_mut(&mut self) -> impl Iterator<Item = &mut EdgeLayer> + '_ {
+        let i = self.i;
+        self.guard
+            .props
+            .iter_mut()
+            .filter_map(move |layer| layer.get_mut(i))
+    }
 }
 
 #[derive(Debug)]
@@ -62,6 +62,26 @@ impl<T: AsTime> TimeIndex<T> {
         }
     }
 
+    /// Only keep the entries for which `keep` returns `true`
+    pub(crate) fn retain(&mut self, mut keep: impl FnMut(&T) -> bool) {
+        match self {
+            TimeIndex::Empty => {}
+            TimeIndex::One(t) => {
+                if !keep(t) {
+                    *self = TimeIndex::Empty;
+                }
+            }
+            TimeIndex::Set(ts) => {
+                ts.retain(|t| keep(t));
+                match ts.len() {
+                    0 => *self = TimeIndex::Empty,
+                    1 => *self = TimeIndex::One(*ts.first().expect("set has one element")),
+                    _ => {}
+                }
+            }
+        }
+    }
+
     #[allow(unused)]
     pub(crate) fn contains(&self, w: Range<i64>) -> bool {
         match self {
@@ -62,6 +62,8 @@ pub enum GraphError {
     GraphNotFound(PathBuf),
     #[error("Graph already exists by name = {0}")]
     GraphNameAlreadyExists(PathBuf),
+    #[error("A bulk load into {0} is already running")]
+    LoadAlreadyRunning(PathBuf),
     #[error("Immutable graph reference already exists. You can access mutable graph apis only exclusively.")]
     IllegalGraphAccess,
     #[error("Incorrect property given.")]
@@ -84,6 +86,12 @@ pub enum GraphError {
     #[error("Tried to mutate constant property {name}: old value {old:?}, new value {new:?}")]
     ConstantPropertyMutationError { name: ArcStr, old: Prop, new: Prop },
 
+    #[error("Update violates the graph schema: {0}")]
+    SchemaViolation(String),
+
+    #[error("Failed to migrate property {name}: {reason}")]
+    PropertyMigrationError { name: String, reason: String },
+
     #[error("Failed to parse time string")]
     ParseTime {
         #[from]
@@ -96,6 +104,34 @@ pub enum GraphError {
     #[error("Edge already exists for nodes {0:?} {1:?}")]
     EdgeExistsError(GID, GID),
 
+    #[error("Hyperedge already exists with ID {0:?}")]
+    HyperedgeExistsError(GID),
+
+    #[error("Invalid edge interval: end {end} is before start {start}")]
+    InvalidEdgeInterval { start: i64, end: i64 },
+
+    #[error("Merge conflict: {0}")]
+    MergeConflict(String),
+
+    #[error("Cannot combine views: {0}")]
+    IncompatibleViews(String),
+
+    #[error("Invalid computed property {name}: {reason}")]
+    InvalidComputedProperty { name: String, reason: String },
+
+    #[error("Failed to parse property expression {expr:?}: {reason}")]
+    PropExprParseError { expr: String, reason: String },
+
+    #[error("Cannot merge node {merge:?} into node {keep:?}: {reason}")]
+    InvalidNodeMerge {
+        keep: GID,
+        merge: GID,
+        reason: String,
+    },
+
+    #[error("Cannot unmerge node {node:?}: {reason}")]
+    InvalidNodeUnmerge { node: GID, reason: String },
+
     #[error("No Node with ID {0}")]
     NodeIdError(u64),
 
@@ -143,10 +179,22 @@ pub enum GraphError {
     )]
     ColumnDoesNotExist(String),
 
+    #[cfg(feature = "arrow")]
+    #[error("Property '{0}' has the same name as one of the exported columns")]
+    ReservedColumnName(String),
+
     #[cfg(feature = "storage")]
     #[error("Raphtory Arrow Error: {0}")]
     DiskGraphError(#[from] RAError),
 
+    #[cfg(feature = "storage")]
+    #[error("Properties of type {0:?} cannot be stored in a disk graph")]
+    UnsupportedDiskPropType(PropType),
+
+    #[cfg(feature = "storage")]
+    #[error("Edge ({src}, {dst}) is deleted in layer {layer} where it has no additions, this cannot be stored in a disk graph")]
+    UnsupportedDiskEdgeDeletion { src: GID, dst: GID, layer: String },
+
     #[cfg(feature = "search")]
     #[error("Index operation failed")]
     IndexError {
@@ -226,4 +274,6 @@ pub enum MutateGraphError {
     AmbiguousLayersError,
     #[error("Invalid Node id {0:?}")]
     InvalidNodeId(GID),
+    #[error("Node id {0:?} already resolves to another node")]
+    NodeIdAlreadyMapped(GID),
 }
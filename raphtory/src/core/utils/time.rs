@@ -1,5 +1,6 @@
 use crate::core::utils::time::error::{ParseTimeError::InvalidDateTimeString, *};
-use chrono::{DateTime, Duration, Months, NaiveDate, NaiveDateTime, TimeZone};
+use chrono::{DateTime, Duration, LocalResult, Months, NaiveDate, NaiveDateTime, Offset, TimeZone};
+use chrono_tz::Tz;
 use itertools::{Either, Itertools};
 use regex::Regex;
 use std::ops::{Add, Sub};
@@ -25,6 +26,12 @@ pub mod error {
         ParseError(#[from] ParseError),
         #[error("negative interval is not supported")]
         NegativeInt,
+        #[error("'{0}' is not a valid time zone, expected a name from the IANA time zone database like 'Europe/London'")]
+        InvalidTimezone(String),
+        #[error("time zones only apply to intervals of calendar units, not to discrete intervals")]
+        DiscreteTimezone,
+        #[error("'{0}' is outside the range of supported datetimes")]
+        OutOfRange(String),
         #[error("'{0}' is not a valid datetime, valid formats are RFC3339, RFC2822, %Y-%m-%d, %Y-%m-%dT%H:%M:%S%.3f, %Y-%m-%dT%H:%M:%S%, %Y-%m-%d %H:%M:%S%.3f and %Y-%m-%d %H:%M:%S%")]
         InvalidDateTimeString(String),
     }
@@ -164,10 +171,17 @@ impl From<Duration> for IntervalSize {
     }
 }
 
+const WEEK_MILLIS: u64 = 7 * 24 * 60 * 60 * 1000;
+/// The mean length of a month in the gregorian calendar, only used to estimate multiples of intervals
+const MEAN_MONTH_MILLIS: i64 = 2_629_746_000;
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct Interval {
     pub(crate) epoch_alignment: bool,
     pub(crate) size: IntervalSize,
+    /// Temporal intervals are added in the wall clock time of this time zone if set, so a day is
+    /// always from midnight to midnight, also across daylight saving time transitions
+    pub(crate) timezone: Option<Tz>,
 }
 
 impl Default for Interval {
@@ -175,6 +189,7 @@ impl Default for Interval {
         Self {
             epoch_alignment: false,
             size: IntervalSize::Discrete(1),
+            timezone: None,
         }
     }
 }
@@ -210,6 +225,7 @@ impl TryFrom<&str> for Interval {
                     .into_iter()
                     .reduce(|a, b| a.add_temporal(b))
                     .unwrap(),
+                timezone: None,
             })
         } else {
             Err(errors.first().unwrap().clone())
@@ -223,6 +239,7 @@ impl TryFrom<u64> for Interval {
         Ok(Self {
             epoch_alignment: false,
             size: IntervalSize::Discrete(value),
+            timezone: None,
         })
     }
 }
@@ -233,6 +250,7 @@ impl TryFrom<u32> for Interval {
         Ok(Self {
             epoch_alignment: false,
             size: IntervalSize::Discrete(value as u64),
+            timezone: None,
         })
     }
 }
@@ -244,6 +262,7 @@ impl TryFrom<i32> for Interval {
             Ok(Self {
                 epoch_alignment: false,
                 size: IntervalSize::Discrete(value as u64),
+                timezone: None,
             })
         } else {
             Err(ParseTimeError::NegativeInt)
@@ -259,6 +278,7 @@ impl TryFrom<i64> for Interval {
             Ok(Self {
                 epoch_alignment: false,
                 size: IntervalSize::Discrete(value as u64),
+                timezone: None,
             })
         } else {
             Err(ParseTimeError::NegativeInt)
@@ -275,10 +295,153 @@ impl Interval {
         }
     }
 
+    /// Interpret the interval in the wall clock time of the time zone `timezone` (e.g.,
+    /// "Europe/London") instead of UTC
+    pub fn with_timezone(self, timezone: &str) -> Result<Self, ParseTimeError> {
+        if let IntervalSize::Discrete(_) = self.size {
+            return Err(ParseTimeError::DiscreteTimezone);
+        }
+        Ok(Self {
+            timezone: Some(parse_timezone(timezone)?),
+            ..self
+        })
+    }
+
+    /// `t` plus the interval, fails if the result is outside the range of supported datetimes
+    pub(crate) fn add_to(&self, t: i64) -> Result<i64, ParseTimeError> {
+        let out_of_range = || ParseTimeError::OutOfRange(format!("{t} + {self:?}"));
+        match self.size {
+            IntervalSize::Discrete(number) => t.checked_add(number as i64).ok_or_else(out_of_range),
+            IntervalSize::Temporal { millis, months } => {
+                // first we add the number of months and then the number of milliseconds for
+                // consistency with sub_from (we revert back the steps) so we guarantee that:
+                // time + interval - interval = time
+                let datetime = to_local(t, self.timezone)?
+                    .checked_add_months(Months::new(months))
+                    .and_then(|datetime| {
+                        datetime.checked_add_signed(Duration::try_milliseconds(millis as i64)?)
+                    })
+                    .ok_or_else(out_of_range)?;
+                from_local(datetime, self.timezone)
+            }
+        }
+    }
+
+    /// `t` minus the interval, fails if the result is outside the range of supported datetimes
+    pub(crate) fn sub_from(&self, t: i64) -> Result<i64, ParseTimeError> {
+        let out_of_range = || ParseTimeError::OutOfRange(format!("{t} - {self:?}"));
+        match self.size {
+            IntervalSize::Discrete(number) => t.checked_sub(number as i64).ok_or_else(out_of_range),
+            IntervalSize::Temporal { millis, months } => {
+                // first we subtract the number of milliseconds and then the number of months for
+                // consistency with add_to
+                let datetime = to_local(t, self.timezone)?
+                    .checked_sub_signed(
+                        Duration::try_milliseconds(millis as i64).ok_or_else(out_of_range)?,
+                    )
+                    .and_then(|datetime| datetime.checked_sub_months(Months::new(months)))
+                    .ok_or_else(out_of_range)?;
+                from_local(datetime, self.timezone)
+            }
+        }
+    }
+
+    /// The time zone of the interval, `None` for UTC
+    pub fn timezone(&self) -> Option<Tz> {
+        self.timezone
+    }
+
+    /// Add `n` times the interval to `anchor` (subtract if `n` is negative). Months are added
+    /// all at once, so adding one month `n` times to the 31st of a month stays on the last day of
+    /// every month instead of drifting.
+    pub(crate) fn nth_from(&self, anchor: i64, n: i64) -> Result<i64, ParseTimeError> {
+        let out_of_range = || ParseTimeError::OutOfRange(format!("{anchor} + {n} * {self:?}"));
+        match self.size {
+            IntervalSize::Discrete(size) => n
+                .checked_mul(size as i64)
+                .and_then(|offset| anchor.checked_add(offset))
+                .ok_or_else(out_of_range),
+            IntervalSize::Temporal { millis, months } => {
+                let datetime = to_local(anchor, self.timezone)?;
+                let months = n.checked_mul(months as i64).ok_or_else(out_of_range)?;
+                let datetime = u32::try_from(months.unsigned_abs())
+                    .ok()
+                    .and_then(|abs| {
+                        if months >= 0 {
+                            datetime.checked_add_months(Months::new(abs))
+                        } else {
+                            datetime.checked_sub_months(Months::new(abs))
+                        }
+                    })
+                    .ok_or_else(out_of_range)?;
+                let datetime = n
+                    .checked_mul(millis as i64)
+                    .and_then(Duration::try_milliseconds)
+                    .and_then(|offset| datetime.checked_add_signed(offset))
+                    .ok_or_else(out_of_range)?;
+                from_local(datetime, self.timezone)
+            }
+        }
+    }
+
+    /// The largest `n` such that `self.nth_from(anchor, n) <= t`
+    pub(crate) fn floor_index(&self, anchor: i64, t: i64) -> Result<i64, ParseTimeError> {
+        let estimated_size = match self.size {
+            IntervalSize::Discrete(size) => size as i64,
+            IntervalSize::Temporal { millis, months } => {
+                millis as i64 + months as i64 * MEAN_MONTH_MILLIS
+            }
+        };
+        if estimated_size == 0 {
+            return Ok(0);
+        }
+        let mut n = t
+            .checked_sub(anchor)
+            .ok_or_else(|| ParseTimeError::OutOfRange(t.to_string()))?
+            .div_euclid(estimated_size);
+        while self.nth_from(anchor, n)? > t {
+            n -= 1;
+        }
+        while self.nth_from(anchor, n + 1)? <= t {
+            n += 1;
+        }
+        Ok(n)
+    }
+
+    /// The anchor of intervals aligned to the calendar: midnight of 1970-01-01 in the time zone
+    /// of the interval, or midnight of Monday 1970-01-05 for whole weeks so they are ISO weeks.
+    /// Multiples of months are then aligned to the start of a month, of three months to quarters,
+    /// and of twelve months to years.
+    pub(crate) fn calendar_anchor(&self) -> Result<i64, ParseTimeError> {
+        match self.size {
+            IntervalSize::Discrete(_) => Ok(0),
+            IntervalSize::Temporal { millis, months } => {
+                let day = if months == 0 && millis > 0 && millis % WEEK_MILLIS == 0 {
+                    5
+                } else {
+                    1
+                };
+                let midnight = NaiveDate::from_ymd_opt(1970, 1, day)
+                    .and_then(|date| date.and_hms_opt(0, 0, 0))
+                    .ok_or_else(|| ParseTimeError::OutOfRange(format!("1970-01-0{day}")))?;
+                from_local(midnight, self.timezone)
+            }
+        }
+    }
+
+    /// The start of the bucket of the size of the interval that contains `t`. Buckets are aligned
+    /// to the calendar, see [Interval::calendar_anchor]. Fails if the bucket is outside the range
+    /// of supported datetimes.
+    pub(crate) fn bucket_start(&self, t: i64) -> Result<i64, ParseTimeError> {
+        let anchor = self.calendar_anchor()?;
+        self.nth_from(anchor, self.floor_index(anchor, t)?)
+    }
+
     fn parse_duration(number: &str, unit: &str) -> Result<IntervalSize, ParseTimeError> {
         let number: i64 = number.parse::<u64>()? as i64;
         let duration = match unit {
             "year" | "years" => IntervalSize::months(number * 12),
+            "quarter" | "quarters" => IntervalSize::months(number * 3),
             "month" | "months" => IntervalSize::months(number),
             "week" | "weeks" => Duration::weeks(number).into(),
             "day" | "days" => Duration::days(number).into(),
@@ -292,48 +455,61 @@ impl Interval {
     }
 }
 
+/// Parse the name of a time zone from the IANA time zone database, e.g., "Europe/London"
+pub(crate) fn parse_timezone(timezone: &str) -> Result<Tz, ParseTimeError> {
+    timezone
+        .parse::<Tz>()
+        .map_err(|_| ParseTimeError::InvalidTimezone(timezone.to_string()))
+}
+
+/// The wall clock time of the timestamp `t` in `timezone`, or in UTC if `None`
+fn to_local(t: i64, timezone: Option<Tz>) -> Result<NaiveDateTime, ParseTimeError> {
+    let datetime = DateTime::from_timestamp_millis(t)
+        .ok_or_else(|| ParseTimeError::OutOfRange(t.to_string()))?;
+    Ok(match timezone {
+        Some(timezone) => datetime.with_timezone(&timezone).naive_local(),
+        None => datetime.naive_utc(),
+    })
+}
+
+/// The timestamp of the wall clock time `datetime` in `timezone`, or in UTC if `None`. Ambiguous
+/// times resolve to the earlier timestamp, and times skipped by a transition use the offset from
+/// before the transition.
+fn from_local(datetime: NaiveDateTime, timezone: Option<Tz>) -> Result<i64, ParseTimeError> {
+    match timezone {
+        None => Ok(datetime.and_utc().timestamp_millis()),
+        Some(timezone) => match timezone.from_local_datetime(&datetime) {
+            LocalResult::Single(datetime) | LocalResult::Ambiguous(datetime, _) => {
+                Ok(datetime.timestamp_millis())
+            }
+            LocalResult::None => {
+                let out_of_range = || ParseTimeError::OutOfRange(datetime.to_string());
+                let before = datetime
+                    .checked_sub_signed(Duration::days(1))
+                    .ok_or_else(out_of_range)?;
+                let offset = timezone.offset_from_utc_datetime(&before).fix();
+                let datetime = datetime
+                    .checked_sub_signed(Duration::seconds(offset.local_minus_utc() as i64))
+                    .ok_or_else(out_of_range)?;
+                Ok(datetime.and_utc().timestamp_millis())
+            }
+        },
+    }
+}
+
+/// Panics if the result is outside the range of supported datetimes
 impl Sub<Interval> for i64 {
     type Output = i64;
     fn sub(self, rhs: Interval) -> Self::Output {
-        match rhs.size {
-            IntervalSize::Discrete(number) => self - (number as i64),
-            IntervalSize::Temporal { millis, months } => {
-                // first we subtract the number of milliseconds and then the number of months for
-                // consistency with the implementation of Add (we revert back the steps) so we
-                // guarantee that:  time + interval - interval = time
-                let datetime = DateTime::from_timestamp_millis(self - millis as i64)
-                    .unwrap_or_else(|| {
-                        panic!("{self} cannot be interpreted as a milliseconds timestamp")
-                    })
-                    .naive_utc();
-                (datetime - Months::new(months))
-                    .and_utc()
-                    .timestamp_millis()
-            }
-        }
+        rhs.sub_from(self).unwrap_or_else(|err| panic!("{err}"))
     }
 }
 
+/// Panics if the result is outside the range of supported datetimes
 impl Add<Interval> for i64 {
     type Output = i64;
     fn add(self, rhs: Interval) -> Self::Output {
-        match rhs.size {
-            IntervalSize::Discrete(number) => self + (number as i64),
-            IntervalSize::Temporal { millis, months } => {
-                // first we add the number of months and then the number of milliseconds for
-                // consistency with the implementation of Sub (we revert back the steps) so we
-                // guarantee that:  time + interval - interval = time
-                let datetime = DateTime::from_timestamp_millis(self)
-                    .unwrap_or_else(|| {
-                        panic!("{self} cannot be interpreted as a milliseconds timestamp")
-                    })
-                    .naive_utc();
-                (datetime + Months::new(months))
-                    .and_utc()
-                    .timestamp_millis()
-                    + millis as i64
-            }
-        }
+        rhs.add_to(self).unwrap_or_else(|err| panic!("{err}"))
     }
 }
 
@@ -390,6 +566,66 @@ mod time_tests {
         assert_eq!(dt + mix_interval, dt_mix);
     }
 
+    #[test]
+    fn timezone_aware_intervals() {
+        let day = Interval::try_from("1 day")
+            .unwrap()
+            .with_timezone("Europe/London")
+            .unwrap();
+        // the clocks went forward in London on 2024-03-31, so that day only had 23 hours
+        let midnight_gmt = "2024-03-31T00:00:00+00:00".try_into_time().unwrap();
+        let midnight_bst = "2024-04-01T00:00:00+01:00".try_into_time().unwrap();
+        assert_eq!(midnight_gmt + day, midnight_bst);
+        assert_eq!(midnight_bst - day, midnight_gmt);
+        assert_eq!(midnight_bst - midnight_gmt, 23 * 60 * 60 * 1000);
+
+        let noon = "2024-04-01T12:00:00+01:00".try_into_time().unwrap();
+        assert_eq!(day.bucket_start(noon), Ok(midnight_bst));
+        assert_eq!(day.bucket_start(midnight_bst - 1), Ok(midnight_gmt));
+
+        let result = Interval::try_from("1 day")
+            .unwrap()
+            .with_timezone("Mars/Olympus");
+        assert_eq!(
+            result,
+            Err(ParseTimeError::InvalidTimezone("Mars/Olympus".to_string()))
+        );
+        let result = Interval::try_from(5u64)
+            .unwrap()
+            .with_timezone("Europe/London");
+        assert_eq!(result, Err(ParseTimeError::DiscreteTimezone));
+    }
+
+    #[test]
+    fn calendar_alignment() {
+        let week: Interval = "1 week".try_into().unwrap();
+        let wednesday = "2024-01-03 12:00:00".try_into_time().unwrap();
+        let monday = "2024-01-01 00:00:00".try_into_time().unwrap();
+        assert_eq!(week.bucket_start(wednesday), Ok(monday));
+
+        let quarter: Interval = "1 quarter".try_into().unwrap();
+        let may = "2024-05-17 00:00:00".try_into_time().unwrap();
+        let april = "2024-04-01 00:00:00".try_into_time().unwrap();
+        assert_eq!(quarter.bucket_start(may), Ok(april));
+
+        // multiples of months don't drift when starting at the end of a month
+        let month: Interval = "1 month".try_into().unwrap();
+        let end_of_january = "2024-01-31 00:00:00".try_into_time().unwrap();
+        let end_of_march = "2024-03-31 00:00:00".try_into_time().unwrap();
+        assert_eq!(month.nth_from(end_of_january, 2), Ok(end_of_march));
+        assert_eq!(month.nth_from(end_of_march, -2), Ok(end_of_january));
+
+        // times outside the range of datetimes are errors instead of panics
+        assert!(matches!(
+            month.bucket_start(i64::MAX),
+            Err(ParseTimeError::OutOfRange(_))
+        ));
+        assert!(matches!(
+            month.nth_from(end_of_january, i64::MAX),
+            Err(ParseTimeError::OutOfRange(_))
+        ));
+    }
+
     #[test]
     fn invalid_intervals() {
         let result: Result<Interval, ParseTimeError> = "".try_into();
@@ -2,13 +2,14 @@ use super::time_from_input;
 use crate::{
     core::{
         entities::nodes::node_ref::AsNodeRef,
+        storage::timeindex::AsTime,
         utils::{errors::GraphError, time::IntoTimeWithFormat},
     },
     db::{
         api::{
             mutation::{
                 internal::{InternalAdditionOps, InternalDeletionOps},
-                TryIntoInputTime,
+                AdditionOps, CollectProperties, InputTime, TryIntoInputTime,
             },
             view::StaticGraphViewOps,
         },
@@ -51,4 +52,42 @@ pub trait DeletionOps:
         let time: i64 = t.parse_time(fmt)?;
         self.delete_edge(time, src, dst, layer)
     }
+
+    /// Adds an edge that is alive for the interval `[start, end)`, i.e., the edge is added at
+    /// `start` with the given properties and deleted again at `end`.
+    ///
+    /// Returns an error if `end` is before `start`.
+    ///
+    /// Only graphs with deletions, i.e., a
+    /// [PersistentGraph](crate::db::graph::views::deletion_graph::PersistentGraph), support interval
+    /// edges. An event [Graph](crate::db::graph::graph::Graph) can add them through its
+    /// [persistent_graph](crate::db::graph::graph::Graph::persistent_graph) view.
+    fn add_interval_edge<V: AsNodeRef, T: TryIntoInputTime, PI: CollectProperties>(
+        &self,
+        start: T,
+        end: T,
+        src: V,
+        dst: V,
+        props: PI,
+        layer: Option<&str>,
+    ) -> Result<EdgeView<Self>, GraphError> {
+        let start = time_from_input(self, start)?;
+        let end = time_from_input(self, end)?;
+        if end.t() < start.t() {
+            return Err(GraphError::InvalidEdgeInterval {
+                start: start.t(),
+                end: end.t(),
+            });
+        }
+        let edge = self.add_edge(
+            InputTime::Indexed(start.t(), start.i()),
+            src,
+            dst,
+            props,
+            layer,
+        )?;
+        let layer = self.resolve_layer(layer)?.inner();
+        self.internal_delete_edge(end, edge.edge.src(), edge.edge.dst(), layer)?;
+        Ok(edge)
+    }
 }
@@ -0,0 +1,81 @@
+use super::time_from_input;
+use crate::{
+    core::{
+        entities::nodes::node_ref::AsNodeRef, storage::timeindex::AsTime, utils::errors::GraphError,
+    },
+    db::{
+        api::{
+            mutation::{
+                internal::{InternalAdditionOps, InternalHyperedgeOps},
+                CollectProperties, TryIntoInputTime,
+            },
+            view::{internal::CoreGraphOps, StaticGraphViewOps},
+        },
+        graph::hyperedge::HyperedgeView,
+    },
+};
+use raphtory_api::core::entities::GID;
+
+pub trait HyperedgeAdditionOps: StaticGraphViewOps {
+    /// Add a hyperedge connecting an arbitrary number of nodes to the graph
+    ///
+    /// # Arguments
+    ///
+    /// * `t` - The time
+    /// * `id` - The unique id of the hyperedge (can be a string or integer)
+    /// * `participants` - The participating nodes with their optional role
+    /// * `props` - The properties of the hyperedge
+    ///
+    /// Returns:
+    ///
+    /// A result containing the hyperedge or an error if a hyperedge with the same id exists
+    fn add_hyperedge<'a, H: Into<GID>, V: AsNodeRef, T: TryIntoInputTime, PI: CollectProperties>(
+        &self,
+        t: T,
+        id: H,
+        participants: impl IntoIterator<Item = (V, Option<&'a str>)>,
+        props: PI,
+    ) -> Result<HyperedgeView<Self>, GraphError>;
+}
+
+impl<G: InternalAdditionOps + InternalHyperedgeOps + StaticGraphViewOps> HyperedgeAdditionOps
+    for G
+{
+    fn add_hyperedge<'a, H: Into<GID>, V: AsNodeRef, T: TryIntoInputTime, PI: CollectProperties>(
+        &self,
+        t: T,
+        id: H,
+        participants: impl IntoIterator<Item = (V, Option<&'a str>)>,
+        props: PI,
+    ) -> Result<HyperedgeView<Self>, GraphError> {
+        let id = id.into();
+        // claim the id before touching the participants so a failed addition leaves the graph
+        // unchanged, also when the same id is added concurrently
+        let _reservation = self
+            .core_graph()
+            .hyperedges()
+            .map(|hyperedges| hyperedges.reserve(&id))
+            .transpose()?;
+        let ti = time_from_input(self, t)?;
+        let properties = props.collect_properties(|name, dtype| {
+            Ok(self.resolve_hyperedge_property(name, dtype)?.inner())
+        })?;
+        let participants = participants
+            .into_iter()
+            .map(|(v, role)| {
+                let v_id = self.resolve_node(v)?.inner();
+                // the hyperedge is part of the history of its participants
+                self.internal_add_node(ti, v_id, &[])?;
+                let role = role
+                    .map(|role| self.resolve_hyperedge_role(role))
+                    .transpose()?
+                    .map(|role| role.inner());
+                Ok((v_id, role))
+            })
+            .collect::<Result<Vec<_>, GraphError>>()?;
+        let hid = self
+            .internal_add_hyperedge(ti, (&id).into(), &participants, &properties)?
+            .inner();
+        Ok(HyperedgeView::new(self.clone(), hid, id, ti.t()))
+    }
+}
@@ -0,0 +1,77 @@
+use crate::{
+    core::{
+        entities::VID, storage::timeindex::TimeIndexEntry, utils::errors::GraphError, Prop,
+        PropType,
+    },
+    db::api::view::internal::Base,
+};
+use raphtory_api::core::{entities::GidRef, storage::dict_mapper::MaybeNew};
+
+pub trait InternalHyperedgeOps {
+    /// map role name to id, allocating a new role if needed
+    fn resolve_hyperedge_role(&self, role: &str) -> Result<MaybeNew<usize>, GraphError>;
+
+    /// map hyperedge property key to internal id, allocating new property if needed and checking
+    /// property type
+    fn resolve_hyperedge_property(
+        &self,
+        prop: &str,
+        dtype: PropType,
+    ) -> Result<MaybeNew<usize>, GraphError>;
+
+    /// add a new hyperedge, returns the internal id of the hyperedge
+    fn internal_add_hyperedge(
+        &self,
+        t: TimeIndexEntry,
+        id: GidRef,
+        participants: &[(VID, Option<usize>)],
+        props: &[(usize, Prop)],
+    ) -> Result<MaybeNew<usize>, GraphError>;
+}
+
+pub trait InheritHyperedgeOps: Base {}
+
+impl<G: InheritHyperedgeOps> DelegateHyperedgeOps for G
+where
+    G::Base: InternalHyperedgeOps,
+{
+    type Internal = G::Base;
+
+    fn graph(&self) -> &Self::Internal {
+        self.base()
+    }
+}
+
+pub trait DelegateHyperedgeOps {
+    type Internal: InternalHyperedgeOps + ?Sized;
+
+    fn graph(&self) -> &Self::Internal;
+}
+
+impl<G: DelegateHyperedgeOps> InternalHyperedgeOps for G {
+    #[inline]
+    fn resolve_hyperedge_role(&self, role: &str) -> Result<MaybeNew<usize>, GraphError> {
+        self.graph().resolve_hyperedge_role(role)
+    }
+
+    #[inline]
+    fn resolve_hyperedge_property(
+        &self,
+        prop: &str,
+        dtype: PropType,
+    ) -> Result<MaybeNew<usize>, GraphError> {
+        self.graph().resolve_hyperedge_property(prop, dtype)
+    }
+
+    #[inline]
+    fn internal_add_hyperedge(
+        &self,
+        t: TimeIndexEntry,
+        id: GidRef,
+        participants: &[(VID, Option<usize>)],
+        props: &[(usize, Prop)],
+    ) -> Result<MaybeNew<usize>, GraphError> {
+        self.graph()
+            .internal_add_hyperedge(t, id, participants, props)
+    }
+}
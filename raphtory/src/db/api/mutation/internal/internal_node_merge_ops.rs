@@ -0,0 +1,46 @@
+use crate::{
+    core::{
+        entities::{graph::node_merge::NodeMerge, VID},
+        utils::errors::GraphError,
+    },
+    db::api::view::internal::Base,
+};
+
+pub trait InternalNodeMergeOps {
+    /// Merge node `merged` into node `keep`, returns the record of the merge
+    fn internal_merge_nodes(&self, keep: VID, merged: VID) -> Result<NodeMerge, GraphError>;
+
+    /// Undo the merge of node `merged`
+    fn internal_unmerge_node(&self, merged: VID) -> Result<(), GraphError>;
+}
+
+pub trait InheritNodeMergeOps: Base {}
+
+impl<G: InheritNodeMergeOps> DelegateNodeMergeOps for G
+where
+    G::Base: InternalNodeMergeOps,
+{
+    type Internal = G::Base;
+
+    fn graph(&self) -> &Self::Internal {
+        self.base()
+    }
+}
+
+pub trait DelegateNodeMergeOps {
+    type Internal: InternalNodeMergeOps + ?Sized;
+
+    fn graph(&self) -> &Self::Internal;
+}
+
+impl<G: DelegateNodeMergeOps> InternalNodeMergeOps for G {
+    #[inline]
+    fn internal_merge_nodes(&self, keep: VID, merged: VID) -> Result<NodeMerge, GraphError> {
+        self.graph().internal_merge_nodes(keep, merged)
+    }
+
+    #[inline]
+    fn internal_unmerge_node(&self, merged: VID) -> Result<(), GraphError> {
+        self.graph().internal_unmerge_node(merged)
+    }
+}
@@ -0,0 +1,117 @@
+use crate::{
+    core::{
+        entities::{LayerIds, VID},
+        utils::errors::GraphError,
+    },
+    db::api::view::internal::Base,
+};
+use raphtory_api::core::entities::EID;
+use std::ops::Range;
+
+pub trait InternalRemovalOps {
+    /// Remove node `v` and all its edges
+    fn internal_remove_node(&self, v: VID) -> Result<(), GraphError>;
+
+    /// Remove all updates and properties of edge `eid` in `layer_ids`
+    fn internal_remove_edge(&self, eid: EID, layer_ids: &LayerIds) -> Result<(), GraphError>;
+
+    /// Remove the updates of temporal property `prop_id` (or all temporal properties if `None`)
+    /// of node `v` in window `w`
+    fn internal_remove_node_property_updates(
+        &self,
+        v: VID,
+        w: Range<i64>,
+        prop_id: Option<usize>,
+    ) -> Result<(), GraphError>;
+
+    /// Remove the updates of temporal property `prop_id` (or all temporal properties if `None`)
+    /// of edge `eid` in `layer_ids` and window `w`
+    fn internal_remove_edge_property_updates(
+        &self,
+        eid: EID,
+        layer_ids: &LayerIds,
+        w: Range<i64>,
+        prop_id: Option<usize>,
+    ) -> Result<(), GraphError>;
+
+    /// Remove the updates of temporal graph property `prop_id` (or all temporal properties if
+    /// `None`) in window `w`
+    fn internal_remove_graph_property_updates(
+        &self,
+        w: Range<i64>,
+        prop_id: Option<usize>,
+    ) -> Result<(), GraphError>;
+
+    /// Drop all history before `t`, if `keep_alive` is set the state of the graph at `t` is
+    /// preserved for persistent semantics
+    fn internal_truncate_before(&self, t: i64, keep_alive: bool) -> Result<(), GraphError>;
+}
+
+pub trait InheritRemovalOps: Base {}
+
+impl<G: InheritRemovalOps> DelegateRemovalOps for G
+where
+    G::Base: InternalRemovalOps,
+{
+    type Internal = G::Base;
+
+    fn graph(&self) -> &Self::Internal {
+        self.base()
+    }
+}
+
+pub trait DelegateRemovalOps {
+    type Internal: InternalRemovalOps + ?Sized;
+
+    fn graph(&self) -> &Self::Internal;
+}
+
+impl<G: DelegateRemovalOps> InternalRemovalOps for G {
+    #[inline]
+    fn internal_remove_node(&self, v: VID) -> Result<(), GraphError> {
+        self.graph().internal_remove_node(v)
+    }
+
+    #[inline]
+    fn internal_remove_edge(&self, eid: EID, layer_ids: &LayerIds) -> Result<(), GraphError> {
+        self.graph().internal_remove_edge(eid, layer_ids)
+    }
+
+    #[inline]
+    fn internal_remove_node_property_updates(
+        &self,
+        v: VID,
+        w: Range<i64>,
+        prop_id: Option<usize>,
+    ) -> Result<(), GraphError> {
+        self.graph()
+            .internal_remove_node_property_updates(v, w, prop_id)
+    }
+
+    #[inline]
+    fn internal_remove_edge_property_updates(
+        &self,
+        eid: EID,
+        layer_ids: &LayerIds,
+        w: Range<i64>,
+        prop_id: Option<usize>,
+    ) -> Result<(), GraphError> {
+        self.graph()
+            .internal_remove_edge_property_updates(eid, layer_ids, w, prop_id)
+    }
+
+    #[inline]
+    fn internal_remove_graph_property_updates(
+        &self,
+        w: Range<i64>,
+        prop_id: Option<usize>,
+    ) -> Result<(), GraphError> {
+        self.graph()
+            .internal_remove_graph_property_updates(w, prop_id)
+    }
+
+    #[inline]
+    fn internal_truncate_before(&self, t: i64, keep_alive: bool) -> Result<(), GraphError> {
+        self.graph().internal_truncate_before(t, keep_alive)
+    }
+}
@@ -1,14 +1,23 @@
 mod internal_addition_ops;
 mod internal_deletion_ops;
+mod internal_hyperedge_ops;
+mod internal_node_merge_ops;
 mod internal_property_additions_ops;
+mod internal_removal_ops;
 
 use crate::db::api::view::internal::Base;
 pub use internal_addition_ops::*;
 pub use internal_deletion_ops::*;
+pub use internal_hyperedge_ops::*;
+pub use internal_node_merge_ops::*;
 pub use internal_property_additions_ops::*;
+pub use internal_removal_ops::*;
 
 pub trait InheritMutationOps: Base {}
 
 impl<G: InheritMutationOps> InheritAdditionOps for G {}
 impl<G: InheritMutationOps> InheritDeletionOps for G {}
+impl<G: InheritMutationOps> InheritHyperedgeOps for G {}
+impl<G: InheritMutationOps> InheritNodeMergeOps for G {}
 impl<G: InheritMutationOps> InheritPropertyAdditionOps for G {}
+impl<G: InheritMutationOps> InheritRemovalOps for G {}
@@ -0,0 +1,427 @@
+//! Merging of two graphs into a new graph, see [crate::prelude::Graph::merge].
+//!
+//! Nodes are matched by their id and edges by their source and destination ids. The histories
+//! and temporal properties of both graphs are combined, such that updates that exist in both
+//! graphs appear twice in the result. Conflicting node types and constant property values are
+//! resolved using a [MergePolicy].
+
+use crate::{
+    core::{utils::errors::GraphError, Prop},
+    db::api::{
+        mutation::{
+            internal::{InternalAdditionOps, InternalDeletionOps, InternalPropertyAdditionOps},
+            time_from_input, AdditionOps, PropertyAdditionOps,
+        },
+        view::{internal::InternalMaterialize, StaticGraphViewOps},
+    },
+    prelude::{EdgeViewOps, GraphViewOps, LayerOps, NodeViewOps},
+};
+use raphtory_api::core::{entities::GID, storage::arc_str::ArcStr};
+use std::{
+    collections::HashMap,
+    fmt::{Debug, Display, Formatter},
+    sync::Arc,
+};
+
+/// The entity a [MergeConflict] occurred on
+#[derive(Clone, Debug, PartialEq)]
+pub enum MergeEntity {
+    Graph,
+    Node(GID),
+    Edge { src: GID, dst: GID, layer: ArcStr },
+}
+
+/// Two different values for the node type or a constant property of the same entity
+#[derive(Clone, Debug, PartialEq)]
+pub struct MergeConflict {
+    pub entity: MergeEntity,
+    /// The name of the constant property or `None` for a conflict between node types
+    pub key: Option<ArcStr>,
+    /// The value in the left graph (node types are given as `Prop::Str`)
+    pub left: Prop,
+    /// The value in the right graph (node types are given as `Prop::Str`)
+    pub right: Prop,
+}
+
+impl Display for MergeConflict {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match &self.key {
+            None => write!(f, "node type of {:?}", self.entity)?,
+            Some(key) => write!(f, "constant property '{key}' of {:?}", self.entity)?,
+        }
+        write!(f, " differs: {:?} != {:?}", self.left, self.right)
+    }
+}
+
+type ConflictResolver = dyn Fn(&MergeConflict) -> Result<Prop, GraphError> + Send + Sync;
+
+/// How to resolve conflicting node types and constant property values when merging graphs
+#[derive(Clone, Default)]
+pub enum MergePolicy {
+    /// Keep the value of the left graph
+    KeepLeft,
+    /// Keep the value of the right graph
+    KeepRight,
+    /// Fail the merge with [GraphError::MergeConflict]
+    #[default]
+    Error,
+    /// Compute the value with a closure (node types need to be resolved to a `Prop::Str`)
+    Custom(Arc<ConflictResolver>),
+}
+
+impl Debug for MergePolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            MergePolicy::KeepLeft => write!(f, "KeepLeft"),
+            MergePolicy::KeepRight => write!(f, "KeepRight"),
+            MergePolicy::Error => write!(f, "Error"),
+            MergePolicy::Custom(_) => write!(f, "Custom"),
+        }
+    }
+}
+
+impl MergePolicy {
+    pub fn custom(
+        resolver: impl Fn(&MergeConflict) -> Result<Prop, GraphError> + Send + Sync + 'static,
+    ) -> Self {
+        MergePolicy::Custom(Arc::new(resolver))
+    }
+
+    fn resolve(&self, conflict: MergeConflict) -> Result<Prop, GraphError> {
+        match self {
+            MergePolicy::KeepLeft => Ok(conflict.left),
+            MergePolicy::KeepRight => Ok(conflict.right),
+            MergePolicy::Error => Err(GraphError::MergeConflict(conflict.to_string())),
+            MergePolicy::Custom(resolver) => resolver(&conflict),
+        }
+    }
+
+    /// Returns the merged value or `None` if the left value should be kept
+    fn merge_value(
+        &self,
+        entity: impl FnOnce() -> MergeEntity,
+        key: Option<ArcStr>,
+        left: Option<Prop>,
+        right: Prop,
+    ) -> Result<Option<Prop>, GraphError> {
+        match left {
+            None => Ok(Some(right)),
+            Some(left) if left == right => Ok(None),
+            Some(left) => {
+                let conflict = MergeConflict {
+                    entity: entity(),
+                    key,
+                    left: left.clone(),
+                    right,
+                };
+                let merged = self.resolve(conflict)?;
+                Ok((merged != left).then_some(merged))
+            }
+        }
+    }
+}
+
+fn layer_name(layer: &ArcStr) -> Option<&str> {
+    (layer.as_ref() != "_default").then_some(layer.as_ref())
+}
+
+/// Copy the updates of `source` into `target`, resolving constant properties using `policy`
+fn merge_updates<'graph, G, S>(
+    target: &G,
+    source: &S,
+    policy: &MergePolicy,
+) -> Result<(), GraphError>
+where
+    G: StaticGraphViewOps
+        + InternalAdditionOps
+        + InternalDeletionOps
+        + InternalPropertyAdditionOps
+        + InternalMaterialize,
+    S: GraphViewOps<'graph>,
+{
+    // make sure we preserve all layers even if they are empty
+    for layer in source.unique_layers() {
+        if let Some(layer) = layer_name(&layer) {
+            target.resolve_layer(Some(layer))?;
+        }
+    }
+
+    for (name, values) in source.properties().temporal().iter() {
+        for (t, value) in values.iter() {
+            target.add_properties(t, [(name.clone(), value)])?;
+        }
+    }
+    for (name, value) in source.properties().constant().iter() {
+        let existing = target.properties().constant().get(&name);
+        if let Some(value) =
+            policy.merge_value(|| MergeEntity::Graph, Some(name.clone()), existing, value)?
+        {
+            target.update_constant_properties([(name, value)])?;
+        }
+    }
+
+    for node in source.nodes().iter() {
+        let id = node.id();
+        let vid = target.resolve_node(&id)?.inner();
+        for t in node.history() {
+            let t = time_from_input(target, t)?;
+            target.internal_add_node(t, vid, &[])?;
+        }
+        for (name, values) in node.properties().temporal().iter() {
+            for (t, value) in values.iter() {
+                let prop_id = target
+                    .resolve_node_property(&name, value.dtype(), false)?
+                    .inner();
+                let t = time_from_input(target, t)?;
+                target.internal_add_node(t, vid, &[(prop_id, value)])?;
+            }
+        }
+        let target_node = target.node(vid).expect("node was added");
+        for (name, value) in node.properties().constant().iter() {
+            let existing = target_node.properties().constant().get(&name);
+            if let Some(value) = policy.merge_value(
+                || MergeEntity::Node(id.clone()),
+                Some(name.clone()),
+                existing,
+                value,
+            )? {
+                target_node.update_constant_properties([(name, value)])?;
+            }
+        }
+    }
+
+    for edge in source.edges().iter() {
+        let src = edge.src().id();
+        let dst = edge.dst().id();
+        for layer_edge in edge.explode_layers() {
+            let layer = layer_edge.layer_name()?;
+            for update in layer_edge.explode() {
+                target.add_edge(
+                    update.time()?,
+                    &src,
+                    &dst,
+                    update.properties().temporal().collect_properties(),
+                    layer_name(&layer),
+                )?;
+            }
+            if target.include_deletions() {
+                let src_id = target.resolve_node(&src)?.inner();
+                let dst_id = target.resolve_node(&dst)?.inner();
+                let layer_id = target.resolve_layer(layer_name(&layer))?.inner();
+                for t in layer_edge.deletions() {
+                    let t = time_from_input(target, t)?;
+                    target.internal_delete_edge(t, src_id, dst_id, layer_id)?;
+                }
+            }
+            let constant = layer_edge
+                .properties()
+                .constant()
+                .iter()
+                .collect::<Vec<_>>();
+            if constant.is_empty() {
+                continue;
+            }
+            let target_edge = target.edge(&src, &dst).expect("edge was added");
+            let existing = target_edge.layers(layer.clone())?.properties().constant();
+            for (name, value) in constant {
+                let entity = || MergeEntity::Edge {
+                    src: src.clone(),
+                    dst: dst.clone(),
+                    layer: layer.clone(),
+                };
+                if let Some(value) =
+                    policy.merge_value(entity, Some(name.clone()), existing.get(&name), value)?
+                {
+                    target_edge.update_constant_properties([(name, value)], layer_name(&layer))?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Merge `left` and `right` into the empty graph `target`
+pub(crate) fn merge_graphs<'left, 'right, G, L, R>(
+    target: &G,
+    left: &L,
+    right: &R,
+    policy: &MergePolicy,
+) -> Result<(), GraphError>
+where
+    G: StaticGraphViewOps
+        + InternalAdditionOps
+        + InternalDeletionOps
+        + InternalPropertyAdditionOps
+        + InternalMaterialize,
+    L: GraphViewOps<'left>,
+    R: GraphViewOps<'right>,
+{
+    // node types cannot be changed once set, so they are resolved before adding any nodes
+    let mut node_types: Vec<(GID, Option<ArcStr>)> = vec![];
+    let mut index: HashMap<GID, usize> = HashMap::new();
+    for node in left.nodes().iter() {
+        index.insert(node.id(), node_types.len());
+        node_types.push((node.id(), node.node_type()));
+    }
+    for node in right.nodes().iter() {
+        let id = node.id();
+        let right_type = node.node_type();
+        match index.get(&id) {
+            None => {
+                index.insert(id.clone(), node_types.len());
+                node_types.push((id, right_type));
+            }
+            Some(&i) => {
+                if let Some(right_type) = right_type {
+                    let left_type = node_types[i].1.clone().map(Prop::Str);
+                    let merged = policy.merge_value(
+                        || MergeEntity::Node(id.clone()),
+                        None,
+                        left_type,
+                        Prop::Str(right_type),
+                    )?;
+                    match merged {
+                        None => {}
+                        Some(Prop::Str(node_type)) => node_types[i].1 = Some(node_type),
+                        Some(other) => {
+                            return Err(GraphError::NodeTypeError(format!(
+                                "merged node type of {id:?} needs to be a string, got {other:?}"
+                            )))
+                        }
+                    }
+                }
+            }
+        }
+    }
+    for (id, node_type) in node_types {
+        match node_type {
+            None => {
+                target.resolve_node(&id)?;
+            }
+            Some(node_type) => {
+                target.resolve_node_and_type(&id, &node_type)?;
+            }
+        }
+    }
+
+    merge_updates(target, left, policy)?;
+    merge_updates(target, right, policy)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{db::graph::views::deletion_graph::PersistentGraph, prelude::*};
+    use itertools::Itertools;
+
+    #[test]
+    fn test_merge_histories_and_layers() {
+        let left = Graph::new();
+        left.add_edge(1, "a", "b", [("weight", 1)], Some("x"))
+            .unwrap();
+        left.add_node(0, "a", [("score", 1.0)], Some("person"))
+            .unwrap();
+        let right = Graph::new();
+        right
+            .add_edge(2, "a", "b", [("weight", 2)], Some("y"))
+            .unwrap();
+        right.add_edge(3, "b", "c", NO_PROPS, None).unwrap();
+        right.add_node(4, "c", NO_PROPS, Some("company")).unwrap();
+
+        let merged = left.merge(&right, &MergePolicy::Error).unwrap();
+        assert_eq!(merged.count_nodes(), 3);
+        assert_eq!(merged.count_edges(), 2);
+        let edge = merged.edge("a", "b").unwrap();
+        assert_eq!(edge.history(), vec![1, 2]);
+        assert_eq!(edge.layer_names().collect_vec(), vec!["x", "y"]);
+        assert_eq!(
+            edge.properties()
+                .temporal()
+                .get("weight")
+                .unwrap()
+                .values()
+                .collect_vec(),
+            vec![Prop::I32(1), Prop::I32(2)]
+        );
+        assert_eq!(
+            merged.node("a").unwrap().node_type().unwrap().to_string(),
+            "person"
+        );
+        assert_eq!(
+            merged.node("c").unwrap().node_type().unwrap().to_string(),
+            "company"
+        );
+        assert_eq!(
+            merged.node("a").unwrap().properties().get("score").unwrap(),
+            Prop::F64(1.0)
+        );
+    }
+
+    #[test]
+    fn test_merge_conflicts() {
+        let left = Graph::new();
+        left.add_node(0, 1, NO_PROPS, Some("person")).unwrap();
+        left.node(1)
+            .unwrap()
+            .add_constant_properties([("name", "left")])
+            .unwrap();
+        left.add_constant_properties([("source", "left")]).unwrap();
+        let right = Graph::new();
+        right.add_node(0, 1, NO_PROPS, Some("company")).unwrap();
+        right
+            .node(1)
+            .unwrap()
+            .add_constant_properties([("name", "right")])
+            .unwrap();
+        right
+            .add_constant_properties([("source", "right")])
+            .unwrap();
+
+        assert!(matches!(
+            left.merge(&right, &MergePolicy::Error),
+            Err(GraphError::MergeConflict(_))
+        ));
+
+        let merged = left.merge(&right, &MergePolicy::KeepLeft).unwrap();
+        let node = merged.node(1).unwrap();
+        assert_eq!(node.node_type().unwrap().to_string(), "person");
+        assert_eq!(node.properties().get("name").unwrap(), Prop::str("left"));
+
+        let merged = left.merge(&right, &MergePolicy::KeepRight).unwrap();
+        let node = merged.node(1).unwrap();
+        assert_eq!(node.node_type().unwrap().to_string(), "company");
+        assert_eq!(node.properties().get("name").unwrap(), Prop::str("right"));
+        assert_eq!(
+            merged.properties().get("source").unwrap(),
+            Prop::str("right")
+        );
+
+        let policy = MergePolicy::custom(|conflict| match (&conflict.left, &conflict.right) {
+            (Prop::Str(left), Prop::Str(right)) => Ok(Prop::str(format!("{left}+{right}"))),
+            _ => Err(GraphError::MergeConflict(conflict.to_string())),
+        });
+        let merged = left.merge(&right, &policy).unwrap();
+        let node = merged.node(1).unwrap();
+        assert_eq!(node.node_type().unwrap().to_string(), "person+company");
+        assert_eq!(
+            node.properties().get("name").unwrap(),
+            Prop::str("left+right")
+        );
+    }
+
+    #[test]
+    fn test_merge_persistent_graphs() {
+        let left = PersistentGraph::new();
+        left.add_edge(1, 1, 2, NO_PROPS, None).unwrap();
+        left.delete_edge(5, 1, 2, None).unwrap();
+        let right = PersistentGraph::new();
+        right.add_edge(7, 1, 2, NO_PROPS, None).unwrap();
+
+        let merged = left.merge(&right, &MergePolicy::Error).unwrap();
+        let edge = merged.edge(1, 2).unwrap();
+        assert_eq!(edge.history(), vec![1, 7]);
+        assert_eq!(edge.deletions(), vec![5]);
+        assert!(!merged.at(6).has_edge(1, 2));
+        assert!(merged.at(8).has_edge(1, 2));
+    }
+}
@@ -11,15 +11,25 @@ use crate::{
 
 mod addition_ops;
 mod deletion_ops;
+mod hyperedge_addition_ops;
 mod import_ops;
 pub mod internal;
+pub(crate) mod merge;
+mod node_merge_ops;
 mod property_addition_ops;
+mod removal_ops;
+pub mod schema;
 
 pub use addition_ops::AdditionOps;
 pub use deletion_ops::DeletionOps;
+pub use hyperedge_addition_ops::HyperedgeAdditionOps;
 pub use import_ops::ImportOps;
+pub use merge::{MergeConflict, MergeEntity, MergePolicy};
+pub use node_merge_ops::NodeMergeOps;
 pub use property_addition_ops::PropertyAdditionOps;
 use raphtory_api::core::storage::timeindex::TimeIndexEntry;
+pub use removal_ops::RemovalOps;
+pub use schema::{EntitySchema, GraphSchema, Migration, PropertySchema, SchemaOps};
 
 use self::internal::InternalAdditionOps;
 
@@ -0,0 +1,300 @@
+use super::removal_ops::resolve_existing_node;
+use crate::{
+    core::{
+        entities::nodes::node_ref::{AsNodeRef, NodeRef},
+        utils::errors::GraphError,
+    },
+    db::api::{
+        mutation::internal::InternalNodeMergeOps,
+        view::{internal::CoreGraphOps, StaticGraphViewOps},
+    },
+    prelude::GraphViewOps,
+};
+use raphtory_api::core::entities::GID;
+
+/// Merging of nodes that turn out to be the same entity after ingestion, e.g., as a result of
+/// entity resolution.
+///
+/// The id of a merged node becomes an alias that resolves to the node it was merged into. Merges
+/// and undoing them are appended to the write-ahead log of a cached graph.
+pub trait NodeMergeOps: InternalNodeMergeOps + StaticGraphViewOps + Sized {
+    /// Merge node `merge` into node `keep`.
+    ///
+    /// The history, properties and edges of `merge` are added to `keep`, constant properties
+    /// that `keep` already has take precedence. Edges of `merge` are combined with the edges of
+    /// `keep` to the same neighbours and edges between the two nodes become self-loops of `keep`.
+    /// Afterwards, the ids of `merge` and of the nodes that were merged into it resolve to `keep`
+    /// and `keep` takes their place in their hyperedges.
+    ///
+    /// Returns an error if either node does not exist or both refer to the same node.
+    fn merge_nodes<V: AsNodeRef, W: AsNodeRef>(&self, keep: V, merge: W) -> Result<(), GraphError> {
+        let keep = resolve_existing_node(self, keep.as_node_ref())?;
+        let merge = resolve_existing_node(self, merge.as_node_ref())?;
+        self.internal_merge_nodes(keep, merge)?;
+        Ok(())
+    }
+
+    /// Undo the merge of the node with id `node`.
+    ///
+    /// The updates that were added by the merge are removed again and the merged node is restored
+    /// together with its edges and its id. Merges into the same node need to be undone in reverse
+    /// order and a node that was merged into a node which was merged itself can only be restored
+    /// after undoing the later merge.
+    fn unmerge_node<V: AsNodeRef>(&self, node: V) -> Result<(), GraphError> {
+        let node = node.as_node_ref();
+        let merges = self.core_graph().node_merges();
+        let merged = match node {
+            NodeRef::Internal(vid) => merges
+                .and_then(|merges| merges.merged_into(vid))
+                .map(|_| vid),
+            NodeRef::External(gid) => merges.and_then(|merges| merges.merged_node(gid)),
+        };
+        let merged = merged.ok_or_else(|| GraphError::InvalidNodeUnmerge {
+            node: match node {
+                NodeRef::Internal(vid) => self.node_id(vid),
+                NodeRef::External(gid) => gid.to_owned(),
+            },
+            reason: "the node was not merged".to_owned(),
+        })?;
+        self.internal_unmerge_node(merged)
+    }
+
+    /// The ids that were merged into `node` and resolve to it
+    fn node_aliases<V: AsNodeRef>(&self, node: V) -> Result<Vec<GID>, GraphError> {
+        let vid = resolve_existing_node(self, node.as_node_ref())?;
+        Ok(self
+            .core_graph()
+            .node_merges()
+            .map(|merges| {
+                merges
+                    .merged_nodes_of(vid)
+                    .into_iter()
+                    .map(|v| self.node_id(v))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
+    /// The id of the node that `id` resolves to, i.e., the id of the node it was merged into for
+    /// the ids of merged nodes. Returns `None` if there is no node with this id.
+    fn canonical_id<V: AsNodeRef>(&self, id: V) -> Option<GID> {
+        self.internalise_node(id.as_node_ref())
+            .filter(|vid| self.has_node(*vid))
+            .map(|vid| self.node_id(vid))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{db::graph::views::deletion_graph::PersistentGraph, prelude::*};
+    use itertools::Itertools;
+    use raphtory_api::core::entities::GID;
+
+    fn setup() -> Graph {
+        let g = Graph::new();
+        g.add_node(1, "acct_1", [("balance", 10i64)], None).unwrap();
+        g.add_node(2, "A-0001", [("balance", 20i64)], None).unwrap();
+        g.node("acct_1")
+            .unwrap()
+            .add_constant_properties([("owner", "alice")])
+            .unwrap();
+        g.node("A-0001")
+            .unwrap()
+            .add_constant_properties([("owner", "bob"), ("bank", "x")])
+            .unwrap();
+        g.add_edge(3, "acct_1", "b", [("amount", 1i64)], None)
+            .unwrap();
+        g.add_edge(4, "A-0001", "b", [("amount", 2i64)], None)
+            .unwrap();
+        g.add_edge(5, "c", "A-0001", NO_PROPS, Some("transfer"))
+            .unwrap();
+        g.add_edge(6, "A-0001", "acct_1", NO_PROPS, None).unwrap();
+        g
+    }
+
+    #[test]
+    fn test_merge_nodes() {
+        let g = setup();
+        g.merge_nodes("acct_1", "A-0001").unwrap();
+
+        assert_eq!(g.count_nodes(), 3);
+        assert_eq!(g.count_edges(), 3);
+        let node = g.node("A-0001").unwrap();
+        assert_eq!(node.id(), GID::Str("acct_1".into()));
+        assert_eq!(node.history(), [1, 2, 3, 4, 5, 6]);
+        let balance = node.properties().temporal().get("balance").unwrap();
+        assert_eq!(balance.history(), [1, 2]);
+        assert_eq!(balance.values(), [Prop::I64(10), Prop::I64(20)]);
+        // existing constant properties of the kept node take precedence
+        assert_eq!(node.properties().get("owner"), Some(Prop::str("alice")));
+        assert_eq!(node.properties().get("bank"), Some(Prop::str("x")));
+
+        let edge = g.edge("acct_1", "b").unwrap();
+        assert_eq!(edge.history(), [3, 4]);
+        assert_eq!(
+            edge.properties().temporal().get("amount").unwrap().values(),
+            [Prop::I64(1), Prop::I64(2)]
+        );
+        assert_eq!(
+            g.edge("c", "acct_1").unwrap().layer_names().collect_vec(),
+            ["transfer"]
+        );
+        assert_eq!(g.edge("acct_1", "acct_1").unwrap().history(), [6]);
+
+        assert_eq!(
+            g.node_aliases("acct_1").unwrap(),
+            [GID::Str("A-0001".into())]
+        );
+        assert_eq!(g.canonical_id("A-0001"), Some(GID::Str("acct_1".into())));
+
+        // updates for the alias are added to the kept node
+        g.add_edge(7, "A-0001", "b", NO_PROPS, None).unwrap();
+        assert_eq!(g.edge("acct_1", "b").unwrap().history(), [3, 4, 7]);
+        assert_eq!(g.count_nodes(), 3);
+    }
+
+    #[test]
+    fn test_unmerge_node() {
+        let g = setup();
+        let before = g.materialize().unwrap();
+        g.merge_nodes("acct_1", "A-0001").unwrap();
+        g.unmerge_node("A-0001").unwrap();
+
+        assert_eq!(g.count_nodes(), 4);
+        assert_eq!(g.count_edges(), 4);
+        for id in ["acct_1", "A-0001", "b", "c"] {
+            let node = g.node(id).unwrap();
+            let original = before.node(id).unwrap();
+            assert_eq!(node.history(), original.history());
+            assert_eq!(
+                node.properties().as_map(),
+                original.properties().as_map(),
+                "properties of {id}"
+            );
+        }
+        assert_eq!(g.edge("acct_1", "b").unwrap().history(), [3]);
+        assert_eq!(g.edge("A-0001", "b").unwrap().history(), [4]);
+        assert!(g.has_edge("c", "A-0001"));
+        assert!(!g.has_edge("c", "acct_1"));
+        assert!(!g.has_edge("acct_1", "acct_1"));
+        assert!(g.node_aliases("acct_1").unwrap().is_empty());
+        assert_eq!(g.canonical_id("A-0001"), Some(GID::Str("A-0001".into())));
+
+        assert!(g.unmerge_node("A-0001").is_err());
+    }
+
+    #[test]
+    fn test_updates_after_merge_are_kept() {
+        let g = setup();
+        g.merge_nodes("acct_1", "A-0001").unwrap();
+        g.add_node(7, "A-0001", [("balance", 30i64)], None).unwrap();
+        g.unmerge_node("A-0001").unwrap();
+
+        assert_eq!(g.node("acct_1").unwrap().history(), [1, 3, 6, 7]);
+        assert_eq!(
+            g.node("acct_1")
+                .unwrap()
+                .properties()
+                .temporal()
+                .get("balance")
+                .unwrap()
+                .values(),
+            [Prop::I64(10), Prop::I64(30)]
+        );
+        assert_eq!(g.node("A-0001").unwrap().history(), [2, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_merge_hyperedges() {
+        let g = setup();
+        g.add_hyperedge(7, "m1", [("A-0001", Some("host")), ("b", None)], NO_PROPS)
+            .unwrap();
+        g.merge_nodes("acct_1", "A-0001").unwrap();
+
+        let participants = |g: &Graph| {
+            g.hyperedge("m1")
+                .unwrap()
+                .participants()
+                .into_iter()
+                .map(|(node, role)| (node.name(), role.map(|role| role.to_string())))
+                .collect_vec()
+        };
+        assert_eq!(
+            participants(&g),
+            [
+                ("acct_1".to_owned(), Some("host".to_owned())),
+                ("b".to_owned(), None)
+            ]
+        );
+        assert_eq!(g.node("acct_1").unwrap().hyperedge_degree(), 1);
+        assert_eq!(
+            g.node("b")
+                .unwrap()
+                .hyperedge_neighbours()
+                .name()
+                .collect_vec(),
+            ["acct_1"]
+        );
+
+        g.unmerge_node("A-0001").unwrap();
+        assert_eq!(
+            participants(&g),
+            [
+                ("A-0001".to_owned(), Some("host".to_owned())),
+                ("b".to_owned(), None)
+            ]
+        );
+        assert_eq!(g.node("acct_1").unwrap().hyperedge_degree(), 0);
+        assert_eq!(g.node("A-0001").unwrap().hyperedge_degree(), 1);
+    }
+
+    #[test]
+    fn test_merge_errors_and_order() {
+        let g = setup();
+        assert!(g.merge_nodes("acct_1", "acct_1").is_err());
+        assert!(g.merge_nodes("acct_1", "missing").is_err());
+
+        g.merge_nodes("acct_1", "A-0001").unwrap();
+        // the alias resolves to the kept node
+        assert!(g.merge_nodes("acct_1", "A-0001").is_err());
+        g.merge_nodes("acct_1", "b").unwrap();
+        assert!(g.unmerge_node("A-0001").is_err());
+        g.unmerge_node("b").unwrap();
+        g.unmerge_node("A-0001").unwrap();
+        assert_eq!(g.count_nodes(), 4);
+    }
+
+    #[test]
+    fn test_transitive_aliases() {
+        let g = PersistentGraph::new();
+        g.add_edge(1, "a", "x", NO_PROPS, None).unwrap();
+        g.add_edge(2, "b", "x", NO_PROPS, None).unwrap();
+        g.delete_edge(3, "b", "x", None).unwrap();
+        g.add_node(4, "c", NO_PROPS, None).unwrap();
+
+        g.merge_nodes("b", "a").unwrap();
+        g.merge_nodes("c", "b").unwrap();
+        assert_eq!(g.canonical_id("a"), Some(GID::Str("c".into())));
+        assert_eq!(
+            g.node_aliases("c")
+                .unwrap()
+                .into_iter()
+                .sorted()
+                .collect_vec(),
+            [GID::Str("a".into()), GID::Str("b".into())]
+        );
+        let edge = g.edge("c", "x").unwrap();
+        assert_eq!(edge.history(), [1, 2]);
+        assert_eq!(edge.deletions(), [3]);
+
+        assert!(g.unmerge_node("a").is_err());
+        g.unmerge_node("b").unwrap();
+        assert_eq!(g.canonical_id("a"), Some(GID::Str("b".into())));
+        assert!(!g.has_edge("c", "x"));
+
+        // removing a node drops the aliases of the nodes that were merged into it
+        g.remove_node("b").unwrap();
+        assert!(g.canonical_id("a").is_none());
+        assert!(g.unmerge_node("a").is_err());
+    }
+}
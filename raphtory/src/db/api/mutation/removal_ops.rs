@@ -0,0 +1,329 @@
+use crate::{
+    core::{
+        entities::{
+            nodes::node_ref::{AsNodeRef, NodeRef},
+            LayerIds, VID,
+        },
+        utils::{errors::GraphError, time::IntoTime},
+    },
+    db::api::{
+        mutation::internal::InternalRemovalOps,
+        view::{
+            internal::{CoreGraphOps, InternalLayerOps, InternalMaterialize},
+            StaticGraphViewOps,
+        },
+    },
+    prelude::GraphViewOps,
+};
+use raphtory_api::core::entities::EID;
+
+pub(super) fn resolve_existing_node<G: StaticGraphViewOps>(
+    graph: &G,
+    v: NodeRef,
+) -> Result<VID, GraphError> {
+    graph
+        .internalise_node(v)
+        .filter(|vid| graph.has_node(*vid))
+        .ok_or_else(|| match v {
+            NodeRef::External(gid) => GraphError::NodeNameError(gid.to_string()),
+            NodeRef::Internal(vid) => GraphError::NodeNameError(vid.0.to_string()),
+        })
+}
+
+fn resolve_existing_edge<G: StaticGraphViewOps>(
+    graph: &G,
+    src: NodeRef,
+    dst: NodeRef,
+) -> Result<EID, GraphError> {
+    let src_id = resolve_existing_node(graph, src)?;
+    let dst_id = resolve_existing_node(graph, dst)?;
+    graph
+        .edge(src_id, dst_id)
+        .map(|edge| edge.edge.pid())
+        .ok_or_else(|| GraphError::EdgeNameError {
+            src: graph.node_id(src_id).to_string(),
+            dst: graph.node_id(dst_id).to_string(),
+        })
+}
+
+/// `None` selects all layers of the edge
+fn resolve_layers<G: StaticGraphViewOps>(
+    graph: &G,
+    layer: Option<&str>,
+) -> Result<LayerIds, GraphError> {
+    match layer {
+        None => Ok(LayerIds::All),
+        Some(layer) => graph.layer_ids_from_names(layer.into()),
+    }
+}
+
+/// Destructive maintenance operations that remove data from a graph.
+///
+/// Removed nodes and edges keep an empty placeholder in storage such that the ids of the other
+/// entities do not change, all their updates and properties are dropped. Removals are appended
+/// to the write-ahead log of a cached graph and replayed in order with the other updates.
+pub trait RemovalOps: InternalRemovalOps + StaticGraphViewOps + Sized {
+    /// Remove a node together with all its edges, the node no longer takes part in any
+    /// hyperedges.
+    ///
+    /// Adding an update for the same id afterwards creates a new node.
+    ///
+    /// Returns an error if the node does not exist.
+    fn remove_node<V: AsNodeRef>(&self, node: V) -> Result<(), GraphError> {
+        let vid = resolve_existing_node(self, node.as_node_ref())?;
+        self.internal_remove_node(vid)
+    }
+
+    /// Remove an edge from `layer`, or from all its layers if `layer` is `None`.
+    ///
+    /// The times of the removed updates are dropped from the history of the endpoints unless
+    /// they have other edge or property updates at the same time.
+    ///
+    /// Returns an error if the edge does not exist.
+    fn remove_edge<V: AsNodeRef>(
+        &self,
+        src: V,
+        dst: V,
+        layer: Option<&str>,
+    ) -> Result<(), GraphError> {
+        let eid = resolve_existing_edge(self, src.as_node_ref(), dst.as_node_ref())?;
+        let layer_ids = resolve_layers(self, layer)?;
+        self.internal_remove_edge(eid, &layer_ids)
+    }
+
+    /// Remove the updates of temporal property `name` (or all temporal properties if `name` is
+    /// `None`) of `node` in the interval `[start, end)`
+    fn remove_node_property_updates<V: AsNodeRef, T: IntoTime>(
+        &self,
+        node: V,
+        start: T,
+        end: T,
+        name: Option<&str>,
+    ) -> Result<(), GraphError> {
+        let vid = resolve_existing_node(self, node.as_node_ref())?;
+        let prop_id = match name {
+            None => None,
+            Some(name) => match self.node_meta().temporal_prop_meta().get_id(name) {
+                Some(id) => Some(id),
+                None => return Ok(()),
+            },
+        };
+        self.internal_remove_node_property_updates(vid, start.into_time()..end.into_time(), prop_id)
+    }
+
+    /// Remove the updates of temporal property `name` (or all temporal properties if `name` is
+    /// `None`) of the edge from `src` to `dst` in the interval `[start, end)`. Only updates in
+    /// `layer` are removed if it is set.
+    fn remove_edge_property_updates<V: AsNodeRef, T: IntoTime>(
+        &self,
+        src: V,
+        dst: V,
+        start: T,
+        end: T,
+        name: Option<&str>,
+        layer: Option<&str>,
+    ) -> Result<(), GraphError> {
+        let eid = resolve_existing_edge(self, src.as_node_ref(), dst.as_node_ref())?;
+        let layer_ids = resolve_layers(self, layer)?;
+        let prop_id = match name {
+            None => None,
+            Some(name) => match self.edge_meta().temporal_prop_meta().get_id(name) {
+                Some(id) => Some(id),
+                None => return Ok(()),
+            },
+        };
+        self.internal_remove_edge_property_updates(
+            eid,
+            &layer_ids,
+            start.into_time()..end.into_time(),
+            prop_id,
+        )
+    }
+
+    /// Remove the updates of temporal graph property `name` (or all temporal graph properties if
+    /// `name` is `None`) in the interval `[start, end)`
+    fn remove_property_updates<T: IntoTime>(
+        &self,
+        start: T,
+        end: T,
+        name: Option<&str>,
+    ) -> Result<(), GraphError> {
+        let prop_id = match name {
+            None => None,
+            Some(name) => match self.graph_meta().get_temporal_id(name) {
+                Some(id) => Some(id),
+                None => return Ok(()),
+            },
+        };
+        self.internal_remove_graph_property_updates(start.into_time()..end.into_time(), prop_id)
+    }
+
+    /// Drop all history before `t` (e.g., to enforce a retention period).
+    ///
+    /// Nodes and edges without updates at or after `t` are removed. For graphs with persistent
+    /// semantics, edges that are alive at `t` are re-added at `t` and the last property values
+    /// before `t` are moved to `t`, such that the state of the graph at `t` does not change.
+    fn truncate_before<T: IntoTime>(&self, t: T) -> Result<(), GraphError> {
+        self.internal_truncate_before(t.into_time(), self.include_deletions())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{db::graph::views::deletion_graph::PersistentGraph, prelude::*};
+    use itertools::Itertools;
+
+    #[test]
+    fn test_remove_node_and_edges() {
+        let g = Graph::new();
+        g.add_edge(1, "a", "b", NO_PROPS, None).unwrap();
+        g.add_edge(2, "b", "c", NO_PROPS, Some("x")).unwrap();
+        g.add_edge(3, "b", "c", NO_PROPS, Some("y")).unwrap();
+        g.add_edge(4, "c", "a", NO_PROPS, None).unwrap();
+
+        g.remove_edge("b", "c", Some("x")).unwrap();
+        assert_eq!(g.edge("b", "c").unwrap().history(), [3]);
+
+        g.remove_node("a").unwrap();
+        assert!(!g.has_node("a"));
+        assert_eq!(g.count_nodes(), 2);
+        assert_eq!(g.count_edges(), 1);
+        assert_eq!(g.node("c").unwrap().degree(), 1);
+        assert!(g.remove_node("a").is_err());
+        assert!(g.remove_edge("a", "b", None).is_err());
+
+        g.remove_edge("b", "c", None).unwrap();
+        assert_eq!(g.count_edges(), 0);
+
+        // re-adding creates a fresh node without the old history
+        g.add_node(5, "a", NO_PROPS, None).unwrap();
+        assert_eq!(g.node("a").unwrap().history(), [5]);
+    }
+
+    #[test]
+    fn test_remove_edge_cleans_node_history() {
+        let g = Graph::new();
+        g.add_edge(1, "a", "b", NO_PROPS, None).unwrap();
+        g.add_edge(2, "a", "b", NO_PROPS, Some("x")).unwrap();
+        g.add_edge(2, "a", "c", NO_PROPS, None).unwrap();
+        g.add_node(3, "b", [("p", 1)], None).unwrap();
+        g.add_edge(3, "a", "b", NO_PROPS, None).unwrap();
+
+        g.remove_edge("a", "b", Some("x")).unwrap();
+        assert_eq!(g.node("a").unwrap().history(), [1, 2, 3]);
+        assert_eq!(g.node("b").unwrap().history(), [1, 3]);
+
+        g.remove_edge("a", "b", None).unwrap();
+        assert_eq!(g.node("a").unwrap().history(), [2]);
+        // the property update of b at 3 is kept
+        assert_eq!(g.node("b").unwrap().history(), [3]);
+        assert_eq!(g.earliest_time(), Some(2));
+    }
+
+    #[test]
+    fn test_remove_node_from_hyperedges() {
+        let g = Graph::new();
+        g.add_hyperedge(1, "m1", [("a", Some("host")), ("b", None)], NO_PROPS)
+            .unwrap();
+        g.add_hyperedge(2, "m2", [("a", None), ("a", Some("guest"))], NO_PROPS)
+            .unwrap();
+
+        g.remove_node("a").unwrap();
+        assert_eq!(g.count_hyperedges(), 2);
+        assert_eq!(
+            g.hyperedge("m1")
+                .unwrap()
+                .nodes()
+                .iter()
+                .map(|v| v.name())
+                .collect_vec(),
+            ["b"]
+        );
+        assert!(g.hyperedge("m2").unwrap().participants().is_empty());
+
+        // the new node does not inherit the hyperedges of the removed node
+        g.add_node(3, "a", NO_PROPS, None).unwrap();
+        assert_eq!(g.node("a").unwrap().hyperedge_degree(), 0);
+    }
+
+    #[test]
+    fn test_remove_property_updates() {
+        let g = Graph::new();
+        for t in 0..5 {
+            g.add_node(t, "a", [("p", t), ("q", t)], None).unwrap();
+            g.add_edge(t, "a", "b", [("w", t)], None).unwrap();
+            g.add_properties(t, [("g", t)]).unwrap();
+        }
+
+        g.remove_node_property_updates("a", 1, 3, Some("p"))
+            .unwrap();
+        let node = g.node("a").unwrap();
+        assert_eq!(
+            node.properties().temporal().get("p").unwrap().history(),
+            [0, 3, 4]
+        );
+        assert_eq!(
+            node.properties().temporal().get("q").unwrap().history(),
+            [0, 1, 2, 3, 4]
+        );
+        // unknown properties are ignored
+        g.remove_node_property_updates("a", 1, 3, Some("missing"))
+            .unwrap();
+
+        g.remove_edge_property_updates("a", "b", 3, 10, None, None)
+            .unwrap();
+        assert_eq!(
+            g.edge("a", "b")
+                .unwrap()
+                .properties()
+                .temporal()
+                .get("w")
+                .unwrap()
+                .values()
+                .collect_vec(),
+            [Prop::I64(0), Prop::I64(1), Prop::I64(2)]
+        );
+        // only property updates are removed, not the edge updates themselves
+        assert_eq!(g.edge("a", "b").unwrap().history(), [0, 1, 2, 3, 4]);
+
+        g.remove_property_updates(i64::MIN, 4, None).unwrap();
+        assert_eq!(g.properties().temporal().get("g").unwrap().history(), [4]);
+    }
+
+    #[test]
+    fn test_truncate_event_graph() {
+        let g = Graph::new();
+        g.add_edge(1, "a", "b", [("w", 1)], None).unwrap();
+        g.add_edge(2, "b", "c", NO_PROPS, None).unwrap();
+        g.add_edge(5, "a", "b", [("w", 5)], None).unwrap();
+
+        g.truncate_before(3).unwrap();
+        assert_eq!(g.earliest_time(), Some(5));
+        assert!(!g.has_node("c"));
+        assert!(!g.has_edge("b", "c"));
+        let edge = g.edge("a", "b").unwrap();
+        assert_eq!(edge.history(), [5]);
+        assert_eq!(
+            edge.properties().temporal().get("w").unwrap().history(),
+            [5]
+        );
+    }
+
+    #[test]
+    fn test_truncate_persistent_graph() {
+        let g = PersistentGraph::new();
+        g.add_edge(1, "a", "b", [("w", 1)], None).unwrap();
+        g.add_edge(2, "b", "c", NO_PROPS, None).unwrap();
+        g.delete_edge(3, "b", "c", None).unwrap();
+        g.add_edge(6, "a", "b", [("w", 6)], None).unwrap();
+        let before = g.at(4).edges().id().collect_vec();
+
+        g.truncate_before(4).unwrap();
+        assert_eq!(g.earliest_time(), Some(4));
+        assert!(!g.has_edge("b", "c"));
+        assert_eq!(g.at(4).edges().id().collect_vec(), before);
+        let edge = g.edge("a", "b").unwrap();
+        assert_eq!(edge.history(), [4, 6]);
+        assert_eq!(edge.at(4).properties().get("w"), Some(Prop::I32(1)));
+    }
+}
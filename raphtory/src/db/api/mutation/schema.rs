@@ -0,0 +1,727 @@
+//! Optional schemas that are enforced on the updates of a graph, and migrations of property types.
+//!
+//! A [GraphSchema] declares the node types and layers of a graph and, for each of them, the
+//! names and types of their properties. Once a schema is set with [SchemaOps::set_schema]:
+//!
+//! - nodes can only be given declared node types and edges can only be added to declared layers,
+//!   unless no node types or no layers are declared at all. Untyped nodes and the default layer
+//!   are always allowed and are checked against the schema declared for `_default`, if any.
+//! - nodes of a declared type and edges in a declared layer can only have the declared
+//!   properties, with the declared type and one of the allowed values.
+//! - every addition of such a node or edge needs to include the required temporal properties.
+//!   Constant properties are set separately from additions, so `required` has no effect on them.
+//!
+//! The schema is saved and cached together with the graph.
+//!
+//! The type of a property is fixed by its first update. [SchemaOps::migrate_node_property] and
+//! [SchemaOps::migrate_edge_property] change it by rewriting all stored values. Only conversions
+//! without loss are supported: widening integers (e.g. `I32` to `I64`), integers to floats that
+//! represent them exactly, `F32` to `F64`, and numbers or booleans to `Str`. A `Str` property can
+//! also be made categorical with [Migration::Categorical], which restricts it to the values it
+//! already has.
+
+use crate::{
+    core::{entities::properties::props::Meta, utils::errors::GraphError, Prop, PropType},
+    db::api::{storage::storage::Storage, view::Base},
+};
+use raphtory_api::core::storage::arc_str::ArcStr;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+const DEFAULT: &str = "_default";
+
+/// The type and constraints of a property
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PropertySchema {
+    pub dtype: PropType,
+    #[serde(default)]
+    pub required: bool,
+    #[serde(default)]
+    pub allowed_values: Option<Vec<Prop>>,
+}
+
+impl PropertySchema {
+    pub fn new(dtype: PropType) -> Self {
+        Self {
+            dtype,
+            required: false,
+            allowed_values: None,
+        }
+    }
+
+    pub fn required(mut self) -> Self {
+        self.required = true;
+        self
+    }
+
+    pub fn allowed_values<P: Into<Prop>>(mut self, values: impl IntoIterator<Item = P>) -> Self {
+        self.allowed_values = Some(values.into_iter().map(|v| v.into()).collect());
+        self
+    }
+}
+
+/// The properties of a node type or layer
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct EntitySchema {
+    #[serde(default)]
+    pub properties: HashMap<String, PropertySchema>,
+    #[serde(default)]
+    pub constant_properties: HashMap<String, PropertySchema>,
+}
+
+impl EntitySchema {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn property(mut self, name: impl Into<String>, schema: PropertySchema) -> Self {
+        self.properties.insert(name.into(), schema);
+        self
+    }
+
+    pub fn constant_property(mut self, name: impl Into<String>, schema: PropertySchema) -> Self {
+        self.constant_properties.insert(name.into(), schema);
+        self
+    }
+
+    fn props(&self, is_static: bool) -> &HashMap<String, PropertySchema> {
+        if is_static {
+            &self.constant_properties
+        } else {
+            &self.properties
+        }
+    }
+
+    fn check_update<'a>(
+        &self,
+        entity: &str,
+        props: impl IntoIterator<Item = (ArcStr, &'a Prop)>,
+        is_static: bool,
+    ) -> Result<(), GraphError> {
+        let declared = self.props(is_static);
+        let mut seen = vec![];
+        for (name, value) in props {
+            let name: &str = name.as_ref();
+            let schema = declared.get(name).ok_or_else(|| {
+                GraphError::SchemaViolation(format!(
+                    "property '{name}' is not declared for {entity}"
+                ))
+            })?;
+            if value.dtype() != schema.dtype {
+                return Err(GraphError::PropertyTypeError {
+                    name: name.to_owned(),
+                    expected: schema.dtype,
+                    actual: value.dtype(),
+                });
+            }
+            if let Some(allowed) = &schema.allowed_values {
+                if !allowed.contains(value) {
+                    return Err(GraphError::SchemaViolation(format!(
+                        "value {value} of property '{name}' is not allowed for {entity}"
+                    )));
+                }
+            }
+            seen.push(name.to_owned());
+        }
+        if !is_static {
+            if let Some((name, _)) = declared
+                .iter()
+                .find(|(name, schema)| schema.required && !seen.contains(name))
+            {
+                return Err(GraphError::SchemaViolation(format!(
+                    "required property '{name}' is missing for {entity}"
+                )));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The node types and layers of a graph and their properties
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GraphSchema {
+    #[serde(default)]
+    pub node_types: HashMap<String, EntitySchema>,
+    #[serde(default)]
+    pub layers: HashMap<String, EntitySchema>,
+}
+
+fn check_declared(
+    kind: &str,
+    declared: &HashMap<String, EntitySchema>,
+    name: &str,
+) -> Result<(), GraphError> {
+    if declared.is_empty() || name == DEFAULT || declared.contains_key(name) {
+        Ok(())
+    } else {
+        Err(GraphError::SchemaViolation(format!(
+            "{kind} '{name}' is not declared"
+        )))
+    }
+}
+
+fn check_property_type(
+    declared: &HashMap<String, EntitySchema>,
+    name: &str,
+    dtype: PropType,
+    is_static: bool,
+) -> Result<(), GraphError> {
+    for entity in declared.values() {
+        if let Some(schema) = entity.props(is_static).get(name) {
+            if schema.dtype != dtype {
+                return Err(GraphError::PropertyTypeError {
+                    name: name.to_owned(),
+                    expected: schema.dtype,
+                    actual: dtype,
+                });
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Check that the properties declared for `declared` have one type per name and match the types
+/// of the properties that already exist in `meta`
+fn check_declared_types(
+    kind: &str,
+    declared: &HashMap<String, EntitySchema>,
+    meta: &Meta,
+) -> Result<(), GraphError> {
+    for is_static in [false, true] {
+        let mapper = if is_static {
+            meta.const_prop_meta()
+        } else {
+            meta.temporal_prop_meta()
+        };
+        let mut types: HashMap<&str, PropType> = HashMap::new();
+        for (name, schema) in declared.values().flat_map(|e| e.props(is_static)) {
+            if *types.entry(name).or_insert(schema.dtype) != schema.dtype {
+                return Err(GraphError::SchemaViolation(format!(
+                    "property '{name}' of {kind}s is declared with different types"
+                )));
+            }
+            let existing = mapper
+                .get_id(name)
+                .and_then(|id| mapper.get_dtype(id))
+                .filter(|dtype| *dtype != PropType::Empty);
+            if let Some(existing) = existing {
+                if existing != schema.dtype {
+                    return Err(GraphError::PropertyTypeError {
+                        name: name.clone(),
+                        expected: schema.dtype,
+                        actual: existing,
+                    });
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+impl GraphSchema {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn node_type(mut self, name: impl Into<String>, schema: EntitySchema) -> Self {
+        self.node_types.insert(name.into(), schema);
+        self
+    }
+
+    pub fn layer(mut self, name: impl Into<String>, schema: EntitySchema) -> Self {
+        self.layers.insert(name.into(), schema);
+        self
+    }
+
+    /// Encode the schema as JSON, this is how it is saved with the graph
+    pub fn to_json(&self) -> Result<String, GraphError> {
+        serde_json::to_string(self)
+            .map_err(|err| GraphError::SchemaViolation(format!("failed to encode schema: {err}")))
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, GraphError> {
+        serde_json::from_str(json)
+            .map_err(|err| GraphError::SchemaViolation(format!("failed to decode schema: {err}")))
+    }
+
+    /// Check that the node types, layers and property types that already exist in a graph are
+    /// compatible with the schema
+    pub(crate) fn check_meta(&self, node_meta: &Meta, edge_meta: &Meta) -> Result<(), GraphError> {
+        for node_type in node_meta.get_all_node_types() {
+            check_declared("node type", &self.node_types, &node_type)?;
+        }
+        for layer in edge_meta.layer_meta().get_keys().iter() {
+            check_declared("layer", &self.layers, layer)?;
+        }
+        check_declared_types("node", &self.node_types, node_meta)?;
+        check_declared_types("edge", &self.layers, edge_meta)
+    }
+
+    pub(crate) fn check_node_type(&self, node_type: &str) -> Result<(), GraphError> {
+        check_declared("node type", &self.node_types, node_type)
+    }
+
+    pub(crate) fn check_layer(&self, layer: &str) -> Result<(), GraphError> {
+        check_declared("layer", &self.layers, layer)
+    }
+
+    pub(crate) fn check_node_property(
+        &self,
+        name: &str,
+        dtype: PropType,
+        is_static: bool,
+    ) -> Result<(), GraphError> {
+        check_property_type(&self.node_types, name, dtype, is_static)
+    }
+
+    pub(crate) fn check_edge_property(
+        &self,
+        name: &str,
+        dtype: PropType,
+        is_static: bool,
+    ) -> Result<(), GraphError> {
+        check_property_type(&self.layers, name, dtype, is_static)
+    }
+
+    fn entities(&self, owner: PropertyOwner) -> &HashMap<String, EntitySchema> {
+        match owner {
+            PropertyOwner::Node => &self.node_types,
+            PropertyOwner::Edge => &self.layers,
+        }
+    }
+
+    /// Whether any node type (or layer) declares the property `name`
+    pub(crate) fn declares_property(
+        &self,
+        owner: PropertyOwner,
+        name: &str,
+        is_static: bool,
+    ) -> bool {
+        self.entities(owner)
+            .values()
+            .any(|entity| entity.props(is_static).contains_key(name))
+    }
+
+    /// Add `values` to the allowed values of the property `name` wherever it is declared
+    pub(crate) fn add_allowed_values(
+        &mut self,
+        owner: PropertyOwner,
+        name: &str,
+        is_static: bool,
+        values: &[Prop],
+    ) {
+        let entities = match owner {
+            PropertyOwner::Node => &mut self.node_types,
+            PropertyOwner::Edge => &mut self.layers,
+        };
+        for entity in entities.values_mut() {
+            let props = if is_static {
+                &mut entity.constant_properties
+            } else {
+                &mut entity.properties
+            };
+            if let Some(schema) = props.get_mut(name) {
+                let allowed = schema.allowed_values.get_or_insert_with(Vec::new);
+                for value in values {
+                    if !allowed.contains(value) {
+                        allowed.push(value.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    /// Check an update of a node of type `node_type` with properties `props`
+    pub(crate) fn check_node_update<'a>(
+        &self,
+        node_type: Option<&str>,
+        props: impl IntoIterator<Item = (ArcStr, &'a Prop)>,
+        is_static: bool,
+    ) -> Result<(), GraphError> {
+        let node_type = node_type.unwrap_or(DEFAULT);
+        match self.node_types.get(node_type) {
+            Some(schema) => {
+                schema.check_update(&format!("node type '{node_type}'"), props, is_static)
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Check an update of an edge in `layer` with properties `props`
+    pub(crate) fn check_edge_update<'a>(
+        &self,
+        layer: &str,
+        props: impl IntoIterator<Item = (ArcStr, &'a Prop)>,
+        is_static: bool,
+    ) -> Result<(), GraphError> {
+        match self.layers.get(layer) {
+            Some(schema) => schema.check_update(&format!("layer '{layer}'"), props, is_static),
+            None => Ok(()),
+        }
+    }
+}
+
+/// The target of a property migration
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Migration {
+    /// Convert the values to another type
+    Type(PropType),
+    /// Keep the `Str` values, but only allow the values that the property already has in the
+    /// schema
+    Categorical,
+}
+
+impl From<PropType> for Migration {
+    fn from(value: PropType) -> Self {
+        Migration::Type(value)
+    }
+}
+
+/// Whether values of type `from` can be migrated to `to` without loss
+pub(crate) fn can_migrate(from: PropType, to: Migration) -> bool {
+    use PropType::*;
+    let to = match to {
+        Migration::Categorical => return from == Str,
+        Migration::Type(to) => to,
+    };
+    match (from, to) {
+        (from, to) if from == to => true,
+        (U8, U16 | U32 | U64 | I32 | I64 | F32 | F64) => true,
+        (U16, U32 | U64 | I32 | I64 | F32 | F64) => true,
+        (U32, U64 | I64 | F64) => true,
+        (I32, I64 | F64) => true,
+        (F32, F64) => true,
+        (U8 | U16 | U32 | U64 | I32 | I64 | F32 | F64 | Bool, Str) => true,
+        _ => false,
+    }
+}
+
+/// Convert the value `prop` of property `name` to type `to`, fails for conversions that are not
+/// allowed by [can_migrate]
+pub(crate) fn migrate_value(name: &str, prop: Prop, to: PropType) -> Result<Prop, GraphError> {
+    let from = prop.dtype();
+    if from == to {
+        return Ok(prop);
+    }
+    let error = || GraphError::PropertyMigrationError {
+        name: name.to_owned(),
+        reason: format!("{from:?} can't be converted to {to:?} without loss"),
+    };
+    if !can_migrate(from, to.into()) {
+        return Err(error());
+    }
+    let value = match to {
+        PropType::Str => Prop::str(prop.to_string()),
+        PropType::F32 => Prop::F32(prop.as_f64().ok_or_else(error)? as f32),
+        PropType::F64 => Prop::F64(prop.as_f64().ok_or_else(error)?),
+        _ => {
+            let value = prop.as_i128().ok_or_else(error)?;
+            match to {
+                PropType::U16 => Prop::U16(value as u16),
+                PropType::U32 => Prop::U32(value as u32),
+                PropType::U64 => Prop::U64(value as u64),
+                PropType::I32 => Prop::I32(value as i32),
+                PropType::I64 => Prop::I64(value as i64),
+                _ => return Err(error()),
+            }
+        }
+    };
+    Ok(value)
+}
+
+/// The owner of a property that is migrated
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum PropertyOwner {
+    Node,
+    Edge,
+}
+
+pub trait SchemaOps {
+    /// Enforce `schema` on all future updates of the graph.
+    ///
+    /// Fails if the graph already has node types, layers or property types that don't match the
+    /// schema. Existing property values are not checked.
+    fn set_schema(&self, schema: GraphSchema) -> Result<(), GraphError>;
+
+    /// The schema that is enforced on the graph, if any
+    fn schema(&self) -> Option<GraphSchema>;
+
+    /// Stop enforcing a schema on the graph
+    fn remove_schema(&self);
+
+    /// Migrate the node property `name` to another type (e.g. `PropType::I64`) and convert its
+    /// stored values, or make it categorical.
+    ///
+    /// A categorical property needs to be declared in the schema, its current values are added
+    /// to its allowed values. This should not run concurrently with updates to the property.
+    fn migrate_node_property(
+        &self,
+        name: &str,
+        constant: bool,
+        to: impl Into<Migration>,
+    ) -> Result<(), GraphError>;
+
+    /// Migrate the edge property `name` in all layers, see [SchemaOps::migrate_node_property]
+    fn migrate_edge_property(
+        &self,
+        name: &str,
+        constant: bool,
+        to: impl Into<Migration>,
+    ) -> Result<(), GraphError>;
+}
+
+impl<G: Base<Base = Storage>> SchemaOps for G {
+    fn set_schema(&self, schema: GraphSchema) -> Result<(), GraphError> {
+        self.base().set_schema(Some(schema))
+    }
+
+    fn schema(&self) -> Option<GraphSchema> {
+        self.base().schema().map(|schema| schema.as_ref().clone())
+    }
+
+    fn remove_schema(&self) {
+        // removing a schema can't conflict with the graph
+        let _ = self.base().set_schema(None);
+    }
+
+    fn migrate_node_property(
+        &self,
+        name: &str,
+        constant: bool,
+        to: impl Into<Migration>,
+    ) -> Result<(), GraphError> {
+        self.base()
+            .migrate_property(PropertyOwner::Node, name, constant, to.into())
+    }
+
+    fn migrate_edge_property(
+        &self,
+        name: &str,
+        constant: bool,
+        to: impl Into<Migration>,
+    ) -> Result<(), GraphError> {
+        self.base()
+            .migrate_property(PropertyOwner::Edge, name, constant, to.into())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{db::graph::views::deletion_graph::PersistentGraph, prelude::*};
+
+    fn schema() -> GraphSchema {
+        GraphSchema::new()
+            .node_type(
+                "person",
+                EntitySchema::new()
+                    .property("age", PropertySchema::new(PropType::I64).required())
+                    .constant_property(
+                        "role",
+                        PropertySchema::new(PropType::Str).allowed_values(["admin", "user"]),
+                    ),
+            )
+            .node_type("company", EntitySchema::new())
+            .layer(
+                "follows",
+                EntitySchema::new().property("weight", PropertySchema::new(PropType::F64)),
+            )
+    }
+
+    #[test]
+    fn test_schema_rejects_invalid_updates() {
+        let g = Graph::new();
+        g.set_schema(schema()).unwrap();
+
+        let alice = g
+            .add_node(0, "alice", [("age", Prop::I64(30))], Some("person"))
+            .unwrap();
+        alice
+            .add_constant_properties([("role", Prop::str("admin"))])
+            .unwrap();
+        g.add_node(0, "acme", NO_PROPS, Some("company")).unwrap();
+        g.add_node(0, "untyped", [("anything", 1u64)], None)
+            .unwrap();
+        g.add_edge(1, "alice", "acme", [("weight", 0.5)], Some("follows"))
+            .unwrap();
+
+        // undeclared node type and layer
+        assert!(g.add_node(1, "bob", NO_PROPS, Some("robot")).is_err());
+        assert!(g
+            .add_edge(1, "alice", "acme", NO_PROPS, Some("likes"))
+            .is_err());
+        // wrong type, missing required property, undeclared property
+        assert!(g
+            .add_node(1, "alice", [("age", Prop::I32(31))], None)
+            .is_err());
+        assert!(g.add_node(1, "alice", NO_PROPS, None).is_err());
+        assert!(g
+            .add_node(
+                1,
+                "alice",
+                [("age", Prop::I64(31)), ("height", Prop::I64(1))],
+                None
+            )
+            .is_err());
+        // value that is not allowed
+        assert!(alice
+            .update_constant_properties([("role", Prop::str("owner"))])
+            .is_err());
+        assert!(g
+            .add_edge(1, "alice", "acme", [("weight", 1u64)], Some("follows"))
+            .is_err());
+
+        assert_eq!(alice.properties().get("age"), Some(Prop::I64(30)));
+        assert_eq!(g.count_temporal_edges(), 1);
+
+        g.remove_schema();
+        g.add_node(1, "bob", NO_PROPS, Some("robot")).unwrap();
+    }
+
+    #[test]
+    fn test_schema_conflicting_with_graph() {
+        let g = PersistentGraph::new();
+        g.add_node(0, 1, [("age", Prop::F64(1.0))], Some("person"))
+            .unwrap();
+        assert!(matches!(
+            g.set_schema(schema()),
+            Err(GraphError::PropertyTypeError { .. })
+        ));
+        g.add_node(0, 2, NO_PROPS, Some("robot")).unwrap();
+        assert!(matches!(
+            g.set_schema(GraphSchema::new().node_type("person", EntitySchema::new())),
+            Err(GraphError::SchemaViolation(_))
+        ));
+        assert_eq!(g.schema(), None);
+    }
+
+    #[test]
+    fn test_migrate_property_types() {
+        let g = Graph::new();
+        g.add_node(0, 1, [("count", Prop::I32(1))], None).unwrap();
+        g.add_node(1, 1, [("count", Prop::I32(2))], None).unwrap();
+        g.add_edge(0, 1, 2, [("weight", Prop::F32(0.5))], Some("a"))
+            .unwrap();
+        g.add_edge(0, 1, 2, [("weight", Prop::F32(1.5))], Some("b"))
+            .unwrap();
+        g.edge(1, 2)
+            .unwrap()
+            .add_constant_properties([("flag", true)], Some("a"))
+            .unwrap();
+
+        assert!(g
+            .migrate_node_property("count", false, PropType::U8)
+            .is_err());
+        g.migrate_node_property("count", false, PropType::I64)
+            .unwrap();
+        g.migrate_edge_property("weight", false, PropType::F64)
+            .unwrap();
+        g.migrate_edge_property("flag", true, PropType::Str)
+            .unwrap();
+
+        let node = g.node(1).unwrap();
+        assert_eq!(
+            node.properties().temporal().get("count").unwrap().values(),
+            [Prop::I64(1), Prop::I64(2)]
+        );
+        let edge = g.edge(1, 2).unwrap();
+        assert_eq!(
+            edge.layers("b").unwrap().properties().get("weight"),
+            Some(Prop::F64(1.5))
+        );
+        assert_eq!(
+            edge.layers("a")
+                .unwrap()
+                .properties()
+                .constant()
+                .get("flag"),
+            Some(Prop::str("true"))
+        );
+
+        // new updates use the migrated type
+        assert!(g.add_node(2, 1, [("count", Prop::I32(3))], None).is_err());
+        g.add_node(2, 1, [("count", Prop::I64(3))], None).unwrap();
+    }
+
+    #[test]
+    fn test_categorical_property() {
+        let g = Graph::new();
+        g.add_node(0, 1, [("kind", Prop::str("a"))], None).unwrap();
+        g.add_node(1, 2, [("kind", Prop::str("b"))], None).unwrap();
+        g.add_node(2, 3, [("kind", Prop::str("a"))], None).unwrap();
+        g.add_node(0, 4, [("count", Prop::I32(1))], None).unwrap();
+
+        // needs to be declared in the schema and have type `Str`
+        assert!(g
+            .migrate_node_property("kind", false, Migration::Categorical)
+            .is_err());
+        g.set_schema(
+            GraphSchema::new().node_type(
+                "_default",
+                EntitySchema::new()
+                    .property("kind", PropertySchema::new(PropType::Str))
+                    .property("count", PropertySchema::new(PropType::I32)),
+            ),
+        )
+        .unwrap();
+        assert!(g
+            .migrate_node_property("count", false, Migration::Categorical)
+            .is_err());
+        g.migrate_node_property("kind", false, Migration::Categorical)
+            .unwrap();
+
+        let schema = g.schema().unwrap();
+        assert_eq!(
+            schema.node_types["_default"].properties["kind"].allowed_values,
+            Some(vec![Prop::str("a"), Prop::str("b")])
+        );
+        assert!(g.add_node(3, 1, [("kind", Prop::str("c"))], None).is_err());
+        g.add_node(3, 1, [("kind", Prop::str("b"))], None).unwrap();
+    }
+
+    #[cfg(feature = "proto")]
+    #[test]
+    fn test_schema_is_saved_and_cached() {
+        use crate::serialise::{CacheOps, StableDecode, StableEncode};
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("graph");
+        let g = Graph::new();
+        g.add_node(0, 1, [("count", Prop::I32(1))], Some("person"))
+            .unwrap();
+        g.cache(&path).unwrap();
+        let schema = GraphSchema::new().node_type(
+            "person",
+            EntitySchema::new().property("count", PropertySchema::new(PropType::I32)),
+        );
+        g.set_schema(schema.clone()).unwrap();
+        g.write_updates().unwrap();
+        assert_eq!(Graph::load_cached(&path).unwrap().schema(), Some(schema));
+
+        // migrations of cached graphs rewrite the cache
+        g.remove_schema();
+        g.migrate_node_property("count", false, PropType::I64)
+            .unwrap();
+        g.add_node(1, 1, [("count", Prop::I64(2))], Some("person"))
+            .unwrap();
+        g.write_updates().unwrap();
+        let loaded = Graph::load_cached(&path).unwrap();
+        assert_eq!(loaded.schema(), None);
+        assert_eq!(
+            loaded
+                .node(1)
+                .unwrap()
+                .properties()
+                .temporal()
+                .get("count")
+                .unwrap()
+                .values(),
+            [Prop::I64(1), Prop::I64(2)]
+        );
+
+        g.set_schema(GraphSchema::new().node_type("person", EntitySchema::new()))
+            .unwrap();
+        let decoded = Graph::decode_from_bytes(&g.encode_to_vec()).unwrap();
+        assert_eq!(decoded.schema(), g.schema());
+    }
+}
@@ -0,0 +1,600 @@
+//! Computed properties are derived from the stored properties or the structure of a graph view
+//! and are only evaluated when they are accessed.
+//!
+//! There are two kinds of computed properties:
+//!
+//! - [PropExpr] expressions combine the properties of the same node or edge with arithmetic,
+//!   e.g., `amount * fx_rate`. An expression that refers to a temporal property is temporal
+//!   itself and has a value for every update of the temporal properties it refers to, otherwise
+//!   it is a constant property.
+//! - Closures compute a single value for a node or edge in the current window of the view,
+//!   e.g., the degree of a node, and appear as constant properties.
+//!
+//! Computed properties are registered on a view with
+//! [ComputedPropertiesOps](crate::db::api::view::ComputedPropertiesOps) and show up through
+//! [Properties](crate::db::api::properties::Properties) like stored properties.
+
+use crate::{
+    core::{utils::errors::GraphError, Prop},
+    db::{
+        api::{properties::internal::PropertiesOps, view::internal::DynamicGraph},
+        graph::{edge::EdgeView, node::NodeView},
+    },
+};
+use itertools::Itertools;
+use num_traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub};
+use raphtory_api::core::storage::arc_str::ArcStr;
+use std::{
+    fmt::{Debug, Formatter},
+    iter::Peekable,
+    ops::{Add, Div, Mul, Neg, Sub},
+    str::{CharIndices, FromStr},
+    sync::Arc,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+}
+
+/// An arithmetic expression over the properties of a node or edge.
+///
+/// Expressions can be built with the arithmetic operators or parsed from a string like
+/// `"amount * fx_rate"`. Property names that are not valid identifiers can be quoted with
+/// backticks.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropExpr {
+    /// The value of the property with the given name
+    Prop(ArcStr),
+    /// A literal value
+    Value(Prop),
+    Neg(Box<PropExpr>),
+    Binary(BinaryOp, Box<PropExpr>, Box<PropExpr>),
+}
+
+impl PropExpr {
+    pub fn prop(name: impl Into<ArcStr>) -> Self {
+        PropExpr::Prop(name.into())
+    }
+
+    pub fn value(value: impl Into<Prop>) -> Self {
+        PropExpr::Value(value.into())
+    }
+
+    /// The distinct names of the properties the expression refers to
+    pub fn names(&self) -> Vec<&ArcStr> {
+        let mut names = vec![];
+        self.collect_names(&mut names);
+        names.into_iter().unique().collect()
+    }
+
+    fn collect_names<'a>(&'a self, names: &mut Vec<&'a ArcStr>) {
+        match self {
+            PropExpr::Prop(name) => names.push(name),
+            PropExpr::Value(_) => {}
+            PropExpr::Neg(expr) => expr.collect_names(names),
+            PropExpr::Binary(_, left, right) => {
+                left.collect_names(names);
+                right.collect_names(names);
+            }
+        }
+    }
+
+    /// Evaluate the expression, looking up the values of properties with `lookup`.
+    ///
+    /// Returns `None` if a property is missing or the values do not support the operation.
+    /// Integer operations on the same type stay integers, any other numeric operation is
+    /// evaluated as `f64`.
+    pub fn eval(&self, lookup: &impl Fn(&str) -> Option<Prop>) -> Option<Prop> {
+        match self {
+            PropExpr::Prop(name) => lookup(name),
+            PropExpr::Value(value) => Some(value.clone()),
+            PropExpr::Neg(expr) => match expr.eval(lookup)? {
+                Prop::I32(v) => v.checked_neg().map(Prop::I32),
+                Prop::I64(v) => v.checked_neg().map(Prop::I64),
+                Prop::F32(v) => Some(Prop::F32(-v)),
+                value => value.as_f64().map(|v| Prop::F64(-v)),
+            },
+            PropExpr::Binary(op, left, right) => {
+                apply(*op, left.eval(lookup)?, right.eval(lookup)?)
+            }
+        }
+    }
+}
+
+fn checked<T: CheckedAdd + CheckedSub + CheckedMul + CheckedDiv>(
+    op: BinaryOp,
+    a: T,
+    b: T,
+) -> Option<T> {
+    match op {
+        BinaryOp::Add => a.checked_add(&b),
+        BinaryOp::Sub => a.checked_sub(&b),
+        BinaryOp::Mul => a.checked_mul(&b),
+        BinaryOp::Div => a.checked_div(&b),
+    }
+}
+
+fn apply(op: BinaryOp, left: Prop, right: Prop) -> Option<Prop> {
+    match (left, right) {
+        (Prop::I32(a), Prop::I32(b)) => checked(op, a, b).map(Prop::I32),
+        (Prop::I64(a), Prop::I64(b)) => checked(op, a, b).map(Prop::I64),
+        (Prop::U32(a), Prop::U32(b)) => checked(op, a, b).map(Prop::U32),
+        (Prop::U64(a), Prop::U64(b)) => checked(op, a, b).map(Prop::U64),
+        (Prop::Str(a), Prop::Str(b)) if op == BinaryOp::Add => Prop::Str(a).add(Prop::Str(b)),
+        (left, right) => {
+            let (a, b) = (left.as_f64()?, right.as_f64()?);
+            let value = match op {
+                BinaryOp::Add => a + b,
+                BinaryOp::Sub => a - b,
+                BinaryOp::Mul => a * b,
+                BinaryOp::Div if b == 0.0 => return None,
+                BinaryOp::Div => a / b,
+            };
+            Some(Prop::F64(value))
+        }
+    }
+}
+
+macro_rules! impl_binary_op {
+    ($trait:ident, $method:ident, $op:expr) => {
+        impl $trait for PropExpr {
+            type Output = PropExpr;
+
+            fn $method(self, rhs: PropExpr) -> PropExpr {
+                PropExpr::Binary($op, Box::new(self), Box::new(rhs))
+            }
+        }
+    };
+}
+
+impl_binary_op!(Add, add, BinaryOp::Add);
+impl_binary_op!(Sub, sub, BinaryOp::Sub);
+impl_binary_op!(Mul, mul, BinaryOp::Mul);
+impl_binary_op!(Div, div, BinaryOp::Div);
+
+impl Neg for PropExpr {
+    type Output = PropExpr;
+
+    fn neg(self) -> PropExpr {
+        PropExpr::Neg(Box::new(self))
+    }
+}
+
+impl FromStr for PropExpr {
+    type Err = GraphError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parser = Parser {
+            expr: s,
+            chars: s.char_indices().peekable(),
+        };
+        let expr = parser.sum()?;
+        parser.skip_whitespace();
+        match parser.chars.next() {
+            None => Ok(expr),
+            Some((pos, c)) => Err(parser.error(format!("unexpected {c:?} at {pos}"))),
+        }
+    }
+}
+
+/// Recursive descent parser for expressions with the usual operator precedence
+struct Parser<'a> {
+    expr: &'a str,
+    chars: Peekable<CharIndices<'a>>,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, reason: String) -> GraphError {
+        GraphError::PropExprParseError {
+            expr: self.expr.to_string(),
+            reason,
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|(_, c)| c.is_whitespace()).is_some() {}
+    }
+
+    fn sum(&mut self) -> Result<PropExpr, GraphError> {
+        let mut expr = self.product()?;
+        loop {
+            self.skip_whitespace();
+            match self.chars.next_if(|(_, c)| *c == '+' || *c == '-') {
+                Some((_, '+')) => expr = expr + self.product()?,
+                Some(_) => expr = expr - self.product()?,
+                None => return Ok(expr),
+            }
+        }
+    }
+
+    fn product(&mut self) -> Result<PropExpr, GraphError> {
+        let mut expr = self.factor()?;
+        loop {
+            self.skip_whitespace();
+            match self.chars.next_if(|(_, c)| *c == '*' || *c == '/') {
+                Some((_, '*')) => expr = expr * self.factor()?,
+                Some(_) => expr = expr / self.factor()?,
+                None => return Ok(expr),
+            }
+        }
+    }
+
+    fn factor(&mut self) -> Result<PropExpr, GraphError> {
+        self.skip_whitespace();
+        match self.chars.peek().copied() {
+            None => Err(self.error("unexpected end of expression".to_string())),
+            Some((_, '-')) => {
+                self.chars.next();
+                Ok(-self.factor()?)
+            }
+            Some((_, '(')) => {
+                self.chars.next();
+                let expr = self.sum()?;
+                self.skip_whitespace();
+                match self.chars.next() {
+                    Some((_, ')')) => Ok(expr),
+                    _ => Err(self.error("missing closing parenthesis".to_string())),
+                }
+            }
+            Some((_, quote @ ('`' | '"' | '\''))) => {
+                self.chars.next();
+                let text = self.take_while(|c| c != quote);
+                if self.chars.next().is_none() {
+                    return Err(self.error(format!("missing closing {quote}")));
+                }
+                Ok(match quote {
+                    '`' => PropExpr::prop(text),
+                    _ => PropExpr::value(text),
+                })
+            }
+            Some((pos, c)) if c.is_ascii_digit() || c == '.' => {
+                let number = self.take_while(|c| c.is_ascii_alphanumeric() || c == '.');
+                if let Ok(value) = number.parse::<i64>() {
+                    Ok(PropExpr::value(value))
+                } else if let Ok(value) = number.parse::<f64>() {
+                    Ok(PropExpr::value(value))
+                } else {
+                    Err(self.error(format!("invalid number {number:?} at {pos}")))
+                }
+            }
+            Some((_, c)) if c.is_alphabetic() || c == '_' => {
+                let name = self.take_while(|c| c.is_alphanumeric() || c == '_');
+                Ok(PropExpr::prop(name))
+            }
+            Some((pos, c)) => Err(self.error(format!("unexpected {c:?} at {pos}"))),
+        }
+    }
+
+    fn take_while(&mut self, cond: impl Fn(char) -> bool) -> &'a str {
+        let start = match self.chars.peek() {
+            None => return "",
+            Some((pos, _)) => *pos,
+        };
+        let mut end = start;
+        while let Some((pos, c)) = self.chars.next_if(|(_, c)| cond(*c)) {
+            end = pos + c.len_utf8();
+        }
+        &self.expr[start..end]
+    }
+}
+
+/// Closure of a computed property that is evaluated for a node or edge of the view
+pub type ComputedFn<E> = Arc<dyn Fn(&E) -> Option<Prop> + Send + Sync>;
+
+/// A computed property of a node (`E = NodeView<DynamicGraph>`) or edge
+/// (`E = EdgeView<DynamicGraph>`)
+pub enum ComputedProp<E> {
+    Expr(PropExpr),
+    Fn { op: ComputedFn<E>, cached: bool },
+}
+
+pub type ComputedNodeProp = ComputedProp<NodeView<DynamicGraph>>;
+pub type ComputedEdgeProp = ComputedProp<EdgeView<DynamicGraph>>;
+
+impl<E> Clone for ComputedProp<E> {
+    fn clone(&self) -> Self {
+        match self {
+            ComputedProp::Expr(expr) => ComputedProp::Expr(expr.clone()),
+            ComputedProp::Fn { op, cached } => ComputedProp::Fn {
+                op: op.clone(),
+                cached: *cached,
+            },
+        }
+    }
+}
+
+impl<E> Debug for ComputedProp<E> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ComputedProp::Expr(expr) => f.debug_tuple("Expr").field(expr).finish(),
+            ComputedProp::Fn { cached, .. } => {
+                f.debug_struct("Fn").field("cached", cached).finish()
+            }
+        }
+    }
+}
+
+impl<E> From<PropExpr> for ComputedProp<E> {
+    fn from(value: PropExpr) -> Self {
+        ComputedProp::Expr(value)
+    }
+}
+
+impl<E> ComputedProp<E> {
+    /// A property computed by `op` for each node or edge in the current window of the view
+    pub fn func<F: Fn(&E) -> Option<Prop> + Send + Sync + 'static>(op: F) -> Self {
+        ComputedProp::Fn {
+            op: Arc::new(op),
+            cached: false,
+        }
+    }
+
+    /// Cache the values of a closure for each node or edge and window of the view.
+    ///
+    /// Cached values are not invalidated when the underlying graph changes.
+    /// This has no effect for expressions.
+    pub fn cached(self) -> Self {
+        match self {
+            ComputedProp::Fn { op, .. } => ComputedProp::Fn { op, cached: true },
+            expr => expr,
+        }
+    }
+
+    /// Whether the property is temporal for `entity`
+    pub(crate) fn is_temporal<P: PropertiesOps + ?Sized>(&self, entity: &P) -> bool {
+        match self {
+            ComputedProp::Expr(expr) => expr
+                .names()
+                .into_iter()
+                .any(|name| entity.get_temporal_prop_id(name).is_some()),
+            ComputedProp::Fn { .. } => false,
+        }
+    }
+
+    /// The values of a temporal expression for `entity`, computed at every update of the
+    /// temporal properties it refers to
+    pub(crate) fn temporal_updates<P: PropertiesOps + ?Sized>(
+        &self,
+        entity: &P,
+    ) -> Vec<(i64, Prop)> {
+        match self {
+            ComputedProp::Expr(expr) => {
+                let names = expr.names();
+                let ids = names
+                    .iter()
+                    .map(|name| entity.get_temporal_prop_id(name))
+                    .collect_vec();
+                // the histories are sorted, merge them lazily and keep track of the latest value
+                // of each property instead of looking it up again at every time
+                let updates = ids
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(index, id)| Some((index, (*id)?)))
+                    .map(|(index, id)| {
+                        entity
+                            .temporal_history(id)
+                            .into_iter()
+                            .zip(entity.temporal_values(id))
+                            .map(move |(t, value)| (t, index, value))
+                    })
+                    .kmerge_by(|(t1, ..), (t2, ..)| t1 < t2);
+                let mut latest: Vec<Option<Prop>> = vec![None; names.len()];
+                let mut values = vec![];
+                for (t, group) in &updates.chunk_by(|(t, ..)| *t) {
+                    for (_, index, value) in group {
+                        latest[index] = Some(value);
+                    }
+                    let value = expr.eval(&|name| {
+                        let index = names.iter().position(|other| **other == name)?;
+                        match ids[index] {
+                            Some(_) => latest[index].clone(),
+                            None => const_value(entity, name),
+                        }
+                    });
+                    values.extend(value.map(|value| (t, value)));
+                }
+                values
+            }
+            ComputedProp::Fn { .. } => vec![],
+        }
+    }
+
+    /// The value of a constant expression for `entity`, closures are evaluated by the view
+    pub(crate) fn const_expr_value<P: PropertiesOps + ?Sized>(&self, entity: &P) -> Option<Prop> {
+        match self {
+            ComputedProp::Expr(expr) if !self.is_temporal(entity) => {
+                expr.eval(&|name| const_value(entity, name))
+            }
+            _ => None,
+        }
+    }
+}
+
+fn const_value<P: PropertiesOps + ?Sized>(entity: &P, name: &str) -> Option<Prop> {
+    entity
+        .get_const_prop_id(name)
+        .and_then(|id| entity.get_const_prop(id))
+}
+
+/// The computed node and edge properties of a view
+#[derive(Clone, Debug, Default)]
+pub struct ComputedProperties {
+    node: Vec<(ArcStr, ComputedNodeProp)>,
+    edge: Vec<(ArcStr, ComputedEdgeProp)>,
+}
+
+/// Computed properties use ids counting down from `usize::MAX` such that they never clash with
+/// the ids of stored properties
+#[inline]
+pub(crate) fn computed_prop_id(index: usize) -> usize {
+    usize::MAX - index
+}
+
+#[inline]
+pub(crate) fn computed_prop_index(id: usize) -> Option<usize> {
+    (id > usize::MAX / 2).then(|| usize::MAX - id)
+}
+
+fn insert<E>(
+    props: &mut Vec<(ArcStr, ComputedProp<E>)>,
+    name: &str,
+    prop: ComputedProp<E>,
+) -> Result<(), GraphError> {
+    let index = match props.iter().position(|(n, _)| n.as_ref() == name) {
+        Some(index) => {
+            props[index].1 = prop;
+            index
+        }
+        None => {
+            props.push((name.into(), prop));
+            props.len() - 1
+        }
+    };
+    // follow the references of the new expression to make sure it does not depend on itself
+    let mut stack = vec![index];
+    let mut visited = vec![];
+    while let Some(current) = stack.pop() {
+        if let ComputedProp::Expr(expr) = &props[current].1 {
+            for reference in expr.names() {
+                if let Some(next) = props.iter().position(|(n, _)| n == reference) {
+                    if next == index {
+                        return Err(GraphError::InvalidComputedProperty {
+                            name: name.to_string(),
+                            reason: format!("cyclic reference via {reference}"),
+                        });
+                    }
+                    if !visited.contains(&next) {
+                        visited.push(next);
+                        stack.push(next);
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+impl ComputedProperties {
+    /// Add a computed node property, replacing any computed node property with the same name
+    pub fn add_node_prop(
+        &mut self,
+        name: &str,
+        prop: impl Into<ComputedNodeProp>,
+    ) -> Result<(), GraphError> {
+        let mut node = self.node.clone();
+        insert(&mut node, name, prop.into())?;
+        self.node = node;
+        Ok(())
+    }
+
+    /// Add a computed edge property, replacing any computed edge property with the same name
+    pub fn add_edge_prop(
+        &mut self,
+        name: &str,
+        prop: impl Into<ComputedEdgeProp>,
+    ) -> Result<(), GraphError> {
+        let mut edge = self.edge.clone();
+        insert(&mut edge, name, prop.into())?;
+        self.edge = edge;
+        Ok(())
+    }
+
+    pub fn node_prop_names(&self) -> impl Iterator<Item = &ArcStr> {
+        self.node.iter().map(|(name, _)| name)
+    }
+
+    pub fn edge_prop_names(&self) -> impl Iterator<Item = &ArcStr> {
+        self.edge.iter().map(|(name, _)| name)
+    }
+
+    pub(crate) fn num_node_props(&self) -> usize {
+        self.node.len()
+    }
+
+    pub(crate) fn num_edge_props(&self) -> usize {
+        self.edge.len()
+    }
+
+    pub(crate) fn node_prop_index(&self, name: &str) -> Option<usize> {
+        self.node.iter().position(|(n, _)| n.as_ref() == name)
+    }
+
+    pub(crate) fn edge_prop_index(&self, name: &str) -> Option<usize> {
+        self.edge.iter().position(|(n, _)| n.as_ref() == name)
+    }
+
+    pub(crate) fn node_prop(&self, index: usize) -> &(ArcStr, ComputedNodeProp) {
+        &self.node[index]
+    }
+
+    pub(crate) fn edge_prop(&self, index: usize) -> &(ArcStr, ComputedEdgeProp) {
+        &self.edge[index]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.node.is_empty() && self.edge.is_empty()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_parse_and_eval() {
+        let props: HashMap<&str, Prop> = HashMap::from([
+            ("amount", Prop::I64(10)),
+            ("fx_rate", Prop::F64(1.5)),
+            ("count", Prop::I64(4)),
+            ("first name", Prop::str("Ada")),
+        ]);
+        let eval = |expr: &str| {
+            expr.parse::<PropExpr>()
+                .unwrap()
+                .eval(&|name| props.get(name).cloned())
+        };
+        assert_eq!(eval("amount * fx_rate"), Some(Prop::F64(15.0)));
+        assert_eq!(eval("amount + count * 2"), Some(Prop::I64(18)));
+        assert_eq!(eval("(amount + count) * 2"), Some(Prop::I64(28)));
+        assert_eq!(eval("-amount / 4"), Some(Prop::I64(-2)));
+        assert_eq!(eval("amount / 0"), None);
+        assert_eq!(eval("amount - 0.5"), Some(Prop::F64(9.5)));
+        assert_eq!(
+            eval("`first name` + ' Lovelace'"),
+            Some(Prop::str("Ada Lovelace"))
+        );
+        assert_eq!(eval("missing + 1"), None);
+
+        assert!("amount *".parse::<PropExpr>().is_err());
+        assert!("(amount".parse::<PropExpr>().is_err());
+        assert!("amount ? 1".parse::<PropExpr>().is_err());
+    }
+
+    #[test]
+    fn test_cyclic_references() {
+        let mut props = ComputedProperties::default();
+        props
+            .add_node_prop("a", "b + 1".parse::<PropExpr>().unwrap())
+            .unwrap();
+        props
+            .add_node_prop("b", "c * 2".parse::<PropExpr>().unwrap())
+            .unwrap();
+        assert!(props
+            .add_node_prop("c", "a - 1".parse::<PropExpr>().unwrap())
+            .is_err());
+        assert!(props
+            .add_node_prop("d", "d + 1".parse::<PropExpr>().unwrap())
+            .is_err());
+        assert_eq!(props.num_node_props(), 2);
+        assert_eq!(
+            computed_prop_index(computed_prop_id(props.num_node_props())),
+            Some(2)
+        );
+        assert_eq!(computed_prop_index(3), None);
+    }
+}
@@ -1,9 +1,12 @@
 mod constant_props;
+pub mod computed;
 pub mod dyn_props;
 pub mod internal;
 mod props;
+mod temporal_analytics;
 mod temporal_props;
 
 pub use constant_props::*;
 pub use props::*;
+pub use temporal_analytics::*;
 pub use temporal_props::*;
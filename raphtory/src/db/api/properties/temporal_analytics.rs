@@ -0,0 +1,701 @@
+//! Aggregations over the history of temporal properties.
+//!
+//! The history of a single property is aggregated with the methods of [TemporalPropertyView]. The
+//! same aggregations are available for a property of all nodes or all edges in a view, computed
+//! in parallel into a [NodeState] or [EdgeState].
+//!
+//! Buckets of [TemporalPropertyView::resample] are aligned to the calendar in the time zone of
+//! the interval (UTC by default), so resampling by `"1 day"` produces one bucket per calendar day,
+//! by `"1 week"` one per ISO week and by `"1 month"` one per calendar month. Time-weighted
+//! statistics treat the property as a step function: every value holds until the next update.
+
+use crate::{
+    core::{
+        entities::edges::edge_ref::EdgeRef,
+        utils::time::{error::ParseTimeError, Interval},
+        Prop, PropType,
+    },
+    db::{
+        api::{
+            properties::{internal::PropertiesOps, TemporalPropertyView},
+            state::{EdgeState, NodeState},
+            view::BaseNodeViewOps,
+        },
+        graph::{edge::EdgeView, edges::Edges, node::NodeView, nodes::Nodes},
+    },
+    prelude::*,
+};
+use itertools::Itertools;
+use raphtory_api::core::storage::arc_str::ArcStr;
+use rayon::prelude::*;
+use std::{cmp::Ordering, collections::VecDeque, sync::Arc};
+
+/// How the values of a property are combined
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Aggregation {
+    Sum,
+    /// The mean of numeric values as `F64`
+    Mean,
+    Min,
+    Max,
+    /// The number of values as `U64`
+    Count,
+    First,
+    Last,
+}
+
+impl Aggregation {
+    /// Combine `values`, returns `None` if there are no values or their type doesn't support the
+    /// aggregation (e.g., the mean of strings)
+    pub fn apply(&self, values: impl IntoIterator<Item = Prop>) -> Option<Prop> {
+        let mut values = values.into_iter();
+        match self {
+            Aggregation::Sum => {
+                let first = values.next()?;
+                values.try_fold(first, |acc, value| acc.add(value))
+            }
+            Aggregation::Mean => {
+                let (sum, count) = values.try_fold((0.0, 0usize), |(sum, count), value| {
+                    value.as_f64().map(|value| (sum + value, count + 1))
+                })?;
+                (count > 0).then(|| Prop::F64(sum / count as f64))
+            }
+            Aggregation::Min => {
+                let first = values.next()?;
+                values.try_fold(first, |acc, value| acc.min(value))
+            }
+            Aggregation::Max => {
+                let first = values.next()?;
+                values.try_fold(first, |acc, value| acc.max(value))
+            }
+            Aggregation::Count => Some(Prop::U64(values.count() as u64)),
+            Aggregation::First => values.next(),
+            Aggregation::Last => values.last(),
+        }
+    }
+}
+
+fn resample(
+    history: &[(i64, Prop)],
+    interval: Interval,
+    aggregation: Aggregation,
+) -> Result<Vec<(i64, Option<Prop>)>, ParseTimeError> {
+    let mut buckets = vec![];
+    let mut start = 0;
+    while start < history.len() {
+        let bucket = interval.bucket_start(history[start].0)?;
+        let mut end = start + 1;
+        while end < history.len() && interval.bucket_start(history[end].0)? == bucket {
+            end += 1;
+        }
+        let values = history[start..end].iter().map(|(_, value)| value.clone());
+        buckets.push((bucket, aggregation.apply(values)));
+        start = end;
+    }
+    Ok(buckets)
+}
+
+/// The state of an aggregation over a window that slides forward over a history. Every value
+/// enters and leaves the window once, so the aggregate of each window is updated in amortised
+/// constant time instead of being recomputed.
+struct SlidingWindow<'a> {
+    history: &'a [(i64, Prop)],
+    aggregation: Aggregation,
+    start: usize,
+    end: usize,
+    /// The number of values of each type in the window
+    types: Vec<(PropType, usize)>,
+    /// The number of values in the window that are not comparable to themselves, e.g., `NaN`
+    incomparable: usize,
+    /// The number of values in the window that are not finite, they are not part of `float_sum`
+    /// as it couldn't recover from adding them
+    non_finite: usize,
+    /// Indices of the values that can still become the minimum (or maximum) of the window as
+    /// values leave it, the first one is the current minimum (or maximum)
+    extremes: VecDeque<usize>,
+    int_sum: i128,
+    float_sum: f64,
+}
+
+impl<'a> SlidingWindow<'a> {
+    fn new(history: &'a [(i64, Prop)], aggregation: Aggregation) -> Self {
+        Self {
+            history,
+            aggregation,
+            start: 0,
+            end: 0,
+            types: vec![],
+            incomparable: 0,
+            non_finite: 0,
+            extremes: VecDeque::new(),
+            int_sum: 0,
+            float_sum: 0.0,
+        }
+    }
+
+    fn count_type(&mut self, dtype: PropType, added: bool) {
+        match self.types.iter().position(|(t, _)| *t == dtype) {
+            Some(i) if added => self.types[i].1 += 1,
+            Some(i) => {
+                self.types[i].1 -= 1;
+                if self.types[i].1 == 0 {
+                    self.types.swap_remove(i);
+                }
+            }
+            None => self.types.push((dtype, 1)),
+        }
+    }
+
+    /// Whether `ord` is the order of a value that evicts an earlier one from the extremes
+    fn evicts(&self, ord: Ordering) -> bool {
+        match self.aggregation {
+            Aggregation::Min => ord == Ordering::Less,
+            _ => ord == Ordering::Greater,
+        }
+    }
+
+    /// Add the next value of the history to the window
+    fn push(&mut self) {
+        let value = &self.history[self.end].1;
+        self.count_type(value.dtype(), true);
+        if value.partial_cmp(value).is_none() {
+            self.incomparable += 1;
+        }
+        if let Some(v) = value.as_i128() {
+            self.int_sum += v;
+        }
+        match value.as_f64() {
+            Some(v) if v.is_finite() => self.float_sum += v,
+            Some(_) => self.non_finite += 1,
+            None => {}
+        }
+        while let Some(&last) = self.extremes.back() {
+            match value.partial_cmp(&self.history[last].1) {
+                Some(ord) if self.evicts(ord) => {
+                    self.extremes.pop_back();
+                }
+                _ => break,
+            }
+        }
+        self.extremes.push_back(self.end);
+        self.end += 1;
+    }
+
+    /// Remove the first value from the window
+    fn pop(&mut self) {
+        let value = &self.history[self.start].1;
+        self.count_type(value.dtype(), false);
+        if value.partial_cmp(value).is_none() {
+            self.incomparable -= 1;
+        }
+        if let Some(v) = value.as_i128() {
+            self.int_sum -= v;
+        }
+        match value.as_f64() {
+            Some(v) if v.is_finite() => self.float_sum -= v,
+            Some(_) => self.non_finite -= 1,
+            None => {}
+        }
+        if self.extremes.front() == Some(&self.start) {
+            self.extremes.pop_front();
+        }
+        self.start += 1;
+    }
+
+    fn values(&self) -> impl Iterator<Item = Prop> + 'a {
+        self.history[self.start..self.end]
+            .iter()
+            .map(|(_, value)| value.clone())
+    }
+
+    /// The aggregate of the window, the same as [Aggregation::apply] on its values
+    fn aggregate(&self) -> Option<Prop> {
+        let len = self.end - self.start;
+        if len == 0 || self.non_finite > 0 {
+            return self.aggregation.apply(self.values());
+        }
+        let single_type = match self.types.as_slice() {
+            [(dtype, _)] => Some(*dtype),
+            _ => None,
+        };
+        match self.aggregation {
+            Aggregation::Count => Some(Prop::U64(len as u64)),
+            Aggregation::First => Some(self.history[self.start].1.clone()),
+            Aggregation::Last => Some(self.history[self.end - 1].1.clone()),
+            Aggregation::Mean => self
+                .types
+                .iter()
+                .all(|(dtype, _)| is_numeric(*dtype))
+                .then(|| Prop::F64(self.float_sum / len as f64)),
+            Aggregation::Min | Aggregation::Max => {
+                // values of different types are not comparable
+                if single_type.is_none() || self.incomparable > 0 {
+                    return None;
+                }
+                let first = *self.extremes.front()?;
+                Some(self.history[first].1.clone())
+            }
+            Aggregation::Sum => match single_type? {
+                PropType::U8 => u8::try_from(self.int_sum).ok().map(Prop::U8),
+                PropType::U16 => u16::try_from(self.int_sum).ok().map(Prop::U16),
+                PropType::U32 => u32::try_from(self.int_sum).ok().map(Prop::U32),
+                PropType::U64 => u64::try_from(self.int_sum).ok().map(Prop::U64),
+                PropType::I32 => i32::try_from(self.int_sum).ok().map(Prop::I32),
+                PropType::I64 => i64::try_from(self.int_sum).ok().map(Prop::I64),
+                PropType::F32 => Some(Prop::F32(self.float_sum as f32)),
+                PropType::F64 => Some(Prop::F64(self.float_sum)),
+                // e.g., strings are concatenated, which can't be undone incrementally
+                _ => self.aggregation.apply(self.values()),
+            },
+        }
+    }
+}
+
+fn is_numeric(dtype: PropType) -> bool {
+    use PropType::*;
+    matches!(dtype, U8 | U16 | U32 | U64 | I32 | I64 | F32 | F64)
+}
+
+fn rolling(
+    history: &[(i64, Prop)],
+    window: Interval,
+    aggregation: Aggregation,
+) -> Result<Vec<(i64, Option<Prop>)>, ParseTimeError> {
+    let mut sliding = SlidingWindow::new(history, aggregation);
+    let mut rolled = Vec::with_capacity(history.len());
+    for (t, _) in history {
+        let window_start = window.sub_from(*t)?;
+        sliding.push();
+        // the window always contains the current value, even if it is empty
+        while sliding.start + 1 < sliding.end && history[sliding.start].0 <= window_start {
+            sliding.pop();
+        }
+        rolled.push((*t, sliding.aggregate()));
+    }
+    Ok(rolled)
+}
+
+fn numeric(history: &[(i64, Prop)]) -> impl Iterator<Item = (i64, f64)> + '_ {
+    history
+        .iter()
+        .filter_map(|(t, value)| Some((*t, value.as_f64()?)))
+}
+
+fn time_weighted_mean(history: &[(i64, Prop)], start: i64, end: i64) -> Option<f64> {
+    let values: Vec<_> = history
+        .iter()
+        .map(|(t, value)| Some((*t, value.as_f64()?)))
+        .collect::<Option<_>>()?;
+    let mut weighted_sum = 0.0;
+    let mut duration = 0;
+    for (i, (t, value)) in values.iter().enumerate() {
+        let from = (*t).max(start);
+        let to = values.get(i + 1).map_or(end, |(next, _)| (*next).min(end));
+        if to > from {
+            weighted_sum += value * (to - from) as f64;
+            duration += to - from;
+        }
+    }
+    (duration > 0).then(|| weighted_sum / duration as f64)
+}
+
+impl<P: PropertiesOps> TemporalPropertyView<P> {
+    fn collect_history(&self) -> Vec<(i64, Prop)> {
+        self.iter().collect()
+    }
+
+    /// Combine all values of the property
+    pub fn aggregate(&self, aggregation: Aggregation) -> Option<Prop> {
+        aggregation.apply(self.values())
+    }
+
+    /// Combine the values of the property per bucket of size `interval`, returns the start of
+    /// each bucket with updates and its aggregate
+    pub fn resample<I: TryInto<Interval, Error = ParseTimeError>>(
+        &self,
+        interval: I,
+        aggregation: Aggregation,
+    ) -> Result<Vec<(i64, Option<Prop>)>, ParseTimeError> {
+        resample(&self.collect_history(), interval.try_into()?, aggregation)
+    }
+
+    /// For every update, combine the values of the property in the `window` that ends with it,
+    /// i.e., the values updated in `(t - window, t]`
+    pub fn rolling<I: TryInto<Interval, Error = ParseTimeError>>(
+        &self,
+        window: I,
+        aggregation: Aggregation,
+    ) -> Result<Vec<(i64, Option<Prop>)>, ParseTimeError> {
+        rolling(&self.collect_history(), window.try_into()?, aggregation)
+    }
+
+    /// The change of a numeric property at every update after the first
+    pub fn deltas(&self) -> Vec<(i64, f64)> {
+        numeric(&self.collect_history())
+            .tuple_windows()
+            .map(|((_, previous), (t, value))| (t, value - previous))
+            .collect()
+    }
+
+    /// The change of a numeric property per unit of time between consecutive updates, updates at
+    /// the same time as the previous one are skipped
+    pub fn rates(&self) -> Vec<(i64, f64)> {
+        numeric(&self.collect_history())
+            .tuple_windows()
+            .filter(|((t0, _), (t1, _))| t1 > t0)
+            .map(|((t0, previous), (t1, value))| (t1, (value - previous) / (t1 - t0) as f64))
+            .collect()
+    }
+
+    /// The mean of a numeric property over `[start, end)` weighted by how long each value held.
+    ///
+    /// The last value updated before `start` holds from `start`, time before the first update
+    /// is not counted. Returns `None` if the property has no value in the interval or is not
+    /// numeric.
+    pub fn time_weighted_mean(&self, start: i64, end: i64) -> Option<f64> {
+        time_weighted_mean(&self.collect_history(), start, end)
+    }
+}
+
+impl<'graph, G: GraphViewOps<'graph>, GH: GraphViewOps<'graph>> Nodes<'graph, G, GH> {
+    fn map_temporal_property<O, F>(&self, name: &str, op: F) -> NodeState<'graph, O, G, GH>
+    where
+        O: Clone + Send + Sync + 'graph,
+        F: Fn(Option<TemporalPropertyView<NodeView<GH>>>) -> O + Send + Sync + Clone + 'graph,
+    {
+        let name = ArcStr::from(name);
+        self.map(move |_, g, v| {
+            let node = NodeView::new_internal(g.clone(), v);
+            op(node.properties().temporal().get(&name))
+        })
+        .compute()
+    }
+
+    /// [TemporalPropertyView::aggregate] of property `name` of every node
+    pub fn aggregate_property(
+        &self,
+        name: &str,
+        aggregation: Aggregation,
+    ) -> NodeState<'graph, Option<Prop>, G, GH> {
+        self.map_temporal_property(name, move |prop| prop?.aggregate(aggregation))
+    }
+
+    /// [TemporalPropertyView::resample] of property `name` of every node
+    pub fn resample_property<I: TryInto<Interval, Error = ParseTimeError>>(
+        &self,
+        name: &str,
+        interval: I,
+        aggregation: Aggregation,
+    ) -> Result<NodeState<'graph, Vec<(i64, Option<Prop>)>, G, GH>, ParseTimeError> {
+        let interval = interval.try_into()?;
+        self.map_temporal_property(name, move |prop| match prop {
+            Some(prop) => resample(&prop.collect_history(), interval, aggregation),
+            None => Ok(vec![]),
+        })
+        .transpose()
+    }
+
+    /// [TemporalPropertyView::rolling] of property `name` of every node
+    pub fn rolling_property<I: TryInto<Interval, Error = ParseTimeError>>(
+        &self,
+        name: &str,
+        window: I,
+        aggregation: Aggregation,
+    ) -> Result<NodeState<'graph, Vec<(i64, Option<Prop>)>, G, GH>, ParseTimeError> {
+        let window = window.try_into()?;
+        self.map_temporal_property(name, move |prop| match prop {
+            Some(prop) => rolling(&prop.collect_history(), window, aggregation),
+            None => Ok(vec![]),
+        })
+        .transpose()
+    }
+
+    /// [TemporalPropertyView::time_weighted_mean] of property `name` of every node
+    pub fn time_weighted_mean_property(
+        &self,
+        name: &str,
+        start: i64,
+        end: i64,
+    ) -> NodeState<'graph, Option<f64>, G, GH> {
+        self.map_temporal_property(name, move |prop| prop?.time_weighted_mean(start, end))
+    }
+}
+
+impl<'graph, G: GraphViewOps<'graph>, GH: GraphViewOps<'graph>> Edges<'graph, G, GH> {
+    fn map_temporal_property<O, F>(&self, name: &str, op: F) -> EdgeState<'graph, O, G, GH>
+    where
+        O: Send,
+        F: Fn(Option<TemporalPropertyView<EdgeView<G, GH>>>) -> O + Send + Sync,
+    {
+        let edges: Arc<[EdgeRef]> = (self.edges)().collect();
+        let values = edges
+            .par_iter()
+            .map(|e| {
+                let edge = EdgeView::new_filtered(self.base_graph.clone(), self.graph.clone(), *e);
+                op(edge.properties().temporal().get(name))
+            })
+            .collect();
+        EdgeState::new(self.base_graph.clone(), self.graph.clone(), edges, values)
+    }
+
+    /// [TemporalPropertyView::aggregate] of property `name` of every edge
+    pub fn aggregate_property(
+        &self,
+        name: &str,
+        aggregation: Aggregation,
+    ) -> EdgeState<'graph, Option<Prop>, G, GH> {
+        self.map_temporal_property(name, |prop| prop?.aggregate(aggregation))
+    }
+
+    /// [TemporalPropertyView::resample] of property `name` of every edge
+    pub fn resample_property<I: TryInto<Interval, Error = ParseTimeError>>(
+        &self,
+        name: &str,
+        interval: I,
+        aggregation: Aggregation,
+    ) -> Result<EdgeState<'graph, Vec<(i64, Option<Prop>)>, G, GH>, ParseTimeError> {
+        let interval = interval.try_into()?;
+        self.map_temporal_property(name, |prop| match prop {
+            Some(prop) => resample(&prop.collect_history(), interval, aggregation),
+            None => Ok(vec![]),
+        })
+        .transpose()
+    }
+
+    /// [TemporalPropertyView::rolling] of property `name` of every edge
+    pub fn rolling_property<I: TryInto<Interval, Error = ParseTimeError>>(
+        &self,
+        name: &str,
+        window: I,
+        aggregation: Aggregation,
+    ) -> Result<EdgeState<'graph, Vec<(i64, Option<Prop>)>, G, GH>, ParseTimeError> {
+        let window = window.try_into()?;
+        self.map_temporal_property(name, |prop| match prop {
+            Some(prop) => rolling(&prop.collect_history(), window, aggregation),
+            None => Ok(vec![]),
+        })
+        .transpose()
+    }
+
+    /// [TemporalPropertyView::time_weighted_mean] of property `name` of every edge
+    pub fn time_weighted_mean_property(
+        &self,
+        name: &str,
+        start: i64,
+        end: i64,
+    ) -> EdgeState<'graph, Option<f64>, G, GH> {
+        self.map_temporal_property(name, |prop| prop?.time_weighted_mean(start, end))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{db::api::state::NodeStateOps, test_utils::test_graph};
+
+    const DAY: i64 = 24 * 60 * 60 * 1000;
+
+    fn price_graph() -> Graph {
+        let g = Graph::new();
+        for (t, price) in [(0, 10.0), (DAY / 2, 20.0), (DAY, 30.0), (3 * DAY, 40.0)] {
+            g.add_node(t, "a", [("price", price)], None).unwrap();
+        }
+        g.add_node(0, "b", [("price", 1.0)], None).unwrap();
+        g.add_node(0, "c", [("name", "c")], None).unwrap();
+        g
+    }
+
+    #[test]
+    fn test_aggregation() {
+        let values = || [Prop::I64(3), Prop::I64(1), Prop::I64(2)];
+        assert_eq!(Aggregation::Sum.apply(values()), Some(Prop::I64(6)));
+        assert_eq!(Aggregation::Mean.apply(values()), Some(Prop::F64(2.0)));
+        assert_eq!(Aggregation::Min.apply(values()), Some(Prop::I64(1)));
+        assert_eq!(Aggregation::Max.apply(values()), Some(Prop::I64(3)));
+        assert_eq!(Aggregation::Count.apply(values()), Some(Prop::U64(3)));
+        assert_eq!(Aggregation::First.apply(values()), Some(Prop::I64(3)));
+        assert_eq!(Aggregation::Last.apply(values()), Some(Prop::I64(2)));
+        assert_eq!(Aggregation::Sum.apply([]), None);
+        assert_eq!(Aggregation::Mean.apply([Prop::str("a")]), None);
+    }
+
+    #[test]
+    fn test_resample_and_rolling() {
+        let graph = price_graph();
+        test_graph(&graph, |graph| {
+            let price = graph
+                .node("a")
+                .unwrap()
+                .properties()
+                .temporal()
+                .get("price")
+                .unwrap();
+            assert_eq!(
+                price.resample("1 day", Aggregation::Mean).unwrap(),
+                [
+                    (0, Some(Prop::F64(15.0))),
+                    (DAY, Some(Prop::F64(30.0))),
+                    (3 * DAY, Some(Prop::F64(40.0)))
+                ]
+            );
+            assert_eq!(
+                price.resample("1 month", Aggregation::Count).unwrap(),
+                [(0, Some(Prop::U64(4)))]
+            );
+            assert_eq!(
+                price.rolling(DAY, Aggregation::Sum).unwrap(),
+                [
+                    (0, Some(Prop::F64(10.0))),
+                    (DAY / 2, Some(Prop::F64(30.0))),
+                    (DAY, Some(Prop::F64(50.0))),
+                    (3 * DAY, Some(Prop::F64(40.0)))
+                ]
+            );
+            assert_eq!(
+                price.deltas(),
+                [(DAY / 2, 10.0), (DAY, 10.0), (3 * DAY, 10.0)]
+            );
+            assert_eq!(price.rates()[2], (3 * DAY, 10.0 / (2 * DAY) as f64));
+        });
+    }
+
+    #[test]
+    fn test_time_weighted_mean() {
+        let graph = price_graph();
+        test_graph(&graph, |graph| {
+            let price = graph
+                .node("a")
+                .unwrap()
+                .properties()
+                .temporal()
+                .get("price")
+                .unwrap();
+            // 10 for half a day, 20 for half a day, 30 for two days
+            assert_eq!(price.time_weighted_mean(0, 3 * DAY), Some(25.0));
+            // only 20 holds in the interval although it was updated before it
+            assert_eq!(price.time_weighted_mean(DAY / 2 + 1, DAY), Some(20.0));
+            assert_eq!(price.time_weighted_mean(-DAY, 0), None);
+        });
+    }
+
+    #[test]
+    fn test_properties_of_all_nodes() {
+        let graph = price_graph();
+        test_graph(&graph, |graph| {
+            let nodes = graph.nodes();
+            let max = nodes.aggregate_property("price", Aggregation::Max);
+            assert_eq!(max.get_by_node("a"), Some(&Some(Prop::F64(40.0))));
+            assert_eq!(max.get_by_node("b"), Some(&Some(Prop::F64(1.0))));
+            assert_eq!(max.get_by_node("c"), Some(&None));
+
+            let daily = nodes
+                .resample_property("price", "1 day", Aggregation::Last)
+                .unwrap();
+            assert_eq!(daily.get_by_node("a").unwrap().len(), 3);
+            assert_eq!(daily.get_by_node("c"), Some(&vec![]));
+
+            let means = nodes.time_weighted_mean_property("price", 0, DAY);
+            assert_eq!(means.get_by_node("a"), Some(&Some(15.0)));
+            assert_eq!(means.get_by_node("b"), Some(&Some(1.0)));
+        });
+    }
+
+    #[test]
+    fn test_properties_of_all_edges() {
+        let graph = Graph::new();
+        graph.add_edge(0, 1, 2, [("weight", 1i64)], None).unwrap();
+        graph.add_edge(1, 1, 2, [("weight", 3i64)], None).unwrap();
+        graph.add_edge(0, 2, 3, [("weight", 5i64)], None).unwrap();
+        test_graph(&graph, |graph| {
+            let edges = graph.edges();
+            let sums = edges.aggregate_property("weight", Aggregation::Sum);
+            assert_eq!(sums.get_by_edge(1, 2), Some(&Some(Prop::I64(4))));
+            assert_eq!(sums.get_by_edge(2, 3), Some(&Some(Prop::I64(5))));
+            assert_eq!(sums.get_by_edge(1, 3), None);
+            assert_eq!(
+                edges
+                    .time_weighted_mean_property("weight", 0, 2)
+                    .into_iter()
+                    .collect::<Vec<_>>(),
+                [Some(2.0), Some(5.0)]
+            );
+            let rolled = edges
+                .rolling_property("weight", 1, Aggregation::Max)
+                .unwrap();
+            assert_eq!(
+                rolled.get_by_edge(1, 2),
+                Some(&vec![(0, Some(Prop::I64(1))), (1, Some(Prop::I64(3)))])
+            );
+        });
+    }
+
+    #[test]
+    fn test_rolling_matches_recomputing_every_window() {
+        let history: Vec<_> = [
+            (0, Prop::I64(5)),
+            (1, Prop::I64(3)),
+            (1, Prop::I64(8)),
+            (2, Prop::I64(3)),
+            (4, Prop::I64(1)),
+            (5, Prop::F64(2.0)),
+            (7, Prop::F64(f64::NAN)),
+            (9, Prop::F64(4.0)),
+            (12, Prop::str("a")),
+            (12, Prop::str("b")),
+        ]
+        .into_iter()
+        .collect();
+        let aggregations = [
+            Aggregation::Sum,
+            Aggregation::Mean,
+            Aggregation::Min,
+            Aggregation::Max,
+            Aggregation::Count,
+            Aggregation::First,
+            Aggregation::Last,
+        ];
+        for window in [0i64, 1, 2, 3, 5] {
+            for aggregation in aggregations {
+                let expected: Vec<_> = history
+                    .iter()
+                    .enumerate()
+                    .map(|(end, (t, _))| {
+                        let start = history[..end]
+                            .iter()
+                            .position(|(s, _)| *s > *t - window)
+                            .unwrap_or(end);
+                        let values = history[start..=end].iter().map(|(_, v)| v.clone());
+                        (*t, aggregation.apply(values))
+                    })
+                    .collect();
+                let rolled = rolling(&history, window.try_into().unwrap(), aggregation).unwrap();
+                // NaN is not equal to itself, compare the debug output instead
+                assert_eq!(
+                    format!("{rolled:?}"),
+                    format!("{expected:?}"),
+                    "{aggregation:?} over {window}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_out_of_range_times_are_errors() {
+        let graph = Graph::new();
+        graph
+            .add_node(i64::MAX, 1, [("value", 1i64)], None)
+            .unwrap();
+        let value = graph
+            .node(1)
+            .unwrap()
+            .properties()
+            .temporal()
+            .get("value")
+            .unwrap();
+        assert!(value.resample("1 month", Aggregation::Sum).is_err());
+        assert!(graph
+            .nodes()
+            .rolling_property("value", "1 day", Aggregation::Sum)
+            .is_err());
+    }
+}
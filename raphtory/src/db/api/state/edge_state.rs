@@ -0,0 +1,107 @@
+use crate::{
+    core::entities::{edges::edge_ref::EdgeRef, nodes::node_ref::AsNodeRef, VID},
+    db::graph::edge::EdgeView,
+    prelude::GraphViewOps,
+};
+use once_cell::sync::OnceCell;
+use rayon::prelude::*;
+use std::{collections::HashMap, marker::PhantomData, sync::Arc};
+
+/// The values of an operation on every edge of an [Edges](crate::db::graph::edges::Edges) view,
+/// in the order of its edges
+pub struct EdgeState<'graph, V, G, GH = G> {
+    base_graph: G,
+    graph: GH,
+    edges: Arc<[EdgeRef]>,
+    values: Vec<V>,
+    /// The position of the first edge between two nodes, built on first lookup
+    index: OnceCell<HashMap<(VID, VID), usize>>,
+    _marker: PhantomData<&'graph ()>,
+}
+
+impl<'graph, V, G: GraphViewOps<'graph>, GH: GraphViewOps<'graph>> EdgeState<'graph, V, G, GH> {
+    pub(crate) fn new(base_graph: G, graph: GH, edges: Arc<[EdgeRef]>, values: Vec<V>) -> Self {
+        Self {
+            base_graph,
+            graph,
+            edges,
+            values,
+            index: OnceCell::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    pub fn values(&self) -> impl Iterator<Item = &V> + '_ {
+        self.values.iter()
+    }
+
+    pub fn par_values(&self) -> impl IndexedParallelIterator<Item = &V> + '_
+    where
+        V: Sync,
+    {
+        self.values.par_iter()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (EdgeView<&G, &GH>, &V)> + '_ {
+        self.edges
+            .iter()
+            .zip(self.values.iter())
+            .map(|(e, v)| (EdgeView::new_filtered(&self.base_graph, &self.graph, *e), v))
+    }
+
+    pub fn get_by_index(&self, index: usize) -> Option<(EdgeView<&G, &GH>, &V)> {
+        let edge = *self.edges.get(index)?;
+        Some((
+            EdgeView::new_filtered(&self.base_graph, &self.graph, edge),
+            &self.values[index],
+        ))
+    }
+
+    /// The value of the (first) edge from `src` to `dst`
+    pub fn get_by_edge<N: AsNodeRef>(&self, src: N, dst: N) -> Option<&V> {
+        let src = self.graph.internalise_node(src.as_node_ref())?;
+        let dst = self.graph.internalise_node(dst.as_node_ref())?;
+        let index = self.index.get_or_init(|| {
+            let mut index = HashMap::with_capacity(self.edges.len());
+            for (i, e) in self.edges.iter().enumerate() {
+                index.entry((e.src(), e.dst())).or_insert(i);
+            }
+            index
+        });
+        index.get(&(src, dst)).map(|i| &self.values[*i])
+    }
+}
+
+impl<'graph, V, E, G: GraphViewOps<'graph>, GH: GraphViewOps<'graph>>
+    EdgeState<'graph, Result<V, E>, G, GH>
+{
+    /// The state with the values unwrapped, or the first error
+    pub(crate) fn transpose(self) -> Result<EdgeState<'graph, V, G, GH>, E> {
+        let values = self.values.into_iter().collect::<Result<_, E>>()?;
+        Ok(EdgeState::new(
+            self.base_graph,
+            self.graph,
+            self.edges,
+            values,
+        ))
+    }
+}
+
+impl<'graph, V, G: GraphViewOps<'graph>, GH: GraphViewOps<'graph>> IntoIterator
+    for EdgeState<'graph, V, G, GH>
+{
+    type Item = V;
+    type IntoIter = std::vec::IntoIter<V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.values.into_iter()
+    }
+}
@@ -1,9 +1,11 @@
+mod edge_state;
 mod group_by;
 mod lazy_node_state;
 mod node_state;
 mod ops;
 mod ord_ops;
 
+pub use edge_state::EdgeState;
 pub use lazy_node_state::LazyNodeState;
 pub(crate) use node_state::Index;
 pub use node_state::NodeState;
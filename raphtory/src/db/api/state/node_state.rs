@@ -98,6 +98,21 @@ impl<'graph, V, G: GraphViewOps<'graph>, GH: GraphViewOps<'graph>> NodeState<'gr
     }
 }
 
+impl<'graph, V, E, G: GraphViewOps<'graph>, GH: GraphViewOps<'graph>>
+    NodeState<'graph, Result<V, E>, G, GH>
+{
+    /// The state with the values unwrapped, or the first error
+    pub(crate) fn transpose(self) -> Result<NodeState<'graph, V, G, GH>, E> {
+        let values = self.values.into_iter().collect::<Result<_, E>>()?;
+        Ok(NodeState::new(
+            self.base_graph,
+            self.graph,
+            values,
+            self.keys,
+        ))
+    }
+}
+
 impl<'graph, V: Send + Sync + 'graph, G: GraphViewOps<'graph>, GH: GraphViewOps<'graph>>
     IntoIterator for NodeState<'graph, V, G, GH>
 {
@@ -145,11 +145,9 @@ impl<'a> EdgesStorageRef<'a> {
             }
             #[cfg(feature = "storage")]
             EdgesStorageRef::Disk(storage) => EdgeStorageEntry::Disk(
-                storage.edge(
-                    edge.pid(),
-                    edge.layer()
-                        .expect("DiskGraph edges should always have layer set currently"),
-                ),
+                storage
+                    .edge(edge)
+                    .unwrap_or_else(|| panic!("edge {edge:?} not found")),
             ),
         }
     }
@@ -163,4 +161,16 @@ impl<'a> EdgesStorageRef<'a> {
             EdgesStorageRef::Disk(storage) => storage.len(),
         }
     }
+
+    /// The number of edge ids, disk graphs have an id for the copy of an edge in each of its
+    /// layers
+    #[inline]
+    pub fn num_ids(&self) -> usize {
+        match self {
+            EdgesStorageRef::Mem(storage) => storage.len(),
+            EdgesStorageRef::Unlocked(storage) => storage.len(),
+            #[cfg(feature = "storage")]
+            EdgesStorageRef::Disk(storage) => storage.num_ids(),
+        }
+    }
 }
@@ -0,0 +1,36 @@
+use super::GraphStorage;
+use crate::{
+    core::{
+        entities::{edges::edge_ref::EdgeRef, VID},
+        Prop,
+    },
+    db::api::{
+        properties::computed::ComputedProperties, view::internal::InternalComputedPropertiesOps,
+    },
+};
+
+impl InternalComputedPropertiesOps for GraphStorage {
+    fn computed_properties(&self) -> Option<&ComputedProperties> {
+        None
+    }
+
+    fn computed_node_prop(
+        &self,
+        _v: VID,
+        _index: usize,
+        _start: Option<i64>,
+        _end: Option<i64>,
+    ) -> Option<Prop> {
+        None
+    }
+
+    fn computed_edge_prop(
+        &self,
+        _e: EdgeRef,
+        _index: usize,
+        _start: Option<i64>,
+        _end: Option<i64>,
+    ) -> Option<Prop> {
+        None
+    }
+}
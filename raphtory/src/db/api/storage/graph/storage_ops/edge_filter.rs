@@ -6,18 +6,28 @@ use crate::{
 
 impl EdgeFilterOps for GraphStorage {
     fn edges_filtered(&self) -> bool {
-        false
+        match self {
+            GraphStorage::Mem(storage) => storage.graph.has_removed_edges(),
+            GraphStorage::Unlocked(storage) => storage.has_removed_edges(),
+            #[cfg(feature = "storage")]
+            GraphStorage::Disk(_) => false,
+        }
     }
 
     fn edge_list_trusted(&self) -> bool {
-        true
+        !self.edges_filtered()
     }
 
     fn edge_filter_includes_node_filter(&self) -> bool {
+        // removing a node also removes all its edges
         true
     }
 
-    fn filter_edge(&self, _edge: EdgeStorageRef, _layer_ids: &LayerIds) -> bool {
-        true
+    fn filter_edge(&self, edge: EdgeStorageRef, _layer_ids: &LayerIds) -> bool {
+        match edge {
+            EdgeStorageRef::Mem(edge) => !edge.edge_store().is_removed(),
+            #[cfg(feature = "storage")]
+            EdgeStorageRef::Disk(_) => true,
+        }
     }
 }
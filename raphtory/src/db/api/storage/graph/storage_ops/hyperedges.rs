@@ -0,0 +1,70 @@
+use super::GraphStorage;
+use crate::{
+    core::{
+        entities::{graph::tgraph::TemporalGraph, VID},
+        storage::timeindex::TimeIndexEntry,
+        utils::errors::GraphError,
+        Prop, PropType,
+    },
+    db::api::mutation::internal::InternalHyperedgeOps,
+};
+use raphtory_api::core::{entities::GidRef, storage::dict_mapper::MaybeNew};
+
+impl InternalHyperedgeOps for TemporalGraph {
+    fn resolve_hyperedge_role(&self, role: &str) -> Result<MaybeNew<usize>, GraphError> {
+        Ok(self.hyperedges.resolve_role(role))
+    }
+
+    fn resolve_hyperedge_property(
+        &self,
+        prop: &str,
+        dtype: PropType,
+    ) -> Result<MaybeNew<usize>, GraphError> {
+        self.hyperedges.resolve_property(prop, dtype)
+    }
+
+    fn internal_add_hyperedge(
+        &self,
+        t: TimeIndexEntry,
+        id: GidRef,
+        participants: &[(VID, Option<usize>)],
+        props: &[(usize, Prop)],
+    ) -> Result<MaybeNew<usize>, GraphError> {
+        self.add_hyperedge(t, id.to_owned(), participants, props)
+    }
+}
+
+impl InternalHyperedgeOps for GraphStorage {
+    fn resolve_hyperedge_role(&self, role: &str) -> Result<MaybeNew<usize>, GraphError> {
+        match self {
+            GraphStorage::Unlocked(storage) => storage.resolve_hyperedge_role(role),
+            _ => Err(GraphError::AttemptToMutateImmutableGraph),
+        }
+    }
+
+    fn resolve_hyperedge_property(
+        &self,
+        prop: &str,
+        dtype: PropType,
+    ) -> Result<MaybeNew<usize>, GraphError> {
+        match self {
+            GraphStorage::Unlocked(storage) => storage.resolve_hyperedge_property(prop, dtype),
+            _ => Err(GraphError::AttemptToMutateImmutableGraph),
+        }
+    }
+
+    fn internal_add_hyperedge(
+        &self,
+        t: TimeIndexEntry,
+        id: GidRef,
+        participants: &[(VID, Option<usize>)],
+        props: &[(usize, Prop)],
+    ) -> Result<MaybeNew<usize>, GraphError> {
+        match self {
+            GraphStorage::Unlocked(storage) => {
+                storage.internal_add_hyperedge(t, id, participants, props)
+            }
+            _ => Err(GraphError::AttemptToMutateImmutableGraph),
+        }
+    }
+}
@@ -8,7 +8,8 @@ use crate::{
     core::{
         entities::{
             edges::edge_ref::EdgeRef,
-            graph::tgraph::TemporalGraph,
+            graph::{node_merge::NodeMerges, tgraph::TemporalGraph},
+            hyperedges::Hyperedges,
             nodes::node_ref::NodeRef,
             properties::{graph_meta::GraphMeta, props::Meta},
             LayerIds, EID, VID,
@@ -39,7 +40,10 @@ use itertools::Itertools;
 use raphtory_api::core::entities::ELID;
 use rayon::prelude::*;
 use serde::{Deserialize, Serialize};
-use std::{iter, sync::Arc};
+use std::{
+    iter,
+    sync::{atomic::Ordering, Arc},
+};
 
 #[cfg(feature = "storage")]
 use crate::{
@@ -58,14 +62,18 @@ use crate::{
 };
 
 pub mod additions;
+pub mod computed_props;
 pub mod const_props;
 pub mod deletions;
 pub mod edge_filter;
+pub mod hyperedges;
 pub mod layer_ops;
 pub mod list_ops;
 pub mod materialize;
 pub mod node_filter;
+pub mod node_merges;
 pub mod prop_add;
+pub mod removals;
 pub mod time_props;
 pub mod time_semantics;
 
@@ -235,7 +243,7 @@ impl GraphStorage {
                 EdgesStorageRef::Unlocked(UnlockedEdges(&storage.storage))
             }
             #[cfg(feature = "storage")]
-            GraphStorage::Disk(storage) => EdgesStorageRef::Disk(DiskEdgesRef::new(&storage.inner)),
+            GraphStorage::Disk(storage) => EdgesStorageRef::Disk(DiskEdgesRef::new(storage)),
         }
     }
 
@@ -247,7 +255,7 @@ impl GraphStorage {
                 GraphStorage::Mem(LockedGraph::new(storage.clone())).owned_edges()
             }
             #[cfg(feature = "storage")]
-            GraphStorage::Disk(storage) => EdgesStorage::Disk(DiskEdges::new(&storage.inner)),
+            GraphStorage::Disk(storage) => EdgesStorage::Disk(DiskEdges::new(storage)),
         }
     }
 
@@ -260,10 +268,9 @@ impl GraphStorage {
             }
             #[cfg(feature = "storage")]
             GraphStorage::Disk(storage) => {
-                let layer = eid
-                    .layer()
-                    .expect("disk_graph EdgeRefs should always have layer set");
-                EdgeStorageEntry::Disk(storage.inner.layers()[layer].edge(eid.pid()))
+                let edge = DiskEdgesRef::new(storage).edge(eid);
+                // like the in-memory storage, the id needs to refer to an edge of the graph
+                EdgeStorageEntry::Disk(edge.unwrap_or_else(|| panic!("edge {eid:?} not found")))
             }
         }
     }
@@ -278,12 +285,10 @@ impl GraphStorage {
                 EdgeOwnedEntry::Mem(storage.storage.get_edge_arc(eid.pid()))
             }
             #[cfg(feature = "storage")]
-            GraphStorage::Disk(storage) => {
-                let _ = eid
-                    .layer()
-                    .expect("disk_graph EdgeRefs should always have layer set");
-                EdgeOwnedEntry::Disk(DiskOwnedEdge::new(&storage.inner, eid))
-            }
+            GraphStorage::Disk(storage) => EdgeOwnedEntry::Disk(
+                DiskOwnedEdge::new(storage, eid)
+                    .unwrap_or_else(|| panic!("edge {eid:?} not found")),
+            ),
         }
     }
 
@@ -753,4 +758,54 @@ impl GraphStorage {
             GraphStorage::Disk(storage) => storage.graph_meta(),
         }
     }
+
+    /// The hyperedges of the graph, `None` for disk graphs which do not support them
+    pub fn hyperedges(&self) -> Option<&Hyperedges> {
+        match self {
+            GraphStorage::Mem(storage) => Some(&storage.graph.hyperedges),
+            GraphStorage::Unlocked(storage) => Some(&storage.hyperedges),
+            #[cfg(feature = "storage")]
+            GraphStorage::Disk(_) => None,
+        }
+    }
+
+    /// Check if both refer to the same underlying graph
+    pub fn ptr_eq(&self, other: &GraphStorage) -> bool {
+        std::ptr::eq(self.graph_ptr(), other.graph_ptr())
+    }
+
+    fn graph_ptr(&self) -> *const () {
+        match self {
+            GraphStorage::Mem(storage) => Arc::as_ptr(&storage.graph) as *const (),
+            GraphStorage::Unlocked(storage) => Arc::as_ptr(storage) as *const (),
+            #[cfg(feature = "storage")]
+            GraphStorage::Disk(storage) => Arc::as_ptr(storage) as *const (),
+        }
+    }
+
+    /// A counter that changes whenever the graph is mutated, disk graphs are immutable
+    pub fn version(&self) -> usize {
+        match self {
+            GraphStorage::Mem(storage) => storage.graph.version.load(Ordering::Relaxed),
+            GraphStorage::Unlocked(storage) => storage.version.load(Ordering::Relaxed),
+            #[cfg(feature = "storage")]
+            GraphStorage::Disk(_) => 0,
+        }
+    }
+
+    pub(crate) fn bump_version(&self) {
+        if let GraphStorage::Unlocked(storage) = self {
+            storage.version.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    /// The records of merged nodes, `None` for disk graphs which do not support merging nodes
+    pub fn node_merges(&self) -> Option<&NodeMerges> {
+        match self {
+            GraphStorage::Mem(storage) => Some(&storage.graph.node_merges),
+            GraphStorage::Unlocked(storage) => Some(&storage.node_merges),
+            #[cfg(feature = "storage")]
+            GraphStorage::Disk(_) => None,
+        }
+    }
 }
@@ -7,15 +7,24 @@ use crate::{
 impl NodeFilterOps for GraphStorage {
     #[inline]
     fn node_list_trusted(&self) -> bool {
-        true
+        !self.nodes_filtered()
     }
     #[inline]
     fn nodes_filtered(&self) -> bool {
-        false
+        match self {
+            GraphStorage::Mem(storage) => storage.graph.has_removed_nodes(),
+            GraphStorage::Unlocked(storage) => storage.has_removed_nodes(),
+            #[cfg(feature = "storage")]
+            GraphStorage::Disk(_) => false,
+        }
     }
 
     #[inline]
-    fn filter_node(&self, _node: NodeStorageRef, _layer_ids: &LayerIds) -> bool {
-        true
+    fn filter_node(&self, node: NodeStorageRef, _layer_ids: &LayerIds) -> bool {
+        match node {
+            NodeStorageRef::Mem(node) => !node.is_removed(),
+            #[cfg(feature = "storage")]
+            NodeStorageRef::Disk(_) => true,
+        }
     }
 }
@@ -0,0 +1,37 @@
+use super::GraphStorage;
+use crate::{
+    core::{
+        entities::{
+            graph::{node_merge::NodeMerge, tgraph::TemporalGraph},
+            VID,
+        },
+        utils::errors::GraphError,
+    },
+    db::api::mutation::internal::InternalNodeMergeOps,
+};
+
+impl InternalNodeMergeOps for TemporalGraph {
+    fn internal_merge_nodes(&self, keep: VID, merged: VID) -> Result<NodeMerge, GraphError> {
+        self.merge_nodes(keep, merged)
+    }
+
+    fn internal_unmerge_node(&self, merged: VID) -> Result<(), GraphError> {
+        self.unmerge_node(merged)
+    }
+}
+
+impl InternalNodeMergeOps for GraphStorage {
+    fn internal_merge_nodes(&self, keep: VID, merged: VID) -> Result<NodeMerge, GraphError> {
+        match self {
+            GraphStorage::Unlocked(storage) => storage.internal_merge_nodes(keep, merged),
+            _ => Err(GraphError::AttemptToMutateImmutableGraph),
+        }
+    }
+
+    fn internal_unmerge_node(&self, merged: VID) -> Result<(), GraphError> {
+        match self {
+            GraphStorage::Unlocked(storage) => storage.internal_unmerge_node(merged),
+            _ => Err(GraphError::AttemptToMutateImmutableGraph),
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use super::GraphStorage;
+use crate::{
+    core::{
+        entities::{graph::tgraph::TemporalGraph, LayerIds},
+        utils::errors::GraphError,
+    },
+    db::api::mutation::internal::InternalRemovalOps,
+};
+use raphtory_api::core::entities::{EID, VID};
+use std::ops::Range;
+
+impl InternalRemovalOps for TemporalGraph {
+    fn internal_remove_node(&self, v: VID) -> Result<(), GraphError> {
+        self.remove_node(v);
+        self.recompute_time_bounds();
+        Ok(())
+    }
+
+    fn internal_remove_edge(&self, eid: EID, layer_ids: &LayerIds) -> Result<(), GraphError> {
+        self.remove_edge(eid, layer_ids);
+        self.recompute_time_bounds();
+        Ok(())
+    }
+
+    fn internal_remove_node_property_updates(
+        &self,
+        v: VID,
+        w: Range<i64>,
+        prop_id: Option<usize>,
+    ) -> Result<(), GraphError> {
+        self.remove_node_property_updates(v, w, prop_id)
+    }
+
+    fn internal_remove_edge_property_updates(
+        &self,
+        eid: EID,
+        layer_ids: &LayerIds,
+        w: Range<i64>,
+        prop_id: Option<usize>,
+    ) -> Result<(), GraphError> {
+        self.remove_edge_property_updates(eid, layer_ids, w, prop_id)
+    }
+
+    fn internal_remove_graph_property_updates(
+        &self,
+        w: Range<i64>,
+        prop_id: Option<usize>,
+    ) -> Result<(), GraphError> {
+        self.remove_graph_property_updates(w, prop_id)
+    }
+
+    fn internal_truncate_before(&self, t: i64, keep_alive: bool) -> Result<(), GraphError> {
+        self.truncate_before(t, keep_alive)
+    }
+}
+
+impl InternalRemovalOps for GraphStorage {
+    fn internal_remove_node(&self, v: VID) -> Result<(), GraphError> {
+        match self {
+            GraphStorage::Unlocked(storage) => storage.internal_remove_node(v),
+            _ => Err(GraphError::AttemptToMutateImmutableGraph),
+        }
+    }
+
+    fn internal_remove_edge(&self, eid: EID, layer_ids: &LayerIds) -> Result<(), GraphError> {
+        match self {
+            GraphStorage::Unlocked(storage) => storage.internal_remove_edge(eid, layer_ids),
+            _ => Err(GraphError::AttemptToMutateImmutableGraph),
+        }
+    }
+
+    fn internal_remove_node_property_updates(
+        &self,
+        v: VID,
+        w: Range<i64>,
+        prop_id: Option<usize>,
+    ) -> Result<(), GraphError> {
+        match self {
+            GraphStorage::Unlocked(storage) => {
+                storage.internal_remove_node_property_updates(v, w, prop_id)
+            }
+            _ => Err(GraphError::AttemptToMutateImmutableGraph),
+        }
+    }
+
+    fn internal_remove_edge_property_updates(
+        &self,
+        eid: EID,
+        layer_ids: &LayerIds,
+        w: Range<i64>,
+        prop_id: Option<usize>,
+    ) -> Result<(), GraphError> {
+        match self {
+            GraphStorage::Unlocked(storage) => {
+                storage.internal_remove_edge_property_updates(eid, layer_ids, w, prop_id)
+            }
+            _ => Err(GraphError::AttemptToMutateImmutableGraph),
+        }
+    }
+
+    fn internal_remove_graph_property_updates(
+        &self,
+        w: Range<i64>,
+        prop_id: Option<usize>,
+    ) -> Result<(), GraphError> {
+        match self {
+            GraphStorage::Unlocked(storage) => {
+                storage.internal_remove_graph_property_updates(w, prop_id)
+            }
+            _ => Err(GraphError::AttemptToMutateImmutableGraph),
+        }
+    }
+
+    fn internal_truncate_before(&self, t: i64, keep_alive: bool) -> Result<(), GraphError> {
+        match self {
+            GraphStorage::Unlocked(storage) => storage.internal_truncate_before(t, keep_alive),
+            _ => Err(GraphError::AttemptToMutateImmutableGraph),
+        }
+    }
+}
@@ -1,33 +1,44 @@
 use crate::{
     core::{
         entities::{
-            graph::tgraph::TemporalGraph,
+            graph::{node_merge::NodeMerge, tgraph::TemporalGraph},
             nodes::node_ref::{AsNodeRef, NodeRef},
+            properties::props::Meta,
+            LayerIds,
         },
         utils::errors::GraphError,
         Prop, PropType,
     },
     db::api::{
-        mutation::internal::{
-            InternalAdditionOps, InternalDeletionOps, InternalPropertyAdditionOps,
+        mutation::{
+            internal::{
+                InternalAdditionOps, InternalDeletionOps, InternalHyperedgeOps,
+                InternalNodeMergeOps, InternalPropertyAdditionOps, InternalRemovalOps,
+            },
+            schema::{can_migrate, migrate_value, GraphSchema, Migration, PropertyOwner},
         },
         storage::graph::{nodes::node_storage_ops::NodeStorageOps, storage_ops::GraphStorage},
         view::{Base, InheritViewOps},
     },
 };
 use once_cell::sync::OnceCell;
+use parking_lot::{Mutex, RwLock};
 use raphtory_api::core::{
-    entities::{EID, VID},
-    storage::{dict_mapper::MaybeNew, timeindex::TimeIndexEntry},
+    entities::{GidRef, EID, VID},
+    storage::{arc_str::ArcStr, dict_mapper::MaybeNew, timeindex::TimeIndexEntry},
 };
 use serde::{Deserialize, Serialize};
 use std::{
+    collections::BTreeSet,
     fmt::{Display, Formatter},
+    ops::Range,
     sync::Arc,
 };
 
 #[cfg(feature = "proto")]
 use crate::serialise::incremental::GraphWriter;
+#[cfg(feature = "proto")]
+use parking_lot::RwLockReadGuard;
 
 #[derive(Debug, Default, Serialize, Deserialize)]
 pub struct Storage {
@@ -35,6 +46,7 @@ pub struct Storage {
     #[cfg(feature = "proto")]
     #[serde(skip)]
     pub(crate) cache: OnceCell<GraphWriter>,
+    schema: RwLock<Option<Arc<GraphSchema>>>,
 }
 
 impl Display for Storage {
@@ -58,6 +70,7 @@ impl Storage {
             graph: GraphStorage::Unlocked(Arc::new(TemporalGraph::new(num_locks))),
             #[cfg(feature = "proto")]
             cache: OnceCell::new(),
+            schema: RwLock::new(None),
         }
     }
 
@@ -66,9 +79,17 @@ impl Storage {
             graph,
             #[cfg(feature = "proto")]
             cache: OnceCell::new(),
+            schema: RwLock::new(None),
         }
     }
 
+    /// Keep checkpoints from encoding the graph between applying an update and logging it
+    #[cfg(feature = "proto")]
+    #[inline]
+    fn updating(&self) -> Option<RwLockReadGuard<()>> {
+        self.cache.get().map(|cache| cache.updating())
+    }
+
     #[cfg(feature = "proto")]
     #[inline]
     fn if_cache(&self, map_fn: impl FnOnce(&GraphWriter)) {
@@ -76,7 +97,162 @@ impl Storage {
             map_fn(cache)
         }
     }
+
+    #[inline]
+    fn check_schema(
+        &self,
+        check: impl FnOnce(&GraphSchema) -> Result<(), GraphError>,
+    ) -> Result<(), GraphError> {
+        match self.schema.read().as_deref() {
+            Some(schema) => check(schema),
+            None => Ok(()),
+        }
+    }
+
+    fn node_type(&self, v: VID) -> Option<ArcStr> {
+        let type_id = self.graph.node_entry(v).node_type_id();
+        self.graph.node_meta().get_node_type_name_by_id(type_id)
+    }
+
+    fn check_node_constant_update(
+        &self,
+        v: VID,
+        props: &[(usize, Prop)],
+    ) -> Result<(), GraphError> {
+        self.check_schema(|schema| {
+            let props = named_props(self.graph.node_meta(), props, true);
+            schema.check_node_update(self.node_type(v).as_deref(), props, true)
+        })
+    }
+
+    fn check_edge_update(
+        &self,
+        layer: usize,
+        props: &[(usize, Prop)],
+        is_static: bool,
+    ) -> Result<(), GraphError> {
+        self.check_schema(|schema| {
+            let layer = self.graph.edge_meta().get_layer_name_by_id(layer);
+            let props = named_props(self.graph.edge_meta(), props, is_static);
+            schema.check_edge_update(&layer, props, is_static)
+        })
+    }
+
+    pub(crate) fn schema(&self) -> Option<Arc<GraphSchema>> {
+        self.schema.read().clone()
+    }
+
+    /// The JSON encoded schema, [Storage::set_schema] only accepts schemas that can be encoded
+    #[cfg(feature = "proto")]
+    pub(crate) fn schema_json(&self) -> Option<String> {
+        self.schema
+            .read()
+            .as_ref()
+            .and_then(|schema| schema.to_json().ok())
+    }
+
+    pub(crate) fn set_schema(&self, schema: Option<GraphSchema>) -> Result<(), GraphError> {
+        #[cfg(feature = "proto")]
+        let _updating = self.updating();
+
+        if let Some(schema) = &schema {
+            schema.check_meta(self.graph.node_meta(), self.graph.edge_meta())?;
+        }
+        let json = schema.as_ref().map(GraphSchema::to_json).transpose()?;
+        *self.schema.write() = schema.map(Arc::new);
+
+        #[cfg(feature = "proto")]
+        self.if_cache(|cache| cache.set_schema(json));
+        #[cfg(not(feature = "proto"))]
+        let _ = json;
+
+        Ok(())
+    }
+
+    pub(crate) fn migrate_property(
+        &self,
+        owner: PropertyOwner,
+        name: &str,
+        is_static: bool,
+        to: Migration,
+    ) -> Result<(), GraphError> {
+        let error = |reason: String| GraphError::PropertyMigrationError {
+            name: name.to_owned(),
+            reason,
+        };
+        let GraphStorage::Unlocked(graph) = &self.graph else {
+            return Err(GraphError::AttemptToMutateImmutableGraph);
+        };
+        let meta = match owner {
+            PropertyOwner::Node => &graph.node_meta,
+            PropertyOwner::Edge => &graph.edge_meta,
+        };
+        let prop_id = meta
+            .get_prop_id(name, is_static)
+            .ok_or_else(|| error("the property does not exist".to_owned()))?;
+        let mapper = if is_static {
+            meta.const_prop_meta()
+        } else {
+            meta.temporal_prop_meta()
+        };
+        let from = mapper.get_dtype(prop_id).unwrap_or_default();
+        if !can_migrate(from, to) {
+            return Err(error(format!(
+                "{from:?} can't be migrated to {to:?} without loss"
+            )));
+        }
+        let migrate = |dtype: PropType, map: &dyn Fn(Prop) -> Result<Prop, GraphError>| match owner
+        {
+            PropertyOwner::Node => graph.migrate_node_property(prop_id, is_static, dtype, map),
+            PropertyOwner::Edge => graph.migrate_edge_property(prop_id, is_static, dtype, map),
+        };
+        match to {
+            Migration::Type(dtype) => {
+                self.check_schema(|schema| match owner {
+                    PropertyOwner::Node => schema.check_node_property(name, dtype, is_static),
+                    PropertyOwner::Edge => schema.check_edge_property(name, dtype, is_static),
+                })?;
+                migrate(dtype, &|prop| migrate_value(name, prop, dtype))?;
+            }
+            Migration::Categorical => {
+                let mut schema = self
+                    .schema()
+                    .filter(|schema| schema.declares_property(owner, name, is_static))
+                    .ok_or_else(|| {
+                        error("categorical properties need to be declared in the schema".to_owned())
+                    })?
+                    .as_ref()
+                    .clone();
+                // rewriting the values interns the strings such that equal values share storage
+                let categories = Mutex::new(BTreeSet::new());
+                migrate(from, &|prop| {
+                    if let Prop::Str(value) = &prop {
+                        categories.lock().insert(value.clone());
+                    }
+                    Ok(prop)
+                })?;
+                let categories: Vec<_> =
+                    categories.into_inner().into_iter().map(Prop::Str).collect();
+                schema.add_allowed_values(owner, name, is_static, &categories);
+                self.set_schema(Some(schema))?;
+            }
+        }
+        self.graph.bump_version();
+        // the stored values changed and can't be replayed from the log
+        self.rewrite_cache()
+    }
 }
+
+fn named_props<'a>(
+    meta: &'a Meta,
+    props: &'a [(usize, Prop)],
+    is_static: bool,
+) -> impl Iterator<Item = (ArcStr, &'a Prop)> + 'a {
+    props
+        .iter()
+        .map(move |(id, prop)| (meta.get_prop_name(*id, is_static), prop))
+}
+
 impl InheritViewOps for Storage {}
 
 impl InternalAdditionOps for Storage {
@@ -86,6 +262,12 @@ impl InternalAdditionOps for Storage {
     }
 
     fn resolve_layer(&self, layer: Option<&str>) -> Result<MaybeNew<usize>, GraphError> {
+        #[cfg(feature = "proto")]
+        let _updating = self.updating();
+
+        if let Some(layer) = layer {
+            self.check_schema(|schema| schema.check_layer(layer))?;
+        }
         let id = self.graph.resolve_layer(layer)?;
 
         #[cfg(feature = "proto")]
@@ -95,6 +277,9 @@ impl InternalAdditionOps for Storage {
     }
 
     fn resolve_node<V: AsNodeRef>(&self, id: V) -> Result<MaybeNew<VID>, GraphError> {
+        #[cfg(feature = "proto")]
+        let _updating = self.updating();
+
         match id.as_node_ref() {
             NodeRef::Internal(id) => Ok(MaybeNew::Existing(id)),
             NodeRef::External(gid) => {
@@ -113,6 +298,10 @@ impl InternalAdditionOps for Storage {
         id: V,
         node_type: &str,
     ) -> Result<MaybeNew<(MaybeNew<VID>, MaybeNew<usize>)>, GraphError> {
+        #[cfg(feature = "proto")]
+        let _updating = self.updating();
+
+        self.check_schema(|schema| schema.check_node_type(node_type))?;
         let node_and_type = self.graph.resolve_node_and_type(id, node_type)?;
 
         #[cfg(feature = "proto")]
@@ -131,6 +320,9 @@ impl InternalAdditionOps for Storage {
         dtype: PropType,
         is_static: bool,
     ) -> Result<MaybeNew<usize>, GraphError> {
+        #[cfg(feature = "proto")]
+        let _updating = self.updating();
+
         let id = self.graph.resolve_graph_property(prop, dtype, is_static)?;
 
         #[cfg(feature = "proto")]
@@ -145,6 +337,10 @@ impl InternalAdditionOps for Storage {
         dtype: PropType,
         is_static: bool,
     ) -> Result<MaybeNew<usize>, GraphError> {
+        #[cfg(feature = "proto")]
+        let _updating = self.updating();
+
+        self.check_schema(|schema| schema.check_node_property(prop, dtype, is_static))?;
         let id = self.graph.resolve_node_property(prop, dtype, is_static)?;
 
         #[cfg(feature = "proto")]
@@ -159,6 +355,10 @@ impl InternalAdditionOps for Storage {
         dtype: PropType,
         is_static: bool,
     ) -> Result<MaybeNew<usize>, GraphError> {
+        #[cfg(feature = "proto")]
+        let _updating = self.updating();
+
+        self.check_schema(|schema| schema.check_edge_property(prop, dtype, is_static))?;
         let id = self.graph.resolve_edge_property(prop, dtype, is_static)?;
 
         #[cfg(feature = "proto")]
@@ -173,7 +373,15 @@ impl InternalAdditionOps for Storage {
         v: VID,
         props: &[(usize, Prop)],
     ) -> Result<(), GraphError> {
+        #[cfg(feature = "proto")]
+        let _updating = self.updating();
+
+        self.check_schema(|schema| {
+            let props = named_props(self.graph.node_meta(), props, false);
+            schema.check_node_update(self.node_type(v).as_deref(), props, false)
+        })?;
         self.graph.internal_add_node(t, v, props)?;
+        self.graph.bump_version();
 
         #[cfg(feature = "proto")]
         self.if_cache(|cache| cache.add_node_update(t, v, props));
@@ -189,7 +397,12 @@ impl InternalAdditionOps for Storage {
         props: &[(usize, Prop)],
         layer: usize,
     ) -> Result<MaybeNew<EID>, GraphError> {
+        #[cfg(feature = "proto")]
+        let _updating = self.updating();
+
+        self.check_edge_update(layer, props, false)?;
         let id = self.graph.internal_add_edge(t, src, dst, props, layer)?;
+        self.graph.bump_version();
 
         #[cfg(feature = "proto")]
         self.if_cache(|cache| {
@@ -207,7 +420,12 @@ impl InternalAdditionOps for Storage {
         props: &[(usize, Prop)],
         layer: usize,
     ) -> Result<(), GraphError> {
+        #[cfg(feature = "proto")]
+        let _updating = self.updating();
+
+        self.check_edge_update(layer, props, false)?;
         self.graph.internal_add_edge_update(t, edge, props, layer)?;
+        self.graph.bump_version();
 
         #[cfg(feature = "proto")]
         self.if_cache(|cache| cache.add_edge_update(t, edge, props, layer));
@@ -222,7 +440,11 @@ impl InternalPropertyAdditionOps for Storage {
         t: TimeIndexEntry,
         props: &[(usize, Prop)],
     ) -> Result<(), GraphError> {
+        #[cfg(feature = "proto")]
+        let _updating = self.updating();
+
         self.graph.internal_add_properties(t, props)?;
+        self.graph.bump_version();
 
         #[cfg(feature = "proto")]
         self.if_cache(|cache| cache.add_graph_tprops(t, props));
@@ -231,7 +453,11 @@ impl InternalPropertyAdditionOps for Storage {
     }
 
     fn internal_add_constant_properties(&self, props: &[(usize, Prop)]) -> Result<(), GraphError> {
+        #[cfg(feature = "proto")]
+        let _updating = self.updating();
+
         self.graph.internal_add_constant_properties(props)?;
+        self.graph.bump_version();
 
         #[cfg(feature = "proto")]
         self.if_cache(|cache| cache.add_graph_cprops(props));
@@ -243,7 +469,11 @@ impl InternalPropertyAdditionOps for Storage {
         &self,
         props: &[(usize, Prop)],
     ) -> Result<(), GraphError> {
+        #[cfg(feature = "proto")]
+        let _updating = self.updating();
+
         self.graph.internal_update_constant_properties(props)?;
+        self.graph.bump_version();
 
         #[cfg(feature = "proto")]
         self.if_cache(|cache| cache.add_graph_cprops(props));
@@ -256,8 +486,13 @@ impl InternalPropertyAdditionOps for Storage {
         vid: VID,
         props: &[(usize, Prop)],
     ) -> Result<(), GraphError> {
+        #[cfg(feature = "proto")]
+        let _updating = self.updating();
+
+        self.check_node_constant_update(vid, props)?;
         self.graph
             .internal_add_constant_node_properties(vid, props)?;
+        self.graph.bump_version();
 
         #[cfg(feature = "proto")]
         self.if_cache(|cache| cache.add_node_cprops(vid, props));
@@ -270,8 +505,13 @@ impl InternalPropertyAdditionOps for Storage {
         vid: VID,
         props: &[(usize, Prop)],
     ) -> Result<(), GraphError> {
+        #[cfg(feature = "proto")]
+        let _updating = self.updating();
+
+        self.check_node_constant_update(vid, props)?;
         self.graph
             .internal_update_constant_node_properties(vid, props)?;
+        self.graph.bump_version();
 
         #[cfg(feature = "proto")]
         self.if_cache(|cache| cache.add_node_cprops(vid, props));
@@ -285,8 +525,13 @@ impl InternalPropertyAdditionOps for Storage {
         layer: usize,
         props: &[(usize, Prop)],
     ) -> Result<(), GraphError> {
+        #[cfg(feature = "proto")]
+        let _updating = self.updating();
+
+        self.check_edge_update(layer, props, true)?;
         self.graph
             .internal_add_constant_edge_properties(eid, layer, props)?;
+        self.graph.bump_version();
 
         #[cfg(feature = "proto")]
         self.if_cache(|cache| cache.add_edge_cprops(eid, layer, props));
@@ -300,8 +545,13 @@ impl InternalPropertyAdditionOps for Storage {
         layer: usize,
         props: &[(usize, Prop)],
     ) -> Result<(), GraphError> {
+        #[cfg(feature = "proto")]
+        let _updating = self.updating();
+
+        self.check_edge_update(layer, props, true)?;
         self.graph
             .internal_update_constant_edge_properties(eid, layer, props)?;
+        self.graph.bump_version();
 
         #[cfg(feature = "proto")]
         self.if_cache(|cache| cache.add_edge_cprops(eid, layer, props));
@@ -318,7 +568,11 @@ impl InternalDeletionOps for Storage {
         dst: VID,
         layer: usize,
     ) -> Result<MaybeNew<EID>, GraphError> {
+        #[cfg(feature = "proto")]
+        let _updating = self.updating();
+
         let eid = self.graph.internal_delete_edge(t, src, dst, layer)?;
+        self.graph.bump_version();
 
         #[cfg(feature = "proto")]
         self.if_cache(|cache| {
@@ -335,7 +589,11 @@ impl InternalDeletionOps for Storage {
         eid: EID,
         layer: usize,
     ) -> Result<(), GraphError> {
+        #[cfg(feature = "proto")]
+        let _updating = self.updating();
+
         self.graph.internal_delete_existing_edge(t, eid, layer)?;
+        self.graph.bump_version();
 
         #[cfg(feature = "proto")]
         self.if_cache(|cache| cache.delete_edge(eid, t, layer));
@@ -343,3 +601,194 @@ impl InternalDeletionOps for Storage {
         Ok(())
     }
 }
+
+impl Storage {
+    /// Destructive changes cannot be replayed from the write-ahead log, rewrite the cached
+    /// snapshot instead
+    fn rewrite_cache(&self) -> Result<(), GraphError> {
+        #[cfg(feature = "proto")]
+        if let Some(cache) = self.cache.get() {
+            cache.rewrite(&self.graph, self.schema_json())?;
+        }
+        Ok(())
+    }
+}
+
+impl InternalHyperedgeOps for Storage {
+    fn resolve_hyperedge_role(&self, role: &str) -> Result<MaybeNew<usize>, GraphError> {
+        #[cfg(feature = "proto")]
+        let _updating = self.updating();
+
+        let id = self.graph.resolve_hyperedge_role(role)?;
+
+        #[cfg(feature = "proto")]
+        self.if_cache(|cache| cache.resolve_hyperedge_role(role, id));
+
+        Ok(id)
+    }
+
+    fn resolve_hyperedge_property(
+        &self,
+        prop: &str,
+        dtype: PropType,
+    ) -> Result<MaybeNew<usize>, GraphError> {
+        #[cfg(feature = "proto")]
+        let _updating = self.updating();
+
+        let id = self.graph.resolve_hyperedge_property(prop, dtype)?;
+
+        #[cfg(feature = "proto")]
+        self.if_cache(|cache| cache.resolve_hyperedge_property(prop, id, dtype));
+
+        Ok(id)
+    }
+
+    fn internal_add_hyperedge(
+        &self,
+        t: TimeIndexEntry,
+        id: GidRef,
+        participants: &[(VID, Option<usize>)],
+        props: &[(usize, Prop)],
+    ) -> Result<MaybeNew<usize>, GraphError> {
+        #[cfg(feature = "proto")]
+        let _updating = self.updating();
+
+        let hid = self
+            .graph
+            .internal_add_hyperedge(t, id, participants, props)?;
+        self.graph.bump_version();
+
+        #[cfg(feature = "proto")]
+        self.if_cache(|cache| {
+            hid.if_new(|_| cache.add_hyperedge(t, id, participants, props));
+        });
+
+        Ok(hid)
+    }
+}
+
+impl InternalRemovalOps for Storage {
+    fn internal_remove_node(&self, v: VID) -> Result<(), GraphError> {
+        #[cfg(feature = "proto")]
+        let _updating = self.updating();
+
+        self.graph.internal_remove_node(v)?;
+        self.graph.bump_version();
+
+        #[cfg(feature = "proto")]
+        self.if_cache(|cache| cache.remove_node(v));
+
+        Ok(())
+    }
+
+    fn internal_remove_edge(&self, eid: EID, layer_ids: &LayerIds) -> Result<(), GraphError> {
+        #[cfg(feature = "proto")]
+        let _updating = self.updating();
+
+        self.graph.internal_remove_edge(eid, layer_ids)?;
+        self.graph.bump_version();
+
+        #[cfg(feature = "proto")]
+        self.if_cache(|cache| cache.remove_edge(eid, layer_ids));
+
+        Ok(())
+    }
+
+    fn internal_remove_node_property_updates(
+        &self,
+        v: VID,
+        w: Range<i64>,
+        prop_id: Option<usize>,
+    ) -> Result<(), GraphError> {
+        #[cfg(feature = "proto")]
+        let _updating = self.updating();
+
+        self.graph
+            .internal_remove_node_property_updates(v, w.clone(), prop_id)?;
+        self.graph.bump_version();
+
+        #[cfg(feature = "proto")]
+        self.if_cache(|cache| cache.remove_node_tprops(v, w, prop_id));
+
+        Ok(())
+    }
+
+    fn internal_remove_edge_property_updates(
+        &self,
+        eid: EID,
+        layer_ids: &LayerIds,
+        w: Range<i64>,
+        prop_id: Option<usize>,
+    ) -> Result<(), GraphError> {
+        #[cfg(feature = "proto")]
+        let _updating = self.updating();
+
+        self.graph
+            .internal_remove_edge_property_updates(eid, layer_ids, w.clone(), prop_id)?;
+        self.graph.bump_version();
+
+        #[cfg(feature = "proto")]
+        self.if_cache(|cache| cache.remove_edge_tprops(eid, layer_ids, w, prop_id));
+
+        Ok(())
+    }
+
+    fn internal_remove_graph_property_updates(
+        &self,
+        w: Range<i64>,
+        prop_id: Option<usize>,
+    ) -> Result<(), GraphError> {
+        #[cfg(feature = "proto")]
+        let _updating = self.updating();
+
+        self.graph
+            .internal_remove_graph_property_updates(w.clone(), prop_id)?;
+        self.graph.bump_version();
+
+        #[cfg(feature = "proto")]
+        self.if_cache(|cache| cache.remove_graph_tprops(w, prop_id));
+
+        Ok(())
+    }
+
+    fn internal_truncate_before(&self, t: i64, keep_alive: bool) -> Result<(), GraphError> {
+        #[cfg(feature = "proto")]
+        let _updating = self.updating();
+
+        self.graph.internal_truncate_before(t, keep_alive)?;
+        self.graph.bump_version();
+
+        #[cfg(feature = "proto")]
+        self.if_cache(|cache| cache.truncate_before(t, keep_alive));
+
+        Ok(())
+    }
+}
+
+impl InternalNodeMergeOps for Storage {
+    fn internal_merge_nodes(&self, keep: VID, merged: VID) -> Result<NodeMerge, GraphError> {
+        #[cfg(feature = "proto")]
+        let _updating = self.updating();
+
+        let merge = self.graph.internal_merge_nodes(keep, merged)?;
+        self.graph.bump_version();
+
+        #[cfg(feature = "proto")]
+        self.if_cache(|cache| cache.merge_nodes(&self.graph, &merge));
+
+        Ok(merge)
+    }
+
+    fn internal_unmerge_node(&self, merged: VID) -> Result<(), GraphError> {
+        #[cfg(feature = "proto")]
+        let _updating = self.updating();
+
+        self.graph.internal_unmerge_node(merged)?;
+        self.graph.bump_version();
+
+        #[cfg(feature = "proto")]
+        self.if_cache(|cache| cache.unmerge_node(self.graph.node_entry(merged).id()));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{
+    core::utils::errors::GraphError,
+    db::{
+        api::{
+            properties::computed::{ComputedEdgeProp, ComputedNodeProp, ComputedProperties},
+            view::{internal::InternalComputedPropertiesOps, StaticGraphViewOps},
+        },
+        graph::views::computed_props_graph::ComputedPropsGraph,
+    },
+};
+
+/// Register computed node and edge properties on a view.
+///
+/// Computed properties are evaluated lazily when they are accessed through the properties of
+/// a node or edge and respect any windows that are applied to the returned view.
+pub trait ComputedPropertiesOps: StaticGraphViewOps {
+    /// Return a view with an additional computed node property
+    ///
+    /// Arguments:
+    ///     name: the name of the property, it shadows any stored node property with the same name
+    ///     prop: a [PropExpr](crate::db::api::properties::computed::PropExpr) or a closure
+    ///           created with [ComputedProp::func](crate::db::api::properties::computed::ComputedProp::func)
+    ///
+    /// Returns an error if the expression refers to itself through other computed properties.
+    fn with_node_property(
+        &self,
+        name: &str,
+        prop: impl Into<ComputedNodeProp>,
+    ) -> Result<ComputedPropsGraph<Self>, GraphError>;
+
+    /// Return a view with an additional computed edge property
+    ///
+    /// Arguments:
+    ///     name: the name of the property, it shadows any stored edge property with the same name
+    ///     prop: a [PropExpr](crate::db::api::properties::computed::PropExpr) or a closure
+    ///           created with [ComputedProp::func](crate::db::api::properties::computed::ComputedProp::func)
+    ///
+    /// Returns an error if the expression refers to itself through other computed properties.
+    fn with_edge_property(
+        &self,
+        name: &str,
+        prop: impl Into<ComputedEdgeProp>,
+    ) -> Result<ComputedPropsGraph<Self>, GraphError>;
+
+    /// Return a view with the computed properties `props`, replacing any computed properties
+    /// that are registered on this view
+    fn with_computed_properties(&self, props: ComputedProperties) -> ComputedPropsGraph<Self>;
+}
+
+impl<G: StaticGraphViewOps> ComputedPropertiesOps for G {
+    fn with_node_property(
+        &self,
+        name: &str,
+        prop: impl Into<ComputedNodeProp>,
+    ) -> Result<ComputedPropsGraph<Self>, GraphError> {
+        let mut props = self.computed_properties().cloned().unwrap_or_default();
+        props.add_node_prop(name, prop)?;
+        Ok(self.with_computed_properties(props))
+    }
+
+    fn with_edge_property(
+        &self,
+        name: &str,
+        prop: impl Into<ComputedEdgeProp>,
+    ) -> Result<ComputedPropsGraph<Self>, GraphError> {
+        let mut props = self.computed_properties().cloned().unwrap_or_default();
+        props.add_edge_prop(name, prop)?;
+        Ok(self.with_computed_properties(props))
+    }
+
+    fn with_computed_properties(&self, props: ComputedProperties) -> ComputedPropsGraph<Self> {
+        ComputedPropsGraph::new(self.clone(), props)
+    }
+}
@@ -0,0 +1,694 @@
+//! Structured differences between two graph views, see [GraphViewOps::diff].
+//!
+//! Nodes are matched by their id and edges by their source and destination ids and their layer.
+//! Temporal properties are compared by their latest value in each view, such that
+//! `g.at(t1).diff(&g.at(t2))` reports the state changes between `t1` and `t2`.
+
+use crate::{
+    core::{
+        entities::properties::props::PropMapper,
+        utils::{errors::GraphError, time::TryIntoTime},
+        Prop, PropType,
+    },
+    db::api::{
+        mutation::{
+            internal::{InternalAdditionOps, InternalPropertyAdditionOps},
+            AdditionOps, PropertyAdditionOps, RemovalOps,
+        },
+        properties::{internal::PropertiesOps, Properties},
+        view::{internal::CoreGraphOps, LayerOps, StaticGraphViewOps},
+    },
+    prelude::{EdgeViewOps, GraphViewOps, NodeViewOps},
+};
+use raphtory_api::core::entities::GID;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// The value of a property before and after the change, `None` if it does not exist
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PropertyChange {
+    pub key: String,
+    pub before: Option<Prop>,
+    pub after: Option<Prop>,
+}
+
+/// The changed constant and temporal properties of an entity
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct PropertyDiff {
+    pub constant: Vec<PropertyChange>,
+    pub temporal: Vec<PropertyChange>,
+}
+
+impl PropertyDiff {
+    fn new(before: &PropertyState, after: &PropertyState) -> Self {
+        Self {
+            constant: diff_values(&before.constant, &after.constant),
+            temporal: diff_values(&before.temporal, &after.temporal),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.constant.is_empty() && self.temporal.is_empty()
+    }
+
+    fn new_values(changes: &[PropertyChange]) -> Vec<(String, Prop)> {
+        changes
+            .iter()
+            .filter_map(|change| Some((change.key.clone(), change.after.clone()?)))
+            .collect()
+    }
+}
+
+/// A node that was added, removed or changed
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct NodeDiff {
+    pub id: GID,
+    pub node_type_before: Option<String>,
+    pub node_type_after: Option<String>,
+    pub properties: PropertyDiff,
+}
+
+/// An edge in a single layer that was added, removed or changed
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EdgeDiff {
+    pub src: GID,
+    pub dst: GID,
+    pub layer: String,
+    pub properties: PropertyDiff,
+}
+
+/// The difference between two graph views.
+///
+/// Added and removed entities report all their property values as changes from or to `None`.
+/// Layers are compared based on the layers that have at least one edge in each view.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct GraphDiff {
+    pub added_nodes: Vec<NodeDiff>,
+    pub removed_nodes: Vec<NodeDiff>,
+    pub changed_nodes: Vec<NodeDiff>,
+    pub added_edges: Vec<EdgeDiff>,
+    pub removed_edges: Vec<EdgeDiff>,
+    pub changed_edges: Vec<EdgeDiff>,
+    pub added_layers: Vec<String>,
+    pub removed_layers: Vec<String>,
+    pub properties: PropertyDiff,
+}
+
+#[derive(Default)]
+struct PropertyState {
+    constant: BTreeMap<String, Prop>,
+    temporal: BTreeMap<String, Prop>,
+}
+
+impl PropertyState {
+    fn new<P: PropertiesOps + Clone>(properties: Properties<P>) -> Self {
+        Self {
+            constant: properties
+                .constant()
+                .iter()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect(),
+            temporal: properties
+                .temporal()
+                .iter_latest()
+                .map(|(k, v)| (k.to_string(), v))
+                .collect(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct NodeState {
+    node_type: Option<String>,
+    properties: PropertyState,
+}
+
+type EdgeKey = (GID, GID, String);
+
+fn diff_values(
+    before: &BTreeMap<String, Prop>,
+    after: &BTreeMap<String, Prop>,
+) -> Vec<PropertyChange> {
+    let keys: BTreeSet<_> = before.keys().chain(after.keys()).collect();
+    keys.into_iter()
+        .filter_map(|key| {
+            let (before, after) = (before.get(key), after.get(key));
+            (before != after).then(|| PropertyChange {
+                key: key.clone(),
+                before: before.cloned(),
+                after: after.cloned(),
+            })
+        })
+        .collect()
+}
+
+fn node_states<'graph, G: GraphViewOps<'graph>>(graph: &G) -> BTreeMap<GID, NodeState> {
+    graph
+        .nodes()
+        .iter()
+        .map(|node| {
+            let state = NodeState {
+                node_type: node.node_type().map(|t| t.to_string()),
+                properties: PropertyState::new(node.properties()),
+            };
+            (node.id(), state)
+        })
+        .collect()
+}
+
+fn edge_states<'graph, G: GraphViewOps<'graph>>(graph: &G) -> BTreeMap<EdgeKey, PropertyState> {
+    graph
+        .edges()
+        .explode_layers()
+        .iter()
+        .filter_map(|edge| {
+            let layer = edge.layer_name().ok()?.to_string();
+            let key = (edge.src().id(), edge.dst().id(), layer);
+            Some((key, PropertyState::new(edge.properties())))
+        })
+        .collect()
+}
+
+fn node_diff(id: &GID, before: &NodeState, after: &NodeState) -> NodeDiff {
+    NodeDiff {
+        id: id.clone(),
+        node_type_before: before.node_type.clone(),
+        node_type_after: after.node_type.clone(),
+        properties: PropertyDiff::new(&before.properties, &after.properties),
+    }
+}
+
+fn edge_diff(
+    (src, dst, layer): &EdgeKey,
+    before: &PropertyState,
+    after: &PropertyState,
+) -> EdgeDiff {
+    EdgeDiff {
+        src: src.clone(),
+        dst: dst.clone(),
+        layer: layer.clone(),
+        properties: PropertyDiff::new(before, after),
+    }
+}
+
+/// The type of the property `name` in the graph, `None` if it has no values yet
+fn existing_type(mapper: &PropMapper, name: &str) -> Option<PropType> {
+    mapper
+        .get_id(name)
+        .and_then(|id| mapper.get_dtype(id))
+        .filter(|dtype| !matches!(dtype, PropType::Empty))
+}
+
+/// Check that the new values of `changes` have the types of the properties in the graph, or of
+/// the same properties elsewhere in the patch if the graph has no values for them yet
+fn check_types(
+    changes: &[PropertyChange],
+    mapper: Option<&PropMapper>,
+    seen: &mut HashMap<String, PropType>,
+) -> Result<(), GraphError> {
+    for change in changes {
+        let Some(value) = &change.after else {
+            continue;
+        };
+        let actual = value.dtype();
+        let expected = mapper
+            .and_then(|mapper| existing_type(mapper, &change.key))
+            .or_else(|| seen.get(&change.key).copied());
+        match expected {
+            Some(expected) if expected != actual => {
+                return Err(GraphError::PropertyTypeError {
+                    name: change.key.clone(),
+                    expected,
+                    actual,
+                })
+            }
+            _ => {
+                seen.insert(change.key.clone(), actual);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The types of the properties set by a patch, keyed by name within each kind of property
+#[derive(Default)]
+struct PatchTypes {
+    node_constant: HashMap<String, PropType>,
+    node_temporal: HashMap<String, PropType>,
+    edge_constant: HashMap<String, PropType>,
+    edge_temporal: HashMap<String, PropType>,
+    graph_temporal: HashMap<String, PropType>,
+}
+
+impl GraphDiff {
+    /// Compute the changes that turn `before` into `after`
+    pub fn between<'a, 'b, A: GraphViewOps<'a>, B: GraphViewOps<'b>>(
+        before: &A,
+        after: &B,
+    ) -> Self {
+        let mut diff = GraphDiff {
+            properties: PropertyDiff::new(
+                &PropertyState::new(before.properties()),
+                &PropertyState::new(after.properties()),
+            ),
+            ..Default::default()
+        };
+        let empty_node = NodeState::default();
+        let empty_props = PropertyState::default();
+
+        let nodes_before = node_states(before);
+        let nodes_after = node_states(after);
+        for (id, state) in nodes_before.iter() {
+            match nodes_after.get(id) {
+                None => diff.removed_nodes.push(node_diff(id, state, &empty_node)),
+                Some(new_state) => {
+                    let changes = node_diff(id, state, new_state);
+                    if changes.node_type_before != changes.node_type_after
+                        || !changes.properties.is_empty()
+                    {
+                        diff.changed_nodes.push(changes);
+                    }
+                }
+            }
+        }
+        for (id, state) in nodes_after.iter() {
+            if !nodes_before.contains_key(id) {
+                diff.added_nodes.push(node_diff(id, &empty_node, state));
+            }
+        }
+
+        let edges_before = edge_states(before);
+        let edges_after = edge_states(after);
+        for (key, state) in edges_before.iter() {
+            match edges_after.get(key) {
+                None => diff.removed_edges.push(edge_diff(key, state, &empty_props)),
+                Some(new_state) => {
+                    let changes = edge_diff(key, state, new_state);
+                    if !changes.properties.is_empty() {
+                        diff.changed_edges.push(changes);
+                    }
+                }
+            }
+        }
+        for (key, state) in edges_after.iter() {
+            if !edges_before.contains_key(key) {
+                diff.added_edges.push(edge_diff(key, &empty_props, state));
+            }
+        }
+
+        let layers_before: BTreeSet<_> = edges_before.keys().map(|(_, _, l)| l).collect();
+        let layers_after: BTreeSet<_> = edges_after.keys().map(|(_, _, l)| l).collect();
+        diff.added_layers = layers_after
+            .difference(&layers_before)
+            .map(|l| l.to_string())
+            .collect();
+        diff.removed_layers = layers_before
+            .difference(&layers_after)
+            .map(|l| l.to_string())
+            .collect();
+        diff
+    }
+
+    /// Check if the two views are the same
+    pub fn is_empty(&self) -> bool {
+        self.added_nodes.is_empty()
+            && self.removed_nodes.is_empty()
+            && self.changed_nodes.is_empty()
+            && self.added_edges.is_empty()
+            && self.removed_edges.is_empty()
+            && self.changed_edges.is_empty()
+            && self.added_layers.is_empty()
+            && self.removed_layers.is_empty()
+            && self.properties.is_empty()
+    }
+
+    /// Check that the diff can be applied to `graph` without changing it, i.e., that the types
+    /// of all new property values match and that no node changes its type. If `removals` is set,
+    /// the removed nodes and edges also need to exist in `graph`.
+    fn validate<G: StaticGraphViewOps>(&self, graph: &G, removals: bool) -> Result<(), GraphError> {
+        let mut types = PatchTypes::default();
+        for node in self.added_nodes.iter().chain(&self.changed_nodes) {
+            check_types(
+                &node.properties.constant,
+                Some(graph.node_meta().const_prop_meta()),
+                &mut types.node_constant,
+            )?;
+            check_types(
+                &node.properties.temporal,
+                Some(graph.node_meta().temporal_prop_meta()),
+                &mut types.node_temporal,
+            )?;
+            if node.node_type_before == node.node_type_after {
+                continue;
+            }
+            let existing = graph.node(&node.id).and_then(|target| target.node_type());
+            if let (Some(existing), Some(new)) = (existing, &node.node_type_after) {
+                if existing.to_string() != *new {
+                    return Err(GraphError::NodeTypeError(format!(
+                        "Cannot change node type of {} from {existing} to {new}",
+                        node.id
+                    )));
+                }
+            }
+        }
+        for edge in self.added_edges.iter().chain(&self.changed_edges) {
+            check_types(
+                &edge.properties.constant,
+                Some(graph.edge_meta().const_prop_meta()),
+                &mut types.edge_constant,
+            )?;
+            check_types(
+                &edge.properties.temporal,
+                Some(graph.edge_meta().temporal_prop_meta()),
+                &mut types.edge_temporal,
+            )?;
+        }
+        check_types(
+            &self.properties.temporal,
+            Some(graph.graph_meta().temporal_prop_meta()),
+            &mut types.graph_temporal,
+        )?;
+        // graph constants can be overwritten with values of any type
+        check_types(&self.properties.constant, None, &mut HashMap::new())?;
+
+        if removals {
+            for node in self.removed_nodes.iter() {
+                if graph.node(&node.id).is_none() {
+                    return Err(GraphError::NodeNameError(node.id.to_string()));
+                }
+            }
+            for edge in self.removed_edges.iter() {
+                let exists = graph
+                    .layers(edge.layer.as_str())
+                    .is_ok_and(|layer| layer.has_edge(&edge.src, &edge.dst));
+                if !exists {
+                    return Err(GraphError::EdgeNameError {
+                        src: edge.src.to_string(),
+                        dst: edge.dst.to_string(),
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Apply the added and changed nodes, edges and property values to `graph` at time `t`.
+    ///
+    /// Removed entities are ignored, use [GraphDiff::apply] to also remove them. Temporal
+    /// properties that were removed cannot be undone and are ignored as well.
+    ///
+    /// The diff is validated first, such that `graph` is left unchanged if it returns an error
+    /// because a property value has a different type than in `graph` or a node would change its
+    /// type.
+    pub fn apply_additions<
+        G: StaticGraphViewOps + InternalAdditionOps + InternalPropertyAdditionOps,
+        T: TryIntoTime,
+    >(
+        &self,
+        graph: &G,
+        t: T,
+    ) -> Result<(), GraphError> {
+        let t = t.try_into_time()?;
+        self.validate(graph, false)?;
+        self.add_changes(graph, t)
+    }
+
+    fn add_changes<G: StaticGraphViewOps + InternalAdditionOps + InternalPropertyAdditionOps>(
+        &self,
+        graph: &G,
+        t: i64,
+    ) -> Result<(), GraphError> {
+        for node in self.added_nodes.iter().chain(&self.changed_nodes) {
+            let temporal = PropertyDiff::new_values(&node.properties.temporal);
+            // nodes can only get a type if they do not have one yet, which was validated
+            let node_type = match node.node_type_before == node.node_type_after {
+                true => None,
+                false => node.node_type_after.as_deref(),
+            };
+            let target = match graph.node(&node.id) {
+                Some(target) if temporal.is_empty() => {
+                    if let Some(node_type) = node_type {
+                        target.set_node_type(node_type)?;
+                    }
+                    target
+                }
+                _ => graph.add_node(t, &node.id, temporal, node_type)?,
+            };
+            let constant = PropertyDiff::new_values(&node.properties.constant);
+            if !constant.is_empty() {
+                target.update_constant_properties(constant)?;
+            }
+        }
+
+        for (edge, added) in self
+            .added_edges
+            .iter()
+            .map(|edge| (edge, true))
+            .chain(self.changed_edges.iter().map(|edge| (edge, false)))
+        {
+            let layer = Some(edge.layer.as_str());
+            let temporal = PropertyDiff::new_values(&edge.properties.temporal);
+            let in_layer = graph
+                .layers(edge.layer.as_str())
+                .is_ok_and(|g| g.has_edge(&edge.src, &edge.dst));
+            let target = match graph.edge(&edge.src, &edge.dst) {
+                Some(target) if temporal.is_empty() && in_layer && !added => target,
+                _ => graph.add_edge(t, &edge.src, &edge.dst, temporal, layer)?,
+            };
+            let constant = PropertyDiff::new_values(&edge.properties.constant);
+            if !constant.is_empty() {
+                target.update_constant_properties(constant, layer)?;
+            }
+        }
+
+        let temporal = PropertyDiff::new_values(&self.properties.temporal);
+        if !temporal.is_empty() {
+            graph.add_properties(t, temporal)?;
+        }
+        let constant = PropertyDiff::new_values(&self.properties.constant);
+        if !constant.is_empty() {
+            graph.update_constant_properties(constant)?;
+        }
+        Ok(())
+    }
+
+    /// Apply the diff to `graph` at time `t`, see [GraphDiff::apply_additions].
+    ///
+    /// Removed edges are removed from their layer and removed nodes are removed together with
+    /// their edges, see [RemovalOps]. The whole diff is validated first, such that `graph` is
+    /// left unchanged if a removed node or edge does not exist in `graph`.
+    pub fn apply<
+        G: RemovalOps + InternalAdditionOps + InternalPropertyAdditionOps,
+        T: TryIntoTime,
+    >(
+        &self,
+        graph: &G,
+        t: T,
+    ) -> Result<(), GraphError> {
+        let t = t.try_into_time()?;
+        self.validate(graph, true)?;
+        for edge in self.removed_edges.iter() {
+            graph.remove_edge(&edge.src, &edge.dst, Some(&edge.layer))?;
+        }
+        for node in self.removed_nodes.iter() {
+            graph.remove_node(&node.id)?;
+        }
+        self.add_changes(graph, t)
+    }
+
+    pub fn to_json(&self) -> Result<String, serde_json::Error> {
+        serde_json::to_string(self)
+    }
+
+    pub fn from_json(json: &str) -> Result<Self, serde_json::Error> {
+        serde_json::from_str(json)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{db::graph::views::deletion_graph::PersistentGraph, prelude::*};
+    use itertools::Itertools;
+
+    #[test]
+    fn test_diff_between_times() {
+        let g = PersistentGraph::new();
+        g.add_node(0, "a", [("score", 1)], Some("person")).unwrap();
+        g.add_edge(1, "a", "b", [("weight", 1.0)], None).unwrap();
+        g.add_edge(1, "b", "c", NO_PROPS, Some("friends")).unwrap();
+        g.add_node(5, "a", [("score", 2)], None).unwrap();
+        g.delete_edge(6, "b", "c", Some("friends")).unwrap();
+        g.add_edge(7, "c", "d", NO_PROPS, Some("work")).unwrap();
+        g.add_properties(8, [("version", 2)]).unwrap();
+        g.edge("a", "b")
+            .unwrap()
+            .add_constant_properties([("kind", "strong")], None)
+            .unwrap();
+
+        let diff = g.at(2).diff(&g.at(8));
+        assert_eq!(
+            diff.added_nodes.iter().map(|n| n.id.clone()).collect_vec(),
+            vec![GID::Str("d".to_string())]
+        );
+        assert!(diff.removed_nodes.is_empty());
+        assert_eq!(diff.changed_nodes.len(), 1);
+        assert_eq!(
+            diff.changed_nodes[0].properties.temporal,
+            vec![PropertyChange {
+                key: "score".to_string(),
+                before: Some(Prop::I32(1)),
+                after: Some(Prop::I32(2)),
+            }]
+        );
+        assert_eq!(
+            diff.added_edges
+                .iter()
+                .map(|e| (e.src.clone(), e.dst.clone(), e.layer.clone()))
+                .collect_vec(),
+            vec![(
+                GID::Str("c".to_string()),
+                GID::Str("d".to_string()),
+                "work".to_string()
+            )]
+        );
+        assert_eq!(diff.removed_edges.len(), 1);
+        assert_eq!(diff.removed_edges[0].layer, "friends");
+        assert!(diff.changed_edges.is_empty());
+        assert_eq!(diff.added_layers, vec!["work"]);
+        assert_eq!(diff.removed_layers, vec!["friends"]);
+        assert_eq!(
+            diff.properties.temporal,
+            vec![PropertyChange {
+                key: "version".to_string(),
+                before: None,
+                after: Some(Prop::I32(2)),
+            }]
+        );
+        assert!(g.at(8).diff(&g.at(8)).is_empty());
+    }
+
+    #[test]
+    fn test_apply_diff_and_json() {
+        let before = PersistentGraph::new();
+        before.add_edge(0, 1, 2, [("weight", 1)], None).unwrap();
+        before.add_edge(0, 2, 3, NO_PROPS, None).unwrap();
+
+        let after = PersistentGraph::new();
+        after.add_edge(0, 1, 2, [("weight", 2)], None).unwrap();
+        after.add_node(0, 4, NO_PROPS, Some("new")).unwrap();
+        after
+            .node(1)
+            .unwrap()
+            .add_constant_properties([("name", "one")])
+            .unwrap();
+
+        let diff = before.diff(&after);
+        let diff = GraphDiff::from_json(&diff.to_json().unwrap()).unwrap();
+        assert_eq!(diff, before.diff(&after));
+
+        let patched = before.clone();
+        diff.apply(&patched, 1).unwrap();
+        assert!(!patched.at(2).has_edge(2, 3));
+        assert_eq!(
+            patched
+                .at(2)
+                .edge(1, 2)
+                .unwrap()
+                .properties()
+                .get("weight")
+                .unwrap(),
+            Prop::I32(2)
+        );
+        assert_eq!(
+            patched
+                .node(1)
+                .unwrap()
+                .properties()
+                .constant()
+                .get("name")
+                .unwrap(),
+            Prop::str("one")
+        );
+
+        let event_graph = Graph::new();
+        event_graph
+            .add_edge(0, 1, 2, [("weight", 1)], None)
+            .unwrap();
+        diff.apply_additions(&event_graph, 1).unwrap();
+        assert_eq!(
+            event_graph
+                .edge(1, 2)
+                .unwrap()
+                .properties()
+                .get("weight")
+                .unwrap(),
+            Prop::I32(2)
+        );
+        assert_eq!(
+            event_graph
+                .node(4)
+                .unwrap()
+                .node_type()
+                .unwrap()
+                .to_string(),
+            "new"
+        );
+    }
+
+    #[test]
+    fn test_invalid_diff_is_not_applied() {
+        // clones share their storage, so create new graphs to apply the diffs to
+        let new_before = || {
+            let graph = Graph::new();
+            graph.add_node(0, 1, NO_PROPS, None).unwrap();
+            graph
+        };
+        let new_after = || {
+            let graph = Graph::new();
+            graph.add_node(0, 1, NO_PROPS, Some("person")).unwrap();
+            graph.add_edge(0, 1, 2, [("weight", 1.0)], None).unwrap();
+            graph
+        };
+        let (before, after) = (new_before(), new_after());
+        let diff = before.diff(&after);
+
+        // nodes without a type get the new one
+        let untyped = new_before();
+        diff.apply(&untyped, 1).unwrap();
+        assert_eq!(untyped.node(1).unwrap().node_type().unwrap(), "person");
+        assert!(untyped.diff(&after).is_empty());
+
+        // nodes cannot change their type
+        let typed = Graph::new();
+        typed.add_node(0, 1, NO_PROPS, Some("company")).unwrap();
+        assert!(matches!(
+            diff.apply(&typed, 1),
+            Err(GraphError::NodeTypeError(_))
+        ));
+        assert!(typed.edge(1, 2).is_none());
+
+        // property values need to have the same type as in the graph
+        let other_weights = Graph::new();
+        other_weights
+            .add_edge(0, 3, 4, [("weight", 1)], None)
+            .unwrap();
+        assert!(matches!(
+            diff.apply_additions(&other_weights, 1),
+            Err(GraphError::PropertyTypeError { .. })
+        ));
+        assert!(other_weights.node(1).is_none());
+
+        // removed nodes and edges need to exist
+        let reverse = after.diff(&before);
+        assert_eq!(reverse.removed_nodes.len(), 1);
+        assert!(matches!(
+            reverse.apply(&new_before(), 1),
+            Err(GraphError::NodeNameError(_))
+        ));
+        let patched = new_after();
+        reverse.apply(&patched, 1).unwrap();
+        assert!(patched.node(2).is_none());
+        assert!(patched.edge(1, 2).is_none());
+    }
+}
@@ -5,7 +5,7 @@ use raphtory_api::core::{entities::GID, storage::arc_str::ArcStr};
 
 use crate::{
     core::{
-        entities::{edges::edge_ref::EdgeRef, VID},
+        entities::{edges::edge_ref::EdgeRef, LayerIds, VID},
         storage::timeindex::{AsTime, TimeIndexEntry},
         utils::errors::GraphError,
     },
@@ -121,7 +121,8 @@ pub trait EdgeViewOps<'graph>: TimeOps<'graph> + LayerOps<'graph> + Clone {
 
     fn date_time(&self) -> Self::ValueType<Option<DateTime<Utc>>>;
 
-    /// Gets the layer name for the edge if it is restricted to a single layer
+    /// Gets the layer name for the edge if it is restricted to a single layer, i.e., if it was
+    /// exploded or its view only includes a single layer
     fn layer_name(&self) -> Self::ValueType<Result<ArcStr, GraphError>>;
 
     /// Gets the TimeIndexEntry if the edge is exploded
@@ -268,10 +269,9 @@ impl<'graph, E: BaseEdgeViewOps<'graph>> EdgeViewOps<'graph> for E {
 
     /// Gets the layer name for the edge if it is restricted to a single layer
     fn layer_name(&self) -> Self::ValueType<Result<ArcStr, GraphError>> {
-        self.map(|g, e| {
-            e.layer()
-                .map(|l_id| g.get_layer_name(*l_id))
-                .ok_or_else(|| GraphError::LayerNameAPIError)
+        self.map(|g, e| match (e.layer(), g.layer_ids()) {
+            (Some(l_id), _) | (None, LayerIds::One(l_id)) => Ok(g.get_layer_name(*l_id)),
+            _ => Err(GraphError::LayerNameAPIError),
         })
     }
 
@@ -350,6 +350,40 @@ mod test_edge_view {
         });
     }
 
+    #[test]
+    fn test_layer_name_of_mixed_layer_edges() {
+        let graph = Graph::new();
+        graph.add_edge(0, 1, 2, NO_PROPS, Some("a")).unwrap();
+        graph.add_edge(1, 1, 2, NO_PROPS, Some("b")).unwrap();
+        graph.add_edge(0, 2, 3, NO_PROPS, Some("b")).unwrap();
+        test_storage!(&graph, |graph| {
+            // edges that are not exploded only have a layer name in single layer views
+            let layers = graph.layers(["a", "b"]).unwrap();
+            assert!(graph.edges().layer_name().all(|l| l.is_err()));
+            assert!(layers.edges().layer_name().all(|l| l.is_err()));
+            assert!(layers.edge(2, 3).unwrap().layer_name().is_err());
+            assert_eq!(
+                graph
+                    .layers("b")
+                    .unwrap()
+                    .edges()
+                    .layer_name()
+                    .map(|l| l.unwrap())
+                    .collect::<Vec<_>>(),
+                ["b", "b"]
+            );
+            assert_eq!(
+                layers
+                    .edges()
+                    .explode_layers()
+                    .layer_name()
+                    .map(|l| l.unwrap())
+                    .collect::<Vec<_>>(),
+                ["a", "b", "b"]
+            );
+        });
+    }
+
     #[test]
     fn test_exploded_edge_multilayer() {
         let graph = Graph::new();
@@ -384,6 +418,16 @@ mod test_edge_view {
 
             assert!(graph.edge(1, 2).unwrap().layer_name().is_err());
             assert!(graph.edges().layer_name().all(|l| l.is_err()));
+            assert_eq!(
+                graph
+                    .layers("1")
+                    .unwrap()
+                    .edge(1, 2)
+                    .unwrap()
+                    .layer_name()
+                    .unwrap(),
+                "1"
+            );
             assert!(graph
                 .edge(1, 2)
                 .unwrap()
@@ -17,10 +17,16 @@ use crate::{
         graph::{
             edge::EdgeView,
             edges::Edges,
+            graph::Graph,
+            hyperedge::{
+                bipartite_projection, clique_projection, find_hyperedge, graph_hyperedges,
+                HyperedgeView,
+            },
             node::NodeView,
             nodes::Nodes,
             views::{
-                node_subgraph::NodeSubgraph, node_type_filtered_subgraph::TypeFilteredSubgraph,
+                intersection_graph::IntersectionGraph, node_subgraph::NodeSubgraph,
+                node_type_filtered_subgraph::TypeFilteredSubgraph, union_graph::UnionGraph,
             },
         },
     },
@@ -28,7 +34,10 @@ use crate::{
 };
 use chrono::{DateTime, Utc};
 use itertools::Itertools;
-use raphtory_api::core::storage::arc_str::{ArcStr, OptionAsStr};
+use raphtory_api::core::{
+    entities::GID,
+    storage::arc_str::{ArcStr, OptionAsStr},
+};
 use rayon::prelude::*;
 use rustc_hash::FxHashSet;
 use std::{borrow::Borrow, sync::Arc};
@@ -65,6 +74,21 @@ pub trait GraphViewOps<'graph>: BoxableGraphView + Sized + Clone + 'graph {
         nodes: I,
     ) -> NodeSubgraph<Self>;
 
+    /// View of the nodes and edges that are in this view or in `other`
+    ///
+    /// Both views need to be views of the same graph with the same time semantics and time
+    /// bounds, otherwise an error is returned. Properties are taken from this view.
+    fn union<O: GraphViewOps<'graph>>(&self, other: &O) -> Result<UnionGraph<Self, O>, GraphError>;
+
+    /// View of the nodes and edges that are in both this view and `other`
+    ///
+    /// Both views need to be views of the same graph with the same time semantics and time
+    /// bounds, otherwise an error is returned. Properties are taken from this view.
+    fn intersection<O: GraphViewOps<'graph>>(
+        &self,
+        other: &O,
+    ) -> Result<IntersectionGraph<Self, O>, GraphError>;
+
     /// Return all the layer ids in the graph
     fn unique_layers(&self) -> BoxedIter<ArcStr>;
     /// Timestamp of earliest activity in the graph
@@ -113,6 +137,30 @@ pub trait GraphViewOps<'graph>: BoxableGraphView + Sized + Clone + 'graph {
     ///
     /// A view of the properties of the graph
     fn properties(&self) -> Properties<Self>;
+
+    /// Get the changes that turn this view into `other`, see [GraphDiff].
+    fn diff<'other, O: GraphViewOps<'other>>(&self, other: &O) -> GraphDiff;
+
+    /// Return the hyperedges in the window of the graph that have at least one participant in the
+    /// graph
+    fn hyperedges(&self) -> Vec<HyperedgeView<Self>>;
+
+    /// Get the hyperedge with the given id if it is part of the graph
+    fn hyperedge<H: Into<GID>>(&self, id: H) -> Option<HyperedgeView<Self>>;
+
+    /// Return the number of hyperedges in the graph
+    fn count_hyperedges(&self) -> usize;
+
+    /// Project the hyperedges of the graph to a new bipartite graph.
+    ///
+    /// Each hyperedge becomes a node of type `"hyperedge"` with the properties of the hyperedge,
+    /// its participants are connected to it by an edge in the layer of their role.
+    /// Returns an error if the id of a hyperedge is also the id of a node.
+    fn bipartite_projection(&self) -> Result<Graph, GraphError>;
+
+    /// Project the hyperedges of the graph to a new graph where the participants of each
+    /// hyperedge are connected by a clique of edges with the properties of the hyperedge
+    fn clique_projection(&self) -> Result<Graph, GraphError>;
 }
 
 impl<'graph, G: BoxableGraphView + Sized + Clone + 'graph> GraphViewOps<'graph> for G {
@@ -228,6 +276,17 @@ impl<'graph, G: BoxableGraphView + Sized + Clone + 'graph> GraphViewOps<'graph>
         TypeFilteredSubgraph::new(self.clone(), r)
     }
 
+    fn union<O: GraphViewOps<'graph>>(&self, other: &O) -> Result<UnionGraph<Self, O>, GraphError> {
+        UnionGraph::new(self.clone(), other.clone())
+    }
+
+    fn intersection<O: GraphViewOps<'graph>>(
+        &self,
+        other: &O,
+    ) -> Result<IntersectionGraph<Self, O>, GraphError> {
+        IntersectionGraph::new(self.clone(), other.clone())
+    }
+
     fn exclude_nodes<I: IntoIterator<Item = V>, V: AsNodeRef>(&self, nodes: I) -> NodeSubgraph<G> {
         let _layer_ids = self.layer_ids();
 
@@ -446,6 +505,30 @@ impl<'graph, G: BoxableGraphView + Sized + Clone + 'graph> GraphViewOps<'graph>
     fn properties(&self) -> Properties<Self> {
         Properties::new(self.clone())
     }
+
+    fn diff<'other, O: GraphViewOps<'other>>(&self, other: &O) -> GraphDiff {
+        GraphDiff::between(self, other)
+    }
+
+    fn hyperedges(&self) -> Vec<HyperedgeView<Self>> {
+        graph_hyperedges(self)
+    }
+
+    fn hyperedge<H: Into<GID>>(&self, id: H) -> Option<HyperedgeView<Self>> {
+        find_hyperedge(self, &id.into())
+    }
+
+    fn count_hyperedges(&self) -> usize {
+        graph_hyperedges(self).len()
+    }
+
+    fn bipartite_projection(&self) -> Result<Graph, GraphError> {
+        bipartite_projection(self)
+    }
+
+    fn clique_projection(&self) -> Result<Graph, GraphError> {
+        clique_projection(self)
+    }
 }
 
 pub trait StaticGraphViewOps: for<'graph> GraphViewOps<'graph> + 'static {}
@@ -0,0 +1,69 @@
+use crate::{
+    core::{
+        entities::{edges::edge_ref::EdgeRef, VID},
+        Prop,
+    },
+    db::api::{properties::computed::ComputedProperties, view::internal::Base},
+};
+use enum_dispatch::enum_dispatch;
+
+/// Access to the computed properties registered on a view
+#[enum_dispatch]
+pub trait InternalComputedPropertiesOps {
+    /// The computed properties of the view, `None` if the view has no computed properties
+    fn computed_properties(&self) -> Option<&ComputedProperties>;
+
+    /// Evaluate the computed node property closure with index `index` for node `v` in the
+    /// window `start..end`
+    fn computed_node_prop(
+        &self,
+        v: VID,
+        index: usize,
+        start: Option<i64>,
+        end: Option<i64>,
+    ) -> Option<Prop>;
+
+    /// Evaluate the computed edge property closure with index `index` for edge `e` in the
+    /// window `start..end`
+    fn computed_edge_prop(
+        &self,
+        e: EdgeRef,
+        index: usize,
+        start: Option<i64>,
+        end: Option<i64>,
+    ) -> Option<Prop>;
+}
+
+pub trait InheritComputedPropertiesOps: Base {}
+
+impl<G: InheritComputedPropertiesOps> InternalComputedPropertiesOps for G
+where
+    G::Base: InternalComputedPropertiesOps,
+{
+    #[inline]
+    fn computed_properties(&self) -> Option<&ComputedProperties> {
+        self.base().computed_properties()
+    }
+
+    #[inline]
+    fn computed_node_prop(
+        &self,
+        v: VID,
+        index: usize,
+        start: Option<i64>,
+        end: Option<i64>,
+    ) -> Option<Prop> {
+        self.base().computed_node_prop(v, index, start, end)
+    }
+
+    #[inline]
+    fn computed_edge_prop(
+        &self,
+        e: EdgeRef,
+        index: usize,
+        start: Option<i64>,
+        end: Option<i64>,
+    ) -> Option<Prop> {
+        self.base().computed_edge_prop(e, index, start, end)
+    }
+}
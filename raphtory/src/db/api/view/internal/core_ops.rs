@@ -264,7 +264,7 @@ pub trait CoreGraphOps {
             GraphStorage::Mem(storage) => storage.graph.core_get_const_edge_prop(e, id, layer_ids),
             GraphStorage::Unlocked(storage) => storage.core_get_const_edge_prop(e, id, layer_ids),
             #[cfg(feature = "storage")]
-            GraphStorage::Disk(_) => None,
+            GraphStorage::Disk(storage) => storage.core_get_const_edge_prop(e, id, layer_ids),
         }
     }
 
@@ -286,7 +286,7 @@ pub trait CoreGraphOps {
             GraphStorage::Mem(storage) => storage.graph.core_const_edge_prop_ids(e, layer_ids),
             GraphStorage::Unlocked(storage) => storage.core_const_edge_prop_ids(e, layer_ids),
             #[cfg(feature = "storage")]
-            GraphStorage::Disk(_) => Box::new(std::iter::empty()),
+            GraphStorage::Disk(storage) => storage.core_const_edge_prop_ids(e, layer_ids),
         }
     }
 
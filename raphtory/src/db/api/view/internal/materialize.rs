@@ -50,6 +50,7 @@ use serde::{Deserialize, Serialize};
 #[enum_dispatch(TemporalPropertiesOps)]
 #[enum_dispatch(TemporalPropertyViewOps)]
 #[enum_dispatch(ConstPropertiesOps)]
+#[enum_dispatch(InternalComputedPropertiesOps)]
 #[enum_dispatch(InternalAdditionOps)]
 #[enum_dispatch(InternalPropertyAdditionOps)]
 #[derive(Serialize, Deserialize, Clone)]
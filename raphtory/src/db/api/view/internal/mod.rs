@@ -1,4 +1,5 @@
 #![allow(dead_code)]
+mod computed_props;
 mod core_deletion_ops;
 pub mod core_ops;
 mod edge_filter_ops;
@@ -22,6 +23,7 @@ use std::{
     sync::Arc,
 };
 
+pub use computed_props::*;
 pub use core_deletion_ops::*;
 pub use core_ops::*;
 pub use edge_filter_ops::*;
@@ -46,6 +48,7 @@ pub trait BoxableGraphView:
     + InternalMaterialize
     + PropertiesOps
     + ConstPropertiesOps
+    + InternalComputedPropertiesOps
     + Send
     + Sync
 {
@@ -61,6 +64,7 @@ impl<
             + InternalMaterialize
             + PropertiesOps
             + ConstPropertiesOps
+            + InternalComputedPropertiesOps
             + Send
             + Sync,
     > BoxableGraphView for G
@@ -80,6 +84,7 @@ impl<G: InheritViewOps + CoreGraphOps> InheritTimeSemantics for G {}
 impl<G: InheritViewOps> InheritCoreOps for G {}
 impl<G: InheritViewOps> InheritMaterialize for G {}
 impl<G: InheritViewOps> InheritPropertiesOps for G {}
+impl<G: InheritViewOps> InheritComputedPropertiesOps for G {}
 
 /// Trait for marking a struct as not dynamically dispatched.
 /// Used to avoid conflicts when implementing `From` for dynamic wrappers.
@@ -1,5 +1,7 @@
 //! Defines the `ViewApi` trait, which represents the API for querying a view of the graph.
 
+mod computed_props;
+mod diff;
 mod edge;
 mod graph;
 pub mod internal;
@@ -9,6 +11,8 @@ mod reset_filter;
 pub(crate) mod time;
 
 pub(crate) use edge::BaseEdgeViewOps;
+pub use computed_props::*;
+pub use diff::*;
 pub use edge::EdgeViewOps;
 
 pub use graph::*;
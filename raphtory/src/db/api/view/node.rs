@@ -4,14 +4,17 @@ use crate::{
         storage::timeindex::AsTime,
         Direction,
     },
-    db::api::{
-        properties::{internal::PropertiesOps, Properties},
-        storage::graph::{nodes::node_storage_ops::NodeStorageOps, storage_ops::GraphStorage},
-        view::{
-            internal::{CoreGraphOps, OneHopFilter, TimeSemantics},
-            reset_filter::ResetFilter,
-            TimeOps,
+    db::{
+        api::{
+            properties::{internal::PropertiesOps, Properties},
+            storage::graph::{nodes::node_storage_ops::NodeStorageOps, storage_ops::GraphStorage},
+            view::{
+                internal::{CoreGraphOps, OneHopFilter, TimeSemantics},
+                reset_filter::ResetFilter,
+                TimeOps,
+            },
         },
+        graph::hyperedge::{hyperedge_neighbours, node_hyperedges, HyperedgeView},
     },
     prelude::{EdgeViewOps, GraphViewOps, LayerOps},
 };
@@ -167,6 +170,27 @@ pub trait NodeViewOps<'graph>: Clone + TimeOps<'graph> + LayerOps<'graph> {
     ///
     /// An iterator over the neighbours of this node that point out of this node.
     fn out_neighbours(&self) -> Self::PathType;
+
+    /// Get the hyperedges this node takes part in.
+    ///
+    /// Returns:
+    ///
+    /// The hyperedges in the window of the graph that include this node.
+    fn hyperedges(&self) -> Self::ValueType<Vec<HyperedgeView<Self::Graph>>>;
+
+    /// Get the number of hyperedges this node takes part in.
+    ///
+    /// Returns:
+    ///
+    /// The hyperedge degree of this node.
+    fn hyperedge_degree(&self) -> Self::ValueType<usize>;
+
+    /// Get the nodes that share at least one hyperedge with this node.
+    ///
+    /// Returns:
+    ///
+    /// An iterator over the distinct co-participants of this node.
+    fn hyperedge_neighbours(&self) -> Self::PathType;
 }
 
 impl<'graph, V: BaseNodeViewOps<'graph> + 'graph> NodeViewOps<'graph> for V {
@@ -282,6 +306,18 @@ impl<'graph, V: BaseNodeViewOps<'graph> + 'graph> NodeViewOps<'graph> for V {
                 .into_node_neighbours_iter(v, Direction::OUT, g.clone())
         })
     }
+    #[inline]
+    fn hyperedges(&self) -> Self::ValueType<Vec<HyperedgeView<Self::Graph>>> {
+        self.map(|cg, g, v| node_hyperedges(cg, g, v))
+    }
+    #[inline]
+    fn hyperedge_degree(&self) -> Self::ValueType<usize> {
+        self.map(|cg, g, v| node_hyperedges(cg, g, v).len())
+    }
+    #[inline]
+    fn hyperedge_neighbours(&self) -> Self::PathType {
+        self.hop(|cg, g, v| hyperedge_neighbours(cg, g, v).into_iter())
+    }
 }
 
 impl<'graph, V: BaseNodeViewOps<'graph> + OneHopFilter<'graph>> ResetFilter<'graph> for V {}
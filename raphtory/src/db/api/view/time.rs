@@ -1,7 +1,7 @@
 use crate::{
     core::{
         storage::timeindex::AsTime,
-        utils::time::{error::ParseTimeError, Interval, IntoTime},
+        utils::time::{error::ParseTimeError, parse_timezone, Interval, IntoTime},
     },
     db::{
         api::view::{
@@ -211,25 +211,50 @@ impl<'graph, V: OneHopFilter<'graph> + 'graph> TimeOps<'graph> for V {
     }
 }
 
+/// Where the boundaries of the windows of a [WindowSet] are placed
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum Alignment {
+    /// The first window starts at the start of the timeline
+    #[default]
+    Unaligned,
+    /// Windows are aligned to the calendar in the time zone of the step, e.g., daily windows
+    /// start at midnight, weekly windows on Mondays and quarterly windows at the start of a
+    /// quarter
+    Calendar,
+    /// Windows are aligned to this time, e.g., monthly windows anchored on the 5th of a month
+    /// start on the 5th of every month
+    Anchor(i64),
+}
+
 #[derive(Clone)]
 pub struct WindowSet<'graph, T> {
     view: T,
-    cursor: i64,
+    start: i64,
     end: i64,
     step: Interval,
     window: Option<Interval>,
+    alignment: Alignment,
+    /// The end of the next window, `None` if it is outside the range of supported datetimes
+    cursor: Option<i64>,
+    /// Aligned windows and windows in a time zone end `index` steps after the anchor instead of
+    /// one step after the previous window, so that steps of months don't drift
+    anchor: Option<i64>,
+    index: i64,
     _marker: PhantomData<&'graph T>,
 }
 
 impl<'graph, T: TimeOps<'graph> + Clone + 'graph> WindowSet<'graph, T> {
     fn new(view: T, start: i64, end: i64, step: Interval, window: Option<Interval>) -> Self {
-        let cursor_start = start + step;
         Self {
             view,
-            cursor: cursor_start,
+            start,
             end,
             step,
             window,
+            alignment: Alignment::Unaligned,
+            cursor: step.add_to(start).ok(),
+            anchor: None,
+            index: 1,
             _marker: PhantomData,
         }
     }
@@ -239,6 +264,79 @@ impl<'graph, T: TimeOps<'graph> + Clone + 'graph> WindowSet<'graph, T> {
         WindowSet::new(view, 1, 0, Default::default(), None)
     }
 
+    /// Move the windows so their boundaries follow `alignment`. The first window is the first
+    /// one that ends after the start of the timeline.
+    pub fn aligned(mut self, alignment: Alignment) -> Self {
+        self.alignment = alignment;
+        self.reset();
+        self
+    }
+
+    /// Step and size the windows in the wall clock time of the time zone `timezone` (e.g.,
+    /// "Europe/London"), so daily windows go from midnight to midnight also across daylight
+    /// saving time transitions
+    pub fn with_timezone(mut self, timezone: &str) -> Result<Self, ParseTimeError> {
+        // validate the time zone also if there are no windows
+        parse_timezone(timezone)?;
+        if self.start < self.end {
+            self.step = self.step.with_timezone(timezone)?;
+            self.window = self
+                .window
+                .map(|window| window.with_timezone(timezone))
+                .transpose()?;
+            self.reset();
+        }
+        Ok(self)
+    }
+
+    /// Apply the optional `timezone` and `alignment` of the windows, see
+    /// [WindowSet::with_timezone] and [WindowSet::aligned]
+    pub fn with_options(
+        self,
+        timezone: Option<&str>,
+        alignment: Option<Alignment>,
+    ) -> Result<Self, ParseTimeError> {
+        let windows = match timezone {
+            Some(timezone) => self.with_timezone(timezone)?,
+            None => self,
+        };
+        Ok(match alignment {
+            Some(alignment) => windows.aligned(alignment),
+            None => windows,
+        })
+    }
+
+    /// The anchor of the windows and the index of the first window
+    fn first_window(&self) -> Result<(i64, i64), ParseTimeError> {
+        let anchor = match self.alignment {
+            Alignment::Unaligned => self.start,
+            Alignment::Calendar => self.step.calendar_anchor()?,
+            Alignment::Anchor(anchor) => anchor,
+        };
+        Ok((anchor, self.step.floor_index(anchor, self.start)? + 1))
+    }
+
+    fn reset(&mut self) {
+        if self.start >= self.end {
+            return;
+        }
+        if self.alignment == Alignment::Unaligned && self.step.timezone().is_none() {
+            // every window ends one step after the previous one
+            self.anchor = None;
+            self.cursor = self.step.add_to(self.start).ok();
+            return;
+        }
+        match self.first_window() {
+            Ok((anchor, index)) => {
+                self.anchor = Some(anchor);
+                self.index = index;
+                self.cursor = self.step.nth_from(anchor, index).ok();
+            }
+            // the windows can't be placed in the range of supported datetimes
+            Err(_) => *self = WindowSet::empty(self.view.clone()),
+        }
+    }
+
     // TODO: make this optionally public only for the development feature flag
     pub fn temporal(&self) -> bool {
         self.step.epoch_alignment
@@ -280,27 +378,38 @@ impl<'graph, T: TimeOps<'graph> + Clone + 'graph> Iterator for TimeIndex<'graph,
 impl<'graph, T: TimeOps<'graph> + Clone + 'graph> Iterator for WindowSet<'graph, T> {
     type Item = T::WindowedViewType;
     fn next(&mut self) -> Option<Self::Item> {
-        if self.cursor < self.end + self.step {
-            let window_end = self.cursor;
-            let window_start = self.window.map(|w| window_end - w);
-            let window = self.view.internal_window(window_start, Some(window_end));
-            self.cursor = self.cursor + self.step;
-            Some(window)
-        } else {
-            None
+        let window_end = self.cursor?;
+        // an end that is out of range is after every window
+        if self
+            .step
+            .add_to(self.end)
+            .is_ok_and(|last_end| window_end >= last_end)
+        {
+            return None;
         }
+        // a start that is out of range is before every event
+        let window_start = self.window.and_then(|w| w.sub_from(window_end).ok());
+        let window = self.view.internal_window(window_start, Some(window_end));
+        self.cursor = match self.anchor {
+            Some(anchor) => {
+                self.index += 1;
+                self.step.nth_from(anchor, self.index).ok()
+            }
+            None => self.step.add_to(window_end).ok(),
+        };
+        Some(window)
     }
 }
 
 #[cfg(test)]
 mod time_tests {
     use crate::{
-        core::utils::time::TryIntoTime,
+        core::utils::time::{error::ParseTimeError, TryIntoTime},
         db::{
             api::{
                 mutation::AdditionOps,
                 view::{
-                    time::{internal::InternalTimeOps, WindowSet},
+                    time::{internal::InternalTimeOps, Alignment, WindowSet},
                     TimeOps,
                 },
             },
@@ -465,4 +574,123 @@ mod time_tests {
             assert_bounds(windows, expected);
         });
     }
+
+    #[test]
+    fn calendar_aligned_windows_in_timezone() {
+        let start = "2024-03-30T12:00:00+00:00".try_into_time().unwrap();
+        let end = "2024-04-01T12:00:00+01:00".try_into_time().unwrap();
+        let graph = graph_with_timeline(start, end);
+        test_storage!(&graph, |graph| {
+            // the day the clocks go forward in London only has 23 hours
+            let windows = graph
+                .rolling("1 day", None)
+                .unwrap()
+                .with_timezone("Europe/London")
+                .unwrap()
+                .aligned(Alignment::Calendar);
+            let expected = vec![
+                (
+                    "2024-03-30T00:00:00+00:00".try_into_time().ok(),
+                    "2024-03-31T00:00:00+00:00".try_into_time().ok(),
+                ),
+                (
+                    "2024-03-31T00:00:00+00:00".try_into_time().ok(),
+                    "2024-04-01T00:00:00+01:00".try_into_time().ok(),
+                ),
+                (
+                    "2024-04-01T00:00:00+01:00".try_into_time().ok(),
+                    "2024-04-02T00:00:00+01:00".try_into_time().ok(),
+                ),
+            ];
+            assert_bounds(windows, expected);
+        });
+
+        let start = "2024-01-03 12:00:00".try_into_time().unwrap();
+        let end = "2024-01-10 00:00:00".try_into_time().unwrap();
+        let graph = graph_with_timeline(start, end);
+        test_storage!(&graph, |graph| {
+            // ISO weeks start on Mondays
+            let windows = graph
+                .expanding("1 week")
+                .unwrap()
+                .aligned(Alignment::Calendar);
+            let expected = vec![
+                (None, "2024-01-08 00:00:00".try_into_time().ok()),
+                (None, "2024-01-15 00:00:00".try_into_time().ok()),
+            ];
+            assert_bounds(windows, expected);
+        });
+    }
+
+    #[test]
+    fn anchored_windows() {
+        let start = "2024-01-10 00:00:00".try_into_time().unwrap();
+        let end = "2024-03-01 00:00:00".try_into_time().unwrap();
+        let graph = graph_with_timeline(start, end);
+        test_storage!(&graph, |graph| {
+            let anchor = "2024-01-05 00:00:00".try_into_time().unwrap();
+            let windows = graph
+                .rolling("1 month", None)
+                .unwrap()
+                .aligned(Alignment::Anchor(anchor));
+            let expected = vec![
+                (
+                    "2024-01-05 00:00:00".try_into_time().ok(),
+                    "2024-02-05 00:00:00".try_into_time().ok(),
+                ),
+                (
+                    "2024-02-05 00:00:00".try_into_time().ok(),
+                    "2024-03-05 00:00:00".try_into_time().ok(),
+                ),
+            ];
+            assert_bounds(windows, expected);
+        });
+    }
+
+    #[test]
+    fn unaligned_month_steps_are_cumulative() {
+        let start = "2024-01-31 00:00:00".try_into_time().unwrap();
+        let end = "2024-04-15 00:00:00".try_into_time().unwrap();
+        let graph = graph_with_timeline(start, end);
+        test_storage!(&graph, |graph| {
+            // without alignment or time zone every window ends one month after the previous one
+            let windows = graph.expanding("1 month").unwrap();
+            let expected = vec![
+                (None, "2024-02-29 00:00:00".try_into_time().ok()),
+                (None, "2024-03-29 00:00:00".try_into_time().ok()),
+                (None, "2024-04-29 00:00:00".try_into_time().ok()),
+            ];
+            assert_bounds(windows, expected);
+
+            // anchored windows are a whole number of months after the anchor
+            let windows = graph
+                .expanding("1 month")
+                .unwrap()
+                .aligned(Alignment::Anchor(start));
+            let expected = vec![
+                (None, "2024-02-29 00:00:00".try_into_time().ok()),
+                (None, "2024-03-31 00:00:00".try_into_time().ok()),
+                (None, "2024-04-30 00:00:00".try_into_time().ok()),
+            ];
+            assert_bounds(windows, expected);
+        });
+    }
+
+    #[test]
+    fn invalid_timezone_without_windows() {
+        let graph = Graph::new();
+        let windows = graph.rolling("1 day", None).unwrap();
+        assert_eq!(
+            windows.clone().with_timezone("Mars/Olympus").err(),
+            Some(ParseTimeError::InvalidTimezone("Mars/Olympus".to_string()))
+        );
+        assert_eq!(
+            windows
+                .with_timezone("Europe/London")
+                .unwrap()
+                .map(|w| (w.start(), w.end()))
+                .count(),
+            0
+        );
+    }
 }
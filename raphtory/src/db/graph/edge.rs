@@ -20,6 +20,7 @@ use crate::{
                 time_from_input, CollectProperties, TryIntoInputTime,
             },
             properties::{
+                computed::{computed_prop_id, computed_prop_index, ComputedEdgeProp, ComputedProp},
                 internal::{ConstPropertiesOps, TemporalPropertiesOps, TemporalPropertyViewOps},
                 Properties,
             },
@@ -29,10 +30,11 @@ use crate::{
                 BaseEdgeViewOps, IntoDynBoxed, StaticGraphViewOps,
             },
         },
-        graph::{edges::Edges, node::NodeView},
+        graph::{edges::Edges, node::NodeView, views::deletion_graph::edge_alive_intervals},
     },
     prelude::*,
 };
+use itertools::Itertools;
 use raphtory_api::core::storage::arc_str::ArcStr;
 use std::{
     fmt::{Debug, Formatter},
@@ -274,34 +276,94 @@ impl<G: StaticGraphViewOps + InternalPropertyAdditionOps + InternalAdditionOps>
     }
 }
 
+impl<'graph, G: GraphViewOps<'graph>, GH: GraphViewOps<'graph>> EdgeView<G, GH> {
+    /// The computed property with `name` if it is registered on the view
+    fn computed_prop(&self, name: &str) -> Option<(usize, &ComputedEdgeProp)> {
+        let props = self.graph.computed_properties()?;
+        let index = props.edge_prop_index(name)?;
+        Some((index, &props.edge_prop(index).1))
+    }
+
+    fn computed_prop_by_id(&self, id: usize) -> Option<(usize, &(ArcStr, ComputedEdgeProp))> {
+        let index = computed_prop_index(id)?;
+        Some((index, self.graph.computed_properties()?.edge_prop(index)))
+    }
+
+    /// Stored properties with the same name as a computed property are hidden
+    fn is_shadowed(&self, name: &str) -> bool {
+        self.computed_prop(name).is_some()
+    }
+
+    fn computed_prop_ids(&self, temporal: bool) -> impl Iterator<Item = usize> + '_ {
+        self.graph
+            .computed_properties()
+            .into_iter()
+            .flat_map(move |props| {
+                (0..props.num_edge_props())
+                    .filter(move |index| props.edge_prop(*index).1.is_temporal(self) == temporal)
+            })
+            .map(computed_prop_id)
+    }
+
+    fn temporal_prop_vec(&self, id: usize) -> Vec<(i64, Prop)> {
+        match self.computed_prop_by_id(id) {
+            Some((_, (_, prop))) => prop.temporal_updates(self),
+            None => self
+                .graph
+                .temporal_edge_prop_vec(self.edge, id, &self.layer_ids()),
+        }
+    }
+}
+
 impl<'graph, G: GraphViewOps<'graph>, GH: GraphViewOps<'graph>> ConstPropertiesOps
     for EdgeView<G, GH>
 {
     fn get_const_prop_id(&self, name: &str) -> Option<usize> {
+        if let Some((index, prop)) = self.computed_prop(name) {
+            return (!prop.is_temporal(self)).then(|| computed_prop_id(index));
+        }
         self.graph.edge_meta().const_prop_meta().get_id(name)
     }
 
     fn get_const_prop_name(&self, id: usize) -> ArcStr {
-        self.graph
-            .edge_meta()
-            .const_prop_meta()
-            .get_name(id)
-            .clone()
+        match self.computed_prop_by_id(id) {
+            Some((_, (name, _))) => name.clone(),
+            None => self
+                .graph
+                .edge_meta()
+                .const_prop_meta()
+                .get_name(id)
+                .clone(),
+        }
     }
 
     fn const_prop_ids(&self) -> Box<dyn Iterator<Item = usize> + '_> {
-        self.graph
-            .const_edge_prop_ids(self.edge, self.graph.layer_ids().clone())
-    }
-
-    fn const_prop_keys(&self) -> Box<dyn Iterator<Item = ArcStr> + '_> {
-        let reverse_map = self.graph.edge_meta().const_prop_meta().get_keys();
-        Box::new(self.const_prop_ids().map(move |id| reverse_map[id].clone()))
+        Box::new(
+            self.graph
+                .const_edge_prop_ids(self.edge, self.graph.layer_ids().clone())
+                .filter(|id| !self.is_shadowed(&self.get_const_prop_name(*id)))
+                .chain(
+                    self.computed_prop_ids(false)
+                        .filter(|id| self.get_const_prop(*id).is_some()),
+                ),
+        )
     }
 
     fn get_const_prop(&self, id: usize) -> Option<Prop> {
-        self.graph
-            .get_const_edge_prop(self.edge, id, self.graph.layer_ids().clone())
+        match self.computed_prop_by_id(id) {
+            Some((index, (_, prop))) => match prop {
+                ComputedProp::Expr(_) => prop.const_expr_value(self),
+                ComputedProp::Fn { .. } => self.graph.computed_edge_prop(
+                    self.edge,
+                    index,
+                    self.graph.view_start(),
+                    self.graph.view_end(),
+                ),
+            },
+            None => self
+                .graph
+                .get_const_edge_prop(self.edge, id, self.graph.layer_ids().clone()),
+        }
     }
 }
 
@@ -309,24 +371,20 @@ impl<'graph, G: GraphViewOps<'graph>, GH: GraphViewOps<'graph>> TemporalProperty
     for EdgeView<G, GH>
 {
     fn temporal_history(&self, id: usize) -> Vec<i64> {
-        self.graph
-            .temporal_edge_prop_vec(self.edge, id, &self.layer_ids())
+        self.temporal_prop_vec(id)
             .into_iter()
             .map(|(t, _)| t)
             .collect()
     }
     fn temporal_history_date_time(&self, id: usize) -> Option<Vec<DateTime<Utc>>> {
-        self.graph
-            .temporal_edge_prop_vec(self.edge, id, &self.layer_ids())
+        self.temporal_prop_vec(id)
             .into_iter()
             .map(|(t, _)| t.dt())
             .collect()
     }
 
     fn temporal_values(&self, id: usize) -> Vec<Prop> {
-        let layer_ids = self.layer_ids();
-        self.graph
-            .temporal_edge_prop_vec(self.edge, id, &layer_ids)
+        self.temporal_prop_vec(id)
             .into_iter()
             .map(|(_, v)| v)
             .collect()
@@ -337,6 +395,9 @@ impl<'graph, G: GraphViewOps<'graph>, GH: GraphViewOps<'graph>> TemporalProperti
     for EdgeView<G, GH>
 {
     fn get_temporal_prop_id(&self, name: &str) -> Option<usize> {
+        if let Some((index, prop)) = self.computed_prop(name) {
+            return prop.is_temporal(self).then(|| computed_prop_id(index));
+        }
         let layer_ids = self.layer_ids();
         self.graph
             .edge_meta()
@@ -349,11 +410,15 @@ impl<'graph, G: GraphViewOps<'graph>, GH: GraphViewOps<'graph>> TemporalProperti
     }
 
     fn get_temporal_prop_name(&self, id: usize) -> ArcStr {
-        self.graph
-            .edge_meta()
-            .temporal_prop_meta()
-            .get_name(id)
-            .clone()
+        match self.computed_prop_by_id(id) {
+            Some((_, (name, _))) => name.clone(),
+            None => self
+                .graph
+                .edge_meta()
+                .temporal_prop_meta()
+                .get_name(id)
+                .clone(),
+        }
     }
 
     fn temporal_prop_ids(&self) -> Box<dyn Iterator<Item = usize> + '_> {
@@ -364,15 +429,9 @@ impl<'graph, G: GraphViewOps<'graph>, GH: GraphViewOps<'graph>> TemporalProperti
                 .filter(move |id| {
                     self.graph
                         .has_temporal_edge_prop(self.edge, *id, &layer_ids)
-                }),
-        )
-    }
-
-    fn temporal_prop_keys(&self) -> Box<dyn Iterator<Item = ArcStr> + '_> {
-        let reverse_map = self.graph.edge_meta().temporal_prop_meta().get_keys();
-        Box::new(
-            self.temporal_prop_ids()
-                .map(move |id| reverse_map[id].clone()),
+                        && !self.is_shadowed(&self.get_temporal_prop_name(*id))
+                })
+                .chain(self.computed_prop_ids(true)),
         )
     }
 }
@@ -412,6 +471,206 @@ impl<'graph, G: GraphViewOps<'graph>, GH: GraphViewOps<'graph>> OneHopFilter<'gr
     }
 }
 
+impl<'graph, G: GraphViewOps<'graph>, GH: GraphViewOps<'graph>> EdgeView<G, GH> {
+    /// The properties of the edge in the layer `name` only, `None` if the edge has no updates in
+    /// that layer in the current view
+    pub fn layer_properties(&self, name: &str) -> Option<Properties<Self>> {
+        let layer = self.graph.get_layer_id(name)?;
+        self.explode_layers()
+            .iter()
+            .find(|e| e.edge.layer() == Some(&layer))
+            .map(|e| e.properties())
+    }
+
+    /// Merge the properties of the edge in `layers`, where earlier layers take precedence over
+    /// later ones. `Layer::All` merges all layers of the view in the order they were created.
+    /// Layers the edge has no updates in are skipped, unknown layers are an error.
+    pub fn merge_layers<L: Into<Layer>>(
+        &self,
+        layers: L,
+    ) -> Result<LayerMergedEdge<G, GH>, GraphError> {
+        let names: Vec<ArcStr> = match layers.into() {
+            Layer::All => self.graph.unique_layers().collect(),
+            Layer::None => vec![],
+            Layer::Default => vec!["_default".into()],
+            Layer::One(name) => vec![name],
+            Layer::Multiple(names) => names.to_vec(),
+        };
+        let edge_layers: Vec<_> = self.explode_layers().iter().collect();
+        let mut layers = Vec::with_capacity(names.len());
+        for name in names {
+            let id = self.graph.get_layer_id(&name).ok_or_else(|| {
+                GraphError::invalid_layer(
+                    name.to_string(),
+                    self.graph.unique_layers().map(|l| l.to_string()).collect(),
+                )
+            })?;
+            layers.extend(
+                edge_layers
+                    .iter()
+                    .find(|e| e.edge.layer() == Some(&id))
+                    .cloned(),
+            );
+        }
+        Ok(LayerMergedEdge {
+            edge: self.clone(),
+            layers: layers.into(),
+        })
+    }
+
+    /// The total time the edge is alive in the current view, following the semantics of
+    /// persistent graphs (i.e., from an addition until the next deletion). For an exploded edge
+    /// only the time from its update onwards is counted.
+    ///
+    /// Returns `None` if the edge stays alive without bound.
+    pub fn duration(&self) -> Option<i64> {
+        let layer_ids = self.graph.layer_ids().constrain_from_edge(self.edge);
+        let start = self
+            .edge
+            .time_t()
+            .or_else(|| self.graph.view_start())
+            .unwrap_or(i64::MIN);
+        let end = self.graph.view_end().unwrap_or(i64::MAX);
+        let edge = self.graph.core_edge(self.edge.into());
+        edge_alive_intervals(edge.as_ref(), &layer_ids)
+            .into_iter()
+            .try_fold(0i64, |total, alive| {
+                let (alive_start, alive_end) = (alive.start.max(start), alive.end.min(end));
+                if alive_start >= alive_end {
+                    Some(total)
+                } else if alive_start == i64::MIN || alive_end == i64::MAX {
+                    None
+                } else {
+                    total.checked_add(alive_end - alive_start)
+                }
+            })
+    }
+}
+
+/// The properties of an edge merged across several of its layers, see [EdgeView::merge_layers].
+///
+/// Constant properties are taken from the first layer that has them. Temporal properties combine
+/// the updates of all merged layers, and updates at the same time are ordered such that the
+/// layer with the highest precedence comes last, i.e., its value is the one seen at that time.
+#[derive(Clone)]
+pub struct LayerMergedEdge<G, GH = G> {
+    edge: EdgeView<G, GH>,
+    /// The edge restricted to each of the merged layers, from highest to lowest precedence
+    layers: Arc<[EdgeView<G, GH>]>,
+}
+
+impl<'graph, G: GraphViewOps<'graph>, GH: GraphViewOps<'graph>> LayerMergedEdge<G, GH> {
+    /// The edge whose layers are merged
+    pub fn edge(&self) -> &EdgeView<G, GH> {
+        &self.edge
+    }
+
+    /// The names of the merged layers the edge has updates in, from highest to lowest precedence
+    pub fn layer_names(&self) -> Vec<ArcStr> {
+        self.layers
+            .iter()
+            .flat_map(|layer| layer.layer_name())
+            .collect()
+    }
+
+    pub fn properties(&self) -> Properties<Self> {
+        Properties::new(self.clone())
+    }
+
+    /// The updates of the temporal property `id` of each layer are already sorted by time, so
+    /// merge them in order, with updates of higher precedence layers last at the same time
+    fn temporal_updates(&self, id: usize) -> impl Iterator<Item = (i64, Prop)> + '_ {
+        self.layers
+            .iter()
+            .enumerate()
+            .map(|(rank, layer)| {
+                layer
+                    .temporal_history(id)
+                    .into_iter()
+                    .zip(layer.temporal_values(id))
+                    .map(move |(t, value)| (t, rank, value))
+            })
+            .kmerge_by(|(t1, rank1, _), (t2, rank2, _)| (t1, rank2) < (t2, rank1))
+            .map(|(t, _, value)| (t, value))
+    }
+}
+
+impl<'graph, G: GraphViewOps<'graph>, GH: GraphViewOps<'graph>> ConstPropertiesOps
+    for LayerMergedEdge<G, GH>
+{
+    fn get_const_prop_id(&self, name: &str) -> Option<usize> {
+        self.edge.get_const_prop_id(name)
+    }
+
+    fn get_const_prop_name(&self, id: usize) -> ArcStr {
+        self.edge.get_const_prop_name(id)
+    }
+
+    fn const_prop_ids(&self) -> Box<dyn Iterator<Item = usize> + '_> {
+        Box::new(
+            self.layers
+                .iter()
+                .flat_map(|layer| layer.const_prop_ids())
+                .unique(),
+        )
+    }
+
+    fn get_const_prop(&self, id: usize) -> Option<Prop> {
+        self.layers
+            .iter()
+            .find_map(|layer| layer.get_const_prop(id))
+    }
+}
+
+impl<'graph, G: GraphViewOps<'graph>, GH: GraphViewOps<'graph>> TemporalPropertyViewOps
+    for LayerMergedEdge<G, GH>
+{
+    fn temporal_history(&self, id: usize) -> Vec<i64> {
+        self.temporal_updates(id).map(|(t, _)| t).collect()
+    }
+
+    fn temporal_values(&self, id: usize) -> Vec<Prop> {
+        self.temporal_updates(id).map(|(_, value)| value).collect()
+    }
+
+    fn temporal_value_at(&self, id: usize, t: i64) -> Option<Prop> {
+        // the latest update of each layer, ties go to the layer with the highest precedence
+        self.layers
+            .iter()
+            .filter_map(|layer| {
+                let history = layer.temporal_history(id);
+                let index = history.partition_point(|ti| *ti <= t);
+                (index > 0).then(|| (history[index - 1], layer))
+            })
+            .rev()
+            .max_by_key(|(ti, _)| *ti)
+            .and_then(|(_, layer)| layer.temporal_value_at(id, t))
+    }
+}
+
+impl<'graph, G: GraphViewOps<'graph>, GH: GraphViewOps<'graph>> TemporalPropertiesOps
+    for LayerMergedEdge<G, GH>
+{
+    fn get_temporal_prop_id(&self, name: &str) -> Option<usize> {
+        self.layers
+            .iter()
+            .find_map(|layer| layer.get_temporal_prop_id(name))
+    }
+
+    fn get_temporal_prop_name(&self, id: usize) -> ArcStr {
+        self.edge.get_temporal_prop_name(id)
+    }
+
+    fn temporal_prop_ids(&self) -> Box<dyn Iterator<Item = usize> + '_> {
+        Box::new(
+            self.layers
+                .iter()
+                .flat_map(|layer| layer.temporal_prop_ids())
+                .unique(),
+        )
+    }
+}
+
 #[cfg(test)]
 mod test_edge {
     use crate::{core::IntoPropMap, prelude::*, test_storage, test_utils::test_graph};
@@ -450,8 +709,7 @@ mod test_edge {
             .add_constant_properties([("test_prop", "test_val")], Some("layer 2"))
             .unwrap();
 
-        // FIXME: multilayer edge views are not supported yet (Issue #47)
-        test_graph(&graph, |graph| {
+        test_storage!(&graph, |graph| {
             assert_eq!(
                 graph
                     .edge(1, 2)
@@ -552,4 +810,75 @@ mod test_edge {
         let e = g.add_edge(1, 1, 2, NO_PROPS, Some("test")).unwrap();
         assert_eq!(e.earliest_time(), Some(1));
     }
+
+    #[test]
+    fn test_layer_properties_and_merged_layers() {
+        let graph = Graph::new();
+        graph
+            .add_edge(0, 1, 2, [("weight", 1i64)], Some("a"))
+            .unwrap();
+        graph
+            .add_edge(1, 1, 2, [("weight", 2i64)], Some("b"))
+            .unwrap();
+        let edge = graph
+            .add_edge(1, 1, 2, [("weight", 3i64)], Some("a"))
+            .unwrap();
+        edge.add_constant_properties([("name", "from a")], Some("a"))
+            .unwrap();
+        edge.add_constant_properties([("name", "from b")], Some("b"))
+            .unwrap();
+
+        test_storage!(&graph, |graph| {
+            let edge = graph.edge(1, 2).unwrap();
+            assert_eq!(
+                edge.layer_properties("b").unwrap().get("weight"),
+                Some(Prop::I64(2))
+            );
+            assert_eq!(
+                edge.layer_properties("a").unwrap().get("name"),
+                Some(Prop::str("from a"))
+            );
+            assert!(edge.layer_properties("_default").is_none());
+            assert!(edge.layer_properties("missing").is_none());
+
+            let merged = edge.merge_layers(["b", "a"]).unwrap();
+            assert_eq!(merged.layer_names(), ["b", "a"]);
+            assert_eq!(merged.properties().get("name"), Some(Prop::str("from b")));
+            let weight = merged.properties().temporal().get("weight").unwrap();
+            assert_eq!(
+                weight.iter().collect_vec(),
+                [(0, Prop::I64(1)), (1, Prop::I64(3)), (1, Prop::I64(2))]
+            );
+            assert_eq!(weight.at(1), Some(Prop::I64(2)));
+            assert_eq!(
+                edge.merge_layers(["a", "b"])
+                    .unwrap()
+                    .properties()
+                    .get("weight"),
+                Some(Prop::I64(3))
+            );
+
+            // layers the edge has no updates in are skipped, unknown layers are an error
+            assert_eq!(
+                edge.merge_layers(["_default", "a"]).unwrap().layer_names(),
+                ["a"]
+            );
+            assert!(edge.merge_layers(["missing"]).is_err());
+
+            let windowed = graph.window(0, 1).edge(1, 2).unwrap();
+            let merged = windowed.merge_layers(["b", "a"]).unwrap();
+            assert_eq!(merged.layer_names(), ["a"]);
+            assert_eq!(merged.properties().get("weight"), Some(Prop::I64(1)));
+        });
+
+        let graph = graph.persistent_graph();
+        let merged = graph
+            .window(2, 3)
+            .edge(1, 2)
+            .unwrap()
+            .merge_layers(Layer::All)
+            .unwrap();
+        assert_eq!(merged.layer_names(), ["a", "b"]);
+        assert_eq!(merged.properties().get("weight"), Some(Prop::I64(3)));
+    }
 }
@@ -18,8 +18,12 @@
 
 use super::views::deletion_graph::PersistentGraph;
 use crate::{
+    core::utils::errors::GraphError,
     db::api::{
-        mutation::internal::InheritMutationOps,
+        mutation::{
+            internal::InheritMutationOps, merge::merge_graphs, MergePolicy, NodeMergeOps,
+            RemovalOps,
+        },
         storage::{graph::storage_ops::GraphStorage, storage::Storage},
         view::internal::{Base, InheritViewOps, Static},
     },
@@ -158,6 +162,10 @@ impl Base for Graph {
 
 impl InheritMutationOps for Graph {}
 
+impl RemovalOps for Graph {}
+
+impl NodeMergeOps for Graph {}
+
 impl InheritViewOps for Graph {}
 
 impl Graph {
@@ -203,6 +211,21 @@ impl Graph {
     pub fn persistent_graph(&self) -> PersistentGraph {
         PersistentGraph::from_storage(self.inner.clone())
     }
+
+    /// Merge this graph with `other` into a new graph
+    ///
+    /// Nodes are matched by id and edges by the ids of their endpoints, combining the histories
+    /// and layers of both graphs. Conflicting node types and constant property values are
+    /// resolved using `policy`, where this graph is the left and `other` the right side.
+    pub fn merge<'graph, G: GraphViewOps<'graph>>(
+        &self,
+        other: &G,
+        policy: &MergePolicy,
+    ) -> Result<Graph, GraphError> {
+        let merged = Graph::new();
+        merge_graphs(&merged, self, other, policy)?;
+        Ok(merged)
+    }
 }
 
 #[cfg(test)]
@@ -1026,8 +1049,7 @@ mod db_tests {
         graph.add_edge(0, 11, 33, NO_PROPS, Some("layer2"))?;
         graph.add_edge(0, 11, 44, NO_PROPS, Some("layer2"))?;
 
-        // FIXME: needs multilayer support (Issue #47)
-        test_graph(&graph, |graph| {
+        test_storage!(&graph, |graph| {
             assert!(graph.has_edge(11, 22));
             assert!(graph.default_layer().has_edge(11, 22));
             assert!(!graph.default_layer().has_edge(11, 44));
@@ -1724,8 +1746,7 @@ mod db_tests {
         graph.add_edge(0, 0, 1, NO_PROPS, None)?;
         graph.add_edge(0, 0, 1, NO_PROPS, Some("awesome name"))?;
 
-        // FIXME: Needs multilayer support (Issue #47)
-        test_graph(&graph, |graph| {
+        test_storage!(&graph, |graph| {
             let what = graph.edges().id().collect_vec();
             assert_eq!(what, vec![(0u64.into(), 1u64.into())]);
 
@@ -1740,8 +1761,7 @@ mod db_tests {
         let graph = Graph::new();
         graph.add_edge(0, 1, 2, NO_PROPS, Some("layer")).unwrap();
 
-        // FIXME: Needs multilayer support (Issue #47)
-        test_graph(&graph, |graph| {
+        test_storage!(&graph, |graph| {
             assert!(graph.edge(1, 2).is_some());
             assert!(graph.layers("layer").unwrap().edge(1, 2).is_some())
         });
@@ -1759,8 +1779,7 @@ mod db_tests {
             .expect("add edge");
         graph.add_edge(1, 1, 4, NO_PROPS, None).expect("add edge");
 
-        // FIXME: Needs multilayer support (Issue #47)
-        test_graph(&graph, |graph| {
+        test_storage!(&graph, |graph| {
             let g_layers = graph.layers(vec!["layer1", "layer3"]).expect("layer");
 
             assert!(g_layers.layers("layer1").unwrap().edge(1, 2).is_some());
@@ -1835,8 +1854,7 @@ mod db_tests {
         graph.add_edge(2, 1, 2, NO_PROPS, Some("layer1")).unwrap();
         graph.add_edge(3, 1, 2, NO_PROPS, None).unwrap();
 
-        // FIXME: Needs multilayer support (Issue #47)
-        test_graph(&graph, |graph| {
+        test_storage!(&graph, |graph| {
             let e = graph.edge(1, 2).expect("edge");
 
             let layer_exploded = e
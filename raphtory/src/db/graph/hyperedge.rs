@@ -0,0 +1,566 @@
+//! Defines the `HyperedgeView` struct, which represents a multi-party event in the graph.
+//!
+//! A hyperedge happens at a single point in time and connects an arbitrary number of nodes, each
+//! optionally taking part in a named role. Hyperedges are visible in a view if their time is
+//! inside the window of the view and at least one of their participants is part of the view.
+
+use crate::{
+    core::{
+        entities::{hyperedges::HyperedgeStore, VID},
+        storage::timeindex::AsTime,
+        utils::errors::GraphError,
+        Prop,
+    },
+    db::{
+        api::{
+            properties::{
+                internal::{ConstPropertiesOps, TemporalPropertiesOps, TemporalPropertyViewOps},
+                Properties,
+            },
+            storage::graph::storage_ops::GraphStorage,
+            view::internal::*,
+        },
+        graph::node::NodeView,
+    },
+    prelude::*,
+};
+use chrono::{DateTime, Utc};
+use itertools::Itertools;
+use raphtory_api::core::storage::arc_str::{ArcStr, OptionAsStr};
+use std::{
+    fmt::{Debug, Formatter},
+    iter,
+};
+
+/// View of a hyperedge in a graph
+#[derive(Clone)]
+pub struct HyperedgeView<G> {
+    pub graph: G,
+    pub(crate) hid: usize,
+    id: GID,
+    time: i64,
+}
+
+impl<G> Static for HyperedgeView<G> {}
+
+/// Whether node `v` is part of `graph`
+pub(crate) fn node_visible<'graph, G: GraphViewOps<'graph>>(graph: &G, v: VID) -> bool {
+    !graph.nodes_filtered()
+        || graph.filter_node(graph.core_node_entry(v).as_ref(), graph.layer_ids())
+}
+
+fn in_window<'graph, G: GraphViewOps<'graph>>(graph: &G, hyperedge: &HyperedgeStore) -> bool {
+    let t = hyperedge.time.t();
+    graph.view_start().map_or(true, |start| t >= start)
+        && graph.view_end().map_or(true, |end| t < end)
+}
+
+/// The participants of `hyperedge` where merged nodes resolve to the node they were merged into
+fn resolved_participants<'a>(
+    storage: &'a GraphStorage,
+    hyperedge: &'a HyperedgeStore,
+) -> impl Iterator<Item = (VID, Option<usize>)> + 'a {
+    let merges = storage.node_merges().filter(|merges| !merges.is_empty());
+    hyperedge
+        .participants
+        .iter()
+        .map(move |(v, role)| (merges.map_or(*v, |merges| merges.resolve(*v)), *role))
+}
+
+/// The hyperedges of `graph` that node `v` takes part in, including the hyperedges of the nodes
+/// that were merged into `v`, assumes `v` is part of `graph`
+pub(crate) fn node_hyperedges<'graph, G: GraphViewOps<'graph>>(
+    storage: &GraphStorage,
+    graph: &G,
+    v: VID,
+) -> Vec<HyperedgeView<G>> {
+    match storage.hyperedges() {
+        None => vec![],
+        Some(hyperedges) => {
+            let merged = storage
+                .node_merges()
+                .map(|merges| merges.merged_nodes_of(v))
+                .unwrap_or_default();
+            let entries = hyperedges.read();
+            iter::once(v)
+                .chain(merged)
+                .flat_map(|u| entries.of_node(u).iter().copied())
+                .sorted()
+                .dedup()
+                .filter_map(|hid| Some((hid, entries.get(hid)?)))
+                .filter(|(_, hyperedge)| in_window(graph, hyperedge))
+                .map(|(hid, hyperedge)| HyperedgeView::from_store(graph.clone(), hid, hyperedge))
+                .collect()
+        }
+    }
+}
+
+/// The distinct nodes of `graph` that share a hyperedge with node `v`
+pub(crate) fn hyperedge_neighbours<'graph, G: GraphViewOps<'graph>>(
+    storage: &GraphStorage,
+    graph: &G,
+    v: VID,
+) -> Vec<VID> {
+    match storage.hyperedges() {
+        None => vec![],
+        Some(hyperedges) => {
+            let entries = hyperedges.read();
+            node_hyperedges(storage, graph, v)
+                .into_iter()
+                .filter_map(|hyperedge| entries.get(hyperedge.hid))
+                .flat_map(|hyperedge| resolved_participants(storage, hyperedge).map(|(u, _)| u))
+                .filter(|u| *u != v)
+                .unique()
+                .filter(|u| node_visible(graph, *u))
+                .collect()
+        }
+    }
+}
+
+fn visible<'graph, G: GraphViewOps<'graph>>(graph: &G, hyperedge: &HyperedgeStore) -> bool {
+    in_window(graph, hyperedge)
+        && resolved_participants(graph.core_graph(), hyperedge).any(|(v, _)| node_visible(graph, v))
+}
+
+/// All hyperedges visible in `graph`
+pub(crate) fn graph_hyperedges<'graph, G: GraphViewOps<'graph>>(
+    graph: &G,
+) -> Vec<HyperedgeView<G>> {
+    match graph.core_graph().hyperedges() {
+        None => vec![],
+        Some(hyperedges) => hyperedges
+            .read()
+            .iter()
+            .filter(|(_, hyperedge)| visible(graph, hyperedge))
+            .map(|(hid, hyperedge)| HyperedgeView::from_store(graph.clone(), hid, hyperedge))
+            .collect(),
+    }
+}
+
+/// Find hyperedge `id` if it is visible in `graph`
+pub(crate) fn find_hyperedge<'graph, G: GraphViewOps<'graph>>(
+    graph: &G,
+    id: &GID,
+) -> Option<HyperedgeView<G>> {
+    let hyperedges = graph.core_graph().hyperedges()?;
+    let entries = hyperedges.read();
+    let hid = entries.find(id)?;
+    let hyperedge = entries.get(hid)?;
+    visible(graph, hyperedge).then(|| HyperedgeView::from_store(graph.clone(), hid, hyperedge))
+}
+
+impl<G> HyperedgeView<G> {
+    pub(crate) fn new(graph: G, hid: usize, id: GID, time: i64) -> Self {
+        Self {
+            graph,
+            hid,
+            id,
+            time,
+        }
+    }
+
+    fn from_store(graph: G, hid: usize, hyperedge: &HyperedgeStore) -> Self {
+        Self::new(graph, hid, hyperedge.id.clone(), hyperedge.time.t())
+    }
+
+    /// The same hyperedge viewed through `graph`
+    pub(crate) fn with_graph<GG>(&self, graph: GG) -> HyperedgeView<GG> {
+        HyperedgeView {
+            graph,
+            hid: self.hid,
+            id: self.id.clone(),
+            time: self.time,
+        }
+    }
+}
+
+impl<'graph, G: GraphViewOps<'graph>> HyperedgeView<G> {
+    /// Apply `op` to the stored hyperedge, `None` if the graph does not support hyperedges or
+    /// the hyperedge was removed
+    fn with_store<O>(&self, op: impl FnOnce(&HyperedgeStore) -> O) -> Option<O> {
+        let hyperedges = self.graph.core_graph().hyperedges()?;
+        let entries = hyperedges.read();
+        entries.get(self.hid).map(op)
+    }
+
+    /// The id of the hyperedge
+    pub fn id(&self) -> GID {
+        self.id.clone()
+    }
+
+    /// The time of the hyperedge
+    pub fn time(&self) -> i64 {
+        self.time
+    }
+
+    /// The time of the hyperedge as a date time
+    pub fn date_time(&self) -> Option<DateTime<Utc>> {
+        self.time().dt()
+    }
+
+    /// The participants of the hyperedge that are part of the graph together with their role
+    pub fn participants(&self) -> Vec<(NodeView<G>, Option<ArcStr>)> {
+        let participants = self
+            .with_store(|hyperedge| {
+                resolved_participants(self.graph.core_graph(), hyperedge).collect_vec()
+            })
+            .unwrap_or_default();
+        let role_meta = self
+            .graph
+            .core_graph()
+            .hyperedges()
+            .map(|hyperedges| hyperedges.role_meta());
+        participants
+            .into_iter()
+            .filter(|(v, _)| node_visible(&self.graph, *v))
+            .map(|(v, role)| {
+                let role = role.zip(role_meta).map(|(role, meta)| meta.get_name(role));
+                (NodeView::new_internal(self.graph.clone(), v), role)
+            })
+            .collect()
+    }
+
+    /// The distinct nodes that take part in the hyperedge and are part of the graph
+    pub fn nodes(&self) -> Vec<NodeView<G>> {
+        self.with_store(|hyperedge| {
+            resolved_participants(self.graph.core_graph(), hyperedge)
+                .map(|(v, _)| v)
+                .unique()
+                .collect_vec()
+        })
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|v| node_visible(&self.graph, *v))
+        .map(|v| NodeView::new_internal(self.graph.clone(), v))
+        .collect()
+    }
+
+    /// The properties of the hyperedge
+    pub fn properties(&self) -> Properties<Self> {
+        Properties::new(self.clone())
+    }
+
+    fn prop_values(&self) -> Vec<(ArcStr, Prop)> {
+        let props = self
+            .with_store(|hyperedge| hyperedge.props.clone())
+            .unwrap_or_default();
+        props
+            .into_iter()
+            .map(|(id, prop)| (self.get_temporal_prop_name(id), prop))
+            .collect()
+    }
+}
+
+impl<'graph, G: GraphViewOps<'graph>> Debug for HyperedgeView<G> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "HyperedgeView(id={:?}, time={}, nodes={:?})",
+            self.id(),
+            self.time(),
+            self.nodes().iter().map(|node| node.id()).collect_vec()
+        )
+    }
+}
+
+impl<'graph, G: GraphViewOps<'graph>> TemporalPropertiesOps for HyperedgeView<G> {
+    fn get_temporal_prop_id(&self, name: &str) -> Option<usize> {
+        let id = self
+            .graph
+            .core_graph()
+            .hyperedges()?
+            .prop_meta()
+            .get_id(name)?;
+        self.with_store(|hyperedge| hyperedge.prop(id).is_some())?
+            .then_some(id)
+    }
+
+    fn get_temporal_prop_name(&self, id: usize) -> ArcStr {
+        // ids only come from `temporal_prop_ids` which is empty without hyperedge storage
+        self.graph
+            .core_graph()
+            .hyperedges()
+            .map(|hyperedges| hyperedges.prop_meta().get_name(id))
+            .unwrap_or_default()
+    }
+
+    fn temporal_prop_ids(&self) -> Box<dyn Iterator<Item = usize> + '_> {
+        let ids = self.with_store(|hyperedge| {
+            hyperedge
+                .props
+                .iter()
+                .map(|(id, _)| *id)
+                .sorted()
+                .collect_vec()
+        });
+        Box::new(ids.into_iter().flatten())
+    }
+}
+
+impl<'graph, G: GraphViewOps<'graph>> TemporalPropertyViewOps for HyperedgeView<G> {
+    fn temporal_value(&self, id: usize) -> Option<Prop> {
+        self.with_store(|hyperedge| hyperedge.prop(id).cloned())
+            .flatten()
+    }
+
+    fn temporal_history(&self, id: usize) -> Vec<i64> {
+        self.with_store(|hyperedge| {
+            hyperedge
+                .prop(id)
+                .map(|_| hyperedge.time.t())
+                .into_iter()
+                .collect()
+        })
+        .unwrap_or_default()
+    }
+
+    fn temporal_values(&self, id: usize) -> Vec<Prop> {
+        self.temporal_value(id).into_iter().collect()
+    }
+}
+
+impl<'graph, G: GraphViewOps<'graph>> ConstPropertiesOps for HyperedgeView<G> {
+    fn get_const_prop_id(&self, _name: &str) -> Option<usize> {
+        None
+    }
+
+    fn get_const_prop_name(&self, _id: usize) -> ArcStr {
+        unreachable!("hyperedges do not have constant properties")
+    }
+
+    fn const_prop_ids(&self) -> Box<dyn Iterator<Item = usize> + '_> {
+        Box::new(std::iter::empty())
+    }
+
+    fn get_const_prop(&self, _id: usize) -> Option<Prop> {
+        None
+    }
+}
+
+/// Project the hyperedges of `graph` to a bipartite graph.
+///
+/// Each hyperedge becomes a node of type `"hyperedge"` with the same id that holds the properties
+/// of the hyperedge, its participants are connected to it with an edge in the layer of their
+/// role (or the default layer if they do not have one).
+pub(crate) fn bipartite_projection<'graph, G: GraphViewOps<'graph>>(
+    graph: &G,
+) -> Result<Graph, GraphError> {
+    let projected = Graph::new();
+    for hyperedge in graph.hyperedges() {
+        let id = hyperedge.id();
+        if graph.has_node(&id) {
+            return Err(GraphError::NodeExistsError(id));
+        }
+        let t = hyperedge.time();
+        projected.add_node(t, &id, hyperedge.prop_values(), Some("hyperedge"))?;
+        for (node, role) in hyperedge.participants() {
+            projected.add_node(t, node.id(), NO_PROPS, node.node_type().as_str())?;
+            projected.add_edge(t, node.id(), id.clone(), NO_PROPS, role.as_str())?;
+        }
+    }
+    Ok(projected)
+}
+
+/// Project the hyperedges of `graph` to a graph where the participants of each hyperedge are
+/// connected by a clique of edges that carry the properties of the hyperedge.
+///
+/// Edges point from earlier to later participants in the order the participants were added.
+pub(crate) fn clique_projection<'graph, G: GraphViewOps<'graph>>(
+    graph: &G,
+) -> Result<Graph, GraphError> {
+    let projected = Graph::new();
+    for hyperedge in graph.hyperedges() {
+        let t = hyperedge.time();
+        let props = hyperedge.prop_values();
+        let nodes = hyperedge.nodes();
+        for node in nodes.iter() {
+            projected.add_node(t, node.id(), NO_PROPS, node.node_type().as_str())?;
+        }
+        for (i, src) in nodes.iter().enumerate() {
+            for dst in &nodes[i + 1..] {
+                projected.add_edge(t, src.id(), dst.id(), props.clone(), None)?;
+            }
+        }
+    }
+    Ok(projected)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        core::utils::errors::GraphError, db::graph::views::deletion_graph::PersistentGraph,
+        prelude::*,
+    };
+    use itertools::Itertools;
+
+    fn meeting_graph() -> Graph {
+        let g = Graph::new();
+        g.add_hyperedge(
+            1,
+            "m1",
+            [("Alice", Some("host")), ("Bob", None), ("Charlie", None)],
+            [("duration", 30i64)],
+        )
+        .unwrap();
+        g.add_hyperedge(
+            5,
+            "m2",
+            [("Bob", Some("host")), ("Dave", None)],
+            [("duration", 60i64)],
+        )
+        .unwrap();
+        g
+    }
+
+    #[test]
+    fn test_add_hyperedge() {
+        let g = meeting_graph();
+        assert_eq!(g.count_hyperedges(), 2);
+        assert_eq!(g.count_nodes(), 4);
+        assert_eq!(g.count_edges(), 0);
+        assert_eq!(g.node("Bob").unwrap().history(), [1, 5]);
+
+        let m1 = g.hyperedge("m1").unwrap();
+        assert_eq!(m1.id(), GID::Str("m1".into()));
+        assert_eq!(m1.time(), 1);
+        assert_eq!(
+            m1.participants()
+                .into_iter()
+                .map(|(node, role)| (node.name(), role.map(|role| role.to_string())))
+                .collect_vec(),
+            [
+                ("Alice".to_string(), Some("host".to_string())),
+                ("Bob".to_string(), None),
+                ("Charlie".to_string(), None)
+            ]
+        );
+        assert_eq!(m1.properties().get("duration"), Some(Prop::I64(30)));
+        assert_eq!(
+            m1.properties()
+                .temporal()
+                .get("duration")
+                .unwrap()
+                .history(),
+            [1]
+        );
+        assert!(g.hyperedge("m3").is_none());
+    }
+
+    #[test]
+    fn test_duplicate_hyperedge() {
+        let g = meeting_graph();
+        let res = g.add_hyperedge(7, "m1", [("Eve", None)], NO_PROPS);
+        assert!(matches!(res, Err(GraphError::HyperedgeExistsError(_))));
+        assert!(!g.has_node("Eve"));
+    }
+
+    #[test]
+    fn test_hyperedge_views_survive_truncation() {
+        let g = meeting_graph();
+        let m1 = g.hyperedge("m1").unwrap();
+        let m2 = g.hyperedge("m2").unwrap();
+
+        g.truncate_before(3).unwrap();
+        assert_eq!(g.count_hyperedges(), 1);
+        // internal ids are stable, the view still refers to the same hyperedge
+        assert_eq!(m2.properties().get("duration"), Some(Prop::I64(60)));
+        assert_eq!(m2.nodes().len(), 2);
+        // views of removed hyperedges are empty
+        assert_eq!(m1.id(), GID::Str("m1".into()));
+        assert!(m1.participants().is_empty());
+        assert_eq!(m1.properties().get("duration"), None);
+    }
+
+    #[test]
+    fn test_hyperedge_views() {
+        let g = meeting_graph();
+        let w = g.window(0, 3);
+        assert_eq!(w.count_hyperedges(), 1);
+        assert!(w.hyperedge("m2").is_none());
+        assert_eq!(w.node("Bob").unwrap().hyperedge_degree(), 1);
+        assert_eq!(g.node("Bob").unwrap().hyperedge_degree(), 2);
+        assert_eq!(g.node("Dave").unwrap().hyperedge_degree(), 1);
+
+        let sg = g.subgraph(["Alice", "Charlie"]);
+        assert_eq!(sg.count_hyperedges(), 1);
+        assert_eq!(
+            sg.hyperedge("m1")
+                .unwrap()
+                .nodes()
+                .iter()
+                .map(|node| node.name())
+                .collect_vec(),
+            ["Alice", "Charlie"]
+        );
+    }
+
+    #[test]
+    fn test_hyperedge_neighbours() {
+        let g = meeting_graph();
+        let neighbours = |g: &Graph, t_end: i64, name: &str| {
+            g.window(0, t_end)
+                .node(name)
+                .unwrap()
+                .hyperedge_neighbours()
+                .name()
+                .sorted()
+                .collect_vec()
+        };
+        assert_eq!(neighbours(&g, 10, "Bob"), ["Alice", "Charlie", "Dave"]);
+        assert_eq!(neighbours(&g, 3, "Bob"), ["Alice", "Charlie"]);
+        assert_eq!(neighbours(&g, 10, "Dave"), ["Bob"]);
+        assert_eq!(
+            g.nodes().hyperedge_degree().values().collect_vec(),
+            [1, 2, 1, 1]
+        );
+    }
+
+    #[test]
+    fn test_bipartite_projection() {
+        let g = meeting_graph();
+        let projected = g.bipartite_projection().unwrap();
+        assert_eq!(projected.count_nodes(), 6);
+        assert_eq!(
+            projected.node("m1").unwrap().node_type().as_str(),
+            Some("hyperedge")
+        );
+        assert_eq!(
+            projected.node("m2").unwrap().properties().get("duration"),
+            Some(Prop::I64(60))
+        );
+        assert!(projected.layers("host").unwrap().has_edge("Alice", "m1"));
+        assert!(projected.default_layer().has_edge("Bob", "m1"));
+        assert_eq!(projected.node("m1").unwrap().in_degree(), 3);
+
+        g.add_node(10, "m1", NO_PROPS, None).unwrap();
+        assert!(matches!(
+            g.bipartite_projection(),
+            Err(GraphError::NodeExistsError(_))
+        ));
+    }
+
+    #[test]
+    fn test_clique_projection() {
+        let g = meeting_graph();
+        let projected = g.window(0, 3).clique_projection().unwrap();
+        assert_eq!(projected.count_nodes(), 3);
+        assert_eq!(projected.count_edges(), 3);
+        let edge = projected.edge("Alice", "Charlie").unwrap();
+        assert_eq!(edge.history(), [1]);
+        assert_eq!(edge.properties().get("duration"), Some(Prop::I64(30)));
+
+        let projected = g.clique_projection().unwrap();
+        assert_eq!(projected.count_edges(), 4);
+        assert!(projected.has_edge("Bob", "Dave"));
+    }
+
+    #[test]
+    fn test_persistent_graph_hyperedges() {
+        let g = PersistentGraph::new();
+        g.add_hyperedge(1, 1u64, [(1, None), (2, None)], NO_PROPS)
+            .unwrap();
+        // hyperedges are events and do not persist
+        assert_eq!(g.window(2, 5).count_hyperedges(), 0);
+        assert_eq!(g.at(1).count_hyperedges(), 1);
+    }
+}
@@ -12,11 +12,15 @@ use crate::{
                 time_from_input, CollectProperties, TryIntoInputTime,
             },
             properties::{
+                computed::{computed_prop_id, computed_prop_index, ComputedNodeProp, ComputedProp},
                 internal::{ConstPropertiesOps, TemporalPropertiesOps, TemporalPropertyViewOps},
                 Properties,
             },
             view::{
-                internal::{CoreGraphOps, OneHopFilter, Static, TimeSemantics},
+                internal::{
+                    CoreGraphOps, InternalComputedPropertiesOps, OneHopFilter, Static,
+                    TimeSemantics,
+                },
                 BaseNodeViewOps, IntoDynBoxed, StaticGraphViewOps,
             },
         },
@@ -172,8 +176,50 @@ impl<G, GH: CoreGraphOps> Hash for NodeView<G, GH> {
     }
 }
 
-impl<G, GH: CoreGraphOps + TimeSemantics> TemporalPropertiesOps for NodeView<G, GH> {
+impl<G, GH: CoreGraphOps + TimeSemantics + InternalComputedPropertiesOps> NodeView<G, GH> {
+    /// The computed property with `name` if it is registered on the view
+    fn computed_prop(&self, name: &str) -> Option<(usize, &ComputedNodeProp)> {
+        let props = self.graph.computed_properties()?;
+        let index = props.node_prop_index(name)?;
+        Some((index, &props.node_prop(index).1))
+    }
+
+    fn computed_prop_by_id(&self, id: usize) -> Option<(usize, &(ArcStr, ComputedNodeProp))> {
+        let index = computed_prop_index(id)?;
+        Some((index, self.graph.computed_properties()?.node_prop(index)))
+    }
+
+    /// Stored properties with the same name as a computed property are hidden
+    fn is_shadowed(&self, name: &str) -> bool {
+        self.computed_prop(name).is_some()
+    }
+
+    fn computed_prop_ids(&self, temporal: bool) -> impl Iterator<Item = usize> + '_ {
+        self.graph
+            .computed_properties()
+            .into_iter()
+            .flat_map(move |props| {
+                (0..props.num_node_props())
+                    .filter(move |index| props.node_prop(*index).1.is_temporal(self) == temporal)
+            })
+            .map(computed_prop_id)
+    }
+
+    fn temporal_prop_vec(&self, id: usize) -> Vec<(i64, Prop)> {
+        match self.computed_prop_by_id(id) {
+            Some((_, (_, prop))) => prop.temporal_updates(self),
+            None => self.graph.temporal_node_prop_vec(self.node, id),
+        }
+    }
+}
+
+impl<G, GH: CoreGraphOps + TimeSemantics + InternalComputedPropertiesOps> TemporalPropertiesOps
+    for NodeView<G, GH>
+{
     fn get_temporal_prop_id(&self, name: &str) -> Option<usize> {
+        if let Some((index, prop)) = self.computed_prop(name) {
+            return prop.is_temporal(self).then(|| computed_prop_id(index));
+        }
         self.graph
             .node_meta()
             .temporal_prop_meta()
@@ -182,49 +228,53 @@ impl<G, GH: CoreGraphOps + TimeSemantics> TemporalPropertiesOps for NodeView<G,
     }
 
     fn get_temporal_prop_name(&self, id: usize) -> ArcStr {
-        self.graph
-            .node_meta()
-            .temporal_prop_meta()
-            .get_name(id)
-            .clone()
+        match self.computed_prop_by_id(id) {
+            Some((_, (name, _))) => name.clone(),
+            None => self
+                .graph
+                .node_meta()
+                .temporal_prop_meta()
+                .get_name(id)
+                .clone(),
+        }
     }
 
     fn temporal_prop_ids(&self) -> Box<dyn Iterator<Item = usize> + '_> {
         Box::new(
             self.graph
                 .temporal_node_prop_ids(self.node)
-                .filter(|id| self.graph.has_temporal_node_prop(self.node, *id)),
+                .filter(|id| {
+                    self.graph.has_temporal_node_prop(self.node, *id)
+                        && !self.is_shadowed(&self.get_temporal_prop_name(*id))
+                })
+                .chain(self.computed_prop_ids(true)),
         )
     }
 }
 
-impl<G, GH: TimeSemantics> TemporalPropertyViewOps for NodeView<G, GH> {
+impl<G, GH: CoreGraphOps + TimeSemantics + InternalComputedPropertiesOps> TemporalPropertyViewOps
+    for NodeView<G, GH>
+{
     fn temporal_value(&self, id: usize) -> Option<Prop> {
-        self.graph
-            .temporal_node_prop_vec(self.node, id)
-            .last()
-            .map(|(_, v)| v.to_owned())
+        self.temporal_prop_vec(id).pop().map(|(_, v)| v)
     }
 
     fn temporal_history(&self, id: usize) -> Vec<i64> {
-        self.graph
-            .temporal_node_prop_vec(self.node, id)
+        self.temporal_prop_vec(id)
             .into_iter()
             .map(|(t, _)| t)
             .collect()
     }
 
     fn temporal_history_date_time(&self, id: usize) -> Option<Vec<DateTime<Utc>>> {
-        self.graph
-            .temporal_node_prop_vec(self.node, id)
+        self.temporal_prop_vec(id)
             .into_iter()
             .map(|(t, _)| t.dt())
             .collect()
     }
 
     fn temporal_values(&self, id: usize) -> Vec<Prop> {
-        self.graph
-            .temporal_node_prop_vec(self.node, id)
+        self.temporal_prop_vec(id)
             .into_iter()
             .map(|(_, v)| v)
             .collect()
@@ -239,25 +289,53 @@ impl<G, GH: TimeSemantics> TemporalPropertyViewOps for NodeView<G, GH> {
     }
 }
 
-impl<G, GH: CoreGraphOps> ConstPropertiesOps for NodeView<G, GH> {
+impl<G, GH: CoreGraphOps + TimeSemantics + InternalComputedPropertiesOps> ConstPropertiesOps
+    for NodeView<G, GH>
+{
     fn get_const_prop_id(&self, name: &str) -> Option<usize> {
+        if let Some((index, prop)) = self.computed_prop(name) {
+            return (!prop.is_temporal(self)).then(|| computed_prop_id(index));
+        }
         self.graph.node_meta().const_prop_meta().get_id(name)
     }
 
     fn get_const_prop_name(&self, id: usize) -> ArcStr {
-        self.graph
-            .node_meta()
-            .const_prop_meta()
-            .get_name(id)
-            .clone()
+        match self.computed_prop_by_id(id) {
+            Some((_, (name, _))) => name.clone(),
+            None => self
+                .graph
+                .node_meta()
+                .const_prop_meta()
+                .get_name(id)
+                .clone(),
+        }
     }
 
     fn const_prop_ids(&self) -> Box<dyn Iterator<Item = usize> + '_> {
-        self.graph.constant_node_prop_ids(self.node)
+        Box::new(
+            self.graph
+                .constant_node_prop_ids(self.node)
+                .filter(|id| !self.is_shadowed(&self.get_const_prop_name(*id)))
+                .chain(
+                    self.computed_prop_ids(false)
+                        .filter(|id| self.get_const_prop(*id).is_some()),
+                ),
+        )
     }
 
     fn get_const_prop(&self, id: usize) -> Option<Prop> {
-        self.graph.constant_node_prop(self.node, id)
+        match self.computed_prop_by_id(id) {
+            Some((index, (_, prop))) => match prop {
+                ComputedProp::Expr(_) => prop.const_expr_value(self),
+                ComputedProp::Fn { .. } => self.graph.computed_node_prop(
+                    self.node,
+                    index,
+                    self.graph.view_start(),
+                    self.graph.view_end(),
+                ),
+            },
+            None => self.graph.constant_node_prop(self.node, id),
+        }
     }
 }
 
@@ -0,0 +1,124 @@
+use crate::{
+    core::entities::LayerIds,
+    db::{
+        api::{
+            properties::internal::InheritPropertiesOps,
+            storage::graph::edges::edge_ref::EdgeStorageRef,
+            view::internal::{
+                Base, EdgeFilterOps, Immutable, InheritComputedPropertiesOps, InheritCoreOps,
+                InheritLayerOps, InheritListOps, InheritMaterialize, InheritNodeFilterOps,
+                InheritTimeSemantics, Static,
+            },
+        },
+        graph::views::deletion_graph::edge_alive_intervals,
+    },
+    prelude::GraphViewOps,
+};
+use std::ops::Range;
+
+/// How the lifespan of an edge is compared against the query interval of an [ActiveIntervalGraph]
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
+pub enum IntervalSemantics {
+    /// The edge is alive at some point during the query interval
+    #[default]
+    Overlap,
+    /// The edge is alive for the whole query interval
+    Containment,
+}
+
+impl IntervalSemantics {
+    fn matches(&self, alive: &Range<i64>, query: &Range<i64>) -> bool {
+        match self {
+            IntervalSemantics::Overlap => {
+                if alive.is_empty() {
+                    query.contains(&alive.start)
+                } else {
+                    alive.start < query.end && alive.end > query.start
+                }
+            }
+            IntervalSemantics::Containment => alive.start <= query.start && alive.end >= query.end,
+        }
+    }
+}
+
+/// View of a graph that only includes the edges that are active during the interval
+/// `[start, end)`, following the semantics of persistent graphs.
+#[derive(Clone, Debug)]
+pub struct ActiveIntervalGraph<G> {
+    pub(crate) graph: G,
+    pub(crate) interval: Range<i64>,
+    pub(crate) semantics: IntervalSemantics,
+}
+
+impl<G> Static for ActiveIntervalGraph<G> {}
+
+impl<'graph, G: GraphViewOps<'graph>> Base for ActiveIntervalGraph<G> {
+    type Base = G;
+    #[inline(always)]
+    fn base(&self) -> &Self::Base {
+        &self.graph
+    }
+}
+
+impl<'graph, G: GraphViewOps<'graph>> ActiveIntervalGraph<G> {
+    pub fn new(graph: G, start: i64, end: i64, semantics: IntervalSemantics) -> Self {
+        Self {
+            graph,
+            interval: start..end,
+            semantics,
+        }
+    }
+
+    /// The start of the query interval
+    pub fn interval_start(&self) -> i64 {
+        self.interval.start
+    }
+
+    /// The end of the query interval (exclusive)
+    pub fn interval_end(&self) -> i64 {
+        self.interval.end
+    }
+
+    pub fn semantics(&self) -> IntervalSemantics {
+        self.semantics
+    }
+}
+
+impl<'graph, G: GraphViewOps<'graph>> Immutable for ActiveIntervalGraph<G> {}
+
+impl<'graph, G: GraphViewOps<'graph>> InheritCoreOps for ActiveIntervalGraph<G> {}
+
+impl<'graph, G: GraphViewOps<'graph>> InheritTimeSemantics for ActiveIntervalGraph<G> {}
+
+impl<'graph, G: GraphViewOps<'graph>> InheritPropertiesOps for ActiveIntervalGraph<G> {}
+
+impl<'graph, G: GraphViewOps<'graph>> InheritMaterialize for ActiveIntervalGraph<G> {}
+
+impl<'graph, G: GraphViewOps<'graph>> InheritComputedPropertiesOps for ActiveIntervalGraph<G> {}
+
+impl<'graph, G: GraphViewOps<'graph>> InheritLayerOps for ActiveIntervalGraph<G> {}
+
+impl<'graph, G: GraphViewOps<'graph>> InheritNodeFilterOps for ActiveIntervalGraph<G> {}
+
+impl<'graph, G: GraphViewOps<'graph>> InheritListOps for ActiveIntervalGraph<G> {}
+
+impl<'graph, G: GraphViewOps<'graph>> EdgeFilterOps for ActiveIntervalGraph<G> {
+    fn edges_filtered(&self) -> bool {
+        true
+    }
+
+    fn edge_list_trusted(&self) -> bool {
+        false
+    }
+
+    fn edge_filter_includes_node_filter(&self) -> bool {
+        self.graph.edge_filter_includes_node_filter()
+    }
+
+    fn filter_edge(&self, edge: EdgeStorageRef, layer_ids: &LayerIds) -> bool {
+        self.graph.filter_edge(edge, layer_ids)
+            && edge_alive_intervals(edge, layer_ids)
+                .iter()
+                .any(|alive| self.semantics.matches(alive, &self.interval))
+    }
+}
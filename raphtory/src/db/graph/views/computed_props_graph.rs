@@ -0,0 +1,391 @@
+//! A view that adds computed node and edge properties to a graph.
+//!
+//! Expressions are evaluated by the property views of nodes and edges, this view only stores
+//! the registered properties and evaluates closures for the window they are accessed in.
+
+use crate::{
+    core::{
+        entities::{edges::edge_ref::EdgeRef, EID, VID},
+        storage::timeindex::AsTime,
+        Prop,
+    },
+    db::{
+        api::{
+            properties::{
+                computed::{ComputedProp, ComputedProperties},
+                internal::InheritPropertiesOps,
+            },
+            view::{
+                internal::{
+                    Base, Immutable, InheritCoreOps, InheritEdgeFilterOps, InheritLayerOps,
+                    InheritListOps, InheritMaterialize, InheritNodeFilterOps, InheritTimeSemantics,
+                    InternalComputedPropertiesOps, Static,
+                },
+                IntoDynamic, StaticGraphViewOps,
+            },
+        },
+        graph::{edge::EdgeView, node::NodeView, views::window_graph::WindowedGraph},
+    },
+    prelude::GraphViewOps,
+};
+use dashmap::DashMap;
+use std::{
+    fmt::{Debug, Formatter},
+    sync::Arc,
+};
+
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+enum CacheKey {
+    Node {
+        index: usize,
+        node: VID,
+    },
+    Edge {
+        index: usize,
+        edge: EID,
+        layer: Option<usize>,
+        time: Option<(i64, usize)>,
+    },
+}
+
+type Cache = DashMap<(CacheKey, Option<i64>, Option<i64>), Option<Prop>>;
+
+#[derive(Clone)]
+pub struct ComputedPropsGraph<G> {
+    pub(crate) graph: G,
+    pub(crate) props: Arc<ComputedProperties>,
+    cache: Arc<Cache>,
+}
+
+impl<G> Static for ComputedPropsGraph<G> {}
+
+impl<'graph, G: GraphViewOps<'graph> + Debug> Debug for ComputedPropsGraph<G> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ComputedPropsGraph")
+            .field("graph", &self.graph)
+            .field("props", &self.props)
+            .finish()
+    }
+}
+
+impl<G: StaticGraphViewOps> ComputedPropsGraph<G> {
+    /// Create a view of `graph` with the computed properties `props`
+    pub fn new(graph: G, props: ComputedProperties) -> Self {
+        Self {
+            graph,
+            props: Arc::new(props),
+            cache: Default::default(),
+        }
+    }
+
+    /// The view the closures are evaluated on
+    fn eval_view(&self, start: Option<i64>, end: Option<i64>) -> impl IntoDynamic {
+        WindowedGraph::new(self.clone(), start, end)
+    }
+
+    fn eval_cached(
+        &self,
+        cached: bool,
+        key: CacheKey,
+        start: Option<i64>,
+        end: Option<i64>,
+        eval: impl FnOnce() -> Option<Prop>,
+    ) -> Option<Prop> {
+        if !cached {
+            return eval();
+        }
+        if let Some(value) = self.cache.get(&(key, start, end)) {
+            return value.clone();
+        }
+        // the lock on the cache is not held during evaluation as closures can access other
+        // computed properties of the same view
+        let value = eval();
+        self.cache.insert((key, start, end), value.clone());
+        value
+    }
+}
+
+impl<'graph, G: GraphViewOps<'graph>> Base for ComputedPropsGraph<G> {
+    type Base = G;
+    #[inline(always)]
+    fn base(&self) -> &Self::Base {
+        &self.graph
+    }
+}
+
+impl<'graph, G: GraphViewOps<'graph>> Immutable for ComputedPropsGraph<G> {}
+
+impl<'graph, G: GraphViewOps<'graph>> InheritCoreOps for ComputedPropsGraph<G> {}
+
+impl<'graph, G: GraphViewOps<'graph>> InheritTimeSemantics for ComputedPropsGraph<G> {}
+
+impl<'graph, G: GraphViewOps<'graph>> InheritListOps for ComputedPropsGraph<G> {}
+
+impl<'graph, G: GraphViewOps<'graph>> InheritNodeFilterOps for ComputedPropsGraph<G> {}
+
+impl<'graph, G: GraphViewOps<'graph>> InheritEdgeFilterOps for ComputedPropsGraph<G> {}
+
+impl<'graph, G: GraphViewOps<'graph>> InheritLayerOps for ComputedPropsGraph<G> {}
+
+impl<'graph, G: GraphViewOps<'graph>> InheritMaterialize for ComputedPropsGraph<G> {}
+
+impl<'graph, G: GraphViewOps<'graph>> InheritPropertiesOps for ComputedPropsGraph<G> {}
+
+impl<G: StaticGraphViewOps> InternalComputedPropertiesOps for ComputedPropsGraph<G> {
+    fn computed_properties(&self) -> Option<&ComputedProperties> {
+        Some(&self.props)
+    }
+
+    fn computed_node_prop(
+        &self,
+        v: VID,
+        index: usize,
+        start: Option<i64>,
+        end: Option<i64>,
+    ) -> Option<Prop> {
+        match &self.props.node_prop(index).1 {
+            ComputedProp::Fn { op, cached } => {
+                let key = CacheKey::Node { index, node: v };
+                self.eval_cached(*cached, key, start, end, || {
+                    let view = self.eval_view(start, end).into_dynamic();
+                    op(&NodeView::new_internal(view, v))
+                })
+            }
+            ComputedProp::Expr(_) => None,
+        }
+    }
+
+    fn computed_edge_prop(
+        &self,
+        e: EdgeRef,
+        index: usize,
+        start: Option<i64>,
+        end: Option<i64>,
+    ) -> Option<Prop> {
+        match &self.props.edge_prop(index).1 {
+            ComputedProp::Fn { op, cached } => {
+                let key = CacheKey::Edge {
+                    index,
+                    edge: e.pid(),
+                    layer: e.layer().copied(),
+                    time: e.time().map(|t| (t.t(), t.i())),
+                };
+                self.eval_cached(*cached, key, start, end, || {
+                    let view = self.eval_view(start, end).into_dynamic();
+                    op(&EdgeView::new(view, e))
+                })
+            }
+            ComputedProp::Expr(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        db::{
+            api::{
+                properties::computed::{ComputedProp, PropExpr},
+                view::{internal::DynamicGraph, ComputedPropertiesOps},
+            },
+            graph::{edge::EdgeView, node::NodeView},
+        },
+        prelude::*,
+    };
+    use std::sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    };
+
+    #[test]
+    fn test_node_expressions() {
+        let g = Graph::new();
+        g.add_node(1, 1, [("amount", 10i64)], None).unwrap();
+        g.add_node(3, 1, [("amount", 20i64)], None).unwrap();
+        g.node(1)
+            .unwrap()
+            .add_constant_properties([("fx_rate", 1.5), ("x", 1.0)])
+            .unwrap();
+
+        let view = g
+            .with_node_property(
+                "amount_usd",
+                "amount * fx_rate".parse::<PropExpr>().unwrap(),
+            )
+            .unwrap()
+            .with_node_property(
+                "double_rate",
+                PropExpr::prop("fx_rate") * PropExpr::value(2.0),
+            )
+            .unwrap()
+            .with_node_property("x", "fx_rate + 1".parse::<PropExpr>().unwrap())
+            .unwrap();
+
+        let node = view.node(1).unwrap();
+        let amount_usd = node.properties().temporal().get("amount_usd").unwrap();
+        assert_eq!(amount_usd.history(), [1, 3]);
+        assert_eq!(amount_usd.values(), [Prop::F64(15.0), Prop::F64(30.0)]);
+        assert_eq!(node.properties().get("amount_usd"), Some(Prop::F64(30.0)));
+        assert_eq!(
+            node.properties().constant().get("double_rate"),
+            Some(Prop::F64(3.0))
+        );
+        assert!(node.properties().temporal().get("double_rate").is_none());
+
+        // computed properties hide stored properties with the same name
+        assert_eq!(node.properties().get("x"), Some(Prop::F64(2.5)));
+        assert_eq!(
+            node.properties()
+                .constant()
+                .keys()
+                .into_iter()
+                .filter(|k| k == "x")
+                .count(),
+            1
+        );
+
+        // expressions are evaluated for the window of the view
+        let windowed = view.window(2, 5).node(1).unwrap();
+        let amount_usd = windowed.properties().temporal().get("amount_usd").unwrap();
+        assert_eq!(amount_usd.history(), [3]);
+        assert!(view
+            .window(5, 10)
+            .node(1)
+            .and_then(|node| node.properties().get("amount_usd"))
+            .is_none());
+
+        // the stored graph is unchanged
+        assert!(g.node(1).unwrap().properties().get("amount_usd").is_none());
+    }
+
+    #[test]
+    fn test_expressions_of_several_temporal_properties() {
+        let g = Graph::new();
+        g.add_node(1, 1, [("a", 1i64)], None).unwrap();
+        g.add_node(2, 1, [("b", 10i64)], None).unwrap();
+        g.add_node(3, 1, [("a", 2i64), ("b", 20i64)], None).unwrap();
+        g.add_node(4, 1, [("b", 30i64)], None).unwrap();
+
+        let view = g
+            .with_node_property("sum", "a + b".parse::<PropExpr>().unwrap())
+            .unwrap();
+        let sum = view
+            .node(1)
+            .unwrap()
+            .properties()
+            .temporal()
+            .get("sum")
+            .unwrap();
+        // the expression has no value before both properties are set
+        assert_eq!(sum.history(), [2, 3, 4]);
+        assert_eq!(sum.values(), [Prop::I64(11), Prop::I64(22), Prop::I64(32)]);
+    }
+
+    #[test]
+    fn test_edge_expressions() {
+        let g = Graph::new();
+        g.add_edge(1, 1, 2, [("weight", 1i64)], None).unwrap();
+        g.add_edge(2, 1, 2, [("weight", 3i64)], None).unwrap();
+
+        let view = g
+            .with_edge_property("double_weight", "weight * 2".parse::<PropExpr>().unwrap())
+            .unwrap();
+        let edge = view.edge(1, 2).unwrap();
+        assert_eq!(
+            edge.properties()
+                .temporal()
+                .get("double_weight")
+                .unwrap()
+                .values(),
+            [Prop::I64(2), Prop::I64(6)]
+        );
+        let exploded: Vec<_> = edge
+            .explode()
+            .iter()
+            .map(|e| e.properties().get("double_weight"))
+            .collect();
+        assert_eq!(exploded, [Some(Prop::I64(2)), Some(Prop::I64(6))]);
+    }
+
+    #[test]
+    fn test_closures_per_window() {
+        let g = Graph::new();
+        g.add_edge(1, 1, 2, NO_PROPS, None).unwrap();
+        g.add_edge(5, 1, 3, NO_PROPS, None).unwrap();
+
+        let degree = ComputedProp::func(|node: &NodeView<DynamicGraph>| {
+            Some(Prop::U64(node.degree() as u64))
+        });
+        let span = ComputedProp::func(|edge: &EdgeView<DynamicGraph>| {
+            Some(Prop::I64(edge.latest_time()? - edge.earliest_time()?))
+        });
+        let view = g
+            .with_node_property("degree", degree)
+            .unwrap()
+            .with_edge_property("span", span)
+            .unwrap();
+
+        assert_eq!(
+            view.node(1).unwrap().properties().get("degree"),
+            Some(Prop::U64(2))
+        );
+        assert_eq!(
+            view.window(0, 3)
+                .node(1)
+                .unwrap()
+                .properties()
+                .get("degree"),
+            Some(Prop::U64(1))
+        );
+        assert_eq!(
+            view.edge(1, 2).unwrap().properties().constant().get("span"),
+            Some(Prop::I64(0))
+        );
+        assert!(view
+            .node(1)
+            .unwrap()
+            .properties()
+            .constant()
+            .keys()
+            .contains(&"degree".into()));
+    }
+
+    #[test]
+    fn test_cached_closures() {
+        let g = Graph::new();
+        g.add_edge(1, 1, 2, NO_PROPS, None).unwrap();
+        g.add_edge(5, 1, 3, NO_PROPS, None).unwrap();
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        let counter = calls.clone();
+        let degree = ComputedProp::func(move |node: &NodeView<DynamicGraph>| {
+            counter.fetch_add(1, Ordering::Relaxed);
+            Some(Prop::U64(node.degree() as u64))
+        });
+        let view = g.with_node_property("degree", degree.cached()).unwrap();
+
+        let node = view.node(1).unwrap();
+        assert_eq!(node.properties().get("degree"), Some(Prop::U64(2)));
+        assert_eq!(node.properties().get("degree"), Some(Prop::U64(2)));
+        assert_eq!(calls.load(Ordering::Relaxed), 1);
+
+        let windowed = view.window(0, 3).node(1).unwrap();
+        assert_eq!(windowed.properties().get("degree"), Some(Prop::U64(1)));
+        assert_eq!(windowed.properties().get("degree"), Some(Prop::U64(1)));
+        assert_eq!(calls.load(Ordering::Relaxed), 2);
+    }
+
+    #[test]
+    fn test_cyclic_properties_are_rejected() {
+        let g = Graph::new();
+        let view = g
+            .with_node_property("a", "b + 1".parse::<PropExpr>().unwrap())
+            .unwrap();
+        assert!(view
+            .with_node_property("b", "a * 2".parse::<PropExpr>().unwrap())
+            .is_err());
+        assert!(g
+            .with_edge_property("c", "c + 1".parse::<PropExpr>().unwrap())
+            .is_err());
+    }
+}
@@ -2,11 +2,15 @@ use crate::{
     core::{
         entities::{edges::edge_ref::EdgeRef, LayerIds, VID},
         storage::timeindex::{AsTime, TimeIndexEntry, TimeIndexIntoOps, TimeIndexOps},
+        utils::{errors::GraphError, time::IntoTime},
         Prop,
     },
     db::{
         api::{
-            mutation::internal::InheritMutationOps,
+            mutation::{
+                internal::InheritMutationOps, merge::merge_graphs, MergePolicy, NodeMergeOps,
+                RemovalOps,
+            },
             properties::internal::InheritPropertiesOps,
             storage::{
                 graph::{
@@ -19,7 +23,10 @@ use crate::{
             },
             view::{internal::*, BoxedIter, IntoDynBoxed},
         },
-        graph::graph::graph_equal,
+        graph::{
+            graph::graph_equal,
+            views::active_interval_graph::{ActiveIntervalGraph, IntervalSemantics},
+        },
     },
     prelude::*,
 };
@@ -109,6 +116,57 @@ fn edge_alive_at_start(e: EdgeStorageRef, t: i64, layer_ids: &LayerIds) -> bool
         .any(|(_, additions, deletions)| alive_at(&additions, &deletions, t))
 }
 
+/// The intervals `[start, end)` during which an edge is alive in a single layer.
+///
+/// An edge that is deleted before it is first added is alive from `i64::MIN` and an edge that is
+/// not deleted after its last addition stays alive until `i64::MAX`. An addition and deletion at
+/// the same time results in an empty interval.
+fn alive_intervals<
+    A: TimeIndexOps<IndexType = TimeIndexEntry> + ?Sized,
+    D: TimeIndexOps<IndexType = TimeIndexEntry> + ?Sized,
+>(
+    additions: &A,
+    deletions: &D,
+) -> Vec<Range<i64>> {
+    let mut intervals = vec![];
+    let mut alive_since = None;
+    let updates = additions
+        .iter()
+        .map(|t| (t, true))
+        .merge_by(deletions.iter().map(|t| (t, false)), |a, b| a.0 <= b.0);
+    for (i, (t, is_addition)) in updates.enumerate() {
+        if is_addition {
+            alive_since.get_or_insert(t.t());
+        } else {
+            match alive_since.take() {
+                Some(start) => intervals.push(start..t.t()),
+                None if i == 0 => intervals.push(i64::MIN..t.t()),
+                None => {}
+            }
+        }
+    }
+    if let Some(start) = alive_since {
+        intervals.push(start..i64::MAX);
+    }
+    intervals
+}
+
+/// The sorted and disjoint intervals during which an edge is alive in any of the layers
+pub(crate) fn edge_alive_intervals(e: EdgeStorageRef, layer_ids: &LayerIds) -> Vec<Range<i64>> {
+    let intervals = e
+        .updates_iter(layer_ids)
+        .flat_map(|(_, additions, deletions)| alive_intervals(&additions, &deletions))
+        .sorted_by_key(|interval| interval.start);
+    let mut merged: Vec<Range<i64>> = vec![];
+    for interval in intervals {
+        match merged.last_mut() {
+            Some(last) if interval.start <= last.end => last.end = last.end.max(interval.end),
+            _ => merged.push(interval),
+        }
+    }
+    merged
+}
+
 impl PersistentGraph {
     pub fn new() -> Self {
         Self::default()
@@ -126,6 +184,35 @@ impl PersistentGraph {
     pub fn event_graph(&self) -> Graph {
         Graph::from_storage(self.0.clone())
     }
+
+    /// View of the graph that only includes the edges that are active during `[start, end)`
+    ///
+    /// With [IntervalSemantics::Overlap] an edge is included if it is alive at any point of the
+    /// interval, with [IntervalSemantics::Containment] it needs to be alive for all of it.
+    ///
+    /// Only a persistent graph has edges that are alive for an interval, an event [Graph] needs to
+    /// be converted with [Graph::persistent_graph] first.
+    pub fn active_during<T1: IntoTime, T2: IntoTime>(
+        &self,
+        start: T1,
+        end: T2,
+        semantics: IntervalSemantics,
+    ) -> ActiveIntervalGraph<Self> {
+        ActiveIntervalGraph::new(self.clone(), start.into_time(), end.into_time(), semantics)
+    }
+
+    /// Merge this graph with `other` into a new graph, see [Graph::merge]
+    ///
+    /// Edge deletions of both graphs are preserved.
+    pub fn merge<'graph, G: GraphViewOps<'graph>>(
+        &self,
+        other: &G,
+        policy: &MergePolicy,
+    ) -> Result<PersistentGraph, GraphError> {
+        let merged = PersistentGraph::new();
+        merge_graphs(&merged, self, other, policy)?;
+        Ok(merged)
+    }
 }
 
 impl<'graph, G: GraphViewOps<'graph>> PartialEq<G> for PersistentGraph {
@@ -154,6 +241,10 @@ impl InternalMaterialize for PersistentGraph {
 
 impl DeletionOps for PersistentGraph {}
 
+impl RemovalOps for PersistentGraph {}
+
+impl NodeMergeOps for PersistentGraph {}
+
 impl InheritMutationOps for PersistentGraph {}
 
 impl InheritListOps for PersistentGraph {}
@@ -164,6 +255,8 @@ impl HasDeletionOps for PersistentGraph {}
 
 impl InheritPropertiesOps for PersistentGraph {}
 
+impl InheritComputedPropertiesOps for PersistentGraph {}
+
 impl InheritLayerOps for PersistentGraph {}
 
 impl InheritEdgeFilterOps for PersistentGraph {}
@@ -642,12 +735,16 @@ impl TimeSemantics for PersistentGraph {
 #[cfg(test)]
 mod test_deletions {
     use crate::{
+        core::utils::errors::GraphError,
         db::{
             api::view::time::internal::InternalTimeOps,
             graph::{
                 edge::EdgeView,
                 graph::assert_graph_equal,
-                views::deletion_graph::{PersistentGraph, TimeSemantics},
+                views::{
+                    active_interval_graph::IntervalSemantics,
+                    deletion_graph::{PersistentGraph, TimeSemantics},
+                },
             },
         },
         prelude::*,
@@ -1243,4 +1340,64 @@ mod test_deletions {
             vec![(GID::U64(0), GID::U64(1))]
         );
     }
+
+    #[test]
+    fn test_interval_edges() {
+        let g = PersistentGraph::new();
+        g.add_interval_edge(1, 5, 1, 2, [("weight", 1)], None)
+            .unwrap();
+        g.add_interval_edge(3, 10, 2, 3, NO_PROPS, Some("a"))
+            .unwrap();
+        g.add_edge(8, 3, 4, NO_PROPS, None).unwrap();
+        assert!(matches!(
+            g.add_interval_edge(5, 4, 1, 2, NO_PROPS, None),
+            Err(GraphError::InvalidEdgeInterval { start: 5, end: 4 })
+        ));
+
+        let e = g.edge(1, 2).unwrap();
+        assert_eq!(e.history(), vec![1]);
+        assert_eq!(e.deletions(), vec![5]);
+        assert!(g.at(4).has_edge(1, 2));
+        assert!(!g.at(5).has_edge(1, 2));
+
+        assert_eq!(
+            g.active_during(4, 6, IntervalSemantics::Overlap)
+                .edges()
+                .id()
+                .collect_vec(),
+            vec![(GID::U64(1), GID::U64(2)), (GID::U64(2), GID::U64(3))]
+        );
+        assert_eq!(
+            g.active_during(4, 6, IntervalSemantics::Containment)
+                .edges()
+                .id()
+                .collect_vec(),
+            vec![(GID::U64(2), GID::U64(3))]
+        );
+        assert_eq!(
+            g.active_during(5, 8, IntervalSemantics::Overlap)
+                .edges()
+                .id()
+                .collect_vec(),
+            vec![(GID::U64(2), GID::U64(3))]
+        );
+        assert_eq!(
+            g.active_during(9, 20, IntervalSemantics::Containment)
+                .edges()
+                .id()
+                .collect_vec(),
+            vec![(GID::U64(3), GID::U64(4))]
+        );
+        assert!(g
+            .active_during(4, 6, IntervalSemantics::Overlap)
+            .layers("a")
+            .unwrap()
+            .has_edge(2, 3));
+
+        assert_eq!(g.edge(1, 2).unwrap().duration(), Some(4));
+        assert_eq!(g.edge(2, 3).unwrap().duration(), Some(7));
+        assert_eq!(g.edge(3, 4).unwrap().duration(), None);
+        assert_eq!(g.window(0, 12).edge(3, 4).unwrap().duration(), Some(4));
+        assert_eq!(g.window(4, 6).edge(1, 2).unwrap().duration(), Some(1));
+    }
 }
@@ -0,0 +1,201 @@
+use crate::{
+    core::{entities::LayerIds, utils::errors::GraphError},
+    db::{
+        api::{
+            properties::internal::InheritPropertiesOps,
+            storage::graph::{
+                edges::{edge_ref::EdgeStorageRef, edge_storage_ops::EdgeStorageOps},
+                nodes::node_ref::NodeStorageRef,
+            },
+            view::{
+                internal::{
+                    Base, EdgeFilterOps, Immutable, InheritComputedPropertiesOps, InheritCoreOps,
+                    InheritListOps, InheritMaterialize, InheritTimeSemantics, InternalLayerOps,
+                    NodeFilterOps, Static,
+                },
+                Layer,
+            },
+        },
+        graph::views::union_graph::{
+            check_compatible_views, view_includes_edge, view_includes_node,
+        },
+    },
+    prelude::GraphViewOps,
+};
+
+/// View that includes the nodes and edges that are in both of two views of the same graph
+///
+/// Both views need to be based on the same underlying storage and have the same time
+/// semantics and time bounds, window the intersection instead of the views to restrict it in
+/// time. Properties and materialisation are taken from the `left` view, the layers are the
+/// intersection of both views.
+#[derive(Clone, Debug)]
+pub struct IntersectionGraph<L, R> {
+    pub(crate) left: L,
+    pub(crate) right: R,
+    pub(crate) layers: LayerIds,
+}
+
+impl<L, R> Static for IntersectionGraph<L, R> {}
+
+impl<'graph, L: GraphViewOps<'graph>, R: GraphViewOps<'graph>> Base for IntersectionGraph<L, R> {
+    type Base = L;
+    #[inline(always)]
+    fn base(&self) -> &Self::Base {
+        &self.left
+    }
+}
+
+impl<'graph, L: GraphViewOps<'graph>, R: GraphViewOps<'graph>> IntersectionGraph<L, R> {
+    pub fn new(left: L, right: R) -> Result<Self, GraphError> {
+        check_compatible_views(&left, &right)?;
+        let layers = left.layer_ids().intersect(right.layer_ids());
+        Ok(Self {
+            left,
+            right,
+            layers,
+        })
+    }
+
+    pub fn left(&self) -> &L {
+        &self.left
+    }
+
+    pub fn right(&self) -> &R {
+        &self.right
+    }
+}
+
+impl<'graph, L: GraphViewOps<'graph>, R: GraphViewOps<'graph>> Immutable
+    for IntersectionGraph<L, R>
+{
+}
+
+impl<'graph, L: GraphViewOps<'graph>, R: GraphViewOps<'graph>> InheritCoreOps
+    for IntersectionGraph<L, R>
+{
+}
+
+impl<'graph, L: GraphViewOps<'graph>, R: GraphViewOps<'graph>> InheritTimeSemantics
+    for IntersectionGraph<L, R>
+{
+}
+
+impl<'graph, L: GraphViewOps<'graph>, R: GraphViewOps<'graph>> InheritPropertiesOps
+    for IntersectionGraph<L, R>
+{
+}
+
+impl<'graph, L: GraphViewOps<'graph>, R: GraphViewOps<'graph>> InheritMaterialize
+    for IntersectionGraph<L, R>
+{
+}
+
+impl<'graph, L: GraphViewOps<'graph>, R: GraphViewOps<'graph>> InheritComputedPropertiesOps
+    for IntersectionGraph<L, R>
+{
+}
+
+impl<'graph, L: GraphViewOps<'graph>, R: GraphViewOps<'graph>> InheritListOps
+    for IntersectionGraph<L, R>
+{
+}
+
+impl<'graph, L: GraphViewOps<'graph>, R: GraphViewOps<'graph>> InternalLayerOps
+    for IntersectionGraph<L, R>
+{
+    fn layer_ids(&self) -> &LayerIds {
+        &self.layers
+    }
+
+    fn layer_ids_from_names(&self, key: Layer) -> Result<LayerIds, GraphError> {
+        let left = self.left.layer_ids_from_names(key.clone())?;
+        let right = self.right.layer_ids_from_names(key)?;
+        Ok(left.intersect(&right))
+    }
+
+    fn valid_layer_ids_from_names(&self, key: Layer) -> LayerIds {
+        self.left
+            .valid_layer_ids_from_names(key.clone())
+            .intersect(&self.right.valid_layer_ids_from_names(key))
+    }
+}
+
+impl<'graph, L: GraphViewOps<'graph>, R: GraphViewOps<'graph>> NodeFilterOps
+    for IntersectionGraph<L, R>
+{
+    fn nodes_filtered(&self) -> bool {
+        self.left.nodes_filtered() || self.right.nodes_filtered()
+    }
+
+    fn node_list_trusted(&self) -> bool {
+        false
+    }
+
+    fn filter_node(&self, node: NodeStorageRef, _layer_ids: &LayerIds) -> bool {
+        view_includes_node(&self.left, node) && view_includes_node(&self.right, node)
+    }
+}
+
+impl<'graph, L: GraphViewOps<'graph>, R: GraphViewOps<'graph>> EdgeFilterOps
+    for IntersectionGraph<L, R>
+{
+    fn edges_filtered(&self) -> bool {
+        true
+    }
+
+    fn edge_list_trusted(&self) -> bool {
+        false
+    }
+
+    fn edge_filter_includes_node_filter(&self) -> bool {
+        true
+    }
+
+    fn filter_edge(&self, edge: EdgeStorageRef, layer_ids: &LayerIds) -> bool {
+        edge.has_layer(layer_ids)
+            && view_includes_edge(&self.left, edge)
+            && view_includes_edge(&self.right, edge)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{core::utils::errors::GraphError, prelude::*};
+    use raphtory_api::core::entities::GID;
+
+    #[test]
+    fn test_intersection_of_subgraphs_and_layers() {
+        let graph = Graph::new();
+        graph.add_edge(1, 1, 2, NO_PROPS, Some("a")).unwrap();
+        graph.add_edge(5, 2, 3, NO_PROPS, Some("b")).unwrap();
+        graph.add_edge(9, 3, 4, NO_PROPS, Some("a")).unwrap();
+
+        let intersection = graph
+            .subgraph([1, 2, 3])
+            .intersection(&graph.subgraph([2, 3, 4]))
+            .unwrap();
+        assert_eq!(
+            intersection.nodes().id().collect::<Vec<_>>(),
+            [2, 3].map(GID::U64)
+        );
+        assert_eq!(intersection.count_edges(), 1);
+        assert!(intersection.has_edge(2, 3));
+
+        let intersection = graph
+            .layers("a")
+            .unwrap()
+            .intersection(&graph.subgraph([1, 2, 3]))
+            .unwrap();
+        assert_eq!(intersection.count_edges(), 1);
+        assert!(intersection.has_edge(1, 2));
+        assert!(!intersection.has_edge(2, 3));
+        assert!(!intersection.has_edge(3, 4));
+
+        assert!(matches!(
+            graph.window(0, 6).intersection(&graph.window(4, 10)),
+            Err(GraphError::IncompatibleViews(_))
+        ));
+        assert!(!intersection.window(2, 10).has_edge(1, 2));
+    }
+}
@@ -9,8 +9,9 @@ use crate::{
         storage::graph::edges::{edge_ref::EdgeStorageRef, edge_storage_ops::EdgeStorageOps},
         view::{
             internal::{
-                Base, EdgeFilterOps, Immutable, InheritCoreOps, InheritListOps, InheritMaterialize,
-                InheritNodeFilterOps, InheritTimeSemantics, InternalLayerOps, Static,
+                Base, EdgeFilterOps, Immutable, InheritComputedPropertiesOps, InheritCoreOps,
+                InheritListOps, InheritMaterialize, InheritNodeFilterOps, InheritTimeSemantics,
+                InternalLayerOps, Static,
             },
             Layer,
         },
@@ -57,6 +58,8 @@ impl<'graph, G: GraphViewOps<'graph>> InheritCoreOps for LayeredGraph<G> {}
 
 impl<'graph, G: GraphViewOps<'graph>> InheritMaterialize for LayeredGraph<G> {}
 
+impl<'graph, G: GraphViewOps<'graph>> InheritComputedPropertiesOps for LayeredGraph<G> {}
+
 impl<'graph, G: GraphViewOps<'graph>> InheritPropertiesOps for LayeredGraph<G> {}
 
 impl<'graph, G: GraphViewOps<'graph>> EdgeFilterOps for LayeredGraph<G> {
@@ -129,7 +132,7 @@ impl<'graph, G: GraphViewOps<'graph>> InternalLayerOps for LayeredGraph<G> {
 
 #[cfg(test)]
 mod test_layers {
-    use crate::{prelude::*, test_utils::test_graph};
+    use crate::{prelude::*, test_storage};
     use itertools::Itertools;
     use raphtory_api::core::entities::GID;
 
@@ -141,8 +144,7 @@ mod test_layers {
         graph.add_edge(0, 2, 3, NO_PROPS, Some("layer2")).unwrap();
         graph.add_edge(3, 2, 4, NO_PROPS, Some("layer1")).unwrap();
 
-        // FIXME: Needs multilayer support (Issue #47)
-        test_graph(&graph, |graph| {
+        test_storage!(&graph, |graph| {
             let neighbours = graph
                 .layers(vec!["layer1", "layer2"])
                 .unwrap()
@@ -213,8 +215,7 @@ mod test_layers {
         assert!(e1.has_layer("2"));
         assert!(e1.layers("2").unwrap().history().is_empty());
 
-        // FIXME: Needs multilayer support (Issue #47)
-        test_graph(&graph, |graph| {
+        test_storage!(&graph, |graph| {
             let e = graph.edge(1, 2).unwrap();
             // layers with non-existing layers errors
             assert!(e.layers(["1", "3"]).is_err());
@@ -1,5 +1,9 @@
+pub mod active_interval_graph;
+pub mod computed_props_graph;
 pub mod deletion_graph;
+pub mod intersection_graph;
 pub mod layer_graph;
 pub mod node_subgraph;
 pub mod node_type_filtered_subgraph;
+pub mod union_graph;
 pub mod window_graph;
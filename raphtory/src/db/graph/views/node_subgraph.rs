@@ -7,8 +7,9 @@ use crate::{
             nodes::{node_ref::NodeStorageRef, node_storage_ops::NodeStorageOps},
         },
         view::internal::{
-            Base, EdgeFilterOps, Immutable, InheritCoreOps, InheritLayerOps, InheritListOps,
-            InheritMaterialize, InheritTimeSemantics, NodeFilterOps, Static,
+            Base, EdgeFilterOps, Immutable, InheritComputedPropertiesOps, InheritCoreOps,
+            InheritLayerOps, InheritListOps, InheritMaterialize, InheritTimeSemantics,
+            NodeFilterOps, Static,
         },
     },
     prelude::GraphViewOps,
@@ -50,6 +51,8 @@ impl<'graph, G: GraphViewOps<'graph>> InheritCoreOps for NodeSubgraph<G> {}
 impl<'graph, G: GraphViewOps<'graph>> InheritTimeSemantics for NodeSubgraph<G> {}
 impl<'graph, G: GraphViewOps<'graph>> InheritPropertiesOps for NodeSubgraph<G> {}
 impl<'graph, G: GraphViewOps<'graph>> InheritMaterialize for NodeSubgraph<G> {}
+
+impl<'graph, G: GraphViewOps<'graph>> InheritComputedPropertiesOps for NodeSubgraph<G> {}
 impl<'graph, G: GraphViewOps<'graph>> InheritLayerOps for NodeSubgraph<G> {}
 
 impl<'graph, G: GraphViewOps<'graph>> NodeSubgraph<G> {
@@ -101,10 +104,7 @@ impl<'graph, G: GraphViewOps<'graph>> NodeFilterOps for NodeSubgraph<G> {
 
 #[cfg(test)]
 mod subgraph_tests {
-    use crate::{
-        algorithms::motifs::triangle_count::triangle_count, prelude::*, test_storage,
-        test_utils::test_graph,
-    };
+    use crate::{algorithms::motifs::triangle_count::triangle_count, prelude::*, test_storage};
     use itertools::Itertools;
 
     #[test]
@@ -114,8 +114,7 @@ mod subgraph_tests {
         graph.add_node(1, 1, NO_PROPS, None).unwrap();
         graph.add_node(2, 2, NO_PROPS, None).unwrap();
 
-        // FIXME: Needs multilayer support (Issue #47)
-        test_graph(&graph, |graph| {
+        test_storage!(&graph, |graph| {
             let sg = graph.subgraph([1, 2]);
 
             let actual = sg.materialize().unwrap().into_events().unwrap();
@@ -168,8 +167,7 @@ mod subgraph_tests {
         graph.add_edge(0, 1, 2, NO_PROPS, Some("1")).unwrap();
         graph.add_edge(0, 3, 4, NO_PROPS, Some("2")).unwrap();
 
-        // FIXME: Needs multilayer support (Issue #47)
-        test_graph(&graph, |graph| {
+        test_storage!(&graph, |graph| {
             let sg = graph.subgraph([1, 2]);
             let sgm = sg.materialize().unwrap();
             assert_eq!(
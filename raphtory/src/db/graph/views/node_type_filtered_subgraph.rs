@@ -4,8 +4,9 @@ use crate::{
         properties::internal::InheritPropertiesOps,
         storage::graph::nodes::{node_ref::NodeStorageRef, node_storage_ops::NodeStorageOps},
         view::internal::{
-            Base, Immutable, InheritCoreOps, InheritEdgeFilterOps, InheritLayerOps, InheritListOps,
-            InheritMaterialize, InheritTimeSemantics, NodeFilterOps, Static,
+            Base, Immutable, InheritComputedPropertiesOps, InheritCoreOps, InheritEdgeFilterOps,
+            InheritLayerOps, InheritListOps, InheritMaterialize, InheritTimeSemantics,
+            NodeFilterOps, Static,
         },
     },
     prelude::GraphViewOps,
@@ -45,6 +46,8 @@ impl<'graph, G: GraphViewOps<'graph>> InheritPropertiesOps for TypeFilteredSubgr
 
 impl<'graph, G: GraphViewOps<'graph>> InheritMaterialize for TypeFilteredSubgraph<G> {}
 
+impl<'graph, G: GraphViewOps<'graph>> InheritComputedPropertiesOps for TypeFilteredSubgraph<G> {}
+
 impl<'graph, G: GraphViewOps<'graph>> InheritLayerOps for TypeFilteredSubgraph<G> {}
 
 impl<'graph, G: GraphViewOps<'graph>> InheritEdgeFilterOps for TypeFilteredSubgraph<G> {}
@@ -0,0 +1,253 @@
+use crate::{
+    core::{entities::LayerIds, utils::errors::GraphError},
+    db::api::{
+        properties::internal::InheritPropertiesOps,
+        storage::graph::{
+            edges::{edge_ref::EdgeStorageRef, edge_storage_ops::EdgeStorageOps},
+            nodes::node_ref::NodeStorageRef,
+        },
+        view::{
+            internal::{
+                Base, CoreGraphOps, EdgeFilterOps, Immutable, InheritComputedPropertiesOps,
+                InheritCoreOps, InheritListOps, InheritMaterialize, InheritTimeSemantics,
+                InternalLayerOps, InternalMaterialize, NodeFilterOps, Static, TimeSemantics,
+            },
+            Layer,
+        },
+    },
+    prelude::GraphViewOps,
+};
+
+/// Check if `node` is included in `view`
+#[inline]
+pub(crate) fn view_includes_node<'graph, G: GraphViewOps<'graph>>(
+    view: &G,
+    node: NodeStorageRef,
+) -> bool {
+    view.filter_node(node, view.layer_ids())
+}
+
+/// Check if `edge` and both its endpoints are included in `view`
+pub(crate) fn view_includes_edge<'graph, G: GraphViewOps<'graph>>(
+    view: &G,
+    edge: EdgeStorageRef,
+) -> bool {
+    let layer_ids = view.layer_ids();
+    view.filter_edge(edge, layer_ids)
+        && (view.edge_filter_includes_node_filter()
+            || (view.filter_node(view.core_node_entry(edge.src()).as_ref(), layer_ids)
+                && view.filter_node(view.core_node_entry(edge.dst()).as_ref(), layer_ids)))
+}
+
+/// Check that `left` and `right` are views of the same graph with the same time semantics and
+/// time bounds, such that the time semantics of `left` apply to the nodes and edges of both
+pub(crate) fn check_compatible_views<'graph, L: GraphViewOps<'graph>, R: GraphViewOps<'graph>>(
+    left: &L,
+    right: &R,
+) -> Result<(), GraphError> {
+    if !left.core_graph().ptr_eq(right.core_graph()) {
+        return Err(GraphError::IncompatibleViews(
+            "the views are not based on the same graph".to_string(),
+        ));
+    }
+    if left.include_deletions() != right.include_deletions() {
+        return Err(GraphError::IncompatibleViews(
+            "only one of the views is a persistent graph".to_string(),
+        ));
+    }
+    let (left_bounds, right_bounds) = (
+        (left.view_start(), left.view_end()),
+        (right.view_start(), right.view_end()),
+    );
+    if left_bounds != right_bounds {
+        return Err(GraphError::IncompatibleViews(format!(
+            "the views have different time bounds {left_bounds:?} and {right_bounds:?}"
+        )));
+    }
+    Ok(())
+}
+
+/// View that includes the nodes and edges of either of two views of the same graph
+///
+/// Both views need to be based on the same underlying storage and have the same time
+/// semantics and time bounds, window the union instead of the views to restrict it in time.
+/// Properties and materialisation are taken from the `left` view, the layers are the union of
+/// both views.
+#[derive(Clone, Debug)]
+pub struct UnionGraph<L, R> {
+    pub(crate) left: L,
+    pub(crate) right: R,
+    pub(crate) layers: LayerIds,
+}
+
+impl<L, R> Static for UnionGraph<L, R> {}
+
+impl<'graph, L: GraphViewOps<'graph>, R: GraphViewOps<'graph>> Base for UnionGraph<L, R> {
+    type Base = L;
+    #[inline(always)]
+    fn base(&self) -> &Self::Base {
+        &self.left
+    }
+}
+
+impl<'graph, L: GraphViewOps<'graph>, R: GraphViewOps<'graph>> UnionGraph<L, R> {
+    pub fn new(left: L, right: R) -> Result<Self, GraphError> {
+        check_compatible_views(&left, &right)?;
+        let layers = left.layer_ids().union(right.layer_ids());
+        Ok(Self {
+            left,
+            right,
+            layers,
+        })
+    }
+
+    pub fn left(&self) -> &L {
+        &self.left
+    }
+
+    pub fn right(&self) -> &R {
+        &self.right
+    }
+}
+
+impl<'graph, L: GraphViewOps<'graph>, R: GraphViewOps<'graph>> Immutable for UnionGraph<L, R> {}
+
+impl<'graph, L: GraphViewOps<'graph>, R: GraphViewOps<'graph>> InheritCoreOps for UnionGraph<L, R> {}
+
+impl<'graph, L: GraphViewOps<'graph>, R: GraphViewOps<'graph>> InheritTimeSemantics
+    for UnionGraph<L, R>
+{
+}
+
+impl<'graph, L: GraphViewOps<'graph>, R: GraphViewOps<'graph>> InheritPropertiesOps
+    for UnionGraph<L, R>
+{
+}
+
+impl<'graph, L: GraphViewOps<'graph>, R: GraphViewOps<'graph>> InheritMaterialize
+    for UnionGraph<L, R>
+{
+}
+
+impl<'graph, L: GraphViewOps<'graph>, R: GraphViewOps<'graph>> InheritComputedPropertiesOps
+    for UnionGraph<L, R>
+{
+}
+
+impl<'graph, L: GraphViewOps<'graph>, R: GraphViewOps<'graph>> InheritListOps for UnionGraph<L, R> {}
+
+impl<'graph, L: GraphViewOps<'graph>, R: GraphViewOps<'graph>> InternalLayerOps
+    for UnionGraph<L, R>
+{
+    fn layer_ids(&self) -> &LayerIds {
+        &self.layers
+    }
+
+    fn layer_ids_from_names(&self, key: Layer) -> Result<LayerIds, GraphError> {
+        let left = self.left.valid_layer_ids_from_names(key.clone());
+        let right = self.right.valid_layer_ids_from_names(key.clone());
+        let layers = left.union(&right);
+        if layers.is_none() {
+            // none of the layers are valid in either view, report the error of the left view
+            self.left.layer_ids_from_names(key)
+        } else {
+            Ok(layers)
+        }
+    }
+
+    fn valid_layer_ids_from_names(&self, key: Layer) -> LayerIds {
+        self.left
+            .valid_layer_ids_from_names(key.clone())
+            .union(&self.right.valid_layer_ids_from_names(key))
+    }
+}
+
+impl<'graph, L: GraphViewOps<'graph>, R: GraphViewOps<'graph>> NodeFilterOps for UnionGraph<L, R> {
+    fn nodes_filtered(&self) -> bool {
+        self.left.nodes_filtered() && self.right.nodes_filtered()
+    }
+
+    fn node_list_trusted(&self) -> bool {
+        false
+    }
+
+    fn filter_node(&self, node: NodeStorageRef, _layer_ids: &LayerIds) -> bool {
+        view_includes_node(&self.left, node) || view_includes_node(&self.right, node)
+    }
+}
+
+impl<'graph, L: GraphViewOps<'graph>, R: GraphViewOps<'graph>> EdgeFilterOps for UnionGraph<L, R> {
+    fn edges_filtered(&self) -> bool {
+        self.left.edges_filtered() && self.right.edges_filtered()
+    }
+
+    fn edge_list_trusted(&self) -> bool {
+        false
+    }
+
+    fn edge_filter_includes_node_filter(&self) -> bool {
+        true
+    }
+
+    fn filter_edge(&self, edge: EdgeStorageRef, layer_ids: &LayerIds) -> bool {
+        edge.has_layer(layer_ids)
+            && (view_includes_edge(&self.left, edge) || view_includes_edge(&self.right, edge))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{core::utils::errors::GraphError, prelude::*};
+    use itertools::Itertools;
+    use raphtory_api::core::entities::GID;
+
+    #[test]
+    fn test_union_of_subgraphs_and_layers() {
+        let graph = Graph::new();
+        graph.add_edge(1, 1, 2, NO_PROPS, Some("a")).unwrap();
+        graph.add_edge(5, 2, 3, NO_PROPS, Some("b")).unwrap();
+        graph.add_edge(9, 3, 4, NO_PROPS, Some("a")).unwrap();
+
+        let union = graph
+            .subgraph([1, 2])
+            .union(&graph.subgraph([3, 4]))
+            .unwrap();
+        assert_eq!(
+            union.nodes().id().collect::<Vec<_>>(),
+            [1, 2, 3, 4].map(GID::U64)
+        );
+        assert!(union.has_edge(1, 2));
+        assert!(!union.has_edge(2, 3));
+        assert!(union.has_edge(3, 4));
+        assert_eq!(union.count_edges(), 2);
+
+        let union = graph
+            .layers("a")
+            .unwrap()
+            .subgraph([1, 2])
+            .union(&graph.layers("b").unwrap())
+            .unwrap();
+        assert_eq!(union.count_edges(), 2);
+        assert!(union.has_edge(1, 2));
+        assert!(union.has_edge(2, 3));
+        assert!(!union.has_edge(3, 4));
+        assert_eq!(union.unique_layers().sorted().collect_vec(), vec!["a", "b"]);
+
+        // the time semantics of both views need to match, window the union instead
+        let windowed = union.window(0, 3);
+        assert_eq!(windowed.count_edges(), 1);
+        assert_eq!(windowed.edge(1, 2).unwrap().history(), [1]);
+        assert!(matches!(
+            graph.window(0, 3).union(&graph.window(8, 10)),
+            Err(GraphError::IncompatibleViews(_))
+        ));
+        assert!(matches!(
+            graph.union(&graph.persistent_graph()),
+            Err(GraphError::IncompatibleViews(_))
+        ));
+        assert!(matches!(
+            graph.union(&Graph::new()),
+            Err(GraphError::IncompatibleViews(_))
+        ));
+    }
+}
@@ -51,8 +51,9 @@ use crate::{
             storage::graph::{edges::edge_ref::EdgeStorageRef, nodes::node_ref::NodeStorageRef},
             view::{
                 internal::{
-                    Base, EdgeFilterOps, Immutable, InheritCoreOps, InheritLayerOps,
-                    InheritListOps, InheritMaterialize, NodeFilterOps, Static, TimeSemantics,
+                    Base, EdgeFilterOps, Immutable, InheritComputedPropertiesOps, InheritCoreOps,
+                    InheritLayerOps, InheritListOps, InheritMaterialize, NodeFilterOps, Static,
+                    TimeSemantics,
                 },
                 BoxedIter,
             },
@@ -122,6 +123,8 @@ impl<'graph, G: GraphViewOps<'graph>> InheritCoreOps for WindowedGraph<G> {}
 
 impl<'graph, G: GraphViewOps<'graph>> InheritMaterialize for WindowedGraph<G> {}
 
+impl<'graph, G: GraphViewOps<'graph>> InheritComputedPropertiesOps for WindowedGraph<G> {}
+
 impl<'graph, G: GraphViewOps<'graph>> InheritStaticPropertiesOps for WindowedGraph<G> {}
 
 impl<'graph, G: GraphViewOps<'graph>> InheritLayerOps for WindowedGraph<G> {}
@@ -0,0 +1,103 @@
+//! Cooperative cancellation for long-running algorithms.
+//!
+//! Algorithms do not take a cancellation argument. Instead, a [CancellationToken] is installed with
+//! [with_cancellation] and algorithms pick it up with [current] when they start. From then on they
+//! hold on to the token itself rather than looking it up again, so they keep observing it when
+//! their work moves to other threads (e.g., rayon workers). The task runner (and other long loops)
+//! stop early at the next check point once the token is cancelled. The result of a cancelled
+//! algorithm is incomplete and should be discarded by the caller.
+
+use std::{
+    cell::RefCell,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+};
+
+/// A cheaply cloneable flag used to ask running algorithms to stop
+#[derive(Clone, Debug, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Ask every algorithm running with this token to stop
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+thread_local! {
+    static CURRENT_TOKEN: RefCell<Option<CancellationToken>> = const { RefCell::new(None) };
+}
+
+struct RestoreToken(Option<CancellationToken>);
+
+impl Drop for RestoreToken {
+    fn drop(&mut self) {
+        let previous = self.0.take();
+        CURRENT_TOKEN.with(|current| *current.borrow_mut() = previous);
+    }
+}
+
+/// Run `f` with `token` installed on the current thread.
+///
+/// Algorithms started by `f` check the token between supersteps and return early once it is
+/// cancelled.
+pub fn with_cancellation<R>(token: &CancellationToken, f: impl FnOnce() -> R) -> R {
+    let previous = CURRENT_TOKEN.with(|current| current.borrow_mut().replace(token.clone()));
+    let _restore = RestoreToken(previous);
+    f()
+}
+
+/// The token installed by the enclosing [with_cancellation] call (a token that is never cancelled
+/// outside of it)
+pub fn current() -> CancellationToken {
+    CURRENT_TOKEN.with(|current| current.borrow().clone().unwrap_or_default())
+}
+
+/// Returns `true` if the token installed on the current thread has been cancelled
+pub fn is_cancelled() -> bool {
+    CURRENT_TOKEN.with(|current| {
+        current
+            .borrow()
+            .as_ref()
+            .map(|token| token.is_cancelled())
+            .unwrap_or(false)
+    })
+}
+
+#[cfg(test)]
+mod cancellation_tests {
+    use super::*;
+
+    #[test]
+    fn cancelled_token_is_only_visible_inside_scope() {
+        let token = CancellationToken::new();
+        token.cancel();
+        assert!(!is_cancelled());
+        assert!(with_cancellation(&token, is_cancelled));
+        assert!(!is_cancelled());
+    }
+
+    #[test]
+    fn current_token_can_be_moved_to_other_threads() {
+        let token = CancellationToken::new();
+        let captured = with_cancellation(&token, current);
+        assert!(!current().is_cancelled());
+        let worker = std::thread::spawn(move || {
+            while !captured.is_cancelled() {
+                std::thread::yield_now();
+            }
+        });
+        token.cancel();
+        worker.join().unwrap();
+    }
+}
@@ -1,4 +1,5 @@
 use super::{
+    cancellation,
     context::{Context, GlobalState},
     custom_pool,
     task::{Job, Step, Task},
@@ -223,6 +224,7 @@ impl<G: StaticGraphViewOps, CS: ComputeState> TaskRunner<G, CS> {
         global_initial_state: Option<Global<CS>>,
     ) -> B {
         let pool = num_threads.map(custom_pool).unwrap_or_else(|| POOL.clone());
+        let token = cancellation::current();
 
         let num_nodes = self.ctx.graph().unfiltered_num_nodes();
         let graph = self.ctx.graph();
@@ -258,7 +260,7 @@ impl<G: StaticGraphViewOps, CS: ComputeState> TaskRunner<G, CS> {
         // To allow the init step to cache stuff we will copy everything from cur_local_state to prev_local_state
         prev_local_state.clone_from_slice(&cur_local_state);
 
-        while !_done && self.ctx.ss() < steps && !tasks.is_empty() {
+        while !_done && self.ctx.ss() < steps && !tasks.is_empty() && !token.is_cancelled() {
             (_done, shard_state, global_state, cur_local_state) = self.run_task_list(
                 &tasks,
                 &pool,
@@ -0,0 +1,159 @@
+//! Constant properties of the edges of disk graphs.
+//!
+//! The columnar layout of a disk graph only holds the temporal properties of edges. Constant
+//! properties are stored per layer in the [EDGE_CONST_PROPS_TABLE] table next to it, with one
+//! column per property and one row per edge of the layer.
+
+use crate::{
+    core::{
+        entities::{properties::props::Meta, LayerIds, EID},
+        utils::errors::GraphError,
+        PropType,
+    },
+    db::api::{
+        storage::graph::edges::edge_storage_ops::EdgeStorageOps, view::internal::CoreGraphOps,
+    },
+    disk_graph::{
+        graph_impl::prop_conversion::{arrow_array_from_props, prop_from_array},
+        layer_tables::{read_layer_tables, write_layer_table, LayerTable},
+    },
+    prelude::{Graph, Prop},
+};
+use polars_arrow::{array::Array, datatypes::Field};
+use pometry_storage::graph::TemporalGraph;
+use rustc_hash::FxHashMap;
+use std::path::Path;
+
+pub const EDGE_CONST_PROPS_TABLE: &str = "edge_const_props";
+
+#[derive(Debug, Default)]
+pub struct EdgeConstProps {
+    /// The columns of each layer indexed by property id
+    layers: Vec<FxHashMap<usize, Box<dyn Array>>>,
+}
+
+impl EdgeConstProps {
+    /// The value of property `prop_id` of the copy of edge `eid` in layer `layer_id`
+    pub fn get(&self, layer_id: usize, eid: EID, prop_id: usize) -> Option<Prop> {
+        let column = self.layers.get(layer_id)?.get(&prop_id)?;
+        (eid.0 < column.len())
+            .then(|| prop_from_array(column.as_ref(), eid.0))
+            .flatten()
+    }
+
+    /// The ids of the properties of the copy of edge `eid` in layer `layer_id` in ascending order
+    pub fn prop_ids(&self, layer_id: usize, eid: EID) -> impl Iterator<Item = usize> + '_ {
+        let mut ids: Vec<_> = self
+            .layers
+            .get(layer_id)
+            .into_iter()
+            .flatten()
+            .filter(|(_, column)| eid.0 < column.len() && !column.is_null(eid.0))
+            .map(|(id, _)| *id)
+            .collect();
+        ids.sort_unstable();
+        ids.into_iter()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.layers.iter().all(|layer| layer.is_empty())
+    }
+
+    /// Write the constant properties of the edges of the in-memory `graph` for its disk copy
+    /// `disk_graph` to `graph_dir`
+    pub(crate) fn write_from_graph(
+        graph: &Graph,
+        disk_graph: &TemporalGraph,
+        graph_dir: impl AsRef<Path>,
+    ) -> Result<(), GraphError> {
+        let meta = graph.edge_meta().const_prop_meta();
+        let prop_types = meta.dtypes().clone();
+        let prop_ids: Vec<_> = (0..prop_types.len())
+            .filter(|id| prop_types[*id] != PropType::Empty)
+            .collect();
+        if prop_ids.is_empty() {
+            return Ok(());
+        }
+
+        let mut layers: Vec<_> = disk_graph
+            .layers()
+            .iter()
+            .map(|layer| vec![vec![None; layer.num_edges()]; prop_ids.len()])
+            .collect();
+        let storage = graph.core_graph();
+        let all_layers = LayerIds::All;
+        for edge in storage.edges().iter(LayerIds::All) {
+            let edge = edge.as_ref();
+            let src = graph.node_id(edge.src());
+            let dst = graph.node_id(edge.dst());
+            let Some((disk_src, disk_dst)) = disk_graph
+                .find_node((&src).into())
+                .zip(disk_graph.find_node((&dst).into()))
+            else {
+                continue;
+            };
+            for layer_id in edge.layer_ids_iter(&all_layers) {
+                let layer_name = graph.get_layer_name(layer_id);
+                let Some(disk_layer_id) = disk_graph.find_layer_id(&layer_name) else {
+                    continue;
+                };
+                let Some(eid) = disk_graph.layers()[disk_layer_id]
+                    .nodes_storage()
+                    .find_edge(disk_src, disk_dst)
+                else {
+                    continue;
+                };
+                for (values, prop_id) in layers[disk_layer_id].iter_mut().zip(&prop_ids) {
+                    values[eid.0] = edge.constant_prop_layer(layer_id, *prop_id);
+                }
+            }
+        }
+
+        for (layer_id, columns) in layers.into_iter().enumerate() {
+            let mut table = LayerTable {
+                fields: vec![],
+                columns: vec![],
+            };
+            for (values, prop_id) in columns.into_iter().zip(&prop_ids) {
+                let prop_type = prop_types[*prop_id];
+                if let Some(column) = arrow_array_from_props(values.into_iter(), prop_type) {
+                    table.fields.push(Field::new(
+                        meta.get_name(*prop_id).as_ref(),
+                        column.data_type().clone(),
+                        true,
+                    ));
+                    table.columns.push(column);
+                }
+            }
+            if !table.columns.is_empty() {
+                write_layer_table(graph_dir.as_ref(), EDGE_CONST_PROPS_TABLE, layer_id, table)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Read the constant properties stored in `graph_dir` and register them in `meta`
+    pub(crate) fn load(
+        graph_dir: impl AsRef<Path>,
+        num_layers: usize,
+        meta: &Meta,
+    ) -> Result<Self, GraphError> {
+        let layers = read_layer_tables(graph_dir, EDGE_CONST_PROPS_TABLE, num_layers)?
+            .into_iter()
+            .map(|table| {
+                let mut columns = FxHashMap::default();
+                for (field, column) in table
+                    .into_iter()
+                    .flat_map(|table| table.fields.into_iter().zip(table.columns))
+                {
+                    let prop_id = meta
+                        .resolve_prop_id(&field.name, field.data_type().into(), true)?
+                        .inner();
+                    columns.insert(prop_id, column);
+                }
+                Ok(columns)
+            })
+            .collect::<Result<_, GraphError>>()?;
+        Ok(Self { layers })
+    }
+}
@@ -0,0 +1,361 @@
+//! Edge deletions of disk graphs.
+//!
+//! The columnar layout of a disk graph only holds the additions of edges. The deletions that
+//! `PersistentGraph` needs for the lifespans of edges are stored per layer in the
+//! [EDGE_DELETIONS_TABLE] table next to it, with one row per edge of the layer holding the lists
+//! of the times and secondary indices of its deletions. The non-empty rows are indexed in memory
+//! by the layer and id of the edge.
+//!
+//! Deletions are attached to the copy of an edge in their layer. A disk graph cannot hold an edge
+//! without additions, deletions in a layer where the edge was never added are rejected.
+
+use crate::{
+    core::{
+        entities::{LayerIds, EID},
+        storage::timeindex::{TimeIndex, TimeIndexOps},
+        utils::errors::GraphError,
+    },
+    db::api::{
+        storage::graph::edges::edge_storage_ops::EdgeStorageOps, view::internal::CoreGraphOps,
+    },
+    disk_graph::{
+        graph_impl::ParquetLayerCols,
+        layer_tables::{read_layer_tables, write_layer_table, LayerTable},
+    },
+    io::{
+        arrow::dataframe::DFChunk,
+        parquet_loaders::{get_parquet_file_paths, process_parquet_file_to_df},
+    },
+    prelude::Graph,
+};
+use polars_arrow::{
+    array::{Array, ListArray, PrimitiveArray, StructArray},
+    datatypes::Field,
+    offset::Offsets,
+};
+use pometry_storage::graph::TemporalGraph;
+use raphtory_api::core::{entities::GidRef, storage::timeindex::TimeIndexEntry};
+use rustc_hash::FxHashMap;
+use std::path::Path;
+
+pub const EDGE_DELETIONS_TABLE: &str = "edge_deletions";
+
+const TIME_COL: &str = "time";
+const SECONDARY_INDEX_COL: &str = "secondary_index";
+
+#[derive(Debug, Default)]
+pub struct EdgeDeletions {
+    layers: Vec<FxHashMap<EID, TimeIndex<TimeIndexEntry>>>,
+}
+
+/// The global ids in column `idx` of `df`, ids can be integers or strings
+fn gid_col(df: &DFChunk, idx: usize) -> Result<Vec<Option<GidRef>>, GraphError> {
+    if let Some(col) = df.iter_col::<u64>(idx) {
+        Ok(col.map(|v| v.map(|v| GidRef::U64(*v))).collect())
+    } else if let Some(col) = df.iter_col::<i64>(idx) {
+        Ok(col.map(|v| v.map(|v| GidRef::U64(*v as u64))).collect())
+    } else if let Some(col) = df.utf8::<i32>(idx) {
+        Ok(col.map(|v| v.map(GidRef::Str)).collect())
+    } else if let Some(col) = df.utf8::<i64>(idx) {
+        Ok(col.map(|v| v.map(GidRef::Str)).collect())
+    } else {
+        Err(GraphError::LoadFailure(format!(
+            "Node ids of edge deletions must be integers or strings, found {:?}",
+            df.chunk[idx].data_type()
+        )))
+    }
+}
+
+impl EdgeDeletions {
+    /// The deletions of the copy of edge `eid` in layer `layer_id`
+    pub fn get(&self, layer_id: usize, eid: EID) -> Option<&TimeIndex<TimeIndexEntry>> {
+        self.layers.get(layer_id)?.get(&eid)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.layers.iter().all(|layer| layer.is_empty())
+    }
+
+    /// Add `deletions` to the edge from `src` to `dst` in layer `layer_id` of `graph`
+    fn insert(
+        &mut self,
+        graph: &TemporalGraph,
+        layer_id: usize,
+        src: GidRef,
+        dst: GidRef,
+        deletions: impl IntoIterator<Item = TimeIndexEntry>,
+    ) -> Result<(), GraphError> {
+        let eid = graph
+            .find_node(src)
+            .zip(graph.find_node(dst))
+            .and_then(|(src, dst)| graph.layers()[layer_id].nodes_storage().find_edge(src, dst))
+            .ok_or_else(|| GraphError::UnsupportedDiskEdgeDeletion {
+                src: src.to_owned(),
+                dst: dst.to_owned(),
+                layer: graph.layer_names()[layer_id].to_string(),
+            })?;
+        if self.layers.len() <= layer_id {
+            self.layers.resize_with(layer_id + 1, Default::default);
+        }
+        let index = self.layers[layer_id].entry(eid).or_default();
+        for t in deletions {
+            index.insert(t);
+        }
+        Ok(())
+    }
+
+    /// Add the deletions in the rows of `df` to layer `layer_id` of `graph`, the rows are numbered
+    /// from `row_offset` to order deletions at the same time
+    fn insert_rows(
+        &mut self,
+        graph: &TemporalGraph,
+        layer_id: usize,
+        df: &DFChunk,
+        (time_idx, src_idx, dst_idx): (usize, usize, usize),
+        row_offset: usize,
+    ) -> Result<(), GraphError> {
+        let src = gid_col(df, src_idx)?;
+        let dst = gid_col(df, dst_idx)?;
+        let time = df.time_iter_col(time_idx).ok_or_else(|| {
+            GraphError::LoadFailure(format!(
+                "Times of edge deletions must be integers or timestamps, found {:?}",
+                df.chunk[time_idx].data_type()
+            ))
+        })?;
+        for (row, ((src, dst), time)) in src.into_iter().zip(dst).zip(time).enumerate() {
+            if let (Some(src), Some(dst), Some(time)) = (src, dst, time) {
+                let t = TimeIndexEntry(time, row_offset + row);
+                self.insert(graph, layer_id, src, dst, [t])?;
+            }
+        }
+        Ok(())
+    }
+
+    /// The deletions of the in-memory `graph` for its disk copy `disk_graph`
+    pub(crate) fn from_graph(
+        graph: &Graph,
+        disk_graph: &TemporalGraph,
+    ) -> Result<Self, GraphError> {
+        let mut deletions = Self::default();
+        let storage = graph.core_graph();
+        let layer_meta = graph.edge_meta().layer_meta();
+        for edge in storage.edges().iter(LayerIds::All) {
+            let edge = edge.as_ref();
+            let src = graph.node_id(edge.src());
+            let dst = graph.node_id(edge.dst());
+            for (layer_id, layer_deletions) in edge.deletions_iter(&LayerIds::All) {
+                if layer_deletions.len() > 0 {
+                    let layer = layer_meta.get_name(layer_id);
+                    let disk_layer_id = disk_graph.find_layer_id(&layer).ok_or_else(|| {
+                        GraphError::UnsupportedDiskEdgeDeletion {
+                            src: src.clone(),
+                            dst: dst.clone(),
+                            layer: layer.to_string(),
+                        }
+                    })?;
+                    deletions.insert(
+                        disk_graph,
+                        disk_layer_id,
+                        (&src).into(),
+                        (&dst).into(),
+                        layer_deletions.iter(),
+                    )?;
+                }
+            }
+        }
+        Ok(deletions)
+    }
+
+    /// The deletions in the rows of the edge lists `deletions` for the single layer of `graph`
+    pub(crate) fn from_edge_lists(
+        graph: &TemporalGraph,
+        deletions: &[StructArray],
+        cols: (usize, usize, usize),
+    ) -> Result<Self, GraphError> {
+        let mut edge_deletions = Self::default();
+        let mut row_offset = 0;
+        for chunk in deletions {
+            let df = DFChunk {
+                chunk: chunk.values().to_vec(),
+            };
+            edge_deletions.insert_rows(graph, 0, &df, cols, row_offset)?;
+            row_offset += df.len();
+        }
+        Ok(edge_deletions)
+    }
+
+    /// The deletions in the `deletions_dir` parquet files of the layers of `graph`
+    pub(crate) fn from_parquets(
+        graph: &TemporalGraph,
+        layer_parquet_cols: &[ParquetLayerCols],
+    ) -> Result<Self, GraphError> {
+        let mut deletions = Self::default();
+        for layer_cols in layer_parquet_cols {
+            let Some(deletions_dir) = layer_cols.deletions_dir else {
+                continue;
+            };
+            let layer_id = graph.find_layer_id(layer_cols.layer).ok_or_else(|| {
+                GraphError::invalid_layer(layer_cols.layer.to_owned(), graph.get_valid_layers())
+            })?;
+            let col_names = [layer_cols.time_col, layer_cols.src_col, layer_cols.dst_col];
+            let mut row_offset = 0;
+            for path in get_parquet_file_paths(Path::new(deletions_dir))? {
+                let df_view = process_parquet_file_to_df(&path, &col_names)?;
+                let cols = (
+                    df_view.get_index(layer_cols.time_col)?,
+                    df_view.get_index(layer_cols.src_col)?,
+                    df_view.get_index(layer_cols.dst_col)?,
+                );
+                for chunk in df_view.chunks {
+                    let df = chunk?;
+                    deletions.insert_rows(graph, layer_id, &df, cols, row_offset)?;
+                    row_offset += df.len();
+                }
+            }
+        }
+        Ok(deletions)
+    }
+
+    /// The deletions of `sources` for the graph `merged` that they were merged into
+    pub(crate) fn merge(
+        sources: &[(&TemporalGraph, &EdgeDeletions)],
+        merged: &TemporalGraph,
+    ) -> Result<Self, GraphError> {
+        let mut deletions = Self::default();
+        for (graph, source) in sources {
+            let layer_names = graph.layer_names();
+            for (layer_id, layer) in source.layers.iter().enumerate() {
+                let merged_layer_id =
+                    merged
+                        .find_layer_id(&layer_names[layer_id])
+                        .ok_or_else(|| {
+                            GraphError::invalid_layer(
+                                layer_names[layer_id].to_string(),
+                                merged.get_valid_layers(),
+                            )
+                        })?;
+                for (eid, layer_deletions) in layer {
+                    let edge = graph.layers()[layer_id].edge(*eid);
+                    let gid = |vid| {
+                        graph.node_gid(vid).ok_or_else(|| {
+                            GraphError::LoadFailure(format!("Node {vid:?} has no global id"))
+                        })
+                    };
+                    deletions.insert(
+                        merged,
+                        merged_layer_id,
+                        gid(edge.src_id())?,
+                        gid(edge.dst_id())?,
+                        layer_deletions.iter(),
+                    )?;
+                }
+            }
+        }
+        Ok(deletions)
+    }
+
+    /// Read the deletions stored in `graph_dir` for the layers of `graph`, layers without a table
+    /// have no deletions
+    pub fn load(graph_dir: impl AsRef<Path>, graph: &TemporalGraph) -> Result<Self, GraphError> {
+        let tables = read_layer_tables(graph_dir, EDGE_DELETIONS_TABLE, graph.layers().len())?;
+        let layers = tables
+            .into_iter()
+            .map(|table| match table {
+                Some(table) => read_layer(&table),
+                None => Ok(FxHashMap::default()),
+            })
+            .collect::<Result<_, GraphError>>()?;
+        Ok(Self { layers })
+    }
+
+    /// Write the deletions for the layers of `graph` to `graph_dir`, nothing is written for layers
+    /// without deletions
+    pub fn write(
+        &self,
+        graph_dir: impl AsRef<Path>,
+        graph: &TemporalGraph,
+    ) -> Result<(), GraphError> {
+        for (layer_id, layer) in self.layers.iter().enumerate() {
+            if layer.is_empty() {
+                continue;
+            }
+            let num_edges = graph.layers()[layer_id].num_edges();
+            write_layer_table(
+                graph_dir.as_ref(),
+                EDGE_DELETIONS_TABLE,
+                layer_id,
+                layer_table(layer, num_edges)?,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// The table of the deletions of a layer with `num_edges` edges
+fn layer_table(
+    layer: &FxHashMap<EID, TimeIndex<TimeIndexEntry>>,
+    num_edges: usize,
+) -> Result<LayerTable, GraphError> {
+    let rows = (0..num_edges).map(|eid| layer.get(&EID(eid)));
+    let offsets =
+        Offsets::<i64>::try_from_lengths(rows.clone().map(|row| row.map_or(0, |ts| ts.len())))?;
+    let (times, secondary): (Vec<_>, Vec<_>) = rows
+        .flatten()
+        .flat_map(|ts| ts.iter())
+        .map(|TimeIndexEntry(t, i)| (t, i as u64))
+        .unzip();
+    let list = |values: Box<dyn Array>| -> Result<Box<dyn Array>, GraphError> {
+        let data_type = ListArray::<i64>::default_datatype(values.data_type().clone());
+        Ok(ListArray::<i64>::try_new(data_type, offsets.clone().into(), values, None)?.boxed())
+    };
+    let columns = vec![
+        list(PrimitiveArray::from_vec(times).boxed())?,
+        list(PrimitiveArray::from_vec(secondary).boxed())?,
+    ];
+    let fields = vec![
+        Field::new(TIME_COL, columns[0].data_type().clone(), false),
+        Field::new(SECONDARY_INDEX_COL, columns[1].data_type().clone(), false),
+    ];
+    Ok(LayerTable { fields, columns })
+}
+
+/// The deletions of the non-empty rows of `table`
+fn read_layer(table: &LayerTable) -> Result<FxHashMap<EID, TimeIndex<TimeIndexEntry>>, GraphError> {
+    let invalid = || GraphError::LoadFailure("Invalid edge deletions table".to_owned());
+    let list = |name| {
+        table
+            .column(name)
+            .and_then(|col| col.as_any().downcast_ref::<ListArray<i64>>())
+            .ok_or_else(invalid)
+    };
+    let (times, secondary) = (list(TIME_COL)?, list(SECONDARY_INDEX_COL)?);
+    let time_values = times
+        .values()
+        .as_any()
+        .downcast_ref::<PrimitiveArray<i64>>()
+        .ok_or_else(invalid)?;
+    let secondary_values = secondary
+        .values()
+        .as_any()
+        .downcast_ref::<PrimitiveArray<u64>>()
+        .ok_or_else(invalid)?;
+    if times.offsets() != secondary.offsets() || time_values.len() != secondary_values.len() {
+        return Err(invalid());
+    }
+
+    let mut layer = FxHashMap::default();
+    for (eid, window) in times.offsets().windows(2).enumerate() {
+        let (start, end) = (window[0] as usize, window[1] as usize);
+        if start == end {
+            continue;
+        }
+        let index: &mut TimeIndex<TimeIndexEntry> = layer.entry(EID(eid)).or_default();
+        for i in start..end {
+            index.insert(TimeIndexEntry(
+                time_values.value(i),
+                secondary_values.value(i) as usize,
+            ));
+        }
+    }
+    Ok(layer)
+}
@@ -8,29 +8,40 @@ use crate::{
         edges::edge_storage_ops::{EdgeStorageOps, TimeIndexRef},
         tprop_storage_ops::TPropOps,
     },
-    disk_graph::graph_impl::tprops::read_tprop_column,
+    disk_graph::{
+        graph_impl::tprops::{read_tprop_column, DiskTPropCol},
+        storage_interface::edge::DiskEdge,
+    },
 };
-use pometry_storage::{edge::Edge, tprops::DiskTProp};
 use raphtory_api::core::storage::timeindex::TimeIndexEntry;
 use rayon::prelude::*;
-use std::{iter, ops::Range};
+use std::ops::Range;
 
-impl<'a> EdgeStorageOps<'a> for Edge<'a> {
+impl<'a> EdgeStorageOps<'a> for DiskEdge<'a> {
     fn in_ref(self) -> EdgeRef {
-        EdgeRef::new_incoming(self.eid(), self.src_id(), self.dst_id()).at_layer(self.layer_id())
+        self.with_layer(EdgeRef::new_incoming(
+            self.eid(),
+            self.src_id(),
+            self.dst_id(),
+        ))
     }
 
     fn out_ref(self) -> EdgeRef {
-        EdgeRef::new_outgoing(self.eid(), self.src_id(), self.dst_id()).at_layer(self.layer_id())
+        self.with_layer(EdgeRef::new_outgoing(
+            self.eid(),
+            self.src_id(),
+            self.dst_id(),
+        ))
     }
 
     fn active(self, layer_ids: &LayerIds, w: Range<i64>) -> bool {
         // this is probably heavy
-        self.has_layer(layer_ids) && self.timestamps::<i64>().active_t(w)
+        self.layers(layer_ids)
+            .any(|edge| edge.timestamps::<i64>().active_t(w.clone()))
     }
 
     fn has_layer(self, layer_ids: &LayerIds) -> bool {
-        layer_ids.contains(&self.layer_id())
+        self.layers(layer_ids).next().is_some()
     }
 
     fn src(self) -> VID {
@@ -42,64 +53,46 @@ impl<'a> EdgeStorageOps<'a> for Edge<'a> {
     }
 
     fn layer_ids_iter(self, layer_ids: &'a LayerIds) -> impl Iterator<Item = usize> + 'a {
-        layer_ids
-            .contains(&self.layer_id())
-            .then_some(self.layer_id())
-            .into_iter()
+        self.layers(layer_ids).map(|edge| edge.layer_id())
     }
 
     fn layer_ids_par_iter(
         self,
         layer_ids: &'a LayerIds,
     ) -> impl ParallelIterator<Item = usize> + 'a {
-        layer_ids
-            .contains(&self.layer_id())
-            .then_some(self.layer_id())
+        self.layer_ids_iter(layer_ids)
+            .collect::<Vec<_>>()
             .into_par_iter()
     }
 
-    fn deletions_iter(
-        self,
-        _layer_ids: &'a LayerIds,
-    ) -> impl Iterator<Item = (usize, TimeIndexRef<'a>)> + 'a {
-        Box::new(iter::empty())
-    }
-
-    fn deletions_par_iter(
-        self,
-        _layer_ids: &'a LayerIds,
-    ) -> impl ParallelIterator<Item = (usize, TimeIndexRef<'a>)> + 'a {
-        rayon::iter::empty()
-    }
-
     fn additions(self, layer_id: usize) -> TimeIndexRef<'a> {
-        if self.layer_id() != layer_id {
-            TimeIndexRef::Ref(&TimeIndex::Empty)
-        } else {
-            TimeIndexRef::External(self.timestamps::<TimeIndexEntry>())
+        match self.layer(layer_id) {
+            Some(edge) => TimeIndexRef::External(edge.timestamps::<TimeIndexEntry>()),
+            None => TimeIndexRef::Ref(&TimeIndex::Empty),
         }
     }
 
-    fn deletions(self, _layer_id: usize) -> TimeIndexRef<'a> {
-        TimeIndexRef::Ref(&TimeIndex::Empty)
+    fn deletions(self, layer_id: usize) -> TimeIndexRef<'a> {
+        TimeIndexRef::Ref(self.layer_deletions(layer_id))
     }
 
     fn has_temporal_prop(self, layer_ids: &LayerIds, prop_id: usize) -> bool {
-        layer_ids.contains(&self.layer_id()) && self.has_temporal_prop_inner(prop_id)
+        self.layers(layer_ids)
+            .any(|edge| edge.has_temporal_prop_inner(prop_id))
     }
 
     fn temporal_prop_layer(self, layer_id: usize, prop_id: usize) -> impl TPropOps<'a> + Sync + 'a {
-        if layer_id == self.layer_id() {
-            self.temporal_property_field(prop_id)
-                .and_then(|field| read_tprop_column(prop_id, field, self))
-        } else {
-            None
-        }
-        .unwrap_or(DiskTProp::empty())
+        self.layer(layer_id)
+            .and_then(|edge| {
+                let layer = self.graph_layer(layer_id);
+                edge.temporal_property_field(prop_id)
+                    .and_then(|field| read_tprop_column(prop_id, field, edge, layer))
+            })
+            .unwrap_or(DiskTPropCol::empty())
     }
 
-    fn constant_prop_layer(self, _layer_id: usize, _prop_id: usize) -> Option<Prop> {
-        // TODO: constant edge properties not implemented in diskgraph yet
-        None
+    fn constant_prop_layer(self, layer_id: usize, prop_id: usize) -> Option<Prop> {
+        let edge = self.layer(layer_id)?;
+        self.const_props().get(layer_id, edge.eid(), prop_id)
     }
 }
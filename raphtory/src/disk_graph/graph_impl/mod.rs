@@ -1,6 +1,9 @@
 use std::path::Path;
 
-use crate::{core::utils::errors::GraphError, disk_graph::DiskGraphStorage, prelude::Graph};
+use crate::{
+    core::utils::errors::GraphError, db::graph::views::deletion_graph::PersistentGraph,
+    disk_graph::DiskGraphStorage, prelude::Graph,
+};
 
 mod edge_storage_ops;
 mod interop;
@@ -15,6 +18,9 @@ pub struct ParquetLayerCols<'a> {
     pub src_col: &'a str,
     pub dst_col: &'a str,
     pub time_col: &'a str,
+    /// Parquet files with the edge deletions of the layer, they have the same columns as the
+    /// additions
+    pub deletions_dir: Option<&'a str>,
 }
 
 impl Graph {
@@ -26,6 +32,16 @@ impl Graph {
     }
 }
 
+impl PersistentGraph {
+    /// Save the graph in disk_graph format together with its edge deletions
+    pub fn persist_as_disk_graph(
+        &self,
+        graph_dir: impl AsRef<Path>,
+    ) -> Result<DiskGraphStorage, GraphError> {
+        DiskGraphStorage::from_graph(&self.event_graph(), graph_dir)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::{
@@ -33,17 +49,31 @@ mod test {
         sync::Arc,
     };
 
+    use chrono::DateTime;
     use itertools::Itertools;
     use proptest::{prelude::*, sample::size_range};
     use rayon::prelude::*;
     use tempfile::TempDir;
 
+    use polars_arrow::{
+        array::{PrimitiveArray, StructArray},
+        datatypes::{ArrowDataType as DataType, Field},
+    };
     use pometry_storage::{graph::TemporalGraph, properties::Properties};
 
     use crate::{
-        db::api::{storage::graph::storage_ops::GraphStorage, view::StaticGraphViewOps},
-        disk_graph::Time,
+        core::{
+            entities::{LayerIds, EID, ELID},
+            utils::errors::GraphError,
+            DocumentInput, Lifespan, PropType,
+        },
+        db::{
+            api::{storage::graph::storage_ops::GraphStorage, view::StaticGraphViewOps},
+            graph::{graph::assert_graph_equal, views::deletion_graph::PersistentGraph},
+        },
+        disk_graph::{storage_interface::edges_ref::DiskEdgesRef, Time},
         prelude::*,
+        serialise::{StableDecode, StableEncode},
     };
 
     use super::{DiskGraphStorage, ParquetLayerCols};
@@ -294,6 +324,120 @@ mod test {
         assert_eq!(disk_graph.num_edges(0), 1);
     }
 
+    fn exploded_edges(g: &impl StaticGraphViewOps) -> Vec<(u64, u64, i64, String, Option<Prop>)> {
+        g.edges()
+            .explode()
+            .iter()
+            .map(|e| {
+                (
+                    e.src().id().as_u64().unwrap(),
+                    e.dst().id().as_u64().unwrap(),
+                    e.time().unwrap(),
+                    e.layer_name().unwrap().to_string(),
+                    e.properties().get("weight"),
+                )
+            })
+            .sorted_by(|a, b| (a.0, a.1, a.2, &a.3).cmp(&(b.0, b.1, b.2, &b.3)))
+            .collect()
+    }
+
+    fn check_multi_layer_edges(mem: &impl StaticGraphViewOps, disk: &impl StaticGraphViewOps) {
+        assert_eq!(exploded_edges(disk), exploded_edges(mem));
+        // edges in several layers of the view are only returned once
+        assert_eq!(disk.count_edges(), mem.count_edges());
+        assert_eq!(
+            disk.edges()
+                .id()
+                .map(|(src, dst)| (src.as_u64().unwrap(), dst.as_u64().unwrap()))
+                .sorted()
+                .collect_vec(),
+            mem.edges()
+                .id()
+                .map(|(src, dst)| (src.as_u64().unwrap(), dst.as_u64().unwrap()))
+                .sorted()
+                .collect_vec()
+        );
+        for mem_edge in mem.edges() {
+            let disk_edge = disk.edge(mem_edge.src().id(), mem_edge.dst().id()).unwrap();
+            assert_eq!(disk_edge.history(), mem_edge.history());
+            assert_eq!(
+                disk_edge.layer_names().sorted().collect_vec(),
+                mem_edge.layer_names().sorted().collect_vec()
+            );
+            assert_eq!(
+                disk_edge
+                    .properties()
+                    .temporal()
+                    .get("weight")
+                    .map(|weight| weight.iter().collect_vec()),
+                mem_edge
+                    .properties()
+                    .temporal()
+                    .get("weight")
+                    .map(|weight| weight.iter().collect_vec())
+            );
+            assert_eq!(
+                disk_edge.explode().time().flatten().collect_vec(),
+                mem_edge.explode().time().flatten().collect_vec()
+            );
+            assert_eq!(
+                disk_edge
+                    .explode_layers()
+                    .layer_name()
+                    .flatten()
+                    .collect_vec(),
+                mem_edge
+                    .explode_layers()
+                    .layer_name()
+                    .flatten()
+                    .collect_vec()
+            );
+        }
+    }
+
+    #[test]
+    fn test_multi_layer_edges() {
+        let mem_graph = Graph::new();
+        mem_graph
+            .add_edge(0, 1, 2, [("weight", 1.0)], Some("a"))
+            .unwrap();
+        mem_graph
+            .add_edge(1, 1, 2, [("weight", 2.0)], Some("b"))
+            .unwrap();
+        mem_graph
+            .add_edge(2, 1, 2, [("weight", 3.0)], Some("a"))
+            .unwrap();
+        mem_graph
+            .add_edge(3, 2, 3, [("weight", 4.0)], Some("b"))
+            .unwrap();
+        mem_graph
+            .add_edge(4, 1, 3, [("weight", 5.0)], Some("c"))
+            .unwrap();
+        mem_graph
+            .add_edge(5, 1, 3, [("weight", 6.0)], Some("a"))
+            .unwrap();
+        let test_dir = TempDir::new().unwrap();
+        let storage = DiskGraphStorage::from_graph(&mem_graph, test_dir.path()).unwrap();
+        let edges = DiskEdgesRef::new(&storage);
+        assert_eq!(edges.count(&LayerIds::All), 3);
+        assert_eq!(edges.iter(LayerIds::All).count(), 3);
+        assert_eq!(edges.par_iter(LayerIds::All).count(), 3);
+        assert!(edges.edge(ELID::new(EID(100), None)).is_none());
+        assert!(edges.edge(ELID::new(EID(100), Some(0))).is_none());
+        let disk_graph = storage.into_graph();
+
+        check_multi_layer_edges(&mem_graph, &disk_graph);
+        check_multi_layer_edges(
+            &mem_graph.layers(["a", "b"]).unwrap(),
+            &disk_graph.layers(["a", "b"]).unwrap(),
+        );
+        check_multi_layer_edges(&mem_graph.window(1, 5), &disk_graph.window(1, 5));
+        check_multi_layer_edges(
+            &mem_graph.valid_layers(["a", "c", "missing"]),
+            &disk_graph.valid_layers(["a", "c", "missing"]),
+        );
+    }
+
     #[test]
     fn test_node_properties() {
         let mem_graph = Graph::new();
@@ -336,6 +480,266 @@ mod test {
         assert_eq!(props.get("const_float").unwrap_f64(), 0.314);
     }
 
+    #[test]
+    fn test_all_property_types() {
+        let time = DateTime::from_timestamp_millis(1_700_000_000_123).unwrap();
+        let list = Prop::List(Arc::new(vec![Prop::I32(1), Prop::I32(2)]));
+        let map = Prop::Map(Arc::new(
+            [("a".into(), Prop::U8(1)), ("b".into(), Prop::F64(0.5))]
+                .into_iter()
+                .collect(),
+        ));
+        let document = Prop::Document(DocumentInput {
+            content: "text".to_owned(),
+            life: Lifespan::Interval { start: 1, end: 5 },
+        });
+        let props = [
+            ("u8", Prop::U8(1)),
+            ("u16", Prop::U16(2)),
+            ("dtime", Prop::DTime(time)),
+            ("ndtime", Prop::NDTime(time.naive_utc())),
+            ("list", list),
+            ("map", map),
+            ("document", document),
+        ];
+
+        let mem_graph = Graph::new();
+        mem_graph
+            .add_edge(0, 0, 1, props.clone(), None)
+            .unwrap()
+            .add_constant_properties(props.clone(), None)
+            .unwrap();
+        mem_graph
+            .add_node(1, 0, props.clone(), None)
+            .unwrap()
+            .add_constant_properties(props.map(|(name, prop)| (format!("const_{name}"), prop)))
+            .unwrap();
+        let test_dir = TempDir::new().unwrap();
+        mem_graph.persist_as_disk_graph(test_dir.path()).unwrap();
+
+        let disk_graph = DiskGraphStorage::load_from_dir(test_dir.path())
+            .unwrap()
+            .into_graph();
+        let edge = disk_graph.edge(0, 1).unwrap();
+        let node = disk_graph.node(0).unwrap();
+        for (name, prop) in props {
+            assert_eq!(
+                edge.properties().temporal().get(name).unwrap().latest(),
+                Some(prop.clone())
+            );
+            assert_eq!(edge.properties().constant().get(name), Some(prop.clone()));
+            assert_eq!(
+                node.properties().temporal().get(name).unwrap().latest(),
+                Some(prop.clone())
+            );
+            assert_eq!(
+                node.properties().constant().get(&format!("const_{name}")),
+                Some(prop)
+            );
+        }
+    }
+
+    #[test]
+    fn test_edge_constant_properties_in_layers() {
+        let mem_graph = Graph::new();
+        mem_graph
+            .add_edge(0, 0, 1, NO_PROPS, Some("a"))
+            .unwrap()
+            .add_constant_properties([("name", "first")], Some("a"))
+            .unwrap();
+        mem_graph
+            .add_edge(1, 0, 1, NO_PROPS, Some("b"))
+            .unwrap()
+            .add_constant_properties([("name", "second")], Some("b"))
+            .unwrap();
+        mem_graph.add_edge(2, 1, 2, NO_PROPS, Some("b")).unwrap();
+        let test_dir = TempDir::new().unwrap();
+        mem_graph.persist_as_disk_graph(test_dir.path()).unwrap();
+
+        let disk_graph = DiskGraphStorage::load_from_dir(test_dir.path())
+            .unwrap()
+            .into_graph();
+        assert_graph_equal(&disk_graph, &mem_graph);
+        let edge = disk_graph.edge(0, 1).unwrap();
+        assert_eq!(
+            edge.layers("a")
+                .unwrap()
+                .properties()
+                .constant()
+                .get("name"),
+            Some(Prop::str("first"))
+        );
+        assert_eq!(
+            edge.properties().constant().get("name"),
+            mem_graph
+                .edge(0, 1)
+                .unwrap()
+                .properties()
+                .constant()
+                .get("name")
+        );
+        assert_eq!(
+            disk_graph
+                .edge(1, 2)
+                .unwrap()
+                .properties()
+                .constant()
+                .get("name"),
+            None
+        );
+    }
+
+    #[test]
+    fn test_heterogeneous_list_property() {
+        let mem_graph = Graph::new();
+        let list = Prop::List(Arc::new(vec![Prop::I32(1), Prop::str("a")]));
+        mem_graph.add_edge(0, 0, 1, [("list", list)], None).unwrap();
+        let test_dir = TempDir::new().unwrap();
+        assert!(matches!(
+            mem_graph.persist_as_disk_graph(test_dir.path()),
+            Err(GraphError::UnsupportedDiskPropType(PropType::List))
+        ));
+    }
+
+    #[test]
+    fn test_unsupported_property_type() {
+        let mem_graph = Graph::new();
+        mem_graph
+            .add_edge(0, 0, 1, [("graph", Prop::Graph(Graph::new()))], None)
+            .unwrap();
+        let test_dir = TempDir::new().unwrap();
+        assert!(matches!(
+            mem_graph.persist_as_disk_graph(test_dir.path()),
+            Err(GraphError::UnsupportedDiskPropType(PropType::Graph))
+        ));
+    }
+
+    #[test]
+    fn test_edge_deletions() {
+        let mem_graph = PersistentGraph::new();
+        mem_graph
+            .add_edge(0, 1, 2, [("weight", 1i64)], None)
+            .unwrap();
+        mem_graph.delete_edge(3, 1, 2, None).unwrap();
+        mem_graph
+            .add_edge(5, 1, 2, [("weight", 2i64)], None)
+            .unwrap();
+        mem_graph.add_edge(1, 2, 3, NO_PROPS, Some("a")).unwrap();
+        mem_graph.delete_edge(4, 2, 3, Some("a")).unwrap();
+        mem_graph.add_edge(2, 3, 1, NO_PROPS, None).unwrap();
+
+        let test_dir = TempDir::new().unwrap();
+        mem_graph.persist_as_disk_graph(test_dir.path()).unwrap();
+        let disk_graph = DiskGraphStorage::load_from_dir(test_dir.path())
+            .unwrap()
+            .into_persistent_graph();
+
+        assert_graph_equal(&disk_graph, &mem_graph);
+        assert_eq!(disk_graph.edge(1, 2).unwrap().deletions(), vec![3]);
+        assert_eq!(disk_graph.edge(2, 3).unwrap().deletions(), vec![4]);
+        assert!(disk_graph.window(4, 5).edge(1, 2).is_none());
+        assert!(disk_graph.window(6, 10).edge(2, 3).is_none());
+        assert!(disk_graph.window(6, 10).edge(3, 1).is_some());
+    }
+
+    #[test]
+    fn test_deletion_without_additions_in_layer() {
+        let mem_graph = PersistentGraph::new();
+        mem_graph.add_edge(0, 1, 2, NO_PROPS, Some("a")).unwrap();
+        mem_graph.add_edge(0, 2, 3, NO_PROPS, Some("b")).unwrap();
+        mem_graph.delete_edge(1, 1, 2, Some("b")).unwrap();
+
+        let test_dir = TempDir::new().unwrap();
+        assert!(matches!(
+            mem_graph.persist_as_disk_graph(test_dir.path()),
+            Err(GraphError::UnsupportedDiskEdgeDeletion { .. })
+        ));
+    }
+
+    #[test]
+    fn test_edge_list_deletions() {
+        let edge_list = |src: Vec<u64>, dst: Vec<u64>, time: Vec<i64>| {
+            StructArray::new(
+                DataType::Struct(vec![
+                    Field::new("src", DataType::UInt64, false),
+                    Field::new("dst", DataType::UInt64, false),
+                    Field::new("time", DataType::Int64, false),
+                ]),
+                vec![
+                    PrimitiveArray::from_vec(src).boxed(),
+                    PrimitiveArray::from_vec(dst).boxed(),
+                    PrimitiveArray::from_vec(time).boxed(),
+                ],
+                None,
+            )
+        };
+        let additions = edge_list(vec![1, 1, 2], vec![2, 2, 3], vec![0, 5, 1]);
+        let deletions = edge_list(vec![1, 2], vec![2, 3], vec![3, 4]);
+
+        let test_dir = TempDir::new().unwrap();
+        DiskGraphStorage::load_from_edge_lists_with_deletions(
+            &[additions],
+            &[deletions],
+            10,
+            10,
+            test_dir.path(),
+            2,
+            0,
+            1,
+        )
+        .unwrap();
+        let disk_graph = DiskGraphStorage::load_from_dir(test_dir.path())
+            .unwrap()
+            .into_persistent_graph();
+
+        assert_eq!(disk_graph.edge(1, 2).unwrap().deletions(), vec![3]);
+        assert_eq!(disk_graph.edge(2, 3).unwrap().deletions(), vec![4]);
+        assert!(disk_graph.window(4, 5).edge(1, 2).is_none());
+        assert!(disk_graph.window(6, 10).edge(1, 2).is_some());
+        assert!(disk_graph.window(6, 10).edge(2, 3).is_none());
+
+        let missing = edge_list(vec![3], vec![1], vec![2]);
+        let test_dir = TempDir::new().unwrap();
+        assert!(matches!(
+            DiskGraphStorage::load_from_edge_lists_with_deletions(
+                &[edge_list(vec![1], vec![2], vec![0])],
+                &[missing],
+                10,
+                10,
+                test_dir.path(),
+                2,
+                0,
+                1,
+            ),
+            Err(GraphError::UnsupportedDiskEdgeDeletion { .. })
+        ));
+    }
+
+    #[test]
+    fn test_encode_multi_layer_disk_graph() {
+        let mem_graph = PersistentGraph::new();
+        mem_graph
+            .add_edge(0, 1, 2, [("weight", 1i64)], None)
+            .unwrap();
+        mem_graph
+            .add_edge(1, 1, 2, [("weight", 2i64)], Some("a"))
+            .unwrap();
+        mem_graph.add_edge(2, 2, 3, NO_PROPS, Some("b")).unwrap();
+        mem_graph.delete_edge(3, 1, 2, Some("a")).unwrap();
+
+        let test_dir = TempDir::new().unwrap();
+        let disk_graph = mem_graph
+            .persist_as_disk_graph(test_dir.path())
+            .unwrap()
+            .into_persistent_graph();
+        let decoded = PersistentGraph::decode_from_bytes(&disk_graph.encode_to_vec()).unwrap();
+        assert_graph_equal(&decoded, &mem_graph);
+        assert_eq!(
+            decoded.edge(1, 2).unwrap().layers("a").unwrap().deletions(),
+            vec![3]
+        );
+    }
+
     #[test]
     fn test_only_const_node_properties() {
         let g = Graph::new();
@@ -401,6 +805,7 @@ mod test {
                 src_col: "source",
                 dst_col: "destination",
                 time_col: "time",
+                deletions_dir: None,
             },
             ParquetLayerCols {
                 parquet_dir: v1_layer_path.to_str().unwrap(),
@@ -408,6 +813,7 @@ mod test {
                 src_col: "src",
                 dst_col: "dst",
                 time_col: "Time",
+                deletions_dir: None,
             },
         ];
 
@@ -765,6 +1171,56 @@ mod storage_tests {
         assert_graph_equal(&merged_g_disk.into_graph(), &merged_g_expected)
     }
 
+    #[test]
+    fn test_stage_and_compact() {
+        let base = Graph::new();
+        add_edges(&base, &[(0, 1, 2), (1, 2, 3)]);
+        let base_dir = TempDir::new().unwrap();
+        let base_disk = base.persist_as_disk_graph(&base_dir).unwrap();
+        assert!(!base_disk.has_staged_appends().unwrap());
+
+        // nodes of the batches are not added in order
+        let batch1 = Graph::new();
+        batch1.add_edge(2, 5, 1, NO_PROPS, None).unwrap();
+        batch1.add_edge(3, 2, 3, NO_PROPS, None).unwrap();
+        let batch2 = Graph::new();
+        batch2.add_edge(4, 4, 0, NO_PROPS, None).unwrap();
+        base_disk.stage_graph(&batch1).unwrap();
+        base_disk.stage_graph(&batch2).unwrap();
+        assert_eq!(base_disk.staged_dirs().unwrap().len(), 2);
+
+        // the base graph is unchanged until it is compacted
+        assert_graph_equal(&base_disk.clone().into_graph(), &base);
+
+        let compacted_dir = TempDir::new().unwrap();
+        let compacted = base_disk.compact(compacted_dir.path()).unwrap();
+        assert!(!compacted.has_staged_appends().unwrap());
+
+        let expected = Graph::new();
+        add_edges(&expected, &[(0, 1, 2), (1, 2, 3)]);
+        for (t, src, dst) in [(2, 5, 1), (3, 2, 3), (4, 4, 0)] {
+            expected.add_edge(t, src, dst, NO_PROPS, None).unwrap();
+        }
+        assert_graph_equal(&compacted.into_graph(), &expected);
+    }
+
+    #[test]
+    fn test_compact_without_staged_appends() {
+        let base = Graph::new();
+        add_edges(&base, &[(0, 1, 2), (1, 2, 3)]);
+        let base_dir = TempDir::new().unwrap();
+        let base_disk = base.persist_as_disk_graph(&base_dir).unwrap();
+
+        let compacted_dir = TempDir::new().unwrap();
+        let compacted = base_disk.compact(compacted_dir.path()).unwrap();
+        assert_eq!(compacted.graph_dir(), compacted_dir.path());
+        drop(base_disk);
+        drop(base_dir);
+
+        let reloaded = DiskGraphStorage::load_from_dir(compacted_dir.path()).unwrap();
+        assert_graph_equal(&reloaded.into_graph(), &base);
+    }
+
     #[test]
     fn test_merge_proptest() {
         proptest!(|(left_edges in prop::collection::vec((0i64..10, 0u64..10, 0u64..10), 0..=100), right_edges in prop::collection::vec((0i64..10, 0u64..10, 0u64..10), 0..=100))| {
@@ -1,18 +1,25 @@
 use crate::{
     arrow2::{
-        array::{Array, BooleanArray, PrimitiveArray, Utf8Array},
+        array::{Array, BooleanArray, PrimitiveArray, StructArray, Utf8Array},
         datatypes::{ArrowDataType as DataType, ArrowSchema as Schema, Field},
     },
     core::{
-        entities::{properties::props::PropMapper, VID},
+        entities::{properties::props::PropMapper, LayerIds, VID},
         storage::timeindex::TimeIndexOps,
-        utils::iter::GenLockedIter,
-        PropType,
+        utils::{errors::GraphError, iter::GenLockedIter},
+        DocumentInput, IntoPropMap, Lifespan, PropType,
     },
     db::api::{
-        storage::graph::{nodes::node_storage_ops::NodeStorageOps, tprop_storage_ops::TPropOps},
+        storage::graph::{
+            edges::edge_storage_ops::EdgeStorageOps, nodes::node_storage_ops::NodeStorageOps,
+            tprop_storage_ops::TPropOps,
+        },
         view::internal::CoreGraphOps,
     },
+    io::{
+        arrow::prop_handler::value_as_prop,
+        parquet_export::{merge_data_types, nested_data_type, prop_array, scalar_data_type},
+    },
     prelude::{Graph, Prop, PropUnwrap},
 };
 use itertools::Itertools;
@@ -20,7 +27,8 @@ use pometry_storage::{
     properties::{node_ts, NodePropsBuilder, Properties},
     RAError,
 };
-use std::path::Path;
+use raphtory_api::core::storage::arc_str::ArcStr;
+use std::{collections::HashMap, path::Path};
 
 pub fn make_node_properties_from_graph(
     graph: &Graph,
@@ -94,30 +102,219 @@ pub fn make_node_properties_from_graph(
     Ok(props)
 }
 
-pub fn arrow_dtype_from_prop_type(prop_type: PropType) -> DataType {
-    match prop_type {
-        PropType::Str => DataType::LargeUtf8,
-        PropType::U8 => DataType::UInt8,
-        PropType::U16 => DataType::UInt16,
-        PropType::I32 => DataType::Int32,
-        PropType::I64 => DataType::Int64,
-        PropType::U32 => DataType::UInt32,
-        PropType::U64 => DataType::UInt64,
-        PropType::F32 => DataType::Float32,
-        PropType::F64 => DataType::Float64,
-        PropType::Bool => DataType::Boolean,
-        PropType::Empty
-        | PropType::List
+/// Name of the Arrow extension type of the columns that hold documents
+pub const DOCUMENT_EXTENSION: &str = "raphtory.document";
+
+impl From<&DataType> for PropType {
+    fn from(value: &DataType) -> Self {
+        match value {
+            DataType::Utf8 => PropType::Str,
+            DataType::LargeUtf8 => PropType::Str,
+            DataType::UInt8 => PropType::U8,
+            DataType::UInt16 => PropType::U16,
+            DataType::Int32 => PropType::I32,
+            DataType::Int64 => PropType::I64,
+            DataType::UInt32 => PropType::U32,
+            DataType::UInt64 => PropType::U64,
+            DataType::Float32 => PropType::F32,
+            DataType::Float64 => PropType::F64,
+            DataType::Boolean => PropType::Bool,
+            DataType::Timestamp(_, Some(_)) => PropType::DTime,
+            DataType::Timestamp(_, None) => PropType::NDTime,
+            DataType::LargeList(_) => PropType::List,
+            DataType::Struct(_) => PropType::Map,
+            DataType::Extension(name, _, _) if name == DOCUMENT_EXTENSION => PropType::Document,
+
+            _ => PropType::Empty,
+        }
+    }
+}
+
+/// Check that all the properties of `graph` can be stored in a disk graph
+pub fn check_disk_graph_props(graph: &Graph) -> Result<(), GraphError> {
+    let metas = [
+        (
+            graph.node_meta().temporal_prop_meta(),
+            PropKind::NodeTemporal,
+        ),
+        (graph.node_meta().const_prop_meta(), PropKind::NodeConst),
+        (
+            graph.edge_meta().temporal_prop_meta(),
+            PropKind::EdgeTemporal,
+        ),
+        (graph.edge_meta().const_prop_meta(), PropKind::EdgeConst),
+    ];
+    for (meta, kind) in metas {
+        for (prop_id, prop_type) in meta.dtypes().iter().enumerate() {
+            match prop_type {
+                // properties without values do not get a column
+                PropType::Empty => {}
+                // the items of lists and the fields of maps need to have the same type for all
+                // values of a property
+                PropType::List | PropType::Map => {
+                    let mut data_type = None;
+                    for_each_prop_value(graph, kind, prop_id, |value| {
+                        data_type = merge_data_types(data_type.take(), nested_data_type(&value)?)?;
+                        Ok(())
+                    })
+                    .map_err(|_| GraphError::UnsupportedDiskPropType(*prop_type))?;
+                }
+                _ => {
+                    arrow_dtype_from_prop_type(*prop_type)?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+#[derive(Copy, Clone, Debug)]
+enum PropKind {
+    NodeTemporal,
+    NodeConst,
+    EdgeTemporal,
+    EdgeConst,
+}
+
+/// Call `f` with every value of the property `prop_id` of kind `kind` in `graph`
+fn for_each_prop_value(
+    graph: &Graph,
+    kind: PropKind,
+    prop_id: usize,
+    mut f: impl FnMut(Prop) -> Result<(), GraphError>,
+) -> Result<(), GraphError> {
+    let gs = graph.core_graph();
+    match kind {
+        PropKind::NodeTemporal | PropKind::NodeConst => {
+            for vid in 0..graph.unfiltered_num_nodes() {
+                let node = gs.node_entry(VID(vid));
+                let node = node.as_ref();
+                match kind {
+                    PropKind::NodeTemporal => {
+                        for (_, value) in node.tprop(prop_id).iter() {
+                            f(value)?;
+                        }
+                    }
+                    _ => {
+                        if let Some(value) = node.prop(prop_id) {
+                            f(value)?;
+                        }
+                    }
+                }
+            }
+        }
+        PropKind::EdgeTemporal | PropKind::EdgeConst => {
+            let all_layers = LayerIds::All;
+            for edge in gs.edges().iter(LayerIds::All) {
+                let edge = edge.as_ref();
+                for layer_id in edge.layer_ids_iter(&all_layers) {
+                    match kind {
+                        PropKind::EdgeTemporal => {
+                            for (_, value) in edge.temporal_prop_layer(layer_id, prop_id).iter() {
+                                f(value)?;
+                            }
+                        }
+                        _ => {
+                            if let Some(value) = edge.constant_prop_layer(layer_id, prop_id) {
+                                f(value)?;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// The Arrow type of the columns of properties of type `prop_type`. The type of lists and maps
+/// depends on their values and is not known from the property type alone.
+pub fn arrow_dtype_from_prop_type(prop_type: PropType) -> Result<DataType, GraphError> {
+    let dtype = match prop_type {
+        PropType::Document => document_data_type(),
+        PropType::List
         | PropType::Map
-        | PropType::NDTime
+        | PropType::Empty
         | PropType::Graph
-        | PropType::PersistentGraph
-        | PropType::Document
-        | PropType::DTime => panic!("{prop_type:?} not supported as disk_graph property"),
+        | PropType::PersistentGraph => return Err(GraphError::UnsupportedDiskPropType(prop_type)),
+        prop_type => scalar_data_type(prop_type)?,
+    };
+    Ok(dtype)
+}
+
+/// Documents are stored as structs with the content and the bounds of their lifespan, the bounds
+/// that do not apply to the lifespan are null
+fn document_data_type() -> DataType {
+    DataType::Extension(
+        DOCUMENT_EXTENSION.to_owned(),
+        Box::new(DataType::Struct(vec![
+            Field::new("content", DataType::LargeUtf8, true),
+            Field::new("start", DataType::Int64, true),
+            Field::new("end", DataType::Int64, true),
+            Field::new("time", DataType::Int64, true),
+        ])),
+        None,
+    )
+}
+
+fn document_array(props: impl Iterator<Item = Option<Prop>>) -> Option<Box<dyn Array>> {
+    let values = props
+        .map(|prop| match prop? {
+            Prop::Document(document) => {
+                let mut fields = vec![("content", Prop::str(document.content.as_str()))];
+                match document.life {
+                    Lifespan::Interval { start, end } => {
+                        fields.extend([("start", Prop::I64(start)), ("end", Prop::I64(end))])
+                    }
+                    Lifespan::Event { time } => fields.push(("time", Prop::I64(time))),
+                    Lifespan::Inherited => {}
+                }
+                Some(fields.into_prop_map())
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let data_type = document_data_type();
+    let array = prop_array(data_type.to_logical_type(), values).ok()?;
+    let array = array.as_any().downcast_ref::<StructArray>()?;
+    let array = StructArray::try_new(
+        data_type,
+        array.values().to_vec(),
+        array.validity().cloned(),
+    )
+    .ok()?;
+    (array.null_count() < array.len()).then_some(array.boxed())
+}
+
+fn document_from_map(map: &HashMap<ArcStr, Prop>) -> Option<Prop> {
+    let content = map.get("content")?.clone().into_str()?;
+    let life = match (map.get("start"), map.get("end"), map.get("time")) {
+        (Some(Prop::I64(start)), Some(Prop::I64(end)), _) => Lifespan::Interval {
+            start: *start,
+            end: *end,
+        },
+        (_, _, Some(Prop::I64(time))) => Lifespan::Event { time: *time },
+        _ => Lifespan::Inherited,
+    };
+    Some(Prop::Document(DocumentInput {
+        content: content.to_string(),
+        life,
+    }))
+}
+
+/// The value of row `i` of a column of lists, maps or documents created by
+/// [arrow_array_from_props]
+pub(crate) fn prop_from_array(array: &dyn Array, i: usize) -> Option<Prop> {
+    match (array.data_type(), value_as_prop(array, i)?) {
+        (DataType::Extension(name, _, _), Prop::Map(map)) if name == DOCUMENT_EXTENSION => {
+            document_from_map(&map)
+        }
+        (_, prop) => Some(prop),
     }
 }
 
-/// Map iterator of prop values to array (returns None if all the props are None)
+/// Map iterator of prop values to array (returns None if all the props are None or the type is
+/// not supported)
 pub fn arrow_array_from_props(
     props: impl Iterator<Item = Option<Prop>>,
     prop_type: PropType,
@@ -163,63 +360,48 @@ pub fn arrow_array_from_props(
             let array: BooleanArray = props.map(|prop| prop.into_bool()).collect();
             array.iter().any(|v| v.is_some()).then_some(array.boxed())
         }
-        PropType::Empty
-        | PropType::List
-        | PropType::Map
-        | PropType::NDTime
-        | PropType::Graph
-        | PropType::PersistentGraph
-        | PropType::Document
-        | PropType::DTime => panic!("{prop_type:?} not supported as disk_graph property"),
+        PropType::DTime => {
+            let array: PrimitiveArray<i64> = props
+                .map(|prop| prop.into_dtime().map(|t| t.timestamp_millis()))
+                .collect();
+            let array = array.to(arrow_dtype_from_prop_type(prop_type).ok()?);
+            array.iter().any(|v| v.is_some()).then_some(array.boxed())
+        }
+        PropType::NDTime => {
+            let array: PrimitiveArray<i64> = props
+                .map(|prop| prop.into_ndtime().map(|t| t.and_utc().timestamp_millis()))
+                .collect();
+            let array = array.to(arrow_dtype_from_prop_type(prop_type).ok()?);
+            array.iter().any(|v| v.is_some()).then_some(array.boxed())
+        }
+        PropType::List | PropType::Map => {
+            let values: Vec<_> = props
+                .map(|prop| prop.filter(|prop| prop.dtype() == prop_type))
+                .collect();
+            let mut data_type = None;
+            for value in values.iter().flatten() {
+                data_type = merge_data_types(data_type, nested_data_type(value).ok()?).ok()?;
+            }
+            let array = prop_array(&data_type?, values).ok()?;
+            (array.null_count() < array.len()).then_some(array)
+        }
+        PropType::Document => document_array(props),
+        PropType::Empty | PropType::Graph | PropType::PersistentGraph => None,
     }
 }
 
-pub fn schema_from_prop_meta(prop_map: &PropMapper) -> Schema {
+pub fn schema_from_prop_meta(prop_map: &PropMapper) -> Result<Schema, GraphError> {
     let time_field = Field::new("time", DataType::Int64, false);
     let mut schema = vec![time_field];
 
     for (id, key) in prop_map.get_keys().iter().enumerate() {
-        match prop_map.get_dtype(id).unwrap() {
-            PropType::Str => {
-                schema.push(Field::new(key, DataType::LargeUtf8, true));
-            }
-            PropType::U8 => {
-                schema.push(Field::new(key, DataType::UInt8, true));
-            }
-            PropType::U16 => {
-                schema.push(Field::new(key, DataType::UInt16, true));
-            }
-            PropType::I32 => {
-                schema.push(Field::new(key, DataType::Int32, true));
-            }
-            PropType::I64 => {
-                schema.push(Field::new(key, DataType::Int64, true));
-            }
-            PropType::U32 => {
-                schema.push(Field::new(key, DataType::UInt32, true));
-            }
-            PropType::U64 => {
-                schema.push(Field::new(key, DataType::UInt64, true));
-            }
-            PropType::F32 => {
-                schema.push(Field::new(key, DataType::Float32, true));
-            }
-            PropType::F64 => {
-                schema.push(Field::new(key, DataType::Float64, true));
-            }
-            PropType::Bool => {
-                schema.push(Field::new(key, DataType::Boolean, true));
-            }
-            prop_type @ (PropType::Empty
-            | PropType::List
-            | PropType::Map
-            | PropType::NDTime
-            | PropType::Graph
-            | PropType::PersistentGraph
-            | PropType::Document
-            | PropType::DTime) => panic!("{:?} not supported as disk_graph property", prop_type),
-        }
+        let prop_type = prop_map.get_dtype(id).unwrap();
+        schema.push(Field::new(
+            key,
+            arrow_dtype_from_prop_type(prop_type)?,
+            true,
+        ));
     }
 
-    Schema::from(schema)
+    Ok(Schema::from(schema))
 }
@@ -1,22 +1,26 @@
 use crate::{
     arrow2::{
-        datatypes::{ArrowDataType as DataType, Field},
+        array::Array,
+        datatypes::{ArrowDataType as DataType, Field, TimeUnit},
         types::{NativeType, Offset},
     },
     core::storage::timeindex::TimeIndexIntoOps,
     db::api::{storage::graph::tprop_storage_ops::TPropOps, view::IntoDynBoxed},
+    disk_graph::graph_impl::prop_conversion::prop_from_array,
     prelude::Prop,
 };
+use chrono::{DateTime, Utc};
 use pometry_storage::{
     chunked_array::{col::ChunkedPrimitiveCol, utf8_col::StringCol},
     edge::Edge,
+    graph_fragment::TempColGraphFragment,
     prelude::{ArrayOps, BaseArrayOps},
     timestamps::TimeStamps,
     tprops::{DiskTProp, EmptyTProp, TPropColumn},
 };
 use raphtory_api::core::storage::timeindex::TimeIndexEntry;
 use rayon::prelude::*;
-use std::{iter, ops::Range};
+use std::{iter, ops::Range, sync::Arc};
 
 impl<'a, T: NativeType + Into<Prop>> TPropOps<'a>
     for TPropColumn<'a, ChunkedPrimitiveCol<'a, T>, TimeIndexEntry>
@@ -126,10 +130,20 @@ where
     Some(TPropColumn::new(props, timestamps))
 }
 
-pub fn read_tprop_column(id: usize, field: Field, edge: Edge) -> Option<DiskTProp<TimeIndexEntry>> {
-    match field.data_type() {
-        DataType::Int64 => new_tprop_column::<i64>(edge, id).map(DiskTProp::I64),
+pub fn read_tprop_column<'a>(
+    id: usize,
+    field: Field,
+    edge: Edge<'a>,
+    layer: &'a TempColGraphFragment,
+) -> Option<DiskTPropCol<'a>> {
+    let conversion = PropConversion::from_data_type(field.data_type());
+    let tprop = match field.data_type().to_logical_type() {
+        DataType::Int64 | DataType::Timestamp(_, _) => {
+            new_tprop_column::<i64>(edge, id).map(DiskTProp::I64)
+        }
         DataType::Int32 => new_tprop_column::<i32>(edge, id).map(DiskTProp::I32),
+        DataType::UInt8 => new_tprop_column::<u8>(edge, id).map(DiskTProp::U8),
+        DataType::UInt16 => new_tprop_column::<u16>(edge, id).map(DiskTProp::U16),
         DataType::UInt32 => new_tprop_column::<u32>(edge, id).map(DiskTProp::U32),
         DataType::UInt64 => new_tprop_column::<u64>(edge, id).map(DiskTProp::U64),
         DataType::Float32 => new_tprop_column::<f32>(edge, id).map(DiskTProp::F32),
@@ -144,7 +158,219 @@ pub fn read_tprop_column(id: usize, field: Field, edge: Edge) -> Option<DiskTPro
             let timestamps = TimeStamps::new(edge.timestamp_slice(), None);
             Some(DiskTProp::Str64(TPropColumn::new(props, timestamps)))
         }
-        _ => todo!(),
+        DataType::LargeList(_) | DataType::Struct(_) => {
+            let timestamps = TimeStamps::new(edge.timestamp_slice(), None);
+            let rows = timestamps.timestamps().range().clone();
+            let values = layer
+                .edges_storage()
+                .temporal_props()
+                .values()
+                .sliced(rows)
+                .iter_chunks()
+                .map(|chunk| chunk.values()[id].clone())
+                .collect();
+            let col = NestedTPropCol::new(timestamps, values);
+            return Some(DiskTPropCol::Nested(col));
+        }
+        _ => None,
+    }?;
+    Some(DiskTPropCol::Column { tprop, conversion })
+}
+
+/// How the values of a property column are converted back to properties
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum PropConversion {
+    /// The column holds the property values
+    Native,
+    /// The column holds timestamps with a time zone
+    DTime(TimeUnit),
+    /// The column holds timestamps without a time zone
+    NDTime(TimeUnit),
+}
+
+fn timestamp_to_datetime(unit: TimeUnit, value: i64) -> Option<DateTime<Utc>> {
+    match unit {
+        TimeUnit::Second => DateTime::from_timestamp(value, 0),
+        TimeUnit::Millisecond => DateTime::from_timestamp_millis(value),
+        TimeUnit::Microsecond => DateTime::from_timestamp_micros(value),
+        TimeUnit::Nanosecond => Some(DateTime::from_timestamp_nanos(value)),
+    }
+}
+
+impl PropConversion {
+    pub fn from_data_type(data_type: &DataType) -> Self {
+        match data_type {
+            DataType::Timestamp(unit, Some(_)) => PropConversion::DTime(*unit),
+            DataType::Timestamp(unit, None) => PropConversion::NDTime(*unit),
+            _ => PropConversion::Native,
+        }
+    }
+
+    pub fn convert(self, prop: Prop) -> Option<Prop> {
+        match (self, prop) {
+            (PropConversion::Native, prop) => Some(prop),
+            (PropConversion::DTime(unit), Prop::I64(value)) => {
+                timestamp_to_datetime(unit, value).map(Prop::DTime)
+            }
+            (PropConversion::NDTime(unit), Prop::I64(value)) => {
+                timestamp_to_datetime(unit, value).map(|t| Prop::NDTime(t.naive_utc()))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// A temporal property column of lists, maps or documents. The values are read from the Arrow
+/// chunks that hold the rows of the timestamps.
+#[derive(Clone, Debug)]
+pub struct NestedTPropCol<'a> {
+    timestamps: TimeStamps<'a, TimeIndexEntry>,
+    values: Arc<[Box<dyn Array>]>,
+}
+
+impl<'a> NestedTPropCol<'a> {
+    pub fn new(timestamps: TimeStamps<'a, TimeIndexEntry>, values: Vec<Box<dyn Array>>) -> Self {
+        Self {
+            timestamps,
+            values: values.into(),
+        }
+    }
+
+    /// The value of the `i`-th update of the column
+    fn value(values: &[Box<dyn Array>], mut i: usize) -> Option<Prop> {
+        for chunk in values {
+            if i < chunk.len() {
+                return prop_from_array(chunk.as_ref(), i);
+            }
+            i -= chunk.len();
+        }
+        None
+    }
+
+    fn iter_range(
+        self,
+        range: Range<usize>,
+    ) -> impl Iterator<Item = (TimeIndexEntry, Prop)> + Send + 'a {
+        let Self { timestamps, values } = self;
+        let (ts, _) = timestamps.into_inner();
+        let timestamps = TimeStamps::<TimeIndexEntry>::new(ts.sliced(range.clone()), None);
+        timestamps
+            .into_iter()
+            .zip(range)
+            .filter_map(move |(t, i)| Some((t, Self::value(&values, i)?)))
+    }
+}
+
+impl<'a> TPropOps<'a> for NestedTPropCol<'a> {
+    fn last_before(&self, t: i64) -> Option<(TimeIndexEntry, Prop)> {
+        let end = self.timestamps.position(&TimeIndexEntry::start(t));
+        (0..end)
+            .rev()
+            .find_map(|i| Some((self.timestamps.get(i), Self::value(&self.values, i)?)))
+    }
+
+    fn iter(self) -> impl Iterator<Item = (TimeIndexEntry, Prop)> + Send + 'a {
+        let len = self.timestamps.timestamps().len();
+        self.iter_range(0..len)
+    }
+
+    fn iter_window(
+        self,
+        r: Range<TimeIndexEntry>,
+    ) -> impl Iterator<Item = (TimeIndexEntry, Prop)> + Send + 'a {
+        let start = self.timestamps.position(&r.start);
+        let end = self.timestamps.position(&r.end);
+        self.iter_range(start..end)
+    }
+
+    fn at(self, ti: &TimeIndexEntry) -> Option<Prop> {
+        let i = self.timestamps.position(ti);
+        (i < self.timestamps.timestamps().len() && self.timestamps.get(i) == *ti)
+            .then(|| Self::value(&self.values, i))
+            .flatten()
+    }
+
+    fn len(self) -> usize {
+        self.values
+            .iter()
+            .map(|chunk| chunk.len() - chunk.null_count())
+            .sum()
+    }
+}
+
+/// A temporal property column of a disk graph that converts its values back to the type of the
+/// property
+#[derive(Clone, Debug)]
+pub enum DiskTPropCol<'a> {
+    Column {
+        tprop: DiskTProp<'a, TimeIndexEntry>,
+        conversion: PropConversion,
+    },
+    Nested(NestedTPropCol<'a>),
+}
+
+impl<'a> DiskTPropCol<'a> {
+    pub fn new(tprop: DiskTProp<'a, TimeIndexEntry>, data_type: &DataType) -> Self {
+        DiskTPropCol::Column {
+            tprop,
+            conversion: PropConversion::from_data_type(data_type),
+        }
+    }
+
+    pub fn empty() -> Self {
+        DiskTPropCol::Column {
+            tprop: DiskTProp::empty(),
+            conversion: PropConversion::Native,
+        }
+    }
+}
+
+impl<'a> TPropOps<'a> for DiskTPropCol<'a> {
+    fn last_before(&self, t: i64) -> Option<(TimeIndexEntry, Prop)> {
+        match self {
+            DiskTPropCol::Column { tprop, conversion } => {
+                let (t, v) = tprop.last_before(t)?;
+                Some((t, conversion.convert(v)?))
+            }
+            DiskTPropCol::Nested(col) => col.last_before(t),
+        }
+    }
+
+    fn iter(self) -> impl Iterator<Item = (TimeIndexEntry, Prop)> + Send + 'a {
+        match self {
+            DiskTPropCol::Column { tprop, conversion } => tprop
+                .iter()
+                .filter_map(move |(t, v)| Some((t, conversion.convert(v)?)))
+                .into_dyn_boxed(),
+            DiskTPropCol::Nested(col) => col.iter().into_dyn_boxed(),
+        }
+    }
+
+    fn iter_window(
+        self,
+        r: Range<TimeIndexEntry>,
+    ) -> impl Iterator<Item = (TimeIndexEntry, Prop)> + Send + 'a {
+        match self {
+            DiskTPropCol::Column { tprop, conversion } => tprop
+                .iter_window(r)
+                .filter_map(move |(t, v)| Some((t, conversion.convert(v)?)))
+                .into_dyn_boxed(),
+            DiskTPropCol::Nested(col) => col.iter_window(r).into_dyn_boxed(),
+        }
+    }
+
+    fn at(self, ti: &TimeIndexEntry) -> Option<Prop> {
+        match self {
+            DiskTPropCol::Column { tprop, conversion } => conversion.convert(tprop.at(ti)?),
+            DiskTPropCol::Nested(col) => col.at(ti),
+        }
+    }
+
+    fn len(self) -> usize {
+        match self {
+            DiskTPropCol::Column { tprop, .. } => tprop.len(),
+            DiskTPropCol::Nested(col) => col.len(),
+        }
     }
 }
 
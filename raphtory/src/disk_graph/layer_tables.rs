@@ -0,0 +1,92 @@
+//! Columns of the edges of a disk graph that are not part of the columnar storage of its layers.
+//!
+//! A table `name` has an Arrow IPC file `{name}_{layer_id}.ipc` in the graph directory for each
+//! layer that has values, with one row per edge of the layer in the order of the edge ids.
+
+use crate::core::utils::errors::GraphError;
+use polars_arrow::{
+    array::Array,
+    datatypes::{ArrowSchema, Field},
+    io::ipc::{
+        read::{read_file_metadata, FileReader},
+        write::{FileWriter, WriteOptions},
+    },
+    record_batch::RecordBatch,
+};
+use std::{
+    fs::File,
+    io::{BufReader, BufWriter},
+    path::{Path, PathBuf},
+};
+
+/// The columns of a table for the edges of a layer
+#[derive(Debug, Clone)]
+pub(crate) struct LayerTable {
+    pub(crate) fields: Vec<Field>,
+    pub(crate) columns: Vec<Box<dyn Array>>,
+}
+
+impl LayerTable {
+    pub(crate) fn column(&self, name: &str) -> Option<&dyn Array> {
+        let index = self.fields.iter().position(|field| field.name == name)?;
+        Some(self.columns[index].as_ref())
+    }
+}
+
+fn table_path(graph_dir: &Path, name: &str, layer_id: usize) -> PathBuf {
+    graph_dir.join(format!("{name}_{layer_id}.ipc"))
+}
+
+/// Write the table `name` of layer `layer_id` to `graph_dir`
+pub(crate) fn write_layer_table(
+    graph_dir: impl AsRef<Path>,
+    name: &str,
+    layer_id: usize,
+    table: LayerTable,
+) -> Result<(), GraphError> {
+    let file = BufWriter::new(File::create(table_path(
+        graph_dir.as_ref(),
+        name,
+        layer_id,
+    ))?);
+    let mut writer = FileWriter::try_new(
+        file,
+        ArrowSchema::from(table.fields),
+        None,
+        WriteOptions { compression: None },
+    )?;
+    writer.write(&RecordBatch::new(table.columns), None)?;
+    writer.finish()?;
+    Ok(())
+}
+
+/// Read the tables `name` of the first `num_layers` layers in `graph_dir`, layers without a file
+/// have no table
+pub(crate) fn read_layer_tables(
+    graph_dir: impl AsRef<Path>,
+    name: &str,
+    num_layers: usize,
+) -> Result<Vec<Option<LayerTable>>, GraphError> {
+    (0..num_layers)
+        .map(|layer_id| {
+            let path = table_path(graph_dir.as_ref(), name, layer_id);
+            if !path.exists() {
+                return Ok(None);
+            }
+            let mut file = BufReader::new(File::open(path)?);
+            let metadata = read_file_metadata(&mut file)?;
+            let fields = metadata.schema.fields.clone();
+            let mut batches = FileReader::new(file, metadata, None, None);
+            let columns = match batches.next() {
+                Some(batch) => batch?.into_arrays(),
+                None => vec![],
+            };
+            if batches.next().is_some() || columns.len() != fields.len() {
+                return Err(GraphError::LoadFailure(format!(
+                    "Invalid table {name} for layer {layer_id}"
+                )));
+            }
+            Ok(Some(LayerTable { fields, columns }))
+        })
+        .collect()
+}
@@ -1,5 +1,7 @@
 use std::{
+    collections::HashMap,
     fmt::{Display, Formatter},
+    iter,
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -12,10 +14,22 @@ use crate::{
         },
         utils::errors::GraphError,
     },
-    db::{api::storage::graph::storage_ops, graph::views::deletion_graph::PersistentGraph},
-    disk_graph::graph_impl::{prop_conversion::make_node_properties_from_graph, ParquetLayerCols},
-    prelude::{Graph, Layer},
+    db::{
+        api::storage::graph::{edges::edge_storage_ops::EdgeStorageOps, storage_ops},
+        graph::views::deletion_graph::PersistentGraph,
+    },
+    disk_graph::{
+        edge_const_props::EdgeConstProps,
+        edge_deletions::EdgeDeletions,
+        graph_impl::{
+            prop_conversion::{check_disk_graph_props, make_node_properties_from_graph},
+            ParquetLayerCols,
+        },
+        storage_interface::edges_ref::DiskEdgesRef,
+    },
+    prelude::{Graph, Layer, Prop},
 };
+use itertools::Itertools;
 use polars_arrow::{
     array::{PrimitiveArray, StructArray},
     datatypes::{ArrowDataType as DataType, Field},
@@ -28,7 +42,11 @@ use raphtory_api::core::entities::edges::edge_ref::EdgeRef;
 use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
 use serde::{Deserialize, Deserializer, Serialize, Serializer};
 
+pub mod edge_const_props;
+pub mod edge_deletions;
 pub mod graph_impl;
+mod layer_tables;
+mod staged_appends;
 pub mod storage_interface;
 
 pub type Time = i64;
@@ -43,6 +61,8 @@ pub struct DiskGraphStorage {
     node_meta: Arc<Meta>,
     edge_meta: Arc<Meta>,
     graph_props: Arc<GraphMeta>,
+    edge_deletions: Arc<EdgeDeletions>,
+    edge_const_props: Arc<EdgeConstProps>,
 }
 
 impl Serialize for DiskGraphStorage {
@@ -96,18 +116,28 @@ impl DiskGraphStorage {
 
     pub fn valid_layer_ids_from_names(&self, key: Layer) -> LayerIds {
         match key {
-            Layer::All | Layer::Default => LayerIds::One(0), // FIXME: need to handle all correctly
-            Layer::One(name) => {
-                let name = name.as_ref();
-                self.inner
-                    .layer_names()
+            Layer::None => LayerIds::None,
+            Layer::All => LayerIds::All,
+            Layer::Default => LayerIds::One(0),
+            Layer::One(name) => self
+                .inner
+                .find_layer_id(&name)
+                .map(LayerIds::One)
+                .unwrap_or(LayerIds::None),
+            Layer::Multiple(names) => {
+                let mut ids = names
                     .iter()
-                    .enumerate()
-                    .find(move |(_, ref n)| n == &name)
-                    .map(|(i, _)| LayerIds::One(i))
-                    .unwrap_or(LayerIds::None)
+                    .flat_map(|name| self.inner.find_layer_id(name))
+                    .collect::<Vec<_>>();
+                ids.sort_unstable();
+                ids.dedup();
+                match ids.len() {
+                    0 => LayerIds::None,
+                    1 => LayerIds::One(ids[0]),
+                    n if n == self.inner.layers().len() => LayerIds::All,
+                    _ => LayerIds::Multiple(ids.into()),
+                }
             }
-            _ => todo!("Layer ids for multiple names not implemented for Diskgraph"),
         }
     }
 
@@ -152,27 +182,60 @@ impl DiskGraphStorage {
         e: EdgeRef,
         layer_ids: &LayerIds,
     ) -> Box<dyn Iterator<Item = usize> + '_> {
-        let layer_id = match layer_ids.constrain_from_edge(e) {
-            LayerIds::One(id) => id,
-            _ => panic!("Only one layer is supported"),
+        // the first column of the edge properties holds the timestamps
+        let layer_ids = layer_ids.constrain_from_edge(e);
+        let num_columns = self
+            .inner
+            .layers()
+            .iter()
+            .enumerate()
+            .filter(|(layer_id, _)| layer_ids.contains(layer_id))
+            .map(|(_, layer)| layer.edges_data_type().len())
+            .max()
+            .unwrap_or(0);
+        Box::new(1..num_columns)
+    }
+
+    pub(crate) fn core_const_edge_prop_ids(
+        &self,
+        e: EdgeRef,
+        layer_ids: LayerIds,
+    ) -> Box<dyn Iterator<Item = usize> + '_> {
+        let layer_ids = layer_ids.constrain_from_edge(e);
+        let Some(edge) = DiskEdgesRef::new(self).edge(e.into()) else {
+            return Box::new(iter::empty());
         };
-        Box::new(1..self.inner.edges_data_type(layer_id).len())
+        let ids: Vec<_> = edge
+            .layer_ids_iter(&layer_ids)
+            .map(|layer_id| self.edge_const_props.prop_ids(layer_id, edge.eid()))
+            .kmerge()
+            .dedup()
+            .collect();
+        Box::new(ids.into_iter())
     }
 
-    pub fn layer_from_ids(&self, layer_ids: &LayerIds) -> Option<usize> {
+    pub(crate) fn core_get_const_edge_prop(
+        &self,
+        e: EdgeRef,
+        prop_id: usize,
+        layer_ids: LayerIds,
+    ) -> Option<Prop> {
+        let layer_ids = layer_ids.constrain_from_edge(e);
+        let edge = DiskEdgesRef::new(self).edge(e.into())?;
         match layer_ids {
-            LayerIds::One(layer_id) => Some(*layer_id),
             LayerIds::None => None,
-            LayerIds::All => match self.inner.layers().len() {
-                0 => None,
-                1 => Some(0),
-                _ => todo!("multilayer edge views not yet supported in Diskgraph"),
-            },
-            LayerIds::Multiple(ids) => match ids.len() {
-                0 => None,
-                1 => Some(ids[0]),
-                _ => todo!("multilayer edge views not yet supported in Diskgraph"),
-            },
+            LayerIds::One(layer_id) => edge.constant_prop_layer(layer_id, prop_id),
+            LayerIds::All if self.inner.layers().len() == 1 => edge.constant_prop_layer(0, prop_id),
+            layer_ids => {
+                let prop_map: HashMap<_, _> = edge
+                    .layer_ids_iter(&layer_ids)
+                    .flat_map(|layer_id| {
+                        edge.constant_prop_layer(layer_id, prop_id)
+                            .map(|p| (self.edge_meta.get_layer_name_by_id(layer_id), p))
+                    })
+                    .collect();
+                (!prop_map.is_empty()).then(|| prop_map.into())
+            }
         }
     }
 
@@ -223,8 +286,21 @@ impl DiskGraphStorage {
         new_graph_dir: impl AsRef<Path>,
     ) -> Result<DiskGraphStorage, GraphError> {
         let graph_dir = new_graph_dir.as_ref();
+        if !self.edge_const_props.is_empty() || !other.edge_const_props.is_empty() {
+            return Err(GraphError::LoadFailure(
+                "Merging disk graphs with constant edge properties is not supported".to_owned(),
+            ));
+        }
         let inner = merge_graphs(graph_dir, &self.inner, &other.inner)?;
-        Ok(DiskGraphStorage::new(inner))
+        let edge_deletions = EdgeDeletions::merge(
+            &[
+                (&*self.inner, &*self.edge_deletions),
+                (&*other.inner, &*other.edge_deletions),
+            ],
+            &inner,
+        )?;
+        edge_deletions.write(graph_dir, &inner)?;
+        Ok(DiskGraphStorage::new(inner).with_edge_deletions(edge_deletions))
     }
 
     fn new(inner_graph: TemporalGraph) -> Self {
@@ -285,14 +361,42 @@ impl DiskGraphStorage {
             node_meta: Arc::new(node_meta),
             edge_meta: Arc::new(edge_meta),
             graph_props: Arc::new(graph_meta),
+            edge_deletions: Default::default(),
+            edge_const_props: Default::default(),
         }
     }
 
+    fn with_edge_deletions(mut self, edge_deletions: EdgeDeletions) -> Self {
+        self.edge_deletions = Arc::new(edge_deletions);
+        self
+    }
+
+    pub fn edge_deletions(&self) -> &EdgeDeletions {
+        &self.edge_deletions
+    }
+
+    fn with_edge_const_props(mut self, graph_dir: impl AsRef<Path>) -> Result<Self, GraphError> {
+        let num_layers = self.inner.layers().len();
+        let const_props = EdgeConstProps::load(graph_dir, num_layers, &self.edge_meta)?;
+        self.edge_const_props = Arc::new(const_props);
+        Ok(self)
+    }
+
+    pub fn edge_const_props(&self) -> &EdgeConstProps {
+        &self.edge_const_props
+    }
+
     pub fn from_graph(graph: &Graph, graph_dir: impl AsRef<Path>) -> Result<Self, GraphError> {
+        check_disk_graph_props(graph)?;
         let inner_graph = TemporalGraph::from_graph(graph, graph_dir.as_ref(), || {
             make_node_properties_from_graph(graph, graph_dir.as_ref())
         })?;
-        Ok(Self::new(inner_graph))
+        let edge_deletions = EdgeDeletions::from_graph(graph, &inner_graph)?;
+        edge_deletions.write(graph_dir.as_ref(), &inner_graph)?;
+        EdgeConstProps::write_from_graph(graph, &inner_graph, graph_dir.as_ref())?;
+        Self::new(inner_graph)
+            .with_edge_deletions(edge_deletions)
+            .with_edge_const_props(graph_dir)
     }
 
     pub fn load_from_edge_lists(
@@ -316,9 +420,40 @@ impl DiskGraphStorage {
         Ok(Self::new(inner))
     }
 
-    pub fn load_from_dir(graph_dir: impl AsRef<Path>) -> Result<DiskGraphStorage, RAError> {
-        let inner = TemporalGraph::new(graph_dir)?;
-        Ok(Self::new(inner))
+    /// Load a graph from edge lists like [DiskGraphStorage::load_from_edge_lists], with the edge
+    /// deletions in the rows of `deletions`. The deletions have the same columns as the edge
+    /// lists and every deleted edge needs to be added in `edge_list`.
+    pub fn load_from_edge_lists_with_deletions(
+        edge_list: &[StructArray],
+        deletions: &[StructArray],
+        chunk_size: usize,
+        t_props_chunk_size: usize,
+        graph_dir: impl AsRef<Path> + Sync,
+        time_col_idx: usize,
+        src_col_idx: usize,
+        dst_col_idx: usize,
+    ) -> Result<Self, GraphError> {
+        let graph = Self::load_from_edge_lists(
+            edge_list,
+            chunk_size,
+            t_props_chunk_size,
+            graph_dir.as_ref(),
+            time_col_idx,
+            src_col_idx,
+            dst_col_idx,
+        )?;
+        let cols = (time_col_idx, src_col_idx, dst_col_idx);
+        let edge_deletions = EdgeDeletions::from_edge_lists(&graph.inner, deletions, cols)?;
+        edge_deletions.write(graph_dir, &graph.inner)?;
+        Ok(graph.with_edge_deletions(edge_deletions))
+    }
+
+    pub fn load_from_dir(graph_dir: impl AsRef<Path>) -> Result<DiskGraphStorage, GraphError> {
+        let inner = TemporalGraph::new(graph_dir.as_ref())?;
+        let edge_deletions = EdgeDeletions::load(graph_dir.as_ref(), &inner)?;
+        Self::new(inner)
+            .with_edge_deletions(edge_deletions)
+            .with_edge_const_props(graph_dir)
     }
 
     pub fn load_from_parquets<P: AsRef<Path>>(
@@ -331,7 +466,7 @@ impl DiskGraphStorage {
         concurrent_files: Option<usize>,
         num_threads: usize,
         node_type_col: Option<&str>,
-    ) -> Result<DiskGraphStorage, RAError> {
+    ) -> Result<DiskGraphStorage, GraphError> {
         let layered_edge_list: Vec<ExternalEdgeList<&Path>> = layer_parquet_cols
             .iter()
             .map(
@@ -341,6 +476,7 @@ impl DiskGraphStorage {
                      src_col,
                      dst_col,
                      time_col,
+                     ..
                  }| {
                     ExternalEdgeList::new(layer, parquet_dir.as_ref(), src_col, dst_col, time_col)
                         .expect("Failed to load events")
@@ -359,7 +495,9 @@ impl DiskGraphStorage {
             node_properties.as_ref().map(|p| p.as_ref()),
             node_type_col,
         )?;
-        Ok(Self::new(t_graph))
+        let edge_deletions = EdgeDeletions::from_parquets(&t_graph, &layer_parquet_cols)?;
+        edge_deletions.write(graph_dir, &t_graph)?;
+        Ok(Self::new(t_graph).with_edge_deletions(edge_deletions))
     }
 
     pub fn filtered_layers_par<'a>(
@@ -0,0 +1,193 @@
+//! Staging batches of updates for a disk graph.
+//!
+//! The files of a disk graph are never modified. New batches of updates are staged as small disk
+//! graphs in the `staged` directory of the graph instead. Staged updates are not part of the
+//! graph: views created with [DiskGraphStorage::into_graph] only include them once the graph is
+//! compacted. Compaction merges the base graph with all its staged batches into a new disk graph
+//! using the same sorted merge as [DiskGraphStorage::merge_by_sorted_gids].
+//!
+//! [DiskGraphStorage::load_compacted] can be used to load a graph and compact its staged batches
+//! in one go.
+
+use crate::{core::utils::errors::GraphError, disk_graph::DiskGraphStorage, prelude::*};
+use itertools::Itertools;
+use polars_arrow::array::StructArray;
+use std::{
+    fs,
+    path::{Path, PathBuf},
+    thread::{self, JoinHandle},
+};
+
+const STAGED_DIR: &str = "staged";
+
+/// Copy of `graph` where the nodes are added in the order of their ids, which is the order the
+/// merge of disk graphs expects
+fn sorted_by_gid(graph: &Graph) -> Result<Graph, GraphError> {
+    let sorted = Graph::new();
+    let nodes = graph.nodes().iter().sorted_by_key(|node| node.id());
+    sorted.import_nodes(nodes, true)?;
+    sorted.import_edges(graph.edges(), true)?;
+    Ok(sorted)
+}
+
+/// Recursively copy the files in `dir` to `new_dir`, leaving out the entries named `skip`
+fn copy_dir(dir: &Path, new_dir: &Path, skip: Option<&str>) -> Result<(), GraphError> {
+    fs::create_dir_all(new_dir)?;
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if skip.is_some_and(|skip| entry.file_name() == skip) {
+            continue;
+        }
+        let target = new_dir.join(entry.file_name());
+        if entry.file_type()?.is_dir() {
+            copy_dir(&entry.path(), &target, None)?;
+        } else {
+            fs::copy(entry.path(), target)?;
+        }
+    }
+    Ok(())
+}
+
+impl DiskGraphStorage {
+    fn staged_root(&self) -> PathBuf {
+        self.graph_dir().join(STAGED_DIR)
+    }
+
+    /// The directories of the batches staged for this graph in the order they were staged
+    pub fn staged_dirs(&self) -> Result<Vec<PathBuf>, GraphError> {
+        let root = self.staged_root();
+        if !root.exists() {
+            return Ok(vec![]);
+        }
+        let mut dirs = vec![];
+        for entry in fs::read_dir(root)? {
+            let path = entry?.path();
+            let seq = path
+                .file_name()
+                .and_then(|name| name.to_str())
+                .and_then(|name| name.parse::<usize>().ok());
+            if let Some(seq) = seq {
+                dirs.push((seq, path));
+            }
+        }
+        dirs.sort_unstable_by_key(|(seq, _)| *seq);
+        Ok(dirs.into_iter().map(|(_, path)| path).collect())
+    }
+
+    /// Load the batches staged for this graph in the order they were staged
+    pub fn staged_batches(&self) -> Result<Vec<DiskGraphStorage>, GraphError> {
+        self.staged_dirs()?
+            .into_iter()
+            .map(|dir| Ok(DiskGraphStorage::load_from_dir(dir)?))
+            .collect()
+    }
+
+    /// Whether updates were staged for this graph since it was last compacted
+    pub fn has_staged_appends(&self) -> Result<bool, GraphError> {
+        Ok(!self.staged_dirs()?.is_empty())
+    }
+
+    fn next_staged_dir(&self) -> Result<PathBuf, GraphError> {
+        let next_seq = match self.staged_dirs()?.last() {
+            Some(last) => {
+                last.file_name()
+                    .and_then(|name| name.to_str())
+                    .and_then(|name| name.parse::<usize>().ok())
+                    .unwrap_or(0)
+                    + 1
+            }
+            None => 0,
+        };
+        let dir = self.staged_root().join(format!("{next_seq:08}"));
+        fs::create_dir_all(&dir)?;
+        Ok(dir)
+    }
+
+    /// Stage the updates of `batch` for this graph, they are only included in the graph once it
+    /// is compacted
+    pub fn stage_graph(&self, batch: &Graph) -> Result<DiskGraphStorage, GraphError> {
+        let batch = sorted_by_gid(batch)?;
+        let dir = self.next_staged_dir()?;
+        DiskGraphStorage::from_graph(&batch, dir)
+    }
+
+    /// Stage edge lists sorted by source, destination and time for this graph (see
+    /// [DiskGraphStorage::load_from_edge_lists])
+    pub fn stage_edge_lists(
+        &self,
+        edge_list: &[StructArray],
+        chunk_size: usize,
+        t_props_chunk_size: usize,
+        time_col_idx: usize,
+        src_col_idx: usize,
+        dst_col_idx: usize,
+    ) -> Result<DiskGraphStorage, GraphError> {
+        let dir = self.next_staged_dir()?;
+        Ok(DiskGraphStorage::load_from_edge_lists(
+            edge_list,
+            chunk_size,
+            t_props_chunk_size,
+            dir,
+            time_col_idx,
+            src_col_idx,
+            dst_col_idx,
+        )?)
+    }
+
+    /// Merge this graph with all its staged batches into a new disk graph in `new_graph_dir`.
+    ///
+    /// The batches are merged in the order they were staged, the intermediate results are
+    /// written next to `new_graph_dir` and removed once they are merged. This graph and its
+    /// staged batches are left untouched. If nothing is staged, the files of this graph are copied
+    /// to `new_graph_dir`.
+    pub fn compact(&self, new_graph_dir: impl AsRef<Path>) -> Result<DiskGraphStorage, GraphError> {
+        let new_graph_dir = new_graph_dir.as_ref();
+        let batches = self.staged_batches()?;
+        if batches.is_empty() {
+            copy_dir(self.graph_dir(), new_graph_dir, Some(STAGED_DIR))?;
+            return DiskGraphStorage::load_from_dir(new_graph_dir);
+        }
+        let num_batches = batches.len();
+        let mut merged = self.clone();
+        let mut previous_dir: Option<PathBuf> = None;
+        for (i, batch) in batches.iter().enumerate() {
+            let dir = if i + 1 == num_batches {
+                new_graph_dir.to_path_buf()
+            } else {
+                let mut name = new_graph_dir.as_os_str().to_owned();
+                name.push(format!(".compaction-{i}"));
+                PathBuf::from(name)
+            };
+            fs::create_dir_all(&dir)?;
+            merged = merged.merge_by_sorted_gids(batch, &dir)?;
+            if let Some(previous_dir) = previous_dir.replace(dir) {
+                fs::remove_dir_all(previous_dir)?;
+            }
+        }
+        Ok(merged)
+    }
+
+    /// Run [DiskGraphStorage::compact] in a background thread
+    pub fn compact_in_background(
+        &self,
+        new_graph_dir: impl AsRef<Path>,
+    ) -> JoinHandle<Result<DiskGraphStorage, GraphError>> {
+        let graph = self.clone();
+        let new_graph_dir = new_graph_dir.as_ref().to_path_buf();
+        thread::spawn(move || graph.compact(new_graph_dir))
+    }
+
+    /// Load the graph in `graph_dir` and, if updates were staged for it, compact it into
+    /// `compacted_graph_dir` so that they are included in queries
+    pub fn load_compacted(
+        graph_dir: impl AsRef<Path>,
+        compacted_graph_dir: impl AsRef<Path>,
+    ) -> Result<DiskGraphStorage, GraphError> {
+        let graph = DiskGraphStorage::load_from_dir(graph_dir)?;
+        if graph.has_staged_appends()? {
+            graph.compact(compacted_graph_dir)
+        } else {
+            Ok(graph)
+        }
+    }
+}
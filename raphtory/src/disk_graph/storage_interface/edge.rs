@@ -1,34 +1,302 @@
 use crate::{
     core::{
-        entities::{edges::edge_ref::EdgeRef, LayerIds, EID, ELID},
-        storage::timeindex::TimeIndexOps,
+        entities::{edges::edge_ref::EdgeRef, LayerIds, EID, ELID, VID},
+        storage::timeindex::{TimeIndex, TimeIndexOps},
+    },
+    db::api::storage::graph::{
+        edges::edge_storage_ops::EdgeStorageIntoOps, variants::layer_variants::LayerVariants,
+    },
+    disk_graph::{
+        edge_const_props::EdgeConstProps, edge_deletions::EdgeDeletions, DiskGraphStorage,
     },
-    db::api::storage::graph::edges::edge_storage_ops::EdgeStorageIntoOps,
 };
-use pometry_storage::{edge::Edge, edges::Edges, graph::TemporalGraph, timestamps::TimeStamps};
+use itertools::Itertools;
+use pometry_storage::{edge::Edge, graph_fragment::TempColGraphFragment, timestamps::TimeStamps};
 use raphtory_api::core::storage::timeindex::TimeIndexEntry;
-use std::ops::Range;
+use std::{iter, ops::Range, sync::Arc};
+
+/// An edge of a disk graph.
+///
+/// Each layer stores its own copy of an edge with its own id. Edges that are found in a single
+/// layer keep their layer in their `EdgeRef`. Edges that span several layers are looked up in
+/// the other layers by their source and destination and have an `EdgeRef` without a layer whose
+/// id is [multi_layer_eid].
+#[derive(Copy, Clone, Debug)]
+pub struct DiskEdge<'a> {
+    layers: &'a [TempColGraphFragment],
+    deletions: &'a EdgeDeletions,
+    const_props: &'a EdgeConstProps,
+    edge: Edge<'a>,
+    multi_layer: bool,
+}
+
+/// The id of an edge that spans several layers, i.e., its id in `layer_id` offset by the number
+/// of edges in the layers before it.
+pub(crate) fn multi_layer_eid(layers: &[TempColGraphFragment], eid: EID, layer_id: usize) -> EID {
+    let offset: usize = layers[..layer_id]
+        .iter()
+        .map(|layer| layer.num_edges())
+        .sum();
+    EID(offset + eid.0)
+}
+
+/// Inverse of [multi_layer_eid], returns the edge id within its layer and the layer id or `None`
+/// if `eid` is out of bounds
+pub(crate) fn resolve_multi_layer_eid(
+    layers: &[TempColGraphFragment],
+    eid: EID,
+) -> Option<(EID, usize)> {
+    let mut local = eid.0;
+    for (layer_id, layer) in layers.iter().enumerate() {
+        if local < layer.num_edges() {
+            return Some((EID(local), layer_id));
+        }
+        local -= layer.num_edges();
+    }
+    None
+}
+
+/// Whether the layer `layer_id` has a copy of `edge`
+fn has_copy(layers: &[TempColGraphFragment], edge: Edge, layer_id: usize) -> bool {
+    layers[layer_id]
+        .nodes_storage()
+        .find_edge(edge.src_id(), edge.dst_id())
+        .is_some()
+}
+
+/// Whether `edge` is the copy of its edge in the first layer of `layer_ids` that has it
+pub(crate) fn is_first_copy_in(
+    layers: &[TempColGraphFragment],
+    edge: Edge,
+    layer_ids: &LayerIds,
+) -> bool {
+    let layer_id = edge.layer_id();
+    match layer_ids {
+        LayerIds::None => false,
+        LayerIds::All => (0..layer_id).all(|other| !has_copy(layers, edge, other)),
+        LayerIds::One(id) => *id == layer_id,
+        LayerIds::Multiple(ids) => {
+            ids.contains(&layer_id)
+                && ids
+                    .iter()
+                    .filter(|other| **other < layer_id)
+                    .all(|other| !has_copy(layers, edge, *other))
+        }
+    }
+}
+
+/// The `EdgeRef` of the copy of an edge in `layer_id`, keeping the direction and time of `eref`
+fn layer_edge_ref(eref: EdgeRef, eid: EID, layer_id: usize) -> EdgeRef {
+    let layer_ref = EdgeRef::new(eid, eref.local(), eref.remote(), eref.dir()).at_layer(layer_id);
+    match eref.time() {
+        Some(t) => layer_ref.at(t),
+        None => layer_ref,
+    }
+}
+
+impl<'a> DiskEdge<'a> {
+    pub(crate) fn new(
+        layers: &'a [TempColGraphFragment],
+        deletions: &'a EdgeDeletions,
+        const_props: &'a EdgeConstProps,
+        edge: Edge<'a>,
+    ) -> Self {
+        Self {
+            layers,
+            deletions,
+            const_props,
+            edge,
+            multi_layer: false,
+        }
+    }
+
+    /// `edge` as an edge that spans several layers. The edge is identified by its copy in the
+    /// first layer that has it, independent of the layers of the view.
+    pub(crate) fn new_multi_layer(
+        layers: &'a [TempColGraphFragment],
+        deletions: &'a EdgeDeletions,
+        const_props: &'a EdgeConstProps,
+        edge: Edge<'a>,
+    ) -> Self {
+        let edge = (0..edge.layer_id())
+            .find_map(|layer_id| {
+                let layer = &layers[layer_id];
+                let eid = layer
+                    .nodes_storage()
+                    .find_edge(edge.src_id(), edge.dst_id())?;
+                Some(layer.edge(eid))
+            })
+            .unwrap_or(edge);
+        Self {
+            layers,
+            deletions,
+            const_props,
+            edge,
+            multi_layer: true,
+        }
+    }
+
+    /// Get the edge with id `eid`, if `eid` has no layer the edge spans all layers. Returns `None`
+    /// if the id is out of bounds.
+    pub(crate) fn from_elid(
+        layers: &'a [TempColGraphFragment],
+        deletions: &'a EdgeDeletions,
+        const_props: &'a EdgeConstProps,
+        eid: ELID,
+    ) -> Option<Self> {
+        match eid.layer() {
+            Some(layer_id) => {
+                let layer = layers.get(layer_id)?;
+                (eid.pid().0 < layer.num_edges())
+                    .then(|| Self::new(layers, deletions, const_props, layer.edge(eid.pid())))
+            }
+            None => {
+                let (eid, layer_id) = resolve_multi_layer_eid(layers, eid.pid())?;
+                Some(Self {
+                    layers,
+                    deletions,
+                    const_props,
+                    edge: layers[layer_id].edge(eid),
+                    multi_layer: true,
+                })
+            }
+        }
+    }
+
+    pub fn eid(self) -> EID {
+        if self.multi_layer {
+            multi_layer_eid(self.layers, self.edge.eid(), self.edge.layer_id())
+        } else {
+            self.edge.eid()
+        }
+    }
+
+    pub fn src_id(self) -> VID {
+        self.edge.src_id()
+    }
+
+    pub fn dst_id(self) -> VID {
+        self.edge.dst_id()
+    }
+
+    /// Restrict `eref` to the layer of this edge unless it spans several layers
+    pub(crate) fn with_layer(self, eref: EdgeRef) -> EdgeRef {
+        if self.multi_layer {
+            eref
+        } else {
+            eref.at_layer(self.edge.layer_id())
+        }
+    }
+
+    /// The copy of this edge in layer `layer_id`
+    pub fn layer(self, layer_id: usize) -> Option<Edge<'a>> {
+        if layer_id == self.edge.layer_id() {
+            Some(self.edge)
+        } else if self.multi_layer {
+            let layer = self.layers.get(layer_id)?;
+            let eid = layer
+                .nodes_storage()
+                .find_edge(self.src_id(), self.dst_id())?;
+            Some(layer.edge(eid))
+        } else {
+            None
+        }
+    }
+
+    /// The storage of layer `layer_id` of the graph
+    pub(crate) fn graph_layer(self, layer_id: usize) -> &'a TempColGraphFragment {
+        &self.layers[layer_id]
+    }
+
+    /// The constant properties of the edges of the graph
+    pub(crate) fn const_props(self) -> &'a EdgeConstProps {
+        self.const_props
+    }
+
+    /// The deletions of the copy of this edge in layer `layer_id`
+    pub fn layer_deletions(self, layer_id: usize) -> &'a TimeIndex<TimeIndexEntry> {
+        self.layer(layer_id)
+            .and_then(|edge| self.deletions.get(layer_id, edge.eid()))
+            .unwrap_or(&TimeIndex::Empty)
+    }
 
-pub type DiskEdge<'a> = Edge<'a>;
+    /// Whether this is the copy of the edge in the first layer that has it
+    pub fn is_first_layer_copy(self) -> bool {
+        (0..self.edge.layer_id()).all(|layer_id| self.layer(layer_id).is_none())
+    }
+
+    /// The copies of this edge in the layers of `layer_ids`
+    pub fn layers<'b>(self, layer_ids: &'b LayerIds) -> impl Iterator<Item = Edge<'a>> + 'b
+    where
+        'a: 'b,
+    {
+        if !self.multi_layer {
+            return LayerVariants::One(
+                layer_ids
+                    .contains(&self.edge.layer_id())
+                    .then_some(self.edge)
+                    .into_iter(),
+            );
+        }
+        match layer_ids {
+            LayerIds::None => LayerVariants::None(iter::empty()),
+            LayerIds::All => LayerVariants::All(
+                (0..self.layers.len()).filter_map(move |layer_id| self.layer(layer_id)),
+            ),
+            LayerIds::One(layer_id) => LayerVariants::One(self.layer(*layer_id).into_iter()),
+            LayerIds::Multiple(ids) => LayerVariants::Multiple(
+                ids.iter().filter_map(move |layer_id| self.layer(*layer_id)),
+            ),
+        }
+    }
+}
 
 #[derive(Debug, Clone)]
 pub struct DiskOwnedEdge {
-    edges: Edges,
+    layers: Arc<[TempColGraphFragment]>,
+    deletions: Arc<EdgeDeletions>,
+    const_props: Arc<EdgeConstProps>,
+    /// The id of the edge within `layer_id`
     eid: EID,
+    layer_id: usize,
+    multi_layer: bool,
 }
 
 impl DiskOwnedEdge {
-    pub(crate) fn new(graph: &TemporalGraph, eid: ELID) -> Self {
-        let layer = eid
-            .layer()
-            .expect("disk_graph EdgeRefs should have layer always defined");
-        Self {
-            edges: graph.layer(layer).edges_storage().clone(),
-            eid: eid.pid(),
-        }
+    /// The edge with id `eid` or `None` if the id is out of bounds
+    pub(crate) fn new(storage: &DiskGraphStorage, eid: ELID) -> Option<Self> {
+        let edge = DiskEdge::from_elid(
+            storage.inner.layers(),
+            &storage.edge_deletions,
+            &storage.edge_const_props,
+            eid,
+        )?;
+        Some(Self {
+            layers: storage.inner.arc_layers().clone(),
+            deletions: storage.edge_deletions.clone(),
+            const_props: storage.edge_const_props.clone(),
+            eid: edge.edge.eid(),
+            layer_id: edge.edge.layer_id(),
+            multi_layer: edge.multi_layer,
+        })
     }
+
     pub fn as_ref(&self) -> DiskEdge {
-        self.edges.edge(self.eid)
+        DiskEdge {
+            layers: &self.layers,
+            deletions: &self.deletions,
+            const_props: &self.const_props,
+            edge: self.layers[self.layer_id].edge(self.eid),
+            multi_layer: self.multi_layer,
+        }
+    }
+
+    /// The ids of the copies of this edge in the layers of `layer_ids`
+    fn layer_eids(&self, layer_ids: &LayerIds) -> Vec<(EID, usize)> {
+        self.as_ref()
+            .layers(layer_ids)
+            .map(|edge| (edge.eid(), edge.layer_id()))
+            .collect()
     }
 }
 
@@ -38,8 +306,9 @@ impl EdgeStorageIntoOps for DiskOwnedEdge {
         layer_ids: LayerIds,
         eref: EdgeRef,
     ) -> impl Iterator<Item = EdgeRef> + Send {
-        let layer_id = self.edges.layer_id();
-        layer_ids.contains(&layer_id).then_some(eref).into_iter()
+        self.layer_eids(&layer_ids)
+            .into_iter()
+            .map(move |(eid, layer_id)| layer_edge_ref(eref, eid, layer_id))
     }
 
     fn into_exploded(
@@ -47,17 +316,22 @@ impl EdgeStorageIntoOps for DiskOwnedEdge {
         layer_ids: LayerIds,
         eref: EdgeRef,
     ) -> impl Iterator<Item = EdgeRef> + Send {
-        let layer_id = self.edges.layer_id();
-        layer_ids
-            .contains(&layer_id)
-            .then(move || {
-                let ts = self.edges.into_time().into_value(self.eid.0);
+        let layer_eids = self.layer_eids(&layer_ids);
+        let layers = self.layers;
+        layer_eids
+            .into_iter()
+            .map(move |(eid, layer_id)| {
+                let ts = layers[layer_id]
+                    .edges_storage()
+                    .clone()
+                    .into_time()
+                    .into_value(eid.0);
                 let range = ts.range().clone();
+                let eref = layer_edge_ref(eref, eid, layer_id);
                 ts.zip(range)
                     .map(move |(t, s)| eref.at(TimeIndexEntry(t, s)))
             })
-            .into_iter()
-            .flatten()
+            .kmerge_by(|e1, e2| e1.time() <= e2.time())
     }
 
     fn into_exploded_window(
@@ -66,19 +340,20 @@ impl EdgeStorageIntoOps for DiskOwnedEdge {
         w: Range<TimeIndexEntry>,
         eref: EdgeRef,
     ) -> impl Iterator<Item = EdgeRef> + Send {
-        let layer_id = self.edges.layer_id();
-        layer_ids
-            .contains(&layer_id)
-            .then(move || {
-                let array = self.edges.into_time();
-                let ts: TimeStamps<TimeIndexEntry> = TimeStamps::new(array.value(self.eid.0), None);
-                let times = ts.range(w).timestamps().into_owned();
+        let layer_eids = self.layer_eids(&layer_ids);
+        let layers = self.layers;
+        layer_eids
+            .into_iter()
+            .map(move |(eid, layer_id)| {
+                let array = layers[layer_id].edges_storage().clone().into_time();
+                let ts: TimeStamps<TimeIndexEntry> = TimeStamps::new(array.value(eid.0), None);
+                let times = ts.range(w.clone()).timestamps().into_owned();
                 let range = times.range().clone();
+                let eref = layer_edge_ref(eref, eid, layer_id);
                 times
                     .zip(range)
                     .map(move |(t, s)| eref.at(TimeIndexEntry(t, s)))
             })
-            .into_iter()
-            .flatten()
+            .kmerge_by(|e1, e2| e1.time() <= e2.time())
     }
 }
@@ -1,26 +1,37 @@
 use crate::{
     core::entities::{LayerIds, EID},
     db::api::storage::graph::variants::layer_variants::LayerVariants,
-    disk_graph::storage_interface::{edge::DiskEdge, edges_ref::DiskEdgesRef},
+    disk_graph::{
+        edge_const_props::EdgeConstProps,
+        edge_deletions::EdgeDeletions,
+        storage_interface::{edge::DiskEdge, edges_ref::DiskEdgesRef},
+        DiskGraphStorage,
+    },
 };
-use pometry_storage::{graph::TemporalGraph, graph_fragment::TempColGraphFragment};
+use pometry_storage::graph_fragment::TempColGraphFragment;
 use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use std::{iter, sync::Arc};
 
 #[derive(Clone, Debug)]
 pub struct DiskEdges {
     layers: Arc<[TempColGraphFragment]>,
+    deletions: Arc<EdgeDeletions>,
+    const_props: Arc<EdgeConstProps>,
 }
 
 impl DiskEdges {
-    pub(crate) fn new(graph: &TemporalGraph) -> Self {
+    pub(crate) fn new(storage: &DiskGraphStorage) -> Self {
         Self {
-            layers: graph.arc_layers().clone(),
+            layers: storage.inner.arc_layers().clone(),
+            deletions: storage.edge_deletions.clone(),
+            const_props: storage.edge_const_props.clone(),
         }
     }
     pub fn as_ref(&self) -> DiskEdgesRef {
         DiskEdgesRef {
             layers: &self.layers,
+            deletions: &self.deletions,
+            const_props: &self.const_props,
         }
     }
 
@@ -76,6 +87,11 @@ impl DiskEdges {
     }
 
     pub fn get(&self, eid: EID, layer_id: usize) -> DiskEdge {
-        self.layers[layer_id].edge(eid)
+        DiskEdge::new(
+            &self.layers,
+            &self.deletions,
+            &self.const_props,
+            self.layers[layer_id].edge(eid),
+        )
     }
 }
@@ -1,79 +1,112 @@
 use crate::{
-    core::entities::{LayerIds, EID},
+    core::entities::{LayerIds, ELID},
     db::api::storage::graph::variants::layer_variants::LayerVariants,
-    disk_graph::storage_interface::edge::DiskEdge,
+    disk_graph::{
+        edge_const_props::EdgeConstProps,
+        edge_deletions::EdgeDeletions,
+        storage_interface::edge::{is_first_copy_in, DiskEdge},
+        DiskGraphStorage,
+    },
 };
-use pometry_storage::{graph::TemporalGraph, graph_fragment::TempColGraphFragment};
+use pometry_storage::graph_fragment::TempColGraphFragment;
 use rayon::iter::{IntoParallelIterator, IntoParallelRefIterator, ParallelIterator};
 use std::iter;
 
 #[derive(Copy, Clone, Debug)]
 pub struct DiskEdgesRef<'a> {
     pub(super) layers: &'a [TempColGraphFragment],
+    pub(super) deletions: &'a EdgeDeletions,
+    pub(super) const_props: &'a EdgeConstProps,
 }
 
 impl<'a> DiskEdgesRef<'a> {
-    pub(crate) fn new(storage: &'a TemporalGraph) -> Self {
+    pub(crate) fn new(storage: &'a DiskGraphStorage) -> Self {
         Self {
-            layers: storage.layers(),
+            layers: storage.inner.layers(),
+            deletions: &storage.edge_deletions,
+            const_props: &storage.edge_const_props,
         }
     }
 
-    pub fn edge(self, eid: EID, layer_id: usize) -> DiskEdge<'a> {
-        self.layers[layer_id].edge(eid)
+    /// Get the edge with id `eid`, if `eid` has no layer the edge spans all layers. Returns `None`
+    /// if the id is out of bounds.
+    pub fn edge(self, eid: ELID) -> Option<DiskEdge<'a>> {
+        DiskEdge::from_elid(self.layers, self.deletions, self.const_props, eid)
     }
 
-    pub fn iter(self, layers: LayerIds) -> impl Iterator<Item = DiskEdge<'a>> {
+    /// The layer of `layers` if it selects a single layer of the graph
+    fn single_layer(self, layers: &LayerIds) -> Option<usize> {
         match layers {
-            LayerIds::None => LayerVariants::None(iter::empty()),
-            LayerIds::All => LayerVariants::All(
+            LayerIds::All if self.layers.len() == 1 => Some(0),
+            LayerIds::One(layer_id) => Some(*layer_id),
+            LayerIds::Multiple(ids) if ids.len() == 1 => Some(ids[0]),
+            _ => None,
+        }
+    }
+
+    /// Iterate over the edges in `layers`. If `layers` selects several layers, edges that are
+    /// found in more than one of them are returned once and span all their layers.
+    pub fn iter(self, layers: LayerIds) -> impl Iterator<Item = DiskEdge<'a>> {
+        let (all_layers, deletions, const_props) = (self.layers, self.deletions, self.const_props);
+        let single_layer = self.single_layer(&layers);
+        let layer_ids = layers.clone();
+        match (single_layer, layers) {
+            (Some(layer_id), _) => LayerVariants::One(self.layers[layer_id].edges_storage().iter()),
+            (None, LayerIds::None) => LayerVariants::None(iter::empty()),
+            (None, LayerIds::Multiple(ids)) => LayerVariants::Multiple(
+                (0..ids.len()).flat_map(move |i| self.layers[ids[i]].edges_storage().iter()),
+            ),
+            (None, _) => LayerVariants::All(
                 self.layers
                     .iter()
                     .flat_map(|layer| layer.edges_storage().iter()),
             ),
-            LayerIds::One(layer_id) => {
-                LayerVariants::One(self.layers[layer_id].edges_storage().iter())
-            }
-            LayerIds::Multiple(ids) => {
-                let ids = ids.clone();
-                LayerVariants::Multiple(
-                    (0..ids.len()).flat_map(move |i| self.layers[ids[i]].edges_storage().iter()),
-                )
-            }
         }
+        .filter(move |edge| {
+            single_layer.is_some() || is_first_copy_in(all_layers, *edge, &layer_ids)
+        })
+        .map(move |edge| match single_layer {
+            Some(_) => DiskEdge::new(all_layers, deletions, const_props, edge),
+            None => DiskEdge::new_multi_layer(all_layers, deletions, const_props, edge),
+        })
     }
 
+    /// Parallel version of [DiskEdgesRef::iter]
     pub fn par_iter(self, layers: LayerIds) -> impl ParallelIterator<Item = DiskEdge<'a>> {
-        match layers {
-            LayerIds::None => LayerVariants::None(rayon::iter::empty()),
-            LayerIds::All => LayerVariants::All(
+        let (all_layers, deletions, const_props) = (self.layers, self.deletions, self.const_props);
+        let single_layer = self.single_layer(&layers);
+        let layer_ids = layers.clone();
+        match (single_layer, layers) {
+            (Some(layer_id), _) => {
+                LayerVariants::One(self.layers[layer_id].edges_storage().par_iter())
+            }
+            (None, LayerIds::None) => LayerVariants::None(rayon::iter::empty()),
+            (None, LayerIds::Multiple(ids)) => LayerVariants::Multiple(
+                (0..ids.len())
+                    .into_par_iter()
+                    .flat_map(move |i| self.layers[ids[i]].edges_storage().par_iter()),
+            ),
+            (None, _) => LayerVariants::All(
                 self.layers
                     .par_iter()
                     .flat_map(|layer| layer.edges_storage().par_iter()),
             ),
-            LayerIds::One(layer_id) => {
-                LayerVariants::One(self.layers[layer_id].edges_storage().par_iter())
-            }
-            LayerIds::Multiple(ids) => {
-                let ids = ids.clone();
-                LayerVariants::Multiple(
-                    (0..ids.len())
-                        .into_par_iter()
-                        .flat_map(move |i| self.layers[ids[i]].edges_storage().par_iter()),
-                )
-            }
         }
+        .filter(move |edge| {
+            single_layer.is_some() || is_first_copy_in(all_layers, *edge, &layer_ids)
+        })
+        .map(move |edge| match single_layer {
+            Some(_) => DiskEdge::new(all_layers, deletions, const_props, edge),
+            None => DiskEdge::new_multi_layer(all_layers, deletions, const_props, edge),
+        })
     }
 
+    /// The number of edges in `layers`, edges found in several layers are counted once
     pub fn count(self, layers: &LayerIds) -> usize {
-        match layers {
-            LayerIds::None => 0,
-            LayerIds::All => self.layers.par_iter().map(|layer| layer.num_edges()).sum(),
-            LayerIds::One(id) => self.layers[*id].num_edges(),
-            LayerIds::Multiple(ids) => ids
-                .par_iter()
-                .map(|layer| self.layers[*layer].num_edges())
-                .sum(),
+        match (self.single_layer(layers), layers) {
+            (Some(layer_id), _) => self.layers[layer_id].num_edges(),
+            (None, LayerIds::None) => 0,
+            (None, _) => self.par_iter(layers.clone()).count(),
         }
     }
 
@@ -96,4 +129,9 @@ impl<'a> DiskEdgesRef<'a> {
     pub fn len(&self) -> usize {
         self.count(&LayerIds::All)
     }
+
+    /// The number of ids of the edges, each copy of an edge in a layer has its own id
+    pub fn num_ids(&self) -> usize {
+        self.layers.iter().map(|layer| layer.num_edges()).sum()
+    }
 }
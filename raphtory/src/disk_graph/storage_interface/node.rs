@@ -11,11 +11,19 @@ use crate::{
         },
         view::internal::NodeAdditions,
     },
+    disk_graph::{
+        graph_impl::{
+            prop_conversion::prop_from_array,
+            tprops::{DiskTPropCol, NestedTPropCol, PropConversion},
+        },
+        storage_interface::edge::multi_layer_eid,
+    },
     prelude::Prop,
 };
 use itertools::Itertools;
 use polars_arrow::datatypes::ArrowDataType;
 use pometry_storage::{graph::TemporalGraph, timestamps::TimeStamps, GidRef};
+use raphtory_api::core::storage::timeindex::TimeIndexEntry;
 use rayon::iter::{IndexedParallelIterator, IntoParallelRefIterator, ParallelIterator};
 use std::{borrow::Cow, iter, sync::Arc};
 
@@ -232,20 +240,39 @@ impl<'a> NodeStorageOps<'a> for DiskNode<'a> {
     }
 
     fn tprop(self, prop_id: usize) -> impl TPropOps<'a> {
-        self.graph
+        let props = self
+            .graph
             .node_properties()
             .temporal_props
             .as_ref()
-            .unwrap()
-            .prop(self.vid, prop_id)
+            .unwrap();
+        let data_type = props.prop_dtypes()[prop_id].data_type();
+        match data_type.to_logical_type() {
+            ArrowDataType::LargeList(_) | ArrowDataType::Struct(_) => {
+                let timestamps = props.timestamps::<TimeIndexEntry>(self.vid);
+                let rows = timestamps.timestamps().range().clone();
+                let values = props
+                    .props()
+                    .sliced(rows)
+                    .iter_chunks()
+                    .map(|chunk| chunk.values()[prop_id].clone())
+                    .collect();
+                DiskTPropCol::Nested(NestedTPropCol::new(timestamps, values))
+            }
+            _ => DiskTPropCol::new(props.prop(self.vid, prop_id), data_type),
+        }
     }
 
     fn prop(self, prop_id: usize) -> Option<Prop> {
         let cprops = self.graph.node_properties().const_props.as_ref()?;
         let prop_type = cprops.prop_dtype(prop_id);
-        match prop_type.data_type {
+        let prop = match prop_type.data_type.to_logical_type() {
             ArrowDataType::Int32 => cprops.prop_native::<i32>(self.vid, prop_id).map(Prop::I32),
-            ArrowDataType::Int64 => cprops.prop_native::<i64>(self.vid, prop_id).map(Prop::I64),
+            ArrowDataType::Int64 | ArrowDataType::Timestamp(_, _) => {
+                cprops.prop_native::<i64>(self.vid, prop_id).map(Prop::I64)
+            }
+            ArrowDataType::UInt8 => cprops.prop_native::<u8>(self.vid, prop_id).map(Prop::U8),
+            ArrowDataType::UInt16 => cprops.prop_native::<u16>(self.vid, prop_id).map(Prop::U16),
             ArrowDataType::UInt32 => cprops.prop_native::<u32>(self.vid, prop_id).map(Prop::U32),
             ArrowDataType::UInt64 => cprops.prop_native::<u64>(self.vid, prop_id).map(Prop::U64),
             ArrowDataType::Float32 => cprops.prop_native::<f32>(self.vid, prop_id).map(Prop::F32),
@@ -253,9 +280,15 @@ impl<'a> NodeStorageOps<'a> for DiskNode<'a> {
             ArrowDataType::Utf8 | ArrowDataType::LargeUtf8 | ArrowDataType::Utf8View => {
                 cprops.prop_str(self.vid, prop_id).map(Prop::str)
             }
+            ArrowDataType::LargeList(_) | ArrowDataType::Struct(_) => {
+                let chunk_size = cprops.props().chunk_size();
+                let chunk = cprops.props().chunk(self.vid.0 / chunk_size);
+                prop_from_array(chunk.values()[prop_id].as_ref(), self.vid.0 % chunk_size)
+            }
             // Add cases for other types, including special handling for complex types
             _ => None, // Placeholder for unhandled types
-        }
+        }?;
+        PropConversion::from_data_type(&prop_type.data_type).convert(prop)
     }
 
     fn edges_iter(
@@ -290,35 +323,31 @@ impl<'a> NodeStorageOps<'a> for DiskNode<'a> {
     }
 
     fn find_edge(self, dst: VID, layer_ids: &LayerIds) -> Option<EdgeRef> {
+        let layers = self.graph.layers();
+        let find_in_layer = |layer_id: usize| {
+            let eid = layers[layer_id].nodes_storage().find_edge(self.vid, dst)?;
+            Some((eid, layer_id))
+        };
+        let single_layer = |layer_id: usize| {
+            let (eid, layer_id) = find_in_layer(layer_id)?;
+            Some(EdgeRef::new_outgoing(eid, self.vid, dst).at_layer(layer_id))
+        };
+        // edges that span several layers are identified by the first layer of the graph that has
+        // them, independent of the layers of the view
+        let multi_layer = |(eid, layer_id): (EID, usize)| {
+            EdgeRef::new_outgoing(multi_layer_eid(layers, eid, layer_id), self.vid, dst)
+        };
         match layer_ids {
             LayerIds::None => None,
-            LayerIds::All => match self.graph.layers().len() {
-                0 => None,
-                1 => {
-                    let eid = self.graph.layers()[0]
-                        .nodes_storage()
-                        .find_edge(self.vid, dst)?;
-                    Some(EdgeRef::new_outgoing(eid, self.vid, dst).at_layer(0))
-                }
-                _ => todo!("multilayer edge views not implemented in diskgraph yet"),
-            },
-            LayerIds::One(id) => {
-                let eid = self.graph.layers()[*id]
-                    .nodes_storage()
-                    .find_edge(self.vid, dst)?;
-                Some(EdgeRef::new_outgoing(eid, self.vid, dst).at_layer(*id))
-            }
-            LayerIds::Multiple(ids) => match ids.len() {
-                0 => None,
-                1 => {
-                    let layer = ids[0];
-                    let eid = self.graph.layers()[layer]
-                        .nodes_storage()
-                        .find_edge(self.vid, dst)?;
-                    Some(EdgeRef::new_outgoing(eid, self.vid, dst).at_layer(layer))
-                }
-                _ => todo!("multtilayer edge views not implemented in diskgraph yet"),
-            },
+            LayerIds::All if layers.len() == 1 => single_layer(0),
+            LayerIds::All => (0..layers.len()).find_map(find_in_layer).map(multi_layer),
+            LayerIds::One(id) => single_layer(*id),
+            LayerIds::Multiple(ids) if ids.len() == 1 => single_layer(ids[0]),
+            LayerIds::Multiple(ids) => ids
+                .iter()
+                .find_map(|id| find_in_layer(*id))
+                .and_then(|_| (0..layers.len()).find_map(find_in_layer))
+                .map(multi_layer),
         }
     }
 }
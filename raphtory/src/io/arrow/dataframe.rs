@@ -46,6 +46,10 @@ pub(crate) struct DFChunk {
 }
 
 impl DFChunk {
+    pub(crate) fn len(&self) -> usize {
+        self.chunk.first().map(|c| c.len()).unwrap_or(0)
+    }
+
     pub(crate) fn iter_col<T: NativeType>(
         &self,
         idx: usize,
@@ -1,5 +1,8 @@
 use crate::{
-    core::utils::errors::GraphError,
+    core::{
+        entities::{nodes::node_ref::AsNodeRef, VID},
+        utils::errors::GraphError,
+    },
     db::api::{
         mutation::{internal::*, AdditionOps},
         view::StaticGraphViewOps,
@@ -31,6 +34,15 @@ fn extract_out_default_type(n_t: Option<&str>) -> Option<&str> {
     }
 }
 
+/// Called with the index of the row (counted from the start of the dataframe) and the result of
+/// adding it for every row without missing values. Returning an error aborts the load.
+pub(crate) type RowHandler<'a> =
+    dyn FnMut(usize, Result<(), GraphError>) -> Result<(), GraphError> + 'a;
+
+fn fail_on_row_error(_row: usize, result: Result<(), GraphError>) -> Result<(), GraphError> {
+    result
+}
+
 pub(crate) fn load_nodes_from_df<
     'a,
     G: StaticGraphViewOps + InternalPropertyAdditionOps + InternalAdditionOps,
@@ -44,6 +56,35 @@ pub(crate) fn load_nodes_from_df<
     node_type: Option<&str>,
     node_type_col: Option<&str>,
     graph: &G,
+) -> Result<(), GraphError> {
+    load_nodes_from_df_with_row_errors(
+        df_view,
+        time,
+        node_id,
+        properties,
+        constant_properties,
+        shared_constant_properties,
+        node_type,
+        node_type_col,
+        graph,
+        &mut fail_on_row_error,
+    )
+}
+
+pub(crate) fn load_nodes_from_df_with_row_errors<
+    'a,
+    G: StaticGraphViewOps + InternalPropertyAdditionOps + InternalAdditionOps,
+>(
+    df_view: DFView<impl Iterator<Item = Result<DFChunk, GraphError>>>,
+    time: &str,
+    node_id: &str,
+    properties: Option<&[&str]>,
+    constant_properties: Option<&[&str]>,
+    shared_constant_properties: Option<&HashMap<String, Prop>>,
+    node_type: Option<&str>,
+    node_type_col: Option<&str>,
+    graph: &G,
+    on_row: &mut RowHandler,
 ) -> Result<(), GraphError> {
     let properties = properties.unwrap_or(&[]);
     let constant_properties = constant_properties.unwrap_or(&[]);
@@ -67,6 +108,7 @@ pub(crate) fn load_nodes_from_df<
     let node_id_index = df_view.get_index(node_id)?;
     let time_index = df_view.get_index(time)?;
     let mut pb = build_progress_bar("Loading nodes".to_string(), df_view.num_rows)?;
+    let mut row_offset = 0;
 
     for chunk in df_view.chunks {
         let df = chunk?;
@@ -108,13 +150,15 @@ pub(crate) fn load_nodes_from_df<
                 .zip(time)
                 .zip(node_type)
                 .map(|((node_id, time), n_t)| (node_id, time, n_t));
-            load_nodes_from_num_iter(
+            load_nodes_from_iter(
                 graph,
                 &mut pb,
                 iter,
                 prop_iter,
                 const_prop_iter,
                 shared_constant_properties,
+                row_offset,
+                on_row,
             )?;
         } else if let (Some(node_id), Some(time)) = (
             df.iter_col::<i64>(node_id_index),
@@ -125,13 +169,15 @@ pub(crate) fn load_nodes_from_df<
                 .zip(node_type)
                 .map(|((node_id, time), n_t)| (node_id, time, n_t));
 
-            load_nodes_from_num_iter(
+            load_nodes_from_iter(
                 graph,
                 &mut pb,
                 iter,
                 prop_iter,
                 const_prop_iter,
                 shared_constant_properties,
+                row_offset,
+                on_row,
             )?;
         } else if let (Some(node_id), Some(time)) =
             (df.utf8::<i32>(node_id_index), df.time_iter_col(time_index))
@@ -141,19 +187,16 @@ pub(crate) fn load_nodes_from_df<
                 .zip(node_type)
                 .map(|((node_id, time), n_t)| (node_id, time, n_t));
 
-            for (((node_id, time, n_t), props), const_props) in
-                iter.zip(prop_iter).zip(const_prop_iter)
-            {
-                if let (Some(node_id), Some(time), n_t) = (node_id, time, n_t) {
-                    let actual_type = extract_out_default_type(n_t);
-                    let v = graph.add_node(time, node_id, props, actual_type)?;
-                    v.add_constant_properties(const_props)?;
-                    if let Some(shared_const_props) = &shared_constant_properties {
-                        v.add_constant_properties(shared_const_props.iter())?;
-                    }
-                }
-                let _ = pb.update(1);
-            }
+            load_nodes_from_iter(
+                graph,
+                &mut pb,
+                iter,
+                prop_iter,
+                const_prop_iter,
+                shared_constant_properties,
+                row_offset,
+                on_row,
+            )?;
         } else if let (Some(node_id), Some(time)) =
             (df.utf8::<i64>(node_id_index), df.time_iter_col(time_index))
         {
@@ -162,24 +205,22 @@ pub(crate) fn load_nodes_from_df<
                 .zip(node_type)
                 .map(|((node_id, time), n_t)| (node_id, time, n_t));
 
-            for (((node_id, time, n_t), props), const_props) in
-                iter.zip(prop_iter).zip(const_prop_iter)
-            {
-                let actual_type = extract_out_default_type(n_t);
-                if let (Some(node_id), Some(time), n_t) = (node_id, time, actual_type) {
-                    let v = graph.add_node(time, node_id, props, n_t)?;
-                    v.add_constant_properties(const_props)?;
-                    if let Some(shared_const_props) = shared_constant_properties {
-                        v.add_constant_properties(shared_const_props)?;
-                    }
-                }
-                let _ = pb.update(1);
-            }
+            load_nodes_from_iter(
+                graph,
+                &mut pb,
+                iter,
+                prop_iter,
+                const_prop_iter,
+                shared_constant_properties,
+                row_offset,
+                on_row,
+            )?;
         } else {
             return Err(GraphError::LoadFailure(
                 "node id column must be either u64 or text, time column must be i64. Ensure these contain no NaN, Null or None values.".to_string(),
             ));
         };
+        row_offset += df.len();
     }
     Ok(())
 }
@@ -198,6 +239,37 @@ pub(crate) fn load_edges_from_df<
     layer: Option<&str>,
     layer_col: Option<&str>,
     graph: &G,
+) -> Result<(), GraphError> {
+    load_edges_from_df_with_row_errors(
+        df_view,
+        time,
+        src,
+        dst,
+        properties,
+        constant_properties,
+        shared_constant_properties,
+        layer,
+        layer_col,
+        graph,
+        &mut fail_on_row_error,
+    )
+}
+
+pub(crate) fn load_edges_from_df_with_row_errors<
+    'a,
+    G: StaticGraphViewOps + InternalPropertyAdditionOps + InternalAdditionOps,
+>(
+    df_view: DFView<impl Iterator<Item = Result<DFChunk, GraphError>>>,
+    time: &str,
+    src: &str,
+    dst: &str,
+    properties: Option<&[&str]>,
+    constant_properties: Option<&[&str]>,
+    shared_constant_properties: Option<&HashMap<String, Prop>>,
+    layer: Option<&str>,
+    layer_col: Option<&str>,
+    graph: &G,
+    on_row: &mut RowHandler,
 ) -> Result<(), GraphError> {
     let properties = properties.unwrap_or(&[]);
     let constant_properties = constant_properties.unwrap_or(&[]);
@@ -221,6 +293,7 @@ pub(crate) fn load_edges_from_df<
     };
     let layer_index = layer_index.transpose()?;
     let mut pb = build_progress_bar("Loading edges".to_string(), df_view.num_rows)?;
+    let mut row_offset = 0;
 
     for chunk in df_view.chunks {
         let df = chunk?;
@@ -239,7 +312,7 @@ pub(crate) fn load_edges_from_df<
                 .map(|i| i.copied())
                 .zip(dst.map(|i| i.copied()))
                 .zip(time);
-            load_edges_from_num_iter(
+            load_edges_from_iter(
                 graph,
                 &mut pb,
                 triplets,
@@ -247,6 +320,8 @@ pub(crate) fn load_edges_from_df<
                 const_prop_iter,
                 shared_constant_properties,
                 layer,
+                row_offset,
+                on_row,
             )?;
         } else if let (Some(src), Some(dst), Some(time)) = (
             df.iter_col::<i64>(src_index),
@@ -257,7 +332,7 @@ pub(crate) fn load_edges_from_df<
                 .map(i64_opt_into_u64_opt)
                 .zip(dst.map(i64_opt_into_u64_opt))
                 .zip(time);
-            load_edges_from_num_iter(
+            load_edges_from_iter(
                 graph,
                 &mut pb,
                 triplets,
@@ -265,6 +340,8 @@ pub(crate) fn load_edges_from_df<
                 const_prop_iter,
                 shared_constant_properties,
                 layer,
+                row_offset,
+                on_row,
             )?;
         } else if let (Some(src), Some(dst), Some(time)) = (
             df.utf8::<i32>(src_index),
@@ -272,43 +349,41 @@ pub(crate) fn load_edges_from_df<
             df.time_iter_col(time_index),
         ) {
             let triplets = src.into_iter().zip(dst.into_iter()).zip(time.into_iter());
-
-            for (((((src, dst), time), props), const_props), layer) in
-                triplets.zip(prop_iter).zip(const_prop_iter).zip(layer)
-            {
-                if let (Some(src), Some(dst), Some(time)) = (src, dst, time) {
-                    let e = graph.add_edge(time, src, dst, props, layer.as_deref())?;
-                    e.add_constant_properties(const_props, layer.as_deref())?;
-                    if let Some(shared_const_props) = &shared_constant_properties {
-                        e.add_constant_properties(shared_const_props.iter(), layer.as_deref())?;
-                    }
-                }
-                let _ = pb.update(1);
-            }
+            load_edges_from_iter(
+                graph,
+                &mut pb,
+                triplets,
+                prop_iter,
+                const_prop_iter,
+                shared_constant_properties,
+                layer,
+                row_offset,
+                on_row,
+            )?;
         } else if let (Some(src), Some(dst), Some(time)) = (
             df.utf8::<i64>(src_index),
             df.utf8::<i64>(dst_index),
             df.time_iter_col(time_index),
         ) {
             let triplets = src.into_iter().zip(dst.into_iter()).zip(time.into_iter());
-            for (((((src, dst), time), props), const_props), layer) in
-                triplets.zip(prop_iter).zip(const_prop_iter).zip(layer)
-            {
-                if let (Some(src), Some(dst), Some(time)) = (src, dst, time) {
-                    let e = graph.add_edge(time, src, dst, props, layer.as_deref())?;
-                    e.add_constant_properties(const_props, layer.as_deref())?;
-                    if let Some(shared_const_props) = &shared_constant_properties {
-                        e.add_constant_properties(shared_const_props.iter(), layer.as_deref())?;
-                    }
-                }
-                let _ = pb.update(1);
-            }
+            load_edges_from_iter(
+                graph,
+                &mut pb,
+                triplets,
+                prop_iter,
+                const_prop_iter,
+                shared_constant_properties,
+                layer,
+                row_offset,
+                on_row,
+            )?;
         } else {
             return Err(GraphError::LoadFailure(
                 "Source and Target columns must be either u64 or text, Time column must be i64. Ensure these contain no NaN, Null or None values."
                     .to_string(),
             ));
         };
+        row_offset += df.len();
     }
     Ok(())
 }
@@ -408,6 +483,145 @@ pub(crate) fn load_edge_deletions_from_df<
     Ok(())
 }
 
+/// Add every row as an edge that is alive for `[start, end)`, see
+/// [DeletionOps::add_interval_edge].
+///
+/// The edge is added at the start of every row, the deletions are only added once all rows are
+/// loaded. Overlapping and adjacent intervals of the same edge and layer are merged such that the
+/// edge is only deleted at the end of their union, independent of the order of the rows.
+pub(crate) fn load_edge_intervals_from_df<
+    'a,
+    G: StaticGraphViewOps + InternalPropertyAdditionOps + InternalAdditionOps + DeletionOps,
+>(
+    df_view: DFView<impl Iterator<Item = Result<DFChunk, GraphError>>>,
+    start: &str,
+    end: &str,
+    src: &str,
+    dst: &str,
+    properties: Option<&[&str]>,
+    constant_properties: Option<&[&str]>,
+    shared_constant_properties: Option<&HashMap<String, Prop>>,
+    layer: Option<&str>,
+    layer_col: Option<&str>,
+    graph: &G,
+) -> Result<(), GraphError> {
+    let properties = properties.unwrap_or(&[]);
+    let constant_properties = constant_properties.unwrap_or(&[]);
+
+    let properties_indices = properties
+        .iter()
+        .map(|name| df_view.get_index(name))
+        .collect::<Result<Vec<_>, GraphError>>()?;
+    let constant_properties_indices = constant_properties
+        .iter()
+        .map(|name| df_view.get_index(name))
+        .collect::<Result<Vec<_>, GraphError>>()?;
+
+    let src_index = df_view.get_index(src)?;
+    let dst_index = df_view.get_index(dst)?;
+    let start_index = df_view.get_index(start)?;
+    let end_index = df_view.get_index(end)?;
+    let layer_index = if let Some(layer_col) = layer_col {
+        Some(df_view.get_index(layer_col.as_ref()))
+    } else {
+        None
+    };
+    let layer_index = layer_index.transpose()?;
+    let mut pb = build_progress_bar("Loading edge intervals".to_string(), df_view.num_rows)?;
+    let mut intervals = HashMap::new();
+
+    for chunk in df_view.chunks {
+        let df = chunk?;
+        let prop_iter = combine_properties(properties, &properties_indices, &df)?;
+        let const_prop_iter =
+            combine_properties(constant_properties, &constant_properties_indices, &df)?;
+
+        let layer = lift_layer(layer, layer_index, &df)?;
+
+        if let (Some(src), Some(dst), Some(start), Some(end)) = (
+            df.iter_col::<u64>(src_index),
+            df.iter_col::<u64>(dst_index),
+            df.time_iter_col(start_index),
+            df.time_iter_col(end_index),
+        ) {
+            let edges = src
+                .map(|i| i.copied())
+                .zip(dst.map(|i| i.copied()))
+                .zip(start.zip(end));
+            load_edge_intervals_from_iter(
+                graph,
+                &mut pb,
+                edges,
+                prop_iter,
+                const_prop_iter,
+                shared_constant_properties,
+                layer,
+                &mut intervals,
+            )?;
+        } else if let (Some(src), Some(dst), Some(start), Some(end)) = (
+            df.iter_col::<i64>(src_index),
+            df.iter_col::<i64>(dst_index),
+            df.time_iter_col(start_index),
+            df.time_iter_col(end_index),
+        ) {
+            let edges = src
+                .map(i64_opt_into_u64_opt)
+                .zip(dst.map(i64_opt_into_u64_opt))
+                .zip(start.zip(end));
+            load_edge_intervals_from_iter(
+                graph,
+                &mut pb,
+                edges,
+                prop_iter,
+                const_prop_iter,
+                shared_constant_properties,
+                layer,
+                &mut intervals,
+            )?;
+        } else if let (Some(src), Some(dst), Some(start), Some(end)) = (
+            df.utf8::<i32>(src_index),
+            df.utf8::<i32>(dst_index),
+            df.time_iter_col(start_index),
+            df.time_iter_col(end_index),
+        ) {
+            let edges = src.into_iter().zip(dst.into_iter()).zip(start.zip(end));
+            load_edge_intervals_from_iter(
+                graph,
+                &mut pb,
+                edges,
+                prop_iter,
+                const_prop_iter,
+                shared_constant_properties,
+                layer,
+                &mut intervals,
+            )?;
+        } else if let (Some(src), Some(dst), Some(start), Some(end)) = (
+            df.utf8::<i64>(src_index),
+            df.utf8::<i64>(dst_index),
+            df.time_iter_col(start_index),
+            df.time_iter_col(end_index),
+        ) {
+            let edges = src.into_iter().zip(dst.into_iter()).zip(start.zip(end));
+            load_edge_intervals_from_iter(
+                graph,
+                &mut pb,
+                edges,
+                prop_iter,
+                const_prop_iter,
+                shared_constant_properties,
+                layer,
+                &mut intervals,
+            )?;
+        } else {
+            return Err(GraphError::LoadFailure(
+                "Source and Target columns must be either u64 or text, Start and End columns must be i64. Ensure these contain no NaN, Null or None values."
+                    .to_string(),
+            ));
+        };
+    }
+    delete_at_interval_ends(graph, intervals)
+}
+
 pub(crate) fn load_node_props_from_df<
     'a,
     G: StaticGraphViewOps + InternalPropertyAdditionOps + InternalAdditionOps,
@@ -664,10 +878,11 @@ fn i64_opt_into_u64_opt(x: Option<&i64>) -> Option<u64> {
     x.map(|x| (*x).try_into().unwrap())
 }
 
-fn load_edges_from_num_iter<
+fn load_edges_from_iter<
     'a,
+    V: AsNodeRef,
     S: AsRef<str>,
-    I: Iterator<Item = ((Option<u64>, Option<u64>), Option<i64>)>,
+    I: Iterator<Item = ((Option<V>, Option<V>), Option<i64>)>,
     PI: Iterator<Item = Vec<(S, Prop)>>,
     IL: Iterator<Item = Option<String>>,
     G: StaticGraphViewOps + InternalPropertyAdditionOps + InternalAdditionOps,
@@ -679,26 +894,108 @@ fn load_edges_from_num_iter<
     constant_properties: PI,
     shared_constant_properties: Option<&HashMap<String, Prop>>,
     layer: IL,
+    row_offset: usize,
+    on_row: &mut RowHandler,
 ) -> Result<(), GraphError> {
-    for (((((src, dst), time), edge_props), const_props), layer) in
-        edges.zip(properties).zip(constant_properties).zip(layer)
+    for (row, (((((src, dst), time), edge_props), const_props), layer)) in edges
+        .zip(properties)
+        .zip(constant_properties)
+        .zip(layer)
+        .enumerate()
     {
         if let (Some(src), Some(dst), Some(time)) = (src, dst, time) {
-            let e = graph.add_edge(time, src, dst, edge_props, layer.as_deref())?;
+            let res = graph
+                .add_edge(time, src, dst, edge_props, layer.as_deref())
+                .and_then(|e| {
+                    e.add_constant_properties(const_props, layer.as_deref())?;
+                    if let Some(shared_const_props) = &shared_constant_properties {
+                        e.add_constant_properties(shared_const_props.iter(), layer.as_deref())?;
+                    }
+                    Ok(())
+                });
+            on_row(row_offset + row, res)?;
+        }
+        let _ = pb.update(1);
+    }
+    Ok(())
+}
+
+fn load_edge_intervals_from_iter<
+    'a,
+    V: AsNodeRef,
+    S: AsRef<str>,
+    I: Iterator<Item = ((Option<V>, Option<V>), (Option<i64>, Option<i64>))>,
+    PI: Iterator<Item = Vec<(S, Prop)>>,
+    IL: Iterator<Item = Option<String>>,
+    G: StaticGraphViewOps + InternalPropertyAdditionOps + InternalAdditionOps + DeletionOps,
+>(
+    graph: &G,
+    pb: &mut Bar,
+    edges: I,
+    properties: PI,
+    constant_properties: PI,
+    shared_constant_properties: Option<&HashMap<String, Prop>>,
+    layer: IL,
+    intervals: &mut EdgeIntervals,
+) -> Result<(), GraphError> {
+    for (((((src, dst), (start, end)), edge_props), const_props), layer) in
+        edges.zip(properties).zip(constant_properties).zip(layer)
+    {
+        if let (Some(src), Some(dst), Some(start)) = (src, dst, start) {
+            if let Some(end) = end.filter(|end| *end < start) {
+                return Err(GraphError::InvalidEdgeInterval { start, end });
+            }
+            let e = graph.add_edge(start, src, dst, edge_props, layer.as_deref())?;
             e.add_constant_properties(const_props, layer.as_deref())?;
             if let Some(shared_const_props) = &shared_constant_properties {
                 e.add_constant_properties(shared_const_props.iter(), layer.as_deref())?;
             }
+            intervals
+                .entry((e.edge.src(), e.edge.dst(), layer))
+                .or_default()
+                .push((start, end));
         }
         let _ = pb.update(1);
     }
     Ok(())
 }
 
-fn load_nodes_from_num_iter<
+/// The `(start, end)` intervals of every `(src, dst, layer)` edge, an interval without end never
+/// ends
+type EdgeIntervals = HashMap<(VID, VID, Option<String>), Vec<(i64, Option<i64>)>>;
+
+/// Delete every edge at the end of each union of its overlapping or adjacent intervals
+fn delete_at_interval_ends<G: StaticGraphViewOps + DeletionOps>(
+    graph: &G,
+    intervals: EdgeIntervals,
+) -> Result<(), GraphError> {
+    for ((src, dst, layer), mut intervals) in intervals {
+        intervals.sort_unstable_by_key(|(start, _)| *start);
+        // `None` until the first interval, `Some(None)` if the union never ends
+        let mut union_end: Option<Option<i64>> = None;
+        for (start, end) in intervals {
+            union_end = match union_end {
+                Some(Some(union_end)) if union_end < start => {
+                    graph.delete_edge(union_end, src, dst, layer.as_deref())?;
+                    Some(end)
+                }
+                Some(Some(union_end)) => Some(end.map(|end| end.max(union_end))),
+                Some(None) => Some(None),
+                None => Some(end),
+            };
+        }
+        if let Some(Some(union_end)) = union_end {
+            graph.delete_edge(union_end, src, dst, layer.as_deref())?;
+        }
+    }
+    Ok(())
+}
+
+fn load_nodes_from_iter<
     'a,
+    V: AsNodeRef,
     S: AsRef<str>,
-    I: Iterator<Item = (Option<u64>, Option<i64>, Option<&'a str>)>,
+    I: Iterator<Item = (Option<V>, Option<i64>, Option<&'a str>)>,
     PI: Iterator<Item = Vec<(S, Prop)>>,
     G: StaticGraphViewOps + InternalPropertyAdditionOps + InternalAdditionOps,
 >(
@@ -708,22 +1005,24 @@ fn load_nodes_from_num_iter<
     properties: PI,
     constant_properties: PI,
     shared_constant_properties: Option<&HashMap<String, Prop>>,
+    row_offset: usize,
+    on_row: &mut RowHandler,
 ) -> Result<(), GraphError> {
-    for (((node, time, node_type), props), const_props) in
-        nodes.zip(properties).zip(constant_properties)
+    for (row, (((node, time, node_type), props), const_props)) in
+        nodes.zip(properties).zip(constant_properties).enumerate()
     {
-        if let (Some(v), Some(t), n_t, props, const_props) =
-            (node, time, node_type, props, const_props)
-        {
-            let actual_node_type = extract_out_default_type(n_t);
-            let v = graph.add_node(t, v, props, actual_node_type)?;
-            v.add_constant_properties(const_props)?;
-
-            if let Some(shared_const_props) = &shared_constant_properties {
-                v.add_constant_properties(shared_const_props.iter())?;
-            }
-            let _ = pb.update(1);
+        if let (Some(v), Some(t)) = (node, time) {
+            let actual_node_type = extract_out_default_type(node_type);
+            let res = graph.add_node(t, v, props, actual_node_type).and_then(|v| {
+                v.add_constant_properties(const_props)?;
+                if let Some(shared_const_props) = &shared_constant_properties {
+                    v.add_constant_properties(shared_const_props.iter())?;
+                }
+                Ok(())
+            });
+            on_row(row_offset + row, res)?;
         }
+        let _ = pb.update(1);
     }
     Ok(())
 }
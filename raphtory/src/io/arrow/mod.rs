@@ -1,6 +1,6 @@
 pub mod dataframe;
 pub mod df_loaders;
-mod prop_handler;
+pub(crate) mod prop_handler;
 
 #[cfg(test)]
 mod test {
@@ -186,4 +186,96 @@ mod test {
             ]
         );
     }
+
+    #[test]
+    fn load_adjacent_edge_intervals_from_pretend_df() {
+        let df = DFView {
+            names: vec!["src", "dst", "start", "end", "weight"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            chunks: vec![Ok(DFChunk {
+                chunk: vec![
+                    Box::new(PrimitiveArray::<u64>::from(vec![Some(1), Some(1)])),
+                    Box::new(PrimitiveArray::<u64>::from(vec![Some(2), Some(2)])),
+                    Box::new(PrimitiveArray::<i64>::from(vec![Some(1), Some(5)])),
+                    Box::new(PrimitiveArray::<i64>::from(vec![Some(5), Some(10)])),
+                    Box::new(PrimitiveArray::<f64>::from(vec![Some(1.0), Some(2.0)])),
+                ],
+            })]
+            .into_iter(),
+            num_rows: 2,
+        };
+        let graph = PersistentGraph::new();
+        load_edge_intervals_from_df(
+            df,
+            "start",
+            "end",
+            "src",
+            "dst",
+            Some(&*vec!["weight"]),
+            None,
+            None,
+            None,
+            None,
+            &graph,
+        )
+        .expect("failed to load edge intervals from pretend df");
+
+        let edge = graph.edge(1, 2).unwrap();
+        // adjacent intervals are merged, the edge is only deleted at the end of the second one
+        assert_eq!(edge.deletions(), vec![10]);
+        assert!(graph.at(5).edge(1, 2).unwrap().is_valid());
+        assert_eq!(
+            graph.at(5).edge(1, 2).unwrap().properties().get("weight"),
+            Some(Prop::F64(2.0))
+        );
+    }
+
+    #[test]
+    fn load_overlapping_edge_intervals_out_of_order_from_pretend_df() {
+        let df = DFView {
+            names: vec!["src", "dst", "start", "end"]
+                .iter()
+                .map(|s| s.to_string())
+                .collect(),
+            chunks: vec![
+                Ok(DFChunk {
+                    chunk: vec![
+                        Box::new(PrimitiveArray::<u64>::from(vec![Some(1), Some(1)])),
+                        Box::new(PrimitiveArray::<u64>::from(vec![Some(2), Some(2)])),
+                        Box::new(PrimitiveArray::<i64>::from(vec![Some(3), Some(20)])),
+                        Box::new(PrimitiveArray::<i64>::from(vec![Some(10), Some(25)])),
+                    ],
+                }),
+                Ok(DFChunk {
+                    chunk: vec![
+                        Box::new(PrimitiveArray::<u64>::from(vec![Some(1), Some(1)])),
+                        Box::new(PrimitiveArray::<u64>::from(vec![Some(2), Some(2)])),
+                        Box::new(PrimitiveArray::<i64>::from(vec![Some(1), Some(22)])),
+                        Box::new(PrimitiveArray::<i64>::from(vec![Some(5), Some(30)])),
+                    ],
+                }),
+            ]
+            .into_iter(),
+            num_rows: 4,
+        };
+        let graph = PersistentGraph::new();
+        load_edge_intervals_from_df(
+            df, "start", "end", "src", "dst", None, None, None, None, None, &graph,
+        )
+        .expect("failed to load edge intervals from pretend df");
+
+        // [1, 5) and [3, 10) overlap as do [20, 25) and [22, 30)
+        let edge = graph.edge(1, 2).unwrap();
+        assert_eq!(edge.history(), vec![1, 3, 20, 22]);
+        assert_eq!(edge.deletions(), vec![10, 30]);
+        let alive = |t: i64| graph.at(t).edge(1, 2).is_some_and(|e| e.is_valid());
+        for t in [1, 5, 9, 20, 25, 29] {
+            assert!(alive(t), "edge should be alive at {t}");
+        }
+        for t in [0, 10, 19, 30] {
+            assert!(!alive(t), "edge should not be alive at {t}");
+        }
+    }
 }
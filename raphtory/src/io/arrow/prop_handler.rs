@@ -1,9 +1,13 @@
 use chrono::{DateTime, Utc};
 use polars_arrow::{
-    array::{Array, BooleanArray, FixedSizeListArray, ListArray, PrimitiveArray, Utf8Array},
+    array::{
+        Array, BooleanArray, FixedSizeListArray, ListArray, PrimitiveArray, StructArray, Utf8Array,
+    },
     datatypes::{ArrowDataType as DataType, TimeUnit},
 };
 
+use std::sync::Arc;
+
 use crate::{
     core::{utils::errors::GraphError, IntoPropList},
     io::arrow::dataframe::DFChunk,
@@ -114,6 +118,12 @@ fn arr_as_prop(arr: Box<dyn Array>) -> Prop {
                 .map(|elem| arr_as_prop(elem))
                 .into_prop_list()
         }
+        DataType::Struct(_) => {
+            let arr = arr.as_any().downcast_ref::<StructArray>().unwrap();
+            (0..arr.len())
+                .filter_map(|i| struct_as_prop(arr, i))
+                .into_prop_list()
+        }
         _ => panic!("Data type not recognized"),
     }
 }
@@ -134,6 +144,11 @@ fn is_data_type_supported(dt: &DataType) -> Result<(), GraphError> {
         DataType::List(v) => is_data_type_supported(v.data_type())?,
         DataType::FixedSizeList(v, _) => is_data_type_supported(v.data_type())?,
         DataType::LargeList(v) => is_data_type_supported(v.data_type())?,
+        DataType::Struct(fields) => {
+            for field in fields {
+                is_data_type_supported(field.data_type())?
+            }
+        }
         DataType::Timestamp(_, _) => {}
         _ => Err(GraphError::UnsupportedDataType)?,
     }
@@ -203,6 +218,12 @@ pub(crate) fn lift_property<'a: 'b, 'b>(
             let arr = arr.as_any().downcast_ref::<ListArray<i64>>().unwrap();
             iter_as_arr_prop(name, arr.iter())
         }
+        DataType::Struct(_) => {
+            let arr = arr.as_any().downcast_ref::<StructArray>().unwrap();
+            let r: Box<dyn Iterator<Item = Option<(&'b str, Prop)>> + 'b> =
+                Box::new((0..arr.len()).map(move |i| struct_as_prop(arr, i).map(|v| (name, v))));
+            r
+        }
         DataType::Timestamp(timeunit, timezone) => {
             let arr = arr.as_any().downcast_ref::<PrimitiveArray<i64>>().unwrap();
             match timezone {
@@ -342,6 +363,77 @@ pub(crate) fn lift_property<'a: 'b, 'b>(
     r
 }
 
+/// The value of row `i` of a struct array as a map property, fields without a value are left out
+fn struct_as_prop(arr: &StructArray, i: usize) -> Option<Prop> {
+    if arr.is_null(i) {
+        return None;
+    }
+    let values = arr
+        .fields()
+        .iter()
+        .zip(arr.values())
+        .filter_map(|(field, values)| {
+            value_as_prop(values.as_ref(), i).map(|v| (field.name.as_str().into(), v))
+        })
+        .collect();
+    Some(Prop::Map(Arc::new(values)))
+}
+
+/// The items of a list value as a list property, items without a value are left out
+fn list_as_prop(values: Box<dyn Array>) -> Prop {
+    (0..values.len())
+        .filter_map(|i| value_as_prop(values.as_ref(), i))
+        .collect::<Vec<_>>()
+        .into()
+}
+
+/// The value of row `i` of an array of a supported type
+pub(crate) fn value_as_prop(arr: &dyn Array, i: usize) -> Option<Prop> {
+    if arr.is_null(i) {
+        return None;
+    }
+    let prop = match arr.data_type().to_logical_type() {
+        DataType::Boolean => Prop::Bool(arr.as_any().downcast_ref::<BooleanArray>()?.value(i)),
+        DataType::Int32 => Prop::I32(arr.as_any().downcast_ref::<PrimitiveArray<i32>>()?.value(i)),
+        DataType::Int64 => Prop::I64(arr.as_any().downcast_ref::<PrimitiveArray<i64>>()?.value(i)),
+        DataType::UInt8 => Prop::U8(arr.as_any().downcast_ref::<PrimitiveArray<u8>>()?.value(i)),
+        DataType::UInt16 => Prop::U16(arr.as_any().downcast_ref::<PrimitiveArray<u16>>()?.value(i)),
+        DataType::UInt32 => Prop::U32(arr.as_any().downcast_ref::<PrimitiveArray<u32>>()?.value(i)),
+        DataType::UInt64 => Prop::U64(arr.as_any().downcast_ref::<PrimitiveArray<u64>>()?.value(i)),
+        DataType::Float32 => {
+            Prop::F32(arr.as_any().downcast_ref::<PrimitiveArray<f32>>()?.value(i))
+        }
+        DataType::Float64 => {
+            Prop::F64(arr.as_any().downcast_ref::<PrimitiveArray<f64>>()?.value(i))
+        }
+        DataType::Utf8 => Prop::str(arr.as_any().downcast_ref::<Utf8Array<i32>>()?.value(i)),
+        DataType::LargeUtf8 => Prop::str(arr.as_any().downcast_ref::<Utf8Array<i64>>()?.value(i)),
+        DataType::List(_) => list_as_prop(arr.as_any().downcast_ref::<ListArray<i32>>()?.value(i)),
+        DataType::LargeList(_) => {
+            list_as_prop(arr.as_any().downcast_ref::<ListArray<i64>>()?.value(i))
+        }
+        DataType::FixedSizeList(_, _) => {
+            list_as_prop(arr.as_any().downcast_ref::<FixedSizeListArray>()?.value(i))
+        }
+        DataType::Struct(_) => struct_as_prop(arr.as_any().downcast_ref::<StructArray>()?, i)?,
+        DataType::Timestamp(timeunit, timezone) => {
+            let v = arr.as_any().downcast_ref::<PrimitiveArray<i64>>()?.value(i);
+            let time = match timeunit {
+                TimeUnit::Second => DateTime::<Utc>::from_timestamp(v, 0)?,
+                TimeUnit::Millisecond => DateTime::<Utc>::from_timestamp_millis(v)?,
+                TimeUnit::Microsecond => DateTime::<Utc>::from_timestamp_micros(v)?,
+                TimeUnit::Nanosecond => DateTime::<Utc>::from_timestamp_nanos(v),
+            };
+            match timezone {
+                Some(_) => Prop::DTime(time),
+                None => Prop::NDTime(time.naive_utc()),
+            }
+        }
+        _ => return None,
+    };
+    Some(prop)
+}
+
 pub(crate) fn lift_layer<'a>(
     layer_name: Option<&str>,
     layer_index: Option<usize>,
@@ -0,0 +1,421 @@
+use crate::{
+    core::{utils::errors::GraphError, Prop},
+    db::api::{
+        mutation::internal::{InternalAdditionOps, InternalPropertyAdditionOps},
+        view::StaticGraphViewOps,
+    },
+    io::{
+        arrow::{dataframe::*, df_loaders::*},
+        parquet_loaders::process_parquet_file_to_df,
+    },
+};
+use itertools::Itertools;
+use polars_arrow::io::ipc::read::{read_file_metadata, FileReader};
+use std::{collections::HashMap, fmt::Debug, fs::File, path::Path};
+
+/// File formats supported by the bulk loaders
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DataFileFormat {
+    Parquet,
+    ArrowIpc,
+}
+
+impl DataFileFormat {
+    /// Guess the format of a file from its extension
+    pub fn from_path(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()? {
+            "parquet" => Some(DataFileFormat::Parquet),
+            "arrow" | "ipc" | "feather" => Some(DataFileFormat::ArrowIpc),
+            _ => None,
+        }
+    }
+}
+
+/// A row that could not be added to the graph
+#[derive(Debug)]
+pub struct RowError {
+    /// Index of the row in the input file
+    pub row: usize,
+    pub error: GraphError,
+}
+
+/// Summary of a bulk load
+#[derive(Debug, Default)]
+pub struct LoadReport {
+    /// Total number of rows in the input file
+    pub num_rows: usize,
+    /// Number of rows that were added to the graph completely, rows with missing values are
+    /// skipped
+    pub num_loaded: usize,
+    /// Number of rows that failed to load, these may have been added to the graph partially
+    pub num_failed: usize,
+    /// The first `max_errors` row errors
+    pub errors: Vec<RowError>,
+}
+
+impl LoadReport {
+    fn row_handler(
+        &mut self,
+        max_errors: usize,
+    ) -> impl FnMut(usize, Result<(), GraphError>) -> Result<(), GraphError> + '_ {
+        move |row, result| {
+            match result {
+                Ok(()) => self.num_loaded += 1,
+                Err(error) => {
+                    self.num_failed += 1;
+                    if self.errors.len() < max_errors {
+                        self.errors.push(RowError { row, error });
+                    }
+                }
+            }
+            Ok(())
+        }
+    }
+}
+
+/// Load edges from a Parquet or Arrow IPC file.
+///
+/// Unlike `load_edges_from_parquet`, rows that fail to load do not abort the load but are collected
+/// in the returned [LoadReport]. `on_progress` is called with the number of rows read so far and the
+/// total number of rows each time a chunk of the file is processed.
+pub fn load_edges_from_file<
+    G: StaticGraphViewOps + InternalPropertyAdditionOps + InternalAdditionOps,
+>(
+    graph: &G,
+    path: &Path,
+    format: DataFileFormat,
+    time: &str,
+    src: &str,
+    dst: &str,
+    properties: Option<&[&str]>,
+    constant_properties: Option<&[&str]>,
+    shared_constant_properties: Option<&HashMap<String, Prop>>,
+    layer: Option<&str>,
+    layer_col: Option<&str>,
+    max_errors: usize,
+    on_progress: impl FnMut(usize, usize),
+) -> Result<LoadReport, GraphError> {
+    let mut cols_to_check = vec![src, dst, time];
+    cols_to_check.extend(properties.unwrap_or(&Vec::new()));
+    cols_to_check.extend(constant_properties.unwrap_or(&Vec::new()));
+    if let Some(ref layer_col) = layer_col {
+        cols_to_check.push(layer_col.as_ref());
+    }
+
+    let mut report = LoadReport::default();
+    let df_view = process_file_to_df(path, format, &cols_to_check)?;
+    df_view.check_cols_exist(&cols_to_check)?;
+    report.num_rows = df_view.num_rows;
+    load_edges_from_df_with_row_errors(
+        with_progress(df_view, on_progress),
+        time,
+        src,
+        dst,
+        properties,
+        constant_properties,
+        shared_constant_properties,
+        layer,
+        layer_col,
+        graph,
+        &mut report.row_handler(max_errors),
+    )?;
+    Ok(report)
+}
+
+/// Load nodes from a Parquet or Arrow IPC file.
+///
+/// See [load_edges_from_file] for how row errors and progress are reported.
+pub fn load_nodes_from_file<
+    G: StaticGraphViewOps + InternalPropertyAdditionOps + InternalAdditionOps,
+>(
+    graph: &G,
+    path: &Path,
+    format: DataFileFormat,
+    time: &str,
+    id: &str,
+    node_type: Option<&str>,
+    node_type_col: Option<&str>,
+    properties: Option<&[&str]>,
+    constant_properties: Option<&[&str]>,
+    shared_constant_properties: Option<&HashMap<String, Prop>>,
+    max_errors: usize,
+    on_progress: impl FnMut(usize, usize),
+) -> Result<LoadReport, GraphError> {
+    let mut cols_to_check = vec![id, time];
+    cols_to_check.extend(properties.unwrap_or(&Vec::new()));
+    cols_to_check.extend(constant_properties.unwrap_or(&Vec::new()));
+    if let Some(ref node_type_col) = node_type_col {
+        cols_to_check.push(node_type_col.as_ref());
+    }
+
+    let mut report = LoadReport::default();
+    let df_view = process_file_to_df(path, format, &cols_to_check)?;
+    df_view.check_cols_exist(&cols_to_check)?;
+    report.num_rows = df_view.num_rows;
+    load_nodes_from_df_with_row_errors(
+        with_progress(df_view, on_progress),
+        time,
+        id,
+        properties,
+        constant_properties,
+        shared_constant_properties,
+        node_type,
+        node_type_col,
+        graph,
+        &mut report.row_handler(max_errors),
+    )?;
+    Ok(report)
+}
+
+fn with_progress(
+    df_view: DFView<impl Iterator<Item = Result<DFChunk, GraphError>>>,
+    mut on_progress: impl FnMut(usize, usize),
+) -> DFView<impl Iterator<Item = Result<DFChunk, GraphError>>> {
+    let num_rows = df_view.num_rows;
+    let mut rows_read = 0;
+    let chunks = df_view.chunks.inspect(move |chunk| {
+        if let Ok(chunk) = chunk {
+            rows_read += chunk.len();
+            on_progress(rows_read, num_rows);
+        }
+    });
+    DFView {
+        names: df_view.names,
+        chunks,
+        num_rows,
+    }
+}
+
+fn process_file_to_df(
+    path: &Path,
+    format: DataFileFormat,
+    col_names: &[&str],
+) -> Result<DFView<Box<dyn Iterator<Item = Result<DFChunk, GraphError>>>>, GraphError> {
+    match format {
+        DataFileFormat::Parquet => {
+            let df_view = process_parquet_file_to_df(path, col_names)?;
+            Ok(DFView {
+                names: df_view.names,
+                chunks: Box::new(df_view.chunks),
+                num_rows: df_view.num_rows,
+            })
+        }
+        DataFileFormat::ArrowIpc => {
+            let df_view = process_ipc_file_to_df(path, col_names)?;
+            Ok(DFView {
+                names: df_view.names,
+                chunks: Box::new(df_view.chunks),
+                num_rows: df_view.num_rows,
+            })
+        }
+    }
+}
+
+pub(crate) fn process_ipc_file_to_df(
+    ipc_file_path: &Path,
+    col_names: &[&str],
+) -> Result<DFView<impl Iterator<Item = Result<DFChunk, GraphError>>>, GraphError> {
+    let mut file = File::open(ipc_file_path)?;
+    let metadata = read_file_metadata(&mut file)?;
+
+    // Only read the columns that are needed, the reader returns them in schema order
+    let projection = metadata
+        .schema
+        .fields
+        .iter()
+        .enumerate()
+        .filter(|(_, f)| col_names.contains(&f.name.as_str()))
+        .map(|(i, _)| i)
+        .collect_vec();
+    let names = projection
+        .iter()
+        .map(|i| metadata.schema.fields[*i].name.clone())
+        .collect_vec();
+
+    // The footer does not store the number of rows, count them reading a single column one batch
+    // at a time
+    let num_rows = match projection.first() {
+        Some(&col) => {
+            let counter = FileReader::new(
+                File::open(ipc_file_path)?,
+                metadata.clone(),
+                Some(vec![col]),
+                None,
+            );
+            counter
+                .map(|batch| batch.map(|batch| batch.len()))
+                .sum::<Result<usize, _>>()
+                .map_err(ipc_error)?
+        }
+        None => 0,
+    };
+
+    let reader = FileReader::new(file, metadata, Some(projection), None);
+    let chunks = reader.map(|result| {
+        result
+            .map(|batch| DFChunk {
+                chunk: batch.into_arrays(),
+            })
+            .map_err(ipc_error)
+    });
+
+    Ok(DFView {
+        names,
+        chunks,
+        num_rows,
+    })
+}
+
+fn ipc_error(err: impl Debug) -> GraphError {
+    GraphError::LoadFailure(format!("Failed to process IPC file: {:?}", err))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::prelude::*;
+    use polars_arrow::{
+        array::{PrimitiveArray, Utf8Array},
+        datatypes::{ArrowDataType, ArrowSchema, Field},
+        io::ipc::write::{FileWriter, WriteOptions},
+        record_batch::RecordBatch,
+    };
+    use tempfile::TempDir;
+
+    fn write_ipc_file(path: &Path) {
+        let schema = ArrowSchema::from(vec![
+            Field::new("src", ArrowDataType::UInt64, false),
+            Field::new("dst", ArrowDataType::UInt64, false),
+            Field::new("time", ArrowDataType::Int64, false),
+            Field::new("weight", ArrowDataType::Float64, false),
+            Field::new("weight_str", ArrowDataType::Utf8, false),
+        ]);
+        let batch = RecordBatch::new(vec![
+            PrimitiveArray::<u64>::from_vec(vec![1, 2, 3]).boxed(),
+            PrimitiveArray::<u64>::from_vec(vec![2, 3, 4]).boxed(),
+            PrimitiveArray::<i64>::from_vec(vec![1, 2, 3]).boxed(),
+            PrimitiveArray::<f64>::from_vec(vec![1.0, 2.0, 3.0]).boxed(),
+            Utf8Array::<i32>::from_slice(["a", "b", "c"]).boxed(),
+        ]);
+        let file = File::create(path).unwrap();
+        let mut writer =
+            FileWriter::try_new(file, schema, None, WriteOptions { compression: None }).unwrap();
+        writer.write(&batch, None).unwrap();
+        writer.finish().unwrap();
+    }
+
+    #[test]
+    fn load_edges_from_ipc_file() {
+        let tmp_dir = TempDir::new().unwrap();
+        let path = tmp_dir.path().join("edges.arrow");
+        write_ipc_file(&path);
+
+        let graph = Graph::new();
+        let mut progress = vec![];
+        let report = load_edges_from_file(
+            &graph,
+            &path,
+            DataFileFormat::from_path(&path).unwrap(),
+            "time",
+            "src",
+            "dst",
+            Some(&["weight"]),
+            None,
+            None,
+            None,
+            None,
+            10,
+            |done, total| progress.push((done, total)),
+        )
+        .unwrap();
+
+        assert_eq!(report.num_rows, 3);
+        assert_eq!(report.num_loaded, 3);
+        assert_eq!(report.num_failed, 0);
+        assert_eq!(progress, vec![(3, 3)]);
+        assert_eq!(graph.count_edges(), 3);
+        assert_eq!(
+            graph
+                .edge(2, 3)
+                .unwrap()
+                .properties()
+                .temporal()
+                .get("weight")
+                .unwrap()
+                .latest(),
+            Some(Prop::F64(2.0))
+        );
+    }
+
+    #[test]
+    fn row_errors_are_reported_and_skipped() {
+        let tmp_dir = TempDir::new().unwrap();
+        let path = tmp_dir.path().join("edges.arrow");
+        write_ipc_file(&path);
+
+        let graph = Graph::new();
+        // "weight" already exists with a different type so every row fails
+        graph
+            .add_edge(0, 1, 2, [("weight", Prop::I64(1))], None)
+            .unwrap();
+        let report = load_edges_from_file(
+            &graph,
+            &path,
+            DataFileFormat::ArrowIpc,
+            "time",
+            "src",
+            "dst",
+            Some(&["weight"]),
+            None,
+            None,
+            None,
+            None,
+            2,
+            |_, _| {},
+        )
+        .unwrap();
+
+        assert_eq!(report.num_loaded, 0);
+        assert_eq!(report.num_failed, 3);
+        assert_eq!(
+            report.errors.iter().map(|e| e.row).collect_vec(),
+            vec![0, 1]
+        );
+        assert_eq!(graph.count_temporal_edges(), 1);
+    }
+
+    #[test]
+    fn partially_applied_rows_are_not_loaded() {
+        let tmp_dir = TempDir::new().unwrap();
+        let path = tmp_dir.path().join("edges.arrow");
+        write_ipc_file(&path);
+
+        let graph = Graph::new();
+        // the edges are added but their constant property has the wrong type
+        graph
+            .add_edge(0, 1, 2, NO_PROPS, None)
+            .unwrap()
+            .add_constant_properties([("weight_str", 1i64)], None)
+            .unwrap();
+        let report = load_edges_from_file(
+            &graph,
+            &path,
+            DataFileFormat::ArrowIpc,
+            "time",
+            "src",
+            "dst",
+            None,
+            Some(&["weight_str"]),
+            None,
+            None,
+            None,
+            10,
+            |_, _| {},
+        )
+        .unwrap();
+
+        assert_eq!(report.num_loaded, 0);
+        assert_eq!(report.num_failed, 3);
+        assert_eq!(graph.count_edges(), 3);
+    }
+}
@@ -284,7 +284,7 @@ impl CsvLoader {
     ///
     /// An error of type CsvErr is returned if an I/O error occurs while reading the directory.
     ///
-    fn files_vec(&self) -> Result<Vec<PathBuf>, CsvErr> {
+    pub(crate) fn files_vec(&self) -> Result<Vec<PathBuf>, CsvErr> {
         let mut paths = vec![];
         let mut queue = VecDeque::from([self.path.to_path_buf()]);
 
@@ -438,7 +438,10 @@ impl CsvLoader {
     ///
     /// Returns a `csv::Reader` that can be used to read the CSV file, or a `CsvErr` if there was an error.
     ///
-    fn csv_reader(&self, file_path: PathBuf) -> Result<csv::Reader<Box<dyn io::Read>>, CsvErr> {
+    pub(crate) fn csv_reader(
+        &self,
+        file_path: PathBuf,
+    ) -> Result<csv::Reader<Box<dyn io::Read>>, CsvErr> {
         let is_gziped = file_path
             .file_name()
             .and_then(|name| name.to_str())
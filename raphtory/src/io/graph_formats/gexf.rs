@@ -0,0 +1,721 @@
+//! GEXF reader and writer.
+//!
+//! With [HistoryMode::Latest] a static graph is written. Otherwise the graph is dynamic, with
+//! [HistoryMode::Lifespan] every node and edge gets `start` and `end` attributes and with
+//! [HistoryMode::Full] it gets one spell per update (one spell per interval the edge is alive
+//! for a persistent graph) and temporal properties become dynamic attributes. Times are written
+//! as integers. The layer of an edge is written as its `kind` and the type of a node as the value
+//! of the reserved attribute with id `raphtory:node_type`. Graph properties are not exported as
+//! GEXF has no graph attributes.
+//!
+//! The reader adds nodes and edges at the start of each of their spells (or at their `start`
+//! time) and sets dynamic attribute values at their start time. The ends of the spells of an
+//! edge become deletions when reading into a persistent graph.
+
+use super::{
+    parse_time, parse_typed_prop, prop_to_text,
+    xml::{local_name, read_attributes, XmlWriter},
+    ExportedElement, ExportedGraph, GraphFormatError, HistoryMode, ImportTarget, ImportedEdge,
+    ImportedGraph, ImportedNode, Updates, DEFAULT_TIME, NODE_TYPE_ATTR,
+};
+use crate::prelude::*;
+use quick_xml::{
+    events::{BytesStart, Event},
+    Reader,
+};
+use std::{
+    collections::HashMap,
+    io::{BufRead, Write},
+};
+
+const GEXF_NS: &str = "http://www.gexf.net/1.2draft";
+const GEXF_VERSION: &str = "1.2";
+const LABEL_ATTR: &str = "label";
+const WEIGHT_ATTR: &str = "weight";
+/// Id of the attribute that holds node types. The ids of the attributes for properties are
+/// numbers, so a property called `node_type` does not clash with it.
+const NODE_TYPE_ID: &str = "raphtory:node_type";
+
+fn gexf_type(prop: &Prop) -> &'static str {
+    match prop {
+        Prop::Bool(_) => "boolean",
+        Prop::U8(_) | Prop::U16(_) | Prop::I32(_) => "integer",
+        Prop::U32(_) | Prop::U64(_) | Prop::I64(_) => "long",
+        Prop::F32(_) => "float",
+        Prop::F64(_) => "double",
+        _ => "string",
+    }
+}
+
+/// The attribute definitions of one class (node or edge), in order of first use
+#[derive(Default)]
+struct Attributes {
+    ids: HashMap<String, String>,
+    definitions: Vec<(String, String, &'static str)>,
+    /// Whether the reserved node type attribute is used
+    node_type: bool,
+}
+
+impl Attributes {
+    fn id(&mut self, title: &str, attr_type: &'static str) -> String {
+        let definitions = &mut self.definitions;
+        self.ids
+            .entry(title.to_owned())
+            .or_insert_with(|| {
+                let id = definitions.len().to_string();
+                definitions.push((id.clone(), title.to_owned(), attr_type));
+                id
+            })
+            .clone()
+    }
+}
+
+/// An element ready to be written: its XML attributes, attribute values and spells
+struct GexfElement {
+    attributes: Vec<(&'static str, String)>,
+    /// (attribute id, value, start, end of the interval (exclusive))
+    values: Vec<(String, String, Option<i64>, Option<i64>)>,
+    spells: Vec<(Option<i64>, Option<i64>)>,
+}
+
+impl GexfElement {
+    fn new(
+        element: &ExportedElement,
+        attributes: &mut Attributes,
+        mode: HistoryMode,
+        persistent: bool,
+    ) -> Self {
+        let mut values: Vec<_> = element
+            .props
+            .iter()
+            .map(|(name, prop)| {
+                let id = attributes.id(name, gexf_type(prop));
+                (id, prop_to_text(prop), None, None)
+            })
+            .collect();
+        for (name, updates) in &element.temporal {
+            for (i, (t, prop)) in updates.iter().enumerate() {
+                let id = attributes.id(name, gexf_type(prop));
+                let end = updates.get(i + 1).map(|(next, _)| *next);
+                values.push((id, prop_to_text(prop), Some(*t), end));
+            }
+        }
+        let mut xml_attributes = vec![];
+        let mut spells = vec![];
+        match mode {
+            HistoryMode::Latest => {}
+            HistoryMode::Lifespan => {
+                let (start, end) = element.lifespan(persistent);
+                xml_attributes.extend(start.map(|t| ("start", t.to_string())));
+                xml_attributes.extend(end.map(|t| ("end", t.to_string())));
+            }
+            HistoryMode::Full => spells = element.spells(persistent),
+        }
+        Self {
+            attributes: xml_attributes,
+            values,
+            spells,
+        }
+    }
+
+    fn write<W: Write>(
+        &self,
+        writer: &mut XmlWriter<W>,
+        name: &str,
+        mut attributes: Vec<(&str, &str)>,
+    ) -> Result<(), GraphFormatError> {
+        attributes.extend(self.attributes.iter().map(|(k, v)| (*k, v.as_str())));
+        writer.start(name, &attributes)?;
+        if !self.values.is_empty() {
+            writer.start("attvalues", &[])?;
+            for (id, value, start, end) in &self.values {
+                let start = start.map(|t| t.to_string());
+                let end = end.map(|t| t.to_string());
+                let mut attributes = vec![("for", id.as_str()), ("value", value.as_str())];
+                attributes.extend(start.as_deref().map(|t| ("start", t)));
+                attributes.extend(end.as_deref().map(|t| ("endopen", t)));
+                writer.empty("attvalue", &attributes)?;
+            }
+            writer.end("attvalues")?;
+        }
+        if !self.spells.is_empty() {
+            writer.start("spells", &[])?;
+            for (start, end) in &self.spells {
+                let start = start.map(|t| t.to_string());
+                let end = end.map(|t| t.to_string());
+                let mut attributes = vec![];
+                attributes.extend(start.as_deref().map(|t| ("start", t)));
+                attributes.extend(end.as_deref().map(|t| ("end", t)));
+                writer.empty("spell", &attributes)?;
+            }
+            writer.end("spells")?;
+        }
+        writer.end(name)
+    }
+}
+
+fn write_attributes<W: Write>(
+    writer: &mut XmlWriter<W>,
+    class: &str,
+    mode: &str,
+    attributes: &Attributes,
+) -> Result<(), GraphFormatError> {
+    if attributes.definitions.is_empty() && !attributes.node_type {
+        return Ok(());
+    }
+    writer.start("attributes", &[("class", class), ("mode", mode)])?;
+    if attributes.node_type {
+        writer.empty(
+            "attribute",
+            &[
+                ("id", NODE_TYPE_ID),
+                ("title", NODE_TYPE_ATTR),
+                ("type", "string"),
+            ],
+        )?;
+    }
+    for (id, title, attr_type) in &attributes.definitions {
+        writer.empty(
+            "attribute",
+            &[
+                ("id", id.as_str()),
+                ("title", title.as_str()),
+                ("type", *attr_type),
+            ],
+        )?;
+    }
+    writer.end("attributes")
+}
+
+/// Write `graph` as a GEXF document
+pub fn write_gexf<'graph, G: GraphViewOps<'graph>, W: Write>(
+    graph: &G,
+    writer: W,
+    mode: HistoryMode,
+) -> Result<(), GraphFormatError> {
+    let exported = ExportedGraph::new(graph, mode);
+    let mut node_attributes = Attributes::default();
+    let mut edge_attributes = Attributes::default();
+
+    let nodes: Vec<_> = exported
+        .nodes
+        .iter()
+        .map(|node| {
+            let mut element = GexfElement::new(
+                &node.element,
+                &mut node_attributes,
+                mode,
+                exported.persistent,
+            );
+            if let Some(node_type) = &node.node_type {
+                node_attributes.node_type = true;
+                element
+                    .values
+                    .push((NODE_TYPE_ID.to_owned(), node_type.to_string(), None, None));
+            }
+            (node.id.to_string(), element)
+        })
+        .collect();
+    let edges: Vec<_> = exported
+        .edges
+        .iter()
+        .map(|edge| {
+            let element = GexfElement::new(
+                &edge.element,
+                &mut edge_attributes,
+                mode,
+                exported.persistent,
+            );
+            (
+                edge.src.to_string(),
+                edge.dst.to_string(),
+                edge.layer.to_string(),
+                element,
+            )
+        })
+        .collect();
+
+    let graph_mode = if mode == HistoryMode::Latest {
+        "static"
+    } else {
+        "dynamic"
+    };
+    let attribute_mode = if mode == HistoryMode::Full {
+        "dynamic"
+    } else {
+        "static"
+    };
+    let mut writer = XmlWriter::new(writer)?;
+    writer.start("gexf", &[("xmlns", GEXF_NS), ("version", GEXF_VERSION)])?;
+    let mut graph_attributes = vec![("mode", graph_mode), ("defaultedgetype", "directed")];
+    if mode != HistoryMode::Latest {
+        graph_attributes.push(("timeformat", "integer"));
+    }
+    writer.start("graph", &graph_attributes)?;
+    write_attributes(&mut writer, "node", attribute_mode, &node_attributes)?;
+    write_attributes(&mut writer, "edge", attribute_mode, &edge_attributes)?;
+    writer.start("nodes", &[])?;
+    for (id, element) in &nodes {
+        element.write(&mut writer, "node", vec![("id", id), ("label", id)])?;
+    }
+    writer.end("nodes")?;
+    writer.start("edges", &[])?;
+    for (index, (src, dst, layer, element)) in edges.iter().enumerate() {
+        let id = index.to_string();
+        element.write(
+            &mut writer,
+            "edge",
+            vec![
+                ("id", id.as_str()),
+                ("source", src),
+                ("target", dst),
+                ("kind", layer),
+            ],
+        )?;
+    }
+    writer.end("edges")?;
+    writer.end("graph")?;
+    writer.end("gexf")?;
+    writer.finish()
+}
+
+/// An `<attribute>` definition read from a document
+struct AttributeDefinition {
+    title: String,
+    attr_type: String,
+    default: Option<String>,
+}
+
+/// A `<node>` or `<edge>` element while it is read
+#[derive(Default)]
+struct PendingElement {
+    attributes: Vec<(String, String)>,
+    /// The times of the `<spell>` elements, `None` if the element has no spells
+    spells: Option<Vec<(Option<i64>, Option<i64>)>>,
+    /// (attribute id, value, start)
+    values: Vec<(String, String, Option<i64>)>,
+}
+
+fn attr<'a>(attributes: &'a [(String, String)], name: &str) -> Option<&'a str> {
+    attributes
+        .iter()
+        .find(|(key, _)| key == name)
+        .map(|(_, value)| value.as_str())
+}
+
+fn required_attr<'a>(
+    attributes: &'a [(String, String)],
+    element: &str,
+    name: &str,
+) -> Result<&'a str, GraphFormatError> {
+    attr(attributes, name).ok_or_else(|| {
+        GraphFormatError::InvalidDocument(format!("<{element}> is missing the '{name}' attribute"))
+    })
+}
+
+fn parse_opt_time(
+    attributes: &[(String, String)],
+    closed: &str,
+    open: &str,
+) -> Result<Option<i64>, GraphFormatError> {
+    attr(attributes, closed)
+        .or_else(|| attr(attributes, open))
+        .map(parse_time)
+        .transpose()
+}
+
+impl PendingElement {
+    /// The start and end times of the spells of the element (or of the element itself)
+    fn spells(&self) -> Result<(Vec<i64>, Vec<i64>), GraphFormatError> {
+        match &self.spells {
+            Some(spells) => Ok((
+                spells.iter().filter_map(|(start, _)| *start).collect(),
+                spells.iter().filter_map(|(_, end)| *end).collect(),
+            )),
+            None => Ok((
+                parse_opt_time(&self.attributes, "start", "startopen")?
+                    .into_iter()
+                    .collect(),
+                parse_opt_time(&self.attributes, "end", "endopen")?
+                    .into_iter()
+                    .collect(),
+            )),
+        }
+    }
+
+    /// The updates of the element: one per spell start and one per dynamic attribute value.
+    /// Values of the reserved node type attribute are returned separately.
+    fn updates(
+        &self,
+        starts: &[i64],
+        definitions: &HashMap<String, AttributeDefinition>,
+    ) -> Result<(Updates, Option<String>), GraphFormatError> {
+        let first = starts.iter().copied().min().unwrap_or(DEFAULT_TIME);
+        let mut updates: Updates = starts.iter().map(|t| (*t, vec![])).collect();
+        updates.entry(first).or_default();
+        let mut node_type = None;
+        for (id, value, start) in &self.values {
+            if id == NODE_TYPE_ID {
+                node_type.get_or_insert_with(|| value.clone());
+                continue;
+            }
+            let definition = definitions.get(id).ok_or_else(|| {
+                GraphFormatError::InvalidDocument(format!("undefined attribute '{id}'"))
+            })?;
+            let prop = parse_typed_prop(&definition.attr_type, value);
+            updates
+                .entry(start.unwrap_or(first))
+                .or_default()
+                .push((definition.title.clone(), prop));
+        }
+        for (id, definition) in definitions {
+            let seen = self.values.iter().any(|(value_id, _, _)| value_id == id);
+            if let (false, Some(default)) = (seen, &definition.default) {
+                let prop = parse_typed_prop(&definition.attr_type, default);
+                updates
+                    .entry(first)
+                    .or_default()
+                    .push((definition.title.clone(), prop));
+            }
+        }
+        Ok((updates, node_type))
+    }
+}
+
+/// Builds the imported graph from the events of a GEXF document
+#[derive(Default)]
+struct GexfReader {
+    imported: ImportedGraph,
+    has_graph: bool,
+    /// The class of the enclosing `<attributes>` element
+    class: Option<String>,
+    /// The class and id of the enclosing `<attribute>` element
+    attribute: Option<(String, String)>,
+    in_default: bool,
+    definitions: HashMap<String, HashMap<String, AttributeDefinition>>,
+    /// The enclosing `<node>` and `<edge>` elements
+    elements: Vec<(String, PendingElement)>,
+}
+
+impl GexfReader {
+    fn start(&mut self, start: &BytesStart) -> Result<(), GraphFormatError> {
+        let name = local_name(start);
+        let attributes = read_attributes(start)?;
+        match name.as_str() {
+            "graph" => self.has_graph = true,
+            "attributes" => {
+                self.class = Some(attr(&attributes, "class").unwrap_or("node").to_owned())
+            }
+            "attribute" => {
+                if let Some(class) = &self.class {
+                    let id = required_attr(&attributes, &name, "id")?.to_owned();
+                    let definition = AttributeDefinition {
+                        title: attr(&attributes, "title").unwrap_or(&id).to_owned(),
+                        attr_type: attr(&attributes, "type").unwrap_or("string").to_owned(),
+                        default: None,
+                    };
+                    self.definitions
+                        .entry(class.clone())
+                        .or_default()
+                        .insert(id.clone(), definition);
+                    self.attribute = Some((class.clone(), id));
+                }
+            }
+            "default" => self.in_default = self.attribute.is_some(),
+            "node" | "edge" => self.elements.push((
+                name,
+                PendingElement {
+                    attributes,
+                    ..Default::default()
+                },
+            )),
+            "spells" => {
+                if let Some((_, element)) = self.elements.last_mut() {
+                    element.spells.get_or_insert_with(Vec::new);
+                }
+            }
+            "spell" => {
+                if let Some((_, element)) = self.elements.last_mut() {
+                    let start = parse_opt_time(&attributes, "start", "startopen")?;
+                    let end = parse_opt_time(&attributes, "end", "endopen")?;
+                    element
+                        .spells
+                        .get_or_insert_with(Vec::new)
+                        .push((start, end));
+                }
+            }
+            "attvalue" => {
+                if let Some((_, element)) = self.elements.last_mut() {
+                    let id = attr(&attributes, "for")
+                        .or_else(|| attr(&attributes, "id"))
+                        .ok_or_else(|| {
+                            GraphFormatError::InvalidDocument(
+                                "<attvalue> is missing the 'for' attribute".into(),
+                            )
+                        })?;
+                    let value = required_attr(&attributes, &name, "value")?;
+                    let start = parse_opt_time(&attributes, "start", "startopen")?;
+                    element
+                        .values
+                        .push((id.to_owned(), value.to_owned(), start));
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn end(&mut self, name: &[u8]) -> Result<(), GraphFormatError> {
+        match name {
+            b"attributes" => self.class = None,
+            b"attribute" => self.attribute = None,
+            b"default" => self.in_default = false,
+            b"node" | b"edge" => {
+                if let Some((name, element)) = self.elements.pop() {
+                    match name.as_str() {
+                        "node" => self.finish_node(element)?,
+                        _ => self.finish_edge(element)?,
+                    }
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    fn text(&mut self, text: &str) {
+        if !self.in_default {
+            return;
+        }
+        if let Some((class, id)) = &self.attribute {
+            if let Some(definition) = self
+                .definitions
+                .get_mut(class)
+                .and_then(|definitions| definitions.get_mut(id))
+            {
+                definition
+                    .default
+                    .get_or_insert_with(String::new)
+                    .push_str(text);
+            }
+        }
+    }
+
+    fn finish_node(&mut self, node: PendingElement) -> Result<(), GraphFormatError> {
+        let id = required_attr(&node.attributes, "node", "id")?.to_owned();
+        let (starts, _) = node.spells()?;
+        let (mut updates, node_type) = node.updates(
+            &starts,
+            self.definitions.entry("node".to_owned()).or_default(),
+        )?;
+        if let Some(label) = attr(&node.attributes, LABEL_ATTR).filter(|label| *label != id) {
+            if let Some(first) = updates.values_mut().next() {
+                first.push((LABEL_ATTR.to_owned(), Prop::str(label)));
+            }
+        }
+        self.imported.nodes.push(ImportedNode {
+            id,
+            node_type,
+            updates,
+        });
+        Ok(())
+    }
+
+    fn finish_edge(&mut self, edge: PendingElement) -> Result<(), GraphFormatError> {
+        let (starts, ends) = edge.spells()?;
+        let (mut updates, _) = edge.updates(
+            &starts,
+            self.definitions.entry("edge".to_owned()).or_default(),
+        )?;
+        if let Some(weight) = attr(&edge.attributes, WEIGHT_ATTR) {
+            let has_weight = updates
+                .values()
+                .any(|props| props.iter().any(|(key, _)| key == WEIGHT_ATTR));
+            if let (false, Some(first)) = (has_weight, updates.values_mut().next()) {
+                first.push((WEIGHT_ATTR.to_owned(), parse_typed_prop("double", weight)));
+            }
+        }
+        self.imported.edges.push(ImportedEdge {
+            src: required_attr(&edge.attributes, "edge", "source")?.to_owned(),
+            dst: required_attr(&edge.attributes, "edge", "target")?.to_owned(),
+            layer: attr(&edge.attributes, "kind").map(|kind| kind.to_owned()),
+            updates,
+            ends,
+        });
+        Ok(())
+    }
+}
+
+/// Read a GEXF document into `graph`
+///
+/// The document is read as a stream of events, only the nodes and edges that were read so far
+/// are kept in memory.
+pub fn read_gexf<G: ImportTarget, R: BufRead>(
+    graph: &G,
+    reader: R,
+) -> Result<(), GraphFormatError> {
+    let mut reader = Reader::from_reader(reader);
+    reader.config_mut().trim_text(true);
+    let mut buf = vec![];
+    let mut gexf = GexfReader::default();
+    loop {
+        match reader.read_event_into(&mut buf)? {
+            Event::Start(start) => gexf.start(&start)?,
+            Event::Empty(start) => {
+                gexf.start(&start)?;
+                gexf.end(start.local_name().as_ref())?;
+            }
+            Event::End(end) => gexf.end(end.local_name().as_ref())?,
+            Event::Text(text) => gexf.text(&text.unescape()?),
+            Event::CData(text) => gexf.text(&String::from_utf8_lossy(&text)),
+            Event::Eof => break,
+            _ => {}
+        }
+        buf.clear();
+    }
+    if !gexf.has_graph {
+        return Err(GraphFormatError::InvalidDocument(
+            "missing <graph> element".to_owned(),
+        ));
+    }
+    gexf.imported.load_into(graph)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod gexf_test {
+    use super::*;
+    use crate::{
+        db::graph::{graph::assert_graph_equal, views::deletion_graph::PersistentGraph},
+        prelude::NO_PROPS,
+    };
+
+    #[test]
+    fn test_gexf_full_history_round_trip() {
+        let g = Graph::new();
+        g.add_node(1, "a", [("score", 1i64)], Some("person"))
+            .unwrap();
+        g.add_node(4, "a", [("score", 2i64)], None).unwrap();
+        g.add_edge(2, "a", "b", [("weight", 0.5)], Some("follows"))
+            .unwrap();
+        g.add_edge(3, "a", "b", [("weight", 1.5)], Some("follows"))
+            .unwrap();
+
+        let mut buffer = vec![];
+        write_gexf(&g, &mut buffer, HistoryMode::Full).unwrap();
+        let loaded = Graph::new();
+        read_gexf(&loaded, buffer.as_slice()).unwrap();
+
+        assert_graph_equal(&g, &loaded);
+        let a = loaded.node("a").unwrap();
+        assert_eq!(a.node_type().as_deref(), Some("person"));
+        assert_eq!(
+            a.properties()
+                .temporal()
+                .get("score")
+                .unwrap()
+                .iter()
+                .collect::<Vec<_>>(),
+            vec![(1, Prop::I64(1)), (4, Prop::I64(2))]
+        );
+        let edge = loaded.edge("a", "b").unwrap();
+        assert_eq!(edge.layer_names().collect::<Vec<_>>(), vec!["follows"]);
+        assert_eq!(
+            edge.properties()
+                .temporal()
+                .get("weight")
+                .unwrap()
+                .iter()
+                .collect::<Vec<_>>(),
+            vec![(2, Prop::F64(0.5)), (3, Prop::F64(1.5))]
+        );
+    }
+
+    #[test]
+    fn test_gexf_spells_of_persistent_graph() {
+        let g = PersistentGraph::new();
+        g.add_edge(1, 1, 2, NO_PROPS, None).unwrap();
+        g.delete_edge(3, 1, 2, None).unwrap();
+        g.add_edge(5, 1, 2, NO_PROPS, None).unwrap();
+
+        let mut buffer = vec![];
+        write_gexf(&g, &mut buffer, HistoryMode::Full).unwrap();
+        let document = String::from_utf8(buffer.clone()).unwrap();
+        assert!(document.contains(r#"<spell start="1" end="3"/>"#));
+        assert!(document.contains(r#"<spell start="5"/>"#));
+
+        let loaded = PersistentGraph::new();
+        read_gexf(&loaded, buffer.as_slice()).unwrap();
+        let edge = loaded.edge(1, 2).unwrap();
+        assert_eq!(edge.history(), vec![1, 5]);
+        assert_eq!(edge.deletions(), vec![3]);
+    }
+
+    #[test]
+    fn test_read_gephi_gexf() {
+        let document = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <gexf xmlns="http://www.gexf.net/1.2draft" version="1.2">
+              <graph mode="static" defaultedgetype="directed">
+                <attributes class="node">
+                  <attribute id="0" title="url" type="string"/>
+                  <attribute id="1" title="indegree" type="float">
+                    <default>0.0</default>
+                  </attribute>
+                </attributes>
+                <nodes>
+                  <node id="0" label="Gephi">
+                    <attvalues><attvalue for="0" value="https://gephi.org"/></attvalues>
+                  </node>
+                  <node id="1" label="1"/>
+                </nodes>
+                <edges>
+                  <edge id="0" source="0" target="1" weight="2.5"/>
+                </edges>
+              </graph>
+            </gexf>"#;
+        let g = Graph::new();
+        read_gexf(&g, document.as_bytes()).unwrap();
+
+        let node = g.node(0).unwrap();
+        assert_eq!(node.properties().get("label"), Some(Prop::str("Gephi")));
+        assert_eq!(
+            node.properties().get("url"),
+            Some(Prop::str("https://gephi.org"))
+        );
+        assert_eq!(node.properties().get("indegree"), Some(Prop::F32(0.0)));
+        assert_eq!(g.node(1).unwrap().properties().get("label"), None);
+        assert_eq!(
+            g.edge(0, 1).unwrap().properties().get("weight"),
+            Some(Prop::F64(2.5))
+        );
+    }
+
+    #[test]
+    fn test_node_type_property_does_not_clash_with_node_type() {
+        let g = Graph::new();
+        g.add_node(1, "a", [("node_type", "property")], Some("person"))
+            .unwrap();
+        g.add_node(2, "b", [("node_type", "other")], None).unwrap();
+
+        let mut buffer = vec![];
+        write_gexf(&g, &mut buffer, HistoryMode::Latest).unwrap();
+        let loaded = Graph::new();
+        read_gexf(&loaded, buffer.as_slice()).unwrap();
+
+        let a = loaded.node("a").unwrap();
+        assert_eq!(a.node_type().as_deref(), Some("person"));
+        assert_eq!(a.properties().get("node_type"), Some(Prop::str("property")));
+        let b = loaded.node("b").unwrap();
+        assert_eq!(b.node_type(), None);
+        assert_eq!(b.properties().get("node_type"), Some(Prop::str("other")));
+    }
+
+    #[test]
+    fn test_read_gexf_requires_graph() {
+        let document = r#"<gexf xmlns="http://www.gexf.net/1.2draft" version="1.2"/>"#;
+        assert!(matches!(
+            read_gexf(&Graph::new(), document.as_bytes()),
+            Err(GraphFormatError::InvalidDocument(_))
+        ));
+    }
+}
@@ -0,0 +1,370 @@
+//! GraphML reader and writer.
+//!
+//! Properties are written as `<data>` elements with one `<key>` per property name and element
+//! kind. GraphML has no notion of time, with [HistoryMode::Lifespan] and [HistoryMode::Full] the
+//! lifespan of nodes and edges is written as the `start` and `end` attributes. The reader uses
+//! `start` as the time of an element and, for persistent graphs, `end` as the deletion time of
+//! an edge.
+
+use super::{
+    parse_time, parse_typed_prop, prop_to_text,
+    xml::{XmlElement, XmlWriter},
+    ExportedElement, ExportedGraph, GraphFormatError, HistoryMode, ImportTarget, ImportedEdge,
+    ImportedGraph, ImportedNode, Updates, DEFAULT_TIME, LAYER_ATTR, NODE_TYPE_ATTR,
+};
+use crate::prelude::*;
+use std::{
+    collections::HashMap,
+    io::{BufRead, Write},
+};
+
+const GRAPHML_NS: &str = "http://graphml.graphdrawing.org/xmlns";
+const START_ATTR: &str = "start";
+const END_ATTR: &str = "end";
+
+fn graphml_type(prop: &Prop) -> &'static str {
+    match prop {
+        Prop::Bool(_) => "boolean",
+        Prop::U8(_) | Prop::U16(_) | Prop::I32(_) => "int",
+        Prop::U32(_) | Prop::U64(_) | Prop::I64(_) => "long",
+        Prop::F32(_) => "float",
+        Prop::F64(_) => "double",
+        _ => "string",
+    }
+}
+
+/// The `<key>` definitions of a document, in order of first use
+#[derive(Default)]
+struct Keys {
+    ids: HashMap<(&'static str, String), String>,
+    definitions: Vec<(String, &'static str, String, &'static str)>,
+}
+
+impl Keys {
+    fn id(&mut self, domain: &'static str, name: &str, attr_type: &'static str) -> String {
+        let definitions = &mut self.definitions;
+        self.ids
+            .entry((domain, name.to_owned()))
+            .or_insert_with(|| {
+                let id = format!("{}{}", &domain[..1], definitions.len());
+                definitions.push((id.clone(), domain, name.to_owned(), attr_type));
+                id
+            })
+            .clone()
+    }
+
+    /// The `<data>` elements of a node or edge as (key id, text) pairs
+    fn data(
+        &mut self,
+        domain: &'static str,
+        element: &ExportedElement,
+        lifespan: Option<(Option<i64>, Option<i64>)>,
+    ) -> Vec<(String, String)> {
+        let mut data: Vec<_> = element
+            .props
+            .iter()
+            .map(|(name, prop)| {
+                (
+                    self.id(domain, name, graphml_type(prop)),
+                    prop_to_text(prop),
+                )
+            })
+            .collect();
+        if let Some((start, end)) = lifespan {
+            for (name, t) in [(START_ATTR, start), (END_ATTR, end)] {
+                if let Some(t) = t {
+                    data.push((self.id(domain, name, "long"), t.to_string()));
+                }
+            }
+        }
+        data
+    }
+}
+
+/// Write `graph` as a GraphML document
+pub fn write_graphml<'graph, G: GraphViewOps<'graph>, W: Write>(
+    graph: &G,
+    writer: W,
+    mode: HistoryMode,
+) -> Result<(), GraphFormatError> {
+    let exported = ExportedGraph::new(graph, mode);
+    let with_lifespan = mode != HistoryMode::Latest;
+    let mut keys = Keys::default();
+
+    let graph_data: Vec<_> = exported
+        .props
+        .iter()
+        .map(|(name, prop)| {
+            (
+                keys.id("graph", name, graphml_type(prop)),
+                prop_to_text(prop),
+            )
+        })
+        .collect();
+    let nodes: Vec<_> = exported
+        .nodes
+        .iter()
+        .map(|node| {
+            let lifespan = with_lifespan.then(|| node.element.lifespan(exported.persistent));
+            let mut data = keys.data("node", &node.element, lifespan);
+            if let Some(node_type) = &node.node_type {
+                data.push((
+                    keys.id("node", NODE_TYPE_ATTR, "string"),
+                    node_type.to_string(),
+                ));
+            }
+            (node.id.to_string(), data)
+        })
+        .collect();
+    let edges: Vec<_> = exported
+        .edges
+        .iter()
+        .map(|edge| {
+            let lifespan = with_lifespan.then(|| edge.element.lifespan(exported.persistent));
+            let mut data = keys.data("edge", &edge.element, lifespan);
+            data.push((
+                keys.id("edge", LAYER_ATTR, "string"),
+                edge.layer.to_string(),
+            ));
+            (edge.src.to_string(), edge.dst.to_string(), data)
+        })
+        .collect();
+
+    let mut writer = XmlWriter::new(writer)?;
+    writer.start("graphml", &[("xmlns", GRAPHML_NS)])?;
+    for (id, domain, name, attr_type) in &keys.definitions {
+        writer.empty(
+            "key",
+            &[
+                ("id", id.as_str()),
+                ("for", *domain),
+                ("attr.name", name.as_str()),
+                ("attr.type", *attr_type),
+            ],
+        )?;
+    }
+    writer.start("graph", &[("id", "G"), ("edgedefault", "directed")])?;
+    write_data(&mut writer, &graph_data)?;
+    for (id, data) in &nodes {
+        writer.start("node", &[("id", id.as_str())])?;
+        write_data(&mut writer, data)?;
+        writer.end("node")?;
+    }
+    for (index, (src, dst, data)) in edges.iter().enumerate() {
+        let id = format!("e{index}");
+        writer.start(
+            "edge",
+            &[("id", id.as_str()), ("source", src), ("target", dst)],
+        )?;
+        write_data(&mut writer, data)?;
+        writer.end("edge")?;
+    }
+    writer.end("graph")?;
+    writer.end("graphml")?;
+    writer.finish()
+}
+
+fn write_data<W: Write>(
+    writer: &mut XmlWriter<W>,
+    data: &[(String, String)],
+) -> Result<(), GraphFormatError> {
+    for (key, text) in data {
+        writer.text_element("data", &[("key", key.as_str())], text)?;
+    }
+    Ok(())
+}
+
+/// A `<key>` definition read from a document
+struct KeyDefinition {
+    /// Kind of element the key applies to (`node`, `edge`, `graph` or `all`)
+    domain: String,
+    name: String,
+    attr_type: String,
+    default: Option<String>,
+}
+
+/// The values of the `<data>` children of `element`, with the defaults of the keys of `domain`
+fn read_data(
+    element: &XmlElement,
+    keys: &HashMap<String, KeyDefinition>,
+    domain: &str,
+) -> Vec<(String, Prop)> {
+    let mut values: HashMap<&str, &str> = element
+        .children("data")
+        .filter_map(|data| Some((data.attr("key")?, data.text.as_str())))
+        .collect();
+    for (id, key) in keys {
+        if let (true, Some(default)) = (key.domain == domain || key.domain == "all", &key.default) {
+            values.entry(id.as_str()).or_insert(default.as_str());
+        }
+    }
+    values
+        .into_iter()
+        .filter_map(|(id, text)| {
+            let key = keys.get(id)?;
+            Some((key.name.clone(), parse_typed_prop(&key.attr_type, text)))
+        })
+        .collect()
+}
+
+/// Remove the value of the attribute `name` from `data` and return it as text
+fn take_attr(data: &mut Vec<(String, Prop)>, name: &str) -> Option<String> {
+    let index = data.iter().position(|(key, _)| key == name)?;
+    Some(prop_to_text(&data.remove(index).1))
+}
+
+/// Read a GraphML document into `graph`
+pub fn read_graphml<G: ImportTarget, R: BufRead>(
+    graph: &G,
+    reader: R,
+) -> Result<(), GraphFormatError> {
+    let root = XmlElement::parse(reader)?;
+    let mut keys = HashMap::new();
+    for key in root.children("key") {
+        let id = key.required_attr("id")?;
+        keys.insert(
+            id.to_owned(),
+            KeyDefinition {
+                domain: key.attr("for").unwrap_or("all").to_owned(),
+                name: key.attr("attr.name").unwrap_or(id).to_owned(),
+                attr_type: key.attr("attr.type").unwrap_or("string").to_owned(),
+                default: key.child("default").map(|default| default.text.clone()),
+            },
+        );
+    }
+    let graph_element = root
+        .child("graph")
+        .ok_or_else(|| GraphFormatError::InvalidDocument("missing <graph> element".to_owned()))?;
+
+    let mut imported = ImportedGraph {
+        props: read_data(graph_element, &keys, "graph"),
+        ..Default::default()
+    };
+    for node in graph_element.children("node") {
+        let mut data = read_data(node, &keys, "node");
+        let node_type = take_attr(&mut data, NODE_TYPE_ATTR);
+        let t = take_attr(&mut data, START_ATTR)
+            .map(|t| parse_time(&t))
+            .transpose()?
+            .unwrap_or(DEFAULT_TIME);
+        take_attr(&mut data, END_ATTR);
+        imported.nodes.push(ImportedNode {
+            id: node.required_attr("id")?.to_owned(),
+            node_type,
+            updates: Updates::from([(t, data)]),
+        });
+    }
+    for edge in graph_element.children("edge") {
+        let mut data = read_data(edge, &keys, "edge");
+        let layer = take_attr(&mut data, LAYER_ATTR);
+        let t = take_attr(&mut data, START_ATTR)
+            .map(|t| parse_time(&t))
+            .transpose()?
+            .unwrap_or(DEFAULT_TIME);
+        let ends = take_attr(&mut data, END_ATTR)
+            .map(|t| parse_time(&t))
+            .transpose()?;
+        imported.edges.push(ImportedEdge {
+            src: edge.required_attr("source")?.to_owned(),
+            dst: edge.required_attr("target")?.to_owned(),
+            layer,
+            updates: Updates::from([(t, data)]),
+            ends: ends.into_iter().collect(),
+        });
+    }
+    imported.load_into(graph)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod graphml_test {
+    use super::*;
+    use crate::{
+        db::graph::{graph::assert_graph_equal, views::deletion_graph::PersistentGraph},
+        prelude::NO_PROPS,
+    };
+
+    #[test]
+    fn test_graphml_round_trip() {
+        let g = Graph::new();
+        g.add_node(1, "a", [("score", 0.5)], Some("person"))
+            .unwrap();
+        g.add_edge(2, "a", "b", [("weight", 3i64)], Some("follows"))
+            .unwrap();
+        g.add_edge(3, "b", "c", [("label", "x <&> y")], None)
+            .unwrap();
+        g.add_constant_properties([("name", "test")]).unwrap();
+
+        let mut buffer = vec![];
+        write_graphml(&g, &mut buffer, HistoryMode::Lifespan).unwrap();
+        let loaded = Graph::new();
+        read_graphml(&loaded, buffer.as_slice()).unwrap();
+
+        assert_graph_equal(&g, &loaded);
+        let a = loaded.node("a").unwrap();
+        assert_eq!(a.node_type().as_deref(), Some("person"));
+        assert_eq!(a.properties().get("score"), Some(Prop::F64(0.5)));
+        assert_eq!(
+            loaded
+                .layers("follows")
+                .unwrap()
+                .edge("a", "b")
+                .unwrap()
+                .properties()
+                .get("weight"),
+            Some(Prop::I64(3))
+        );
+        assert_eq!(
+            loaded.edge("b", "c").unwrap().properties().get("label"),
+            Some(Prop::str("x <&> y"))
+        );
+        assert_eq!(loaded.properties().get("name"), Some(Prop::str("test")));
+    }
+
+    #[test]
+    fn test_graphml_edge_lifespans() {
+        let g = PersistentGraph::new();
+        g.add_edge(1, 1, 2, NO_PROPS, None).unwrap();
+        g.delete_edge(5, 1, 2, None).unwrap();
+
+        let mut buffer = vec![];
+        write_graphml(&g, &mut buffer, HistoryMode::Lifespan).unwrap();
+        let loaded = PersistentGraph::new();
+        read_graphml(&loaded, buffer.as_slice()).unwrap();
+
+        let edge = loaded.edge(1, 2).unwrap();
+        assert_eq!(edge.history(), vec![1]);
+        assert_eq!(edge.deletions(), vec![5]);
+    }
+
+    #[test]
+    fn test_read_graphml_with_defaults() {
+        let document = r#"<?xml version="1.0" encoding="UTF-8"?>
+            <graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+              <key id="d0" for="node" attr.name="color" attr.type="string">
+                <default>yellow</default>
+              </key>
+              <key id="d1" for="edge" attr.name="weight" attr.type="double"/>
+              <graph id="G" edgedefault="undirected">
+                <node id="n0"><data key="d0">green</data></node>
+                <node id="n1"/>
+                <edge source="n0" target="n1"><data key="d1">1.5</data></edge>
+              </graph>
+            </graphml>"#;
+        let g = Graph::new();
+        read_graphml(&g, document.as_bytes()).unwrap();
+
+        assert_eq!(
+            g.node("n0").unwrap().properties().get("color"),
+            Some(Prop::str("green"))
+        );
+        assert_eq!(
+            g.node("n1").unwrap().properties().get("color"),
+            Some(Prop::str("yellow"))
+        );
+        assert_eq!(
+            g.edge("n0", "n1").unwrap().properties().get("weight"),
+            Some(Prop::F64(1.5))
+        );
+    }
+}
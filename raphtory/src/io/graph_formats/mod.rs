@@ -0,0 +1,397 @@
+//! Readers and writers for graph exchange formats.
+//!
+//! - [graphml]: GraphML, read and written by most graph tools (Cytoscape, yEd, NetworkX, ...)
+//! - [gexf]: GEXF, the native format of Gephi, including its dynamic graphs
+//! - [node_link]: the node-link JSON format of NetworkX and D3
+//!
+//! The writers work on any graph view, [HistoryMode] selects how the temporal history of the view
+//! is flattened. The readers load into a [Graph] or a [PersistentGraph] (see [ImportTarget]).
+//! Elements without timestamps are added at time 0. Edge layers are written as the `layer`
+//! attribute of edges (`kind` in GEXF) and node types as the `node_type` attribute of nodes (a
+//! reserved attribute in GEXF).
+
+pub mod gexf;
+pub mod graphml;
+pub mod node_link;
+mod xml;
+
+use crate::{
+    core::{
+        entities::LayerIds,
+        utils::{errors::GraphError, time::TryIntoTime},
+    },
+    db::{
+        api::view::internal::{CoreGraphOps, GraphType, InternalMaterialize},
+        graph::views::deletion_graph::PersistentGraph,
+    },
+    prelude::*,
+};
+use raphtory_api::core::storage::arc_str::ArcStr;
+use std::{collections::BTreeMap, io};
+
+const NODE_TYPE_ATTR: &str = "node_type";
+const LAYER_ATTR: &str = "layer";
+const DEFAULT_TIME: i64 = 0;
+
+#[derive(thiserror::Error, Debug)]
+pub enum GraphFormatError {
+    #[error("IO operation failed: {0}")]
+    Io(#[from] io::Error),
+    #[error("Invalid XML: {0}")]
+    Xml(#[from] quick_xml::Error),
+    #[error("Invalid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Invalid document: {0}")]
+    InvalidDocument(String),
+    #[error(transparent)]
+    GraphError(#[from] GraphError),
+}
+
+/// How the writers flatten the temporal history of a graph
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum HistoryMode {
+    /// Only the latest value of every property, without any timestamps
+    #[default]
+    Latest,
+    /// The latest value of every property and the lifespan (`start` and `end`) of every node
+    /// and edge
+    Lifespan,
+    /// All updates of nodes, edges and temporal properties (GEXF spells and dynamic attributes,
+    /// node-link histories). GraphML cannot represent them and falls back to `Lifespan`.
+    Full,
+}
+
+/// Graphs the readers can load into
+pub trait ImportTarget: AdditionOps + PropertyAdditionOps {
+    /// Record the end of the lifespan of an edge, only persistent graphs keep track of it
+    fn end_edge(&self, t: i64, src: &GID, dst: &GID, layer: Option<&str>)
+        -> Result<(), GraphError>;
+}
+
+impl ImportTarget for Graph {
+    fn end_edge(
+        &self,
+        _t: i64,
+        _src: &GID,
+        _dst: &GID,
+        _layer: Option<&str>,
+    ) -> Result<(), GraphError> {
+        Ok(())
+    }
+}
+
+impl ImportTarget for PersistentGraph {
+    fn end_edge(
+        &self,
+        t: i64,
+        src: &GID,
+        dst: &GID,
+        layer: Option<&str>,
+    ) -> Result<(), GraphError> {
+        self.delete_edge(t, src, dst, layer)?;
+        Ok(())
+    }
+}
+
+/// The history and properties of a node or edge as seen by the writers
+pub(crate) struct ExportedElement {
+    /// Constant properties and, unless all updates are exported, the latest value of temporal
+    /// properties
+    pub(crate) props: Vec<(ArcStr, Prop)>,
+    /// All updates of temporal properties (only for [HistoryMode::Full])
+    pub(crate) temporal: Vec<(ArcStr, Vec<(i64, Prop)>)>,
+    pub(crate) additions: Vec<i64>,
+    pub(crate) deletions: Vec<i64>,
+}
+
+impl ExportedElement {
+    fn new(
+        constant: impl Iterator<Item = (ArcStr, Prop)>,
+        temporal: Vec<(ArcStr, Vec<(i64, Prop)>)>,
+        additions: Vec<i64>,
+        deletions: Vec<i64>,
+        mode: HistoryMode,
+    ) -> Self {
+        let mut props: Vec<_> = constant.collect();
+        if mode == HistoryMode::Full {
+            return Self {
+                props,
+                temporal,
+                additions,
+                deletions,
+            };
+        }
+        for (key, updates) in temporal {
+            if let Some((_, latest)) = updates.into_iter().last() {
+                props.retain(|(k, _)| k != &key);
+                props.push((key, latest));
+            }
+        }
+        Self {
+            props,
+            temporal: vec![],
+            additions,
+            deletions,
+        }
+    }
+
+    /// The first and last time the element is alive, `None` if it is unbounded
+    pub(crate) fn lifespan(&self, persistent: bool) -> (Option<i64>, Option<i64>) {
+        let start = self.additions.first().copied();
+        let end = if persistent {
+            // an edge that was not deleted after its last addition is still alive
+            match (self.additions.last(), self.deletions.last()) {
+                (Some(added), Some(deleted)) if deleted >= added => Some(*deleted),
+                (None, deleted) => deleted.copied(),
+                _ => None,
+            }
+        } else {
+            self.additions.last().copied()
+        };
+        (start, end)
+    }
+
+    /// The intervals the element is alive. Updates of event graphs are instantaneous, persistent
+    /// graphs keep an edge alive from an addition until the next deletion.
+    pub(crate) fn spells(&self, persistent: bool) -> Vec<(Option<i64>, Option<i64>)> {
+        if !persistent {
+            return self
+                .additions
+                .iter()
+                .map(|t| (Some(*t), Some(*t)))
+                .collect();
+        }
+        let mut events: Vec<(i64, bool)> = self
+            .additions
+            .iter()
+            .map(|t| (*t, true))
+            .chain(self.deletions.iter().map(|t| (*t, false)))
+            .collect();
+        // deletions sort before additions at the same time
+        events.sort();
+        let mut spells = vec![];
+        let mut open: Option<Option<i64>> = None;
+        for (t, is_addition) in events {
+            match (is_addition, open) {
+                (true, None) => open = Some(Some(t)),
+                (false, Some(start)) => {
+                    spells.push((start, Some(t)));
+                    open = None;
+                }
+                // a deletion without a previous addition ends an edge that was always alive
+                (false, None) if spells.is_empty() => spells.push((None, Some(t))),
+                _ => {}
+            }
+        }
+        if let Some(start) = open {
+            spells.push((start, None));
+        }
+        spells
+    }
+}
+
+pub(crate) struct ExportedNode {
+    pub(crate) id: GID,
+    pub(crate) node_type: Option<ArcStr>,
+    pub(crate) element: ExportedElement,
+}
+
+pub(crate) struct ExportedEdge {
+    pub(crate) src: GID,
+    pub(crate) dst: GID,
+    pub(crate) layer: ArcStr,
+    pub(crate) element: ExportedElement,
+}
+
+/// A graph view flattened for the writers, with one edge per layer
+pub(crate) struct ExportedGraph {
+    pub(crate) persistent: bool,
+    pub(crate) props: Vec<(ArcStr, Prop)>,
+    pub(crate) nodes: Vec<ExportedNode>,
+    pub(crate) edges: Vec<ExportedEdge>,
+}
+
+impl ExportedGraph {
+    pub(crate) fn new<'graph, G: GraphViewOps<'graph>>(graph: &G, mode: HistoryMode) -> Self {
+        let mut props: Vec<_> = graph.properties().constant().iter().collect();
+        for (key, value) in graph.properties().temporal().iter_latest() {
+            props.retain(|(k, _)| k != &key);
+            props.push((key, value));
+        }
+
+        let nodes = graph
+            .nodes()
+            .iter()
+            .map(|node| {
+                let properties = node.properties();
+                let temporal = properties
+                    .temporal()
+                    .iter()
+                    .map(|(key, prop)| (key, prop.iter().collect()))
+                    .collect();
+                ExportedNode {
+                    id: node.id(),
+                    node_type: node.node_type(),
+                    element: ExportedElement::new(
+                        properties.constant().iter(),
+                        temporal,
+                        node.history(),
+                        vec![],
+                        mode,
+                    ),
+                }
+            })
+            .collect();
+
+        let const_prop_meta = graph.edge_meta().const_prop_meta();
+        let edges = graph
+            .edges()
+            .explode_layers()
+            .iter()
+            .filter_map(|edge| {
+                // the constant properties of an edge view are merged over the layers of the
+                // graph, so they are looked up for the layer of the edge directly
+                let layer_ids = LayerIds::One(*edge.edge.layer()?);
+                let constant = graph
+                    .const_edge_prop_ids(edge.edge, layer_ids.clone())
+                    .filter_map(|id| {
+                        graph
+                            .get_const_edge_prop(edge.edge, id, layer_ids.clone())
+                            .map(|value| (const_prop_meta.get_name(id).clone(), value))
+                    });
+                let temporal = edge
+                    .properties()
+                    .temporal()
+                    .iter()
+                    .map(|(key, prop)| (key, prop.iter().collect()))
+                    .collect();
+                Some(ExportedEdge {
+                    src: edge.src().id(),
+                    dst: edge.dst().id(),
+                    layer: edge.layer_name().ok()?,
+                    element: ExportedElement::new(
+                        constant,
+                        temporal,
+                        edge.history(),
+                        edge.deletions(),
+                        mode,
+                    ),
+                })
+            })
+            .collect();
+
+        Self {
+            persistent: matches!(graph.graph_type(), GraphType::PersistentGraph),
+            props,
+            nodes,
+            edges,
+        }
+    }
+}
+
+/// Text representation of a property value in the XML formats
+pub(crate) fn prop_to_text(prop: &Prop) -> String {
+    match prop {
+        Prop::List(_) | Prop::Map(_) => prop.to_json().to_string(),
+        Prop::DTime(value) => value.to_rfc3339(),
+        _ => prop.to_string(),
+    }
+}
+
+/// Parse a property value written with the XML type `attr_type` (GraphML and GEXF share the
+/// names of the basic types). Values that do not match their type are kept as strings.
+pub(crate) fn parse_typed_prop(attr_type: &str, text: &str) -> Prop {
+    let parsed = match attr_type {
+        "boolean" => text.parse().ok().map(Prop::Bool),
+        "int" | "integer" => text.parse().ok().map(Prop::I32),
+        "long" => text.parse().ok().map(Prop::I64),
+        "float" => text.parse().ok().map(Prop::F32),
+        "double" => text.parse().ok().map(Prop::F64),
+        _ => None,
+    };
+    parsed.unwrap_or_else(|| Prop::str(text))
+}
+
+/// Parse a timestamp, either as milliseconds since the epoch or as a date
+pub(crate) fn parse_time(text: &str) -> Result<i64, GraphFormatError> {
+    if let Ok(t) = text.parse::<i64>() {
+        return Ok(t);
+    }
+    if let Ok(t) = text.parse::<f64>() {
+        return Ok(t.floor() as i64);
+    }
+    Ok(text.try_into_time().map_err(GraphError::from)?)
+}
+
+/// Updates of a node or edge read from a file, keyed by time
+pub(crate) type Updates = BTreeMap<i64, Vec<(String, Prop)>>;
+
+pub(crate) struct ImportedNode {
+    pub(crate) id: String,
+    pub(crate) node_type: Option<String>,
+    pub(crate) updates: Updates,
+}
+
+pub(crate) struct ImportedEdge {
+    pub(crate) src: String,
+    pub(crate) dst: String,
+    pub(crate) layer: Option<String>,
+    pub(crate) updates: Updates,
+    /// Ends of the lifespan of the edge (deletions in a persistent graph)
+    pub(crate) ends: Vec<i64>,
+}
+
+/// A graph read from a file, before it is added to the target graph
+#[derive(Default)]
+pub(crate) struct ImportedGraph {
+    pub(crate) props: Vec<(String, Prop)>,
+    pub(crate) nodes: Vec<ImportedNode>,
+    pub(crate) edges: Vec<ImportedEdge>,
+}
+
+impl ImportedGraph {
+    pub(crate) fn load_into<G: ImportTarget>(self, graph: &G) -> Result<(), GraphError> {
+        // the formats only have string ids, numeric ids are restored if all ids are numbers
+        let numeric_ids = self
+            .nodes
+            .iter()
+            .map(|node| &node.id)
+            .chain(self.edges.iter().flat_map(|edge| [&edge.src, &edge.dst]))
+            .all(|id| id.parse::<u64>().is_ok());
+        let gid = |id: &str| match id.parse::<u64>() {
+            Ok(id) if numeric_ids => GID::U64(id),
+            _ => GID::Str(id.to_owned()),
+        };
+
+        if !self.props.is_empty() {
+            graph.add_constant_properties(self.props)?;
+        }
+        for node in self.nodes {
+            let id = gid(&node.id);
+            let mut node_type = node.node_type.as_deref();
+            let mut updates = node.updates;
+            if updates.is_empty() {
+                updates.insert(DEFAULT_TIME, vec![]);
+            }
+            for (t, props) in updates {
+                graph.add_node(t, &id, props, node_type.take())?;
+            }
+        }
+        for edge in self.edges {
+            let src = gid(&edge.src);
+            let dst = gid(&edge.dst);
+            let layer = edge.layer.as_deref();
+            let mut updates = edge.updates;
+            if updates.is_empty() {
+                updates.insert(DEFAULT_TIME, vec![]);
+            }
+            for (t, props) in updates {
+                graph.add_edge(t, &src, &dst, props, layer)?;
+            }
+            for t in edge.ends {
+                graph.end_edge(t, &src, &dst, layer)?;
+            }
+        }
+        Ok(())
+    }
+}
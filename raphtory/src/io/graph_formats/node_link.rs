@@ -0,0 +1,347 @@
+//! Node-link JSON reader and writer (the format of `networkx.node_link_data` and D3).
+//!
+//! The document has the `nodes` and `links` arrays and the graph properties in `graph`. Node ids
+//! are written as numbers or strings. Properties are written as fields of the nodes and links,
+//! the layer of a link as its `key` and the type of a node as its `node_type`. Depending on the
+//! [HistoryMode], nodes and links also get:
+//!
+//! - `start` and `end`: their lifespan ([HistoryMode::Lifespan])
+//! - `history`, `deletions` and `temporal`: the times of their additions and deletions and the
+//!   updates of their temporal properties as `[time, value]` pairs ([HistoryMode::Full])
+
+use super::{
+    parse_time, ExportedElement, ExportedGraph, GraphFormatError, HistoryMode, ImportTarget,
+    ImportedEdge, ImportedGraph, ImportedNode, Updates, DEFAULT_TIME, LAYER_ATTR, NODE_TYPE_ATTR,
+};
+use crate::prelude::*;
+use serde_json::{json, Map, Value};
+use std::io::{Read, Write};
+
+const ID: &str = "id";
+const SOURCE: &str = "source";
+const TARGET: &str = "target";
+const KEY: &str = "key";
+const START: &str = "start";
+const END: &str = "end";
+const HISTORY: &str = "history";
+const DELETIONS: &str = "deletions";
+const TEMPORAL: &str = "temporal";
+const RESERVED: [&str; 11] = [
+    ID,
+    SOURCE,
+    TARGET,
+    KEY,
+    LAYER_ATTR,
+    NODE_TYPE_ATTR,
+    START,
+    END,
+    HISTORY,
+    DELETIONS,
+    TEMPORAL,
+];
+
+fn gid_to_json(id: &GID) -> Value {
+    match id {
+        GID::U64(id) => json!(id),
+        GID::Str(id) => json!(id),
+    }
+}
+
+/// Add the properties and history of `element` to the JSON object of a node or link
+fn write_element(
+    object: &mut Map<String, Value>,
+    element: &ExportedElement,
+    mode: HistoryMode,
+    persistent: bool,
+) {
+    for (name, prop) in &element.props {
+        object.insert(name.to_string(), prop.to_json());
+    }
+    match mode {
+        HistoryMode::Latest => {}
+        HistoryMode::Lifespan => {
+            let (start, end) = element.lifespan(persistent);
+            object.insert(START.to_owned(), json!(start));
+            object.insert(END.to_owned(), json!(end));
+        }
+        HistoryMode::Full => {
+            object.insert(HISTORY.to_owned(), json!(element.additions));
+            if !element.deletions.is_empty() {
+                object.insert(DELETIONS.to_owned(), json!(element.deletions));
+            }
+            let temporal: Map<String, Value> = element
+                .temporal
+                .iter()
+                .map(|(name, updates)| {
+                    let updates = updates
+                        .iter()
+                        .map(|(t, prop)| json!([t, prop.to_json()]))
+                        .collect();
+                    (name.to_string(), Value::Array(updates))
+                })
+                .collect();
+            if !temporal.is_empty() {
+                object.insert(TEMPORAL.to_owned(), Value::Object(temporal));
+            }
+        }
+    }
+}
+
+/// Write `graph` as a node-link JSON document
+pub fn write_node_link<'graph, G: GraphViewOps<'graph>, W: Write>(
+    graph: &G,
+    writer: W,
+    mode: HistoryMode,
+) -> Result<(), GraphFormatError> {
+    let exported = ExportedGraph::new(graph, mode);
+    let nodes: Vec<_> = exported
+        .nodes
+        .iter()
+        .map(|node| {
+            let mut object = Map::new();
+            write_element(&mut object, &node.element, mode, exported.persistent);
+            object.insert(ID.to_owned(), gid_to_json(&node.id));
+            if let Some(node_type) = &node.node_type {
+                object.insert(NODE_TYPE_ATTR.to_owned(), json!(node_type.to_string()));
+            }
+            Value::Object(object)
+        })
+        .collect();
+    let links: Vec<_> = exported
+        .edges
+        .iter()
+        .map(|edge| {
+            let mut object = Map::new();
+            write_element(&mut object, &edge.element, mode, exported.persistent);
+            object.insert(SOURCE.to_owned(), gid_to_json(&edge.src));
+            object.insert(TARGET.to_owned(), gid_to_json(&edge.dst));
+            object.insert(KEY.to_owned(), json!(edge.layer.to_string()));
+            Value::Object(object)
+        })
+        .collect();
+    let graph_props: Map<String, Value> = exported
+        .props
+        .iter()
+        .map(|(name, prop)| (name.to_string(), prop.to_json()))
+        .collect();
+
+    let document = json!({
+        "directed": true,
+        "multigraph": true,
+        "graph": graph_props,
+        "nodes": nodes,
+        "links": links,
+    });
+    serde_json::to_writer(writer, &document)?;
+    Ok(())
+}
+
+fn invalid(reason: impl Into<String>) -> GraphFormatError {
+    GraphFormatError::InvalidDocument(reason.into())
+}
+
+/// Node ids can be numbers or strings
+fn json_to_id(value: Option<&Value>, field: &str) -> Result<String, GraphFormatError> {
+    match value {
+        Some(Value::String(id)) => Ok(id.clone()),
+        Some(Value::Number(id)) => Ok(id.to_string()),
+        _ => Err(invalid(format!("missing or invalid '{field}'"))),
+    }
+}
+
+fn json_to_time(value: &Value) -> Result<i64, GraphFormatError> {
+    match value {
+        Value::Number(t) => t
+            .as_i64()
+            .or_else(|| t.as_f64().map(|t| t.floor() as i64))
+            .ok_or_else(|| invalid(format!("invalid time {t}"))),
+        Value::String(t) => parse_time(t),
+        _ => Err(invalid(format!("invalid time {value}"))),
+    }
+}
+
+fn json_to_times(value: Option<&Value>) -> Result<Vec<i64>, GraphFormatError> {
+    match value {
+        None | Some(Value::Null) => Ok(vec![]),
+        Some(Value::Array(times)) => times.iter().map(json_to_time).collect(),
+        Some(t) => Ok(vec![json_to_time(t)?]),
+    }
+}
+
+fn json_to_props<'a>(fields: impl Iterator<Item = (&'a String, &'a Value)>) -> Vec<(String, Prop)> {
+    fields
+        .filter_map(|(name, value)| Some((name.clone(), Prop::try_from(value.clone()).ok()?)))
+        .collect()
+}
+
+/// The updates of a node or link, together with the times in its `end` or `deletions` field
+fn read_element(object: &Map<String, Value>) -> Result<(Updates, Vec<i64>), GraphFormatError> {
+    let mut additions = json_to_times(object.get(HISTORY))?;
+    if additions.is_empty() {
+        additions = json_to_times(object.get(START))?;
+    }
+    let first = additions.iter().copied().min().unwrap_or(DEFAULT_TIME);
+    let mut updates: Updates = additions.into_iter().map(|t| (t, vec![])).collect();
+    updates.entry(first).or_default().extend(json_to_props(
+        object
+            .iter()
+            .filter(|(name, _)| !RESERVED.contains(&name.as_str())),
+    ));
+    if let Some(temporal) = object
+        .get(TEMPORAL)
+        .and_then(|temporal| temporal.as_object())
+    {
+        for (name, history) in temporal {
+            for update in history.as_array().into_iter().flatten() {
+                let (t, value) = match update.as_array().map(|update| update.as_slice()) {
+                    Some([t, value]) => (json_to_time(t)?, value),
+                    _ => return Err(invalid(format!("invalid update of '{name}': {update}"))),
+                };
+                if let Ok(prop) = Prop::try_from(value.clone()) {
+                    updates.entry(t).or_default().push((name.clone(), prop));
+                }
+            }
+        }
+    }
+    let mut ends = json_to_times(object.get(DELETIONS))?;
+    if ends.is_empty() {
+        ends = json_to_times(object.get(END))?;
+    }
+    Ok((updates, ends))
+}
+
+/// Read a node-link JSON document into `graph`
+pub fn read_node_link<G: ImportTarget, R: Read>(
+    graph: &G,
+    reader: R,
+) -> Result<(), GraphFormatError> {
+    let document: Value = serde_json::from_reader(reader)?;
+    let mut imported = ImportedGraph::default();
+    if let Some(props) = document.get("graph").and_then(|props| props.as_object()) {
+        imported.props = json_to_props(props.iter());
+    }
+    for node in document
+        .get("nodes")
+        .and_then(|nodes| nodes.as_array())
+        .into_iter()
+        .flatten()
+    {
+        let node = node
+            .as_object()
+            .ok_or_else(|| invalid("nodes must be objects"))?;
+        let (updates, _) = read_element(node)?;
+        imported.nodes.push(ImportedNode {
+            id: json_to_id(node.get(ID), ID)?,
+            node_type: node
+                .get(NODE_TYPE_ATTR)
+                .and_then(|node_type| node_type.as_str())
+                .map(|node_type| node_type.to_owned()),
+            updates,
+        });
+    }
+    // networkx writes the links as `edges` in newer versions
+    let links = document.get("links").or_else(|| document.get("edges"));
+    for link in links
+        .and_then(|links| links.as_array())
+        .into_iter()
+        .flatten()
+    {
+        let link = link
+            .as_object()
+            .ok_or_else(|| invalid("links must be objects"))?;
+        let (updates, ends) = read_element(link)?;
+        // integer keys only distinguish parallel edges, they are not layers
+        let layer = link
+            .get(LAYER_ATTR)
+            .or_else(|| link.get(KEY))
+            .and_then(|layer| layer.as_str())
+            .map(|layer| layer.to_owned());
+        imported.edges.push(ImportedEdge {
+            src: json_to_id(link.get(SOURCE), SOURCE)?,
+            dst: json_to_id(link.get(TARGET), TARGET)?,
+            layer,
+            updates,
+            ends,
+        });
+    }
+    imported.load_into(graph)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod node_link_test {
+    use super::*;
+    use crate::db::graph::{graph::assert_graph_equal, views::deletion_graph::PersistentGraph};
+
+    #[test]
+    fn test_node_link_full_history_round_trip() {
+        let g = PersistentGraph::new();
+        g.add_node(0, 1, [("name", "one")], Some("person")).unwrap();
+        g.add_edge(1, 1, 2, [("weight", 1i64)], Some("a")).unwrap();
+        g.add_edge(4, 1, 2, [("weight", 2i64)], Some("a")).unwrap();
+        g.delete_edge(6, 1, 2, Some("a")).unwrap();
+        g.add_edge(2, 2, 3, [("weight", 5i64)], None).unwrap();
+        g.add_constant_properties([("source", "test")]).unwrap();
+
+        let mut buffer = vec![];
+        write_node_link(&g, &mut buffer, HistoryMode::Full).unwrap();
+        let loaded = PersistentGraph::new();
+        read_node_link(&loaded, buffer.as_slice()).unwrap();
+
+        assert_graph_equal(&g, &loaded);
+        assert_eq!(
+            loaded.node(1).unwrap().node_type().as_deref(),
+            Some("person")
+        );
+        let edge = loaded.layers("a").unwrap().edge(1, 2).unwrap();
+        assert_eq!(edge.history(), vec![1, 4]);
+        assert_eq!(edge.deletions(), vec![6]);
+        assert_eq!(
+            edge.properties()
+                .temporal()
+                .get("weight")
+                .unwrap()
+                .iter()
+                .collect::<Vec<_>>(),
+            vec![(1, Prop::I64(1)), (4, Prop::I64(2))]
+        );
+        assert_eq!(loaded.properties().get("source"), Some(Prop::str("test")));
+    }
+
+    #[test]
+    fn test_node_link_latest_values() {
+        let g = Graph::new();
+        g.add_edge(1, "a", "b", [("weight", 1.0)], None).unwrap();
+        g.add_edge(2, "a", "b", [("weight", 2.0)], None).unwrap();
+
+        let mut buffer = vec![];
+        write_node_link(&g, &mut buffer, HistoryMode::Latest).unwrap();
+        let document: Value = serde_json::from_slice(&buffer).unwrap();
+        assert_eq!(
+            document["links"],
+            json!([{"source": "a", "target": "b", "key": "_default", "weight": 2.0}])
+        );
+    }
+
+    #[test]
+    fn test_read_networkx_node_link() {
+        let document = r#"{
+            "directed": false,
+            "multigraph": false,
+            "graph": {},
+            "nodes": [{"id": 0, "club": "Mr. Hi"}, {"id": 1, "club": "Officer"}],
+            "links": [{"source": 0, "target": 1, "weight": 4}]
+        }"#;
+        let g = Graph::new();
+        read_node_link(&g, document.as_bytes()).unwrap();
+
+        assert_eq!(
+            g.node(0).unwrap().properties().get("club"),
+            Some(Prop::str("Mr. Hi"))
+        );
+        assert_eq!(
+            g.edge(0, 1).unwrap().properties().get("weight"),
+            Some(Prop::I64(4))
+        );
+    }
+}
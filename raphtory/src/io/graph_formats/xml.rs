@@ -0,0 +1,172 @@
+//! XML helpers: a minimal document model for the GraphML reader, element helpers for the
+//! streaming GEXF reader and the writer shared by both formats
+
+use super::GraphFormatError;
+use quick_xml::{
+    events::{BytesDecl, BytesEnd, BytesStart, BytesText, Event},
+    Reader, Writer,
+};
+use std::io::{BufRead, Write};
+
+/// An element of a parsed document, namespaces are stripped from element and attribute names
+#[derive(Debug, Default)]
+pub(crate) struct XmlElement {
+    pub(crate) name: String,
+    pub(crate) attributes: Vec<(String, String)>,
+    pub(crate) children: Vec<XmlElement>,
+    pub(crate) text: String,
+}
+
+impl XmlElement {
+    /// Parse a document and return its root element
+    pub(crate) fn parse(reader: impl BufRead) -> Result<Self, GraphFormatError> {
+        let mut reader = Reader::from_reader(reader);
+        reader.config_mut().trim_text(true);
+        let mut buf = vec![];
+        let mut stack: Vec<XmlElement> = vec![];
+        loop {
+            let element = match reader.read_event_into(&mut buf)? {
+                Event::Start(start) => {
+                    stack.push(Self::from_start(&start)?);
+                    None
+                }
+                Event::Empty(start) => Some(Self::from_start(&start)?),
+                Event::End(_) => Some(stack.pop().ok_or_else(|| {
+                    GraphFormatError::InvalidDocument("unexpected closing tag".to_owned())
+                })?),
+                Event::Text(text) => {
+                    if let Some(current) = stack.last_mut() {
+                        current.text.push_str(&text.unescape()?);
+                    }
+                    None
+                }
+                Event::CData(text) => {
+                    if let Some(current) = stack.last_mut() {
+                        current.text.push_str(&String::from_utf8_lossy(&text));
+                    }
+                    None
+                }
+                Event::Eof => {
+                    return Err(GraphFormatError::InvalidDocument(
+                        "unexpected end of document".to_owned(),
+                    ))
+                }
+                _ => None,
+            };
+            if let Some(element) = element {
+                match stack.last_mut() {
+                    Some(parent) => parent.children.push(element),
+                    None => return Ok(element),
+                }
+            }
+            buf.clear();
+        }
+    }
+
+    fn from_start(start: &BytesStart) -> Result<Self, GraphFormatError> {
+        Ok(Self {
+            name: local_name(start),
+            attributes: read_attributes(start)?,
+            ..Default::default()
+        })
+    }
+
+    pub(crate) fn attr(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// The value of a mandatory attribute
+    pub(crate) fn required_attr(&self, name: &str) -> Result<&str, GraphFormatError> {
+        self.attr(name).ok_or_else(|| {
+            GraphFormatError::InvalidDocument(format!(
+                "<{}> is missing the '{}' attribute",
+                self.name, name
+            ))
+        })
+    }
+
+    pub(crate) fn children<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Self> + 'a {
+        self.children.iter().filter(move |child| child.name == name)
+    }
+
+    pub(crate) fn child(&self, name: &str) -> Option<&Self> {
+        self.children(name).next()
+    }
+}
+
+/// The name of an element without its namespace
+pub(crate) fn local_name(start: &BytesStart) -> String {
+    String::from_utf8_lossy(start.local_name().as_ref()).into_owned()
+}
+
+/// The unescaped attributes of an element, namespaces are stripped from their names
+pub(crate) fn read_attributes(
+    start: &BytesStart,
+) -> Result<Vec<(String, String)>, GraphFormatError> {
+    start
+        .attributes()
+        .map(|attribute| {
+            let attribute = attribute.map_err(quick_xml::Error::from)?;
+            let key = String::from_utf8_lossy(attribute.key.local_name().as_ref()).into_owned();
+            Ok((key, attribute.unescape_value()?.into_owned()))
+        })
+        .collect()
+}
+
+/// Indenting writer for the GraphML and GEXF documents
+pub(crate) struct XmlWriter<W: Write>(Writer<W>);
+
+impl<W: Write> XmlWriter<W> {
+    pub(crate) fn new(writer: W) -> Result<Self, GraphFormatError> {
+        let mut writer = Writer::new_with_indent(writer, b' ', 2);
+        writer.write_event(Event::Decl(BytesDecl::new("1.0", Some("UTF-8"), None)))?;
+        Ok(Self(writer))
+    }
+
+    pub(crate) fn start(
+        &mut self,
+        name: &str,
+        attributes: &[(&str, &str)],
+    ) -> Result<(), GraphFormatError> {
+        self.0.write_event(Event::Start(
+            BytesStart::new(name).with_attributes(attributes.iter().copied()),
+        ))?;
+        Ok(())
+    }
+
+    pub(crate) fn end(&mut self, name: &str) -> Result<(), GraphFormatError> {
+        self.0.write_event(Event::End(BytesEnd::new(name)))?;
+        Ok(())
+    }
+
+    pub(crate) fn empty(
+        &mut self,
+        name: &str,
+        attributes: &[(&str, &str)],
+    ) -> Result<(), GraphFormatError> {
+        self.0.write_event(Event::Empty(
+            BytesStart::new(name).with_attributes(attributes.iter().copied()),
+        ))?;
+        Ok(())
+    }
+
+    pub(crate) fn text_element(
+        &mut self,
+        name: &str,
+        attributes: &[(&str, &str)],
+        text: &str,
+    ) -> Result<(), GraphFormatError> {
+        self.start(name, attributes)?;
+        self.0.write_event(Event::Text(BytesText::new(text)))?;
+        self.end(name)
+    }
+
+    pub(crate) fn finish(self) -> Result<(), GraphFormatError> {
+        let mut writer = self.0.into_inner();
+        writer.flush()?;
+        Ok(())
+    }
+}
@@ -0,0 +1,1027 @@
+//! Declarative loader specifications.
+//!
+//! A [LoaderSpec] describes how the columns of CSV and Parquet sources map to the nodes and edges
+//! of a graph, instead of hand-coding the mapping for every dataset. Specs can be written in YAML,
+//! JSON or TOML, e.g.,
+//!
+//! ```yaml
+//! sources:
+//!   - path: transfers.csv
+//!     edges:
+//!       src: { column: from, type: u64 }
+//!       dst: { column: to, type: u64 }
+//!       time: { column: date, format: "%Y-%m-%d %H:%M:%S" }
+//!       layer: transfers
+//!       properties:
+//!         - { column: amount, type: f64 }
+//!       constant_properties: [currency]
+//!   - path: accounts
+//!     format: parquet
+//!     nodes:
+//!       id: account
+//!       time: opened
+//!       node_type_col: kind
+//!       shared_constant_properties: { bank: Raphtory }
+//! ```
+//!
+//! Every file of a source is streamed in chunks that are converted to the declared column types
+//! and loaded with the same dataframe loaders as the Parquet and pandas loaders. CSV files are read
+//! with the [CsvLoader], so they can be compressed with gzip or bzip2. Columns without a declared
+//! type keep the type of the source, i.e., text for CSV files. Time columns hold integers or
+//! timestamps, or text that is parsed with the format of the column or, without a format, as
+//! RFC 3339 or ISO 8601.
+//!
+//! A file that lacks one of the columns of the spec is an error. [LoaderSpec::validate] runs the
+//! conversion without loading anything and reports the values that don't match the declared type.
+//! [LoaderSpec::load_into_graph] validates all sources before the first one is loaded, which
+//! reads every source twice but never holds more than a chunk of a file in memory.
+
+use crate::{
+    core::{
+        utils::{
+            errors::GraphError,
+            time::{IntoTimeWithFormat, TryIntoTime},
+        },
+        Prop,
+    },
+    db::api::{
+        mutation::internal::{InternalAdditionOps, InternalPropertyAdditionOps},
+        view::StaticGraphViewOps,
+    },
+    io::{
+        arrow::{
+            dataframe::{DFChunk, DFView},
+            df_loaders::{load_edges_from_df, load_nodes_from_df},
+        },
+        csv_loader::CsvLoader,
+        parquet_loaders::{get_parquet_file_paths, process_parquet_file_to_df},
+    },
+};
+use chrono::NaiveDate;
+use csv::StringRecord;
+use polars_arrow::{
+    array::{new_null_array, Array, BooleanArray, PrimitiveArray, Utf8Array},
+    compute::cast::{self, CastOptions},
+    datatypes::ArrowDataType as DataType,
+    types::NativeType,
+};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    fmt::{Display, Formatter},
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+/// The type a column is converted to before it is loaded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColumnType {
+    Str,
+    Bool,
+    I32,
+    I64,
+    U8,
+    U16,
+    U32,
+    U64,
+    F32,
+    F64,
+}
+
+impl ColumnType {
+    fn data_type(self) -> DataType {
+        match self {
+            ColumnType::Str => DataType::LargeUtf8,
+            ColumnType::Bool => DataType::Boolean,
+            ColumnType::I32 => DataType::Int32,
+            ColumnType::I64 => DataType::Int64,
+            ColumnType::U8 => DataType::UInt8,
+            ColumnType::U16 => DataType::UInt16,
+            ColumnType::U32 => DataType::UInt32,
+            ColumnType::U64 => DataType::UInt64,
+            ColumnType::F32 => DataType::Float32,
+            ColumnType::F64 => DataType::Float64,
+        }
+    }
+}
+
+/// A column, optionally with the type it is converted to
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ColumnSpec {
+    Name(String),
+    Typed {
+        column: String,
+        #[serde(rename = "type", default)]
+        dtype: Option<ColumnType>,
+    },
+}
+
+impl ColumnSpec {
+    pub fn column(&self) -> &str {
+        match self {
+            ColumnSpec::Name(column) => column,
+            ColumnSpec::Typed { column, .. } => column,
+        }
+    }
+
+    pub fn dtype(&self) -> Option<ColumnType> {
+        match self {
+            ColumnSpec::Name(_) => None,
+            ColumnSpec::Typed { dtype, .. } => *dtype,
+        }
+    }
+}
+
+/// The time column, optionally with the `chrono` format of its text values
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum TimeSpec {
+    Name(String),
+    Formatted {
+        column: String,
+        #[serde(default)]
+        format: Option<String>,
+    },
+}
+
+impl TimeSpec {
+    pub fn column(&self) -> &str {
+        match self {
+            TimeSpec::Name(column) => column,
+            TimeSpec::Formatted { column, .. } => column,
+        }
+    }
+
+    pub fn format(&self) -> Option<&str> {
+        match self {
+            TimeSpec::Name(_) => None,
+            TimeSpec::Formatted { format, .. } => format.as_deref(),
+        }
+    }
+}
+
+/// A constant property value given in the spec
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum SpecValue {
+    Bool(bool),
+    I64(i64),
+    F64(f64),
+    Str(String),
+}
+
+impl From<&SpecValue> for Prop {
+    fn from(value: &SpecValue) -> Self {
+        match value {
+            SpecValue::Bool(v) => Prop::Bool(*v),
+            SpecValue::I64(v) => Prop::I64(*v),
+            SpecValue::F64(v) => Prop::F64(*v),
+            SpecValue::Str(v) => Prop::str(v.as_str()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct NodesSpec {
+    pub id: ColumnSpec,
+    pub time: TimeSpec,
+    #[serde(default)]
+    pub node_type: Option<String>,
+    #[serde(default)]
+    pub node_type_col: Option<String>,
+    #[serde(default)]
+    pub properties: Vec<ColumnSpec>,
+    #[serde(default)]
+    pub constant_properties: Vec<ColumnSpec>,
+    #[serde(default)]
+    pub shared_constant_properties: HashMap<String, SpecValue>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct EdgesSpec {
+    pub src: ColumnSpec,
+    pub dst: ColumnSpec,
+    pub time: TimeSpec,
+    #[serde(default)]
+    pub layer: Option<String>,
+    #[serde(default)]
+    pub layer_col: Option<String>,
+    #[serde(default)]
+    pub properties: Vec<ColumnSpec>,
+    #[serde(default)]
+    pub constant_properties: Vec<ColumnSpec>,
+    #[serde(default)]
+    pub shared_constant_properties: HashMap<String, SpecValue>,
+}
+
+/// What the rows of a source are loaded as
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MappingSpec {
+    Nodes(NodesSpec),
+    Edges(EdgesSpec),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SourceFormat {
+    Csv,
+    Parquet,
+}
+
+fn default_delimiter() -> char {
+    ','
+}
+
+/// A CSV or Parquet file, or a directory of them, and the mapping of its columns
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SourceSpec {
+    pub path: PathBuf,
+    /// Inferred from the extension of `path` if not set, directories default to Parquet
+    #[serde(default)]
+    pub format: Option<SourceFormat>,
+    /// The delimiter of CSV files, which always need a header
+    #[serde(default = "default_delimiter")]
+    pub delimiter: char,
+    #[serde(flatten)]
+    pub mapping: MappingSpec,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct LoaderSpec {
+    pub sources: Vec<SourceSpec>,
+}
+
+/// A problem with a source found while validating a spec
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SpecIssue {
+    pub source: PathBuf,
+    pub column: String,
+    pub message: String,
+}
+
+impl Display for SpecIssue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}, column '{}': {}",
+            self.source.display(),
+            self.column,
+            self.message
+        )
+    }
+}
+
+/// The result of validating a spec without loading it
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ValidationReport {
+    /// Number of rows that would be loaded
+    pub num_rows: usize,
+    pub issues: Vec<SpecIssue>,
+}
+
+impl ValidationReport {
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+impl Display for ValidationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} rows, {} issues", self.num_rows, self.issues.len())?;
+        for issue in &self.issues {
+            write!(f, "\n  {issue}")?;
+        }
+        Ok(())
+    }
+}
+
+fn invalid_spec(err: impl Display) -> GraphError {
+    GraphError::LoadFailure(format!("Invalid loader spec: {err}"))
+}
+
+/// The text values of a string column, `None` for columns of other types
+fn utf8_values(array: &dyn Array) -> Option<Vec<Option<&str>>> {
+    if let Some(array) = array.as_any().downcast_ref::<Utf8Array<i32>>() {
+        Some(array.iter().collect())
+    } else {
+        array
+            .as_any()
+            .downcast_ref::<Utf8Array<i64>>()
+            .map(|array| array.iter().collect())
+    }
+}
+
+fn parse_primitive<T: NativeType + FromStr>(values: &[Option<&str>]) -> Box<dyn Array> {
+    Box::new(
+        values
+            .iter()
+            .map(|value| value.and_then(|v| v.trim().parse::<T>().ok()))
+            .collect::<PrimitiveArray<T>>(),
+    )
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value.trim().to_lowercase().as_str() {
+        "true" | "t" | "yes" | "1" => Some(true),
+        "false" | "f" | "no" | "0" => Some(false),
+        _ => None,
+    }
+}
+
+/// Convert `array` to `dtype`, returns the converted column and the number of values that could
+/// not be converted
+fn convert_column(array: Box<dyn Array>, dtype: ColumnType) -> (Box<dyn Array>, usize) {
+    let target = dtype.data_type();
+    if array.data_type() == &target {
+        return (array, 0);
+    }
+    let converted = match utf8_values(array.as_ref()) {
+        Some(values) => match dtype {
+            ColumnType::Str => Box::new(Utf8Array::<i64>::from_iter(values.iter().copied())),
+            ColumnType::Bool => Box::new(
+                values
+                    .iter()
+                    .map(|value| value.and_then(parse_bool))
+                    .collect::<BooleanArray>(),
+            ),
+            ColumnType::I32 => parse_primitive::<i32>(&values),
+            ColumnType::I64 => parse_primitive::<i64>(&values),
+            ColumnType::U8 => parse_primitive::<u8>(&values),
+            ColumnType::U16 => parse_primitive::<u16>(&values),
+            ColumnType::U32 => parse_primitive::<u32>(&values),
+            ColumnType::U64 => parse_primitive::<u64>(&values),
+            ColumnType::F32 => parse_primitive::<f32>(&values),
+            ColumnType::F64 => parse_primitive::<f64>(&values),
+        },
+        None => cast::cast(array.as_ref(), &target, CastOptions::default())
+            .unwrap_or_else(|_| new_null_array(target, array.len())),
+    };
+    let failed = converted.null_count().saturating_sub(array.null_count());
+    (converted, failed)
+}
+
+fn parse_time(value: &str, format: Option<&str>) -> Option<i64> {
+    match format {
+        Some(format) => value.parse_time(format).ok().or_else(|| {
+            NaiveDate::parse_from_str(value, format)
+                .ok()
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+                .map(|datetime| datetime.and_utc().timestamp_millis())
+        }),
+        None => value.try_into_time().ok(),
+    }
+}
+
+/// Convert a time column to milliseconds, returns the converted column and the number of values
+/// that could not be converted
+fn convert_time_column(array: Box<dyn Array>, format: Option<&str>) -> (Box<dyn Array>, usize) {
+    if let Some(values) = utf8_values(array.as_ref()) {
+        let converted: Box<dyn Array> = Box::new(
+            values
+                .iter()
+                .map(|value| value.and_then(|v| parse_time(v, format)))
+                .collect::<PrimitiveArray<i64>>(),
+        );
+        let failed = converted.null_count().saturating_sub(array.null_count());
+        return (converted, failed);
+    }
+    match array.data_type() {
+        DataType::Int64 | DataType::Timestamp(_, _) => (array, 0),
+        _ => convert_column(array, ColumnType::I64),
+    }
+}
+
+/// Number of rows of a CSV file that are converted at a time
+const CSV_CHUNK_SIZE: usize = 100_000;
+
+/// The chunks of a file of a source, read lazily
+struct FileChunks {
+    names: Vec<String>,
+    chunks: Box<dyn Iterator<Item = Result<DFChunk, GraphError>>>,
+    /// Number of rows of the file if it is known without reading it
+    num_rows: usize,
+}
+
+fn read_failure(path: &Path, err: impl Display) -> GraphError {
+    GraphError::LoadFailure(format!("Failed to read {path:?}: {err}"))
+}
+
+/// The records of a CSV file as chunks of text columns
+struct CsvChunks {
+    path: PathBuf,
+    reader: csv::Reader<Box<dyn io::Read>>,
+    /// The index of each column in the records
+    indices: Vec<usize>,
+    done: bool,
+}
+
+impl Iterator for CsvChunks {
+    type Item = Result<DFChunk, GraphError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let mut values: Vec<Vec<Option<String>>> = vec![vec![]; self.indices.len()];
+        let mut record = StringRecord::new();
+        let mut num_rows = 0;
+        while num_rows < CSV_CHUNK_SIZE {
+            match self.reader.read_record(&mut record) {
+                Ok(true) => {}
+                Ok(false) => {
+                    self.done = true;
+                    break;
+                }
+                Err(err) => {
+                    self.done = true;
+                    return Some(Err(read_failure(&self.path, err)));
+                }
+            }
+            for (index, column) in self.indices.iter().zip(values.iter_mut()) {
+                column.push(
+                    record
+                        .get(*index)
+                        .filter(|value| !value.is_empty())
+                        .map(|value| value.to_string()),
+                );
+            }
+            num_rows += 1;
+        }
+        if num_rows == 0 {
+            return None;
+        }
+        let chunk = values
+            .into_iter()
+            .map(|column| Box::new(Utf8Array::<i64>::from_iter(column)) as Box<dyn Array>)
+            .collect();
+        Some(Ok(DFChunk { chunk }))
+    }
+}
+
+fn csv_loader(path: &Path, delimiter: char) -> CsvLoader {
+    CsvLoader::new(path)
+        .set_header(true)
+        .set_delimiter(&delimiter.to_string())
+        .with_filter(Regex::new(r"\.csv(\.gz|\.bz2)?$").expect("valid regex"))
+}
+
+fn read_csv(path: &Path, delimiter: char, columns: &[&str]) -> Result<FileChunks, GraphError> {
+    let mut reader = csv_loader(path, delimiter)
+        .csv_reader(path.to_path_buf())
+        .map_err(|err| read_failure(path, err))?;
+    let header = reader
+        .headers()
+        .map_err(|err| read_failure(path, err))?
+        .clone();
+    let (names, indices): (Vec<String>, Vec<usize>) = columns
+        .iter()
+        .filter_map(|column| {
+            header
+                .iter()
+                .position(|name| name == *column)
+                .map(|index| (column.to_string(), index))
+        })
+        .unzip();
+    Ok(FileChunks {
+        names,
+        chunks: Box::new(CsvChunks {
+            path: path.to_path_buf(),
+            reader,
+            indices,
+            done: false,
+        }),
+        num_rows: 0,
+    })
+}
+
+fn read_parquet(path: &Path, columns: &[&str]) -> Result<FileChunks, GraphError> {
+    let df_view = process_parquet_file_to_df(path, columns)?;
+    Ok(FileChunks {
+        names: df_view.names,
+        chunks: Box::new(df_view.chunks),
+        num_rows: df_view.num_rows,
+    })
+}
+
+impl SourceSpec {
+    fn format(&self) -> SourceFormat {
+        self.format.unwrap_or_else(
+            || match self.path.extension().and_then(|ext| ext.to_str()) {
+                Some("csv") | Some("tsv") | Some("txt") => SourceFormat::Csv,
+                _ => SourceFormat::Parquet,
+            },
+        )
+    }
+
+    fn files(&self) -> Result<Vec<PathBuf>, GraphError> {
+        match self.format() {
+            SourceFormat::Parquet => get_parquet_file_paths(&self.path),
+            SourceFormat::Csv if self.path.is_dir() => {
+                let mut files = csv_loader(&self.path, self.delimiter)
+                    .files_vec()
+                    .map_err(|err| read_failure(&self.path, err))?;
+                files.sort();
+                Ok(files)
+            }
+            SourceFormat::Csv => Ok(vec![self.path.clone()]),
+        }
+    }
+
+    fn time(&self) -> &TimeSpec {
+        match &self.mapping {
+            MappingSpec::Nodes(spec) => &spec.time,
+            MappingSpec::Edges(spec) => &spec.time,
+        }
+    }
+
+    /// The value columns of the mapping, i.e., all columns except the time column
+    fn value_columns(&self) -> Vec<&ColumnSpec> {
+        match &self.mapping {
+            MappingSpec::Nodes(spec) => [&spec.id]
+                .into_iter()
+                .chain(&spec.properties)
+                .chain(&spec.constant_properties)
+                .collect(),
+            MappingSpec::Edges(spec) => [&spec.src, &spec.dst]
+                .into_iter()
+                .chain(&spec.properties)
+                .chain(&spec.constant_properties)
+                .collect(),
+        }
+    }
+
+    fn columns(&self) -> Vec<&str> {
+        let mut columns = vec![self.time().column()];
+        columns.extend(self.value_columns().into_iter().map(|c| c.column()));
+        match &self.mapping {
+            MappingSpec::Nodes(spec) => columns.extend(spec.node_type_col.as_deref()),
+            MappingSpec::Edges(spec) => columns.extend(spec.layer_col.as_deref()),
+        }
+        columns
+    }
+
+    /// Open the file at `path` of the source, fails if it lacks one of the columns of the spec
+    fn open(&self, path: &Path) -> Result<FileChunks, GraphError> {
+        let columns = self.columns();
+        let file = match self.format() {
+            SourceFormat::Csv => read_csv(path, self.delimiter, &columns)?,
+            SourceFormat::Parquet => read_parquet(path, &columns)?,
+        };
+        let missing: Vec<_> = columns
+            .iter()
+            .filter(|column| !file.names.iter().any(|name| name == *column))
+            .map(|column| column.to_string())
+            .collect();
+        if !missing.is_empty() {
+            return Err(GraphError::ColumnDoesNotExist(format!(
+                "{} (in {})",
+                missing.join(", "),
+                path.display()
+            )));
+        }
+        Ok(file)
+    }
+
+    /// The columns of a file that are converted with their type, `None` for the time column
+    fn conversions(&self, names: &[String]) -> Vec<(usize, Option<ColumnType>)> {
+        let mut conversions = vec![];
+        for (index, name) in names.iter().enumerate() {
+            if name == self.time().column() {
+                conversions.push((index, None));
+            } else if let Some(dtype) = self
+                .value_columns()
+                .into_iter()
+                .find(|c| c.column() == name)
+                .and_then(|c| c.dtype())
+            {
+                conversions.push((index, Some(dtype)));
+            }
+        }
+        conversions
+    }
+
+    /// Convert the columns of `chunk`, returns the number of values of each conversion that could
+    /// not be converted
+    fn convert_chunk(
+        &self,
+        conversions: &[(usize, Option<ColumnType>)],
+        chunk: &mut DFChunk,
+    ) -> Vec<usize> {
+        conversions
+            .iter()
+            .map(|(index, dtype)| {
+                let column = chunk.chunk[*index].clone();
+                let (converted, failed) = match dtype {
+                    Some(dtype) => convert_column(column, *dtype),
+                    None => convert_time_column(column, self.time().format()),
+                };
+                chunk.chunk[*index] = converted;
+                failed
+            })
+            .collect()
+    }
+
+    fn issue(
+        &self,
+        path: &Path,
+        column: &str,
+        dtype: Option<ColumnType>,
+        failed: usize,
+    ) -> SpecIssue {
+        let expected = match dtype {
+            Some(dtype) => format!("{dtype:?}"),
+            None => match self.time().format() {
+                Some(format) => format!("time with format '{format}'"),
+                None => "time".to_string(),
+            },
+        };
+        SpecIssue {
+            source: path.to_path_buf(),
+            column: column.to_string(),
+            message: format!("{failed} values can't be read as {expected}"),
+        }
+    }
+
+    /// Stream the files of the source through the conversion and add the problems found to
+    /// `report`
+    fn validate(&self, report: &mut ValidationReport) -> Result<(), GraphError> {
+        for path in self.files()? {
+            let file = self.open(&path)?;
+            let conversions = self.conversions(&file.names);
+            let mut failed = vec![0; conversions.len()];
+            for chunk in file.chunks {
+                let mut chunk = chunk?;
+                report.num_rows += chunk.len();
+                for (total, chunk_failed) in failed
+                    .iter_mut()
+                    .zip(self.convert_chunk(&conversions, &mut chunk))
+                {
+                    *total += chunk_failed;
+                }
+            }
+            for ((index, dtype), failed) in conversions.into_iter().zip(failed) {
+                if failed > 0 {
+                    report
+                        .issues
+                        .push(self.issue(&path, &file.names[index], dtype, failed));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Stream the files of the source into `graph`, fails on the first value that can't be
+    /// converted
+    fn load<G: StaticGraphViewOps + InternalPropertyAdditionOps + InternalAdditionOps>(
+        &self,
+        graph: &G,
+    ) -> Result<(), GraphError> {
+        for path in self.files()? {
+            let file = self.open(&path)?;
+            let conversions = self.conversions(&file.names);
+            let names = file.names.clone();
+            let chunks = file.chunks.map(|chunk| {
+                let mut chunk = chunk?;
+                let failed = self.convert_chunk(&conversions, &mut chunk);
+                for ((index, dtype), failed) in conversions.iter().zip(failed) {
+                    if failed > 0 {
+                        let issue = self.issue(&path, &names[*index], *dtype, failed);
+                        return Err(GraphError::LoadFailure(format!(
+                            "Source doesn't match the loader spec: {issue}"
+                        )));
+                    }
+                }
+                Ok(chunk)
+            });
+            let df_view = DFView {
+                names: file.names,
+                chunks,
+                num_rows: file.num_rows,
+            };
+            self.load_df(df_view, graph)?;
+        }
+        Ok(())
+    }
+
+    fn load_df<G: StaticGraphViewOps + InternalPropertyAdditionOps + InternalAdditionOps>(
+        &self,
+        df_view: DFView<impl Iterator<Item = Result<DFChunk, GraphError>>>,
+        graph: &G,
+    ) -> Result<(), GraphError> {
+        let time = self.time().column();
+        match &self.mapping {
+            MappingSpec::Nodes(spec) => {
+                let properties: Vec<_> = spec.properties.iter().map(|c| c.column()).collect();
+                let constant_properties: Vec<_> = spec
+                    .constant_properties
+                    .iter()
+                    .map(|c| c.column())
+                    .collect();
+                let shared: HashMap<String, Prop> = spec
+                    .shared_constant_properties
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.into()))
+                    .collect();
+                load_nodes_from_df(
+                    df_view,
+                    time,
+                    spec.id.column(),
+                    Some(&properties),
+                    Some(&constant_properties),
+                    Some(&shared),
+                    spec.node_type.as_deref(),
+                    spec.node_type_col.as_deref(),
+                    graph,
+                )
+            }
+            MappingSpec::Edges(spec) => {
+                let properties: Vec<_> = spec.properties.iter().map(|c| c.column()).collect();
+                let constant_properties: Vec<_> = spec
+                    .constant_properties
+                    .iter()
+                    .map(|c| c.column())
+                    .collect();
+                let shared: HashMap<String, Prop> = spec
+                    .shared_constant_properties
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.into()))
+                    .collect();
+                load_edges_from_df(
+                    df_view,
+                    time,
+                    spec.src.column(),
+                    spec.dst.column(),
+                    Some(&properties),
+                    Some(&constant_properties),
+                    Some(&shared),
+                    spec.layer.as_deref(),
+                    spec.layer_col.as_deref(),
+                    graph,
+                )
+            }
+        }
+    }
+}
+
+impl LoaderSpec {
+    pub fn from_json_str(spec: &str) -> Result<Self, GraphError> {
+        serde_json::from_str(spec).map_err(invalid_spec)
+    }
+
+    pub fn from_toml_str(spec: &str) -> Result<Self, GraphError> {
+        toml::from_str(spec).map_err(invalid_spec)
+    }
+
+    pub fn from_yaml_str(spec: &str) -> Result<Self, GraphError> {
+        serde_yaml::from_str(spec).map_err(invalid_spec)
+    }
+
+    /// Read the spec at `path`, its format is chosen by the extension (`.yaml`, `.yml`, `.json`
+    /// or `.toml`). Relative source paths are resolved against the directory of the spec.
+    pub fn from_path(path: impl AsRef<Path>) -> Result<Self, GraphError> {
+        let path = path.as_ref();
+        let text = fs::read_to_string(path)?;
+        let mut spec = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("yaml") | Some("yml") => Self::from_yaml_str(&text)?,
+            Some("json") => Self::from_json_str(&text)?,
+            Some("toml") => Self::from_toml_str(&text)?,
+            _ => {
+                return Err(invalid_spec(format!(
+                    "unknown format of {path:?}, expected .yaml, .json or .toml"
+                )))
+            }
+        };
+        if let Some(base) = path.parent() {
+            for source in spec.sources.iter_mut() {
+                if source.path.is_relative() {
+                    source.path = base.join(&source.path);
+                }
+            }
+        }
+        Ok(spec)
+    }
+
+    /// Stream all sources through the conversion and check them against the spec without
+    /// loading anything (dry run)
+    pub fn validate(&self) -> Result<ValidationReport, GraphError> {
+        let mut report = ValidationReport::default();
+        for source in &self.sources {
+            source.validate(&mut report)?;
+        }
+        Ok(report)
+    }
+
+    /// Load all sources into `graph` in the order they are listed.
+    ///
+    /// The sources are validated first, nothing is loaded if any of them doesn't match the spec.
+    pub fn load_into_graph<
+        G: StaticGraphViewOps + InternalPropertyAdditionOps + InternalAdditionOps,
+    >(
+        &self,
+        graph: &G,
+    ) -> Result<(), GraphError> {
+        let report = self.validate()?;
+        if !report.is_valid() {
+            return Err(GraphError::LoadFailure(format!(
+                "Sources don't match the loader spec: {report}"
+            )));
+        }
+        for source in &self.sources {
+            source.load(graph)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use std::io::Write;
+    use tempfile::tempdir;
+
+    fn write_file(path: &Path, contents: &str) {
+        fs::File::create(path)
+            .unwrap()
+            .write_all(contents.as_bytes())
+            .unwrap();
+    }
+
+    const SPEC: &str = r#"
+sources:
+  - path: accounts.csv
+    nodes:
+      id: { column: account, type: u64 }
+      time: opened
+      node_type_col: kind
+      constant_properties: [owner]
+  - path: transfers.csv
+    edges:
+      src: { column: from, type: u64 }
+      dst: { column: to, type: u64 }
+      time: { column: date, format: "%d/%m/%Y" }
+      layer: transfers
+      properties:
+        - { column: amount, type: f64 }
+      shared_constant_properties: { currency: EUR }
+"#;
+
+    #[test]
+    fn test_load_yaml_spec() {
+        let dir = tempdir().unwrap();
+        write_file(
+            &dir.path().join("accounts.csv"),
+            "account,opened,kind,owner\n1,2024-01-01,savings,Alice\n2,2024-01-02,current,Bob\n",
+        );
+        write_file(
+            &dir.path().join("transfers.csv"),
+            "from,to,date,amount\n1,2,03/01/2024,10.5\n2,1,04/01/2024,3\n",
+        );
+        let spec_path = dir.path().join("spec.yaml");
+        write_file(&spec_path, SPEC);
+
+        let spec = LoaderSpec::from_path(&spec_path).unwrap();
+        let report = spec.validate().unwrap();
+        assert!(report.is_valid(), "{report}");
+        assert_eq!(report.num_rows, 4);
+
+        let g = Graph::new();
+        spec.load_into_graph(&g).unwrap();
+        assert_eq!(g.count_nodes(), 2);
+        assert_eq!(g.count_temporal_edges(), 2);
+        let alice = g.node(1).unwrap();
+        assert_eq!(alice.node_type().as_str(), Some("savings"));
+        assert_eq!(
+            alice.properties().constant().get("owner"),
+            Some(Prop::str("Alice"))
+        );
+        let edge = g.layers("transfers").unwrap().edge(1, 2).unwrap();
+        assert_eq!(edge.earliest_time(), Some(1704240000000));
+        assert_eq!(edge.properties().get("amount"), Some(Prop::F64(10.5)));
+        assert_eq!(
+            edge.properties().constant().get("currency"),
+            Some(Prop::str("EUR"))
+        );
+    }
+
+    #[test]
+    fn test_dry_run_reports_mismatches() {
+        let dir = tempdir().unwrap();
+        write_file(
+            &dir.path().join("accounts.csv"),
+            "account,opened,kind,owner\nx,2024-01-01,savings,Alice\n",
+        );
+        write_file(
+            &dir.path().join("transfers.csv"),
+            "from,to,date,amount\n1,2,2024-01-03,ten\n",
+        );
+        let spec_path = dir.path().join("spec.yaml");
+        write_file(&spec_path, SPEC);
+
+        let spec = LoaderSpec::from_path(&spec_path).unwrap();
+        let report = spec.validate().unwrap();
+        let mut columns: Vec<_> = report.issues.iter().map(|i| i.column.as_str()).collect();
+        columns.sort();
+        assert_eq!(columns, ["account", "amount", "date"]);
+
+        let g = Graph::new();
+        assert!(spec.load_into_graph(&g).is_err());
+        assert_eq!(g.count_nodes(), 0);
+    }
+
+    #[test]
+    fn test_missing_column_is_an_error() {
+        let dir = tempdir().unwrap();
+        write_file(
+            &dir.path().join("accounts.csv"),
+            "account,opened,kind\n1,2024-01-01,savings\n",
+        );
+        write_file(
+            &dir.path().join("transfers.csv"),
+            "from,to,date,amount\n1,2,03/01/2024,10\n",
+        );
+        let spec_path = dir.path().join("spec.yaml");
+        write_file(&spec_path, SPEC);
+
+        let spec = LoaderSpec::from_path(&spec_path).unwrap();
+        assert!(matches!(
+            spec.validate(),
+            Err(GraphError::ColumnDoesNotExist(columns)) if columns.starts_with("owner")
+        ));
+        let g = Graph::new();
+        assert!(spec.load_into_graph(&g).is_err());
+        assert_eq!(g.count_nodes(), 0);
+    }
+
+    #[test]
+    fn test_load_compressed_csv_dir() {
+        let dir = tempdir().unwrap();
+        let transfers = dir.path().join("transfers");
+        fs::create_dir(&transfers).unwrap();
+        write_file(
+            &transfers.join("0.csv"),
+            "from,to,date,amount\n1,2,03/01/2024,10.5\n",
+        );
+        let mut encoder = flate2::write::GzEncoder::new(
+            fs::File::create(transfers.join("1.csv.gz")).unwrap(),
+            flate2::Compression::default(),
+        );
+        encoder
+            .write_all(b"from,to,date,amount\n2,3,04/01/2024,3\n")
+            .unwrap();
+        encoder.finish().unwrap();
+        write_file(&transfers.join("notes.txt"), "not a source");
+
+        let mut spec = LoaderSpec::from_yaml_str(
+            r#"
+sources:
+  - path: transfers
+    format: csv
+    edges:
+      src: from
+      dst: to
+      time: { column: date, format: "%d/%m/%Y" }
+      properties:
+        - { column: amount, type: f64 }
+"#,
+        )
+        .unwrap();
+        spec.sources[0].path = transfers;
+        let g = Graph::new();
+        spec.load_into_graph(&g).unwrap();
+        assert_eq!(g.count_edges(), 2);
+        assert_eq!(
+            g.edge("2", "3").unwrap().properties().get("amount"),
+            Some(Prop::F64(3.0))
+        );
+    }
+
+    #[test]
+    fn test_spec_formats_are_equivalent() {
+        let yaml = LoaderSpec::from_yaml_str(SPEC).unwrap();
+        let json = LoaderSpec::from_json_str(&serde_json::to_string(&yaml).unwrap()).unwrap();
+        assert_eq!(yaml, json);
+        let toml_spec = r#"
+            [[sources]]
+            path = "transfers.csv"
+            [sources.edges]
+            src = "from"
+            dst = "to"
+            time = { column = "date", format = "%d/%m/%Y" }
+            properties = [{ column = "amount", type = "f64" }]
+        "#;
+        let spec = LoaderSpec::from_toml_str(toml_spec).unwrap();
+        let MappingSpec::Edges(edges) = &spec.sources[0].mapping else {
+            panic!("expected an edge mapping")
+        };
+        assert_eq!(edges.properties[0].dtype(), Some(ColumnType::F64));
+        assert_eq!(edges.time.format(), Some("%d/%m/%Y"));
+    }
+}
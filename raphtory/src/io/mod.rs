@@ -1,7 +1,15 @@
 #[cfg(feature = "arrow")]
 pub(crate) mod arrow;
+#[cfg(feature = "arrow")]
+pub mod bulk_loaders;
 pub mod csv_loader;
+pub mod graph_formats;
 pub mod json_loader;
+#[cfg(feature = "arrow")]
+pub mod loader_spec;
 pub mod neo4j_loader;
 #[cfg(feature = "arrow")]
+pub mod parquet_export;
+#[cfg(feature = "arrow")]
 pub mod parquet_loaders;
+pub mod stream_loader;
@@ -0,0 +1,1122 @@
+//! Export graph views to Parquet datasets.
+//!
+//! A view is written to a directory with one sub-directory per table. Each table is split into
+//! `part-<n>.parquet` files and uses the column layout expected by the loaders in
+//! [parquet_loaders](crate::io::parquet_loaders), so the exported view can be loaded back with
+//! `load_*_from_parquet`:
+//!
+//! | Directory         | Columns                                  | Loader                               |
+//! |-------------------|------------------------------------------|--------------------------------------|
+//! | `nodes`           | `time`, `id`, `node_type`, temporal props | [load_nodes_from_parquet]            |
+//! | `node_properties` | `id`, `node_type`, constant props        | [load_node_props_from_parquet]       |
+//! | `edges`           | `time`, `src`, `dst`, `layer`, temporal props | [load_edges_from_parquet]       |
+//! | `edge_deletions`  | `time`, `src`, `dst`, `layer`            | [load_edge_deletions_from_parquet]   |
+//! | `edge_properties` | `src`, `dst`, `layer`, constant props    | [load_edge_props_from_parquet]       |
+//!
+//! Node ids are written as `UInt64` if all ids of the view are integers and as strings otherwise.
+//! Every property of the graph gets a column which is null for rows without a value. List
+//! properties are written as `LargeList` and map properties as `Struct` columns, nested properties
+//! without any value in the view are omitted. Graph properties are not exported.
+//!
+//! Tables are written incrementally, at most [ROWS_PER_ROW_GROUP] rows are buffered in memory.
+//!
+//! Windows of a persistent graph report the edges and property values that are still alive at the
+//! start of the window at the start time. These are exported with the time of the update that
+//! added them instead, so that loading the dataset into a persistent graph restores the view.
+//!
+//! [load_nodes_from_parquet]: crate::io::parquet_loaders::load_nodes_from_parquet
+//! [load_node_props_from_parquet]: crate::io::parquet_loaders::load_node_props_from_parquet
+//! [load_edges_from_parquet]: crate::io::parquet_loaders::load_edges_from_parquet
+//! [load_edge_deletions_from_parquet]: crate::io::parquet_loaders::load_edge_deletions_from_parquet
+//! [load_edge_props_from_parquet]: crate::io::parquet_loaders::load_edge_props_from_parquet
+
+use crate::{
+    core::{
+        entities::{properties::props::PropMapper, LayerIds},
+        storage::timeindex::{AsTime, TimeIndexEntry, TimeIndexOps},
+        utils::errors::GraphError,
+        PropType,
+    },
+    db::api::{
+        storage::graph::{
+            edges::edge_storage_ops::EdgeStorageOps, nodes::node_storage_ops::NodeStorageOps,
+            tprop_storage_ops::TPropOps,
+        },
+        view::internal::{CoreGraphOps, GraphType, InternalMaterialize},
+    },
+    prelude::*,
+};
+use polars_arrow::{
+    array::{Array, BooleanArray, ListArray, PrimitiveArray, StructArray, Utf8Array},
+    bitmap::MutableBitmap,
+    datatypes::{ArrowDataType as DataType, ArrowSchema, Field, TimeUnit},
+    offset::Offsets,
+    record_batch::RecordBatch,
+    types::NativeType,
+};
+use polars_parquet::write::{
+    transverse, CompressionOptions, Encoding, FileWriter, RowGroupIterator, Version, WriteOptions,
+};
+use raphtory_api::core::storage::arc_str::ArcStr;
+use std::{
+    collections::{BTreeMap, HashMap},
+    fs,
+    fs::File,
+    path::{Path, PathBuf},
+};
+
+pub const NODES_DIR: &str = "nodes";
+pub const NODE_PROPERTIES_DIR: &str = "node_properties";
+pub const EDGES_DIR: &str = "edges";
+pub const EDGE_DELETIONS_DIR: &str = "edge_deletions";
+pub const EDGE_PROPERTIES_DIR: &str = "edge_properties";
+
+pub const TIME_COL: &str = "time";
+pub const ID_COL: &str = "id";
+pub const NODE_TYPE_COL: &str = "node_type";
+pub const SRC_COL: &str = "src";
+pub const DST_COL: &str = "dst";
+pub const LAYER_COL: &str = "layer";
+
+/// Default maximum number of rows written to a single Parquet file
+pub const DEFAULT_ROWS_PER_FILE: usize = 1_000_000;
+
+/// Maximum number of rows buffered before they are written to a file as a row group
+pub const ROWS_PER_ROW_GROUP: usize = 64 * 1024;
+
+pub trait ParquetExport {
+    /// Export the view to a Parquet dataset in the directory `path`
+    ///
+    /// Existing Parquet files in the table directories are replaced.
+    fn to_parquet(&self, path: impl AsRef<Path>) -> Result<(), GraphError> {
+        self.to_parquet_partitioned(path, DEFAULT_ROWS_PER_FILE)
+    }
+
+    /// Export the view to a Parquet dataset in the directory `path`, writing at most
+    /// `rows_per_file` rows to each file
+    fn to_parquet_partitioned(
+        &self,
+        path: impl AsRef<Path>,
+        rows_per_file: usize,
+    ) -> Result<(), GraphError>;
+}
+
+impl<'graph, G: GraphViewOps<'graph>> ParquetExport for G {
+    fn to_parquet_partitioned(
+        &self,
+        path: impl AsRef<Path>,
+        rows_per_file: usize,
+    ) -> Result<(), GraphError> {
+        let path = path.as_ref();
+        let rows_per_file = rows_per_file.max(1);
+        let numeric_ids = self
+            .nodes()
+            .iter()
+            .all(|node| matches!(node.id(), GID::U64(_)));
+        let id_type = if numeric_ids {
+            DataType::UInt64
+        } else {
+            DataType::LargeUtf8
+        };
+        let node_meta = self.node_meta();
+        let edge_meta = self.edge_meta();
+
+        write_table(
+            &path.join(NODES_DIR),
+            &[
+                (TIME_COL, DataType::Int64),
+                (ID_COL, id_type.clone()),
+                (NODE_TYPE_COL, DataType::LargeUtf8),
+            ],
+            node_meta.temporal_prop_meta(),
+            rows_per_file,
+            |sink| node_rows(self, numeric_ids, sink),
+        )?;
+        write_table(
+            &path.join(NODE_PROPERTIES_DIR),
+            &[
+                (ID_COL, id_type.clone()),
+                (NODE_TYPE_COL, DataType::LargeUtf8),
+            ],
+            node_meta.const_prop_meta(),
+            rows_per_file,
+            |sink| node_property_rows(self, numeric_ids, sink),
+        )?;
+        write_table(
+            &path.join(EDGES_DIR),
+            &[
+                (TIME_COL, DataType::Int64),
+                (SRC_COL, id_type.clone()),
+                (DST_COL, id_type.clone()),
+                (LAYER_COL, DataType::LargeUtf8),
+            ],
+            edge_meta.temporal_prop_meta(),
+            rows_per_file,
+            |sink| edge_rows(self, numeric_ids, sink),
+        )?;
+        write_table(
+            &path.join(EDGE_DELETIONS_DIR),
+            &[
+                (TIME_COL, DataType::Int64),
+                (SRC_COL, id_type.clone()),
+                (DST_COL, id_type.clone()),
+                (LAYER_COL, DataType::LargeUtf8),
+            ],
+            &PropMapper::default(),
+            rows_per_file,
+            |sink| edge_deletion_rows(self, numeric_ids, sink),
+        )?;
+        write_table(
+            &path.join(EDGE_PROPERTIES_DIR),
+            &[
+                (SRC_COL, id_type.clone()),
+                (DST_COL, id_type),
+                (LAYER_COL, DataType::LargeUtf8),
+            ],
+            edge_meta.const_prop_meta(),
+            rows_per_file,
+            |sink| edge_property_rows(self, numeric_ids, sink),
+        )?;
+        Ok(())
+    }
+}
+
+/// A row of a table: the values of the key columns followed by the properties set in the row
+struct Row {
+    keys: Vec<Option<Prop>>,
+    props: Vec<(ArcStr, Prop)>,
+}
+
+type RowSink<'a> = &'a mut dyn FnMut(Row) -> Result<(), GraphError>;
+
+/// The start of the view if it is a window of a persistent graph. The view reports the edges and
+/// property values that are alive at the start of the window with their time clamped to the start.
+fn persistent_window_start<'graph, G: GraphViewOps<'graph>>(graph: &G) -> Option<i64> {
+    match graph.graph_type() {
+        GraphType::PersistentGraph => graph.view_start(),
+        GraphType::EventGraph => None,
+    }
+}
+
+fn id_prop(id: &GID, numeric_ids: bool) -> Prop {
+    match id {
+        GID::U64(id) if numeric_ids => Prop::U64(*id),
+        _ => Prop::Str(id.to_str().as_ref().into()),
+    }
+}
+
+fn node_rows<'graph, G: GraphViewOps<'graph>>(
+    graph: &G,
+    numeric_ids: bool,
+    sink: RowSink,
+) -> Result<(), GraphError> {
+    let window_start = persistent_window_start(graph);
+    let prop_meta = graph.node_meta().temporal_prop_meta();
+    for node in graph.nodes() {
+        // one row per update time, temporal properties updated at the same time share a row
+        let mut rows: BTreeMap<i64, Vec<(ArcStr, Prop)>> =
+            node.history().into_iter().map(|t| (t, vec![])).collect();
+        for (key, prop) in node.properties().temporal().iter() {
+            for (t, value) in prop.iter() {
+                let t = match window_start {
+                    Some(start) if t == start => prop_meta
+                        .get_id(&key)
+                        .and_then(|id| {
+                            graph
+                                .core_node_entry(node.node)
+                                .tprop(id)
+                                .last_before(start.saturating_add(1))
+                        })
+                        .map_or(t, |(t, _)| t.t()),
+                    _ => t,
+                };
+                rows.entry(t).or_default().push((key.clone(), value));
+            }
+        }
+        let id = id_prop(&node.id(), numeric_ids);
+        let node_type = node.node_type().map(Prop::Str);
+        for (t, props) in rows {
+            sink(Row {
+                keys: vec![Some(Prop::I64(t)), Some(id.clone()), node_type.clone()],
+                props,
+            })?;
+        }
+    }
+    Ok(())
+}
+
+fn node_property_rows<'graph, G: GraphViewOps<'graph>>(
+    graph: &G,
+    numeric_ids: bool,
+    sink: RowSink,
+) -> Result<(), GraphError> {
+    for node in graph.nodes() {
+        let props: Vec<_> = node.properties().constant().iter().collect();
+        if props.is_empty() {
+            continue;
+        }
+        sink(Row {
+            keys: vec![
+                Some(id_prop(&node.id(), numeric_ids)),
+                node.node_type().map(Prop::Str),
+            ],
+            props,
+        })?;
+    }
+    Ok(())
+}
+
+fn edge_rows<'graph, G: GraphViewOps<'graph>>(
+    graph: &G,
+    numeric_ids: bool,
+    sink: RowSink,
+) -> Result<(), GraphError> {
+    let persistent = matches!(graph.graph_type(), GraphType::PersistentGraph);
+    for edge in graph.edges().explode() {
+        let mut t = edge.time()?;
+        if let (true, Some(time), Some(layer)) = (persistent, edge.edge.time(), edge.edge.layer()) {
+            let entry = graph.core_edge(edge.edge.into());
+            let additions = entry.additions(*layer);
+            if !additions.active(time..TimeIndexEntry(time.0, time.1.saturating_add(1))) {
+                // the edge is alive at the start of the window, it is exported with the time of
+                // the addition before the window (or skipped if it was only added at the start)
+                match additions.range(TimeIndexEntry::MIN..time).last() {
+                    Some(addition) => t = addition.t(),
+                    None => continue,
+                }
+            }
+        }
+        sink(Row {
+            keys: vec![
+                Some(Prop::I64(t)),
+                Some(id_prop(&edge.src().id(), numeric_ids)),
+                Some(id_prop(&edge.dst().id(), numeric_ids)),
+                Some(Prop::Str(edge.layer_name()?)),
+            ],
+            props: edge.properties().temporal().iter_latest().collect(),
+        })?;
+    }
+    Ok(())
+}
+
+fn edge_deletion_rows<'graph, G: GraphViewOps<'graph>>(
+    graph: &G,
+    numeric_ids: bool,
+    sink: RowSink,
+) -> Result<(), GraphError> {
+    for edge in graph.edges().explode_layers() {
+        let layer = edge.layer_name()?;
+        for t in edge.deletions() {
+            sink(Row {
+                keys: vec![
+                    Some(Prop::I64(t)),
+                    Some(id_prop(&edge.src().id(), numeric_ids)),
+                    Some(id_prop(&edge.dst().id(), numeric_ids)),
+                    Some(Prop::Str(layer.clone())),
+                ],
+                props: vec![],
+            })?;
+        }
+    }
+    Ok(())
+}
+
+fn edge_property_rows<'graph, G: GraphViewOps<'graph>>(
+    graph: &G,
+    numeric_ids: bool,
+    sink: RowSink,
+) -> Result<(), GraphError> {
+    let const_prop_meta = graph.edge_meta().const_prop_meta();
+    for edge in graph.edges().explode_layers() {
+        // the constant properties of an edge view are merged over the layers of the graph, so
+        // they are looked up for the layer of the edge directly
+        let layer_ids = match edge.edge.layer() {
+            Some(layer_id) => LayerIds::One(*layer_id),
+            None => continue,
+        };
+        let props = graph
+            .const_edge_prop_ids(edge.edge, layer_ids.clone())
+            .filter_map(|id| {
+                graph
+                    .get_const_edge_prop(edge.edge, id, layer_ids.clone())
+                    .map(|value| (const_prop_meta.get_name(id).clone(), value))
+            })
+            .collect::<Vec<_>>();
+        if props.is_empty() {
+            continue;
+        }
+        sink(Row {
+            keys: vec![
+                Some(id_prop(&edge.src().id(), numeric_ids)),
+                Some(id_prop(&edge.dst().id(), numeric_ids)),
+                Some(Prop::Str(edge.layer_name()?)),
+            ],
+            props,
+        })?;
+    }
+    Ok(())
+}
+
+/// Write the rows produced by `rows` to the directory `dir`.
+///
+/// The table has the key columns `keys` and a column for each property in `prop_meta`. The types
+/// of nested properties are not known up front, so `rows` is run an additional time to infer them
+/// if the table has list or map properties.
+fn write_table(
+    dir: &Path,
+    keys: &[(&str, DataType)],
+    prop_meta: &PropMapper,
+    rows_per_file: usize,
+    rows: impl Fn(RowSink) -> Result<(), GraphError>,
+) -> Result<(), GraphError> {
+    let mut fields: Vec<_> = keys
+        .iter()
+        .map(|(name, data_type)| Field::new(*name, data_type.clone(), true))
+        .collect();
+    let mut nested = HashMap::new();
+    let prop_types = prop_meta.dtypes().clone();
+    for (id, name) in prop_meta.get_keys().iter().enumerate() {
+        if keys.iter().any(|(key, _)| *key == name.as_ref()) {
+            return Err(GraphError::ReservedColumnName(name.to_string()));
+        }
+        match prop_types.get(id).copied().unwrap_or_default() {
+            PropType::Empty => {}
+            PropType::List | PropType::Map => {
+                nested.insert(name.clone(), None);
+            }
+            dtype => fields.push(Field::new(name.as_ref(), scalar_data_type(dtype)?, true)),
+        }
+    }
+    if !nested.is_empty() {
+        rows(&mut |row| {
+            for (name, value) in row.props {
+                if let Some(data_type) = nested.get_mut(&name) {
+                    *data_type = merge_data_types(data_type.take(), nested_data_type(&value)?)?;
+                }
+            }
+            Ok(())
+        })?;
+        for (name, data_type) in nested {
+            if let Some(data_type) = data_type {
+                fields.push(Field::new(name.as_ref(), data_type, true));
+            }
+        }
+    }
+
+    let mut writer = TableWriter::new(dir, fields, keys.len(), rows_per_file)?;
+    rows(&mut |row| writer.push(row))?;
+    writer.finish()
+}
+
+/// Writes the rows of a table to files with at most `rows_per_file` rows, buffering at most
+/// [ROWS_PER_ROW_GROUP] rows in memory
+struct TableWriter {
+    dir: PathBuf,
+    schema: ArrowSchema,
+    num_keys: usize,
+    prop_columns: HashMap<ArcStr, usize>,
+    rows_per_file: usize,
+    columns: Vec<Vec<Option<Prop>>>,
+    num_buffered: usize,
+    file: Option<FileWriter<File>>,
+    rows_in_file: usize,
+    num_files: usize,
+}
+
+impl TableWriter {
+    fn new(
+        dir: &Path,
+        fields: Vec<Field>,
+        num_keys: usize,
+        rows_per_file: usize,
+    ) -> Result<Self, GraphError> {
+        fs::create_dir_all(dir)?;
+        // remove the files of a previous export so they are not picked up by the loaders
+        for entry in fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_some_and(|ext| ext == "parquet") {
+                fs::remove_file(path)?;
+            }
+        }
+        let prop_columns = fields
+            .iter()
+            .enumerate()
+            .skip(num_keys)
+            .map(|(idx, field)| (ArcStr::from(field.name.as_str()), idx))
+            .collect();
+        let columns = vec![vec![]; fields.len()];
+        Ok(Self {
+            dir: dir.to_path_buf(),
+            schema: ArrowSchema::from(fields),
+            num_keys,
+            prop_columns,
+            rows_per_file,
+            columns,
+            num_buffered: 0,
+            file: None,
+            rows_in_file: 0,
+            num_files: 0,
+        })
+    }
+
+    fn push(&mut self, row: Row) -> Result<(), GraphError> {
+        for (column, key) in self.columns.iter_mut().zip(row.keys) {
+            column.push(key);
+        }
+        for column in &mut self.columns[self.num_keys..] {
+            column.push(None);
+        }
+        for (name, value) in row.props {
+            // nested properties without a type have no values in the view
+            if let Some(&idx) = self.prop_columns.get(&name) {
+                self.columns[idx][self.num_buffered] = Some(value);
+            }
+        }
+        self.num_buffered += 1;
+        if self.num_buffered == ROWS_PER_ROW_GROUP.min(self.rows_per_file - self.rows_in_file) {
+            self.flush()?;
+        }
+        Ok(())
+    }
+
+    /// Write the buffered rows as a row group and start a new file once the current one is full
+    fn flush(&mut self) -> Result<(), GraphError> {
+        if self.num_buffered == 0 {
+            return Ok(());
+        }
+        let columns = self
+            .columns
+            .iter_mut()
+            .zip(self.schema.fields.iter())
+            .map(|(values, field)| prop_array(&field.data_type, std::mem::take(values)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let batch = RecordBatch::new(columns);
+        let encodings = self
+            .schema
+            .fields
+            .iter()
+            .map(|field| transverse(&field.data_type, |_| Encoding::Plain))
+            .collect();
+        let row_groups = RowGroupIterator::try_new(
+            std::iter::once(Ok(batch)),
+            &self.schema,
+            write_options(),
+            encodings,
+        )?;
+        let file = self.current_file()?;
+        for group in row_groups {
+            file.write(group?)?;
+        }
+
+        self.rows_in_file += self.num_buffered;
+        self.num_buffered = 0;
+        if self.rows_in_file == self.rows_per_file {
+            self.end_file()?;
+        }
+        Ok(())
+    }
+
+    fn current_file(&mut self) -> Result<&mut FileWriter<File>, GraphError> {
+        if self.file.is_none() {
+            let path = self.dir.join(part_name(self.num_files));
+            self.file = Some(FileWriter::try_new(
+                File::create(path)?,
+                self.schema.clone(),
+                write_options(),
+            )?);
+        }
+        Ok(self.file.as_mut().unwrap())
+    }
+
+    fn end_file(&mut self) -> Result<(), GraphError> {
+        if let Some(mut file) = self.file.take() {
+            file.end(None)?;
+            self.num_files += 1;
+            self.rows_in_file = 0;
+        }
+        Ok(())
+    }
+
+    fn finish(mut self) -> Result<(), GraphError> {
+        self.flush()?;
+        if self.num_files == 0 {
+            // still write a file so the dataset has a schema
+            self.current_file()?;
+        }
+        self.end_file()
+    }
+}
+
+fn part_name(part: usize) -> String {
+    format!("part-{part:05}.parquet")
+}
+
+fn write_options() -> WriteOptions {
+    WriteOptions {
+        write_statistics: true,
+        compression: CompressionOptions::Snappy,
+        version: Version::V2,
+        data_pagesize_limit: None,
+    }
+}
+
+/// The arrow type of the values of a property of type `dtype` that is not nested
+pub(crate) fn scalar_data_type(dtype: PropType) -> Result<DataType, GraphError> {
+    let data_type = match dtype {
+        PropType::Str => DataType::LargeUtf8,
+        PropType::Bool => DataType::Boolean,
+        PropType::U8 => DataType::UInt8,
+        PropType::U16 => DataType::UInt16,
+        PropType::U32 => DataType::UInt32,
+        PropType::U64 => DataType::UInt64,
+        PropType::I32 => DataType::Int32,
+        PropType::I64 => DataType::Int64,
+        PropType::F32 => DataType::Float32,
+        PropType::F64 => DataType::Float64,
+        PropType::DTime => DataType::Timestamp(TimeUnit::Millisecond, Some("UTC".to_string())),
+        PropType::NDTime => DataType::Timestamp(TimeUnit::Millisecond, None),
+        _ => return Err(GraphError::UnsupportedDataType),
+    };
+    Ok(data_type)
+}
+
+/// The arrow type of a property value, `None` if the value does not determine it (empty lists
+/// and maps)
+pub(crate) fn nested_data_type(value: &Prop) -> Result<Option<DataType>, GraphError> {
+    match value {
+        Prop::List(values) => {
+            let mut item_type = None;
+            for value in values.iter() {
+                item_type = merge_data_types(item_type, nested_data_type(value)?)?;
+            }
+            Ok(item_type.map(|item_type| ListArray::<i64>::default_datatype(item_type)))
+        }
+        Prop::Map(values) => {
+            let mut fields = values
+                .iter()
+                .filter_map(|(key, value)| {
+                    nested_data_type(value)
+                        .map(|data_type| {
+                            data_type.map(|data_type| Field::new(key.as_ref(), data_type, true))
+                        })
+                        .transpose()
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            fields.sort_by(|a, b| a.name.cmp(&b.name));
+            Ok((!fields.is_empty()).then_some(DataType::Struct(fields)))
+        }
+        value => scalar_data_type(value.dtype()).map(Some),
+    }
+}
+
+/// Combine the types of two values of the same property, lists combine the types of their items
+/// and maps the fields for their keys
+pub(crate) fn merge_data_types(
+    left: Option<DataType>,
+    right: Option<DataType>,
+) -> Result<Option<DataType>, GraphError> {
+    let (left, right) = match (left, right) {
+        (None, data_type) | (data_type, None) => return Ok(data_type),
+        (Some(left), Some(right)) => (left, right),
+    };
+    match (left, right) {
+        (DataType::LargeList(left), DataType::LargeList(right)) => {
+            let item_type = merge_data_types(
+                Some(left.data_type().clone()),
+                Some(right.data_type().clone()),
+            )?;
+            Ok(item_type.map(|item_type| ListArray::<i64>::default_datatype(item_type)))
+        }
+        (DataType::Struct(left), DataType::Struct(right)) => {
+            let mut fields: BTreeMap<String, Option<DataType>> = left
+                .into_iter()
+                .map(|field| (field.name, Some(field.data_type)))
+                .collect();
+            for field in right {
+                let data_type = fields.remove(&field.name).flatten();
+                fields.insert(
+                    field.name,
+                    merge_data_types(data_type, Some(field.data_type))?,
+                );
+            }
+            Ok(Some(DataType::Struct(
+                fields
+                    .into_iter()
+                    .filter_map(|(name, data_type)| {
+                        data_type.map(|data_type| Field::new(name, data_type, true))
+                    })
+                    .collect(),
+            )))
+        }
+        (left, right) if left == right => Ok(Some(left)),
+        _ => Err(GraphError::UnsupportedDataType),
+    }
+}
+
+fn primitive_column<T: NativeType>(
+    values: &[Option<Prop>],
+    f: impl Fn(&Prop) -> Option<T>,
+) -> PrimitiveArray<T> {
+    values
+        .iter()
+        .map(|value| value.as_ref().and_then(&f))
+        .collect()
+}
+
+/// Convert property values to an arrow array of type `data_type`
+pub(crate) fn prop_array(
+    data_type: &DataType,
+    values: Vec<Option<Prop>>,
+) -> Result<Box<dyn Array>, GraphError> {
+    let column: Box<dyn Array> = match data_type {
+        DataType::LargeUtf8 => Box::new(
+            values
+                .iter()
+                .map(|value| match value {
+                    Some(Prop::Str(v)) => Some(v.as_ref()),
+                    _ => None,
+                })
+                .collect::<Utf8Array<i64>>(),
+        ),
+        DataType::Boolean => Box::new(
+            values
+                .iter()
+                .map(|value| match value {
+                    Some(Prop::Bool(v)) => Some(*v),
+                    _ => None,
+                })
+                .collect::<BooleanArray>(),
+        ),
+        DataType::UInt8 => Box::new(primitive_column(&values, |p| match p {
+            Prop::U8(v) => Some(*v),
+            _ => None,
+        })),
+        DataType::UInt16 => Box::new(primitive_column(&values, |p| match p {
+            Prop::U16(v) => Some(*v),
+            _ => None,
+        })),
+        DataType::UInt32 => Box::new(primitive_column(&values, |p| match p {
+            Prop::U32(v) => Some(*v),
+            _ => None,
+        })),
+        DataType::UInt64 => Box::new(primitive_column(&values, |p| match p {
+            Prop::U64(v) => Some(*v),
+            _ => None,
+        })),
+        DataType::Int32 => Box::new(primitive_column(&values, |p| match p {
+            Prop::I32(v) => Some(*v),
+            _ => None,
+        })),
+        DataType::Int64 => Box::new(primitive_column(&values, |p| match p {
+            Prop::I64(v) => Some(*v),
+            _ => None,
+        })),
+        DataType::Float32 => Box::new(primitive_column(&values, |p| match p {
+            Prop::F32(v) => Some(*v),
+            _ => None,
+        })),
+        DataType::Float64 => Box::new(primitive_column(&values, |p| match p {
+            Prop::F64(v) => Some(*v),
+            _ => None,
+        })),
+        DataType::Timestamp(_, Some(_)) => Box::new(
+            primitive_column(&values, |p| match p {
+                Prop::DTime(v) => Some(v.timestamp_millis()),
+                _ => None,
+            })
+            .to(data_type.clone()),
+        ),
+        DataType::Timestamp(_, None) => Box::new(
+            primitive_column(&values, |p| match p {
+                Prop::NDTime(v) => Some(v.and_utc().timestamp_millis()),
+                _ => None,
+            })
+            .to(data_type.clone()),
+        ),
+        DataType::LargeList(item_field) => {
+            let mut lengths = Vec::with_capacity(values.len());
+            let mut validity = MutableBitmap::with_capacity(values.len());
+            let mut items = vec![];
+            for value in values {
+                match value {
+                    Some(Prop::List(list)) => {
+                        lengths.push(list.len());
+                        validity.push(true);
+                        items.extend(list.iter().cloned().map(Some));
+                    }
+                    _ => {
+                        lengths.push(0);
+                        validity.push(false);
+                    }
+                }
+            }
+            Box::new(ListArray::<i64>::try_new(
+                data_type.clone(),
+                Offsets::<i64>::try_from_lengths(lengths.into_iter())?.into(),
+                prop_array(item_field.data_type(), items)?,
+                validity.into(),
+            )?)
+        }
+        DataType::Struct(fields) => {
+            let mut validity = MutableBitmap::with_capacity(values.len());
+            let mut field_values = vec![Vec::with_capacity(values.len()); fields.len()];
+            for value in values {
+                let map = match value {
+                    Some(Prop::Map(map)) => Some(map),
+                    _ => None,
+                };
+                validity.push(map.is_some());
+                for (field, field_values) in fields.iter().zip(field_values.iter_mut()) {
+                    field_values.push(
+                        map.as_ref()
+                            .and_then(|map| map.get(field.name.as_str()).cloned()),
+                    );
+                }
+            }
+            let arrays = fields
+                .iter()
+                .zip(field_values)
+                .map(|(field, values)| prop_array(field.data_type(), values))
+                .collect::<Result<Vec<_>, _>>()?;
+            Box::new(StructArray::try_new(
+                data_type.clone(),
+                arrays,
+                validity.into(),
+            )?)
+        }
+        _ => return Err(GraphError::UnsupportedDataType),
+    };
+    Ok(column)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        core::IntoPropMap,
+        db::graph::{graph::assert_graph_equal, views::deletion_graph::PersistentGraph},
+        io::parquet_loaders::*,
+        prelude::NO_PROPS,
+    };
+    use tempfile::TempDir;
+
+    fn load_graph(path: &Path, node_props: &[&str], edge_props: &[&str]) -> Graph {
+        let g = Graph::new();
+        load_nodes_from_parquet(
+            &g,
+            &path.join(NODES_DIR),
+            TIME_COL,
+            ID_COL,
+            None,
+            Some(NODE_TYPE_COL),
+            Some(node_props),
+            None,
+            None,
+        )
+        .unwrap();
+        load_edges_from_parquet(
+            &g,
+            path.join(EDGES_DIR),
+            TIME_COL,
+            SRC_COL,
+            DST_COL,
+            Some(edge_props),
+            None,
+            None,
+            None,
+            Some(LAYER_COL),
+        )
+        .unwrap();
+        g
+    }
+
+    #[test]
+    fn test_export_window_round_trip() {
+        let g = Graph::new();
+        g.add_node(0, 1, [("score", Prop::F64(0.5))], Some("person"))
+            .unwrap();
+        g.add_node(5, 1, [("score", Prop::F64(1.5))], None).unwrap();
+        g.add_node(20, 4, NO_PROPS, None).unwrap();
+        g.add_edge(1, 1, 2, [("weight", Prop::I64(1))], Some("a"))
+            .unwrap();
+        g.add_edge(2, 1, 2, [("weight", Prop::I64(2))], Some("b"))
+            .unwrap();
+        g.add_edge(3, 2, 3, [("name", Prop::str("x"))], None)
+            .unwrap();
+        g.add_edge(30, 3, 4, [("weight", Prop::I64(3))], None)
+            .unwrap();
+
+        let view = g.window(0, 10);
+        let dir = TempDir::new().unwrap();
+        view.to_parquet_partitioned(dir.path(), 2).unwrap();
+        assert_eq!(fs::read_dir(dir.path().join(EDGES_DIR)).unwrap().count(), 2);
+
+        let loaded = load_graph(dir.path(), &["score"], &["weight", "name"]);
+        assert_graph_equal(&view, &loaded);
+        assert_eq!(
+            loaded.node(1).unwrap().node_type().as_deref(),
+            Some("person")
+        );
+        assert_eq!(
+            loaded
+                .node(1)
+                .unwrap()
+                .properties()
+                .temporal()
+                .get("score")
+                .unwrap()
+                .values(),
+            vec![Prop::F64(0.5), Prop::F64(1.5)]
+        );
+        assert_eq!(
+            loaded
+                .layers("b")
+                .unwrap()
+                .edge(1, 2)
+                .unwrap()
+                .properties()
+                .get("weight"),
+            Some(Prop::I64(2))
+        );
+    }
+
+    #[test]
+    fn test_export_constant_properties_and_deletions() {
+        let g = PersistentGraph::new();
+        g.add_edge(0, "a", "b", NO_PROPS, Some("x")).unwrap();
+        g.add_edge(0, "a", "b", NO_PROPS, Some("y")).unwrap();
+        g.edge("a", "b")
+            .unwrap()
+            .add_constant_properties([("kind", "friend")], Some("x"))
+            .unwrap();
+        g.node("a")
+            .unwrap()
+            .add_constant_properties([("age", 42i64)])
+            .unwrap();
+        g.delete_edge(3, "a", "b", Some("y")).unwrap();
+
+        let dir = TempDir::new().unwrap();
+        g.to_parquet(dir.path()).unwrap();
+
+        let loaded = PersistentGraph::new();
+        load_edges_from_parquet(
+            &loaded,
+            dir.path().join(EDGES_DIR),
+            TIME_COL,
+            SRC_COL,
+            DST_COL,
+            None,
+            None,
+            None,
+            None,
+            Some(LAYER_COL),
+        )
+        .unwrap();
+        load_edge_deletions_from_parquet(
+            &loaded,
+            &dir.path().join(EDGE_DELETIONS_DIR),
+            TIME_COL,
+            SRC_COL,
+            DST_COL,
+            None,
+            Some(LAYER_COL),
+        )
+        .unwrap();
+        load_node_props_from_parquet(
+            &loaded,
+            &dir.path().join(NODE_PROPERTIES_DIR),
+            ID_COL,
+            None,
+            Some(NODE_TYPE_COL),
+            Some(&["age"]),
+            None,
+        )
+        .unwrap();
+        load_edge_props_from_parquet(
+            &loaded,
+            &dir.path().join(EDGE_PROPERTIES_DIR),
+            SRC_COL,
+            DST_COL,
+            Some(&["kind"]),
+            None,
+            None,
+            Some(LAYER_COL),
+        )
+        .unwrap();
+
+        assert_graph_equal(&g, &loaded);
+        let edge = loaded.edge("a", "b").unwrap();
+        assert_eq!(edge.layers("y").unwrap().deletions(), vec![3]);
+        assert_eq!(
+            edge.layers("x")
+                .unwrap()
+                .properties()
+                .constant()
+                .get("kind"),
+            Some(Prop::str("friend"))
+        );
+        assert_eq!(
+            edge.layers("y")
+                .unwrap()
+                .properties()
+                .constant()
+                .get("kind"),
+            None
+        );
+        assert_eq!(
+            loaded.node("a").unwrap().properties().constant().get("age"),
+            Some(Prop::I64(42))
+        );
+    }
+
+    #[test]
+    fn test_reserved_column_name() {
+        let g = Graph::new();
+        g.add_edge(0, 1, 2, [("layer", 1i64)], None).unwrap();
+        let dir = TempDir::new().unwrap();
+        assert!(matches!(
+            g.to_parquet(dir.path()),
+            Err(GraphError::ReservedColumnName(name)) if name == "layer"
+        ));
+    }
+
+    #[test]
+    fn test_export_list_and_map_properties() {
+        let g = Graph::new();
+        g.add_edge(
+            1,
+            1,
+            2,
+            [
+                ("tags", Prop::from(vec![Prop::str("a"), Prop::str("b")])),
+                (
+                    "meta",
+                    [("score", Prop::I64(1)), ("label", Prop::str("x"))].into_prop_map(),
+                ),
+            ],
+            None,
+        )
+        .unwrap();
+        g.add_edge(
+            2,
+            1,
+            2,
+            [("meta", [("score", Prop::I64(2))].into_prop_map())],
+            None,
+        )
+        .unwrap();
+        g.add_edge(3, 2, 3, [("tags", Prop::from(Vec::<Prop>::new()))], None)
+            .unwrap();
+
+        let dir = TempDir::new().unwrap();
+        g.to_parquet(dir.path()).unwrap();
+        let loaded = load_graph(dir.path(), &[], &["tags", "meta"]);
+
+        let edge = loaded.edge(1, 2).unwrap();
+        assert_eq!(
+            edge.properties().temporal().get("tags").unwrap().values(),
+            [Prop::from(vec![Prop::str("a"), Prop::str("b")])]
+        );
+        assert_eq!(
+            edge.properties().temporal().get("meta").unwrap().values(),
+            [
+                [("score", Prop::I64(1)), ("label", Prop::str("x"))].into_prop_map(),
+                [("score", Prop::I64(2))].into_prop_map()
+            ]
+        );
+        assert_eq!(
+            loaded.edge(2, 3).unwrap().properties().get("tags"),
+            Some(Prop::from(Vec::<Prop>::new()))
+        );
+    }
+
+    #[test]
+    fn test_export_persistent_window_keeps_update_times() {
+        let g = PersistentGraph::new();
+        g.add_node(2, "a", [("score", 1.0)], None).unwrap();
+        g.add_edge(1, "a", "b", [("weight", 1i64)], None).unwrap();
+        g.add_edge(15, "a", "b", [("weight", 2i64)], None).unwrap();
+        g.add_edge(10, "b", "c", NO_PROPS, None).unwrap();
+
+        let view = g.window(10, 20);
+        let dir = TempDir::new().unwrap();
+        view.to_parquet(dir.path()).unwrap();
+
+        let loaded = PersistentGraph::new();
+        load_nodes_from_parquet(
+            &loaded,
+            &dir.path().join(NODES_DIR),
+            TIME_COL,
+            ID_COL,
+            None,
+            Some(NODE_TYPE_COL),
+            Some(&["score"]),
+            None,
+            None,
+        )
+        .unwrap();
+        load_edges_from_parquet(
+            &loaded,
+            dir.path().join(EDGES_DIR),
+            TIME_COL,
+            SRC_COL,
+            DST_COL,
+            Some(&["weight"]),
+            None,
+            None,
+            None,
+            Some(LAYER_COL),
+        )
+        .unwrap();
+
+        // the edge alive at the start of the window keeps the time it was added at
+        let edge = loaded.edge("a", "b").unwrap();
+        assert_eq!(edge.history(), [1, 15]);
+        assert_eq!(
+            edge.properties().temporal().get("weight").unwrap().values(),
+            [Prop::I64(1), Prop::I64(2)]
+        );
+        assert_eq!(loaded.edge("b", "c").unwrap().history(), [10]);
+        let score = loaded
+            .node("a")
+            .unwrap()
+            .properties()
+            .temporal()
+            .get("score")
+            .unwrap();
+        assert_eq!(score.history(), [2]);
+        assert_eq!(score.values(), [Prop::F64(1.0)]);
+    }
+
+    #[test]
+    fn test_export_writes_row_groups_incrementally() {
+        let g = Graph::new();
+        let num_updates = ROWS_PER_ROW_GROUP + 10;
+        for t in 0..num_updates {
+            g.add_edge(
+                t as i64,
+                (t % 10) as u64,
+                ((t + 1) % 10) as u64,
+                NO_PROPS,
+                None,
+            )
+            .unwrap();
+        }
+
+        let dir = TempDir::new().unwrap();
+        g.to_parquet_partitioned(dir.path(), ROWS_PER_ROW_GROUP + 5)
+            .unwrap();
+        let edges_dir = dir.path().join(EDGES_DIR);
+        assert_eq!(fs::read_dir(&edges_dir).unwrap().count(), 2);
+        let first = polars_parquet::read::read_metadata(
+            &mut File::open(edges_dir.join(part_name(0))).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(first.num_rows, ROWS_PER_ROW_GROUP + 5);
+        assert_eq!(first.row_groups.len(), 2);
+
+        let loaded = load_graph(dir.path(), &[], &[]);
+        assert_eq!(loaded.count_temporal_edges(), num_updates);
+        assert_graph_equal(&g, &loaded);
+    }
+}
@@ -204,6 +204,58 @@ pub fn load_edge_deletions_from_parquet<
     Ok(())
 }
 
+/// Load edges that are alive for the intervals `[start, end)` given by the `start` and `end`
+/// columns, i.e., every row adds the edge at its start time and deletes it again at its end time.
+/// Rows without an end time only add the edge. Overlapping and adjacent intervals of the same edge
+/// and layer are merged, such that the edge is only deleted at the end of their union independent
+/// of the order of the rows.
+///
+/// Interval edges need deletions and are only supported on graphs with [DeletionOps], i.e., a
+/// [PersistentGraph](crate::db::graph::views::deletion_graph::PersistentGraph). Load them into
+/// `graph.persistent_graph()` to use them with an event graph.
+pub fn load_edge_intervals_from_parquet<
+    G: StaticGraphViewOps + InternalPropertyAdditionOps + InternalAdditionOps + DeletionOps,
+>(
+    graph: &G,
+    parquet_path: &Path,
+    start: &str,
+    end: &str,
+    src: &str,
+    dst: &str,
+    properties: Option<&[&str]>,
+    constant_properties: Option<&[&str]>,
+    shared_constant_properties: Option<&HashMap<String, Prop>>,
+    layer: Option<&str>,
+    layer_col: Option<&str>,
+) -> Result<(), GraphError> {
+    let mut cols_to_check = vec![src, dst, start, end];
+    cols_to_check.extend(properties.unwrap_or(&Vec::new()));
+    cols_to_check.extend(constant_properties.unwrap_or(&Vec::new()));
+    if let Some(ref layer_col) = layer_col {
+        cols_to_check.push(layer_col.as_ref());
+    }
+
+    for path in get_parquet_file_paths(parquet_path)? {
+        let df_view = process_parquet_file_to_df(path.as_path(), &cols_to_check)?;
+        df_view.check_cols_exist(&cols_to_check)?;
+        load_edge_intervals_from_df(
+            df_view,
+            start,
+            end,
+            src,
+            dst,
+            properties,
+            constant_properties,
+            shared_constant_properties,
+            layer,
+            layer_col,
+            graph,
+        )
+        .map_err(|e| GraphError::LoadFailure(format!("Failed to load graph {e:?}")))?;
+    }
+    Ok(())
+}
+
 pub(crate) fn process_parquet_file_to_df(
     parquet_file_path: &Path,
     col_names: &[&str],
@@ -273,7 +325,7 @@ fn read_parquet_file(
     Ok((names, reader, num_rows))
 }
 
-fn get_parquet_file_paths(parquet_path: &Path) -> Result<Vec<PathBuf>, GraphError> {
+pub(crate) fn get_parquet_file_paths(parquet_path: &Path) -> Result<Vec<PathBuf>, GraphError> {
     let mut parquet_files = Vec::new();
     if parquet_path.is_file() {
         parquet_files.push(parquet_path.to_path_buf());
@@ -0,0 +1,939 @@
+//! Continuous ingestion of JSON lines and CSV records into a live graph.
+//!
+//! Unlike [CsvLoader](crate::io::csv_loader::CsvLoader) and
+//! [JsonLinesLoader](crate::io::json_loader::JsonLinesLoader), which load static files once, a
+//! [StreamLoader] follows a source that keeps growing: a file that is appended to, a directory of
+//! rotating files, stdin or a Unix socket. Sources are read in bounded chunks, every complete
+//! record (a JSON line or a CSV record read with the [csv] reader) is applied to the graph with a
+//! user mapping, records that fail to parse are skipped, counted and logged.
+//!
+//! File sources remember the byte offset after the last applied record of each file. Offsets are
+//! keyed by the device and inode number of the file, so they follow files that are rotated by
+//! renaming them and a file that is replaced by a new one at the same path is read from the start.
+//! With a checkpoint file these offsets are persisted after every poll, so a restarted loader
+//! resumes where the previous one stopped. If the graph is cached (see [CacheOps]) its updates are
+//! written before the offsets, so records are applied at least once. Stdin and sockets can't be
+//! resumed.
+//!
+//! Records don't need to arrive in time order. Late records are inserted at their place in the
+//! history of the graph and records with the same timestamp keep the order in which they were
+//! applied (see [TimeIndexEntry](raphtory_api::core::storage::timeindex::TimeIndexEntry)).
+//!
+//! # Example
+//! ```no_run
+//! use raphtory::io::stream_loader::{StreamLoader, StreamSource};
+//! use raphtory::prelude::*;
+//! use serde::Deserialize;
+//! use std::sync::atomic::AtomicBool;
+//!
+//! #[derive(Deserialize)]
+//! struct Transfer {
+//!     time: i64,
+//!     from: String,
+//!     to: String,
+//!     amount: f64,
+//! }
+//!
+//! let g = Graph::new();
+//! let mut loader = StreamLoader::<Transfer>::new(StreamSource::File("transfers.jsonl".into()))
+//!     .with_checkpoint("transfers.offsets");
+//! loader
+//!     .run(
+//!         &g,
+//!         |t: Transfer, g: &Graph| {
+//!             g.add_edge(t.time, t.from, t.to, [("amount", t.amount)], None)?;
+//!             Ok(())
+//!         },
+//!         &AtomicBool::new(false),
+//!     )
+//!     .expect("Failed to ingest transfers");
+//! ```
+
+use crate::{core::utils::errors::GraphError, serialise::CacheOps};
+use csv::StringRecord;
+use regex::Regex;
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{
+    collections::{HashMap, HashSet},
+    error::Error,
+    fmt::{Display, Formatter},
+    fs::{self, File},
+    io::{self, BufReader, Read, Seek, SeekFrom},
+    marker::PhantomData,
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicBool, Ordering},
+    thread,
+    time::Duration,
+};
+
+#[cfg(unix)]
+use std::os::unix::net::UnixListener;
+
+const READ_BUFFER_SIZE: usize = 64 * 1024;
+
+#[derive(Debug)]
+pub enum StreamErr {
+    /// An IO error that occurred while reading the source or the checkpoint.
+    IoError(io::Error),
+    /// An error that occurred while reading or writing the checkpoint.
+    CheckpointError(serde_json::Error),
+    /// A GraphError that occurred while applying a record to the graph.
+    GraphError(GraphError),
+}
+
+impl From<io::Error> for StreamErr {
+    fn from(value: io::Error) -> Self {
+        Self::IoError(value)
+    }
+}
+
+impl From<serde_json::Error> for StreamErr {
+    fn from(value: serde_json::Error) -> Self {
+        Self::CheckpointError(value)
+    }
+}
+
+impl From<GraphError> for StreamErr {
+    fn from(value: GraphError) -> Self {
+        Self::GraphError(value)
+    }
+}
+
+impl Display for StreamErr {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.source() {
+            Some(error) => write!(f, "Stream loader failed with error: {}", error),
+            None => write!(f, "Stream loader failed with unknown error"),
+        }
+    }
+}
+
+impl Error for StreamErr {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            StreamErr::IoError(error) => Some(error),
+            StreamErr::CheckpointError(error) => Some(error),
+            StreamErr::GraphError(error) => Some(error),
+        }
+    }
+}
+
+/// Where the records of a [StreamLoader] come from
+#[derive(Debug, Clone)]
+pub enum StreamSource {
+    /// A file that is appended to. A file that shrinks is assumed to be truncated in place and a
+    /// different file at the path is assumed to replace it, both are read again from the start.
+    File(PathBuf),
+    /// A directory of rotating files, the files whose path matches the filter are read in the
+    /// order of their names
+    Dir {
+        path: PathBuf,
+        regex_filter: Option<Regex>,
+    },
+    /// The standard input of the process, ingestion finishes when it is closed
+    Stdin,
+    /// A Unix socket created at the path, every connection is a separate stream of records
+    #[cfg(unix)]
+    UnixSocket(PathBuf),
+}
+
+/// How the lines of a source are parsed into records
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RecordFormat {
+    /// Every line is a JSON object
+    JsonLines,
+    /// Every line is a CSV record, if `header` is set the first line of every file or
+    /// connection holds the column names
+    Csv { delimiter: u8, header: bool },
+}
+
+/// Number of records read from a source
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct StreamStats {
+    /// Records that were applied to the graph
+    pub records: usize,
+    /// Records that could not be parsed, each of them is logged as a warning
+    pub skipped: usize,
+}
+
+impl StreamStats {
+    pub fn is_empty(&self) -> bool {
+        self.records == 0 && self.skipped == 0
+    }
+
+    fn add(&mut self, other: StreamStats) {
+        self.records += other.records;
+        self.skipped += other.skipped;
+    }
+}
+
+/// Identity of a file that survives renames, so that the offset of a rotated file follows it
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
+enum FileKey {
+    /// Device and inode number of the file
+    Inode { dev: u64, ino: u64 },
+    /// The path of the file on platforms without inode numbers
+    Path(PathBuf),
+}
+
+#[cfg(unix)]
+fn file_key(_path: &Path, metadata: &fs::Metadata) -> FileKey {
+    use std::os::unix::fs::MetadataExt;
+    FileKey::Inode {
+        dev: metadata.dev(),
+        ino: metadata.ino(),
+    }
+}
+
+#[cfg(not(unix))]
+fn file_key(path: &Path, _metadata: &fs::Metadata) -> FileKey {
+    FileKey::Path(path.to_path_buf())
+}
+
+/// An open stream of records that can't be resumed, i.e., stdin or a socket connection
+struct LineStream {
+    reader: Box<dyn Read + Send>,
+    pending: Vec<u8>,
+    header: Option<StringRecord>,
+    closed: bool,
+}
+
+impl LineStream {
+    fn new(reader: impl Read + Send + 'static) -> Self {
+        Self {
+            reader: Box::new(reader),
+            pending: vec![],
+            header: None,
+            closed: false,
+        }
+    }
+
+    /// Read the next chunk of the stream, returns without data if a non-blocking stream is empty
+    fn read(&mut self) -> Result<(), StreamErr> {
+        let mut buf = vec![0; READ_BUFFER_SIZE];
+        match self.reader.read(&mut buf) {
+            Ok(0) => {
+                self.closed = true;
+                // the last record of a closed stream doesn't need a trailing newline
+                if !self.pending.is_empty() && !self.pending.ends_with(b"\n") {
+                    self.pending.push(b'\n');
+                }
+            }
+            Ok(n) => self.pending.extend_from_slice(&buf[..n]),
+            Err(err)
+                if matches!(
+                    err.kind(),
+                    io::ErrorKind::WouldBlock
+                        | io::ErrorKind::TimedOut
+                        | io::ErrorKind::Interrupted
+                ) => {}
+            Err(err) => return Err(err.into()),
+        }
+        Ok(())
+    }
+}
+
+/// A loader that continuously applies the records of a growing source to a graph.
+pub struct StreamLoader<REC> {
+    source: StreamSource,
+    format: RecordFormat,
+    checkpoint: Option<PathBuf>,
+    poll_interval: Duration,
+    follow: bool,
+    started: bool,
+    offsets: HashMap<FileKey, u64>,
+    headers: HashMap<FileKey, StringRecord>,
+    streams: Vec<LineStream>,
+    #[cfg(unix)]
+    listener: Option<UnixListener>,
+    stats: StreamStats,
+    _rec: PhantomData<REC>,
+}
+
+impl<REC: DeserializeOwned> StreamLoader<REC> {
+    /// Creates a new loader of JSON lines from `source`
+    pub fn new(source: StreamSource) -> Self {
+        Self {
+            source,
+            format: RecordFormat::JsonLines,
+            checkpoint: None,
+            poll_interval: Duration::from_millis(500),
+            follow: true,
+            started: false,
+            offsets: HashMap::new(),
+            headers: HashMap::new(),
+            streams: vec![],
+            #[cfg(unix)]
+            listener: None,
+            stats: StreamStats::default(),
+            _rec: PhantomData,
+        }
+    }
+
+    /// Set the format of the records
+    pub fn with_format(mut self, format: RecordFormat) -> Self {
+        self.format = format;
+        self
+    }
+
+    /// Persist the offsets of file sources to `path` and resume from them on start
+    pub fn with_checkpoint(mut self, path: impl Into<PathBuf>) -> Self {
+        self.checkpoint = Some(path.into());
+        self
+    }
+
+    /// Set how long [StreamLoader::run] waits for new records once the source is exhausted
+    pub fn with_poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+
+    /// If set to false [StreamLoader::run] returns once the records that are currently
+    /// available are applied instead of waiting for new ones
+    pub fn set_follow(mut self, follow: bool) -> Self {
+        self.follow = follow;
+        self
+    }
+
+    pub fn poll_interval(&self) -> Duration {
+        self.poll_interval
+    }
+
+    /// Number of lines read since the loader was created
+    pub fn stats(&self) -> StreamStats {
+        self.stats
+    }
+
+    /// Whether the source is closed and no more records can arrive
+    pub fn is_finished(&self) -> bool {
+        matches!(self.source, StreamSource::Stdin)
+            && self.started
+            && self.streams.iter().all(|stream| stream.closed)
+    }
+
+    /// Apply records to `g` until the source is finished, `stop` is set or, if the loader does
+    /// not follow the source, the available records are applied
+    pub fn run<G, F>(
+        &mut self,
+        g: &G,
+        loader: F,
+        stop: &AtomicBool,
+    ) -> Result<StreamStats, StreamErr>
+    where
+        G: CacheOps,
+        F: Fn(REC, &G) -> Result<(), GraphError>,
+    {
+        let mut stats = StreamStats::default();
+        while !stop.load(Ordering::Relaxed) && !self.is_finished() {
+            let polled = self.poll_once(g, &loader)?;
+            stats.add(polled);
+            if !self.follow {
+                break;
+            }
+            if polled.is_empty() {
+                thread::sleep(self.poll_interval);
+            }
+        }
+        Ok(stats)
+    }
+
+    /// Apply the records that are currently available to `g` and persist the offsets.
+    ///
+    /// Reading from stdin blocks until new data arrives.
+    pub fn poll_once<G, F>(&mut self, g: &G, loader: &F) -> Result<StreamStats, StreamErr>
+    where
+        G: CacheOps,
+        F: Fn(REC, &G) -> Result<(), GraphError>,
+    {
+        if !self.started {
+            self.start()?;
+        }
+        let mut stats = StreamStats::default();
+        let result = self.poll_source(g, loader, &mut stats);
+        // persist the progress made before an error as well
+        self.stats.add(stats);
+        flush(g)?;
+        self.write_checkpoint()?;
+        result.map(|_| stats)
+    }
+
+    fn start(&mut self) -> Result<(), StreamErr> {
+        if let Some(checkpoint) = &self.checkpoint {
+            if checkpoint.exists() {
+                let offsets: Vec<(FileKey, u64)> =
+                    serde_json::from_reader(BufReader::new(File::open(checkpoint)?))?;
+                self.offsets = offsets.into_iter().collect();
+            }
+        }
+        match &self.source {
+            StreamSource::Stdin => self.streams.push(LineStream::new(io::stdin())),
+            #[cfg(unix)]
+            StreamSource::UnixSocket(path) => {
+                if path.exists() {
+                    // left behind by a previous run
+                    fs::remove_file(path)?;
+                }
+                let listener = UnixListener::bind(path)?;
+                listener.set_nonblocking(true)?;
+                self.listener = Some(listener);
+            }
+            _ => {}
+        }
+        self.started = true;
+        Ok(())
+    }
+
+    fn poll_source<G, F>(
+        &mut self,
+        g: &G,
+        loader: &F,
+        stats: &mut StreamStats,
+    ) -> Result<(), StreamErr>
+    where
+        F: Fn(REC, &G) -> Result<(), GraphError>,
+    {
+        match self.source.clone() {
+            StreamSource::File(path) => {
+                if let Some(key) = self.poll_file(&path, g, loader, stats)? {
+                    // the file may have been replaced, the offsets of the old one are obsolete
+                    self.offsets.retain(|other, _| *other == key);
+                    self.headers.retain(|other, _| *other == key);
+                }
+                Ok(())
+            }
+            StreamSource::Dir { path, regex_filter } => {
+                let mut keys = HashSet::new();
+                for file in files_in_dir(&path, regex_filter.as_ref())? {
+                    keys.extend(self.poll_file(&file, g, loader, stats)?);
+                }
+                self.offsets.retain(|key, _| keys.contains(key));
+                self.headers.retain(|key, _| keys.contains(key));
+                Ok(())
+            }
+            StreamSource::Stdin => self.poll_streams(g, loader, stats),
+            #[cfg(unix)]
+            StreamSource::UnixSocket(_) => {
+                if let Some(listener) = &self.listener {
+                    loop {
+                        match listener.accept() {
+                            Ok((stream, _)) => {
+                                stream.set_nonblocking(true)?;
+                                self.streams.push(LineStream::new(stream));
+                            }
+                            Err(err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                            Err(err) => return Err(err.into()),
+                        }
+                    }
+                }
+                self.poll_streams(g, loader, stats)?;
+                self.streams.retain(|stream| !stream.closed);
+                Ok(())
+            }
+        }
+    }
+
+    /// Apply the new records of the file at `path`, returns the key of the file unless it no
+    /// longer exists
+    fn poll_file<G, F>(
+        &mut self,
+        path: &Path,
+        g: &G,
+        loader: &F,
+        stats: &mut StreamStats,
+    ) -> Result<Option<FileKey>, StreamErr>
+    where
+        F: Fn(REC, &G) -> Result<(), GraphError>,
+    {
+        let mut file = match File::open(path) {
+            Ok(file) => file,
+            // rotated away since the directory was listed, a later poll picks it up again
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+            Err(err) => return Err(err.into()),
+        };
+        let metadata = file.metadata()?;
+        let key = file_key(path, &metadata);
+        let len = metadata.len();
+        let mut offset = self.offsets.get(&key).copied().unwrap_or(0);
+        if len < offset {
+            // the file was truncated in place, start over
+            offset = 0;
+            self.headers.remove(&key);
+        }
+        if len == offset {
+            return Ok(Some(key));
+        }
+
+        let mut header = self.headers.remove(&key);
+        if header.is_none() && offset > 0 {
+            header = read_csv_header(self.format, &file)?;
+        }
+        file.seek(SeekFrom::Start(offset))?;
+        let (consumed, result) = apply_reader(
+            self.format,
+            &mut header,
+            file.take(len - offset),
+            g,
+            loader,
+            stats,
+        );
+        self.offsets.insert(key.clone(), offset + consumed);
+        if let Some(header) = header {
+            self.headers.insert(key.clone(), header);
+        }
+        result.map(|_| Some(key))
+    }
+
+    fn poll_streams<G, F>(
+        &mut self,
+        g: &G,
+        loader: &F,
+        stats: &mut StreamStats,
+    ) -> Result<(), StreamErr>
+    where
+        F: Fn(REC, &G) -> Result<(), GraphError>,
+    {
+        for stream in self.streams.iter_mut().filter(|stream| !stream.closed) {
+            stream.read()?;
+            let (consumed, result) = apply_records(
+                self.format,
+                &mut stream.header,
+                &stream.pending,
+                g,
+                loader,
+                stats,
+            );
+            stream.pending.drain(..consumed);
+            result?;
+        }
+        Ok(())
+    }
+
+    fn write_checkpoint(&self) -> Result<(), StreamErr> {
+        if let Some(checkpoint) = &self.checkpoint {
+            // write to a temporary file first so a crash never leaves a partial checkpoint
+            let mut tmp_path = checkpoint.as_os_str().to_owned();
+            tmp_path.push(".tmp");
+            let offsets: Vec<_> = self.offsets.iter().collect();
+            serde_json::to_writer(File::create(&tmp_path)?, &offsets)?;
+            fs::rename(&tmp_path, checkpoint)?;
+        }
+        Ok(())
+    }
+}
+
+/// Write the updates of `g` if it is cached
+fn flush<G: CacheOps>(g: &G) -> Result<(), StreamErr> {
+    match g.write_updates() {
+        Ok(()) | Err(GraphError::CacheNotInnitialised) => Ok(()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+fn files_in_dir(path: &Path, regex_filter: Option<&Regex>) -> Result<Vec<PathBuf>, StreamErr> {
+    let mut files = vec![];
+    for entry in fs::read_dir(path)? {
+        let path = entry?.path();
+        let accepted = regex_filter.map_or(true, |pattern| {
+            path.to_str()
+                .filter(|name| pattern.is_match(name))
+                .is_some()
+        });
+        if path.is_file() && accepted {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+fn csv_reader<R: Read>(delimiter: u8, reader: R) -> csv::Reader<R> {
+    csv::ReaderBuilder::new()
+        .has_headers(false)
+        .flexible(true)
+        .delimiter(delimiter)
+        .from_reader(reader)
+}
+
+/// The header of a CSV file that is resumed after its first record
+fn read_csv_header(
+    format: RecordFormat,
+    mut file: &File,
+) -> Result<Option<StringRecord>, StreamErr> {
+    let RecordFormat::Csv {
+        delimiter,
+        header: true,
+    } = format
+    else {
+        return Ok(None);
+    };
+    file.seek(SeekFrom::Start(0))?;
+    let header = csv_reader(delimiter, file)
+        .records()
+        .next()
+        .transpose()
+        .map_err(io::Error::from)?;
+    Ok(header)
+}
+
+fn skip_record(stats: &mut StreamStats, error: &dyn Display) {
+    stats.skipped += 1;
+    tracing::warn!("Skipped a record that failed to parse: {error}");
+}
+
+/// The bytes that are currently available from a source.
+///
+/// Reading past them fails instead of reporting the end of the input, so that the [csv] reader
+/// doesn't parse an incomplete record at the end as if it was complete.
+struct Available<'a>(&'a [u8]);
+
+impl Read for Available<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if self.0.is_empty() {
+            return Err(io::ErrorKind::WouldBlock.into());
+        }
+        self.0.read(buf)
+    }
+}
+
+/// Apply the records read from `reader` in chunks of at most [READ_BUFFER_SIZE] bytes.
+///
+/// Returns the number of bytes consumed (see [apply_records]), only an incomplete record at the
+/// end is kept in memory in addition to the current chunk.
+fn apply_reader<REC, G, F>(
+    format: RecordFormat,
+    header: &mut Option<StringRecord>,
+    mut reader: impl Read,
+    g: &G,
+    loader: &F,
+    stats: &mut StreamStats,
+) -> (u64, Result<(), StreamErr>)
+where
+    REC: DeserializeOwned,
+    F: Fn(REC, &G) -> Result<(), GraphError>,
+{
+    let mut consumed = 0;
+    let mut pending = vec![];
+    let mut buf = vec![0; READ_BUFFER_SIZE];
+    loop {
+        let n = match reader.read(&mut buf) {
+            Ok(0) => return (consumed, Ok(())),
+            Ok(n) => n,
+            Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+            Err(err) => return (consumed, Err(err.into())),
+        };
+        pending.extend_from_slice(&buf[..n]);
+        let (applied, result) = apply_records(format, header, &pending, g, loader, stats);
+        consumed += applied as u64;
+        pending.drain(..applied);
+        if result.is_err() {
+            return (consumed, result);
+        }
+    }
+}
+
+/// Apply the complete records at the start of `bytes` to `g`.
+///
+/// Returns the number of bytes consumed, which stops after the last applied record if the mapping
+/// fails and excludes a trailing record that is not complete yet.
+fn apply_records<REC, G, F>(
+    format: RecordFormat,
+    header: &mut Option<StringRecord>,
+    bytes: &[u8],
+    g: &G,
+    loader: &F,
+    stats: &mut StreamStats,
+) -> (usize, Result<(), StreamErr>)
+where
+    REC: DeserializeOwned,
+    F: Fn(REC, &G) -> Result<(), GraphError>,
+{
+    match format {
+        RecordFormat::JsonLines => apply_json_lines(bytes, g, loader, stats),
+        RecordFormat::Csv {
+            delimiter,
+            header: has_header,
+        } => apply_csv_records(delimiter, has_header, header, bytes, g, loader, stats),
+    }
+}
+
+fn apply_json_lines<REC, G, F>(
+    bytes: &[u8],
+    g: &G,
+    loader: &F,
+    stats: &mut StreamStats,
+) -> (usize, Result<(), StreamErr>)
+where
+    REC: DeserializeOwned,
+    F: Fn(REC, &G) -> Result<(), GraphError>,
+{
+    let mut consumed = 0;
+    while let Some(end) = bytes[consumed..].iter().position(|b| *b == b'\n') {
+        let line = &bytes[consumed..consumed + end];
+        if !line.iter().all(u8::is_ascii_whitespace) {
+            match serde_json::from_slice(line) {
+                Ok(record) => {
+                    if let Err(err) = loader(record, g) {
+                        return (consumed, Err(err.into()));
+                    }
+                    stats.records += 1;
+                }
+                Err(err) => skip_record(stats, &err),
+            }
+        }
+        consumed += end + 1;
+    }
+    (consumed, Ok(()))
+}
+
+/// Apply the CSV records in `bytes`, if `has_header` is set the first record is read into
+/// `header` unless it is read already
+fn apply_csv_records<REC, G, F>(
+    delimiter: u8,
+    has_header: bool,
+    header: &mut Option<StringRecord>,
+    bytes: &[u8],
+    g: &G,
+    loader: &F,
+    stats: &mut StreamStats,
+) -> (usize, Result<(), StreamErr>)
+where
+    REC: DeserializeOwned,
+    F: Fn(REC, &G) -> Result<(), GraphError>,
+{
+    let mut reader = csv_reader(delimiter, Available(bytes));
+    let mut record = StringRecord::new();
+    let mut consumed = 0;
+    loop {
+        let result = reader.read_record(&mut record);
+        let end = reader.position().byte() as usize;
+        match result {
+            // the end of the available bytes, the rest of the record is not there yet
+            Err(err) if matches!(err.kind(), csv::ErrorKind::Io(_)) => break,
+            Ok(false) => break,
+            Err(err) => skip_record(stats, &err),
+            Ok(true) if has_header && header.is_none() => *header = Some(record.clone()),
+            Ok(true) => match record.deserialize(header.as_ref()) {
+                Ok(rec) => {
+                    if let Err(err) = loader(rec, g) {
+                        return (consumed, Err(err.into()));
+                    }
+                    stats.records += 1;
+                }
+                Err(err) => skip_record(stats, &err),
+            },
+        }
+        consumed = end;
+    }
+    (consumed, Ok(()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::*;
+    use serde::Deserialize;
+    use std::{fs::OpenOptions, io::Write};
+    use tempfile::tempdir;
+
+    #[derive(Debug, Deserialize)]
+    struct TestRecord {
+        time: i64,
+        src: String,
+        dst: String,
+    }
+
+    fn add_edge(rec: TestRecord, g: &Graph) -> Result<(), GraphError> {
+        g.add_edge(rec.time, rec.src, rec.dst, NO_PROPS, None)?;
+        Ok(())
+    }
+
+    fn append(path: &Path, data: &str) {
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .unwrap()
+            .write_all(data.as_bytes())
+            .unwrap();
+    }
+
+    #[test]
+    fn test_tail_file_and_resume() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("edges.jsonl");
+        let checkpoint = dir.path().join("offsets.json");
+        append(
+            &file,
+            "{\"time\": 3, \"src\": \"a\", \"dst\": \"b\"}\nnot json\n{\"time\": 1, \"src\": \"b\",",
+        );
+
+        let g = Graph::new();
+        let mut loader = StreamLoader::new(StreamSource::File(file.clone()))
+            .with_checkpoint(&checkpoint)
+            .set_follow(false);
+        let stats = loader.run(&g, add_edge, &AtomicBool::new(false)).unwrap();
+        assert_eq!(
+            stats,
+            StreamStats {
+                records: 1,
+                skipped: 1
+            }
+        );
+        assert_eq!(g.count_edges(), 1);
+
+        // the incomplete line is applied once it is finished, out of order records are fine
+        append(
+            &file,
+            " \"dst\": \"c\"}\n{\"time\": 2, \"src\": \"c\", \"dst\": \"a\"}\n",
+        );
+        let stats = loader.poll_once(&g, &add_edge).unwrap();
+        assert_eq!(stats.records, 2);
+        assert_eq!(g.count_edges(), 3);
+        assert_eq!(g.earliest_time(), Some(1));
+
+        // a new loader resumes after the applied records
+        append(&file, "{\"time\": 4, \"src\": \"a\", \"dst\": \"d\"}\n");
+        let mut resumed = StreamLoader::new(StreamSource::File(file))
+            .with_checkpoint(&checkpoint)
+            .set_follow(false);
+        let stats = resumed.run(&g, add_edge, &AtomicBool::new(false)).unwrap();
+        assert_eq!(
+            stats,
+            StreamStats {
+                records: 1,
+                skipped: 0
+            }
+        );
+        assert_eq!(g.count_edges(), 4);
+        assert_eq!(g.count_temporal_edges(), 4);
+    }
+
+    #[test]
+    fn test_tail_csv_dir() {
+        let dir = tempdir().unwrap();
+        append(&dir.path().join("edges-0.csv"), "time,src,dst\n1,a,b\n");
+        append(&dir.path().join("edges-1.csv"), "time,src,dst\n2,b,c\n");
+        append(&dir.path().join("ignored.txt"), "time,src,dst\n3,c,d\n");
+
+        let g = Graph::new();
+        let source = StreamSource::Dir {
+            path: dir.path().to_path_buf(),
+            regex_filter: Some(Regex::new(r".+\.csv$").unwrap()),
+        };
+        let mut loader = StreamLoader::new(source).with_format(RecordFormat::Csv {
+            delimiter: b',',
+            header: true,
+        });
+        loader.poll_once(&g, &add_edge).unwrap();
+        assert_eq!(g.count_edges(), 2);
+
+        // new records in an older file and a newly rotated file are both picked up
+        append(&dir.path().join("edges-1.csv"), "3,c,a\n");
+        append(&dir.path().join("edges-2.csv"), "time,src,dst\n4,a,c\n");
+        loader.poll_once(&g, &add_edge).unwrap();
+        assert_eq!(g.count_edges(), 4);
+        assert_eq!(
+            loader.stats(),
+            StreamStats {
+                records: 4,
+                skipped: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_csv_record_split_across_polls() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("edges.csv");
+        append(&file, "time,src,dst\n1,\"a\nb\",c\nbroken\n2,\"d\ne");
+
+        let g = Graph::new();
+        let mut loader =
+            StreamLoader::new(StreamSource::File(file.clone())).with_format(RecordFormat::Csv {
+                delimiter: b',',
+                header: true,
+            });
+        loader.poll_once(&g, &add_edge).unwrap();
+        assert!(g.has_node("a\nb"));
+        assert_eq!(g.count_edges(), 1);
+
+        // the quoted newline is not the end of the record, it is applied once it is complete
+        append(&file, "\",f\n");
+        loader.poll_once(&g, &add_edge).unwrap();
+        assert!(g.has_edge("d\ne", "f"));
+        assert_eq!(
+            loader.stats(),
+            StreamStats {
+                records: 2,
+                skipped: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_replaced_file_is_read_from_start() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("edges.jsonl");
+        append(
+            &file,
+            "{\"time\": 1, \"src\": \"a\", \"dst\": \"b\"}\n{\"time\": 2, \"src\": \"b\", \"dst\": \"c\"}\n",
+        );
+
+        let g = Graph::new();
+        let mut loader = StreamLoader::new(StreamSource::File(file.clone()));
+        loader.poll_once(&g, &add_edge).unwrap();
+        assert_eq!(g.count_temporal_edges(), 2);
+
+        // the new file is larger than the offset into the old one
+        let new_file = dir.path().join("edges.jsonl.new");
+        append(
+            &new_file,
+            "{\"time\": 3, \"src\": \"c\", \"dst\": \"d\"}\n{\"time\": 4, \"src\": \"d\", \"dst\": \"e\"}\n{\"time\": 5, \"src\": \"e\", \"dst\": \"f\"}\n",
+        );
+        fs::rename(&new_file, &file).unwrap();
+        loader.poll_once(&g, &add_edge).unwrap();
+        assert_eq!(g.count_temporal_edges(), 5);
+        assert!(g.has_edge("c", "d"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_rotated_file_keeps_offset() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("edges.jsonl");
+        append(&file, "{\"time\": 1, \"src\": \"a\", \"dst\": \"b\"}\n");
+
+        let g = Graph::new();
+        let source = StreamSource::Dir {
+            path: dir.path().to_path_buf(),
+            regex_filter: Some(Regex::new(r"edges.*\.jsonl$").unwrap()),
+        };
+        let mut loader = StreamLoader::new(source);
+        loader.poll_once(&g, &add_edge).unwrap();
+
+        // the rotated file is renamed, its last record is written after the rename
+        let rotated = dir.path().join("edges-0.jsonl");
+        fs::rename(&file, &rotated).unwrap();
+        append(&rotated, "{\"time\": 2, \"src\": \"b\", \"dst\": \"c\"}\n");
+        append(&file, "{\"time\": 3, \"src\": \"c\", \"dst\": \"d\"}\n");
+        loader.poll_once(&g, &add_edge).unwrap();
+        assert_eq!(g.count_temporal_edges(), 3);
+        assert_eq!(loader.stats().records, 3);
+    }
+
+    #[test]
+    fn test_ingest_into_cached_graph() {
+        let dir = tempdir().unwrap();
+        let file = dir.path().join("edges.jsonl");
+        let cache = dir.path().join("graph");
+        append(&file, "{\"time\": 1, \"src\": \"a\", \"dst\": \"b\"}\n");
+
+        let g = Graph::new();
+        g.cache(&cache).unwrap();
+        let mut loader = StreamLoader::new(StreamSource::File(file)).set_follow(false);
+        loader.run(&g, add_edge, &AtomicBool::new(false)).unwrap();
+
+        // the records are written to the cache of the graph after every poll
+        assert_eq!(Graph::load_cached(&cache).unwrap().count_edges(), 1);
+    }
+}
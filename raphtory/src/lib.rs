@@ -116,7 +116,10 @@ pub mod prelude {
         core::{IntoProp, Prop, PropUnwrap},
         db::{
             api::{
-                mutation::{AdditionOps, DeletionOps, ImportOps, PropertyAdditionOps},
+                mutation::{
+                    AdditionOps, DeletionOps, HyperedgeAdditionOps, ImportOps,
+                    NodeMergeOps, PropertyAdditionOps, RemovalOps,
+                },
                 state::{AsOrderedNodeStateOps, NodeStateOps, OrderedNodeStateOps},
                 view::{
                     EdgeViewOps, GraphViewOps, Layer, LayerOps, NodeViewOps, ResetFilter, TimeOps,
@@ -127,6 +130,9 @@ pub mod prelude {
     };
     pub use raphtory_api::core::{entities::GID, input::input_node::InputNode};
 
+    #[cfg(feature = "arrow")]
+    pub use crate::io::parquet_export::ParquetExport;
+
     #[cfg(feature = "proto")]
     pub use crate::serialise::{CacheOps, StableDecode, StableEncode};
 }
@@ -0,0 +1,130 @@
+use crate::{
+    core::utils::errors::GraphError,
+    db::api::view::{EdgeDiff, GraphDiff, MaterializedGraph, NodeDiff},
+    python::utils::PyTime,
+};
+use pyo3::{exceptions::PyValueError, prelude::*};
+use raphtory_api::core::entities::GID;
+
+/// The changes between two graph views, as returned by `GraphView.diff`
+#[pyclass(name = "GraphDiff", frozen)]
+#[derive(Clone)]
+pub struct PyGraphDiff {
+    diff: GraphDiff,
+}
+
+impl From<GraphDiff> for PyGraphDiff {
+    fn from(diff: GraphDiff) -> Self {
+        Self { diff }
+    }
+}
+
+fn node_ids(nodes: &[NodeDiff]) -> Vec<GID> {
+    nodes.iter().map(|node| node.id.clone()).collect()
+}
+
+fn edge_ids(edges: &[EdgeDiff]) -> Vec<(GID, GID, String)> {
+    edges
+        .iter()
+        .map(|edge| (edge.src.clone(), edge.dst.clone(), edge.layer.clone()))
+        .collect()
+}
+
+#[pymethods]
+impl PyGraphDiff {
+    /// The ids of the nodes that were added
+    #[getter]
+    fn added_nodes(&self) -> Vec<GID> {
+        node_ids(&self.diff.added_nodes)
+    }
+
+    /// The ids of the nodes that were removed
+    #[getter]
+    fn removed_nodes(&self) -> Vec<GID> {
+        node_ids(&self.diff.removed_nodes)
+    }
+
+    /// The ids of the nodes with a changed node type or changed property values
+    #[getter]
+    fn changed_nodes(&self) -> Vec<GID> {
+        node_ids(&self.diff.changed_nodes)
+    }
+
+    /// The (src, dst, layer) of the edges that were added
+    #[getter]
+    fn added_edges(&self) -> Vec<(GID, GID, String)> {
+        edge_ids(&self.diff.added_edges)
+    }
+
+    /// The (src, dst, layer) of the edges that were removed
+    #[getter]
+    fn removed_edges(&self) -> Vec<(GID, GID, String)> {
+        edge_ids(&self.diff.removed_edges)
+    }
+
+    /// The (src, dst, layer) of the edges with changed property values
+    #[getter]
+    fn changed_edges(&self) -> Vec<(GID, GID, String)> {
+        edge_ids(&self.diff.changed_edges)
+    }
+
+    /// The layers that have edges after but not before the change
+    #[getter]
+    fn added_layers(&self) -> Vec<String> {
+        self.diff.added_layers.clone()
+    }
+
+    /// The layers that have edges before but not after the change
+    #[getter]
+    fn removed_layers(&self) -> Vec<String> {
+        self.diff.removed_layers.clone()
+    }
+
+    /// Check if there are no changes
+    fn is_empty(&self) -> bool {
+        self.diff.is_empty()
+    }
+
+    /// Apply the changes to `graph` at time `time`
+    ///
+    /// Removed nodes and edges are removed from `graph`. Nothing is changed if the diff does not
+    /// apply to `graph`, i.e., if a property value has a different type than in `graph`, a node
+    /// would change its type or a removed node or edge does not exist.
+    ///
+    /// Arguments:
+    ///     graph (Graph | PersistentGraph): The graph to update
+    ///     time (int | str | datetime): The time of the updates
+    fn apply(&self, graph: MaterializedGraph, time: PyTime) -> Result<(), GraphError> {
+        match graph {
+            MaterializedGraph::EventGraph(graph) => self.diff.apply(&graph, time),
+            MaterializedGraph::PersistentGraph(graph) => self.diff.apply(&graph, time),
+        }
+    }
+
+    /// Export all changes including the property values as a JSON string
+    fn to_json(&self) -> PyResult<String> {
+        self.diff
+            .to_json()
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    /// Load a diff from a JSON string created with `to_json`
+    #[staticmethod]
+    fn from_json(json: &str) -> PyResult<Self> {
+        GraphDiff::from_json(json)
+            .map(|diff| diff.into())
+            .map_err(|e| PyValueError::new_err(e.to_string()))
+    }
+
+    fn __repr__(&self) -> String {
+        format!(
+            "GraphDiff(added_nodes={}, removed_nodes={}, changed_nodes={}, added_edges={}, removed_edges={}, changed_edges={})",
+            self.diff.added_nodes.len(),
+            self.diff.removed_nodes.len(),
+            self.diff.changed_nodes.len(),
+            self.diff.added_edges.len(),
+            self.diff.removed_edges.len(),
+            self.diff.changed_edges.len()
+        )
+    }
+}
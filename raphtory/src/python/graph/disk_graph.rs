@@ -86,6 +86,10 @@ impl<'a> FromPyObject<'a> for ParquetLayerCols<'a> {
                 .get_item("time_col")
                 .and_then(|item| item.expect("time_col is required").extract::<&PyString>())
                 .and_then(|s| s.to_str())?,
+            deletions_dir: dict
+                .get_item("deletions_dir")?
+                .map(|item| item.extract::<&str>())
+                .transpose()?,
         })
     }
 }
@@ -205,6 +209,22 @@ impl PyDiskGraph {
         self.graph.merge_by_sorted_gids(&other.graph, graph_dir)
     }
 
+    /// Stage the updates of `batch` for this graph. The updates are only included in the graph
+    /// once it is compacted.
+    fn stage_graph(&self, batch: &PyGraph) -> Result<DiskGraphStorage, GraphError> {
+        self.graph.stage_graph(&batch.graph)
+    }
+
+    /// Whether updates were staged for this graph since it was last compacted
+    fn has_staged_appends(&self) -> Result<bool, GraphError> {
+        self.graph.has_staged_appends()
+    }
+
+    /// Merge this graph with all the updates staged for it into a new graph in `graph_dir`
+    fn compact(&self, graph_dir: &str) -> Result<DiskGraphStorage, GraphError> {
+        self.graph.compact(graph_dir)
+    }
+
     fn __repr__(&self) -> String {
         StructReprBuilder::new("DiskGraph")
             .add_field("number_of_nodes", self.graph.inner.num_nodes())
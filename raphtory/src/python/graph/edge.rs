@@ -250,6 +250,16 @@ impl PyEdge {
         self.edge.latest_time()
     }
 
+    /// Gets the total time the edge is alive in the current view, following the semantics of
+    /// persistent graphs (from an addition until the next deletion).
+    ///
+    /// Returns:
+    ///     (int) The duration of the edge or None if it stays alive without bound
+    #[getter]
+    pub fn duration(&self) -> Option<i64> {
+        self.edge.duration()
+    }
+
     /// Gets of latest datetime of an edge.
     ///
     /// Returns:
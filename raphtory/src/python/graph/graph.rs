@@ -8,16 +8,19 @@ use crate::{
     core::{entities::nodes::node_ref::NodeRef, utils::errors::GraphError},
     db::{
         api::view::internal::{CoreGraphOps, DynamicGraph, IntoDynamic, MaterializedGraph},
-        graph::{edge::EdgeView, node::NodeView, views::node_subgraph::NodeSubgraph},
+        graph::{
+            edge::EdgeView, hyperedge::HyperedgeView, node::NodeView,
+            views::node_subgraph::NodeSubgraph,
+        },
     },
     io::parquet_loaders::*,
     prelude::*,
     python::{
         graph::{
-            edge::PyEdge, graph_with_deletions::PyPersistentGraph, io::pandas_loaders::*,
-            node::PyNode, views::graph_view::PyGraphView,
+            edge::PyEdge, graph_with_deletions::PyPersistentGraph, hyperedge::PyParticipant,
+            io::pandas_loaders::*, node::PyNode, views::graph_view::PyGraphView,
         },
-        utils::PyTime,
+        utils::{PyMergePolicy, PyTime},
     },
     serialise::{StableDecode, StableEncode},
 };
@@ -250,6 +253,32 @@ impl PyGraph {
             .add_edge(timestamp, src, dst, properties.unwrap_or_default(), layer)
     }
 
+    /// Adds a new hyperedge connecting an arbitrary number of nodes to the graph.
+    ///
+    /// Arguments:
+    ///    timestamp (int, str, or datetime(utc)): The timestamp of the hyperedge.
+    ///    id (str or int): The unique id of the hyperedge.
+    ///    participants (list): The participating nodes, either as node ids or as (node id, role) tuples.
+    ///    properties (dict): The properties of the hyperedge, as a dict of string and properties (optional).
+    ///
+    /// Returns:
+    ///   Hyperedge - The added hyperedge
+    #[pyo3(signature = (timestamp, id, participants, properties = None))]
+    pub fn add_hyperedge(
+        &self,
+        timestamp: PyTime,
+        id: GID,
+        participants: Vec<PyParticipant>,
+        properties: Option<HashMap<String, Prop>>,
+    ) -> Result<HyperedgeView<Graph>, GraphError> {
+        self.graph.add_hyperedge(
+            timestamp,
+            id,
+            participants.iter().map(|p| p.as_participant()),
+            properties.unwrap_or_default(),
+        )
+    }
+
     /// Import a single node into the graph.
     ///
     /// This function takes a PyNode object and an optional boolean flag. If the flag is set to true,
@@ -323,6 +352,160 @@ impl PyGraph {
         self.graph.import_edges(edge_views, force)
     }
 
+    /// Merge this graph with `other` into a new graph
+    ///
+    /// Nodes are matched by id and edges by the ids of their endpoints, combining the histories
+    /// and layers of both graphs. Conflicting node types and constant property values are
+    /// resolved using `policy`, where this graph is the left and `other` the right side.
+    ///
+    /// Arguments:
+    ///     other (GraphView): The graph to merge with
+    ///     policy (str | Callable): "keep_left", "keep_right", "error" (the default) or a function
+    ///         `f(key, left, right)` returning the merged value, where `key` is None for node types
+    ///
+    /// Returns:
+    ///    Graph - The merged graph
+    #[pyo3(signature = (other, policy = None))]
+    fn merge(
+        &self,
+        other: &PyGraphView,
+        policy: Option<PyMergePolicy>,
+    ) -> Result<Graph, GraphError> {
+        let policy = policy.map(|policy| policy.0).unwrap_or_default();
+        self.graph.merge(&other.graph, &policy)
+    }
+
+    //******  Removal Functions  ******//
+
+    /// Remove a node together with all its edges
+    ///
+    /// Arguments:
+    ///     node (str or int): The id of the node
+    pub fn remove_node(&self, node: GID) -> Result<(), GraphError> {
+        self.graph.remove_node(node)
+    }
+
+    /// Remove an edge from `layer`, or from all its layers if `layer` is not set
+    ///
+    /// Arguments:
+    ///     src (str or int): The id of the source node
+    ///     dst (str or int): The id of the destination node
+    ///     layer (str): The layer to remove the edge from (optional)
+    #[pyo3(signature = (src, dst, layer = None))]
+    pub fn remove_edge(&self, src: GID, dst: GID, layer: Option<&str>) -> Result<(), GraphError> {
+        self.graph.remove_edge(src, dst, layer)
+    }
+
+    /// Remove the updates of a temporal node property in the interval `[start, end)`
+    ///
+    /// Arguments:
+    ///     node (str or int): The id of the node
+    ///     start (TimeInput): The start of the interval
+    ///     end (TimeInput): The end of the interval
+    ///     name (str): The name of the property, all temporal properties are affected if not set
+    #[pyo3(signature = (node, start, end, name = None))]
+    pub fn remove_node_property_updates(
+        &self,
+        node: GID,
+        start: PyTime,
+        end: PyTime,
+        name: Option<&str>,
+    ) -> Result<(), GraphError> {
+        self.graph
+            .remove_node_property_updates(node, start, end, name)
+    }
+
+    /// Remove the updates of a temporal edge property in the interval `[start, end)`
+    ///
+    /// Arguments:
+    ///     src (str or int): The id of the source node
+    ///     dst (str or int): The id of the destination node
+    ///     start (TimeInput): The start of the interval
+    ///     end (TimeInput): The end of the interval
+    ///     name (str): The name of the property, all temporal properties are affected if not set
+    ///     layer (str): Only remove updates in this layer (optional)
+    #[pyo3(signature = (src, dst, start, end, name = None, layer = None))]
+    pub fn remove_edge_property_updates(
+        &self,
+        src: GID,
+        dst: GID,
+        start: PyTime,
+        end: PyTime,
+        name: Option<&str>,
+        layer: Option<&str>,
+    ) -> Result<(), GraphError> {
+        self.graph
+            .remove_edge_property_updates(src, dst, start, end, name, layer)
+    }
+
+    /// Remove the updates of a temporal graph property in the interval `[start, end)`
+    ///
+    /// Arguments:
+    ///     start (TimeInput): The start of the interval
+    ///     end (TimeInput): The end of the interval
+    ///     name (str): The name of the property, all temporal properties are affected if not set
+    #[pyo3(signature = (start, end, name = None))]
+    pub fn remove_property_updates(
+        &self,
+        start: PyTime,
+        end: PyTime,
+        name: Option<&str>,
+    ) -> Result<(), GraphError> {
+        self.graph.remove_property_updates(start, end, name)
+    }
+
+    /// Drop all history before `time`
+    ///
+    /// Nodes and edges without updates at or after `time` are removed.
+    ///
+    /// Arguments:
+    ///     time (TimeInput): The new start of the graph
+    pub fn truncate_before(&self, time: PyTime) -> Result<(), GraphError> {
+        self.graph.truncate_before(time)
+    }
+
+    /// Merge node `merge` into node `keep`
+    ///
+    /// The history, properties and edges of `merge` are added to `keep` and the id of `merge`
+    /// resolves to `keep` afterwards. Constant properties that `keep` already has take precedence.
+    ///
+    /// Arguments:
+    ///     keep (str or int): The id of the node to keep
+    ///     merge (str or int): The id of the node to merge into `keep`
+    pub fn merge_nodes(&self, keep: GID, merge: GID) -> Result<(), GraphError> {
+        self.graph.merge_nodes(keep, merge)
+    }
+
+    /// Undo the merge of a node, restoring it with its edges and its id
+    ///
+    /// Arguments:
+    ///     node (str or int): The id of the merged node
+    pub fn unmerge_node(&self, node: GID) -> Result<(), GraphError> {
+        self.graph.unmerge_node(node)
+    }
+
+    /// The ids of the nodes that were merged into `node`
+    ///
+    /// Arguments:
+    ///     node (str or int): The id of the node
+    ///
+    /// Returns:
+    ///     list[str | int]: The aliases of the node
+    pub fn node_aliases(&self, node: GID) -> Result<Vec<GID>, GraphError> {
+        self.graph.node_aliases(node)
+    }
+
+    /// Resolve `id` to the id of the node it refers to, following merges
+    ///
+    /// Arguments:
+    ///     id (str or int): The node id or alias
+    ///
+    /// Returns:
+    ///     Optional[str | int]: The id of the node, or None if the node does not exist
+    pub fn canonical_id(&self, id: GID) -> Option<GID> {
+        self.graph.canonical_id(id)
+    }
+
     //FIXME: This is reimplemented here to get mutable views. If we switch the underlying graph to enum dispatch, this won't be necessary!
     /// Gets the node with the specified id
     ///
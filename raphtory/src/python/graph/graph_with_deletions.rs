@@ -12,15 +12,27 @@ use crate::{
             mutation::{AdditionOps, PropertyAdditionOps},
             view::internal::CoreGraphOps,
         },
-        graph::{edge::EdgeView, node::NodeView, views::deletion_graph::PersistentGraph},
+        graph::{
+            edge::EdgeView,
+            hyperedge::HyperedgeView,
+            node::NodeView,
+            views::{
+                active_interval_graph::{ActiveIntervalGraph, IntervalSemantics},
+                deletion_graph::PersistentGraph,
+            },
+        },
+    },
+    prelude::{
+        DeletionOps, GraphViewOps, HyperedgeAdditionOps, ImportOps, NodeMergeOps, RemovalOps,
     },
-    prelude::{DeletionOps, GraphViewOps, ImportOps},
     python::{
-        graph::{edge::PyEdge, node::PyNode, views::graph_view::PyGraphView},
-        utils::PyTime,
+        graph::{
+            edge::PyEdge, hyperedge::PyParticipant, node::PyNode, views::graph_view::PyGraphView,
+        },
+        utils::{PyMergePolicy, PyTime},
     },
 };
-use pyo3::prelude::*;
+use pyo3::{exceptions::PyValueError, prelude::*};
 use raphtory_api::core::{entities::GID, storage::arc_str::ArcStr};
 use std::{
     collections::HashMap,
@@ -219,6 +231,62 @@ impl PyPersistentGraph {
         self.graph.delete_edge(timestamp, src, dst, layer)
     }
 
+    /// Adds an edge that is alive for the interval `[start, end)`, i.e., the edge is added at
+    /// `start` and deleted again at `end`.
+    ///
+    /// Arguments:
+    ///    start (int): The time the edge is added.
+    ///    end (int): The time the edge is deleted, needs to be at least `start`.
+    ///    src (str or int): The id of the source node.
+    ///    dst (str or int): The id of the destination node.
+    ///    properties (dict): The properties of the edge, as a dict of string and properties
+    ///    layer (str): The layer of the edge.
+    ///
+    /// Returns:
+    ///   The added edge
+    #[pyo3(signature = (start, end, src, dst, properties = None, layer = None))]
+    pub fn add_interval_edge(
+        &self,
+        start: PyTime,
+        end: PyTime,
+        src: GID,
+        dst: GID,
+        properties: Option<HashMap<String, Prop>>,
+        layer: Option<&str>,
+    ) -> Result<EdgeView<PersistentGraph, PersistentGraph>, GraphError> {
+        self.graph
+            .add_interval_edge(start, end, src, dst, properties.unwrap_or_default(), layer)
+    }
+
+    /// Returns a view of the graph that only includes the edges that are active during `[start, end)`
+    ///
+    /// Arguments:
+    ///   start (int): The start of the interval.
+    ///   end (int): The end of the interval (exclusive).
+    ///   semantics (str): "overlap" to include edges that are alive at any point of the interval,
+    ///                    "containment" to only include edges that are alive for all of it. Defaults to "overlap".
+    ///
+    /// Returns:
+    ///   GraphView: The filtered view
+    #[pyo3(signature = (start, end, semantics = "overlap"))]
+    pub fn active_during(
+        &self,
+        start: PyTime,
+        end: PyTime,
+        semantics: &str,
+    ) -> PyResult<ActiveIntervalGraph<PersistentGraph>> {
+        let semantics = match semantics {
+            "overlap" => IntervalSemantics::Overlap,
+            "containment" => IntervalSemantics::Containment,
+            other => {
+                return Err(PyValueError::new_err(format!(
+                    "Unknown interval semantics '{other}', expected 'overlap' or 'containment'"
+                )))
+            }
+        };
+        Ok(self.graph.active_during(start, end, semantics))
+    }
+
     //FIXME: This is reimplemented here to get mutable views. If we switch the underlying graph to enum dispatch, this won't be necessary!
     /// Gets the node with the specified id
     ///
@@ -249,6 +317,32 @@ impl PyPersistentGraph {
         self.graph.edge(src, dst)
     }
 
+    /// Adds a new hyperedge connecting an arbitrary number of nodes to the graph.
+    ///
+    /// Arguments:
+    ///    timestamp (int, str, or datetime(utc)): The timestamp of the hyperedge.
+    ///    id (str or int): The unique id of the hyperedge.
+    ///    participants (list): The participating nodes, either as node ids or as (node id, role) tuples.
+    ///    properties (dict): The properties of the hyperedge, as a dict of string and properties (optional).
+    ///
+    /// Returns:
+    ///   Hyperedge - The added hyperedge
+    #[pyo3(signature = (timestamp, id, participants, properties = None))]
+    pub fn add_hyperedge(
+        &self,
+        timestamp: PyTime,
+        id: GID,
+        participants: Vec<PyParticipant>,
+        properties: Option<HashMap<String, Prop>>,
+    ) -> Result<HyperedgeView<PersistentGraph>, GraphError> {
+        self.graph.add_hyperedge(
+            timestamp,
+            id,
+            participants.iter().map(|p| p.as_participant()),
+            properties.unwrap_or_default(),
+        )
+    }
+
     /// Import a single node into the graph.
     ///
     /// This function takes a PyNode object and an optional boolean flag. If the flag is set to true,
@@ -322,6 +416,160 @@ impl PyPersistentGraph {
         self.graph.import_edges(edge_views, force)
     }
 
+    /// Merge this graph with `other` into a new graph
+    ///
+    /// Nodes are matched by id and edges by the ids of their endpoints, combining the histories
+    /// and layers of both graphs. Edge deletions of both graphs are preserved. Conflicting node types and constant property values are
+    /// resolved using `policy`, where this graph is the left and `other` the right side.
+    ///
+    /// Arguments:
+    ///     other (GraphView): The graph to merge with
+    ///     policy (str | Callable): "keep_left", "keep_right", "error" (the default) or a function
+    ///         `f(key, left, right)` returning the merged value, where `key` is None for node types
+    ///
+    /// Returns:
+    ///    PersistentGraph - The merged graph
+    #[pyo3(signature = (other, policy = None))]
+    fn merge(
+        &self,
+        other: &PyGraphView,
+        policy: Option<PyMergePolicy>,
+    ) -> Result<PersistentGraph, GraphError> {
+        let policy = policy.map(|policy| policy.0).unwrap_or_default();
+        self.graph.merge(&other.graph, &policy)
+    }
+
+    //******  Removal Functions  ******//
+
+    /// Remove a node together with all its edges
+    ///
+    /// Arguments:
+    ///     node (str or int): The id of the node
+    pub fn remove_node(&self, node: GID) -> Result<(), GraphError> {
+        self.graph.remove_node(node)
+    }
+
+    /// Remove an edge from `layer`, or from all its layers if `layer` is not set
+    ///
+    /// Arguments:
+    ///     src (str or int): The id of the source node
+    ///     dst (str or int): The id of the destination node
+    ///     layer (str): The layer to remove the edge from (optional)
+    #[pyo3(signature = (src, dst, layer = None))]
+    pub fn remove_edge(&self, src: GID, dst: GID, layer: Option<&str>) -> Result<(), GraphError> {
+        self.graph.remove_edge(src, dst, layer)
+    }
+
+    /// Remove the updates of a temporal node property in the interval `[start, end)`
+    ///
+    /// Arguments:
+    ///     node (str or int): The id of the node
+    ///     start (TimeInput): The start of the interval
+    ///     end (TimeInput): The end of the interval
+    ///     name (str): The name of the property, all temporal properties are affected if not set
+    #[pyo3(signature = (node, start, end, name = None))]
+    pub fn remove_node_property_updates(
+        &self,
+        node: GID,
+        start: PyTime,
+        end: PyTime,
+        name: Option<&str>,
+    ) -> Result<(), GraphError> {
+        self.graph
+            .remove_node_property_updates(node, start, end, name)
+    }
+
+    /// Remove the updates of a temporal edge property in the interval `[start, end)`
+    ///
+    /// Arguments:
+    ///     src (str or int): The id of the source node
+    ///     dst (str or int): The id of the destination node
+    ///     start (TimeInput): The start of the interval
+    ///     end (TimeInput): The end of the interval
+    ///     name (str): The name of the property, all temporal properties are affected if not set
+    ///     layer (str): Only remove updates in this layer (optional)
+    #[pyo3(signature = (src, dst, start, end, name = None, layer = None))]
+    pub fn remove_edge_property_updates(
+        &self,
+        src: GID,
+        dst: GID,
+        start: PyTime,
+        end: PyTime,
+        name: Option<&str>,
+        layer: Option<&str>,
+    ) -> Result<(), GraphError> {
+        self.graph
+            .remove_edge_property_updates(src, dst, start, end, name, layer)
+    }
+
+    /// Remove the updates of a temporal graph property in the interval `[start, end)`
+    ///
+    /// Arguments:
+    ///     start (TimeInput): The start of the interval
+    ///     end (TimeInput): The end of the interval
+    ///     name (str): The name of the property, all temporal properties are affected if not set
+    #[pyo3(signature = (start, end, name = None))]
+    pub fn remove_property_updates(
+        &self,
+        start: PyTime,
+        end: PyTime,
+        name: Option<&str>,
+    ) -> Result<(), GraphError> {
+        self.graph.remove_property_updates(start, end, name)
+    }
+
+    /// Drop all history before `time`
+    ///
+    /// Nodes and edges without updates at or after `time` are removed.
+    ///
+    /// Arguments:
+    ///     time (TimeInput): The new start of the graph
+    pub fn truncate_before(&self, time: PyTime) -> Result<(), GraphError> {
+        self.graph.truncate_before(time)
+    }
+
+    /// Merge node `merge` into node `keep`
+    ///
+    /// The history, properties and edges of `merge` are added to `keep` and the id of `merge`
+    /// resolves to `keep` afterwards. Constant properties that `keep` already has take precedence.
+    ///
+    /// Arguments:
+    ///     keep (str or int): The id of the node to keep
+    ///     merge (str or int): The id of the node to merge into `keep`
+    pub fn merge_nodes(&self, keep: GID, merge: GID) -> Result<(), GraphError> {
+        self.graph.merge_nodes(keep, merge)
+    }
+
+    /// Undo the merge of a node, restoring it with its edges and its id
+    ///
+    /// Arguments:
+    ///     node (str or int): The id of the merged node
+    pub fn unmerge_node(&self, node: GID) -> Result<(), GraphError> {
+        self.graph.unmerge_node(node)
+    }
+
+    /// The ids of the nodes that were merged into `node`
+    ///
+    /// Arguments:
+    ///     node (str or int): The id of the node
+    ///
+    /// Returns:
+    ///     list[str | int]: The aliases of the node
+    pub fn node_aliases(&self, node: GID) -> Result<Vec<GID>, GraphError> {
+        self.graph.node_aliases(node)
+    }
+
+    /// Resolve `id` to the id of the node it refers to, following merges
+    ///
+    /// Arguments:
+    ///     id (str or int): The node id or alias
+    ///
+    /// Returns:
+    ///     Optional[str | int]: The id of the node, or None if the node does not exist
+    pub fn canonical_id(&self, id: GID) -> Option<GID> {
+        self.graph.canonical_id(id)
+    }
+
     //******  Saving And Loading  ******//
 
     // Alternative constructors are tricky, see: https://gist.github.com/redshiftzero/648e4feeff3843ffd9924f13625f839c
@@ -579,6 +827,108 @@ impl PyPersistentGraph {
         )
     }
 
+    /// Load edges that are alive for the intervals `[start, end)` from a Pandas DataFrame into the graph.
+    ///
+    /// Every row adds the edge at its start time and deletes it again at its end time.
+    /// Rows without an end time only add the edge. Overlapping and adjacent intervals of the same edge
+    /// and layer are merged, such that the edge is only deleted at the end of their union independent of the row order.
+    ///
+    /// Arguments:
+    ///     df (Dataframe): The Pandas DataFrame containing the edges.
+    ///     start_col (str): The column name for the times the edges are added.
+    ///     end_col (str): The column name for the times the edges are deleted.
+    ///     src (str): The column name for the source node ids.
+    ///     dst (str): The column name for the destination node ids.
+    ///     properties (List[str]): List of edge property column names. Defaults to None. (optional)
+    ///     constant_properties (List[str]): List of constant edge property column names. Defaults to None. (optional)
+    ///     shared_constant_properties (dict): A dictionary of constant properties that will be added to every edge. Defaults to None. (optional)
+    ///     layer (str): A constant value to use as the layer for all edges (optional) Defaults to None. (cannot be used in combination with layer_col)
+    ///     layer_col (str): The edge layer col name in dataframe (optional) Defaults to None. (cannot be used in combination with layer)
+    /// Returns:
+    ///     None: If the operation is successful.
+    ///
+    /// Raises:
+    ///     GraphError: If the operation fails.
+    #[pyo3(signature = (df, start_col, end_col, src, dst, properties = None, constant_properties = None, shared_constant_properties = None, layer = None, layer_col = None))]
+    fn load_edge_intervals_from_pandas(
+        &self,
+        df: &PyAny,
+        start_col: &str,
+        end_col: &str,
+        src: &str,
+        dst: &str,
+        properties: Option<Vec<&str>>,
+        constant_properties: Option<Vec<&str>>,
+        shared_constant_properties: Option<HashMap<String, Prop>>,
+        layer: Option<&str>,
+        layer_col: Option<&str>,
+    ) -> Result<(), GraphError> {
+        load_edge_intervals_from_pandas(
+            self.graph.core_graph(),
+            df,
+            start_col,
+            end_col,
+            src,
+            dst,
+            properties.as_ref().map(|props| props.as_ref()),
+            constant_properties.as_ref().map(|props| props.as_ref()),
+            shared_constant_properties.as_ref(),
+            layer,
+            layer_col,
+        )
+    }
+
+    /// Load edges that are alive for the intervals `[start, end)` from a Parquet file into the graph.
+    ///
+    /// Every row adds the edge at its start time and deletes it again at its end time.
+    /// Rows without an end time only add the edge. Overlapping and adjacent intervals of the same edge
+    /// and layer are merged, such that the edge is only deleted at the end of their union independent of the row order.
+    ///
+    /// Arguments:
+    ///     parquet_path (str): Parquet file or directory of Parquet files path containing edges
+    ///     start_col (str): The column name for the times the edges are added.
+    ///     end_col (str): The column name for the times the edges are deleted.
+    ///     src (str): The column name for the source node ids.
+    ///     dst (str): The column name for the destination node ids.
+    ///     properties (List[str]): List of edge property column names. Defaults to None. (optional)
+    ///     constant_properties (List[str]): List of constant edge property column names. Defaults to None. (optional)
+    ///     shared_constant_properties (dict): A dictionary of constant properties that will be added to every edge. Defaults to None. (optional)
+    ///     layer (str): A constant value to use as the layer for all edges (optional) Defaults to None. (cannot be used in combination with layer_col)
+    ///     layer_col (str): The edge layer col name in dataframe (optional) Defaults to None. (cannot be used in combination with layer)
+    /// Returns:
+    ///     None: If the operation is successful.
+    ///
+    /// Raises:
+    ///     GraphError: If the operation fails.
+    #[pyo3(signature = (parquet_path, start_col, end_col, src, dst, properties = None, constant_properties = None, shared_constant_properties = None, layer = None, layer_col = None))]
+    fn load_edge_intervals_from_parquet(
+        &self,
+        parquet_path: PathBuf,
+        start_col: &str,
+        end_col: &str,
+        src: &str,
+        dst: &str,
+        properties: Option<Vec<&str>>,
+        constant_properties: Option<Vec<&str>>,
+        shared_constant_properties: Option<HashMap<String, Prop>>,
+        layer: Option<&str>,
+        layer_col: Option<&str>,
+    ) -> Result<(), GraphError> {
+        load_edge_intervals_from_parquet(
+            &self.graph,
+            parquet_path.as_path(),
+            start_col,
+            end_col,
+            src,
+            dst,
+            properties.as_ref().map(|props| props.as_ref()),
+            constant_properties.as_ref().map(|props| props.as_ref()),
+            shared_constant_properties.as_ref(),
+            layer,
+            layer_col,
+        )
+    }
+
     /// Load node properties from a Pandas DataFrame.
     ///
     /// Arguments:
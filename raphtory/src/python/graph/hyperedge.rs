@@ -0,0 +1,110 @@
+//! The hyperedge module contains the PyHyperedge class, which represents a multi-party event
+//! that connects an arbitrary number of nodes at a single point in time.
+use crate::{
+    db::{
+        api::{
+            properties::Properties,
+            view::{internal::DynamicGraph, IntoDynamic, StaticGraphViewOps},
+        },
+        graph::{hyperedge::HyperedgeView, node::NodeView},
+    },
+    python::types::repr::Repr,
+};
+use chrono::{DateTime, Utc};
+use pyo3::prelude::*;
+use raphtory_api::core::{entities::GID, storage::arc_str::ArcStr};
+
+/// A hyperedge connects an arbitrary number of nodes at a single point in time.
+/// Each participant can optionally take part in the hyperedge with a named role.
+#[pyclass(name = "Hyperedge", frozen)]
+#[derive(Clone)]
+pub struct PyHyperedge {
+    pub(crate) hyperedge: HyperedgeView<DynamicGraph>,
+}
+
+impl<G: StaticGraphViewOps + IntoDynamic> From<HyperedgeView<G>> for PyHyperedge {
+    fn from(value: HyperedgeView<G>) -> Self {
+        Self {
+            hyperedge: value.with_graph(value.graph.clone().into_dynamic()),
+        }
+    }
+}
+
+impl<G: StaticGraphViewOps + IntoDynamic> IntoPy<PyObject> for HyperedgeView<G> {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        PyHyperedge::from(self).into_py(py)
+    }
+}
+
+/// A participant of a new hyperedge, extracted from either a node id or a `(node id, role)` tuple
+pub struct PyParticipant {
+    node: GID,
+    role: Option<String>,
+}
+
+impl PyParticipant {
+    pub fn as_participant(&self) -> (&GID, Option<&str>) {
+        (&self.node, self.role.as_deref())
+    }
+}
+
+impl<'source> FromPyObject<'source> for PyParticipant {
+    fn extract(participant: &'source PyAny) -> PyResult<Self> {
+        match participant.extract::<(GID, Option<String>)>() {
+            Ok((node, role)) => Ok(Self { node, role }),
+            Err(_) => Ok(Self {
+                node: participant.extract()?,
+                role: None,
+            }),
+        }
+    }
+}
+
+#[pymethods]
+impl PyHyperedge {
+    /// The id of the hyperedge
+    #[getter]
+    pub fn id(&self) -> GID {
+        self.hyperedge.id()
+    }
+
+    /// The time of the hyperedge
+    #[getter]
+    pub fn time(&self) -> i64 {
+        self.hyperedge.time()
+    }
+
+    /// The time of the hyperedge as a datetime
+    #[getter]
+    pub fn date_time(&self) -> Option<DateTime<Utc>> {
+        self.hyperedge.date_time()
+    }
+
+    /// The participating nodes together with their role (or None if they do not have a role)
+    #[getter]
+    pub fn participants(&self) -> Vec<(NodeView<DynamicGraph>, Option<ArcStr>)> {
+        self.hyperedge.participants()
+    }
+
+    /// The distinct participating nodes
+    #[getter]
+    pub fn nodes(&self) -> Vec<NodeView<DynamicGraph>> {
+        self.hyperedge.nodes()
+    }
+
+    /// The properties of the hyperedge
+    #[getter]
+    pub fn properties(&self) -> Properties<HyperedgeView<DynamicGraph>> {
+        self.hyperedge.properties()
+    }
+
+    fn __repr__(&self) -> String {
+        self.repr()
+    }
+}
+
+impl Repr for PyHyperedge {
+    fn repr(&self) -> String {
+        format!("{:?}", self.hyperedge)
+    }
+}
@@ -193,6 +193,51 @@ pub fn load_edge_deletions_from_pandas(
     Ok(())
 }
 
+/// Load edges that are alive for the intervals `[start, end)` given by the `start` and `end`
+/// columns, see [crate::io::parquet_loaders::load_edge_intervals_from_parquet].
+pub fn load_edge_intervals_from_pandas(
+    graph: &GraphStorage,
+    df: &PyAny,
+    start: &str,
+    end: &str,
+    src: &str,
+    dst: &str,
+    properties: Option<&[&str]>,
+    constant_properties: Option<&[&str]>,
+    shared_constant_properties: Option<&HashMap<String, Prop>>,
+    layer: Option<&str>,
+    layer_col: Option<&str>,
+) -> Result<(), GraphError> {
+    Python::with_gil(|py| {
+        let mut cols_to_check = vec![src, dst, start, end];
+        cols_to_check.extend(properties.unwrap_or(&Vec::new()));
+        cols_to_check.extend(constant_properties.unwrap_or(&Vec::new()));
+        if let Some(ref layer_col) = layer_col {
+            cols_to_check.push(layer_col.as_ref());
+        }
+
+        let df_view = process_pandas_py_df(df, py, cols_to_check.clone())?;
+        df_view.check_cols_exist(&cols_to_check)?;
+        load_edge_intervals_from_df(
+            df_view,
+            start,
+            end,
+            src,
+            dst,
+            properties,
+            constant_properties,
+            shared_constant_properties,
+            layer,
+            layer_col,
+            graph.core_graph(),
+        )
+        .map_err(|e| GraphLoadException::new_err(format!("{:?}", e)))?;
+        Ok::<(), PyErr>(())
+    })
+    .map_err(|e| GraphError::LoadFailure(format!("Failed to load graph {e:?}")))?;
+    Ok(())
+}
+
 pub(crate) fn process_pandas_py_df<'a>(
     df: &'a PyAny,
     py: Python<'a>,
@@ -1,9 +1,11 @@
 pub mod algorithm_result;
+pub mod diff;
 #[cfg(feature = "storage")]
 pub mod disk_graph;
 pub mod edge;
 pub mod graph;
 pub mod graph_with_deletions;
+pub mod hyperedge;
 
 pub mod edges;
 #[cfg(feature = "search")]
@@ -17,6 +17,7 @@ use crate::{
             },
         },
         graph::{
+            hyperedge::HyperedgeView,
             node::NodeView,
             nodes::Nodes,
             path::{PathFromGraph, PathFromNode},
@@ -218,6 +219,22 @@ impl PyNode {
         self.node.out_degree()
     }
 
+    /// Get the hyperedges this node takes part in.
+    ///
+    /// Returns:
+    ///     A list of the hyperedges in the window of the graph that include this node.
+    pub fn hyperedges(&self) -> Vec<HyperedgeView<DynamicGraph>> {
+        self.node.hyperedges()
+    }
+
+    /// Get the number of hyperedges this node takes part in.
+    ///
+    /// Returns:
+    ///     The hyperedge degree of this node.
+    pub fn hyperedge_degree(&self) -> usize {
+        self.node.hyperedge_degree()
+    }
+
     /// Returns the history of a node, including node additions and changes made to node.
     ///
     /// Returns:
@@ -596,6 +613,14 @@ impl PyNodes {
         self.nodes.out_degree()
     }
 
+    /// Returns the number of hyperedges the nodes take part in
+    ///
+    /// Returns:
+    ///     An iterator of the hyperedge degree of the nodes
+    fn hyperedge_degree(&self) -> LazyNodeState<'static, usize, DynamicGraph> {
+        self.nodes.hyperedge_degree()
+    }
+
     pub fn __getitem__(&self, node: NodeRef) -> PyResult<NodeView<DynamicGraph, DynamicGraph>> {
         self.nodes
             .get(node)
@@ -10,31 +10,35 @@ use crate::{
             properties::Properties,
             view::{
                 internal::{DynamicGraph, IntoDynamic, MaterializedGraph},
-                LayerOps, StaticGraphViewOps,
+                ComputedPropertiesOps, LayerOps, StaticGraphViewOps,
             },
         },
         graph::{
             edge::EdgeView,
             edges::Edges,
             graph::graph_equal,
+            hyperedge::HyperedgeView,
             node::NodeView,
             nodes::Nodes,
             views::{
+                active_interval_graph::ActiveIntervalGraph,
+                computed_props_graph::ComputedPropsGraph, intersection_graph::IntersectionGraph,
                 layer_graph::LayeredGraph, node_subgraph::NodeSubgraph,
-                node_type_filtered_subgraph::TypeFilteredSubgraph, window_graph::WindowedGraph,
+                node_type_filtered_subgraph::TypeFilteredSubgraph, union_graph::UnionGraph,
+                window_graph::WindowedGraph,
             },
         },
     },
     prelude::*,
     python::{
-        graph::{edge::PyEdge, node::PyNode},
+        graph::{diff::PyGraphDiff, edge::PyEdge, node::PyNode},
         types::repr::{Repr, StructReprBuilder},
-        utils::PyTime,
+        utils::{PyComputedProp, PyTime},
     },
 };
 use chrono::prelude::*;
 use pyo3::prelude::*;
-use raphtory_api::core::storage::arc_str::ArcStr;
+use raphtory_api::core::{entities::GID, storage::arc_str::ArcStr};
 
 impl IntoPy<PyObject> for MaterializedGraph {
     fn into_py(self, py: Python<'_>) -> PyObject {
@@ -101,6 +105,34 @@ impl<G: StaticGraphViewOps + IntoDynamic> IntoPy<PyObject> for TypeFilteredSubgr
     }
 }
 
+impl<G: StaticGraphViewOps + IntoDynamic> IntoPy<PyObject> for ActiveIntervalGraph<G> {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        PyGraphView::from(self).into_py(py)
+    }
+}
+
+impl<G: StaticGraphViewOps + IntoDynamic> IntoPy<PyObject> for ComputedPropsGraph<G> {
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        PyGraphView::from(self).into_py(py)
+    }
+}
+
+impl<L: StaticGraphViewOps + IntoDynamic, R: StaticGraphViewOps + IntoDynamic> IntoPy<PyObject>
+    for UnionGraph<L, R>
+{
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        PyGraphView::from(self).into_py(py)
+    }
+}
+
+impl<L: StaticGraphViewOps + IntoDynamic, R: StaticGraphViewOps + IntoDynamic> IntoPy<PyObject>
+    for IntersectionGraph<L, R>
+{
+    fn into_py(self, py: Python<'_>) -> PyObject {
+        PyGraphView::from(self).into_py(py)
+    }
+}
+
 /// The API for querying a view of the graph in a read-only state
 #[pymethods]
 impl PyGraphView {
@@ -334,6 +366,154 @@ impl PyGraphView {
         self.graph.exclude_nodes(nodes)
     }
 
+    /// Returns a view of the nodes and edges that are in this view or in `other`
+    ///
+    /// Both views need to be views of the same graph with the same time semantics and time
+    /// bounds, window the result instead of the views to restrict it in time. Properties are
+    /// taken from this view.
+    ///
+    /// Arguments:
+    ///   other (GraphView): The graph view to combine with
+    ///
+    /// Returns:
+    ///    GraphView - Returns the union of both views
+    fn union(
+        &self,
+        other: &PyGraphView,
+    ) -> Result<UnionGraph<DynamicGraph, DynamicGraph>, GraphError> {
+        self.graph.union(&other.graph)
+    }
+
+    /// Returns a view of the nodes and edges that are in both this view and `other`
+    ///
+    /// Both views need to be views of the same graph with the same time semantics and time
+    /// bounds, window the result instead of the views to restrict it in time. Properties are
+    /// taken from this view.
+    ///
+    /// Arguments:
+    ///   other (GraphView): The graph view to combine with
+    ///
+    /// Returns:
+    ///    GraphView - Returns the intersection of both views
+    fn intersection(
+        &self,
+        other: &PyGraphView,
+    ) -> Result<IntersectionGraph<DynamicGraph, DynamicGraph>, GraphError> {
+        self.graph.intersection(&other.graph)
+    }
+
+    /// Returns a view with an additional computed node property
+    ///
+    /// The property is evaluated lazily for the current window of the view whenever it is
+    /// accessed. Expressions that refer to temporal properties are temporal themselves, all
+    /// other computed properties are constant.
+    ///
+    /// Arguments:
+    ///   name (str): The name of the property, it hides any stored node property with the same name
+    ///   prop (str | Callable[[Node], Any]): An arithmetic expression over the properties of the
+    ///       node like `"amount * fx_rate"` or a function that computes the value for a node
+    ///   cached (bool): Cache the values of the function for each node and window (defaults to False)
+    ///
+    /// Returns:
+    ///    GraphView - Returns the view with the computed property
+    #[pyo3(signature = (name, prop, cached = false))]
+    fn with_node_property(
+        &self,
+        name: &str,
+        prop: PyComputedProp,
+        cached: bool,
+    ) -> Result<ComputedPropsGraph<DynamicGraph>, GraphError> {
+        self.graph
+            .with_node_property(name, prop.into_computed(cached))
+    }
+
+    /// Returns a view with an additional computed edge property
+    ///
+    /// The property is evaluated lazily for the current window of the view whenever it is
+    /// accessed. Expressions that refer to temporal properties are temporal themselves, all
+    /// other computed properties are constant.
+    ///
+    /// Arguments:
+    ///   name (str): The name of the property, it hides any stored edge property with the same name
+    ///   prop (str | Callable[[Edge], Any]): An arithmetic expression over the properties of the
+    ///       edge like `"amount * fx_rate"` or a function that computes the value for an edge
+    ///   cached (bool): Cache the values of the function for each edge and window (defaults to False)
+    ///
+    /// Returns:
+    ///    GraphView - Returns the view with the computed property
+    #[pyo3(signature = (name, prop, cached = false))]
+    fn with_edge_property(
+        &self,
+        name: &str,
+        prop: PyComputedProp,
+        cached: bool,
+    ) -> Result<ComputedPropsGraph<DynamicGraph>, GraphError> {
+        self.graph
+            .with_edge_property(name, prop.into_computed(cached))
+    }
+
+    /// Returns the changes that turn this view into `other`
+    ///
+    /// Nodes are matched by id and edges by source, destination and layer. Temporal properties
+    /// are compared by their latest value, such that `g.at(t1).diff(g.at(t2))` returns the
+    /// changes between `t1` and `t2`.
+    ///
+    /// Arguments:
+    ///   other (GraphView): The graph view to compare with
+    ///
+    /// Returns:
+    ///    GraphDiff - The added, removed and changed nodes, edges, layers and properties
+    fn diff(&self, other: &PyGraphView) -> PyGraphDiff {
+        self.graph.diff(&other.graph).into()
+    }
+
+    /// Return the hyperedges in the window of the graph that have at least one participant in the graph
+    ///
+    /// Returns:
+    ///    list[Hyperedge] - the hyperedges of the graph
+    pub fn hyperedges(&self) -> Vec<HyperedgeView<DynamicGraph>> {
+        self.graph.hyperedges()
+    }
+
+    /// Gets the hyperedge with the specified id
+    ///
+    /// Arguments:
+    ///   id (str or int): the hyperedge id
+    ///
+    /// Returns:
+    ///   the hyperedge with the specified id, or None if the hyperedge is not part of the graph
+    pub fn hyperedge(&self, id: GID) -> Option<HyperedgeView<DynamicGraph>> {
+        self.graph.hyperedge(id)
+    }
+
+    /// Number of hyperedges in the graph
+    ///
+    /// Returns:
+    ///   the number of hyperedges in the graph
+    pub fn count_hyperedges(&self) -> usize {
+        self.graph.count_hyperedges()
+    }
+
+    /// Project the hyperedges of the graph to a new bipartite graph
+    ///
+    /// Each hyperedge becomes a node of type "hyperedge" with the properties of the hyperedge,
+    /// its participants are connected to it by an edge in the layer of their role.
+    ///
+    /// Returns:
+    ///    Graph - the bipartite projection
+    fn bipartite_projection(&self) -> Result<Graph, GraphError> {
+        self.graph.bipartite_projection()
+    }
+
+    /// Project the hyperedges of the graph to a new graph where the participants of each
+    /// hyperedge are connected by a clique of edges with the properties of the hyperedge
+    ///
+    /// Returns:
+    ///    Graph - the clique projection
+    fn clique_projection(&self) -> Result<Graph, GraphError> {
+        self.graph.clique_projection()
+    }
+
     /// Returns a 'materialized' clone of the graph view - i.e. a new graph with a copy of the data seen within the view instead of just a mask over the original graph
     ///
     /// Returns:
@@ -7,10 +7,12 @@ use crate::{
     python::{
         graph::{
             algorithm_result::AlgorithmResult,
+            diff::PyGraphDiff,
             edge::{PyDirection, PyEdge, PyMutableEdge},
             edges::PyEdges,
             graph::{PyGraph, PyGraphEncoder},
             graph_with_deletions::PyPersistentGraph,
+            hyperedge::PyHyperedge,
             index::GraphIndex,
             node::{PyMutableNode, PyNode, PyNodes},
             properties::{PyConstProperties, PyProperties, PyTemporalProp, PyTemporalProperties},
@@ -39,13 +41,15 @@ pub fn add_raphtory_classes(m: &PyModule) -> PyResult<()> {
         PyEdge,
         PyEdges,
         PyMutableEdge,
+        PyHyperedge,
         PyProperties,
         PyConstProperties,
         PyTemporalProperties,
         PyTemporalProp,
         PyDirection,
         AlgorithmResult,
-        GraphIndex
+        GraphIndex,
+        PyGraphDiff
     );
 
     #[cfg(feature = "storage")]
@@ -65,6 +65,16 @@ macro_rules! impl_nodeviewops {
             fn out_neighbours(&self) -> <$base_type as NodeViewOps<'static>>::PathType {
                 self.$field.out_neighbours()
             }
+
+            /// Get the nodes that share at least one hyperedge with this node.
+            ///
+            /// Returns:
+            ///
+            /// An iterator over the distinct co-participants of this node.
+            #[getter]
+            fn hyperedge_neighbours(&self) -> <$base_type as NodeViewOps<'static>>::PathType {
+                self.$field.hyperedge_neighbours()
+            }
         }
     };
 }
@@ -20,11 +20,16 @@ macro_rules! impl_serialise {
                 $crate::serialise::CacheOps::cache(&self.$field, path)
             }
 
-            /// Persist the new updates by appending them to the cache file.
+            /// Persist the new updates by appending them to the write-ahead log of the cache file.
             fn write_updates(&self) -> Result<(), GraphError> {
                 $crate::serialise::CacheOps::write_updates(&self.$field)
             }
 
+            #[doc = concat!(" Rewrite the cache file with the current state of the ", $name, " and clear its write-ahead log.")]
+            fn checkpoint(&self) -> Result<(), GraphError> {
+                $crate::serialise::CacheOps::checkpoint(&self.$field)
+            }
+
             #[doc = concat!(" Load ", $name, " from a file and initialise it as a cache file.")]
             ///
             /// Future updates are tracked. Use `write_updates` to persist them to the
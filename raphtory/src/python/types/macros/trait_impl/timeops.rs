@@ -58,11 +58,19 @@ macro_rules! impl_timeops {
             ///
             /// Arguments:
             ///     step (int | str): The step size of the window.
+            ///     timezone (str | None): Step in the wall clock time of this time zone (e.g., "Europe/London") instead of UTC.
+            ///     alignment (str | int | DateTime | None): "calendar" to align windows to the calendar (e.g., days to midnight and weeks to Mondays), or a time to anchor the windows to. Defaults to starting at the start of the timeline.
             ///
             /// Returns:
             ///     A `WindowSet` object.
-            fn expanding(&self, step: $crate::python::utils::PyInterval) -> Result<$crate::db::api::view::WindowSet<'static, $base_type>, $crate::core::utils::time::error::ParseTimeError> {
-                self.$field.expanding(step)
+            #[pyo3(signature = (step, timezone = None, alignment = None))]
+            fn expanding(
+                &self,
+                step: $crate::python::utils::PyInterval,
+                timezone: Option<&str>,
+                alignment: Option<$crate::python::utils::PyAlignment>,
+            ) -> Result<$crate::db::api::view::WindowSet<'static, $base_type>, $crate::core::utils::time::error::ParseTimeError> {
+                self.$field.expanding(step)?.with_options(timezone, alignment.map(|a| a.0))
             }
 
             /// Creates a `WindowSet` with the given `window` size and optional `step` using a rolling window.
@@ -72,15 +80,20 @@ macro_rules! impl_timeops {
             /// Arguments:
             ///     window (int | str): The size of the window.
             ///     step (int | str | None): The step size of the window. Defaults to `window`.
+            ///     timezone (str | None): Step and size the windows in the wall clock time of this time zone (e.g., "Europe/London") instead of UTC.
+            ///     alignment (str | int | DateTime | None): "calendar" to align windows to the calendar (e.g., days to midnight and weeks to Mondays), or a time to anchor the windows to. Defaults to starting at the start of the timeline.
             ///
             /// Returns:
             ///     A `WindowSet` object.
+            #[pyo3(signature = (window, step = None, timezone = None, alignment = None))]
             fn rolling(
                 &self,
                 window: $crate::python::utils::PyInterval,
                 step: Option<$crate::python::utils::PyInterval>,
+                timezone: Option<&str>,
+                alignment: Option<$crate::python::utils::PyAlignment>,
             ) -> Result<$crate::db::api::view::WindowSet<'static, $base_type>, $crate::core::utils::time::error::ParseTimeError> {
-                self.$field.rolling(window, step)
+                self.$field.rolling(window, step)?.with_options(timezone, alignment.map(|a| a.0))
             }
 
             #[doc = concat!(r" Create a view of the ", $name, r" including all events between `start` (inclusive) and `end` (exclusive)")]
@@ -6,13 +6,25 @@ use crate::{
     core::{
         entities::{nodes::node_ref::NodeRef, GidRef},
         storage::timeindex::AsTime,
-        utils::time::{error::ParseTimeError, Interval, IntoTime, TryIntoTime},
+        utils::{
+            errors::GraphError,
+            time::{error::ParseTimeError, Interval, IntoTime, TryIntoTime},
+        },
     },
-    db::api::view::*,
+    db::api::{
+        mutation::MergePolicy,
+        properties::computed::{ComputedProp, PropExpr},
+        view::*,
+    },
+    prelude::Prop,
     python::graph::node::PyNode,
 };
 use chrono::{DateTime, FixedOffset, NaiveDateTime, Utc};
-use pyo3::{exceptions::PyTypeError, prelude::*, types::PyDateTime};
+use pyo3::{
+    exceptions::{PyTypeError, PyValueError},
+    prelude::*,
+    types::PyDateTime,
+};
 use std::{future::Future, thread};
 
 pub mod errors;
@@ -143,6 +155,114 @@ impl TryFrom<PyInterval> for Interval {
     }
 }
 
+/// The alignment of windows, either `"unaligned"`, `"calendar"` or a time to anchor the windows to
+pub(crate) struct PyAlignment(pub(crate) Alignment);
+
+impl<'source> FromPyObject<'source> for PyAlignment {
+    fn extract(alignment: &'source PyAny) -> PyResult<Self> {
+        if let Ok(string) = alignment.extract::<&str>() {
+            match string {
+                "unaligned" => return Ok(PyAlignment(Alignment::Unaligned)),
+                "calendar" => return Ok(PyAlignment(Alignment::Calendar)),
+                _ => {}
+            }
+        }
+        let anchor = alignment.extract::<PyTime>().map_err(|_| {
+            let message =
+                format!("alignment '{alignment}' must be 'unaligned', 'calendar' or a time");
+            PyTypeError::new_err(message)
+        })?;
+        Ok(PyAlignment(Alignment::Anchor(anchor.into_time())))
+    }
+}
+
+/// The policy of `merge`, either `"keep_left"`, `"keep_right"`, `"error"` or a function
+/// `f(key, left, right)` returning the merged value, where `key` is `None` for node types
+pub(crate) struct PyMergePolicy(pub(crate) MergePolicy);
+
+impl<'source> FromPyObject<'source> for PyMergePolicy {
+    fn extract(policy: &'source PyAny) -> PyResult<Self> {
+        if let Ok(string) = policy.extract::<&str>() {
+            return match string {
+                "keep_left" => Ok(PyMergePolicy(MergePolicy::KeepLeft)),
+                "keep_right" => Ok(PyMergePolicy(MergePolicy::KeepRight)),
+                "error" => Ok(PyMergePolicy(MergePolicy::Error)),
+                _ => Err(PyValueError::new_err(format!(
+                    "Unknown merge policy '{string}', expected 'keep_left', 'keep_right' or 'error'"
+                ))),
+            };
+        }
+        if !policy.is_callable() {
+            let message = format!(
+                "merge policy '{policy}' must be 'keep_left', 'keep_right', 'error' or a function"
+            );
+            return Err(PyTypeError::new_err(message));
+        }
+        let resolver: PyObject = policy.into();
+        Ok(PyMergePolicy(MergePolicy::custom(move |conflict| {
+            Python::with_gil(|py| -> Result<Prop, GraphError> {
+                let merged = resolver.call1(
+                    py,
+                    (
+                        conflict.key.as_ref().map(|key| key.to_string()),
+                        conflict.left.clone(),
+                        conflict.right.clone(),
+                    ),
+                )?;
+                Ok(merged.extract::<Prop>(py)?)
+            })
+        })))
+    }
+}
+
+/// A computed property, either an expression string like `"amount * fx_rate"` or a function
+/// `f(entity)` that returns the value of the property for a node or edge
+pub(crate) enum PyComputedProp {
+    Expr(PropExpr),
+    Fn(PyObject),
+}
+
+impl<'source> FromPyObject<'source> for PyComputedProp {
+    fn extract(prop: &'source PyAny) -> PyResult<Self> {
+        if let Ok(expr) = prop.extract::<&str>() {
+            return Ok(PyComputedProp::Expr(expr.parse()?));
+        }
+        if !prop.is_callable() {
+            let message = format!("computed property '{prop}' must be an expression or a function");
+            return Err(PyTypeError::new_err(message));
+        }
+        Ok(PyComputedProp::Fn(prop.into()))
+    }
+}
+
+impl PyComputedProp {
+    /// Convert to a computed property, the function is called with the node or edge and the
+    /// property has no value if it raises an exception or returns `None`
+    pub(crate) fn into_computed<E: IntoPy<PyObject> + Clone>(
+        self,
+        cached: bool,
+    ) -> ComputedProp<E> {
+        match self {
+            PyComputedProp::Expr(expr) => ComputedProp::Expr(expr),
+            PyComputedProp::Fn(op) => {
+                let prop = ComputedProp::func(move |entity: &E| {
+                    Python::with_gil(|py| {
+                        op.call1(py, (entity.clone(),))
+                            .and_then(|value| value.extract::<Option<Prop>>(py))
+                            .ok()
+                            .flatten()
+                    })
+                });
+                if cached {
+                    prop.cached()
+                } else {
+                    prop
+                }
+            }
+        }
+    }
+}
+
 pub trait WindowSetOps {
     fn build_iter(&self) -> PyGenericIterator;
     fn time_index(&self, center: bool) -> PyGenericIterable;
@@ -3,11 +3,12 @@ use crate::{
         api::view::{
             internal::{DynamicGraph, IntoDynamic, OneHopFilter},
             time::internal::InternalTimeOps,
-            StaticGraphViewOps,
+            ComputedPropertiesOps, StaticGraphViewOps,
         },
         graph::views::{
-            layer_graph::LayeredGraph, node_subgraph::NodeSubgraph,
-            node_type_filtered_subgraph::TypeFilteredSubgraph, window_graph::WindowedGraph,
+            computed_props_graph::ComputedPropsGraph, layer_graph::LayeredGraph,
+            node_subgraph::NodeSubgraph, node_type_filtered_subgraph::TypeFilteredSubgraph,
+            window_graph::WindowedGraph,
         },
     },
     prelude::GraphViewOps,
@@ -77,6 +78,24 @@ impl<G: StaticGraphViewOps + IntoDynamic> DynamicIndexedGraph
     }
 }
 
+impl<G: StaticGraphViewOps + IntoDynamic> DynamicIndexedGraph
+    for ComputedPropsGraph<IndexedGraph<G>>
+{
+    fn into_dynamic_indexed(self) -> IndexedGraph<DynamicGraph> {
+        let g = self
+            .graph
+            .graph
+            .with_computed_properties((*self.props).clone());
+        IndexedGraph {
+            graph: g.into_dynamic(),
+            node_index: self.graph.node_index,
+            edge_index: self.graph.edge_index,
+            reader: self.graph.reader,
+            edge_reader: self.graph.edge_reader,
+        }
+    }
+}
+
 impl<G: StaticGraphViewOps + IntoDynamic> DynamicIndexedGraph for IndexedGraph<G> {
     fn into_dynamic_indexed(self) -> IndexedGraph<DynamicGraph> {
         IndexedGraph {
@@ -198,6 +198,11 @@ impl<'graph, G: GraphViewOps<'graph>> IndexedGraph<G> {
             .get_keys()
             .into_iter()
             .chain(g.node_meta().const_prop_meta().get_keys().into_iter())
+            .chain(
+                g.computed_properties()
+                    .into_iter()
+                    .flat_map(|props| props.node_prop_names().cloned()),
+            )
             .collect::<HashSet<_>>();
 
         for node in g.nodes() {
@@ -257,6 +262,11 @@ impl<'graph, G: GraphViewOps<'graph>> IndexedGraph<G> {
             .get_keys()
             .into_iter()
             .chain(g.edge_meta().const_prop_meta().get_keys())
+            .chain(
+                g.computed_properties()
+                    .into_iter()
+                    .flat_map(|props| props.edge_prop_names().cloned()),
+            )
             .collect::<HashSet<_>>();
 
         for edge in g.edges() {
@@ -7,7 +7,6 @@ use crate::{
         ProtoGraph,
     },
 };
-use std::path::Path;
 
 impl<G: StableEncode> StableEncode for IndexedGraph<G> {
     fn encode_to_proto(&self) -> ProtoGraph {
@@ -24,8 +23,8 @@ impl<'graph, G: StableDecode + GraphViewOps<'graph>> StableDecode for IndexedGra
 }
 
 impl<G: InternalCache> InternalCache for IndexedGraph<G> {
-    fn init_cache(&self, path: impl AsRef<Path>) -> Result<(), GraphError> {
-        self.graph.init_cache(path)
+    fn init_cache(&self, writer: GraphWriter) {
+        self.graph.init_cache(writer)
     }
 
     fn get_cache(&self) -> Option<&GraphWriter> {
@@ -1,52 +1,136 @@
 use crate::{
-    core::{utils::errors::GraphError, Prop, PropType},
+    core::{
+        entities::{graph::node_merge::NodeMerge, LayerIds},
+        utils::errors::GraphError,
+        Prop, PropType,
+    },
     db::{
-        api::{storage::storage::Storage, view::MaterializedGraph},
+        api::{
+            storage::{
+                graph::{edges::edge_storage_ops::EdgeStorageOps, storage_ops::GraphStorage},
+                storage::Storage,
+            },
+            view::MaterializedGraph,
+        },
         graph::views::deletion_graph::PersistentGraph,
     },
     prelude::Graph,
     serialise::{
+        proto::GraphType,
         serialise::{CacheOps, StableDecode, StableEncode},
+        wal::{read_log_prefix, write_snapshot, Wal, WalOptions},
         ProtoGraph,
     },
 };
-use parking_lot::Mutex;
+use parking_lot::{Mutex, RwLock, RwLockReadGuard};
 use prost::Message;
 use raphtory_api::core::{
-    entities::{GidRef, EID, VID},
+    entities::{GidRef, EID, ELID, VID},
     storage::{dict_mapper::MaybeNew, timeindex::TimeIndexEntry},
 };
 use std::{
     fmt::Debug,
-    fs::{File, OpenOptions},
-    io::Write,
-    mem,
-    ops::DerefMut,
-    path::Path,
+    fs, mem,
+    ops::{DerefMut, Range},
+    path::{Path, PathBuf},
 };
 
 #[derive(Debug)]
 pub struct GraphWriter {
-    writer: Mutex<File>,
+    path: PathBuf,
+    wal: Mutex<Wal>,
     proto_delta: Mutex<ProtoGraph>,
+    /// held while an update is applied to the graph and logged, checkpoints hold it exclusively
+    updating: RwLock<()>,
+    graph_type: GraphType,
 }
 
 impl GraphWriter {
-    pub fn new(file: File) -> Self {
+    pub(crate) fn new(path: impl AsRef<Path>, wal: Wal, graph_type: GraphType) -> Self {
         Self {
-            writer: Mutex::new(file),
+            path: path.as_ref().to_path_buf(),
+            wal: Mutex::new(wal),
             proto_delta: Default::default(),
+            updating: RwLock::new(()),
+            graph_type,
         }
     }
+
+    /// Append the pending updates to the write-ahead log
     pub fn write(&self) -> Result<(), GraphError> {
         let proto = mem::take(self.proto_delta.lock().deref_mut());
         let bytes = proto.encode_to_vec();
         if !bytes.is_empty() {
-            self.writer.lock().write_all(&bytes)?;
+            self.wal.lock().append(&bytes)?;
+        }
+        Ok(())
+    }
+
+    /// Sync the write-ahead log to disk regardless of the fsync policy
+    pub fn sync(&self) -> Result<(), GraphError> {
+        self.wal.lock().sync()
+    }
+
+    /// Append the pending updates and hard link the snapshot and write-ahead log to `target`,
+    /// returns the length of the log
+    pub fn link(&self, target: &Path) -> Result<u64, GraphError> {
+        let proto = mem::take(self.proto_delta.lock().deref_mut());
+        let bytes = proto.encode_to_vec();
+        // hold the lock so that no checkpoint replaces the files in between
+        let mut wal = self.wal.lock();
+        if !bytes.is_empty() {
+            wal.append(&bytes)?;
         }
+        wal.link(&self.path, target)
+    }
+
+    /// Whether the write-ahead log reached one of its checkpoint thresholds
+    pub fn needs_checkpoint(&self) -> bool {
+        self.wal.lock().needs_checkpoint()
+    }
+
+    /// Guard to hold while applying an update to the graph and logging it, such that a checkpoint
+    /// never encodes an update without its log entry
+    pub(crate) fn updating(&self) -> RwLockReadGuard<()> {
+        // updates are recursive if they log nested updates
+        self.updating.read_recursive()
+    }
+
+    /// Replace the snapshot with the graph encoded by `encode` and clear the write-ahead log.
+    ///
+    /// No updates are applied while the graph is encoded, such that every update is either part
+    /// of the snapshot or logged afterwards. The pending updates are part of the snapshot and are
+    /// dropped.
+    pub(crate) fn checkpoint(&self, encode: impl FnOnce() -> Vec<u8>) -> Result<(), GraphError> {
+        let _updating = self.updating.write();
+        let mut wal = self.wal.lock();
+        let snapshot = encode();
+        wal.checkpoint(&self.path, &snapshot)?;
+        *self.proto_delta.lock() = ProtoGraph::default();
         Ok(())
     }
 
+    /// Replace the snapshot with the current state of `graph` and clear the write-ahead log.
+    ///
+    /// Used for destructive changes that cannot be expressed as updates in the log.
+    pub(crate) fn rewrite(
+        &self,
+        graph: &GraphStorage,
+        schema: Option<String>,
+    ) -> Result<(), GraphError> {
+        self.checkpoint(|| {
+            let mut proto = graph.encode_to_proto();
+            proto.set_graph_type(self.graph_type);
+            proto.schema = schema;
+            proto.encode_to_vec()
+        })
+    }
+
+    /// Log a new JSON encoded schema, `None` removes the schema
+    pub fn set_schema(&self, schema: Option<String>) {
+        self.proto_delta.lock().schema = Some(schema.unwrap_or_default());
+    }
+
     #[inline]
     pub fn resolve_layer(&self, layer: Option<&str>, layer_id: MaybeNew<usize>) {
         layer_id.if_new(|id| {
@@ -183,24 +267,95 @@ impl GraphWriter {
     pub fn delete_edge(&self, edge: EID, t: TimeIndexEntry, layer: usize) {
         self.proto_delta.lock().del_edge(edge, layer, t)
     }
+
+    pub fn remove_node(&self, v: VID) {
+        self.proto_delta.lock().remove_node(v)
+    }
+
+    pub fn remove_edge(&self, edge: EID, layer_ids: &LayerIds) {
+        self.proto_delta.lock().remove_edge(edge, layer_ids)
+    }
+
+    pub fn remove_node_tprops(&self, v: VID, w: Range<i64>, prop_id: Option<usize>) {
+        self.proto_delta.lock().remove_node_tprops(v, w, prop_id)
+    }
+
+    pub fn remove_edge_tprops(
+        &self,
+        edge: EID,
+        layer_ids: &LayerIds,
+        w: Range<i64>,
+        prop_id: Option<usize>,
+    ) {
+        self.proto_delta
+            .lock()
+            .remove_edge_tprops(edge, layer_ids, w, prop_id)
+    }
+
+    pub fn remove_graph_tprops(&self, w: Range<i64>, prop_id: Option<usize>) {
+        self.proto_delta.lock().remove_graph_tprops(w, prop_id)
+    }
+
+    pub fn truncate_before(&self, t: i64, keep_alive: bool) {
+        self.proto_delta.lock().truncate_before(t, keep_alive)
+    }
+
+    pub fn resolve_hyperedge_role(&self, role: &str, role_id: MaybeNew<usize>) {
+        role_id.if_new(|id| self.proto_delta.lock().new_hyperedge_role(role, id));
+    }
+
+    pub fn resolve_hyperedge_property(
+        &self,
+        prop: &str,
+        prop_id: MaybeNew<usize>,
+        dtype: PropType,
+    ) {
+        prop_id.if_new(|id| self.proto_delta.lock().new_hyperedge_prop(prop, id, &dtype));
+    }
+
+    pub fn add_hyperedge(
+        &self,
+        t: TimeIndexEntry,
+        id: GidRef,
+        participants: &[(VID, Option<usize>)],
+        props: &[(usize, Prop)],
+    ) {
+        self.proto_delta.lock().add_hyperedge(
+            t,
+            id,
+            participants,
+            props.iter().map(|(id, prop)| (*id, prop)),
+        )
+    }
+
+    /// Log the merge of two nodes together with the edges that were created by the merge
+    pub(crate) fn merge_nodes(&self, graph: &GraphStorage, merge: &NodeMerge) {
+        let mut proto = self.proto_delta.lock();
+        for edge in merge.edges.iter().filter(|edge| edge.created) {
+            let entry = graph.edge_entry(ELID::new(edge.target, None));
+            let entry = entry.as_ref();
+            proto.new_edge(entry.src(), entry.dst(), edge.target);
+        }
+        proto.merge_nodes(merge);
+    }
+
+    pub fn unmerge_node(&self, gid: GidRef) {
+        self.proto_delta.lock().unmerge_node(gid)
+    }
 }
 
 pub(crate) trait InternalCache {
-    /// Initialise the cache by pointing it at a proto file.
-    /// Future updates will be appended to the cache.
-    fn init_cache(&self, path: impl AsRef<Path>) -> Result<(), GraphError>;
+    /// Initialise the cache with a writer for its snapshot and write-ahead log.
+    /// Future updates will be appended to the log.
+    fn init_cache(&self, writer: GraphWriter);
 
     /// Get the cache writer if it is initialised.
     fn get_cache(&self) -> Option<&GraphWriter>;
 }
 
 impl InternalCache for Storage {
-    fn init_cache(&self, path: impl AsRef<Path>) -> Result<(), GraphError> {
-        self.cache.get_or_try_init(|| {
-            let file = OpenOptions::new().append(true).open(path)?;
-            Ok::<_, GraphError>(GraphWriter::new(file))
-        })?;
-        Ok(())
+    fn init_cache(&self, writer: GraphWriter) {
+        self.cache.get_or_init(|| writer);
     }
 
     fn get_cache(&self) -> Option<&GraphWriter> {
@@ -209,8 +364,8 @@ impl InternalCache for Storage {
 }
 
 impl InternalCache for Graph {
-    fn init_cache(&self, path: impl AsRef<Path>) -> Result<(), GraphError> {
-        self.inner.init_cache(path)
+    fn init_cache(&self, writer: GraphWriter) {
+        self.inner.init_cache(writer)
     }
 
     fn get_cache(&self) -> Option<&GraphWriter> {
@@ -219,8 +374,8 @@ impl InternalCache for Graph {
 }
 
 impl InternalCache for PersistentGraph {
-    fn init_cache(&self, path: impl AsRef<Path>) -> Result<(), GraphError> {
-        self.0.init_cache(path)
+    fn init_cache(&self, writer: GraphWriter) {
+        self.0.init_cache(writer)
     }
 
     fn get_cache(&self) -> Option<&GraphWriter> {
@@ -229,10 +384,10 @@ impl InternalCache for PersistentGraph {
 }
 
 impl InternalCache for MaterializedGraph {
-    fn init_cache(&self, path: impl AsRef<Path>) -> Result<(), GraphError> {
+    fn init_cache(&self, writer: GraphWriter) {
         match self {
-            MaterializedGraph::EventGraph(g) => g.init_cache(path),
-            MaterializedGraph::PersistentGraph(g) => g.init_cache(path),
+            MaterializedGraph::EventGraph(g) => g.init_cache(writer),
+            MaterializedGraph::PersistentGraph(g) => g.init_cache(writer),
         }
     }
 
@@ -246,18 +401,64 @@ impl InternalCache for MaterializedGraph {
 
 impl<G: InternalCache + StableDecode + StableEncode> CacheOps for G {
     fn cache(&self, path: impl AsRef<Path>) -> Result<(), GraphError> {
-        self.encode(path.as_ref())?;
-        self.init_cache(path)
+        self.cache_with_options(path, WalOptions::default())
+    }
+
+    fn cache_with_options(
+        &self,
+        path: impl AsRef<Path>,
+        options: WalOptions,
+    ) -> Result<(), GraphError> {
+        let path = path.as_ref();
+        let proto = self.encode_to_proto();
+        let snapshot = proto.encode_to_vec();
+        write_snapshot(path, &snapshot)?;
+        let wal = Wal::create(path, &snapshot, options)?;
+        self.init_cache(GraphWriter::new(path, wal, proto.graph_type()));
+        Ok(())
     }
 
     fn write_updates(&self) -> Result<(), GraphError> {
         let cache = self.get_cache().ok_or(GraphError::CacheNotInnitialised)?;
-        cache.write()
+        cache.write()?;
+        if cache.needs_checkpoint() {
+            self.checkpoint()?;
+        }
+        Ok(())
+    }
+
+    fn checkpoint(&self) -> Result<(), GraphError> {
+        let cache = self.get_cache().ok_or(GraphError::CacheNotInnitialised)?;
+        cache.checkpoint(|| self.encode_to_vec())
+    }
+
+    fn link_cache(&self, path: impl AsRef<Path>) -> Result<u64, GraphError> {
+        let cache = self.get_cache().ok_or(GraphError::CacheNotInnitialised)?;
+        cache.link(path.as_ref())
+    }
+
+    fn load_linked_cache(path: impl AsRef<Path>, log_len: u64) -> Result<Self, GraphError> {
+        let path = path.as_ref();
+        let snapshot = fs::read(path)?;
+        let mut proto = ProtoGraph::decode(snapshot.as_slice())?;
+        read_log_prefix(path, &snapshot, log_len, |entry| Ok(proto.merge(entry)?))?;
+        Self::decode_from_proto(&proto)
     }
 
     fn load_cached(path: impl AsRef<Path>) -> Result<Self, GraphError> {
-        let graph = Self::decode(path.as_ref())?;
-        graph.init_cache(path)?;
+        Self::load_cached_with_options(path, WalOptions::default())
+    }
+
+    fn load_cached_with_options(
+        path: impl AsRef<Path>,
+        options: WalOptions,
+    ) -> Result<Self, GraphError> {
+        let path = path.as_ref();
+        let snapshot = fs::read(path)?;
+        let mut proto = ProtoGraph::decode(snapshot.as_slice())?;
+        let wal = Wal::open(path, &snapshot, options, |entry| Ok(proto.merge(entry)?))?;
+        let graph = Self::decode_from_proto(&proto)?;
+        graph.init_cache(GraphWriter::new(path, wal, proto.graph_type()));
         Ok(graph)
     }
 }
@@ -1,5 +1,6 @@
 pub(crate) mod incremental;
 mod serialise;
+mod wal;
 
 mod proto {
     include!(concat!(env!("OUT_DIR"), "/serialise.rs"));
@@ -7,3 +8,4 @@ mod proto {
 
 pub use proto::Graph as ProtoGraph;
 pub use serialise::{CacheOps, StableDecode, StableEncode};
+pub use wal::{restore_cache, wal_path, FsyncPolicy, WalOptions};
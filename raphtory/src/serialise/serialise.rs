@@ -1,8 +1,13 @@
 use crate::{
     core::{
         entities::{
-            edges::edge_store::EdgeStore, graph::tgraph::TemporalGraph,
+            edges::edge_store::EdgeStore,
+            graph::{
+                node_merge::{EdgeMerge, LayerMerge, NodeMerge},
+                tgraph::TemporalGraph,
+            },
             nodes::node_store::NodeStore,
+            LayerIds,
         },
         storage::timeindex::TimeIndexOps,
         utils::errors::GraphError,
@@ -10,14 +15,21 @@ use crate::{
     },
     db::{
         api::{
-            mutation::internal::{
-                InternalAdditionOps, InternalDeletionOps, InternalPropertyAdditionOps,
+            mutation::{
+                internal::{
+                    InternalAdditionOps, InternalDeletionOps, InternalHyperedgeOps,
+                    InternalPropertyAdditionOps, InternalRemovalOps,
+                },
+                GraphSchema, SchemaOps,
             },
             storage::graph::{
                 edges::edge_storage_ops::EdgeStorageOps, nodes::node_storage_ops::NodeStorageOps,
                 storage_ops::GraphStorage, tprop_storage_ops::TPropOps,
             },
-            view::{internal::CoreGraphOps, MaterializedGraph},
+            view::{
+                internal::{CoreGraphOps, EdgeFilterOps, NodeFilterOps},
+                MaterializedGraph,
+            },
         },
         graph::views::deletion_graph::PersistentGraph,
     },
@@ -25,9 +37,10 @@ use crate::{
     serialise::{
         proto,
         proto::{
-            graph_update::*, new_meta::*, new_node, new_node::Gid, prop,
-            prop_type::PropType as SPropType, GraphUpdate, NewEdge, NewMeta, NewNode,
+            graph_update::*, new_hyperedge, new_meta::*, new_node, new_node::Gid, prop,
+            prop_type::PropType as SPropType, GraphUpdate, NewEdge, NewHyperedge, NewMeta, NewNode,
         },
+        wal::WalOptions,
     },
 };
 use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, NaiveTime, Timelike};
@@ -41,7 +54,19 @@ use raphtory_api::core::{
     },
 };
 use rayon::prelude::*;
-use std::{borrow::Borrow, fs::File, io::Write, iter, path::Path, sync::Arc};
+use rustc_hash::FxHashSet;
+use std::{
+    borrow::Borrow,
+    fs::File,
+    io::Write,
+    iter,
+    ops::Range,
+    path::Path,
+    sync::{atomic::Ordering, Arc},
+};
+
+#[cfg(feature = "storage")]
+use crate::db::api::storage::graph::edges::edge_entry::EdgeStorageEntry;
 
 macro_rules! zip_tprop_updates {
     ($iter:expr) => {
@@ -81,16 +106,47 @@ pub trait StableDecode: Sized {
 }
 
 pub trait CacheOps: Sized {
-    /// Write graph to file and append future updates to the same file.
+    /// Write graph to file and append future updates to its write-ahead log (see
+    /// [wal_path](crate::serialise::wal_path)).
     ///
     /// If the file already exists, it's contents are overwritten
     fn cache(&self, path: impl AsRef<Path>) -> Result<(), GraphError>;
 
-    /// Persist the new updates by appending them to the cache file.
+    /// Like [CacheOps::cache] with custom options for the write-ahead log
+    fn cache_with_options(
+        &self,
+        path: impl AsRef<Path>,
+        options: WalOptions,
+    ) -> Result<(), GraphError>;
+
+    /// Persist the new updates by appending them to the write-ahead log.
+    ///
+    /// Checkpoints the graph if the log reached one of the thresholds of its options.
     fn write_updates(&self) -> Result<(), GraphError>;
 
-    /// Load graph from file and append future updates to the same file
+    /// Rewrite the cache file with the current state of the graph and clear the write-ahead log
+    fn checkpoint(&self) -> Result<(), GraphError>;
+
+    /// Append the pending updates to the write-ahead log and hard link the snapshot and the log to
+    /// `path` (the log to [wal_path](crate::serialise::wal_path) of `path`) without copying them.
+    ///
+    /// Returns the length of the log, which together with the links identifies the current state
+    /// of the graph (see [CacheOps::load_linked_cache] and
+    /// [restore_cache](crate::serialise::restore_cache)).
+    fn link_cache(&self, path: impl AsRef<Path>) -> Result<u64, GraphError>;
+
+    /// Load the state of a graph linked to `path` by [CacheOps::link_cache], replaying the first
+    /// `log_len` bytes of its write-ahead log. No cache is initialised for the loaded graph.
+    fn load_linked_cache(path: impl AsRef<Path>, log_len: u64) -> Result<Self, GraphError>;
+
+    /// Load graph from file, replay its write-ahead log and append future updates to the log
     fn load_cached(path: impl AsRef<Path>) -> Result<Self, GraphError>;
+
+    /// Like [CacheOps::load_cached] with custom options for the write-ahead log
+    fn load_cached_with_options(
+        path: impl AsRef<Path>,
+        options: WalOptions,
+    ) -> Result<Self, GraphError>;
 }
 
 fn as_proto_prop_type(p_type: &PropType) -> SPropType {
@@ -206,6 +262,22 @@ impl NewMeta {
         inner.id = id as u64;
         Self::new(Meta::NewNodeType(inner))
     }
+
+    fn new_hyperedge_role(role: &str, id: usize) -> Self {
+        let inner = NewHyperedgeRole {
+            name: role.to_string(),
+            id: id as u64,
+        };
+        Self::new(Meta::NewHyperedgeRole(inner))
+    }
+
+    fn new_hyperedge_prop(key: &str, id: usize, dtype: &PropType) -> Self {
+        let mut inner = NewHyperedgeProp::default();
+        inner.name = key.to_string();
+        inner.id = id as u64;
+        inner.set_p_type(as_proto_prop_type(dtype));
+        Self::new(Meta::NewHyperedgeProp(inner))
+    }
 }
 
 impl GraphUpdate {
@@ -303,6 +375,137 @@ impl GraphUpdate {
         };
         Self::new(Update::DelEdge(inner))
     }
+
+    fn remove_node(node_id: VID) -> Self {
+        let inner = RemoveNode {
+            id: node_id.as_u64(),
+        };
+        Self::new(Update::RemoveNode(inner))
+    }
+
+    fn remove_edge(eid: EID, layer_ids: &LayerIds) -> Self {
+        let inner = RemoveEdge {
+            eid: eid.as_u64(),
+            layers: Some(layer_ids.into()),
+        };
+        Self::new(Update::RemoveEdge(inner))
+    }
+
+    fn remove_node_tprops(node_id: VID, w: Range<i64>, prop_id: Option<usize>) -> Self {
+        let inner = RemoveNodeTProps {
+            id: node_id.as_u64(),
+            start: w.start,
+            end: w.end,
+            prop_id: prop_id.map(|id| id as u64),
+        };
+        Self::new(Update::RemoveNodeTprops(inner))
+    }
+
+    fn remove_edge_tprops(
+        eid: EID,
+        layer_ids: &LayerIds,
+        w: Range<i64>,
+        prop_id: Option<usize>,
+    ) -> Self {
+        let inner = RemoveEdgeTProps {
+            eid: eid.as_u64(),
+            layers: Some(layer_ids.into()),
+            start: w.start,
+            end: w.end,
+            prop_id: prop_id.map(|id| id as u64),
+        };
+        Self::new(Update::RemoveEdgeTprops(inner))
+    }
+
+    fn remove_graph_tprops(w: Range<i64>, prop_id: Option<usize>) -> Self {
+        let inner = RemoveGraphTProps {
+            start: w.start,
+            end: w.end,
+            prop_id: prop_id.map(|id| id as u64),
+        };
+        Self::new(Update::RemoveGraphTprops(inner))
+    }
+
+    fn truncate_before(t: i64, keep_alive: bool) -> Self {
+        let inner = TruncateBefore {
+            time: t,
+            keep_alive,
+        };
+        Self::new(Update::TruncateBefore(inner))
+    }
+
+    fn new_hyperedge(hyperedge: NewHyperedge) -> Self {
+        Self::new(Update::NewHyperedge(hyperedge))
+    }
+
+    fn node_merge(merge: &NodeMerge) -> Self {
+        Self::new(Update::NodeMerge(merge.into()))
+    }
+
+    fn unmerge_node(gid: GidRef) -> Self {
+        let gid = match gid {
+            GidRef::U64(id) => unmerge_node::Gid::GidU64(id),
+            GidRef::Str(name) => unmerge_node::Gid::GidStr(name.to_string()),
+        };
+        let inner = UnmergeNode { gid: Some(gid) };
+        Self::new(Update::UnmergeNode(inner))
+    }
+
+    /// Removals, merges and hyperedges cannot be reordered with the updates around them when the
+    /// log is replayed
+    fn is_ordered(&self) -> bool {
+        matches!(
+            self.update,
+            Some(
+                Update::RemoveNode(_)
+                    | Update::RemoveEdge(_)
+                    | Update::RemoveNodeTprops(_)
+                    | Update::RemoveEdgeTprops(_)
+                    | Update::RemoveGraphTprops(_)
+                    | Update::TruncateBefore(_)
+                    | Update::NodeMerge(_)
+                    | Update::UnmergeNode(_)
+                    | Update::NewHyperedge(_)
+            )
+        )
+    }
+}
+
+impl From<&LayerIds> for Layers {
+    fn from(layer_ids: &LayerIds) -> Self {
+        match layer_ids {
+            LayerIds::None => Layers::default(),
+            LayerIds::All => Layers {
+                all_layers: true,
+                layer_ids: vec![],
+            },
+            LayerIds::One(id) => Layers {
+                all_layers: false,
+                layer_ids: vec![*id as u64],
+            },
+            LayerIds::Multiple(ids) => Layers {
+                all_layers: false,
+                layer_ids: ids.iter().map(|id| *id as u64).collect(),
+            },
+        }
+    }
+}
+
+impl From<&Layers> for LayerIds {
+    fn from(layers: &Layers) -> Self {
+        if layers.all_layers {
+            LayerIds::All
+        } else if layers.layer_ids.is_empty() {
+            LayerIds::None
+        } else {
+            layers
+                .layer_ids
+                .iter()
+                .map(|id| *id as usize)
+                .collect_vec()
+                .into()
+        }
+    }
 }
 
 impl UpdateGraphCProps {
@@ -335,17 +538,159 @@ impl PropPair {
     }
 }
 
+fn as_proto_time(t: &TimeIndexEntry) -> proto::node_merge::TimeEntry {
+    proto::node_merge::TimeEntry {
+        time: t.t(),
+        secondary: t.i() as u64,
+    }
+}
+
+fn as_proto_prop_update((t, prop_id): &(TimeIndexEntry, usize)) -> proto::node_merge::PropUpdate {
+    proto::node_merge::PropUpdate {
+        time: t.t(),
+        secondary: t.i() as u64,
+        prop_id: *prop_id as u64,
+    }
+}
+
+impl From<&NodeMerge> for proto::NodeMerge {
+    fn from(merge: &NodeMerge) -> Self {
+        let edges = merge
+            .edges
+            .iter()
+            .map(|edge| proto::node_merge::EdgeMerge {
+                source: edge.source.as_u64(),
+                target: edge.target.as_u64(),
+                created: edge.created,
+                layers: edge
+                    .layers
+                    .iter()
+                    .map(|layer| proto::node_merge::LayerMerge {
+                        layer_id: layer.layer as u64,
+                        additions: layer.additions.iter().map(as_proto_time).collect(),
+                        deletions: layer.deletions.iter().map(as_proto_time).collect(),
+                        tprops: layer.tprops.iter().map(as_proto_prop_update).collect(),
+                        cprops: layer.cprops.iter().map(|id| *id as u64).collect(),
+                    })
+                    .collect(),
+            })
+            .collect();
+        proto::NodeMerge {
+            keep: merge.keep.as_u64(),
+            merged: merge.merged.as_u64(),
+            times: merge.times.clone(),
+            tprops: merge.tprops.iter().map(as_proto_prop_update).collect(),
+            cprops: merge.cprops.iter().map(|id| *id as u64).collect(),
+            edges,
+        }
+    }
+}
+
+fn as_time(t: &proto::node_merge::TimeEntry) -> TimeIndexEntry {
+    TimeIndexEntry(t.time, t.secondary as usize)
+}
+
+fn as_prop_update(update: &proto::node_merge::PropUpdate) -> (TimeIndexEntry, usize) {
+    (
+        TimeIndexEntry(update.time, update.secondary as usize),
+        update.prop_id as usize,
+    )
+}
+
+impl From<&proto::NodeMerge> for NodeMerge {
+    fn from(merge: &proto::NodeMerge) -> Self {
+        let edges = merge
+            .edges
+            .iter()
+            .map(|edge| EdgeMerge {
+                source: EID(edge.source as usize),
+                target: EID(edge.target as usize),
+                created: edge.created,
+                layers: edge
+                    .layers
+                    .iter()
+                    .map(|layer| LayerMerge {
+                        layer: layer.layer_id as usize,
+                        additions: layer.additions.iter().map(as_time).collect(),
+                        deletions: layer.deletions.iter().map(as_time).collect(),
+                        tprops: layer.tprops.iter().map(as_prop_update).collect(),
+                        cprops: layer.cprops.iter().map(|id| *id as usize).collect(),
+                    })
+                    .collect(),
+            })
+            .collect();
+        NodeMerge {
+            keep: VID(merge.keep as usize),
+            merged: VID(merge.merged as usize),
+            times: merge.times.clone(),
+            tprops: merge.tprops.iter().map(as_prop_update).collect(),
+            cprops: merge.cprops.iter().map(|id| *id as usize).collect(),
+            edges,
+        }
+    }
+}
+
+impl NewHyperedge {
+    fn new(
+        t: TimeIndexEntry,
+        gid: GidRef,
+        participants: &[(VID, Option<usize>)],
+        props: impl Iterator<Item = (usize, impl Borrow<Prop>)>,
+    ) -> Self {
+        let gid = match gid {
+            GidRef::U64(id) => new_hyperedge::Gid::GidU64(id),
+            GidRef::Str(name) => new_hyperedge::Gid::GidStr(name.to_string()),
+        };
+        let participants = participants
+            .iter()
+            .map(|(vid, role)| new_hyperedge::Participant {
+                vid: vid.as_u64(),
+                role: role.map(|role| role as u64),
+            })
+            .collect();
+        let properties = props
+            .map(|(key, value)| PropPair::new(key, value.borrow()))
+            .collect();
+        NewHyperedge {
+            gid: Some(gid),
+            time: t.t(),
+            secondary: t.i() as u64,
+            participants,
+            properties,
+        }
+    }
+}
+
 impl proto::Graph {
     pub fn new_edge(&mut self, src: VID, dst: VID, eid: EID) {
         let edge = NewEdge {
             src: src.as_u64(),
             dst: dst.as_u64(),
             eid: eid.as_u64(),
+            removed: false,
+        };
+        self.edges.push(edge);
+    }
+
+    pub fn removed_edge(&mut self, src: VID, dst: VID, eid: EID) {
+        let edge = NewEdge {
+            src: src.as_u64(),
+            dst: dst.as_u64(),
+            eid: eid.as_u64(),
+            removed: true,
         };
         self.edges.push(edge);
     }
 
     pub fn new_node(&mut self, gid: GidRef, vid: VID, type_id: usize) {
+        self.push_node(gid, vid, type_id, false);
+    }
+
+    pub fn removed_node(&mut self, gid: GidRef, vid: VID) {
+        self.push_node(gid, vid, 0, true);
+    }
+
+    fn push_node(&mut self, gid: GidRef, vid: VID, type_id: usize, removed: bool) {
         let type_id = type_id as u64;
         let gid = match gid {
             GidRef::U64(id) => new_node::Gid::GidU64(id),
@@ -355,6 +700,7 @@ impl proto::Graph {
             type_id,
             gid: Some(gid),
             vid: vid.as_u64(),
+            removed,
         };
         self.nodes.push(node);
     }
@@ -391,6 +737,41 @@ impl proto::Graph {
         self.metas.push(NewMeta::new_node_type(node_type, id));
     }
 
+    pub fn new_hyperedge_role(&mut self, role: &str, id: usize) {
+        self.metas.push(NewMeta::new_hyperedge_role(role, id));
+    }
+
+    pub fn new_hyperedge_prop(&mut self, key: &str, id: usize, dtype: &PropType) {
+        self.metas.push(NewMeta::new_hyperedge_prop(key, id, dtype));
+    }
+
+    pub fn new_hyperedge(
+        &mut self,
+        t: TimeIndexEntry,
+        gid: GidRef,
+        participants: &[(VID, Option<usize>)],
+        props: impl Iterator<Item = (usize, impl Borrow<Prop>)>,
+    ) {
+        self.hyperedges
+            .push(NewHyperedge::new(t, gid, participants, props));
+    }
+
+    /// Log the addition of a hyperedge, which is replayed in order with the other updates
+    pub fn add_hyperedge(
+        &mut self,
+        t: TimeIndexEntry,
+        gid: GidRef,
+        participants: &[(VID, Option<usize>)],
+        props: impl Iterator<Item = (usize, impl Borrow<Prop>)>,
+    ) {
+        let hyperedge = NewHyperedge::new(t, gid, participants, props);
+        self.updates.push(GraphUpdate::new_hyperedge(hyperedge));
+    }
+
+    pub fn new_node_merge(&mut self, merge: &NodeMerge) {
+        self.node_merges.push(merge.into());
+    }
+
     pub fn update_graph_cprops(
         &mut self,
         values: impl Iterator<Item = (usize, impl Borrow<Prop>)>,
@@ -456,18 +837,52 @@ impl proto::Graph {
         self.updates
             .push(GraphUpdate::del_edge(eid, layer_id, time))
     }
+
+    pub fn remove_node(&mut self, node_id: VID) {
+        self.updates.push(GraphUpdate::remove_node(node_id))
+    }
+
+    pub fn remove_edge(&mut self, eid: EID, layer_ids: &LayerIds) {
+        self.updates.push(GraphUpdate::remove_edge(eid, layer_ids))
+    }
+
+    pub fn remove_node_tprops(&mut self, node_id: VID, w: Range<i64>, prop_id: Option<usize>) {
+        self.updates
+            .push(GraphUpdate::remove_node_tprops(node_id, w, prop_id))
+    }
+
+    pub fn remove_edge_tprops(
+        &mut self,
+        eid: EID,
+        layer_ids: &LayerIds,
+        w: Range<i64>,
+        prop_id: Option<usize>,
+    ) {
+        self.updates
+            .push(GraphUpdate::remove_edge_tprops(eid, layer_ids, w, prop_id))
+    }
+
+    pub fn remove_graph_tprops(&mut self, w: Range<i64>, prop_id: Option<usize>) {
+        self.updates
+            .push(GraphUpdate::remove_graph_tprops(w, prop_id))
+    }
+
+    pub fn truncate_before(&mut self, t: i64, keep_alive: bool) {
+        self.updates
+            .push(GraphUpdate::truncate_before(t, keep_alive))
+    }
+
+    pub fn merge_nodes(&mut self, merge: &NodeMerge) {
+        self.updates.push(GraphUpdate::node_merge(merge))
+    }
+
+    pub fn unmerge_node(&mut self, gid: GidRef) {
+        self.updates.push(GraphUpdate::unmerge_node(gid))
+    }
 }
 
 impl StableEncode for GraphStorage {
     fn encode_to_proto(&self) -> proto::Graph {
-        #[cfg(feature = "storage")]
-        if let GraphStorage::Disk(storage) = self {
-            assert!(
-                storage.inner.layers().len() <= 1,
-                "Disk based storage not supported right now because it doesn't have aligned edges"
-            );
-        }
-
         let storage = self.lock();
         let mut graph = proto::Graph::default();
 
@@ -543,10 +958,32 @@ impl StableEncode for GraphStorage {
             graph.new_node_tprop(key, id, dtype);
         }
 
+        // merged nodes and their edges are hidden but keep their data such that the merges can be
+        // replayed and undone
+        let (merged_nodes, merged_edges): (FxHashSet<_>, FxHashSet<_>) = storage
+            .node_merges()
+            .map(|merges| {
+                let merges = merges.read();
+                (
+                    merges.iter().map(|merge| merge.merged).collect(),
+                    merges
+                        .iter()
+                        .flat_map(|merge| merge.edges.iter().map(|edge| edge.source))
+                        .collect(),
+                )
+            })
+            .unwrap_or_default();
+
         // Nodes
         let nodes = storage.nodes();
         for node_id in 0..nodes.len() {
             let node = nodes.node(VID(node_id));
+            if !storage.filter_node(node.as_ref(), &LayerIds::All)
+                && !merged_nodes.contains(&node.vid())
+            {
+                graph.removed_node(node.id(), node.vid());
+                continue;
+            }
             graph.new_node(node.id(), node.vid(), node.node_type_id());
             for (t, group) in
                 zip_tprop_updates!((0..n_temporal_meta.len()).map(|id| (id, node.tprop(id))))
@@ -588,10 +1025,25 @@ impl StableEncode for GraphStorage {
 
         // Edges
         let edges = storage.edges();
-        for eid in 0..edges.len() {
-            let eid = EID(eid);
-            let edge = edges.edge(ELID::new(eid, Some(0))); // This works for DiskStorage as long as it has a single layer
+        let mut num_edges = 0;
+        for eid in 0..edges.num_ids() {
+            // an edge without a layer spans all the layers of the graph
+            let edge = edges.edge(ELID::new(EID(eid), None));
+            #[cfg(feature = "storage")]
+            if let EdgeStorageEntry::Disk(edge) = &edge {
+                // disk graphs store a copy of the edge in each of its layers, only encode the first
+                if !edge.is_first_layer_copy() {
+                    continue;
+                }
+            }
+            let is_merged = merged_edges.contains(&EID(eid));
+            let eid = EID(num_edges);
+            num_edges += 1;
             let edge = edge.as_ref();
+            if !storage.filter_edge(edge, &LayerIds::All) && !is_merged {
+                graph.removed_edge(edge.src(), edge.dst(), eid);
+                continue;
+            }
             graph.new_edge(edge.src(), edge.dst(), eid);
             for layer_id in 0..storage.unfiltered_num_layers() {
                 for (t, props) in
@@ -615,6 +1067,37 @@ impl StableEncode for GraphStorage {
                 );
             }
         }
+
+        // Hyperedges
+        if let Some(hyperedges) = storage.hyperedges() {
+            for (id, role) in hyperedges.role_meta().get_keys().iter().enumerate() {
+                graph.new_hyperedge_role(role, id);
+            }
+            let h_prop_meta = hyperedges.prop_meta();
+            for (id, (key, dtype)) in h_prop_meta
+                .get_keys()
+                .iter()
+                .zip(h_prop_meta.dtypes().iter())
+                .enumerate()
+            {
+                graph.new_hyperedge_prop(key, id, dtype);
+            }
+            for (_, hyperedge) in hyperedges.read().iter() {
+                graph.new_hyperedge(
+                    hyperedge.time,
+                    (&hyperedge.id).into(),
+                    &hyperedge.participants,
+                    hyperedge.props.iter().map(|(id, prop)| (*id, prop)),
+                );
+            }
+        }
+
+        // Node merges
+        if let Some(merges) = storage.node_merges() {
+            for merge in merges.read().iter() {
+                graph.new_node_merge(merge);
+            }
+        }
         graph
     }
 }
@@ -623,6 +1106,7 @@ impl StableEncode for Graph {
     fn encode_to_proto(&self) -> proto::Graph {
         let mut graph = self.core_graph().encode_to_proto();
         graph.set_graph_type(proto::GraphType::Event);
+        graph.schema = self.inner.schema_json();
         graph
     }
 }
@@ -631,6 +1115,7 @@ impl StableEncode for PersistentGraph {
     fn encode_to_proto(&self) -> proto::Graph {
         let mut graph = self.core_graph().encode_to_proto();
         graph.set_graph_type(proto::GraphType::Persistent);
+        graph.schema = self.0.schema_json();
         graph
     }
 }
@@ -644,6 +1129,136 @@ impl StableEncode for MaterializedGraph {
     }
 }
 
+fn add_hyperedge(storage: &TemporalGraph, hyperedge: &NewHyperedge) -> Result<(), GraphError> {
+    let gid = match hyperedge.gid.as_ref().unwrap() {
+        new_hyperedge::Gid::GidStr(name) => GidRef::Str(name),
+        new_hyperedge::Gid::GidU64(gid) => GidRef::U64(*gid),
+    };
+    let participants = hyperedge
+        .participants
+        .iter()
+        .map(|p| (VID(p.vid as usize), p.role.map(|role| role as usize)))
+        .collect::<Vec<_>>();
+    storage.internal_add_hyperedge(
+        TimeIndexEntry(hyperedge.time, hyperedge.secondary as usize),
+        gid,
+        &participants,
+        &collect_props(&hyperedge.properties)?,
+    )?;
+    Ok(())
+}
+
+fn apply_update(storage: &TemporalGraph, update: &GraphUpdate) -> Result<(), GraphError> {
+    if let Some(update) = update.update.as_ref() {
+        match update {
+            Update::UpdateNodeCprops(props) => {
+                storage.internal_update_constant_node_properties(
+                    VID(props.id as usize),
+                    &collect_props(&props.properties)?,
+                )?;
+            }
+            Update::UpdateNodeTprops(props) => {
+                let time = TimeIndexEntry(props.time, props.secondary as usize);
+                let node = VID(props.id as usize);
+                let props = collect_props(&props.properties)?;
+                storage.internal_add_node(time, node, &props)?;
+            }
+            Update::UpdateGraphCprops(props) => {
+                storage.internal_update_constant_properties(&collect_props(&props.properties)?)?;
+            }
+            Update::UpdateGraphTprops(props) => {
+                let time = TimeIndexEntry(props.time, props.secondary as usize);
+                storage.internal_add_properties(time, &collect_props(&props.properties)?)?;
+            }
+            Update::DelEdge(del_edge) => {
+                let time = TimeIndexEntry(del_edge.time, del_edge.secondary as usize);
+                storage.internal_delete_existing_edge(
+                    time,
+                    EID(del_edge.eid as usize),
+                    del_edge.layer_id as usize,
+                )?;
+            }
+            Update::UpdateEdgeCprops(props) => {
+                storage.internal_update_constant_edge_properties(
+                    EID(props.eid as usize),
+                    props.layer_id as usize,
+                    &collect_props(&props.properties)?,
+                )?;
+            }
+            Update::UpdateEdgeTprops(props) => {
+                let time = TimeIndexEntry(props.time, props.secondary as usize);
+                let eid = EID(props.eid as usize);
+                storage.internal_add_edge_update(
+                    time,
+                    eid,
+                    &collect_props(&props.properties)?,
+                    props.layer_id as usize,
+                )?;
+            }
+            Update::UpdateNodeType(update) => {
+                let id = VID(update.id as usize);
+                let type_id = update.type_id as usize;
+                storage.storage.get_node_mut(id).node_type = type_id;
+            }
+            Update::RemoveNode(remove) => {
+                storage.internal_remove_node(VID(remove.id as usize))?;
+            }
+            Update::RemoveEdge(remove) => {
+                let layer_ids = remove.layers.as_ref().map(LayerIds::from);
+                storage.internal_remove_edge(
+                    EID(remove.eid as usize),
+                    &layer_ids.unwrap_or(LayerIds::None),
+                )?;
+            }
+            Update::RemoveNodeTprops(remove) => {
+                storage.internal_remove_node_property_updates(
+                    VID(remove.id as usize),
+                    remove.start..remove.end,
+                    remove.prop_id.map(|id| id as usize),
+                )?;
+            }
+            Update::RemoveEdgeTprops(remove) => {
+                let layer_ids = remove.layers.as_ref().map(LayerIds::from);
+                storage.internal_remove_edge_property_updates(
+                    EID(remove.eid as usize),
+                    &layer_ids.unwrap_or(LayerIds::None),
+                    remove.start..remove.end,
+                    remove.prop_id.map(|id| id as usize),
+                )?;
+            }
+            Update::RemoveGraphTprops(remove) => {
+                storage.internal_remove_graph_property_updates(
+                    remove.start..remove.end,
+                    remove.prop_id.map(|id| id as usize),
+                )?;
+            }
+            Update::TruncateBefore(truncate) => {
+                storage.internal_truncate_before(truncate.time, truncate.keep_alive)?;
+            }
+            Update::NodeMerge(merge) => {
+                storage.apply_node_merge(merge.into())?;
+            }
+            Update::NewHyperedge(hyperedge) => {
+                add_hyperedge(storage, hyperedge)?;
+            }
+            Update::UnmergeNode(unmerge) => {
+                let gid = match unmerge.gid.as_ref().unwrap() {
+                    unmerge_node::Gid::GidStr(name) => GidRef::Str(name),
+                    unmerge_node::Gid::GidU64(gid) => GidRef::U64(*gid),
+                };
+                let merged = storage.node_merges.merged_node(gid).ok_or_else(|| {
+                    GraphError::InvalidNodeUnmerge {
+                        node: gid.to_owned(),
+                        reason: "the node was not merged".to_owned(),
+                    }
+                })?;
+                storage.unmerge_node(merged)?;
+            }
+        }
+    }
+    Ok(())
+}
+
 impl StableDecode for TemporalGraph {
     fn decode_from_proto(graph: &proto::Graph) -> Result<Self, GraphError> {
         let storage = Self::default();
@@ -699,6 +1314,17 @@ impl StableDecode for TemporalGraph {
                             as_prop_type(edge_tprop.p_type()),
                         )
                     }
+                    Meta::NewHyperedgeRole(role) => storage
+                        .hyperedges
+                        .role_meta()
+                        .set_id(role.name.as_str(), role.id as usize),
+                    Meta::NewHyperedgeProp(hyperedge_prop) => {
+                        storage.hyperedges.prop_meta().set_id_and_dtype(
+                            hyperedge_prop.name.as_str(),
+                            hyperedge_prop.id as usize,
+                            as_prop_type(hyperedge_prop.p_type()),
+                        )
+                    }
                 }
             }
         });
@@ -708,10 +1334,16 @@ impl StableDecode for TemporalGraph {
                 Gid::GidU64(gid) => GidRef::U64(*gid),
             };
             let vid = VID(node.vid as usize);
-            storage.logical_to_physical.get_or_init(gid, || vid)?;
             let mut node_store = NodeStore::empty(gid.to_owned());
             node_store.vid = vid;
-            node_store.node_type = node.type_id as usize;
+            if node.removed {
+                node_store.clear();
+                storage.num_removed_nodes.fetch_add(1, Ordering::Relaxed);
+            } else {
+                // a removed node that was added again gets a new, larger id
+                storage.logical_to_physical.insert_latest(gid, vid)?;
+                node_store.node_type = node.type_id as usize;
+            }
             storage.storage.nodes.set(vid, node_store);
             Ok::<(), GraphError>(())
         })?;
@@ -719,69 +1351,38 @@ impl StableDecode for TemporalGraph {
             let eid = EID(edge.eid as usize);
             let src = VID(edge.src as usize);
             let dst = VID(edge.dst as usize);
-            let mut edge = EdgeStore::new(src, dst);
-            edge.eid = eid;
-            storage.storage.edges.set(edge);
+            let mut edge_store = EdgeStore::new(src, dst);
+            edge_store.eid = eid;
+            if edge.removed {
+                edge_store.removed = true;
+                storage.num_removed_edges.fetch_add(1, Ordering::Relaxed);
+            }
+            storage.storage.edges.set(edge_store);
         });
-        graph.updates.par_iter().try_for_each(|update| {
-            if let Some(update) = update.update.as_ref() {
-                match update {
-                    Update::UpdateNodeCprops(props) => {
-                        storage.internal_update_constant_node_properties(
-                            VID(props.id as usize),
-                            &collect_props(&props.properties)?,
-                        )?;
-                    }
-                    Update::UpdateNodeTprops(props) => {
-                        let time = TimeIndexEntry(props.time, props.secondary as usize);
-                        let node = VID(props.id as usize);
-                        let props = collect_props(&props.properties)?;
-                        storage.internal_add_node(time, node, &props)?;
-                    }
-                    Update::UpdateGraphCprops(props) => {
-                        storage.internal_update_constant_properties(&collect_props(
-                            &props.properties,
-                        )?)?;
-                    }
-                    Update::UpdateGraphTprops(props) => {
-                        let time = TimeIndexEntry(props.time, props.secondary as usize);
-                        storage
-                            .internal_add_properties(time, &collect_props(&props.properties)?)?;
-                    }
-                    Update::DelEdge(del_edge) => {
-                        let time = TimeIndexEntry(del_edge.time, del_edge.secondary as usize);
-                        storage.internal_delete_existing_edge(
-                            time,
-                            EID(del_edge.eid as usize),
-                            del_edge.layer_id as usize,
-                        )?;
-                    }
-                    Update::UpdateEdgeCprops(props) => {
-                        storage.internal_update_constant_edge_properties(
-                            EID(props.eid as usize),
-                            props.layer_id as usize,
-                            &collect_props(&props.properties)?,
-                        )?;
-                    }
-                    Update::UpdateEdgeTprops(props) => {
-                        let time = TimeIndexEntry(props.time, props.secondary as usize);
-                        let eid = EID(props.eid as usize);
-                        storage.internal_add_edge_update(
-                            time,
-                            eid,
-                            &collect_props(&props.properties)?,
-                            props.layer_id as usize,
-                        )?;
-                    }
-                    Update::UpdateNodeType(update) => {
-                        let id = VID(update.id as usize);
-                        let type_id = update.type_id as usize;
-                        storage.storage.get_node_mut(id).node_type = type_id;
-                    }
-                }
+        // hyperedges are added in order such that their internal ids are stable, the hyperedges
+        // of the log are added in order with the other updates
+        for hyperedge in graph.hyperedges.iter() {
+            add_hyperedge(&storage, hyperedge)?;
+        }
+        // the merges of the snapshot are replayed first as the merges and unmerges in the log can
+        // depend on them, the snapshot already contains the updates copied by its merges
+        for merge in graph.node_merges.iter() {
+            storage.apply_node_merge(merge.into())?;
+        }
+        // removals and merges depend on the updates before them, which need to be applied first
+        for chunk in graph.updates.split_inclusive(|update| update.is_ordered()) {
+            let (last, updates) = chunk.split_last().expect("chunks are not empty");
+            if last.is_ordered() {
+                updates
+                    .par_iter()
+                    .try_for_each(|update| apply_update(&storage, update))?;
+                apply_update(&storage, last)?;
+            } else {
+                chunk
+                    .par_iter()
+                    .try_for_each(|update| apply_update(&storage, update))?;
             }
-            Ok::<_, GraphError>(())
-        })?;
+        }
         Ok(storage)
     }
 }
@@ -794,16 +1395,27 @@ impl StableDecode for GraphStorage {
     }
 }
 
+/// Set the schema of the decoded graph `g`, if `graph` has one
+fn decode_schema<G: SchemaOps>(graph: &proto::Graph, g: G) -> Result<G, GraphError> {
+    if let Some(json) = graph.schema.as_deref().filter(|json| !json.is_empty()) {
+        g.set_schema(GraphSchema::from_json(json)?)?;
+    }
+    Ok(g)
+}
+
 impl StableDecode for MaterializedGraph {
     fn decode_from_proto(graph: &proto::Graph) -> Result<Self, GraphError> {
         let storage = GraphStorage::decode_from_proto(graph)?;
-        let graph = match graph.graph_type() {
-            proto::GraphType::Event => Self::EventGraph(Graph::from_internal_graph(storage)),
-            proto::GraphType::Persistent => {
-                Self::PersistentGraph(PersistentGraph::from_internal_graph(storage))
+        let g = match graph.graph_type() {
+            proto::GraphType::Event => {
+                Self::EventGraph(decode_schema(graph, Graph::from_internal_graph(storage))?)
             }
+            proto::GraphType::Persistent => Self::PersistentGraph(decode_schema(
+                graph,
+                PersistentGraph::from_internal_graph(storage),
+            )?),
         };
-        Ok(graph)
+        Ok(g)
     }
 }
 
@@ -812,7 +1424,7 @@ impl StableDecode for Graph {
         match graph.graph_type() {
             proto::GraphType::Event => {
                 let storage = GraphStorage::decode_from_proto(graph)?;
-                Ok(Graph::from_internal_graph(storage))
+                decode_schema(graph, Graph::from_internal_graph(storage))
             }
             proto::GraphType::Persistent => Err(GraphError::GraphLoadError),
         }
@@ -825,7 +1437,7 @@ impl StableDecode for PersistentGraph {
             proto::GraphType::Event => Err(GraphError::GraphLoadError),
             proto::GraphType::Persistent => {
                 let storage = GraphStorage::decode_from_proto(graph)?;
-                Ok(PersistentGraph::from_internal_graph(storage))
+                decode_schema(graph, PersistentGraph::from_internal_graph(storage))
             }
         }
     }
@@ -1010,8 +1622,11 @@ mod proto_test {
             graph::graph::assert_graph_equal,
         },
         prelude::*,
-        serialise::{proto::GraphType, ProtoGraph},
+        serialise::{
+            proto::GraphType, restore_cache, wal_path, FsyncPolicy, ProtoGraph, WalOptions,
+        },
     };
+    use std::fs::{self, OpenOptions};
 
     #[test]
     fn node_no_props() {
@@ -1355,9 +1970,10 @@ mod proto_test {
         let g = Graph::new();
         let mut props = vec![];
         write_props_to_vec(&mut props);
-        let temp_cache_file = tempfile::NamedTempFile::new().unwrap();
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("graph");
 
-        g.cache(temp_cache_file.path()).unwrap();
+        g.cache(&path).unwrap();
 
         for t in 0..props.len() {
             g.add_properties(t as i64, (&props[t..t + 1]).to_vec())
@@ -1390,7 +2006,7 @@ mod proto_test {
         g.write_updates().unwrap();
         println!("{g:?}");
 
-        let g2 = Graph::decode(temp_cache_file.path()).unwrap();
+        let g2 = Graph::load_cached(&path).unwrap();
         println!("{g2:?}");
 
         assert_graph_equal(&g, &g2);
@@ -1401,9 +2017,10 @@ mod proto_test {
         let g = PersistentGraph::new();
         let mut props = vec![];
         write_props_to_vec(&mut props);
-        let temp_cache_file = tempfile::NamedTempFile::new().unwrap();
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("graph");
 
-        g.cache(temp_cache_file.path()).unwrap();
+        g.cache(&path).unwrap();
 
         for t in 0..props.len() {
             g.add_properties(t as i64, (&props[t..t + 1]).to_vec())
@@ -1436,12 +2053,414 @@ mod proto_test {
         g.write_updates().unwrap();
         println!("{g:?}");
 
-        let g2 = PersistentGraph::decode(temp_cache_file.path()).unwrap();
+        let g2 = PersistentGraph::load_cached(&path).unwrap();
         println!("{g2:?}");
 
         assert_graph_equal(&g, &g2);
     }
 
+    #[test]
+    fn test_wal_truncates_torn_tail() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("graph");
+        let g = Graph::new();
+        g.cache(&path).unwrap();
+        g.add_edge(1, "Alice", "Bob", NO_PROPS, None).unwrap();
+        g.write_updates().unwrap();
+        g.add_edge(2, "Bob", "Charlie", NO_PROPS, None).unwrap();
+        g.write_updates().unwrap();
+
+        // simulate a crash in the middle of writing the last entry
+        let wal = wal_path(&path);
+        let len = fs::metadata(&wal).unwrap().len();
+        OpenOptions::new()
+            .write(true)
+            .open(&wal)
+            .unwrap()
+            .set_len(len - 3)
+            .unwrap();
+
+        let g2 = Graph::load_cached(&path).unwrap();
+        let expected = Graph::new();
+        expected
+            .add_edge(1, "Alice", "Bob", NO_PROPS, None)
+            .unwrap();
+        assert_graph_equal(&g2, &expected);
+
+        // the torn entry is gone and new updates are appended after the valid ones
+        g2.add_edge(3, "Alice", "Charlie", NO_PROPS, None).unwrap();
+        g2.write_updates().unwrap();
+        expected
+            .add_edge(3, "Alice", "Charlie", NO_PROPS, None)
+            .unwrap();
+        assert_graph_equal(&Graph::load_cached(&path).unwrap(), &expected);
+    }
+
+    #[test]
+    fn test_wal_checkpoint() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("graph");
+        let options = WalOptions {
+            fsync: FsyncPolicy::Never,
+            checkpoint_bytes: Some(1),
+            checkpoint_interval: None,
+        };
+        let g = Graph::new();
+        g.cache_with_options(&path, options).unwrap();
+        let empty_wal_len = fs::metadata(wal_path(&path)).unwrap().len();
+
+        g.add_edge(1, "Alice", "Bob", [("weight", 1.0)], None)
+            .unwrap();
+        g.write_updates().unwrap();
+
+        // the update was folded into the snapshot and the log is empty again
+        assert_eq!(fs::metadata(wal_path(&path)).unwrap().len(), empty_wal_len);
+        assert_graph_equal(&Graph::decode(&path).unwrap(), &g);
+        assert_graph_equal(
+            &Graph::load_cached_with_options(&path, options).unwrap(),
+            &g,
+        );
+    }
+
+    #[test]
+    fn test_wal_of_old_snapshot_is_discarded() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("graph");
+        let g = Graph::new();
+        g.cache(&path).unwrap();
+        g.add_edge(1, "Alice", "Bob", NO_PROPS, None).unwrap();
+        g.write_updates().unwrap();
+
+        // replacing the snapshot without going through the cache orphans the log
+        let other = Graph::new();
+        other.add_node(0, "Charlie", NO_PROPS, None).unwrap();
+        other.encode(&path).unwrap();
+
+        assert_graph_equal(&Graph::load_cached(&path).unwrap(), &other);
+    }
+
+    #[test]
+    fn test_checkpoint_with_concurrent_updates() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("graph");
+        let g = Graph::new();
+        g.cache(&path).unwrap();
+        std::thread::scope(|scope| {
+            scope.spawn(|| {
+                // merges fail when they are replayed twice
+                for i in 0..100 {
+                    let (a, b) = (format!("a{i}"), format!("b{i}"));
+                    g.add_edge(i, a.as_str(), b.as_str(), NO_PROPS, None)
+                        .unwrap();
+                    g.merge_nodes(b.as_str(), a.as_str()).unwrap();
+                    g.write_updates().unwrap();
+                }
+            });
+            for _ in 0..20 {
+                g.checkpoint().unwrap();
+            }
+        });
+        g.write_updates().unwrap();
+
+        let g2 = Graph::load_cached(&path).unwrap();
+        assert_graph_equal(&g, &g2);
+        assert_eq!(g2.count_nodes(), 100);
+    }
+
+    #[test]
+    fn test_linked_cache_survives_checkpoints() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("graph");
+        let linked = temp_dir.path().join("linked");
+        let g = Graph::new();
+        g.cache(&path).unwrap();
+        g.add_edge(1, "Alice", "Bob", NO_PROPS, None).unwrap();
+        let log_len = g.link_cache(&linked).unwrap();
+        let expected = g.materialize().unwrap().into_events().unwrap();
+
+        g.add_edge(2, "Bob", "Charlie", NO_PROPS, None).unwrap();
+        g.write_updates().unwrap();
+        assert_graph_equal(
+            &Graph::load_linked_cache(&linked, log_len).unwrap(),
+            &expected,
+        );
+
+        // the checkpoint replaces the files of the cache, the links keep the old ones
+        g.checkpoint().unwrap();
+        assert_graph_equal(
+            &Graph::load_linked_cache(&linked, log_len).unwrap(),
+            &expected,
+        );
+
+        drop(g);
+        restore_cache(&linked, log_len, &path).unwrap();
+        let restored = Graph::load_cached(&path).unwrap();
+        assert_graph_equal(&restored, &expected);
+
+        // updates after the restore are appended to the truncated log
+        restored.add_node(3, "Dave", NO_PROPS, None).unwrap();
+        restored.write_updates().unwrap();
+        expected.add_node(3, "Dave", NO_PROPS, None).unwrap();
+        assert_graph_equal(&Graph::load_cached(&path).unwrap(), &expected);
+    }
+
+    #[test]
+    fn test_removed_entities_roundtrip() {
+        let g = Graph::new();
+        g.add_edge(1, "Alice", "Bob", NO_PROPS, None).unwrap();
+        g.add_edge(2, "Bob", "Charlie", NO_PROPS, None).unwrap();
+        g.add_edge(3, "Charlie", "Dave", NO_PROPS, None).unwrap();
+        g.remove_node("Bob").unwrap();
+        g.remove_edge("Charlie", "Dave", None).unwrap();
+
+        let g2 = Graph::decode_from_bytes(&g.encode_to_vec()).unwrap();
+        assert_graph_equal(&g, &g2);
+        assert!(!g2.has_node("Bob"));
+        assert!(!g2.has_edge("Charlie", "Dave"));
+
+        // ids of removed entities are not reused
+        g2.add_edge(4, "Bob", "Dave", NO_PROPS, None).unwrap();
+        assert_eq!(g2.count_nodes(), 4);
+        assert_eq!(g2.edge("Bob", "Dave").unwrap().history(), [4]);
+    }
+
+    #[test]
+    fn test_removals_are_logged() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("graph");
+        let g = PersistentGraph::new();
+        g.cache(&path).unwrap();
+        let snapshot = fs::read(&path).unwrap();
+        g.add_edge(1, "Alice", "Bob", [("weight", 1)], None)
+            .unwrap();
+        g.add_edge(2, "Bob", "Charlie", NO_PROPS, None).unwrap();
+        g.add_edge(5, "Alice", "Bob", [("weight", 2)], None)
+            .unwrap();
+        g.delete_edge(3, "Bob", "Charlie", None).unwrap();
+        g.truncate_before(4).unwrap();
+        assert_eq!(g.edge("Alice", "Bob").unwrap().history(), [4, 5]);
+
+        // removals are replayed in order with the updates around them
+        g.add_edge(6, "Charlie", "Dave", NO_PROPS, Some("x"))
+            .unwrap();
+        g.remove_edge("Charlie", "Dave", Some("x")).unwrap();
+        g.add_edge(7, "Charlie", "Dave", NO_PROPS, Some("x"))
+            .unwrap();
+        g.add_node(8, "Eve", [("p", 1)], None).unwrap();
+        g.remove_node_property_updates("Eve", 8, 9, None).unwrap();
+        g.add_node(8, "Eve", [("p", 2)], None).unwrap();
+        g.add_hyperedge(8, "m1", [("Alice", None), ("Eve", None)], NO_PROPS)
+            .unwrap();
+        g.remove_node("Alice").unwrap();
+        g.add_node(9, "Alice", NO_PROPS, None).unwrap();
+        g.write_updates().unwrap();
+
+        // the snapshot is not rewritten
+        assert_eq!(fs::read(&path).unwrap(), snapshot);
+        let g2 = PersistentGraph::load_cached(&path).unwrap();
+        assert_graph_equal(&g, &g2);
+        assert!(!g2.has_edge("Bob", "Charlie"));
+        assert!(!g2.has_edge("Alice", "Bob"));
+        assert_eq!(g2.node("Alice").unwrap().history(), [9]);
+        assert_eq!(g2.edge("Charlie", "Dave").unwrap().history(), [7]);
+        assert_eq!(
+            g2.node("Eve")
+                .unwrap()
+                .properties()
+                .temporal()
+                .get("p")
+                .unwrap()
+                .values()
+                .collect_vec(),
+            [Prop::I32(2)]
+        );
+        assert_eq!(g2.hyperedge("m1").unwrap().nodes().len(), 1);
+    }
+
+    #[test]
+    fn test_hyperedges_roundtrip() {
+        let g = Graph::new();
+        g.add_hyperedge(
+            1,
+            "m1",
+            [("Alice", Some("host")), ("Bob", None)],
+            [("duration", 30i64)],
+        )
+        .unwrap();
+        g.add_hyperedge(
+            2,
+            7u64,
+            [("Bob", None), ("Charlie", Some("guest"))],
+            NO_PROPS,
+        )
+        .unwrap();
+
+        let g2 = Graph::decode_from_bytes(&g.encode_to_vec()).unwrap();
+        assert_graph_equal(&g, &g2);
+        assert_eq!(g2.count_hyperedges(), 2);
+        let m1 = g2.hyperedge("m1").unwrap();
+        assert_eq!(m1.time(), 1);
+        assert_eq!(m1.properties().get("duration"), Some(Prop::I64(30)));
+        assert_eq!(
+            g2.hyperedge(7u64).unwrap().participants()[1].1.as_deref(),
+            Some("guest")
+        );
+    }
+
+    #[test]
+    fn test_hyperedges_cached() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("graph");
+        let g = Graph::new();
+        g.add_hyperedge(1, "m1", [("Alice", None), ("Bob", None)], NO_PROPS)
+            .unwrap();
+        g.cache(&path).unwrap();
+        g.add_hyperedge(3, "m2", [("Bob", Some("host"))], [("topic", "rust")])
+            .unwrap();
+        g.write_updates().unwrap();
+
+        let g2 = Graph::load_cached(&path).unwrap();
+        assert_graph_equal(&g, &g2);
+        assert_eq!(g2.count_hyperedges(), 2);
+        assert_eq!(g2.node("Bob").unwrap().hyperedge_degree(), 2);
+        assert_eq!(
+            g2.hyperedge("m2").unwrap().properties().get("topic"),
+            Some(Prop::str("rust"))
+        );
+    }
+
+    #[test]
+    fn test_truncated_hyperedges_cached() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("graph");
+        let g = Graph::new();
+        g.add_hyperedge(1, "m1", [("Alice", None), ("Bob", None)], NO_PROPS)
+            .unwrap();
+        g.cache(&path).unwrap();
+        g.add_hyperedge(2, "m2", [("Bob", None)], NO_PROPS).unwrap();
+        g.add_node(3, "Bob", NO_PROPS, None).unwrap();
+        // hyperedges in the log are replayed in order with the truncation
+        g.truncate_before(3).unwrap();
+        g.add_hyperedge(4, "m3", [("Bob", None)], NO_PROPS).unwrap();
+        g.write_updates().unwrap();
+
+        let g2 = Graph::load_cached(&path).unwrap();
+        assert_graph_equal(&g, &g2);
+        assert!(g2.hyperedge("m1").is_none());
+        assert!(g2.hyperedge("m2").is_none());
+        assert_eq!(g2.count_hyperedges(), 1);
+        assert_eq!(g2.earliest_time(), Some(3));
+    }
+
+    #[test]
+    fn test_node_merge_roundtrip() {
+        let g = Graph::new();
+        g.add_node(1, "a", [("balance", 10i64)], None).unwrap();
+        g.add_edge(2, "b", "c", [("amount", 5i64)], Some("transfer"))
+            .unwrap();
+        g.add_edge(3, "b", "a", NO_PROPS, None).unwrap();
+        g.node("b")
+            .unwrap()
+            .add_constant_properties([("bank", "x")])
+            .unwrap();
+        g.merge_nodes("a", "b").unwrap();
+
+        let g2 = Graph::decode_from_bytes(&g.encode_to_vec()).unwrap();
+        assert_graph_equal(&g, &g2);
+        assert_eq!(g2.node("b").unwrap().name(), "a");
+        assert_eq!(g2.edge("a", "c").unwrap().history(), [2]);
+
+        g2.unmerge_node("b").unwrap();
+        assert_eq!(g2.count_nodes(), 3);
+        assert_eq!(g2.node("b").unwrap().name(), "b");
+        assert_eq!(g2.node("a").unwrap().properties().get("bank"), None);
+        assert!(g2.has_edge("b", "a"));
+        assert!(!g2.has_edge("a", "c"));
+    }
+
+    #[test]
+    fn test_node_merge_cached() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("graph");
+        let g = Graph::new();
+        g.add_edge(1, "a", "x", NO_PROPS, None).unwrap();
+        g.add_edge(2, "b", "y", NO_PROPS, None).unwrap();
+        g.cache(&path).unwrap();
+        g.merge_nodes("a", "b").unwrap();
+        g.add_edge(3, "b", "x", NO_PROPS, None).unwrap();
+        g.write_updates().unwrap();
+
+        let g2 = Graph::load_cached(&path).unwrap();
+        assert_graph_equal(&g, &g2);
+        assert_eq!(g2.edge("a", "y").unwrap().history(), [2]);
+        assert_eq!(g2.edge("a", "x").unwrap().history(), [1, 3]);
+
+        g.unmerge_node("b").unwrap();
+        g.write_updates().unwrap();
+        let g3 = Graph::load_cached(&path).unwrap();
+        assert_graph_equal(&g, &g3);
+        assert_eq!(g3.count_nodes(), 4);
+        assert!(g3.has_edge("b", "y"));
+    }
+
+    #[test]
+    fn test_node_unmerge_cached() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("graph");
+        let g = Graph::new();
+        g.add_edge(1, "a", "x", NO_PROPS, None).unwrap();
+        g.add_edge(2, "b", "y", NO_PROPS, None).unwrap();
+        g.add_hyperedge(3, "m1", [("b", None), ("x", None)], NO_PROPS)
+            .unwrap();
+        g.merge_nodes("a", "b").unwrap();
+        g.cache(&path).unwrap();
+        // the merge of the snapshot is undone in the log before merging the node again
+        g.unmerge_node("b").unwrap();
+        g.add_edge(4, "b", "x", NO_PROPS, None).unwrap();
+        g.merge_nodes("x", "b").unwrap();
+        g.write_updates().unwrap();
+
+        let g2 = Graph::load_cached(&path).unwrap();
+        assert_graph_equal(&g, &g2);
+        assert_eq!(g2.canonical_id("b"), Some(GID::Str("x".into())));
+        assert!(!g2.has_edge("a", "y"));
+        assert_eq!(g2.edge("x", "y").unwrap().history(), [2]);
+        assert_eq!(g2.edge("x", "x").unwrap().history(), [4]);
+        assert_eq!(
+            g2.hyperedge("m1")
+                .unwrap()
+                .nodes()
+                .iter()
+                .map(|v| v.name())
+                .collect_vec(),
+            ["x"]
+        );
+    }
+
+    #[test]
+    fn test_unmerge_of_added_again_node_cached() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let path = temp_dir.path().join("graph");
+        let g = Graph::new();
+        g.add_edge(1, "a", "x", NO_PROPS, None).unwrap();
+        g.add_node(1, "b", NO_PROPS, None).unwrap();
+        g.cache(&path).unwrap();
+        // the node that is added again gets a new internal id
+        g.remove_node("a").unwrap();
+        g.add_edge(2, "a", "y", NO_PROPS, None).unwrap();
+        g.merge_nodes("b", "a").unwrap();
+        g.unmerge_node("a").unwrap();
+        g.write_updates().unwrap();
+
+        let g2 = Graph::load_cached(&path).unwrap();
+        assert_graph_equal(&g, &g2);
+        assert_eq!(g2.canonical_id("a"), Some(GID::Str("a".into())));
+        assert_eq!(g2.node("a").unwrap().history(), [2]);
+        assert!(g2.has_edge("a", "y"));
+        assert!(!g2.has_edge("a", "x"));
+        assert!(!g2.has_edge("b", "y"));
+    }
+
     // we rely on this to make sure writing no updates does not actually write anything to file
     #[test]
     fn empty_proto_is_empty_bytes() {
@@ -0,0 +1,362 @@
+//! Write-ahead log of the updates of a cached graph.
+//!
+//! The cache of a graph consists of a snapshot, the proto encoding of the graph as of the last
+//! checkpoint, and a log of the updates made since. The log is stored next to the snapshot in the
+//! hidden file `.<snapshot name>.wal`. It starts with a header holding the checksum of the snapshot
+//! it belongs to, so a log left behind by an older snapshot is discarded rather than replayed.
+//!
+//! Each entry of the log is a proto delta prefixed with its length and CRC32 checksum (both as
+//! little-endian `u32`). An entry that was only partially written when the process crashed fails
+//! its checksum and is truncated together with everything after it when the log is opened.
+//!
+//! Snapshots are only ever replaced by renaming a new file into place and the log is only
+//! appended to, so a hard link to both taken by [Wal::link] keeps the state of the graph at that
+//! point available as the first `len` bytes of the linked log without copying anything.
+
+use crate::core::utils::errors::GraphError;
+use std::{
+    ffi::OsString,
+    fs::{self, File, OpenOptions},
+    io::{self, BufReader, Read, Write},
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+const MAGIC: &[u8; 8] = b"RAPHWAL1";
+const HEADER_LEN: usize = MAGIC.len() + 4;
+const ENTRY_HEADER_LEN: usize = 8;
+
+/// When the log is synced to disk
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FsyncPolicy {
+    /// Sync after every write, no acknowledged update is lost on a crash
+    #[default]
+    Always,
+    /// Sync on a write if the last sync is older than the interval
+    Interval(Duration),
+    /// Leave syncing to the operating system
+    Never,
+}
+
+/// Options of the write-ahead log of a cached graph
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WalOptions {
+    pub fsync: FsyncPolicy,
+    /// Rewrite the snapshot once the log is larger than this many bytes
+    pub checkpoint_bytes: Option<u64>,
+    /// Rewrite the snapshot when updates are written and the last checkpoint is older than this
+    pub checkpoint_interval: Option<Duration>,
+}
+
+impl Default for WalOptions {
+    fn default() -> Self {
+        Self {
+            fsync: FsyncPolicy::default(),
+            checkpoint_bytes: Some(64 * 1024 * 1024),
+            checkpoint_interval: None,
+        }
+    }
+}
+
+/// Path of the write-ahead log of the graph cached at `snapshot_path`
+pub fn wal_path(snapshot_path: impl AsRef<Path>) -> PathBuf {
+    hidden_sibling(snapshot_path.as_ref(), ".wal")
+}
+
+fn hidden_sibling(path: &Path, extension: &str) -> PathBuf {
+    let mut name = OsString::from(".");
+    name.push(path.file_name().unwrap_or_default());
+    name.push(extension);
+    path.with_file_name(name)
+}
+
+fn header(snapshot: &[u8]) -> [u8; HEADER_LEN] {
+    let mut header = [0; HEADER_LEN];
+    header[..MAGIC.len()].copy_from_slice(MAGIC);
+    header[MAGIC.len()..].copy_from_slice(&crc32fast::hash(snapshot).to_le_bytes());
+    header
+}
+
+/// Fill `buf` from `reader`, `false` if the reader ends first
+fn read_full(reader: &mut impl Read, buf: &mut [u8]) -> Result<bool, GraphError> {
+    match reader.read_exact(buf) {
+        Ok(()) => Ok(true),
+        Err(err) if err.kind() == io::ErrorKind::UnexpectedEof => Ok(false),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// Whether the log in `reader` starts with the header of `snapshot`
+fn read_header(reader: &mut impl Read, snapshot: &[u8]) -> Result<bool, GraphError> {
+    let mut buf = [0; HEADER_LEN];
+    Ok(read_full(reader, &mut buf)? && buf == header(snapshot))
+}
+
+/// Pass the entries in `reader`, which is positioned after the header, to `f` one at a time.
+///
+/// Stops at the first incomplete or corrupted entry or at the first entry that ends after `limit`
+/// bytes of the log and returns the offset of the end of the last entry that was read.
+fn read_entries(
+    reader: &mut impl Read,
+    limit: u64,
+    mut f: impl FnMut(&[u8]) -> Result<(), GraphError>,
+) -> Result<u64, GraphError> {
+    let mut offset = HEADER_LEN as u64;
+    let mut entry = vec![];
+    loop {
+        let mut entry_header = [0; ENTRY_HEADER_LEN];
+        if offset + ENTRY_HEADER_LEN as u64 > limit || !read_full(reader, &mut entry_header)? {
+            return Ok(offset);
+        }
+        let [l0, l1, l2, l3, c0, c1, c2, c3] = entry_header;
+        let len = u32::from_le_bytes([l0, l1, l2, l3]);
+        let crc = u32::from_le_bytes([c0, c1, c2, c3]);
+        let end = offset + ENTRY_HEADER_LEN as u64 + len as u64;
+        if end > limit {
+            return Ok(offset);
+        }
+        entry.resize(len as usize, 0);
+        if !read_full(reader, &mut entry)? || crc32fast::hash(&entry) != crc {
+            return Ok(offset);
+        }
+        f(&entry)?;
+        offset = end;
+    }
+}
+
+/// Hard link `target` to `source`, falls back to copying if they are on different file systems
+fn link_or_copy(source: &Path, target: &Path) -> Result<(), GraphError> {
+    if fs::hard_link(source, target).is_err() {
+        fs::copy(source, target)?;
+    }
+    Ok(())
+}
+
+/// Sync the directory containing `path` so that renames into it survive a crash
+fn sync_parent_dir(path: &Path) -> Result<(), GraphError> {
+    #[cfg(unix)]
+    {
+        let parent = match path.parent() {
+            Some(parent) if !parent.as_os_str().is_empty() => parent,
+            _ => Path::new("."),
+        };
+        File::open(parent)?.sync_all()?;
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+    Ok(())
+}
+
+/// Write the snapshot at `snapshot_path` through a temporary file that is renamed into place, a
+/// crash never leaves a partially written snapshot behind
+pub(crate) fn write_snapshot(snapshot_path: &Path, snapshot: &[u8]) -> Result<(), GraphError> {
+    let tmp_path = hidden_sibling(snapshot_path, ".tmp");
+    let mut file = File::create(&tmp_path)?;
+    file.write_all(snapshot)?;
+    file.sync_all()?;
+    fs::rename(&tmp_path, snapshot_path)?;
+    sync_parent_dir(snapshot_path)
+}
+
+/// Replay the first `log_len` bytes of the log of the snapshot at `snapshot_path` with contents
+/// `snapshot`, passing each entry to `f`.
+///
+/// Fails if the log does not belong to the snapshot or is shorter than `log_len`.
+pub(crate) fn read_log_prefix(
+    snapshot_path: &Path,
+    snapshot: &[u8],
+    log_len: u64,
+    f: impl FnMut(&[u8]) -> Result<(), GraphError>,
+) -> Result<(), GraphError> {
+    let path = wal_path(snapshot_path);
+    let mut reader = BufReader::new(File::open(&path)?);
+    if !read_header(&mut reader, snapshot)? {
+        return Err(GraphError::LoadFailure(format!(
+            "Write-ahead log {} does not belong to its snapshot",
+            path.display()
+        )));
+    }
+    if read_entries(&mut reader, log_len, f)? != log_len {
+        return Err(GraphError::LoadFailure(format!(
+            "Write-ahead log {} has no valid entries up to offset {log_len}",
+            path.display()
+        )));
+    }
+    Ok(())
+}
+
+/// Replace the cache at `target` with the state linked at `source` by [CacheOps::link_cache],
+/// i.e., its snapshot and the first `log_len` bytes of its log.
+///
+/// The linked files are not copied, the log is truncated in place. Links of the same log with a
+/// larger length (taken later) no longer reproduce their state afterwards.
+///
+/// [CacheOps::link_cache]: crate::serialise::CacheOps::link_cache
+pub fn restore_cache(
+    source: impl AsRef<Path>,
+    log_len: u64,
+    target: impl AsRef<Path>,
+) -> Result<(), GraphError> {
+    let (source, target) = (source.as_ref(), target.as_ref());
+    let snapshot_tmp = hidden_sibling(target, ".tmp");
+    let wal_tmp = hidden_sibling(target, ".wal.tmp");
+    for tmp in [&snapshot_tmp, &wal_tmp] {
+        match fs::remove_file(tmp) {
+            Err(err) if err.kind() != io::ErrorKind::NotFound => return Err(err.into()),
+            _ => {}
+        }
+    }
+    link_or_copy(source, &snapshot_tmp)?;
+    link_or_copy(&wal_path(source), &wal_tmp)?;
+    let wal = OpenOptions::new().write(true).open(&wal_tmp)?;
+    if wal.metadata()?.len() < log_len {
+        return Err(GraphError::LoadFailure(format!(
+            "Write-ahead log of {} is shorter than {log_len} bytes",
+            source.display()
+        )));
+    }
+    wal.set_len(log_len)?;
+    wal.sync_all()?;
+    fs::rename(&snapshot_tmp, target)?;
+    fs::rename(&wal_tmp, wal_path(target))?;
+    sync_parent_dir(target)
+}
+
+#[derive(Debug)]
+pub(crate) struct Wal {
+    file: File,
+    options: WalOptions,
+    len: u64,
+    last_sync: Instant,
+    last_checkpoint: Instant,
+}
+
+impl Wal {
+    fn from_file(file: File, len: u64, options: WalOptions) -> Self {
+        let now = Instant::now();
+        Self {
+            file,
+            options,
+            len,
+            last_sync: now,
+            last_checkpoint: now,
+        }
+    }
+
+    /// Start an empty log for the graph cached at `snapshot_path` with contents `snapshot`
+    pub(crate) fn create(
+        snapshot_path: &Path,
+        snapshot: &[u8],
+        options: WalOptions,
+    ) -> Result<Self, GraphError> {
+        let path = wal_path(snapshot_path);
+        // the new log replaces the old one atomically, a crash never leaves a log without header
+        let tmp_path = hidden_sibling(snapshot_path, ".wal.tmp");
+        let mut file = File::create(&tmp_path)?;
+        file.write_all(&header(snapshot))?;
+        file.sync_all()?;
+        fs::rename(&tmp_path, &path)?;
+        sync_parent_dir(&path)?;
+        let file = OpenOptions::new().append(true).open(&path)?;
+        Ok(Self::from_file(file, HEADER_LEN as u64, options))
+    }
+
+    /// Open the log of the graph cached at `snapshot_path` with contents `snapshot` and pass the
+    /// entries that were written since the snapshot to `f` one at a time.
+    ///
+    /// A missing log or a log that belongs to a different snapshot is replaced by an empty one,
+    /// torn or corrupted entries at the end of the log are truncated.
+    pub(crate) fn open(
+        snapshot_path: &Path,
+        snapshot: &[u8],
+        options: WalOptions,
+        f: impl FnMut(&[u8]) -> Result<(), GraphError>,
+    ) -> Result<Self, GraphError> {
+        let path = wal_path(snapshot_path);
+        let file = match File::open(&path) {
+            Ok(file) => file,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => {
+                return Self::create(snapshot_path, snapshot, options)
+            }
+            Err(err) => return Err(err.into()),
+        };
+        let len = file.metadata()?.len();
+        let mut reader = BufReader::new(file);
+        if !read_header(&mut reader, snapshot)? {
+            return Self::create(snapshot_path, snapshot, options);
+        }
+
+        let offset = read_entries(&mut reader, len, f)?;
+        let file = OpenOptions::new().append(true).open(&path)?;
+        if offset < len {
+            file.set_len(offset)?;
+            file.sync_all()?;
+        }
+        Ok(Self::from_file(file, offset, options))
+    }
+
+    /// Append `entry` to the log and sync it according to the fsync policy
+    pub(crate) fn append(&mut self, entry: &[u8]) -> Result<(), GraphError> {
+        let len = u32::try_from(entry.len())
+            .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "log entry exceeds 4 GiB"))?;
+        let mut frame = Vec::with_capacity(ENTRY_HEADER_LEN + entry.len());
+        frame.extend_from_slice(&len.to_le_bytes());
+        frame.extend_from_slice(&crc32fast::hash(entry).to_le_bytes());
+        frame.extend_from_slice(entry);
+        self.file.write_all(&frame)?;
+        self.len += frame.len() as u64;
+
+        let sync = match self.options.fsync {
+            FsyncPolicy::Always => true,
+            FsyncPolicy::Interval(interval) => self.last_sync.elapsed() >= interval,
+            FsyncPolicy::Never => false,
+        };
+        if sync {
+            self.sync()?;
+        }
+        Ok(())
+    }
+
+    pub(crate) fn sync(&mut self) -> Result<(), GraphError> {
+        self.file.sync_data()?;
+        self.last_sync = Instant::now();
+        Ok(())
+    }
+
+    /// Sync the log and hard link it together with the snapshot at `snapshot_path` to `target`,
+    /// returns the length of the log.
+    pub(crate) fn link(&mut self, snapshot_path: &Path, target: &Path) -> Result<u64, GraphError> {
+        self.sync()?;
+        link_or_copy(snapshot_path, target)?;
+        link_or_copy(&wal_path(snapshot_path), &wal_path(target))?;
+        Ok(self.len)
+    }
+
+    /// Whether the log has entries and has reached one of the checkpoint thresholds
+    pub(crate) fn needs_checkpoint(&self) -> bool {
+        let has_entries = self.len > HEADER_LEN as u64;
+        let too_large = self
+            .options
+            .checkpoint_bytes
+            .is_some_and(|max_bytes| self.len >= max_bytes);
+        let too_old = self
+            .options
+            .checkpoint_interval
+            .is_some_and(|interval| self.last_checkpoint.elapsed() >= interval);
+        has_entries && (too_large || too_old)
+    }
+
+    /// Replace the snapshot at `snapshot_path` with `snapshot` and start an empty log.
+    ///
+    /// The snapshot is renamed into place before the log is reset, if the process crashes in
+    /// between the old log no longer matches the snapshot and is discarded on the next load.
+    pub(crate) fn checkpoint(
+        &mut self,
+        snapshot_path: &Path,
+        snapshot: &[u8],
+    ) -> Result<(), GraphError> {
+        write_snapshot(snapshot_path, snapshot)?;
+        *self = Self::create(snapshot_path, snapshot, self.options)?;
+        Ok(())
+    }
+}